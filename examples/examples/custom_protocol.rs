@@ -1,6 +1,6 @@
 use ethers::prelude::*;
 use std::sync::Arc;
-use uniswap_rs::{Dex, ProtocolType};
+use uniswap_rs::{CustomProtocol, Dex};
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -26,10 +26,14 @@ async fn main() -> eyre::Result<()> {
     // FIXME: The hash of the deployment code of the pairs
     let pair_code_hash: H256 =
         "0x7777777777777777777777777777777777777777777777777777777777777777".parse()?;
+    // FIXME: The fee taken on each swap, in basis points
+    let swap_fee_bps = 25;
 
-    let my_protocol = ProtocolType::new(my_factory, my_router, is_v2, pair_code_hash);
+    let my_protocol = CustomProtocol::new(my_factory, my_router, is_v2, pair_code_hash)
+        .with_swap_fee_bps(swap_fee_bps)
+        .with_name("MySwap");
 
-    let dex = Dex::new_with_chain(client, chain, my_protocol);
+    let dex = Dex::new_custom(client, my_protocol);
 
     println!("Using dex: {dex:#?}");
 
@@ -30,7 +30,7 @@ async fn main() -> eyre::Result<()> {
     // instantiate a new dex
     let dex = Dex::new_with_chain(client.clone(), chain, protocol).unwrap();
 
-    let pair = dex.pair_for(weth, usdc);
+    let pair = dex.pair_for(weth, usdc)?;
 
     // liquidity amount
     let liquidity = U256::exp10(9);
@@ -50,8 +50,9 @@ async fn main() -> eyre::Result<()> {
 
     // create the remove liquidity transaction
     println!("Removing {liquidity} liquidity...");
-    let call =
-        dex.remove_liquidity(NATIVE_ADDRESS, usdc, liquidity, 0.into(), 0.into(), None, None)?;
+    let call = dex
+        .remove_liquidity(NATIVE_ADDRESS, usdc, liquidity, 0.into(), 0.into(), None, None)
+        .await?;
     println!("Sending transaction...");
     let receipt = send(call).await?;
     println!("Successfully removed {liquidity} liquidity from ETH/USDC. Receipt: {receipt:#?}");
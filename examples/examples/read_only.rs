@@ -0,0 +1,40 @@
+use ethers::{prelude::*, utils::format_units};
+use std::sync::Arc;
+use uniswap_rs::{contracts::addresses::address, errors::Error, Amount, Protocol, ProtocolType};
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let chain = Chain::Mainnet;
+    let protocol = ProtocolType::UniswapV2;
+    // a plain provider, without a signer
+    let client: Arc<Provider<Http>> = Arc::new(MAINNET.provider());
+
+    println!("Using {chain:?} {protocol:?}");
+
+    let protocol = Protocol::new_with_chain(client, chain, protocol, None).unwrap();
+    assert!(protocol.is_read_only());
+
+    // get contract addresses from address book
+    let weth = address("WETH", chain);
+    let usdc = address("USDC", chain);
+
+    // read the state of the pair
+    let mut pair = protocol.pair_for(weth, usdc)?;
+    pair.sync(true, true).await?;
+    println!("{pair}");
+
+    // quote a swap through the best route
+    let amount = Amount::exact_in(U256::exp10(18));
+    let trade = protocol.swap_builder(weth, usdc).amount(amount).quote().await?;
+    let amount_out = trade.amounts.last().copied().unwrap_or_default();
+    println!("1 WETH -> {} USDC", format_units(amount_out, 6)?);
+    println!("Price impact: {} bps", trade.price_impact_bps);
+
+    // operations which must be signed are not available
+    let to = Address::repeat_byte(0x11);
+    let res = protocol.swap_builder(weth, usdc).amount(amount).recipient(to).execute().await;
+    assert!(matches!(res, Err(Error::SignerRequired(_))));
+    println!("Executing requires a signer: {}", res.unwrap_err());
+
+    Ok(())
+}
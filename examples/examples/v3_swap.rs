@@ -0,0 +1,64 @@
+use ethers::prelude::*;
+use std::{sync::Arc, time::Duration};
+use uniswap_rs::{
+    contracts::addresses::address,
+    v3::{FeeAmount, Protocol},
+    Amount, SwapOptions, NATIVE_ADDRESS,
+};
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let chain = Chain::Arbitrum;
+    let client = Arc::new({
+        // FIXME: Replace with own RPC URL.
+        let provider = Provider::<Http>::try_from("https://arb1.arbitrum.io/rpc")?;
+        // FIXME: Replace with own private key / wallet.
+        let wallet = "1aeda1fc24f9ea6809619040f1d3374255e17a0a3d9c75d85e0ba676ea42ccbd"
+            .parse::<LocalWallet>()?
+            .with_chain_id(chain);
+        println!("Wallet: {:?}", wallet.address());
+
+        SignerMiddleware::new(provider, wallet)
+    });
+
+    println!("Using {chain:?} Uniswap V3");
+
+    // bridged USDC, which has the deepest 0.05% pool with WETH
+    let usdc: Address = "0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8".parse()?;
+    let weth = address("WETH", chain);
+    let fee = FeeAmount::Low;
+
+    // instantiate the protocol with the canonical addresses
+    let protocol = Protocol::new_with_chain(client.clone(), chain, None).unwrap();
+    println!("Pool: {:?}", protocol.pool_for(usdc, weth, fee)?.address());
+
+    // quote the swap of 1 USDC
+    let amount_in = U256::exp10(6);
+    let amount_out = protocol.quote_exact_input_single(usdc, weth, fee, amount_in).await?.amount;
+    println!("Quote: {amount_out:?} WETH units");
+
+    // create the swap transaction to ETH with 0.5% slippage, the router must have been approved
+    // to transfer USDC; the chain's WETH is used, as it is not overridden
+    let swap_call = protocol
+        .swap(
+            Amount::ExactIn(amount_in),
+            0.5,
+            usdc,
+            NATIVE_ADDRESS,
+            fee,
+            client.address(),
+            Duration::from_secs(300),
+            None,
+            SwapOptions::new(),
+        )
+        .await?;
+
+    // send the transaction and await inclusion in a block
+    println!("Sending transaction...");
+    let pending_tx = swap_call.send().await?;
+    println!("Transaction sent successfully, awaiting inclusion...");
+    let receipt = pending_tx.await?.expect("swap transaction was dropped from mempool");
+    println!("Swap successful. Receipt: {receipt:#?}");
+
+    Ok(())
+}
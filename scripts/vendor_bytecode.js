@@ -0,0 +1,53 @@
+#!/usr/bin/env node
+// Vendors the canonical creation bytecode of WETH9 and the Uniswap V2 and V3 contracts, deployed by
+// `deploy::V2Bytecode::canonical` and `deploy::V3Bytecode::canonical`, from the build artifacts
+// published in the Uniswap npm packages, into `bytecode/<name>.hex`.
+//
+// The pair and pool bytecode must hash to the canonical code hashes, since the routers compute the
+// addresses of the pairs and pools with them, which is checked by the `deploy` tests.
+
+const fs = require("fs");
+const path = require("path");
+
+const OUT = path.resolve(__dirname, "../bytecode");
+const UNPKG = "https://unpkg.com";
+
+const V2_CORE = "@uniswap/v2-core@1.0.1/build";
+const V2_PERIPHERY = "@uniswap/v2-periphery@1.1.0-beta.0/build";
+const V3_CORE = "@uniswap/v3-core@1.0.0/artifacts/contracts";
+const V3_PERIPHERY = "@uniswap/v3-periphery@1.0.0/artifacts/contracts";
+
+// name => artifact
+const artifacts = {
+    WETH9: `${V2_PERIPHERY}/WETH9.json`,
+    UniswapV2Factory: `${V2_CORE}/UniswapV2Factory.json`,
+    UniswapV2Pair: `${V2_CORE}/UniswapV2Pair.json`,
+    UniswapV2Router02: `${V2_PERIPHERY}/UniswapV2Router02.json`,
+    UniswapV3Factory: `${V3_CORE}/UniswapV3Factory.sol/UniswapV3Factory.json`,
+    UniswapV3Pool: `${V3_CORE}/UniswapV3Pool.sol/UniswapV3Pool.json`,
+    SwapRouter: `${V3_PERIPHERY}/SwapRouter.sol/SwapRouter.json`,
+    NonfungiblePositionManager: `${V3_PERIPHERY}/NonfungiblePositionManager.sol/NonfungiblePositionManager.json`,
+    Quoter: `${V3_PERIPHERY}/lens/Quoter.sol/Quoter.json`,
+};
+
+const main = async () => {
+    fs.mkdirSync(OUT, { recursive: true });
+    for (const [name, artifact] of Object.entries(artifacts)) {
+        const res = await fetch(`${UNPKG}/${artifact}`);
+        if (!res.ok) {
+            throw new Error(`${name}: ${res.status} ${res.statusText}`);
+        }
+        const json = await res.json();
+        const bytecode = (json.bytecode.object ?? json.bytecode).replace(/^0x/, "");
+        if (bytecode.includes("__")) {
+            throw new Error(`${name}: bytecode has unlinked libraries`);
+        }
+        fs.writeFileSync(path.join(OUT, `${name}.hex`), `${bytecode}\n`);
+        console.log(`${name}: ${bytecode.length / 2} bytes`);
+    }
+};
+
+main().catch(e => {
+    console.error(e);
+    process.exit(1);
+});
@@ -0,0 +1,157 @@
+//! Generation of the bindings in `src/contracts/bindings` from the ABIs in `abi/`, shared by the
+//! `generate` binary and the build script.
+
+use ethers_contract_abigen::{Abigen, MultiAbigen};
+use eyre::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub const ABI_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/abi");
+pub const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/contracts/bindings");
+
+// abigen's derives cannot be behind a feature, so they are added after the bindings are generated
+const EVENT_ATTRIBUTE: &str = "#[ethevent(name = ";
+const SERDE_ATTRIBUTE: &str =
+    "#[cfg_attr(feature = \"serde\", derive(serde::Deserialize, serde::Serialize))]";
+
+// the ABIs are included from their files instead of being inlined as string literals
+const ABI_CONSTANT: &str = "# [rustfmt :: skip] const __ABI : & str = ";
+const CONTRACT_DOC: &str = " was auto-generated with ethers-rs Abigen.";
+
+// the shared types are emitted in a random order, so they are sorted
+const SHARED_TYPES: &str = "shared_types.rs";
+const SHARED_TYPE_START: &str = "#[doc = ";
+
+/// The features which enable each bindings module. The other modules are always compiled.
+const MODULE_FEATURES: &[(&str, &str)] = &[
+    ("i_nonfungible_position_manager", "v3"),
+    ("i_permit_2", "permit2"),
+    ("i_quoter", "v3"),
+    ("i_quoter_v2", "v3"),
+    ("i_solidly_factory", "v2"),
+    ("i_solidly_router", "v2"),
+    ("i_swap_router", "v3"),
+    ("i_swap_router_02", "v3"),
+    ("i_tick_lens", "v3"),
+    ("i_uniswap_v2_callee", "v2"),
+    ("i_uniswap_v2_factory", "v2"),
+    ("i_uniswap_v2_pair", "v2"),
+    ("i_uniswap_v2_router_02", "v2"),
+    ("i_uniswap_v3_factory", "v3"),
+    ("i_uniswap_v3_pool", "v3"),
+    ("i_uniswap_v3_staker", "staker"),
+    ("i_universal_router", "universal-router"),
+    ("i_universal_router_commands", "universal-router"),
+    ("iv3_migrator", "v3"),
+    ("shared_types", "permit2"),
+];
+
+/// Generates the bindings of the ABIs in [`ABI_PATH`] into `module`.
+///
+/// Requires the `rustfmt` run by abigen to be nightly, e.g. with `cargo +nightly`, as the
+/// `rustfmt.toml` contains nightly-only options.
+pub fn generate(module: impl AsRef<Path>) -> Result<()> {
+    let version = rustfmt_version()?;
+    if !version.contains("nightly") {
+        return Err(eyre::eyre!("Cannot build bindings without rustfmt nightly, found {version}"));
+    }
+
+    // temp: manually build abigens to set rustfmt value
+    let abigens = json_files(ABI_PATH)
+        .map(|path| Abigen::from_file(path).map(|abigen| abigen.rustfmt(true)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let bindings = MultiAbigen::from_abigens(abigens).build()?;
+    bindings.write_to_module(&module, false)?;
+    postprocess(&module)
+}
+
+/// Post-processes the bindings in `module`:
+/// - derives `Deserialize` and `Serialize` on the event filter structs, behind the `serde` feature;
+/// - includes the ABIs from their JSON files;
+/// - puts the modules behind their [features][MODULE_FEATURES];
+/// - sorts the shared types.
+fn postprocess(module: impl AsRef<Path>) -> Result<()> {
+    for path in rust_files(module)? {
+        let bindings = fs::read_to_string(&path)?;
+        let mut processed = String::with_capacity(bindings.len());
+        let mut contract = None;
+        for line in bindings.lines() {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            if trimmed.starts_with(EVENT_ATTRIBUTE) {
+                processed.push_str(&format!("{indent}{SERDE_ATTRIBUTE}\n"));
+            } else if let Some((name, _)) = trimmed.split_once(CONTRACT_DOC) {
+                contract = name.strip_prefix("#[doc = \"");
+            } else if trimmed.starts_with(ABI_CONSTANT) {
+                let contract = contract.ok_or_else(|| eyre::eyre!("no contract name"))?;
+                processed.push_str(&format!(
+                    "{indent}const __ABI: &str = include_str!(\"../../../abi/{contract}.json\");\n"
+                ));
+                continue;
+            } else if let Some(name) =
+                trimmed.strip_prefix("pub mod ").and_then(|name| name.strip_suffix(';'))
+            {
+                if let Some((_, feature)) =
+                    MODULE_FEATURES.iter().find(|(module, _)| *module == name)
+                {
+                    processed.push_str(&format!("{indent}#[cfg(feature = \"{feature}\")]\n"));
+                }
+            }
+            processed.push_str(line);
+            processed.push('\n');
+        }
+        if path.ends_with(SHARED_TYPES) {
+            processed = sort_items(&processed);
+        }
+        fs::write(&path, processed)?;
+    }
+    Ok(())
+}
+
+/// Sorts the top-level items of `source`, each starting with its doc attribute.
+fn sort_items(source: &str) -> String {
+    let mut items: Vec<String> = Vec::new();
+    for line in source.lines() {
+        match items.last_mut() {
+            Some(item) if !line.starts_with(SHARED_TYPE_START) => item.push_str(line),
+            _ => items.push(line.to_string()),
+        }
+        items.last_mut().unwrap().push('\n');
+    }
+    items.sort();
+    items.concat()
+}
+
+/// Returns the sorted paths of the Rust files in `dir`.
+fn rust_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| {
+            path.as_ref().map(|path| path.extension() == Some("rs".as_ref())).unwrap_or(true)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn rustfmt_version() -> Result<String> {
+    let output = Command::new("rustfmt").arg("--version").output()?;
+    let version = String::from_utf8(output.stdout)?;
+    Ok(version.trim().to_string())
+}
+
+/// Returns a list of absolute paths to all the json files under the root.
+///
+/// Modified from: ethers_contract_abigen::util::json_files
+fn json_files(root: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().map(|ext| ext == "json").unwrap_or_default())
+        .map(|e| e.path().into())
+}
@@ -0,0 +1,16 @@
+//! Regenerates the committed bindings in `src/contracts/bindings` from the ABIs in `abi/`.
+//!
+//! ```sh
+//! cargo +nightly run -p uniswap-rs --features abigen --bin generate
+//! ```
+//!
+//! The output is deterministic, so CI checks that the committed bindings are up to date by running
+//! this and diffing the result.
+
+mod codegen;
+
+fn main() -> eyre::Result<()> {
+    codegen::generate(codegen::BINDINGS_PATH)?;
+    println!("Generated the bindings in {}", codegen::BINDINGS_PATH);
+    Ok(())
+}
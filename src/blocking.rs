@@ -0,0 +1,215 @@
+//! Synchronous wrappers of the async API, for consumers without an async runtime.
+//!
+//! Each method drives its async counterpart to completion on a [tokio] runtime, either owned by
+//! the wrapper or provided with a [Handle]. Calling them from within an async runtime panics,
+//! since blocking its thread could deadlock it: use the async API there instead.
+
+use crate::{
+    errors::Result,
+    tokens::{fetch_metadata, TokenInfo},
+    utils::{is_native, resolve_weth},
+    v2::{Pair, Trade},
+    Amount, Deadline, Recipient, Route, Slippage, SwapOptions,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::types::{Address, U256};
+use ethers_providers::Middleware;
+use std::{fmt, future::Future, io, sync::Arc};
+use tokio::runtime::{Builder, Handle, Runtime};
+
+#[cfg(feature = "v3")]
+use crate::errors::Error;
+
+/// The runtime used to drive the async methods.
+#[derive(Clone, Debug)]
+enum Executor {
+    /// A current-thread runtime owned by the wrapper and its clones.
+    Runtime(Arc<Runtime>),
+    /// A handle to a runtime owned by the caller.
+    Handle(Handle),
+}
+
+/// A synchronous wrapper of a [Protocol][crate::Protocol].
+pub struct Protocol<M> {
+    /// The wrapped protocol.
+    protocol: crate::Protocol<M>,
+
+    /// The runtime used to drive the protocol's async methods.
+    executor: Executor,
+}
+
+impl<M> Clone for Protocol<M> {
+    fn clone(&self) -> Self {
+        Self { protocol: self.protocol.clone(), executor: self.executor.clone() }
+    }
+}
+
+impl<M> fmt::Debug for Protocol<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Protocol")
+            .field("protocol", &self.protocol)
+            .field("executor", &self.executor)
+            .finish()
+    }
+}
+
+impl<M: Middleware> Protocol<M> {
+    /// Wraps `protocol`, driving its methods on a new current-thread runtime.
+    pub fn new(protocol: crate::Protocol<M>) -> io::Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { protocol, executor: Executor::Runtime(Arc::new(runtime)) })
+    }
+
+    /// Wraps `protocol`, driving its methods on the runtime of `handle`.
+    pub fn with_handle(protocol: crate::Protocol<M>, handle: Handle) -> Self {
+        Self { protocol, executor: Executor::Handle(handle) }
+    }
+
+    /// Returns a reference to the wrapped protocol.
+    pub fn inner(&self) -> &crate::Protocol<M> {
+        &self.protocol
+    }
+
+    /// Returns a mutable reference to the wrapped protocol.
+    pub fn inner_mut(&mut self) -> &mut crate::Protocol<M> {
+        &mut self.protocol
+    }
+
+    /// Returns the wrapped protocol.
+    pub fn into_inner(self) -> crate::Protocol<M> {
+        self.protocol
+    }
+
+    /// Runs `future` to completion on the wrapper's runtime, for the async methods which are not
+    /// wrapped.
+    ///
+    /// # Panics
+    ///
+    /// If called from within an async runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        if Handle::try_current().is_ok() {
+            panic!(
+                "uniswap_rs::blocking methods cannot be called from within an async runtime, \
+                 use the async API instead"
+            );
+        }
+        match &self.executor {
+            Executor::Runtime(runtime) => runtime.block_on(future),
+            Executor::Handle(handle) => handle.block_on(future),
+        }
+    }
+
+    /// Returns the pair of `token_a` and `token_b`, with its tokens and reserves synced. See
+    /// [`Pair::sync_state`].
+    pub fn pair_for(&self, token_a: Address, token_b: Address) -> Result<Pair<M>> {
+        let pair = self.protocol.pair_for(token_a, token_b)?;
+        self.block_on(pair.sync_state())?;
+        Ok(pair)
+    }
+
+    /// Quotes a swap of `amount` through `path`. See [`Router::quote`][crate::v2::Router::quote].
+    ///
+    /// `weth` overrides the chain's wrapped native token, which replaces the native token in
+    /// `path`. Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol]
+    /// for Uniswap V3.
+    pub fn quote(&self, amount: Amount, path: &[Address], weth: Option<Address>) -> Result<Trade> {
+        match &self.protocol {
+            crate::Protocol::V2(p) => {
+                let weth = resolve_weth(weth, p.chain(), path.iter().any(is_native))?;
+                self.block_on(p.router().quote(p.factory(), amount, path, weth))
+            }
+            #[cfg(feature = "v3")]
+            crate::Protocol::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// The router's `swap` method. See [`Protocol::swap`][crate::Protocol::swap].
+    pub fn swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<U256>>> {
+        self.block_on(self.protocol.swap(
+            amount,
+            slippage_tolerance,
+            route,
+            to,
+            deadline,
+            weth,
+            options,
+        ))
+    }
+
+    /// Fetches the metadata of `tokens`. See [`fetch_metadata`].
+    pub fn fetch_metadata(&self, tokens: &[Address]) -> Result<Vec<TokenInfo>> {
+        self.block_on(fetch_metadata(self.protocol.client(), tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProtocolType;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    const TOKEN_A: Address = Address::repeat_byte(1);
+    const TOKEN_B: Address = Address::repeat_byte(2);
+
+    fn protocol() -> (Protocol<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let (factory, router) = (Address::repeat_byte(0xf0), Address::repeat_byte(0xf1));
+        let protocol =
+            crate::Protocol::new(Arc::new(provider), factory, router, ProtocolType::UniswapV2);
+        (Protocol::new(protocol).unwrap(), mock)
+    }
+
+    #[test]
+    fn test_quote() {
+        let (protocol, mock) = protocol();
+        let reserves = [U256::exp10(21), U256::exp10(21), U256::zero()];
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&reserves.map(Token::Uint)))).unwrap();
+
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let trade = protocol.quote(amount, &[TOKEN_A, TOKEN_B], None).unwrap();
+        assert_eq!(trade.amount_in(), U256::exp10(18));
+        assert!(!trade.amount_out().is_zero());
+    }
+
+    #[test]
+    #[cfg(feature = "v3")]
+    fn test_quote_v3() {
+        let (provider, _mock) = Provider::mocked();
+        let (factory, router, quoter) =
+            (Address::repeat_byte(0xf0), Address::repeat_byte(0xf1), Address::repeat_byte(0xf2));
+        let v3 = crate::v3::Protocol::new(Arc::new(provider), factory, router, quoter);
+        let protocol = Protocol::new(crate::Protocol::V3(v3)).unwrap();
+
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let res = protocol.quote(amount, &[TOKEN_A, TOKEN_B], None);
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+    }
+
+    #[test]
+    fn test_with_handle() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let (protocol, _mock) = protocol();
+        let protocol = Protocol::with_handle(protocol.into_inner(), runtime.handle().clone());
+        assert_eq!(protocol.block_on(async { 1 }), 1);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "cannot be called from within an async runtime")]
+    async fn test_async_context() {
+        let (protocol, _mock) = protocol();
+        let _ = protocol.fetch_metadata(&[TOKEN_A]);
+    }
+}
@@ -0,0 +1,1339 @@
+//! Analytics of the trades and liquidity of Uniswap V2 pairs and V3 pools, and of the positions of
+//! their liquidity providers.
+
+use super::{block_at_timestamp, call_multi, get_block, scan_logs};
+use crate::{
+    contracts::bindings::{i_uniswap_v2_pair, i_uniswap_v3_pool, ierc20::IERC20},
+    errors::{Error, Result},
+    v2::{LiquidityPosition, Pair},
+    v3::{
+        math::{
+            get_sqrt_ratio_at_tick, liquidity::amounts_for_liquidity, sqrt_ratio_to_price, to_f64,
+        },
+        Pool, Position, SwapEvent,
+    },
+    DecodedLog, RequestPolicy,
+};
+use ethers_contract::EthEvent;
+use ethers_core::{
+    abi::InvalidOutputType,
+    types::{Address, BlockNumber, Filter, Log, I256, U256},
+};
+use ethers_providers::Middleware;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
+
+/// The number of blocks whose logs are queried at once by [`candles`] and [`volume`].
+pub const LOGS_CHUNK_SIZE: u64 = 2_000;
+
+/// The number of seconds in a year of 365 days.
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// A pair or pool whose trades are aggregated by [`candles`].
+pub enum CandleSource<'a, M> {
+    /// A Uniswap V2 pair, whose prices are computed from the amounts of its `Swap` events.
+    V2(&'a Pair<M>),
+    /// A Uniswap V3 pool, whose prices are read from the `sqrtPriceX96` of its `Swap` events.
+    V3(&'a Pool<M>),
+}
+
+impl<M> Clone for CandleSource<'_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for CandleSource<'_, M> {}
+
+impl<M> fmt::Debug for CandleSource<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V2(pair) => f.debug_tuple("V2").field(&pair.address()).finish(),
+            Self::V3(pool) => f.debug_tuple("V3").field(&pool.address()).finish(),
+        }
+    }
+}
+
+impl<'a, M> From<&'a Pair<M>> for CandleSource<'a, M> {
+    fn from(pair: &'a Pair<M>) -> Self {
+        Self::V2(pair)
+    }
+}
+
+impl<'a, M> From<&'a Pool<M>> for CandleSource<'a, M> {
+    fn from(pool: &'a Pool<M>) -> Self {
+        Self::V3(pool)
+    }
+}
+
+/// The open, high, low and close prices and the volume of the trades of an interval, returned by
+/// [`candles`].
+///
+/// The prices are those of the base token in terms of the quote token, and the volume is in the
+/// quote token, all adjusted for the tokens' decimals.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Candle {
+    /// The timestamp of the start of the interval.
+    pub timestamp: u64,
+    /// The price of the first trade.
+    pub open: f64,
+    /// The highest price.
+    pub high: f64,
+    /// The lowest price.
+    pub low: f64,
+    /// The price of the last trade.
+    pub close: f64,
+    /// The traded volume.
+    pub volume: f64,
+    /// The number of trades.
+    pub trades: u32,
+}
+
+/// A cache of the timestamps of blocks, by number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BlockTimestamps {
+    timestamps: BTreeMap<u64, u64>,
+}
+
+impl BlockTimestamps {
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached timestamp of `block`.
+    pub fn get(&self, block: u64) -> Option<u64> {
+        self.timestamps.get(&block).copied()
+    }
+
+    /// Caches the timestamp of `block`.
+    pub fn insert(&mut self, block: u64, timestamp: u64) {
+        self.timestamps.insert(block, timestamp);
+    }
+
+    /// Returns the number of cached timestamps.
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Returns whether no timestamp is cached.
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// Fetches the timestamps of the `blocks` which are not cached yet, concurrently and retried
+    /// according to the default [RequestPolicy].
+    pub async fn fetch<M: Middleware>(
+        &mut self,
+        client: &M,
+        blocks: impl IntoIterator<Item = u64>,
+    ) -> Result<()> {
+        let missing: BTreeSet<_> =
+            blocks.into_iter().filter(|block| !self.timestamps.contains_key(block)).collect();
+        let policy = RequestPolicy::new();
+        let fetched: Vec<_> = stream::iter(missing)
+            .map(|number| {
+                policy.retry(move || async move {
+                    let block = get_block(client, number.into()).await?;
+                    Ok((number, block.timestamp.as_u64()))
+                })
+            })
+            .buffered(policy.concurrency())
+            .try_collect()
+            .await?;
+        self.timestamps.extend(fetched);
+        Ok(())
+    }
+}
+
+/// The prices used by [`tvl`] and [`fee_apr`] to value the tokens of a pair in a quote currency.
+pub enum PriceSource<'a, M> {
+    /// Prices the tokens in `quote` with the mid prices of `pairs` and of the valued pair, chained
+    /// from `quote`, like through WETH/USDC to value a WETH pair in USDC.
+    Pairs {
+        /// The pairs of the route.
+        pairs: &'a [Pair<M>],
+        /// The quote token.
+        quote: Address,
+    },
+    /// The prices of whole tokens in the quote currency, like USD. The price of a token which is
+    /// missing is derived from the other one with the pair's mid price.
+    Map(&'a HashMap<Address, f64>),
+}
+
+impl<M> Clone for PriceSource<'_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for PriceSource<'_, M> {}
+
+impl<M> fmt::Debug for PriceSource<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pairs { pairs, quote } => {
+                let pairs: Vec<_> = pairs.iter().map(Pair::address).collect();
+                f.debug_struct("Pairs").field("pairs", &pairs).field("quote", quote).finish()
+            }
+            Self::Map(prices) => f.debug_tuple("Map").field(prices).finish(),
+        }
+    }
+}
+
+/// The total value locked in a pair, returned by [`tvl`].
+///
+/// The reserves are adjusted for the tokens' decimals, and the prices are those of whole tokens in
+/// the quote currency.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Tvl {
+    /// The address of the pair.
+    pub pair: Address,
+    /// The first token of the pair.
+    pub token0: Address,
+    /// The second token of the pair.
+    pub token1: Address,
+    /// The reserve of `token0`.
+    pub reserve0: f64,
+    /// The reserve of `token1`.
+    pub reserve1: f64,
+    /// The price of `token0`.
+    pub price0: f64,
+    /// The price of `token1`.
+    pub price1: f64,
+    /// The value of both reserves.
+    pub value: f64,
+}
+
+/// The sums of the amounts of a pair's `Swap` events, returned by [`volume`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Volume {
+    /// The amount of `token0` paid in.
+    pub amount0_in: U256,
+    /// The amount of `token1` paid in.
+    pub amount1_in: U256,
+    /// The amount of `token0` paid out.
+    pub amount0_out: U256,
+    /// The amount of `token1` paid out.
+    pub amount1_out: U256,
+    /// The number of swaps.
+    pub swaps: u64,
+}
+
+/// The fees earned by a pair's liquidity providers over a window, returned by [`fee_apr`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FeeReport {
+    /// The current value locked in the pair.
+    pub tvl: Tvl,
+    /// The volume of the pair over the window.
+    pub volume: Volume,
+    /// The first block of the window.
+    pub from_block: u64,
+    /// The last block of the window.
+    pub to_block: u64,
+    /// The duration of the window, in seconds.
+    pub window: u64,
+    /// The swap fee of the pair, in basis points.
+    pub fee_bps: u32,
+    /// The value of the amounts paid in, in the quote currency.
+    pub volume_value: f64,
+    /// The value of the fees taken on the amounts paid in, in the quote currency.
+    pub fees_value: f64,
+    /// The annual percentage rate of the fees relative to the current value locked, not
+    /// compounded, like `0.1` for 10%. Zero if the pair has no value locked.
+    pub fee_apr: f64,
+}
+
+/// A liquidity position whose state is taken by [`snapshot`].
+pub enum SnapshotSource<'a, M> {
+    /// Liquidity tokens of a Uniswap V2 pair, like the balance of a liquidity provider.
+    V2 {
+        /// The pair.
+        pair: &'a Pair<M>,
+        /// The amount of liquidity tokens.
+        liquidity: U256,
+    },
+    /// A position of a Uniswap V3 pool, like one returned by a [`PositionManager`].
+    ///
+    /// [`PositionManager`]: crate::v3::PositionManager
+    V3 {
+        /// The pool.
+        pool: &'a Pool<M>,
+        /// The position.
+        position: &'a Position,
+    },
+}
+
+impl<M> Clone for SnapshotSource<'_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for SnapshotSource<'_, M> {}
+
+impl<M> fmt::Debug for SnapshotSource<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V2 { pair, liquidity } => f
+                .debug_struct("V2")
+                .field("pair", &pair.address())
+                .field("liquidity", liquidity)
+                .finish(),
+            Self::V3 { pool, position } => f
+                .debug_struct("V3")
+                .field("pool", &pool.address())
+                .field("position", position)
+                .finish(),
+        }
+    }
+}
+
+/// The position of a [`PositionSnapshot`], with the state needed to compare it with another
+/// snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SnapshotPosition {
+    /// Liquidity tokens of a Uniswap V2 pair.
+    V2 {
+        /// The address of the pair.
+        pair: Address,
+        /// The amount of liquidity tokens.
+        liquidity: U256,
+        /// The reserve of `token0` of the pair.
+        reserve0: U256,
+        /// The reserve of `token1` of the pair.
+        reserve1: U256,
+        /// The total supply of liquidity tokens of the pair.
+        total_supply: U256,
+    },
+    /// A position of a Uniswap V3 pool.
+    V3 {
+        /// The address of the pool.
+        pool: Address,
+        /// The lower tick of the position.
+        tick_lower: i32,
+        /// The upper tick of the position.
+        tick_upper: i32,
+        /// The liquidity of the position.
+        liquidity: u128,
+    },
+}
+
+/// The state of a liquidity position at a block, returned by [`snapshot`] and compared by
+/// [`position_pnl`].
+///
+/// The amounts are raw, and the price is adjusted for the decimals of the snapshot, which can be
+/// persisted with the `serde` feature to compute the P&L of the position later.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PositionSnapshot {
+    /// The position.
+    pub position: SnapshotPosition,
+    /// The first token of the pair or pool.
+    pub token0: Address,
+    /// The second token of the pair or pool.
+    pub token1: Address,
+    /// The decimals of `token0`.
+    pub decimals0: u8,
+    /// The decimals of `token1`.
+    pub decimals1: u8,
+    /// The amount of `token0` which would be received by withdrawing the position, which includes
+    /// the fees earned by a V2 pair's liquidity since they are added to its reserves.
+    pub amount0: U256,
+    /// The amount of `token1` which would be received by withdrawing the position, which includes
+    /// the fees earned by a V2 pair's liquidity since they are added to its reserves.
+    pub amount1: U256,
+    /// The uncollected fees of `token0` of a V3 position. Always zero for V2 pairs.
+    pub fees0: U256,
+    /// The uncollected fees of `token1` of a V3 position. Always zero for V2 pairs.
+    pub fees1: U256,
+    /// The mid price of `token0` in terms of `token1`.
+    pub price: f64,
+    /// The number of the block at which the snapshot was taken.
+    pub block_number: u64,
+}
+
+/// The profit and loss of a liquidity position between two snapshots, returned by
+/// [`position_pnl`].
+///
+/// The values are in the quote token, adjusted for its decimals.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PositionPnl {
+    /// The quote token.
+    pub quote: Address,
+    /// The value of the entry amounts at the entry price.
+    pub entry_value: f64,
+    /// The value of the entry amounts at the current price, had they been held instead.
+    pub hodl_value: f64,
+    /// The current value of the position, excluding the fees earned since the entry.
+    pub position_value: f64,
+    /// The fees of `token0` earned since the entry, adjusted for its decimals.
+    pub fees0: f64,
+    /// The fees of `token1` earned since the entry, adjusted for its decimals.
+    pub fees1: f64,
+    /// The value of the fees earned since the entry.
+    pub fees_value: f64,
+    /// The value lost by providing liquidity instead of holding, excluding fees, as
+    /// `position_value - hodl_value`. Zero or negative.
+    pub impermanent_loss: f64,
+    /// The impermanent loss relative to `hodl_value`, like `-0.05` for 5%.
+    pub impermanent_loss_ratio: f64,
+    /// The value gained since the entry, including fees, as
+    /// `position_value + fees_value - entry_value`.
+    pub net_pnl: f64,
+}
+
+/// Returns the OHLCV candles of the trades of a pair or pool in `blocks`, inclusive, priced in
+/// `quote_token`, over consecutive intervals of `interval`. See [`candles_with`].
+pub async fn candles<'a, M: Middleware + 'a>(
+    source: impl Into<CandleSource<'a, M>>,
+    quote_token: Address,
+    blocks: RangeInclusive<u64>,
+    interval: Duration,
+) -> Result<Vec<Candle>> {
+    candles_with(source, quote_token, blocks, interval, &mut BlockTimestamps::new()).await
+}
+
+/// Returns the OHLCV candles of the trades of a pair or pool in `blocks`, inclusive, priced in
+/// `quote_token`, over consecutive intervals of `interval`, reading and caching the timestamps of
+/// the blocks in `timestamps`.
+///
+/// The `Swap` events are queried in ranges of [`LOGS_CHUNK_SIZE`] blocks, and the timestamps of
+/// their blocks are fetched concurrently. The intervals are aligned to multiples of `interval`
+/// since the Unix epoch. The candles start with the interval of the first trade and end with that
+/// of the last block of the range; intervals without trades carry forward the previous close, with
+/// zero volume. Returns no candles if there are no trades.
+///
+/// Returns [`Error::InvalidCandleInterval`] if `interval` is shorter than one second, and
+/// [`Error::TokenNotInPair`] if `quote_token` is not one of the tokens.
+pub async fn candles_with<'a, M: Middleware + 'a>(
+    source: impl Into<CandleSource<'a, M>>,
+    quote_token: Address,
+    blocks: RangeInclusive<u64>,
+    interval: Duration,
+    timestamps: &mut BlockTimestamps,
+) -> Result<Vec<Candle>> {
+    let interval = interval.as_secs();
+    if interval == 0 {
+        return Err(Error::InvalidCandleInterval);
+    }
+    let source = source.into();
+    let (client, filter, (token0, token1)) = match source {
+        CandleSource::V2(pair) => {
+            let filter = Filter::new()
+                .address(pair.address())
+                .topic0(i_uniswap_v2_pair::SwapFilter::signature());
+            (pair.client(), filter, pair.fetch_tokens().await?)
+        }
+        CandleSource::V3(pool) => {
+            let filter = Filter::new()
+                .address(pool.address())
+                .topic0(i_uniswap_v3_pool::SwapFilter::signature());
+            (pool.client(), filter, pool.fetch_immutables().await?.0)
+        }
+    };
+    let quote_is_token0 = if quote_token == token0 {
+        true
+    } else if quote_token == token1 {
+        false
+    } else {
+        return Err(Error::TokenNotInPair(quote_token));
+    };
+    let decimals = match fetch_decimals(client.clone(), &[token0, token1]).await?[..] {
+        [decimals0, decimals1] => (decimals0, decimals1),
+        _ => return Err(InvalidOutputType("missing decimals".into()).into()),
+    };
+
+    let (from_block, to_block) = (*blocks.start(), *blocks.end());
+    let mut trades = vec![];
+    let policy = RequestPolicy::new();
+    scan_logs(
+        client.as_ref(),
+        &filter,
+        from_block,
+        to_block,
+        LOGS_CHUNK_SIZE,
+        &policy,
+        |logs, _| {
+            for log in logs {
+                let block = log.block_number.unwrap_or_default().as_u64();
+                let trade = match source {
+                    CandleSource::V2(_) => v2_trade(&log, quote_is_token0, decimals)?,
+                    CandleSource::V3(_) => v3_trade(&log, quote_is_token0, decimals)?,
+                };
+                if let Some((price, volume)) = trade {
+                    trades.push((block, price, volume));
+                }
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    if trades.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let blocks = trades.iter().map(|(block, ..)| *block).chain([to_block]);
+    timestamps.fetch(client.as_ref(), blocks).await?;
+    let timestamp = |block| timestamps.get(block).unwrap_or_default();
+    let trades: Vec<_> = trades
+        .into_iter()
+        .map(|(block, price, volume)| (timestamp(block), price, volume))
+        .collect();
+    Ok(aggregate(&trades, interval, timestamp(to_block)))
+}
+
+/// Returns the value of the reserves of `pair` in a quote currency, with the prices of `prices`.
+///
+/// The tokens and the current reserves of `pair` and of the pairs of the price source are fetched
+/// first, with the reserves and the decimals of the tokens read with a [Multicall] request each.
+///
+/// Returns [`Error::NoRouteFound`] if neither token of the pair can be priced.
+///
+/// [Multicall]: crate::utils::Multicall
+pub async fn tvl<M: Middleware>(pair: &Pair<M>, prices: PriceSource<'_, M>) -> Result<Tvl> {
+    Ok(tvl_and_decimals(pair, prices).await?.0)
+}
+
+/// Returns the [`tvl`] of `pair` and the decimals of its tokens.
+async fn tvl_and_decimals<M: Middleware>(
+    pair: &Pair<M>,
+    prices: PriceSource<'_, M>,
+) -> Result<(Tvl, (u8, u8))> {
+    let client = pair.client();
+    let route = match prices {
+        PriceSource::Pairs { pairs, .. } => pairs,
+        PriceSource::Map(_) => &[],
+    };
+    let pairs: Vec<_> = std::iter::once(pair).chain(route).collect();
+    let mut tokens = Vec::with_capacity(pairs.len());
+    for pair in &pairs {
+        tokens.push(pair.fetch_tokens().await?);
+    }
+    let calls = pairs.iter().map(|pair| pair.contract().get_reserves()).collect();
+    let reserves = call_multi(client.clone(), None, calls).await?;
+
+    let mut all: Vec<_> = tokens.iter().flat_map(|&(token0, token1)| [token0, token1]).collect();
+    let mut prices = match prices {
+        PriceSource::Pairs { quote, .. } => {
+            all.push(quote);
+            HashMap::from([(quote, 1.0)])
+        }
+        PriceSource::Map(prices) => prices.clone(),
+    };
+    all.sort_unstable();
+    all.dedup();
+    let decimals: HashMap<_, _> =
+        all.iter().copied().zip(fetch_decimals(client, &all).await?).collect();
+    let decimals = |token| decimals.get(&token).copied().unwrap_or_default();
+    let pairs: Vec<_> = tokens
+        .iter()
+        .zip(&reserves)
+        .map(|(&(token0, token1), &(reserve0, reserve1, _)): (_, &(u128, u128, u32))| {
+            let reserve0 = adjusted(reserve0.into(), decimals(token0));
+            let reserve1 = adjusted(reserve1.into(), decimals(token1));
+            (token0, token1, reserve0, reserve1)
+        })
+        .collect();
+    propagate_prices(&mut prices, &pairs);
+
+    let (token0, token1, reserve0, reserve1) = pairs[0];
+    match (prices.get(&token0), prices.get(&token1)) {
+        (Some(&price0), Some(&price1)) => {
+            let tvl = Tvl {
+                pair: pair.address(),
+                token0,
+                token1,
+                reserve0,
+                reserve1,
+                price0,
+                price1,
+                value: reserve0 * price0 + reserve1 * price1,
+            };
+            Ok((tvl, (decimals(token0), decimals(token1))))
+        }
+        _ => Err(Error::NoRouteFound),
+    }
+}
+
+/// Returns the sums of the amounts of the `Swap` events of `pair` from `from_block` to
+/// `to_block`, inclusive, queried in ranges of [`LOGS_CHUNK_SIZE`] blocks.
+pub async fn volume<M: Middleware>(
+    pair: &Pair<M>,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Volume> {
+    let filter =
+        Filter::new().address(pair.address()).topic0(i_uniswap_v2_pair::SwapFilter::signature());
+    let mut volume = Volume::default();
+    let client = pair.client();
+    let policy = RequestPolicy::new();
+    scan_logs(
+        client.as_ref(),
+        &filter,
+        from_block,
+        to_block,
+        LOGS_CHUNK_SIZE,
+        &policy,
+        |logs, _| {
+            for log in logs {
+                let swap = DecodedLog::<i_uniswap_v2_pair::SwapFilter>::decode(&log)?.event;
+                volume.amount0_in = volume.amount0_in.saturating_add(swap.amount_0_in);
+                volume.amount1_in = volume.amount1_in.saturating_add(swap.amount_1_in);
+                volume.amount0_out = volume.amount0_out.saturating_add(swap.amount_0_out);
+                volume.amount1_out = volume.amount1_out.saturating_add(swap.amount_1_out);
+                volume.swaps += 1;
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(volume)
+}
+
+/// Estimates the annual percentage rate earned by the liquidity providers of `pair` from the swap
+/// fees of approximately the last `window`, relative to its current value locked.
+///
+/// The window starts after the latest block at least `window` before the latest one. The fees are
+/// the pair's [swap fee][Pair::swap_fee_bps] of the amounts paid in by the swaps of the window,
+/// valued at the current prices of [`tvl`], and are extrapolated linearly to a year.
+///
+/// Returns [`Error::InvalidAprWindow`] if no time elapsed in the window, and the errors of
+/// [`tvl`] otherwise.
+pub async fn fee_apr<M: Middleware>(
+    pair: &Pair<M>,
+    prices: PriceSource<'_, M>,
+    window: Duration,
+) -> Result<FeeReport> {
+    let client = pair.client();
+    let latest = get_block(client.as_ref(), BlockNumber::Latest).await?;
+    let timestamp = latest.timestamp.saturating_sub(window.as_secs().into());
+    let start = block_at_timestamp(client.as_ref(), latest.clone(), timestamp).await?;
+    let elapsed = (latest.timestamp - start.timestamp).as_u64();
+    if elapsed == 0 {
+        return Err(Error::InvalidAprWindow);
+    }
+    let from_block = start.number.unwrap_or_default().as_u64() + 1;
+    let to_block = latest.number.unwrap_or_default().as_u64();
+
+    let volume = volume(pair, from_block, to_block).await?;
+    let (tvl, (decimals0, decimals1)) = tvl_and_decimals(pair, prices).await?;
+    let value = |amount0: U256, amount1: U256| {
+        adjusted(amount0, decimals0) * tvl.price0 + adjusted(amount1, decimals1) * tvl.price1
+    };
+    let fee_bps = pair.swap_fee_bps();
+    let volume_value = value(volume.amount0_in, volume.amount1_in);
+    let fees_value = volume_value * fee_bps as f64 / 10_000.0;
+    let fee_apr = if tvl.value > 0.0 {
+        fees_value / tvl.value * SECONDS_PER_YEAR / elapsed as f64
+    } else {
+        0.0
+    };
+    Ok(FeeReport {
+        tvl,
+        volume,
+        from_block,
+        to_block,
+        window: elapsed,
+        fee_bps,
+        volume_value,
+        fees_value,
+        fee_apr,
+    })
+}
+
+/// Returns a snapshot of a liquidity position at the latest block, to be compared with a later
+/// one by [`position_pnl`].
+///
+/// The state of a V2 pair is fetched with [`Pair::sync_state`], and the amounts are the
+/// position's share of its reserves. The state of a V3 pool is fetched with [`Pool::state`] and
+/// the uncollected fees of the position with [`Pool::pending_fees`]. The decimals of the tokens
+/// are then fetched with a [Multicall] request.
+///
+/// Returns [`Error::TokenNotInPair`] if a V3 position is not of the pool's tokens, and the errors
+/// of the fetches otherwise.
+///
+/// [Multicall]: crate::utils::Multicall
+pub async fn snapshot<M: Middleware>(source: SnapshotSource<'_, M>) -> Result<PositionSnapshot> {
+    match source {
+        SnapshotSource::V2 { pair, liquidity } => {
+            let (token0, token1) = pair.fetch_tokens().await?;
+            let state = pair.sync_state().await?;
+            let (reserve0, reserve1) = (state.reserves.0.into(), state.reserves.1.into());
+            let total_supply = state.total_supply;
+            let position = LiquidityPosition::new(
+                (token0, token1),
+                liquidity,
+                (reserve0, reserve1),
+                total_supply,
+            )?;
+            let decimals = fetch_decimals(pair.client(), &[token0, token1]).await?;
+            let price = adjusted(reserve1, decimals[1]) / adjusted(reserve0, decimals[0]);
+            Ok(PositionSnapshot {
+                position: SnapshotPosition::V2 {
+                    pair: pair.address(),
+                    liquidity,
+                    reserve0,
+                    reserve1,
+                    total_supply,
+                },
+                token0,
+                token1,
+                decimals0: decimals[0],
+                decimals1: decimals[1],
+                amount0: position.amount0,
+                amount1: position.amount1,
+                fees0: U256::zero(),
+                fees1: U256::zero(),
+                price: finite(price),
+                block_number: state.block_number,
+            })
+        }
+        SnapshotSource::V3 { pool, position } => {
+            let state = pool.state().await?;
+            let ((token0, token1), ..) = pool.fetch_immutables().await?;
+            for token in [position.token0, position.token1] {
+                if token != token0 && token != token1 {
+                    return Err(Error::TokenNotInPair(token));
+                }
+            }
+            let (amount0, amount1) = amounts_for_liquidity(
+                state.sqrt_price_x96,
+                get_sqrt_ratio_at_tick(position.tick_lower)?,
+                get_sqrt_ratio_at_tick(position.tick_upper)?,
+                position.liquidity,
+            )?;
+            let (fees0, fees1) = pool.pending_fees(position).await?;
+            let decimals = fetch_decimals(pool.client(), &[token0, token1]).await?;
+            Ok(PositionSnapshot {
+                position: SnapshotPosition::V3 {
+                    pool: pool.address(),
+                    tick_lower: position.tick_lower,
+                    tick_upper: position.tick_upper,
+                    liquidity: position.liquidity,
+                },
+                token0,
+                token1,
+                decimals0: decimals[0],
+                decimals1: decimals[1],
+                amount0,
+                amount1,
+                fees0,
+                fees1,
+                price: sqrt_ratio_to_price(state.sqrt_price_x96, decimals[0], decimals[1]),
+                block_number: state.block_number,
+            })
+        }
+    }
+}
+
+/// Returns the profit and loss of a liquidity position from its `entry` snapshot to its `current`
+/// one, in `quote`, one of the position's tokens.
+///
+/// The impermanent loss compares the current value of the position, excluding fees, with the
+/// value of holding the entry amounts instead, both at the current price. The fees of a V2 pair
+/// are approximated from the growth of the square root of its constant product per liquidity
+/// token, which only the fees increase, and those of a V3 position are the increase of its
+/// uncollected fees.
+///
+/// The amounts of each snapshot are adjusted with its own decimals, so a token whose decimals
+/// changed between the snapshots is valued consistently. Values which cannot be computed, like
+/// the price of an empty pair, are zero.
+///
+/// Returns [`Error::SnapshotMismatch`] if the snapshots are not of the same position, including
+/// if its liquidity changed, and [`Error::TokenNotInPair`] if `quote` is not one of its tokens.
+pub fn position_pnl(
+    entry: &PositionSnapshot,
+    current: &PositionSnapshot,
+    quote: Address,
+) -> Result<PositionPnl> {
+    let same_position = match (entry.position, current.position) {
+        (
+            SnapshotPosition::V2 { pair, liquidity, .. },
+            SnapshotPosition::V2 { pair: current_pair, liquidity: current_liquidity, .. },
+        ) => (pair, liquidity) == (current_pair, current_liquidity),
+        (position, current_position) => position == current_position,
+    };
+    if !same_position || (entry.token0, entry.token1) != (current.token0, current.token1) {
+        return Err(Error::SnapshotMismatch);
+    }
+    let quote0 = match quote {
+        quote if quote == entry.token0 => true,
+        quote if quote == entry.token1 => false,
+        _ => return Err(Error::TokenNotInPair(quote)),
+    };
+    // the value of whole amounts at a price of `token0` in terms of `token1`
+    let value = |amount0: f64, amount1: f64, price: f64| {
+        let value = if quote0 { amount0 + amount1 / price } else { amount0 * price + amount1 };
+        finite(value)
+    };
+    let amounts = |snapshot: &PositionSnapshot, amount0, amount1| {
+        (adjusted(amount0, snapshot.decimals0), adjusted(amount1, snapshot.decimals1))
+    };
+
+    let (entry0, entry1) = amounts(entry, entry.amount0, entry.amount1);
+    let (current0, current1) = amounts(current, current.amount0, current.amount1);
+    let entry_value = value(entry0, entry1, entry.price);
+    let hodl_value = value(entry0, entry1, current.price);
+    let (position_value, fees0, fees1) = match (entry.position, current.position) {
+        (SnapshotPosition::V2 { .. }, SnapshotPosition::V2 { .. }) => {
+            // the share of the current amounts which is fees
+            let share = finite(1.0 - v2_growth(entry) / v2_growth(current)).clamp(0.0, 1.0);
+            let position_value = value(current0, current1, current.price) * (1.0 - share);
+            (position_value, current0 * share, current1 * share)
+        }
+        _ => {
+            let (entry_fees0, entry_fees1) = amounts(entry, entry.fees0, entry.fees1);
+            let (current_fees0, current_fees1) = amounts(current, current.fees0, current.fees1);
+            let fees0 = (current_fees0 - entry_fees0).max(0.0);
+            let fees1 = (current_fees1 - entry_fees1).max(0.0);
+            (value(current0, current1, current.price), fees0, fees1)
+        }
+    };
+    let fees_value = value(fees0, fees1, current.price);
+    let impermanent_loss = position_value - hodl_value;
+    Ok(PositionPnl {
+        quote,
+        entry_value,
+        hodl_value,
+        position_value,
+        fees0,
+        fees1,
+        fees_value,
+        impermanent_loss,
+        impermanent_loss_ratio: finite(impermanent_loss / hodl_value),
+        net_pnl: position_value + fees_value - entry_value,
+    })
+}
+
+/// Returns the decimals of `tokens`, fetched with a single [Multicall] request.
+///
+/// [Multicall]: crate::utils::Multicall
+async fn fetch_decimals<M: Middleware>(client: Arc<M>, tokens: &[Address]) -> Result<Vec<u8>> {
+    let calls = tokens
+        .iter()
+        .map(|&token| {
+            let mut call = IERC20::new(Address::zero(), client.clone()).decimals();
+            call.tx.set_to(token);
+            call
+        })
+        .collect();
+    call_multi(client.clone(), None, calls).await
+}
+
+/// Fills in the missing prices of the tokens of `pairs`, as their tokens and reserves adjusted for
+/// decimals, from the prices of the other token and the pairs' mid prices, until no price can be
+/// added.
+fn propagate_prices(prices: &mut HashMap<Address, f64>, pairs: &[(Address, Address, f64, f64)]) {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &(token0, token1, reserve0, reserve1) in pairs {
+            if reserve0 == 0.0 || reserve1 == 0.0 {
+                continue;
+            }
+            match (prices.get(&token0).copied(), prices.get(&token1).copied()) {
+                (Some(price0), None) => {
+                    prices.insert(token1, price0 * reserve0 / reserve1);
+                    changed = true;
+                }
+                (None, Some(price1)) => {
+                    prices.insert(token0, price1 * reserve1 / reserve0);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns `amount` adjusted for `decimals`.
+fn adjusted(amount: U256, decimals: u8) -> f64 {
+    to_f64(amount) / 10f64.powi(decimals as i32)
+}
+
+/// Returns the square root of the constant product of the reserves of a V2 snapshot, adjusted for
+/// decimals, per liquidity token, which only grows with fees. Zero for V3 snapshots.
+fn v2_growth(snapshot: &PositionSnapshot) -> f64 {
+    match snapshot.position {
+        SnapshotPosition::V2 { reserve0, reserve1, total_supply, .. } => {
+            let reserve0 = adjusted(reserve0, snapshot.decimals0);
+            let reserve1 = adjusted(reserve1, snapshot.decimals1);
+            (reserve0 * reserve1).sqrt() / to_f64(total_supply)
+        }
+        SnapshotPosition::V3 { .. } => 0.0,
+    }
+}
+
+/// Returns `value`, or zero if it is not finite.
+fn finite(value: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+/// Decodes a pair's `Swap` event log into the price of the base token, from the net amounts of the
+/// swap, and the volume in the quote token. Returns None if no base or quote token was swapped.
+fn v2_trade(
+    log: &Log,
+    quote_is_token0: bool,
+    (decimals0, decimals1): (u8, u8),
+) -> Result<Option<(f64, f64)>> {
+    let swap = DecodedLog::<i_uniswap_v2_pair::SwapFilter>::decode(log)?.event;
+    let net = |a: U256, b: U256| a.max(b) - a.min(b);
+    let amount0 = net(swap.amount_0_in, swap.amount_0_out);
+    let amount1 = net(swap.amount_1_in, swap.amount_1_out);
+    if amount0.is_zero() || amount1.is_zero() {
+        return Ok(None);
+    }
+    let (amount0, amount1) = (adjusted(amount0, decimals0), adjusted(amount1, decimals1));
+    Ok(Some(if quote_is_token0 {
+        (amount0 / amount1, amount0)
+    } else {
+        (amount1 / amount0, amount1)
+    }))
+}
+
+/// Decodes a pool's `Swap` event log into the price of the base token after the swap, and the
+/// volume in the quote token.
+fn v3_trade(
+    log: &Log,
+    quote_is_token0: bool,
+    (decimals0, decimals1): (u8, u8),
+) -> Result<Option<(f64, f64)>> {
+    let swap = DecodedLog::<i_uniswap_v3_pool::SwapFilter>::decode(log)?.event;
+    let swap = SwapEvent::new(swap, decimals0, decimals1);
+    if swap.price == 0.0 {
+        return Ok(None);
+    }
+    let abs = |amount: I256| amount.into_sign_and_abs().1;
+    Ok(Some(if quote_is_token0 {
+        (1.0 / swap.price, adjusted(abs(swap.amount0), decimals0))
+    } else {
+        (swap.price, adjusted(abs(swap.amount1), decimals1))
+    }))
+}
+
+/// Aggregates `trades`, as their timestamp, price and volume in ascending order, into candles of
+/// `interval` seconds up to the one containing `end`.
+fn aggregate(trades: &[(u64, f64, f64)], interval: u64, end: u64) -> Vec<Candle> {
+    let (first, last) = match (trades.first(), trades.last()) {
+        (Some(first), Some(last)) => (first.0, last.0.max(end)),
+        _ => return vec![],
+    };
+    let mut candles = Vec::new();
+    let mut trades = trades.iter().peekable();
+    let mut close = 0.0;
+    let mut timestamp = first - first % interval;
+    while timestamp <= last {
+        let mut candle = Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0.0,
+            trades: 0,
+        };
+        while let Some(&&(_, price, volume)) =
+            trades.peek().filter(|(time, ..)| *time < timestamp.saturating_add(interval))
+        {
+            if candle.trades == 0 {
+                (candle.open, candle.high, candle.low) = (price, price, price);
+            }
+            candle.high = candle.high.max(price);
+            candle.low = candle.low.min(price);
+            candle.close = price;
+            candle.volume += volume;
+            candle.trades += 1;
+            trades.next();
+        }
+        close = candle.close;
+        candles.push(candle);
+        timestamp = match timestamp.checked_add(interval) {
+            Some(timestamp) => timestamp,
+            None => break,
+        };
+    }
+    candles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{v3::math::Q96, ProtocolType};
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Block, Bytes, TxHash, H256, U64},
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    fn swap_log(topic: H256, data: Vec<Token>, block: u64) -> Log {
+        Log {
+            topics: vec![topic, H256::zero(), H256::zero()],
+            data: abi::encode(&data).into(),
+            block_number: Some(U64::from(block)),
+            ..Default::default()
+        }
+    }
+
+    fn v2_swap(amounts: [u128; 4], block: u64) -> Log {
+        let topic = i_uniswap_v2_pair::SwapFilter::signature();
+        swap_log(topic, amounts.map(|amount| Token::Uint(amount.into())).to_vec(), block)
+    }
+
+    fn block(number: u64, timestamp: u64) -> Block<TxHash> {
+        Block { number: Some(number.into()), timestamp: timestamp.into(), ..Default::default() }
+    }
+
+    fn uints(values: &[u128]) -> Vec<u8> {
+        abi::encode(&values.iter().map(|&value| Token::Uint(value.into())).collect::<Vec<_>>())
+    }
+
+    /// Pushes the `aggregate3` response of successful calls, and the chain id queried by
+    /// [Multicall::new][crate::utils::Multicall::new].
+    fn push_multicall(mock: &MockProvider, results: &[Vec<u8>]) {
+        let results = results
+            .iter()
+            .map(|data| Token::Tuple(vec![Token::Bool(true), Token::Bytes(data.clone())]))
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        mock.push(U256::one()).unwrap();
+    }
+
+    fn push_tokens(mock: &MockProvider, token0: Address, token1: Address) {
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(token1)]))).unwrap();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(token0)]))).unwrap();
+    }
+
+    fn e6(amount: u128) -> u128 {
+        amount * 10u128.pow(6)
+    }
+
+    fn e18(amount: u128) -> u128 {
+        amount * 10u128.pow(18)
+    }
+
+    #[test]
+    fn test_trades() {
+        // 1 token0 with 18 decimals in for 2,000 token1 with 6 decimals out
+        let log = v2_swap([10u128.pow(18), 0, 0, 2_000 * 10u128.pow(6)], 1);
+        assert_eq!(v2_trade(&log, false, (18, 6)).unwrap(), Some((2_000.0, 2_000.0)));
+        assert_eq!(v2_trade(&log, true, (18, 6)).unwrap(), Some((1.0 / 2_000.0, 1.0)));
+        assert_eq!(v2_trade(&v2_swap([1, 0, 0, 0], 1), false, (18, 6)).unwrap(), None);
+
+        // 4 token0 in for 2 token1 out, at a price of 1/4 after the swap
+        let topic = i_uniswap_v3_pool::SwapFilter::signature();
+        let data = vec![
+            Token::Int(I256::exp10(18).saturating_mul(4.into()).into_raw()),
+            Token::Int((-I256::exp10(18).saturating_mul(2.into())).into_raw()),
+            Token::Uint(Q96 / 2),
+            Token::Uint(1_000.into()),
+            Token::Int(I256::from(-13_863).into_raw()),
+        ];
+        let log = swap_log(topic, data, 1);
+        assert_eq!(v3_trade(&log, false, (18, 18)).unwrap(), Some((0.25, 2.0)));
+        assert_eq!(v3_trade(&log, true, (18, 18)).unwrap(), Some((4.0, 4.0)));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let trades =
+            [(10, 1.0, 1.0), (20, 3.0, 2.0), (50, 2.0, 1.0), (70, 4.0, 1.0), (200, 1.0, 5.0)];
+        let candle = |timestamp, [open, high, low, close]: [f64; 4], volume, trades| Candle {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            trades,
+        };
+        assert_eq!(
+            aggregate(&trades, 60, 250),
+            [
+                candle(0, [1.0, 3.0, 1.0, 2.0], 4.0, 3),
+                candle(60, [4.0; 4], 1.0, 1),
+                // no trades: the previous close is carried forward
+                candle(120, [4.0; 4], 0.0, 0),
+                candle(180, [1.0; 4], 5.0, 1),
+                candle(240, [1.0; 4], 0.0, 0),
+            ]
+        );
+        assert!(aggregate(&[], 60, 250).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_candles() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(Arc::new(provider), Address::repeat_byte(3), ProtocolType::UniswapV2);
+        let (token0, token1) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        // LIFO: the timestamps, the logs, the decimals and the tokens
+        for (number, timestamp) in [(110, 1_200), (105, 1_060), (100, 1_000)] {
+            mock.push(block(number, timestamp)).unwrap();
+        }
+        let logs =
+            vec![v2_swap([e18(1), 0, 0, e6(2_000)], 100), v2_swap([0, e6(3_000), e18(1), 0], 105)];
+        mock.push::<Vec<Log>, _>(logs).unwrap();
+        push_multicall(&mock, &[uints(&[18]), uints(&[6])]);
+        push_tokens(&mock, token0, token1);
+
+        let mut timestamps = BlockTimestamps::new();
+        let interval = Duration::from_secs(60);
+        let history =
+            candles_with(&pair, token1, 100..=110, interval, &mut timestamps).await.unwrap();
+        let prices: Vec<_> = history.iter().map(|c| (c.timestamp, c.close, c.volume)).collect();
+        assert_eq!(
+            prices,
+            [
+                (960, 2_000.0, 2_000.0),
+                (1_020, 3_000.0, 3_000.0),
+                (1_080, 3_000.0, 0.0),
+                (1_140, 3_000.0, 0.0),
+                (1_200, 3_000.0, 0.0),
+            ]
+        );
+        assert_eq!(timestamps.len(), 3);
+        assert_eq!(timestamps.get(105), Some(1_060));
+
+        let res = candles(&pair, Address::zero(), 100..=110, interval).await;
+        assert!(matches!(res.unwrap_err(), Error::TokenNotInPair(_)));
+        let res = candles(&pair, token1, 100..=110, Duration::ZERO).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidCandleInterval));
+    }
+
+    #[test]
+    fn test_propagate_prices() {
+        let [a, b, c, d] = [1, 2, 3, 4].map(Address::repeat_byte);
+        // A -> B -> C, and D is not connected
+        let pairs = [(b, c, 10.0, 20_000.0), (a, b, 1_000.0, 10.0), (d, Address::zero(), 1.0, 0.0)];
+        let mut prices = HashMap::from([(c, 1.0)]);
+        propagate_prices(&mut prices, &pairs);
+        assert_eq!(prices, HashMap::from([(a, 20.0), (b, 2_000.0), (c, 1.0)]));
+    }
+
+    #[tokio::test]
+    async fn test_tvl() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let client = Arc::new(provider);
+        let [a, b, quote] = [1, 2, 4].map(Address::repeat_byte);
+        let pair = Pair::new(client.clone(), Address::repeat_byte(3), ProtocolType::UniswapV2);
+        let route = [Pair::new(client, Address::repeat_byte(5), ProtocolType::UniswapV2)];
+
+        // LIFO: the decimals, the reserves, and the tokens of the route and of the pair
+        push_multicall(&mock, &[uints(&[18]), uints(&[18]), uints(&[6])]);
+        let reserves = [uints(&[e18(1_000), e18(10), 1]), uints(&[e18(100), e6(200_000), 1])];
+        push_multicall(&mock, &reserves);
+        push_tokens(&mock, b, quote);
+        push_tokens(&mock, a, b);
+
+        let tvl = tvl(&pair, PriceSource::Pairs { pairs: &route, quote }).await.unwrap();
+        assert_eq!(
+            tvl,
+            Tvl {
+                pair: pair.address(),
+                token0: a,
+                token1: b,
+                reserve0: 1_000.0,
+                reserve1: 10.0,
+                price0: 20.0,
+                price1: 2_000.0,
+                value: 40_000.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fee_apr() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(Arc::new(provider), Address::repeat_byte(5), ProtocolType::UniswapV2);
+        let [weth, usdc] = [2, 4].map(Address::repeat_byte);
+
+        // LIFO: the decimals, the reserves, the tokens, the logs, and the first and latest blocks
+        push_multicall(&mock, &[uints(&[18]), uints(&[6])]);
+        mock.push::<Bytes, _>(Bytes::from(uints(&[e18(100), e6(200_000), 1]))).unwrap();
+        push_tokens(&mock, weth, usdc);
+        let logs =
+            vec![v2_swap([e18(1), 0, 0, e6(2_000)], 95), v2_swap([0, e6(4_000), e18(2), 0], 100)];
+        mock.push::<Vec<Log>, _>(logs).unwrap();
+        mock.push(block(90, 1_090)).unwrap();
+        mock.push(block(100, 1_100)).unwrap();
+
+        let prices = HashMap::from([(usdc, 1.0)]);
+        let window = Duration::from_secs(10);
+        let report = fee_apr(&pair, PriceSource::Map(&prices), window).await.unwrap();
+        assert_eq!((report.from_block, report.to_block, report.window), (91, 100, 10));
+        assert_eq!(
+            report.volume,
+            Volume {
+                amount0_in: e18(1).into(),
+                amount1_in: e6(4_000).into(),
+                amount0_out: e18(2).into(),
+                amount1_out: e6(2_000).into(),
+                swaps: 2,
+            }
+        );
+        assert_eq!((report.tvl.price0, report.tvl.value), (2_000.0, 400_000.0));
+        assert_eq!(report.fee_bps, 30);
+        assert_eq!(report.volume_value, 6_000.0);
+        assert!((report.fees_value - 18.0).abs() < 1e-9);
+        // 18 / 400,000 over 10 seconds
+        let expected = 18.0 / 400_000.0 * SECONDS_PER_YEAR / 10.0;
+        assert!((report.fee_apr - expected).abs() < 1e-9);
+
+        // no price
+        push_multicall(&mock, &[uints(&[18]), uints(&[6])]);
+        mock.push::<Bytes, _>(Bytes::from(uints(&[e18(100), e6(200_000), 1]))).unwrap();
+        let res = tvl(&pair, PriceSource::Map(&HashMap::new())).await;
+        assert!(matches!(res.unwrap_err(), Error::NoRouteFound));
+    }
+
+    fn v2_snapshot(reserves: (u128, u128), amounts: (u128, u128), price: f64) -> PositionSnapshot {
+        PositionSnapshot {
+            position: SnapshotPosition::V2 {
+                pair: Address::repeat_byte(5),
+                liquidity: e18(100).into(),
+                reserve0: reserves.0.into(),
+                reserve1: reserves.1.into(),
+                total_supply: e18(1_000).into(),
+            },
+            token0: Address::repeat_byte(2),
+            token1: Address::repeat_byte(4),
+            decimals0: 18,
+            decimals1: 6,
+            amount0: amounts.0.into(),
+            amount1: amounts.1.into(),
+            fees0: U256::zero(),
+            fees1: U256::zero(),
+            price,
+            block_number: 100,
+        }
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_position_pnl() {
+        let [weth, usdc] = [2, 4].map(Address::repeat_byte);
+        let entry = v2_snapshot((e18(100), e6(200_000)), (e18(10), e6(20_000)), 2_000.0);
+
+        // the price quadrupled without fees: 2 * sqrt(4) / (1 + 4) - 1 = -20%
+        let current = v2_snapshot((e18(50), e6(400_000)), (e18(5), e6(40_000)), 8_000.0);
+        let pnl = position_pnl(&entry, &current, usdc).unwrap();
+        assert_eq!(
+            pnl,
+            PositionPnl {
+                quote: usdc,
+                entry_value: 40_000.0,
+                hodl_value: 100_000.0,
+                position_value: 80_000.0,
+                fees0: 0.0,
+                fees1: 0.0,
+                fees_value: 0.0,
+                impermanent_loss: -20_000.0,
+                impermanent_loss_ratio: -0.2,
+                net_pnl: 40_000.0,
+            }
+        );
+        let pnl = position_pnl(&entry, &current, weth).unwrap();
+        assert_approx(pnl.hodl_value, 12.5);
+        assert_approx(pnl.impermanent_loss_ratio, -0.2);
+
+        // the square root of the constant product grew by 1% with fees
+        let reserves = (e18(505) / 10, e6(404_000));
+        let current = v2_snapshot(reserves, (e18(505) / 100, e6(40_400)), 8_000.0);
+        let pnl = position_pnl(&entry, &current, usdc).unwrap();
+        assert_approx(pnl.position_value, 80_000.0);
+        assert_approx(pnl.fees0, 0.05);
+        assert_approx(pnl.fees1, 400.0);
+        assert_approx(pnl.fees_value, 800.0);
+        assert_approx(pnl.impermanent_loss_ratio, -0.2);
+        assert_approx(pnl.net_pnl, 40_800.0);
+
+        // the decimals of a token changed
+        let mut rebased = current;
+        rebased.decimals1 = 18;
+        rebased.amount1 *= U256::exp10(12);
+        if let SnapshotPosition::V2 { reserve1, .. } = &mut rebased.position {
+            *reserve1 *= U256::exp10(12);
+        }
+        let rebased = position_pnl(&entry, &rebased, usdc).unwrap();
+        assert_approx(rebased.position_value, pnl.position_value);
+        assert_approx(rebased.fees_value, pnl.fees_value);
+
+        // an emptied pair
+        let mut empty = v2_snapshot((0, 0), (0, 0), 0.0);
+        if let SnapshotPosition::V2 { total_supply, .. } = &mut empty.position {
+            *total_supply = U256::zero();
+        }
+        let pnl = position_pnl(&entry, &empty, weth).unwrap();
+        assert_eq!((pnl.hodl_value, pnl.position_value, pnl.fees_value), (0.0, 0.0, 0.0));
+        assert_eq!(pnl.net_pnl, -20.0);
+
+        // a V3 position whose price did not move
+        let mut entry = v2_snapshot((0, 0), (e18(10), e6(20_000)), 2_000.0);
+        entry.position = SnapshotPosition::V3 {
+            pool: Address::repeat_byte(5),
+            tick_lower: -600,
+            tick_upper: 600,
+            liquidity: 1_000,
+        };
+        (entry.fees0, entry.fees1) = (e18(1).into(), e6(100).into());
+        let mut current = entry;
+        (current.fees0, current.fees1) = (e18(2).into(), e6(300).into());
+        let pnl = position_pnl(&entry, &current, usdc).unwrap();
+        assert_eq!((pnl.fees0, pnl.fees1, pnl.fees_value), (1.0, 200.0, 2_200.0));
+        assert_eq!((pnl.impermanent_loss, pnl.net_pnl), (0.0, 2_200.0));
+
+        // different positions
+        let mut other = current;
+        if let SnapshotPosition::V3 { liquidity, .. } = &mut other.position {
+            *liquidity = 2_000;
+        }
+        let res = position_pnl(&entry, &other, usdc);
+        assert!(matches!(res.unwrap_err(), Error::SnapshotMismatch));
+        let res = position_pnl(&entry, &empty, usdc);
+        assert!(matches!(res.unwrap_err(), Error::SnapshotMismatch));
+        let res = position_pnl(&entry, &current, Address::repeat_byte(1));
+        assert!(matches!(res.unwrap_err(), Error::TokenNotInPair(_)));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(Arc::new(provider), Address::repeat_byte(5), ProtocolType::UniswapV2);
+        let [weth, usdc] = [2, 4].map(Address::repeat_byte);
+
+        // LIFO: the decimals, the state and the tokens
+        push_multicall(&mock, &[uints(&[18]), uints(&[6])]);
+        let state = [
+            uints(&[e18(100), e6(200_000), 1]),
+            uints(&[e18(1_000)]),
+            uints(&[100]),
+            uints(&[1_100]),
+        ];
+        push_multicall(&mock, &state);
+        push_tokens(&mock, weth, usdc);
+
+        let source = SnapshotSource::V2 { pair: &pair, liquidity: e18(100).into() };
+        let snapshot = snapshot(source).await.unwrap();
+        assert_eq!(snapshot, v2_snapshot((e18(100), e6(200_000)), (e18(10), e6(20_000)), 2_000.0));
+    }
+}
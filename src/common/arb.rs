@@ -0,0 +1,280 @@
+//! Cyclic arbitrage between two Uniswap V2 pairs of the same tokens.
+
+use crate::{
+    common::call_multi,
+    constants::BPS_U256,
+    contracts::bindings::i_uniswap_v2_pair::IUniswapV2Pair,
+    errors::{Error, Result},
+    v2::{self, sort_reserves, validate_fee, Library},
+};
+use ethers_core::types::{Address, U256, U512};
+use ethers_providers::Middleware;
+
+/// One of the two pairs of an arbitrage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ArbPool {
+    /// The first pair, of `pool_a_reserves` or `protocol_a`.
+    A,
+    /// The second pair, of `pool_b_reserves` or `protocol_b`.
+    B,
+}
+
+impl ArbPool {
+    /// Returns the other pair.
+    #[inline]
+    pub const fn other(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+}
+
+/// A profitable arbitrage, as returned by [`optimal_arb`] and [`scan_pair`].
+///
+/// The arbitrage sells `token_a` for `token_b` in the [`first`][Self::first] pair, then sells all
+/// of the `token_b` back for `token_a` in the other pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ArbOpportunity {
+    /// The pair of the first leg.
+    pub first: ArbPool,
+    /// The amount of `token_a` sold in the first leg.
+    pub amount_in: U256,
+    /// The amount of `token_b` bought in the first leg and sold in the second one.
+    pub amount_mid: U256,
+    /// The amount of `token_a` bought in the second leg.
+    pub amount_out: U256,
+    /// The profit, in `token_a`.
+    pub profit: U256,
+}
+
+impl ArbOpportunity {
+    /// Returns the pair and the path of each leg.
+    pub fn legs(&self, token_a: Address, token_b: Address) -> [(ArbPool, Vec<Address>); 2] {
+        [(self.first, vec![token_a, token_b]), (self.first.other(), vec![token_b, token_a])]
+    }
+}
+
+/// Returns the amount of `token_a` which maximizes the profit of a cyclic arbitrage between two
+/// Uniswap V2 pairs, with the closed-form solution, or None if there is no profit.
+///
+/// The reserves of each pair are `(reserve_a, reserve_b)`, and the fees are in basis points. Both
+/// directions are evaluated; the amounts are those of the pairs' `getAmountOut`.
+///
+/// Returns [`Error::InvalidFee`] if a fee is not lower than `10_000`, and [`Error::Overflow`] if
+/// the reserves are too large for the intermediate values of the solution, which does not happen
+/// with reserves that fit in a `uint112`.
+pub fn optimal_arb(
+    pool_a_reserves: (U256, U256),
+    pool_b_reserves: (U256, U256),
+    fee_a_bps: u32,
+    fee_b_bps: u32,
+) -> Result<Option<ArbOpportunity>> {
+    let (fee_a, fee_b) = (validate_fee(fee_a_bps)?, validate_fee(fee_b_bps)?);
+    let a = (pool_a_reserves, fee_a);
+    let b = (pool_b_reserves, fee_b);
+    let a_first = optimal_arb_directed(ArbPool::A, a, b)?;
+    let b_first = optimal_arb_directed(ArbPool::B, b, a)?;
+    Ok(match (a_first, b_first) {
+        (Some(x), Some(y)) => Some(if x.profit >= y.profit { x } else { y }),
+        (x, y) => x.or(y),
+    })
+}
+
+/// Evaluates the arbitrage which sells `token_a` in the `first` pair.
+///
+/// Composing the two legs gives `out = K * x / (C + D * x)`, with the fee multipliers `f1` and
+/// `f2`, out of `10_000`:
+/// - `K = f1 * f2 * b1 * a2`
+/// - `C = 10_000² * a1 * b2`
+/// - `D = f1 * (10_000 * b2 + f2 * b1)`
+///
+/// The profit `out - x` is maximal for `x = (√(K * C) - C) / D`, and is positive only if `K > C`.
+fn optimal_arb_directed(
+    first: ArbPool,
+    ((a1, b1), fee_1): ((U256, U256), u32),
+    ((a2, b2), fee_2): ((U256, U256), u32),
+) -> Result<Option<ArbOpportunity>> {
+    if [a1, b1, a2, b2].iter().any(U256::is_zero) {
+        return Err(Error::InsufficientLiquidity);
+    }
+    let bps = U512::from(BPS_U256);
+    let (f1, f2) = (bps - fee_1, bps - fee_2);
+    let overflow = || Error::Overflow;
+
+    let k = b1.full_mul(a2).checked_mul(f1 * f2).ok_or_else(overflow)?;
+    let c = a1.full_mul(b2).checked_mul(bps * bps).ok_or_else(overflow)?;
+    if k <= c {
+        return Ok(None);
+    }
+    // fits, as the reserves are U256
+    let d = (bps * U512::from(b2) + f2 * U512::from(b1)) * f1;
+    let root = k.checked_mul(c).ok_or_else(overflow)?.integer_sqrt();
+    let amount_in = U256::try_from((root - c) / d).map_err(|_| overflow())?;
+    if amount_in.is_zero() {
+        return Ok(None);
+    }
+
+    // recompute with the rounding of the pairs
+    let amount_mid = Library::get_amount_out_with_fee(amount_in, a1, b1, fee_1)?;
+    if amount_mid.is_zero() {
+        return Ok(None);
+    }
+    let amount_out = Library::get_amount_out_with_fee(amount_mid, b2, a2, fee_2)?;
+    if amount_out <= amount_in {
+        return Ok(None);
+    }
+    let profit = amount_out - amount_in;
+    Ok(Some(ArbOpportunity { first, amount_in, amount_mid, amount_out, profit }))
+}
+
+/// Fetches the reserves of the `token_a`/`token_b` pairs of both protocols in a single multicall,
+/// and returns the [optimal arbitrage][optimal_arb] between them if its profit, in `token_a`, is
+/// greater than `gas_cost`, also in `token_a`.
+///
+/// The returned [`profit`][ArbOpportunity::profit] is net of `gas_cost`. The fees are those used by
+/// each protocol's router, see [`Router::swap_fee_bps`][v2::Router::swap_fee_bps].
+///
+/// Returns [`Error::StablePairUnsupported`] if either protocol is a Solidly fork with stable
+/// pairs.
+pub async fn scan_pair<M: Middleware>(
+    protocol_a: &v2::Protocol<M>,
+    protocol_b: &v2::Protocol<M>,
+    token_a: Address,
+    token_b: Address,
+    gas_cost: U256,
+) -> Result<Option<ArbOpportunity>> {
+    let protocols = [protocol_a, protocol_b];
+    if protocols.iter().any(|p| p.factory().protocol().stable() == Some(true)) {
+        return Err(Error::StablePairUnsupported);
+    }
+
+    let client = protocol_a.client();
+    let pair = IUniswapV2Pair::new(Address::zero(), client.clone());
+    let calls = protocols
+        .iter()
+        .map(|p| {
+            let mut call = pair.get_reserves();
+            call.tx.set_to(Library::pair_for(p.factory(), token_a, token_b));
+            call
+        })
+        .collect();
+    let reserves = call_multi(client, protocol_a.chain(), calls).await?;
+    let [(a0, a1, _), (b0, b1, _)] = [0, 1].map(|i| sort_reserves(token_a, token_b, reserves[i]));
+
+    let fees = protocols.map(|p| p.router().swap_fee_bps(p.factory()));
+    let opportunity = optimal_arb((a0, a1), (b0, b1), fees[0], fees[1])?;
+    Ok(opportunity.and_then(|mut opportunity| {
+        opportunity.profit = opportunity.profit.checked_sub(gas_cost).filter(|p| !p.is_zero())?;
+        Some(opportunity)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+    use std::sync::Arc;
+
+    type M = Provider<MockProvider>;
+
+    const TOKEN_A: Address = Address::repeat_byte(1);
+    const TOKEN_B: Address = Address::repeat_byte(2);
+
+    fn e18(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(18)
+    }
+
+    /// The profit of the arbitrage which sells `amount_in` in pair A first.
+    fn profit(amount_in: U256, a: (U256, U256), b: (U256, U256)) -> Option<U256> {
+        let mid = Library::get_amount_out(amount_in, a.0, a.1).ok()?;
+        let out = Library::get_amount_out(mid, b.1, b.0).ok()?;
+        out.checked_sub(amount_in)
+    }
+
+    #[test]
+    fn test_optimal_arb() {
+        // B is cheaper in pair A, 1 A = 2 B vs 1 A = 1.8 B
+        let a = (e18(1_000), e18(2_000));
+        let b = (e18(1_000), e18(1_800));
+        let arb = optimal_arb(a, b, 30, 30).unwrap().unwrap();
+        assert_eq!(arb.first, ArbPool::A);
+        assert_eq!(arb.amount_out - arb.amount_in, arb.profit);
+        assert_eq!(profit(arb.amount_in, a, b), Some(arb.profit));
+
+        // the profit is maximal
+        let delta = U256::exp10(16);
+        assert!(profit(arb.amount_in + delta, a, b).unwrap() < arb.profit);
+        assert!(profit(arb.amount_in - delta, a, b).unwrap() < arb.profit);
+
+        // the other direction
+        let arb_b = optimal_arb(b, a, 30, 30).unwrap().unwrap();
+        assert_eq!(arb_b.first, ArbPool::B);
+        assert_eq!((arb_b.amount_in, arb_b.profit), (arb.amount_in, arb.profit));
+        let legs = arb_b.legs(TOKEN_A, TOKEN_B);
+        assert_eq!(legs[0], (ArbPool::B, vec![TOKEN_A, TOKEN_B]));
+        assert_eq!(legs[1], (ArbPool::A, vec![TOKEN_B, TOKEN_A]));
+    }
+
+    #[test]
+    fn test_optimal_arb_unprofitable() {
+        // same price
+        let a = (e18(1_000), e18(2_000));
+        assert_eq!(optimal_arb(a, (e18(10), e18(20)), 30, 30).unwrap(), None);
+
+        // the price difference is smaller than the fees
+        let b = (e18(1_000), e18(1_995));
+        assert_eq!(optimal_arb(a, b, 30, 30).unwrap(), None);
+        assert!(optimal_arb(a, b, 0, 0).unwrap().is_some());
+
+        // dust reserves
+        let dust = (U256::from(2), U256::from(3));
+        assert_eq!(optimal_arb(dust, (3.into(), 2.into()), 30, 30).unwrap(), None);
+
+        assert!(matches!(optimal_arb(a, b, 10_000, 30), Err(Error::InvalidFee)));
+        let empty = (U256::zero(), e18(1));
+        assert!(matches!(optimal_arb(a, empty, 30, 30), Err(Error::InsufficientLiquidity)));
+    }
+
+    #[tokio::test]
+    async fn test_scan_pair() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let protocol = |i: u8| {
+            let factory = Address::repeat_byte(0xf0 + i);
+            v2::Protocol::<M>::new(client.clone(), factory, Address::zero(), Default::default())
+        };
+        let (protocol_a, protocol_b) = (protocol(0), protocol(1));
+        let push_reserves = |reserves: [(u64, u64); 2]| {
+            let results = reserves
+                .into_iter()
+                .map(|(reserve_0, reserve_1)| {
+                    let data =
+                        abi::encode(&[e18(reserve_0), e18(reserve_1), 0.into()].map(Token::Uint));
+                    Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)])
+                })
+                .collect();
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+            // the chain id queried by the multicall
+            mock.push(U256::one()).unwrap();
+        };
+
+        let arb = optimal_arb((e18(1_000), e18(2_000)), (e18(1_000), e18(1_800)), 30, 30);
+        let gross = arb.unwrap().unwrap().profit;
+
+        push_reserves([(1_000, 2_000), (1_000, 1_800)]);
+        let res = scan_pair(&protocol_a, &protocol_b, TOKEN_A, TOKEN_B, U256::exp10(18)).await;
+        assert_eq!(res.unwrap().unwrap().profit, gross - U256::exp10(18));
+
+        // not profitable after gas
+        push_reserves([(1_000, 2_000), (1_000, 1_800)]);
+        let res = scan_pair(&protocol_a, &protocol_b, TOKEN_A, TOKEN_B, gross).await;
+        assert_eq!(res.unwrap(), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! Block queries.
+
+use crate::errors::Result;
+use ethers_contract::ContractError;
+#[cfg(feature = "v2")]
+use ethers_core::types::U256;
+use ethers_core::types::{Block, BlockNumber, TxHash};
+use ethers_providers::{Middleware, ProviderError};
+
+/// Returns `block`, or an error if it is not found.
+pub(crate) async fn get_block<M: Middleware>(
+    client: &M,
+    block: BlockNumber,
+) -> Result<Block<TxHash>> {
+    client
+        .get_block(block)
+        .await
+        .map_err(ContractError::<M>::MiddlewareError)?
+        .ok_or_else(|| ProviderError::CustomError(format!("Block {block} not found")).into())
+}
+
+/// Returns the latest block with a timestamp lower than or equal to `timestamp`, by binary
+/// searching the blocks up to `latest`. Returns the genesis block if `timestamp` is before it.
+///
+/// The search starts from the block `latest.timestamp - timestamp` blocks before `latest`, which
+/// is the earliest possible one on chains with block times of at least one second.
+#[cfg(feature = "v2")]
+pub(crate) async fn block_at_timestamp<M: Middleware>(
+    client: &M,
+    latest: Block<TxHash>,
+    timestamp: U256,
+) -> Result<Block<TxHash>> {
+    if latest.timestamp <= timestamp {
+        return Ok(latest);
+    }
+
+    // invariant: `lo.timestamp <= timestamp < hi.timestamp`, unless `lo` is the genesis block
+    let mut hi = latest.number.unwrap_or_default().as_u64();
+    let elapsed = latest.timestamp - timestamp;
+    let mut lo = hi.saturating_sub(elapsed.try_into().unwrap_or(u64::MAX));
+    let mut lo_block = get_block(client, lo.into()).await?;
+    if lo_block.timestamp > timestamp && lo > 0 {
+        // block times are shorter than one second
+        hi = lo;
+        lo = 0;
+        lo_block = get_block(client, lo.into()).await?;
+    }
+    if lo_block.timestamp >= timestamp {
+        return Ok(lo_block);
+    }
+
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        let block = get_block(client, mid.into()).await?;
+        match block.timestamp.cmp(&timestamp) {
+            std::cmp::Ordering::Equal => return Ok(block),
+            std::cmp::Ordering::Less => (lo, lo_block) = (mid, block),
+            std::cmp::Ordering::Greater => hi = mid,
+        }
+    }
+    Ok(lo_block)
+}
+
+#[cfg(test)]
+#[cfg(feature = "v2")]
+mod tests {
+    use super::*;
+    use ethers_providers::{MockProvider, Provider};
+
+    fn block(number: u64, timestamp: u64) -> Block<TxHash> {
+        Block { number: Some(number.into()), timestamp: timestamp.into(), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_block_at_timestamp() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+
+        // no calls
+        let res = block_at_timestamp(&provider, block(10, 1_000), 1_000.into()).await.unwrap();
+        assert_eq!(res.number, Some(10.into()));
+
+        // 12 second blocks from the genesis block: searches blocks 0, 5, 2, 1
+        for (number, timestamp) in [(1, 892), (2, 904), (5, 940), (0, 880)] {
+            mock.push(block(number, timestamp)).unwrap();
+        }
+        let res = block_at_timestamp(&provider, block(10, 1_000), 900.into()).await.unwrap();
+        assert_eq!(res.number, Some(1.into()));
+
+        // before genesis
+        mock.push(block(0, 880)).unwrap();
+        let res = block_at_timestamp(&provider, block(10, 1_000), 1.into()).await.unwrap();
+        assert_eq!(res.number, Some(0.into()));
+
+        // sub-second blocks: block 9 is after the timestamp, searches blocks 0, 4, 6
+        for (number, timestamp) in [(6, 999), (4, 998), (0, 990), (9, 1_000)] {
+            mock.push(block(number, timestamp)).unwrap();
+        }
+        let res = block_at_timestamp(&provider, block(10, 1_000), 999.into()).await.unwrap();
+        assert_eq!(res.number, Some(6.into()));
+    }
+}
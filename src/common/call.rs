@@ -1,8 +1,21 @@
-use crate::errors::Result;
+use crate::{errors::Result, TxOptions};
 use ethers_contract::builders::ContractCall;
-use ethers_core::abi::{Detokenize, InvalidOutputType, Param, ParamType, Token};
+use ethers_core::{
+    abi::{Detokenize, InvalidOutputType, Param, ParamType, Token},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, U256,
+        U64,
+    },
+};
 use std::{fmt, marker::PhantomData, mem};
 
+#[cfg(feature = "v2")]
+use ethers_contract::{Contract, EthCall};
+#[cfg(feature = "v2")]
+use ethers_core::types::Selector;
+#[cfg(feature = "v2")]
+use ethers_providers::Middleware;
+
 /// Stores the result of a call to decode it later.
 #[must_use]
 pub struct CallResult<V2, V3> {
@@ -53,6 +66,80 @@ impl<V2, V3: Detokenize> CallResult<V2, V3> {
     }
 }
 
+/// The target, calldata, value and chain ID of a call, for signing pipelines which do not go
+/// through the client's middleware. See [`CallExt::bundle`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CallBundle {
+    /// The called contract.
+    pub to: Address,
+    /// The encoded calldata.
+    pub data: Bytes,
+    /// The native token value sent with the call.
+    pub value: U256,
+    /// The chain ID.
+    pub chain_id: U64,
+}
+
+impl CallBundle {
+    /// Returns an EIP-1559 transaction request with only the bundle's fields set.
+    pub fn into_tx_request(self) -> TypedTransaction {
+        Eip1559TransactionRequest::new()
+            .to(self.to)
+            .data(self.data)
+            .value(self.value)
+            .chain_id(self.chain_id)
+            .into()
+    }
+}
+
+impl From<CallBundle> for TypedTransaction {
+    fn from(bundle: CallBundle) -> Self {
+        bundle.into_tx_request()
+    }
+}
+
+/// A contract method call with its arguments already tokenized, built into a [ContractCall] with
+/// [`build`][MethodCall::build].
+///
+/// The bindings' methods instantiate the contract's call builder once per method and middleware.
+/// Assembling the arguments with this instead keeps the logic choosing the method out of the
+/// middleware-generic code, which then only builds one call per output type.
+#[cfg(feature = "v2")]
+#[derive(Clone, Debug)]
+pub(crate) struct MethodCall {
+    selector: Selector,
+    args: Token,
+    value: Option<U256>,
+}
+
+#[cfg(feature = "v2")]
+impl MethodCall {
+    /// Creates a new instance from a call struct of the bindings.
+    pub(crate) fn new<C: EthCall>(call: C) -> Self {
+        Self { selector: C::selector(), args: call.into_token(), value: None }
+    }
+
+    /// Sets the native token value sent with the call.
+    pub(crate) fn value(mut self, value: U256) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Builds the call to `contract`, which must have the method in its ABI.
+    pub(crate) fn build<M: Middleware, D: Detokenize>(
+        self,
+        contract: &Contract<M>,
+    ) -> Result<ContractCall<M, D>> {
+        // the arguments are a tuple, which is flattened back into the method's parameters
+        let call = contract.method_hash(self.selector, self.args)?;
+        Ok(match self.value {
+            Some(value) => call.value(value),
+            None => call,
+        })
+    }
+}
+
 /// Extra [ContractCall] methods.
 pub trait CallExt<M>: private::Sealed {
     /// Change the output of a `ContractCall<M, D>`, which is `D`, to another type by changing the
@@ -90,6 +177,25 @@ pub trait CallExt<M>: private::Sealed {
     ///
     /// This is safe because there are no arguments.
     fn clear_output(self) -> ContractCall<M, ()>;
+
+    /// Returns the call's target, calldata and value, with `chain_id`.
+    ///
+    /// None of the crate's builders require a signer: calls built with a read-only client, like
+    /// a `Provider<Http>`, can be bundled and signed elsewhere. Note that the
+    /// [signer recipient][crate::Recipient::Signer] and
+    /// [balance percentages][crate::Amount::PercentOfBalance] still need the client to have a
+    /// default sender.
+    fn bundle(&self, chain_id: impl Into<U64>) -> CallBundle;
+
+    /// Consumes the call into its transaction request, with `chain_id`. Unlike
+    /// [`bundle`][CallExt::bundle], this keeps all the fields already set on the call, like the
+    /// ones set by [`TxOptions`].
+    fn into_tx_request(self, chain_id: impl Into<U64>) -> TypedTransaction;
+
+    /// Applies `options` to the call. See [`TxOptions::apply`].
+    fn tx_options(self, options: &TxOptions) -> Result<Self>
+    where
+        Self: Sized;
 }
 
 impl<M, Src> CallExt<M> for ContractCall<M, Src> {
@@ -136,6 +242,25 @@ impl<M, Src> CallExt<M> for ContractCall<M, Src> {
         // SAFETY: See the function docs
         unsafe { mem::transmute::<ContractCall<M, Src>, ContractCall<M, ()>>(self) }
     }
+
+    fn bundle(&self, chain_id: impl Into<U64>) -> CallBundle {
+        CallBundle {
+            to: self.tx.to_addr().copied().unwrap_or_default(),
+            data: self.tx.data().cloned().unwrap_or_default(),
+            value: self.tx.value().copied().unwrap_or_default(),
+            chain_id: chain_id.into(),
+        }
+    }
+
+    fn into_tx_request(mut self, chain_id: impl Into<U64>) -> TypedTransaction {
+        self.tx.set_chain_id(chain_id);
+        self.tx
+    }
+
+    #[inline]
+    fn tx_options(self, options: &TxOptions) -> Result<Self> {
+        options.apply(self)
+    }
 }
 
 mod private {
@@ -145,6 +270,7 @@ mod private {
 }
 
 #[cfg(test)]
+#[cfg(feature = "v2")]
 mod tests {
     use super::*;
     use crate::{contracts::bindings::i_uniswap_v2_pair::IUniswapV2Pair, CallResult};
@@ -163,6 +289,26 @@ mod tests {
         cc
     }
 
+    #[test]
+    fn test_bundle() {
+        let cc = cc().tx_options(&TxOptions::new().nonce(1.into())).unwrap();
+        let bundle = cc.bundle(1);
+        assert_eq!(bundle.to, Address::zero());
+        assert_eq!(bundle.data, cc.calldata().unwrap());
+        assert_eq!(bundle.value, U256::zero());
+        assert_eq!(bundle.chain_id, 1.into());
+
+        let tx = bundle.clone().into_tx_request();
+        assert_eq!(tx.to_addr(), Some(&bundle.to));
+        assert_eq!(tx.data(), Some(&bundle.data));
+        assert_eq!(tx.chain_id(), Some(1.into()));
+        assert_eq!(tx.nonce(), None);
+
+        let tx = cc.into_tx_request(1);
+        assert_eq!(tx.chain_id(), Some(1.into()));
+        assert_eq!(tx.nonce(), Some(&1.into()));
+    }
+
     #[test]
     fn test_transmute_zst() {
         let cc = cc();
@@ -0,0 +1,41 @@
+use std::{fmt, time::Duration};
+
+/// A source of the current time, as the [Duration] since the UNIX epoch.
+///
+/// Used instead of [`SystemTime::now`][std::time::SystemTime::now], which panics on
+/// `wasm32-unknown-unknown`.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Returns the [Duration] since the UNIX epoch.
+    fn now(&self) -> Duration;
+}
+
+/// The system's clock: [`SystemTime`][std::time::SystemTime], or JavaScript's `Date.now()` on
+/// `wasm32` with the `wasm` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    fn now(&self) -> Duration {
+        use std::time::SystemTime;
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap()
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn now(&self) -> Duration {
+        Duration::from_millis(js_sys::Date::now() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock() {
+        let now = SystemClock.now();
+        // after 2020-01-01
+        assert!(now > Duration::from_secs(1_577_836_800));
+        assert!(SystemClock.now() >= now);
+    }
+}
@@ -0,0 +1,369 @@
+//! Price comparison of the same trade across multiple protocols.
+
+use crate::{
+    common::Limiter,
+    errors::{Error, Result},
+    utils::{is_native, resolve_weth},
+    v2::{self, Library},
+    v3::{
+        self,
+        math::{mul_div, Q96},
+        FeeAmount, PoolLiquidity,
+    },
+    Amount, ProtocolType, RequestPolicy,
+};
+use ethers_core::types::{Address, Chain, U256};
+use ethers_providers::Middleware;
+use futures_util::{future, stream, StreamExt};
+use std::cmp::Reverse;
+
+/// Where a [ComparedQuote] comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QuoteSource {
+    /// The pair of a Uniswap V2 protocol, or of one of its forks.
+    V2 {
+        /// The type of the protocol.
+        protocol: ProtocolType,
+        /// The address of the protocol's factory.
+        factory: Address,
+    },
+    /// A pool of a Uniswap V3 protocol.
+    V3 {
+        /// The address of the protocol's factory.
+        factory: Address,
+        /// The fee amount of the pool.
+        fee: FeeAmount,
+    },
+}
+
+/// A quote of a trade on a single pair or pool, as returned by [`best_quote`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ComparedQuote {
+    /// Where the quote comes from.
+    pub source: QuoteSource,
+    /// The address of the pair or pool.
+    pub pool: Address,
+    /// The input amount.
+    pub amount_in: U256,
+    /// The output amount.
+    pub amount_out: U256,
+    /// The reserve of the input token in the pair, or the virtual reserve in the range of the
+    /// pool's current price.
+    pub reserve_in: U256,
+    /// Whether the reserve is below [`CompareOptions::min_reserve_in`], so that the quote is
+    /// likely to move before the trade is executed.
+    pub unreliable: bool,
+}
+
+/// The quotes of the same trade across multiple protocols, as returned by [`best_quote`].
+#[derive(Debug, Default)]
+pub struct QuoteComparison {
+    /// The quotes, the best first: by the highest output of an exact input trade, or by the
+    /// lowest input of an exact output trade.
+    pub quotes: Vec<ComparedQuote>,
+    /// The factory address of each protocol which could not quote the trade, with the error.
+    pub failed: Vec<(Address, Error)>,
+}
+
+impl QuoteComparison {
+    /// Returns the best reliable quote, if any.
+    pub fn best(&self) -> Option<&ComparedQuote> {
+        self.quotes.iter().find(|quote| !quote.unreliable)
+    }
+
+    /// Returns the best quote, reliable or not.
+    pub fn best_any(&self) -> Option<&ComparedQuote> {
+        self.quotes.first()
+    }
+}
+
+/// Additional options used by [`best_quote`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CompareOptions {
+    /// The minimum reserve of the input token below which a quote is flagged as
+    /// [unreliable][ComparedQuote::unreliable].
+    pub min_reserve_in: U256,
+
+    /// The concurrency and retry policy of the requests of the protocols' quotes.
+    pub policy: RequestPolicy,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompareOptions {
+    /// Creates a new instance with the default options: no minimum reserve, and the default
+    /// [RequestPolicy].
+    #[inline]
+    pub const fn new() -> Self {
+        Self { min_reserve_in: U256([0; 4]), policy: RequestPolicy::new() }
+    }
+
+    /// Sets the minimum reserve of the input token.
+    #[inline]
+    pub const fn min_reserve_in(mut self, min_reserve_in: U256) -> Self {
+        self.min_reserve_in = min_reserve_in;
+        self
+    }
+
+    /// Sets the maximum number of requests in flight, see [`RequestPolicy::max_concurrency`].
+    #[inline]
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.policy.max_concurrency = concurrency;
+        self
+    }
+
+    /// Sets the concurrency and retry policy.
+    #[inline]
+    pub const fn policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// Quotes swapping `amount` of `token_in` for `token_out` through the direct pair or pools of each
+/// of the `v2` and `v3` protocols, and returns the quotes sorted from the best.
+///
+/// The protocols are quoted concurrently, with at most
+/// [`max_concurrency`][RequestPolicy::max_concurrency] requests of the
+/// [policy][CompareOptions::policy] in flight, and throttled requests retried. Every pool of a V3
+/// protocol with in-range liquidity is quoted, see [`best_pool`][v3::Protocol::best_pool]. The
+/// native token is replaced with each protocol's wrapped native token.
+///
+/// [`Amount::PercentOfBalance`] is resolved once, against the balance of the default sender of
+/// the first protocol's client. Protocols which fail to quote are reported in
+/// [`failed`][QuoteComparison::failed] instead of failing the comparison.
+pub async fn best_quote<M: Middleware>(
+    v2: &[v2::Protocol<M>],
+    v3: &[v3::Protocol<M>],
+    amount: Amount,
+    token_in: Address,
+    token_out: Address,
+    options: CompareOptions,
+) -> Result<QuoteComparison> {
+    let client = match (v2.first(), v3.first()) {
+        (Some(p), _) => p.client(),
+        (None, Some(p)) => p.client(),
+        (None, None) => return Ok(QuoteComparison::default()),
+    };
+    let amount = amount.resolve(client, &[token_in, token_out]).await?;
+
+    let limiter = Limiter::new(options.policy);
+    let targets = v2.iter().map(Target::V2).chain(v3.iter().map(Target::V3));
+    let results: Vec<_> = stream::iter(targets)
+        .map(|target| target.quote(&limiter, amount, token_in, token_out, options.min_reserve_in))
+        .buffer_unordered(options.policy.concurrency())
+        .collect()
+        .await;
+
+    let mut comparison = QuoteComparison::default();
+    for (factory, res) in results {
+        match res {
+            Ok(quotes) => comparison.quotes.extend(quotes),
+            Err(e) => comparison.failed.push((factory, e)),
+        }
+    }
+    if amount.is_exact_in() {
+        comparison.quotes.sort_by_key(|quote| Reverse(quote.amount_out));
+    } else {
+        comparison.quotes.sort_by_key(|quote| quote.amount_in);
+    }
+    Ok(comparison)
+}
+
+/// A protocol to quote, so that the quotes of both versions are futures of the same type.
+enum Target<'a, M> {
+    V2(&'a v2::Protocol<M>),
+    V3(&'a v3::Protocol<M>),
+}
+
+impl<M: Middleware> Target<'_, M> {
+    /// Returns the factory address of the protocol and its quotes.
+    async fn quote(
+        self,
+        limiter: &Limiter,
+        amount: Amount,
+        token_in: Address,
+        token_out: Address,
+        min_reserve_in: U256,
+    ) -> (Address, Result<Vec<ComparedQuote>>) {
+        match self {
+            Self::V2(p) => {
+                let quote = || quote_v2(p, amount, token_in, token_out, min_reserve_in);
+                let res = limiter.run(quote).await;
+                (p.factory().address(), res.map(|quote| vec![quote]))
+            }
+            Self::V3(p) => {
+                let res = quote_v3(p, limiter, amount, token_in, token_out, min_reserve_in).await;
+                (p.factory().address(), res)
+            }
+        }
+    }
+}
+
+async fn quote_v2<M: Middleware>(
+    p: &v2::Protocol<M>,
+    amount: Amount,
+    token_in: Address,
+    token_out: Address,
+    min_reserve_in: U256,
+) -> Result<ComparedQuote> {
+    let path = map_native([token_in, token_out], p.chain())?;
+    let reserves = Library::get_reserves_multi(p.factory(), &path).await?;
+    let trade = p.router().trade(p.factory(), amount, &path, &reserves, None).await?;
+    let reserve_in = reserves[0].0;
+    Ok(ComparedQuote {
+        source: QuoteSource::V2 {
+            protocol: p.factory().protocol(),
+            factory: p.factory().address(),
+        },
+        pool: p.factory().pair_for(path[0], path[1]).address(),
+        amount_in: trade.amount_in(),
+        amount_out: trade.amount_out(),
+        reserve_in,
+        unreliable: reserve_in < min_reserve_in,
+    })
+}
+
+async fn quote_v3<M: Middleware>(
+    p: &v3::Protocol<M>,
+    limiter: &Limiter,
+    amount: Amount,
+    token_in: Address,
+    token_out: Address,
+    min_reserve_in: U256,
+) -> Result<Vec<ComparedQuote>> {
+    let [token_in, token_out] = map_native([token_in, token_out], p.chain())?;
+    let pools = limiter.run(|| p.best_pool(token_in, token_out)).await?;
+    let quotes = pools.iter().map(|pool| async move {
+        let (amount_in, amount_out) = match amount {
+            Amount::ExactIn(amount_in) => {
+                let quote = limiter
+                    .run(|| p.quote_exact_input_single(token_in, token_out, pool.fee, amount_in))
+                    .await?;
+                (amount_in, quote.amount)
+            }
+            Amount::ExactOut(amount_out) => {
+                let quote = limiter
+                    .run(|| p.quote_exact_output_single(token_in, token_out, pool.fee, amount_out))
+                    .await?;
+                (quote.amount, amount_out)
+            }
+            _ => unreachable!("amount is resolved"),
+        };
+        let reserve_in = virtual_reserve(pool, token_in < token_out)?;
+        Ok(ComparedQuote {
+            source: QuoteSource::V3 { factory: p.factory().address(), fee: pool.fee },
+            pool: pool.address,
+            amount_in,
+            amount_out,
+            reserve_in,
+            unreliable: reserve_in < min_reserve_in,
+        })
+    });
+    future::join_all(quotes).await.into_iter().collect()
+}
+
+/// Returns the virtual reserve of token0, `L / sqrtP`, or of token1, `L * sqrtP`, in the range of
+/// the pool's current price.
+fn virtual_reserve(pool: &PoolLiquidity, token_0: bool) -> Result<U256> {
+    let liquidity = U256::from(pool.liquidity);
+    if token_0 {
+        mul_div(liquidity, Q96, pool.sqrt_price_x96)
+    } else {
+        mul_div(liquidity, pool.sqrt_price_x96, Q96)
+    }
+}
+
+/// Replaces the native token in `path` with the wrapped native token of `chain`.
+fn map_native(path: [Address; 2], chain: Option<Chain>) -> Result<[Address; 2]> {
+    let weth = resolve_weth(None, chain, path.iter().any(is_native))?;
+    Ok(path.map(|token| if is_native(&token) { weth } else { token }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+    use std::sync::Arc;
+
+    type M = Provider<MockProvider>;
+
+    // sorted, so that the input token is token0
+    const TOKEN_IN: Address = Address::repeat_byte(1);
+    const TOKEN_OUT: Address = Address::repeat_byte(2);
+
+    fn push_reserves(mock: &MockProvider, reserve_in: U256, reserve_out: U256) {
+        let data = abi::encode(&[reserve_in, reserve_out, 0.into()].map(Token::Uint));
+        mock.push::<Bytes, _>(Bytes::from(data)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_best_quote() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let protocols: Vec<_> =
+            [ProtocolType::UniswapV2, ProtocolType::Sushiswap, ProtocolType::Pancakeswap]
+                .into_iter()
+                .enumerate()
+                .map(|(i, protocol)| {
+                    let factory = Address::repeat_byte(0xf0 + i as u8);
+                    v2::Protocol::<M>::new(client.clone(), factory, Address::zero(), protocol)
+                })
+                .collect();
+
+        // (sorted in reverse, as the mock is LIFO, and quoted one at a time)
+        mock.push(true).unwrap();
+        push_reserves(&mock, U256::exp10(19), U256::exp10(19) * 2);
+        push_reserves(&mock, U256::exp10(21), U256::exp10(21));
+
+        let amount = Amount::exact_in(U256::exp10(18));
+        let options = CompareOptions::new().min_reserve_in(U256::exp10(20)).concurrency(1);
+        let comparison =
+            best_quote(&protocols, &[], amount, TOKEN_IN, TOKEN_OUT, options).await.unwrap();
+
+        // the shallow pair has the best output, but is flagged
+        assert_eq!(comparison.quotes.len(), 2);
+        let best_any = comparison.best_any().unwrap();
+        assert!(best_any.unreliable);
+        let source =
+            QuoteSource::V2 { protocol: ProtocolType::Sushiswap, factory: [0xf1; 20].into() };
+        assert_eq!(best_any.source, source);
+
+        let best = comparison.best().unwrap();
+        assert!(!best.unreliable);
+        assert!(best.amount_out < best_any.amount_out);
+        assert_eq!((best.amount_in, best.reserve_in), (U256::exp10(18), U256::exp10(21)));
+
+        assert_eq!(comparison.failed.len(), 1);
+        assert_eq!(comparison.failed[0].0, Address::repeat_byte(0xf2));
+    }
+
+    #[test]
+    fn test_virtual_reserve() {
+        let mut pool = PoolLiquidity {
+            address: Address::zero(),
+            fee: FeeAmount::Medium,
+            liquidity: 1_000,
+            sqrt_price_x96: Q96,
+            tick: 0,
+        };
+        assert_eq!(virtual_reserve(&pool, true).unwrap(), 1_000.into());
+        assert_eq!(virtual_reserve(&pool, false).unwrap(), 1_000.into());
+
+        // price of 4, sqrt price of 2
+        pool.sqrt_price_x96 = Q96 * 2;
+        assert_eq!(virtual_reserve(&pool, true).unwrap(), 500.into());
+        assert_eq!(virtual_reserve(&pool, false).unwrap(), 2_000.into());
+    }
+}
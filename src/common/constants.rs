@@ -11,4 +11,12 @@ pub use crate::protocol::pair_code_hashes::*;
 /// A unique address to differentiate the native token from any other ERC20 token.
 pub const NATIVE_ADDRESS: Address = Address::repeat_byte(0xee);
 
+/// Alias for [NATIVE_ADDRESS].
+pub const NATIVE: Address = NATIVE_ADDRESS;
+
 pub(crate) const BPS_U256: U256 = U256([10_000u64, 0, 0, 0]);
+
+/// [10**3](https://github.com/Uniswap/v2-core/blob/ee547b17853e71ed4e0101ccfd52e70d5acded58/contracts/UniswapV2Pair.sol#L15)
+///
+/// The amount of liquidity tokens permanently locked by a Uniswap V2 pair when it is first minted.
+pub const MINIMUM_LIQUIDITY: U256 = U256([1_000u64, 0, 0, 0]);
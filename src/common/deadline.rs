@@ -0,0 +1,128 @@
+use super::blocks::get_block;
+use crate::errors::{Error, Result};
+use ethers_core::types::{BlockNumber, U256};
+use ethers_providers::Middleware;
+use std::time::Duration;
+
+/// The timestamp after which a transaction will revert.
+///
+/// Relative deadlines are resolved against the timestamp of the latest block, rather than the
+/// system clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Deadline {
+    /// An absolute UNIX timestamp, in seconds.
+    Absolute(U256),
+    /// A duration after the latest block's timestamp.
+    FromNow(Duration),
+    /// A number of blocks after the latest block, estimated from the average block time of the
+    /// last blocks.
+    BlocksFromNow(u64),
+}
+
+/// Any deadline.
+impl Default for Deadline {
+    fn default() -> Self {
+        Self::Absolute(U256::MAX)
+    }
+}
+
+impl From<U256> for Deadline {
+    fn from(timestamp: U256) -> Self {
+        Self::Absolute(timestamp)
+    }
+}
+
+impl From<Duration> for Deadline {
+    fn from(duration: Duration) -> Self {
+        Self::FromNow(duration)
+    }
+}
+
+impl Deadline {
+    /// An absolute UNIX `timestamp`, in seconds.
+    #[inline]
+    pub const fn absolute(timestamp: U256) -> Self {
+        Self::Absolute(timestamp)
+    }
+
+    /// A `duration` after the latest block's timestamp.
+    #[inline]
+    pub const fn from_now(duration: Duration) -> Self {
+        Self::FromNow(duration)
+    }
+
+    /// A number of `blocks` after the latest block.
+    #[inline]
+    pub const fn blocks_from_now(blocks: u64) -> Self {
+        Self::BlocksFromNow(blocks)
+    }
+
+    /// Resolves the deadline into an absolute UNIX timestamp by querying the latest block through
+    /// `client`.
+    ///
+    /// Returns [`Error::DeadlineInPast`] if the deadline is before the latest block's timestamp.
+    /// [`U256::MAX`] is returned as is, without making any calls.
+    pub async fn resolve<M: Middleware>(&self, client: &M) -> Result<U256> {
+        if *self == Self::Absolute(U256::MAX) {
+            return Ok(U256::MAX);
+        }
+
+        let latest = get_block(client, BlockNumber::Latest).await?;
+        let timestamp = latest.timestamp;
+        match *self {
+            Self::Absolute(deadline) if deadline < timestamp => Err(Error::DeadlineInPast),
+            Self::Absolute(deadline) => Ok(deadline),
+            Self::FromNow(duration) => Ok(timestamp + duration.as_secs()),
+            Self::BlocksFromNow(blocks) => {
+                let number = latest.number.unwrap_or_default().as_u64();
+                let sample = blocks.clamp(1, number.max(1));
+                let past = get_block(client, number.saturating_sub(sample).into()).await?;
+                let block_time = (timestamp.saturating_sub(past.timestamp)) / sample;
+                Ok(timestamp + block_time * blocks)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Block, TxHash};
+    use ethers_providers::{MockProvider, Provider};
+
+    fn block(number: u64, timestamp: u64) -> Block<TxHash> {
+        Block { number: Some(number.into()), timestamp: timestamp.into(), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let (provider, mock) = Provider::mocked();
+
+        // no calls
+        assert_eq!(Deadline::default().resolve(&provider).await.unwrap(), U256::MAX);
+
+        mock.push(block(100, 1_000)).unwrap();
+        let deadline = Deadline::absolute(1_000.into()).resolve(&provider).await.unwrap();
+        assert_eq!(deadline, 1_000.into());
+
+        mock.push(block(100, 1_000)).unwrap();
+        let res = Deadline::absolute(999.into()).resolve(&provider).await;
+        assert!(matches!(res.unwrap_err(), Error::DeadlineInPast));
+
+        mock.push(block(100, 1_000)).unwrap();
+        let deadline = Deadline::from_now(Duration::from_secs(60));
+        assert_eq!(deadline.resolve(&provider).await.unwrap(), 1_060.into());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_blocks() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+
+        // LIFO
+        mock.push(block(90, 880)).unwrap();
+        mock.push(block(100, 1_000)).unwrap();
+        let deadline = Deadline::blocks_from_now(10).resolve(&provider).await.unwrap();
+        assert_eq!(deadline, 1_120.into());
+    }
+}
@@ -0,0 +1,525 @@
+//! Decoding of router calldata, e.g. to classify pending transactions.
+
+use crate::{
+    constants::NATIVE_ADDRESS,
+    contracts::bindings::{
+        i_swap_router::ISwapRouterCalls, i_swap_router_02::ISwapRouter02Calls,
+        i_uniswap_v2_router_02::IUniswapV2Router02Calls,
+    },
+    v3::Path,
+};
+#[cfg(feature = "universal-router")]
+use crate::{
+    contracts::bindings::{
+        i_universal_router::IUniversalRouterCalls,
+        i_universal_router_commands::IUniversalRouterCommandsCalls,
+    },
+    universal_router::Command,
+};
+use ethers_core::{
+    abi::AbiDecode,
+    types::{Address, Bytes, U256},
+};
+
+#[cfg(feature = "addresses")]
+use crate::contracts::addresses::AddressBook;
+
+/// The kind of a router, which determines how its calldata is decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RouterKind {
+    /// A Uniswap V2 `Router02`, or one of its forks.
+    V2,
+    /// A Uniswap V3 `SwapRouter` or `SwapRouter02`, or one of their forks.
+    V3,
+    /// The Universal Router, whose calldata is only decoded with the `universal-router` feature.
+    UniversalRouter,
+}
+
+/// A router call, normalized by [`decode_call`].
+///
+/// Paths are always from the input to the output token, and those of Uniswap V3 omit the fees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DecodedDexCall {
+    /// A swap of an exact input amount.
+    SwapExactIn {
+        /// The tokens of the swap.
+        path: Vec<Address>,
+        /// The input amount, or None if it is the value of the transaction.
+        amount_in: Option<U256>,
+        /// The minimum output amount.
+        min_out: U256,
+        /// The recipient of the output.
+        recipient: Address,
+    },
+    /// A swap of an exact output amount.
+    SwapExactOut {
+        /// The tokens of the swap.
+        path: Vec<Address>,
+        /// The output amount.
+        amount_out: U256,
+        /// The maximum input amount, or None if it is the value of the transaction.
+        max_in: Option<U256>,
+        /// The recipient of the output.
+        recipient: Address,
+    },
+    /// An addition of liquidity to a Uniswap V2 pair.
+    AddLiquidity {
+        /// The first token, which is never the native token.
+        token_a: Address,
+        /// The second token, or [`NATIVE_ADDRESS`] for the native token.
+        token_b: Address,
+        /// The desired amount of `token_a`.
+        amount_a_desired: U256,
+        /// The desired amount of `token_b`, or None if it is the value of the transaction.
+        amount_b_desired: Option<U256>,
+        /// The recipient of the liquidity tokens.
+        recipient: Address,
+    },
+    /// A removal of liquidity from a Uniswap V2 pair.
+    RemoveLiquidity {
+        /// The first token, which is never the native token.
+        token_a: Address,
+        /// The second token, or [`NATIVE_ADDRESS`] for the native token.
+        token_b: Address,
+        /// The amount of liquidity tokens burned.
+        liquidity: U256,
+        /// The recipient of the tokens.
+        recipient: Address,
+    },
+    /// The calls of a router's `multicall`, or the commands of a Universal Router's `execute`.
+    Batch(Vec<DecodedDexCall>),
+    /// Another call of the router, by its name, like `refundETH` or `WrapEth`, or a swap whose
+    /// Uniswap V3 path is invalid.
+    Other(String),
+    /// A call with an unknown selector.
+    Unknown([u8; 4]),
+}
+
+impl DecodedDexCall {
+    /// Returns whether the call is, or contains, a swap.
+    pub fn is_swap(&self) -> bool {
+        match self {
+            Self::SwapExactIn { .. } | Self::SwapExactOut { .. } => true,
+            Self::Batch(calls) => calls.iter().any(Self::is_swap),
+            _ => false,
+        }
+    }
+}
+
+/// Decodes the `input` of a call to `to`, if it is one of the routers of `book`, including the
+/// built-in ones. See [`AddressBook::router_kind`] and [`decode_call`].
+///
+/// Returns None if `to` is not a known router.
+#[cfg(feature = "addresses")]
+pub fn classify(to: Address, input: Bytes, book: &AddressBook) -> Option<DecodedDexCall> {
+    book.router_kind(to).map(|kind| decode_call(kind, &input))
+}
+
+/// Decodes the `input` of a call to a router of `kind`.
+///
+/// Returns [`DecodedDexCall::Unknown`] with the selector of `input` if it cannot be decoded.
+pub fn decode_call(kind: RouterKind, input: &[u8]) -> DecodedDexCall {
+    let decoded = match kind {
+        RouterKind::V2 => IUniswapV2Router02Calls::decode(input).ok().map(from_v2),
+        RouterKind::V3 => match ISwapRouterCalls::decode(input) {
+            Ok(call) => Some(from_v3(call)),
+            Err(_) => ISwapRouter02Calls::decode(input).ok().map(from_v3_02),
+        },
+        #[cfg(feature = "universal-router")]
+        RouterKind::UniversalRouter => {
+            IUniversalRouterCalls::decode(input).ok().map(from_universal_router)
+        }
+        #[cfg(not(feature = "universal-router"))]
+        RouterKind::UniversalRouter => None,
+    };
+    decoded.unwrap_or_else(|| {
+        let mut selector = [0; 4];
+        let len = input.len().min(4);
+        selector[..len].copy_from_slice(&input[..len]);
+        DecodedDexCall::Unknown(selector)
+    })
+}
+
+fn from_v2(call: IUniswapV2Router02Calls) -> DecodedDexCall {
+    use DecodedDexCall::*;
+    use IUniswapV2Router02Calls as C;
+
+    match call {
+        C::SwapExactTokensForTokens(c) => {
+            exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.to)
+        }
+        C::SwapExactTokensForTokensSupportingFeeOnTransferTokens(c) => {
+            exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.to)
+        }
+        C::SwapExactTokensForETH(c) => exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.to),
+        C::SwapExactTokensForETHSupportingFeeOnTransferTokens(c) => {
+            exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.to)
+        }
+        C::SwapExactETHForTokens(c) => exact_in(c.path, None, c.amount_out_min, c.to),
+        C::SwapExactETHForTokensSupportingFeeOnTransferTokens(c) => {
+            exact_in(c.path, None, c.amount_out_min, c.to)
+        }
+        C::SwapTokensForExactTokens(c) => {
+            exact_out(c.path, c.amount_out, Some(c.amount_in_max), c.to)
+        }
+        C::SwapTokensForExactETH(c) => exact_out(c.path, c.amount_out, Some(c.amount_in_max), c.to),
+        C::SwapETHForExactTokens(c) => exact_out(c.path, c.amount_out, None, c.to),
+        C::AddLiquidity(c) => AddLiquidity {
+            token_a: c.token_a,
+            token_b: c.token_b,
+            amount_a_desired: c.amount_a_desired,
+            amount_b_desired: Some(c.amount_b_desired),
+            recipient: c.to,
+        },
+        C::AddLiquidityETH(c) => AddLiquidity {
+            token_a: c.token,
+            token_b: NATIVE_ADDRESS,
+            amount_a_desired: c.amount_token_desired,
+            amount_b_desired: None,
+            recipient: c.to,
+        },
+        C::RemoveLiquidity(c) => remove(c.token_a, c.token_b, c.liquidity, c.to),
+        C::RemoveLiquidityWithPermit(c) => remove(c.token_a, c.token_b, c.liquidity, c.to),
+        C::RemoveLiquidityETH(c) => remove(c.token, NATIVE_ADDRESS, c.liquidity, c.to),
+        C::RemoveLiquidityETHWithPermit(c) => remove(c.token, NATIVE_ADDRESS, c.liquidity, c.to),
+        C::RemoveLiquidityETHSupportingFeeOnTransferTokens(c) => {
+            remove(c.token, NATIVE_ADDRESS, c.liquidity, c.to)
+        }
+        C::RemoveLiquidityETHWithPermitSupportingFeeOnTransferTokens(c) => {
+            remove(c.token, NATIVE_ADDRESS, c.liquidity, c.to)
+        }
+        C::Weth(_) => Other("WETH".into()),
+        C::Factory(_) => Other("factory".into()),
+        C::GetAmountIn(_) => Other("getAmountIn".into()),
+        C::GetAmountOut(_) => Other("getAmountOut".into()),
+        C::GetAmountsIn(_) => Other("getAmountsIn".into()),
+        C::GetAmountsOut(_) => Other("getAmountsOut".into()),
+        C::Quote(_) => Other("quote".into()),
+    }
+}
+
+fn from_v3(call: ISwapRouterCalls) -> DecodedDexCall {
+    use DecodedDexCall::*;
+    use ISwapRouterCalls as C;
+
+    match call {
+        C::ExactInputSingle(c) => {
+            let p = c.params;
+            exact_in(
+                vec![p.token_in, p.token_out],
+                Some(p.amount_in),
+                p.amount_out_minimum,
+                p.recipient,
+            )
+        }
+        C::ExactInput(c) => {
+            let p = c.params;
+            v3_exact_in(&p.path, p.amount_in, p.amount_out_minimum, p.recipient)
+        }
+        C::ExactOutputSingle(c) => {
+            let p = c.params;
+            let path = vec![p.token_in, p.token_out];
+            exact_out(path, p.amount_out, Some(p.amount_in_maximum), p.recipient)
+        }
+        C::ExactOutput(c) => {
+            let p = c.params;
+            v3_exact_out(&p.path, p.amount_out, p.amount_in_maximum, p.recipient)
+        }
+        C::Multicall(c) => batch(RouterKind::V3, &c.data),
+        C::Weth9(_) => Other("WETH9".into()),
+        C::Factory(_) => Other("factory".into()),
+        C::RefundETH(_) => Other("refundETH".into()),
+        C::SweepToken(_) => Other("sweepToken".into()),
+        C::UniswapV3SwapCallback(_) => Other("uniswapV3SwapCallback".into()),
+        C::UnwrapWETH9(_) => Other("unwrapWETH9".into()),
+    }
+}
+
+fn from_v3_02(call: ISwapRouter02Calls) -> DecodedDexCall {
+    use ISwapRouter02Calls as C;
+
+    match call {
+        C::ExactInputSingle(c) => {
+            let p = c.params;
+            exact_in(
+                vec![p.token_in, p.token_out],
+                Some(p.amount_in),
+                p.amount_out_minimum,
+                p.recipient,
+            )
+        }
+        C::ExactInput(c) => {
+            let p = c.params;
+            v3_exact_in(&p.path, p.amount_in, p.amount_out_minimum, p.recipient)
+        }
+        C::ExactOutputSingle(c) => {
+            let p = c.params;
+            let path = vec![p.token_in, p.token_out];
+            exact_out(path, p.amount_out, Some(p.amount_in_maximum), p.recipient)
+        }
+        C::ExactOutput(c) => {
+            let p = c.params;
+            v3_exact_out(&p.path, p.amount_out, p.amount_in_maximum, p.recipient)
+        }
+        C::SwapExactTokensForTokens(c) => {
+            exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.to)
+        }
+        C::SwapTokensForExactTokens(c) => {
+            exact_out(c.path, c.amount_out, Some(c.amount_in_max), c.to)
+        }
+        C::Multicall(c) => batch(RouterKind::V3, &c.data),
+        C::MulticallWithDeadline(c) => batch(RouterKind::V3, &c.data),
+        C::MulticallWithPreviousBlockhash(c) => batch(RouterKind::V3, &c.data),
+    }
+}
+
+#[cfg(feature = "universal-router")]
+fn from_universal_router(call: IUniversalRouterCalls) -> DecodedDexCall {
+    use DecodedDexCall::*;
+    use IUniversalRouterCalls as C;
+
+    let (commands, inputs) = match call {
+        C::Execute(c) => (c.commands, c.inputs),
+        C::ExecuteWithCommandsAndInputs(c) => (c.commands, c.inputs),
+        C::CollectRewards(_) => return Other("collectRewards".into()),
+        C::OnERC1155BatchReceived(_) => return Other("onERC1155BatchReceived".into()),
+        C::OnERC1155Received(_) => return Other("onERC1155Received".into()),
+        C::OnERC721Received(_) => return Other("onERC721Received".into()),
+        C::SupportsInterface(_) => return Other("supportsInterface".into()),
+        C::UniswapV3SwapCallback(_) => return Other("uniswapV3SwapCallback".into()),
+    };
+    // decode each command on its own, so that a single unsupported one does not fail the rest
+    let calls = commands
+        .iter()
+        .zip(&inputs)
+        .map(|(&byte, input)| {
+            let (command, _) = Command::decode(byte);
+            match command.decode_input(input) {
+                Ok(call) => from_command(command, call),
+                Err(_) => Unknown([byte, 0, 0, 0]),
+            }
+        })
+        .collect();
+    Batch(calls)
+}
+
+#[cfg(feature = "universal-router")]
+fn from_command(command: Command, call: IUniversalRouterCommandsCalls) -> DecodedDexCall {
+    use IUniversalRouterCommandsCalls as C;
+
+    match call {
+        C::V2SwapExactIn(c) => exact_in(c.path, Some(c.amount_in), c.amount_out_min, c.recipient),
+        C::V2SwapExactOut(c) => exact_out(c.path, c.amount_out, Some(c.amount_in_max), c.recipient),
+        C::V3SwapExactIn(c) => v3_exact_in(&c.path, c.amount_in, c.amount_out_min, c.recipient),
+        C::V3SwapExactOut(c) => v3_exact_out(&c.path, c.amount_out, c.amount_in_max, c.recipient),
+        _ => DecodedDexCall::Other(format!("{command:?}")),
+    }
+}
+
+fn exact_in(
+    path: Vec<Address>,
+    amount_in: Option<U256>,
+    min_out: U256,
+    recipient: Address,
+) -> DecodedDexCall {
+    DecodedDexCall::SwapExactIn { path, amount_in, min_out, recipient }
+}
+
+fn exact_out(
+    path: Vec<Address>,
+    amount_out: U256,
+    max_in: Option<U256>,
+    recipient: Address,
+) -> DecodedDexCall {
+    DecodedDexCall::SwapExactOut { path, amount_out, max_in, recipient }
+}
+
+fn remove(
+    token_a: Address,
+    token_b: Address,
+    liquidity: U256,
+    recipient: Address,
+) -> DecodedDexCall {
+    DecodedDexCall::RemoveLiquidity { token_a, token_b, liquidity, recipient }
+}
+
+fn v3_exact_in(path: &Bytes, amount_in: U256, min_out: U256, recipient: Address) -> DecodedDexCall {
+    match Path::decode(path) {
+        Ok(path) => exact_in(path.tokens().to_vec(), Some(amount_in), min_out, recipient),
+        Err(_) => DecodedDexCall::Other("exactInput".into()),
+    }
+}
+
+/// Exact output paths are encoded in reverse, from the output to the input token.
+fn v3_exact_out(
+    path: &Bytes,
+    amount_out: U256,
+    max_in: U256,
+    recipient: Address,
+) -> DecodedDexCall {
+    match Path::decode(path) {
+        Ok(path) => {
+            exact_out(path.reverse().tokens().to_vec(), amount_out, Some(max_in), recipient)
+        }
+        Err(_) => DecodedDexCall::Other("exactOutput".into()),
+    }
+}
+
+fn batch(kind: RouterKind, data: &[Bytes]) -> DecodedDexCall {
+    DecodedDexCall::Batch(data.iter().map(|input| decode_call(kind, input)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::{
+        i_swap_router::{ExactOutputCall, ExactOutputParams, UnwrapWETH9Call},
+        i_swap_router_02::{
+            ExactInputSingleCall, ExactInputSingleParams, MulticallWithDeadlineCall,
+        },
+        i_uniswap_v2_router_02::{AddLiquidityETHCall, SwapExactETHForTokensCall},
+    };
+    use ethers_core::abi::AbiEncode;
+
+    const A: Address = Address::repeat_byte(0x0a);
+    const B: Address = Address::repeat_byte(0x0b);
+    const C: Address = Address::repeat_byte(0x0c);
+    const TO: Address = Address::repeat_byte(0x70);
+
+    #[test]
+    fn test_decode_v2() {
+        let call = SwapExactETHForTokensCall {
+            amount_out_min: 100.into(),
+            path: vec![A, B],
+            to: TO,
+            deadline: U256::MAX,
+        };
+        let expected = DecodedDexCall::SwapExactIn {
+            path: vec![A, B],
+            amount_in: None,
+            min_out: 100.into(),
+            recipient: TO,
+        };
+        assert_eq!(decode_call(RouterKind::V2, &call.encode()), expected);
+
+        let call = AddLiquidityETHCall {
+            token: A,
+            amount_token_desired: 10.into(),
+            amount_token_min: 9.into(),
+            amount_eth_min: 1.into(),
+            to: TO,
+            deadline: U256::MAX,
+        };
+        let decoded = decode_call(RouterKind::V2, &call.encode());
+        assert!(
+            matches!(decoded, DecodedDexCall::AddLiquidity { token_b, .. } if token_b == NATIVE_ADDRESS)
+        );
+        assert!(!decoded.is_swap());
+
+        let decoded = decode_call(RouterKind::V2, &[0xde, 0xad, 0xbe, 0xef, 0, 0]);
+        assert_eq!(decoded, DecodedDexCall::Unknown([0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode_call(RouterKind::V2, &[0x01]), DecodedDexCall::Unknown([1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_decode_v3() {
+        // exact output paths are reversed
+        let path = Path::new(&[C, B, A], &[500, 3000]).unwrap().encode();
+        let params = ExactOutputParams {
+            path,
+            recipient: TO,
+            deadline: U256::MAX,
+            amount_out: 100.into(),
+            amount_in_maximum: 200.into(),
+        };
+        let expected = DecodedDexCall::SwapExactOut {
+            path: vec![A, B, C],
+            amount_out: 100.into(),
+            max_in: Some(200.into()),
+            recipient: TO,
+        };
+        let input = ExactOutputCall { params }.encode();
+        assert_eq!(decode_call(RouterKind::V3, &input), expected);
+
+        // SwapRouter02
+        let params = ExactInputSingleParams {
+            token_in: A,
+            token_out: B,
+            fee: 500,
+            recipient: TO,
+            amount_in: 100.into(),
+            amount_out_minimum: 90.into(),
+            sqrt_price_limit_x96: 0.into(),
+        };
+        let swap = ExactInputSingleCall { params }.encode().into();
+        let unwrap = UnwrapWETH9Call { amount_minimum: 90.into(), recipient: TO }.encode().into();
+        let input = MulticallWithDeadlineCall { deadline: U256::MAX, data: vec![swap, unwrap] };
+        let decoded = decode_call(RouterKind::V3, &input.encode());
+        let expected = DecodedDexCall::Batch(vec![
+            DecodedDexCall::SwapExactIn {
+                path: vec![A, B],
+                amount_in: Some(100.into()),
+                min_out: 90.into(),
+                recipient: TO,
+            },
+            DecodedDexCall::Other("unwrapWETH9".into()),
+        ]);
+        assert_eq!(decoded, expected);
+        assert!(decoded.is_swap());
+    }
+
+    #[test]
+    #[cfg(feature = "universal-router")]
+    fn test_decode_universal_router() {
+        use crate::{
+            contracts::bindings::i_universal_router::ExecuteCall, universal_router::CommandBuilder,
+        };
+
+        let path = Path::new(&[A, B], &[500]).unwrap().encode();
+        let mut builder = CommandBuilder::new();
+        builder
+            .wrap_eth(false, TO, 1.into())
+            .v3_swap_exact_in(false, TO, 100.into(), 90.into(), path, false)
+            .add_command_raw(0x3f, Bytes::default());
+        let (commands, inputs) = builder.build();
+        let input = ExecuteCall { commands, inputs }.encode();
+        let expected = DecodedDexCall::Batch(vec![
+            DecodedDexCall::Other("WrapEth".into()),
+            DecodedDexCall::SwapExactIn {
+                path: vec![A, B],
+                amount_in: Some(100.into()),
+                min_out: 90.into(),
+                recipient: TO,
+            },
+            DecodedDexCall::Unknown([0x3f, 0, 0, 0]),
+        ]);
+        assert_eq!(decode_call(RouterKind::UniversalRouter, &input), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "addresses")]
+    fn test_classify() {
+        use crate::{
+            contracts::addresses::{address, ContractAddresses},
+            ProtocolType,
+        };
+        use ethers_core::types::Chain;
+
+        let mut book = AddressBook::new();
+        let input = Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(classify(C, input.clone(), &book), None);
+
+        let router = address("UniversalRouter", Chain::Mainnet);
+        assert_eq!(book.router_kind(router), Some(RouterKind::UniversalRouter));
+        let router = address("UniswapV2Router02", Chain::Mainnet);
+        assert_eq!(book.router_kind(router), Some(RouterKind::V2));
+        let router = address("UniswapV3Router01", Chain::Mainnet);
+        assert_eq!(book.router_kind(router), Some(RouterKind::V3));
+
+        book.insert(ProtocolType::UniswapV3, Chain::Mainnet, ContractAddresses::new(B, C));
+        let decoded = classify(C, input, &book);
+        assert_eq!(decoded, Some(DecodedDexCall::Unknown([0xde, 0xad, 0xbe, 0xef])));
+    }
+}
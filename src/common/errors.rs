@@ -1,7 +1,13 @@
 //! Errors
 
-use ethers_contract::{ContractError, MulticallError};
-use ethers_core::abi::InvalidOutputType;
+use crate::common::revert_reason;
+#[cfg(feature = "universal-router")]
+use crate::contracts::bindings::i_universal_router::IUniversalRouterErrors;
+use ethers_contract::{ContractError, EthError, MulticallError};
+use ethers_core::{
+    abi::{AbiDecode, Error as AbiError, InvalidOutputType},
+    types::U256,
+};
 use ethers_providers::{Middleware, ProviderError};
 use thiserror::Error as ThisError;
 
@@ -9,7 +15,11 @@ use thiserror::Error as ThisError;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Error thrown by Uniswap.
+///
+/// Reverts of calls made through a provider are decoded with [`Error::from_revert`] into the
+/// variants of the "Revert" section, or into the matching variant of the other sections.
 #[derive(Debug, ThisError)]
+#[non_exhaustive]
 #[rustfmt::skip]
 pub enum Error {
     /* ----------------------------------------- Generic ---------------------------------------- */
@@ -22,9 +32,142 @@ pub enum Error {
     #[error("{0}")]
     MulticallError(String),
 
-    /// Thrown when a provider call fails.
+    /// Thrown when a call of a [Multicall][crate::utils::Multicall] which is not allowed to fail
+    /// reverts, or returns data which cannot be decoded.
+    #[error("Multicall call {index} failed: {data}")]
+    MulticallCallFailed {
+        /// The index of the call.
+        index: usize,
+        /// The data returned by the call.
+        data: ethers_core::types::Bytes,
+    },
+
+    /// Thrown when a signer fails to sign a message.
+    #[error("{0}")]
+    SignerError(String),
+
+    /// Thrown when a provider call fails, other than by reverting.
     #[error(transparent)]
-    ProviderError(#[from] ProviderError),
+    ProviderError(ProviderError),
+
+    /// Thrown when the state at a past block is queried from a node which has pruned it, like a
+    /// full node.
+    #[error("The state at block {block:?} is not available, an archive node is required")]
+    ArchiveRequired {
+        /// The queried block.
+        block: ethers_core::types::BlockId,
+    },
+
+    /// Thrown when a sent transaction is dropped from the mempool before being included.
+    #[error("Transaction {0:?} was dropped")]
+    TransactionDropped(ethers_core::types::H256),
+
+    /// Thrown when [`TxOptions`][crate::TxOptions] conflict with each other, or with the call they
+    /// are applied to.
+    #[error("Conflicting transaction options: {0}")]
+    ConflictingTxOptions(&'static str),
+
+    /// Thrown when [`TxOptions`][crate::TxOptions] send a value to a non-payable function.
+    #[error("Cannot send a value to the non-payable function {0:?}")]
+    NonPayableValue(String),
+
+    /// Thrown when a privately submitted transaction is not included before its maximum block
+    /// number.
+    #[error("Private transaction {tx_hash:?} was not included by block {max_block_number}")]
+    PrivateTransactionExpired {
+        /// The hash of the transaction.
+        tx_hash: ethers_core::types::H256,
+        /// The maximum block number of the transaction.
+        max_block_number: ethers_core::types::U64,
+    },
+
+    /// Thrown when an owner address is required and the client does not have a default sender.
+    #[error("Client does not have a default sender")]
+    MissingSender,
+
+    /// Thrown when an operation which must be signed, like executing a swap, is called with a
+    /// read-only client, which does not have a default sender.
+    #[error("{0} requires a signer, but the client does not have a default sender")]
+    SignerRequired(&'static str),
+
+    /// Thrown when an ENS name cannot be resolved to an address.
+    #[error("Could not resolve ENS name {name:?}: {reason}")]
+    EnsResolutionFailed {
+        /// The ENS name.
+        name: String,
+        /// The reason why it could not be resolved.
+        reason: String,
+    },
+
+    /// Thrown when resolving an ENS name on a chain which does not have ENS.
+    #[error("ENS is not supported on chain {0}")]
+    EnsNotSupported(ethers_core::types::U256),
+
+    /// Thrown when an addressbook file cannot be parsed, or has an invalid value at `key`.
+    #[error("Invalid addressbook at {key:?}: {reason}")]
+    InvalidAddressBook {
+        /// The dot-separated path of the invalid value, empty if the whole file is invalid.
+        key: String,
+        /// The reason why the value is invalid.
+        reason: String,
+    },
+
+    /// Thrown when the chain ID of a client is not a known [`Chain`][ethers_core::types::Chain].
+    #[error("Unknown chain ID: {0}")]
+    UnknownChainId(ethers_core::types::U256),
+
+    /// Thrown when parsing a protocol from a string which is not the name of a built-in one.
+    #[error("Unknown protocol {0:?}, expected one of: {}", crate::protocol::protocol_names())]
+    UnknownProtocol(String),
+
+    /// Thrown when the addresses of a protocol are not found for a chain.
+    #[error("{protocol} addresses not found for chain {chain:?}")]
+    ProtocolNotFound {
+        /// The protocol.
+        protocol: crate::ProtocolType,
+        /// The chain.
+        chain: ethers_core::types::Chain,
+    },
+
+    /// Thrown when a protocol's addresses do not match the chain, see
+    /// `Protocol::verify`.
+    #[error(
+        "Protocol verification failed: {}",
+        .0.failed().map(|check| check.name).collect::<Vec<_>>().join(", ")
+    )]
+    VerificationFailed(Box<crate::verify::VerificationReport>),
+
+    /// Thrown when a [token list][crate::tokens::TokenList] cannot be parsed.
+    #[error("Invalid token list: {0}")]
+    InvalidTokenList(String),
+
+    /// Thrown when the build artifact of a contract cannot be read.
+    #[error("Invalid artifact: {0}")]
+    InvalidArtifact(String),
+
+    /// Thrown when a symbol is not in a token list for a chain.
+    #[error("Symbol {symbol:?} not found for chain {chain:?}")]
+    UnknownSymbol {
+        /// The symbol.
+        symbol: String,
+        /// The chain.
+        chain: ethers_core::types::Chain,
+    },
+
+    /// Thrown when more than one token of a token list has a symbol on a chain.
+    #[error("Symbol {symbol:?} is ambiguous for chain {chain:?}: {candidates:?}")]
+    AmbiguousSymbol {
+        /// The symbol.
+        symbol: String,
+        /// The chain.
+        chain: ethers_core::types::Chain,
+        /// The addresses of the tokens with the symbol.
+        candidates: Vec<ethers_core::types::Address>,
+    },
+
+    /// Thrown when a pair of symbols is not of the form `BASE/QUOTE`.
+    #[error("Invalid pair of symbols, expected \"BASE/QUOTE\": {0:?}")]
+    InvalidPairSymbols(String),
 
     /* ------------------------------------------- Dex ------------------------------------------ */
 
@@ -36,16 +179,166 @@ pub enum Error {
     #[error("Cannot swap a token into itself")]
     SwapToSelf,
 
+    /// Thrown when no route with liquidity is found between two tokens.
+    #[error("No route found")]
+    NoRouteFound,
+
+    /// Thrown when the provided swap fee is not lower than 100%.
+    #[error("Swap fee must be in range: 0..10000 bps")]
+    InvalidFee,
+
+    /// Thrown when the provided percentage is greater than 100%.
+    #[error("Percentage must be in range: 0..=10000 bps")]
+    InvalidPercentage,
+
+    /// Thrown when parsing an invalid decimal amount.
+    #[error("Invalid decimal amount: {0:?}")]
+    InvalidUnits(String),
+
+    /// Thrown when a swap's amount is zero.
+    #[error("Swap amount is zero")]
+    ZeroAmount,
+
+    /// Thrown when building a swap with a `SwapBuilder` whose amount is not
+    /// set.
+    #[error("Swap amount is not set")]
+    MissingAmount,
+
+    /// Thrown when using an [Amount][crate::Amount] which must be resolved against a balance
+    /// first.
+    #[error("Percentage of balance amounts must be resolved first")]
+    UnresolvedAmount,
+
+    /// Thrown when the provided deadline is before the latest block's timestamp.
+    #[error("Deadline is in the past")]
+    DeadlineInPast,
+
     /// Thrown when trying to create a WETH deposit or withdrawal and WETH has not been set.
     #[error("WETH has yet to be set")]
     WethNotSet,
 
+    /// Thrown when swapping the native token without an explicit wrapped native token, on a chain
+    /// which is not set or whose wrapped native token is not in the addressbook.
+    #[error("Wrapped native token not found for chain {0:?}")]
+    WrappedNativeNotFound(Option<ethers_core::types::Chain>),
+
+    /// Thrown when requesting a fee-on-transfer swap with an exact output amount.
+    #[error("Fee-on-transfer swaps only support exact input amounts")]
+    FeeOnTransferExactOut,
+
+    /// Thrown when a pair has not been deployed.
+    #[error("Pair {token_a:?}/{token_b:?} has not been deployed")]
+    PairNotDeployed {
+        /// The first token of the pair.
+        token_a: ethers_core::types::Address,
+        /// The second token of the pair.
+        token_b: ethers_core::types::Address,
+    },
+
+    /// Thrown when the address of a pair returned by a factory differs from the computed one.
+    #[error("Pair address mismatch: expected {expected:?}, got {actual:?}")]
+    PairAddressMismatch {
+        /// The address computed with the protocol's pair code hash.
+        expected: ethers_core::types::Address,
+        /// The address returned by the factory.
+        actual: ethers_core::types::Address,
+    },
+
+    /// Thrown when reading the cached state of a pair which has not been synced.
+    #[error("Pair has not been synced")]
+    PairNotSynced,
+
+    /// Thrown when a token is not one of the tokens of a pair.
+    #[error("Token {0:?} is not in the pair")]
+    TokenNotInPair(ethers_core::types::Address),
+
+    /// Thrown when a transaction receipt does not contain any pair `Swap` events.
+    #[error("No swap events found in the transaction receipt")]
+    NoSwapEvents,
+
+    /// Thrown when a Uniswap V3 pool has not been deployed.
+    #[error("Pool {0:?} has not been deployed")]
+    PoolNotDeployed(ethers_core::types::Address),
+
+    /// Thrown when no Uniswap V3 pool with liquidity exists for a pair of tokens.
+    #[error("No pool with liquidity found")]
+    NoPoolFound,
+
+    /// Thrown when a swap's price impact is greater than the maximum allowed one.
+    #[error("Price impact too high: {actual} bps > {max} bps")]
+    PriceImpactTooHigh {
+        /// The swap's price impact, in basis points.
+        actual: u32,
+        /// The maximum allowed price impact, in basis points.
+        max: u32,
+    },
+
+    /// Thrown when computing a time-weighted average price over a window in which no time has
+    /// elapsed.
+    #[error("TWAP window must be at least one second long")]
+    InvalidTwapWindow,
+
+    /// Thrown when building candles with an interval shorter than one second.
+    #[error("Candle interval must be at least one second long")]
+    InvalidCandleInterval,
+
+    /// Thrown when estimating a fee APR over a window in which no time has elapsed.
+    #[error("APR window must be at least one second long")]
+    InvalidAprWindow,
+
+    /// Thrown when computing the P&L of a position between snapshots of different positions.
+    #[error("snapshots are not of the same position")]
+    SnapshotMismatch,
+
+    /// Thrown when the price observations of a Uniswap V3 pool do not cover a TWAP window.
+    #[error("TWAP window is longer than the oldest observation, from {available:?} ago")]
+    OracleWindowTooLong {
+        /// The age of the oldest observation.
+        available: std::time::Duration,
+    },
+
+    /// Thrown when swapping an exact output amount through a protocol which only supports exact
+    /// inputs, like Solidly forks.
+    #[error("Protocol only supports exact input swaps")]
+    ExactOutUnsupported,
+
+    /// Thrown when an operation is not supported by the protocol, like quoting a swap through
+    /// Uniswap V3 with a `SwapBuilder`.
+    #[error("Operation is not supported by the protocol")]
+    UnsupportedProtocol,
+
+    /// Thrown when computing with the constant product invariant on Solidly stable pairs.
+    #[error("Solidly stable pairs are not supported")]
+    StablePairUnsupported,
+
+    /// Thrown when decoding a Universal Router command which is not supported.
+    #[error("Invalid Universal Router command: {0:#04x}")]
+    InvalidCommand(u8),
+
+    /// Thrown when decoding Universal Router commands whose number differs from that of their
+    /// inputs.
+    #[error("{commands} Universal Router commands but {inputs} inputs")]
+    CommandInputsMismatch {
+        /// The number of commands.
+        commands: usize,
+        /// The number of inputs.
+        inputs: usize,
+    },
+
     /* ----------------------------------------- Library ---------------------------------------- */
 
     /// Thrown when providing identical addresses as parameters.
     #[error("Sorting identical addresses")]
     IdenticalAddresses,
 
+    /// Thrown when providing the zero address as a token.
+    #[error("Zero address")]
+    ZeroAddress,
+
+    /// Thrown when providing an amount equal to zero.
+    #[error("Amount is zero")]
+    InsufficientAmount,
+
     /// Thrown when providing an input amount equal to zero.
     #[error("Input amount is zero")]
     InsufficientInputAmount,
@@ -58,18 +351,311 @@ pub enum Error {
     #[error("Liquidity is zero")]
     InsufficientLiquidity,
 
+    /// Thrown when quoting a trade through a pair with no reserves.
+    #[error("Pair reserves are zero")]
+    ZeroReserves,
+
+    /// Thrown when an intermediate value of the stable swap math does not fit in a [U256].
+    ///
+    /// [U256]: ethers_core::types::U256
+    #[error("Arithmetic overflow")]
+    Overflow,
+
     /// Thrown when the provided path is empty or contains only one address.
     #[error("Path length must be greater than or equal to 2")]
     InvalidPath,
 
+    /// Thrown when the number of fees in a Uniswap V3 path is not one less than the number of
+    /// tokens.
+    #[error("Path must have one fee per pair of tokens")]
+    InvalidPathFees,
+
+    /// Thrown when a Uniswap V3 fee does not fit in a `uint24`.
+    #[error("Fee must fit in 24 bits: {0}")]
+    FeeTooLarge(u32),
+
+    /// Thrown when parsing a Uniswap V3 fee amount from a string which is not one.
+    #[error(
+        "Invalid fee amount {0:?}, expected a percentage like \"0.05%\", a value in hundredths \
+         of bips like \"500\", or one of: lowest, low, medium, high"
+    )]
+    InvalidFeeAmount(String),
+
+    /// Thrown when decoding a Uniswap V3 path whose length is not that of one or more hops.
+    #[error("Encoded path length must be 20 + 23 * n bytes, with n > 0")]
+    InvalidEncodedPath,
+
+    /// Thrown when a tick is out of the range of Uniswap V3 ticks.
+    #[error("Tick must be in range: -887272..=887272: {0}")]
+    InvalidTick(i32),
+
+    /// Thrown when a tick spacing is not positive.
+    #[error("Tick spacing must be positive: {0}")]
+    InvalidTickSpacing(i32),
+
+    /// Thrown when a `sqrtPriceX96` is out of the range of Uniswap V3 prices.
+    #[error("Sqrt price must be in range: MIN_SQRT_RATIO..MAX_SQRT_RATIO: {0}")]
+    InvalidSqrtRatio(ethers_core::types::U256),
+
+    /// Thrown when a price is not finite or out of the range of Uniswap V3 prices.
+    #[error("Price out of range: {0}")]
+    InvalidPrice(f64),
+
+    /// Thrown when simulating a Uniswap V3 swap which reaches a word of the tick bitmap which is
+    /// not loaded.
+    #[error("Tick bitmap word is not loaded: {0}")]
+    TickWordNotLoaded(i16),
+
+    /// Thrown when the native token address is in the middle of a path.
+    #[error("Native token can only be at the start or end of the path")]
+    NativeMidPath,
+
+    /// Thrown when the number of provided reserves does not match the number of pairs in a path.
+    #[error("Reserves length must be equal to the number of pairs in the path")]
+    InvalidReserves,
+
     /// Thrown when the factory provided returns none for pair_code_hash
     #[error("Custom protocol is missing pair_code_hash")]
     NoPairCodeHash,
+
+    /// Thrown when detecting the pair code hash of a factory which has not created any pairs.
+    #[error("Factory has not created any pairs")]
+    NoPairs,
+
+    /// Thrown when no pair code hash matches the address of a factory's pair.
+    #[error("Could not detect the pair code hash")]
+    PairCodeHashNotFound,
+
+    /* ----------------------------------------- Revert ----------------------------------------- */
+
+    /// Thrown when a transaction is executed after its deadline, like `UniswapV2Router: EXPIRED`,
+    /// `Transaction too old` or `TransactionDeadlinePassed()`.
+    #[error("Transaction deadline has passed")]
+    Expired,
+
+    /// Thrown when a swap or a liquidity operation does not meet its minimum output or maximum
+    /// input amounts, like `UniswapV2Router: INSUFFICIENT_OUTPUT_AMOUNT`, `Too little received` or
+    /// `V3TooLittleReceived()`.
+    #[error("Slippage tolerance exceeded")]
+    SlippageExceeded,
+
+    /// Thrown when a token transfer fails, like `TransferHelper: TRANSFER_FROM_FAILED` or `STF`.
+    #[error("Token transfer failed")]
+    TransferFailed,
+
+    /// Thrown when a Universal Router command reverts.
+    #[error("Universal Router command {index} failed: {error}")]
+    CommandFailed {
+        /// The index of the command.
+        index: usize,
+        /// The decoded revert of the command.
+        error: Box<Error>,
+    },
+
+    /// Thrown when a call reverts with a reason which is not decoded into another variant, or
+    /// with an empty reason if it reverts without any data.
+    #[error("Execution reverted: {0:?}")]
+    Reverted(String),
+
+    /// Thrown when a call reverts with a Solidity `Panic(uint256)`, like an arithmetic overflow.
+    #[error("Panic: {}", panic_reason(.0))]
+    Panic(ethers_core::types::U256),
+
+    /// Thrown when a call reverts with a custom error which is not decoded into another variant.
+    #[error("Execution reverted with custom error 0x{}", ethers_core::utils::hex::encode(.selector))]
+    CustomError {
+        /// The selector of the custom error.
+        selector: [u8; 4],
+        /// The revert data, including the selector.
+        data: ethers_core::types::Bytes,
+    },
+}
+
+impl Error {
+    /// Returns [`Error::ArchiveRequired`] if this is the error of a node which has pruned the
+    /// state at `block`, like geth's `missing trie node`, or `self` otherwise.
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    pub(crate) fn archive_required(self, block: ethers_core::types::BlockId) -> Self {
+        let message = match &self {
+            Self::ContractError(message) | Self::MulticallError(message) => message.to_lowercase(),
+            Self::ProviderError(e) => e.to_string().to_lowercase(),
+            _ => return self,
+        };
+        if MISSING_STATE_MESSAGES.iter().any(|pattern| message.contains(pattern)) {
+            Self::ArchiveRequired { block }
+        } else {
+            self
+        }
+    }
+
+    /// Decodes the data of a revert.
+    ///
+    /// Decodes the standard `Error(string)` and `Panic(uint256)` encodings, the reasons of the
+    /// Uniswap V2 and V3 periphery and core contracts, and, with the `universal-router` feature,
+    /// the custom errors of the Universal Router. Other reasons and custom errors are returned as
+    /// [`Error::Reverted`] and [`Error::CustomError`].
+    pub fn from_revert(data: impl AsRef<[u8]>) -> Self {
+        let data = data.as_ref();
+        if let Some(reason) = revert_reason(data) {
+            return Self::from_reason(&reason);
+        }
+        if let Ok(code) = Panic::decode(data) {
+            return Self::Panic(code.0);
+        }
+        #[cfg(feature = "universal-router")]
+        if let Ok(error) = IUniversalRouterErrors::decode(data) {
+            return Self::from_universal_router(error, data);
+        }
+        match data {
+            [a, b, c, d, ..] => {
+                Self::CustomError { selector: [*a, *b, *c, *d], data: data.to_vec().into() }
+            }
+            _ => Self::Reverted(String::new()),
+        }
+    }
+
+    /// Matches a revert reason, which may be prefixed by the name of the contract, like
+    /// `UniswapV2Router: EXPIRED`.
+    fn from_reason(reason: &str) -> Self {
+        let (contract, code) = reason.split_once(": ").unwrap_or(("", reason));
+        match code {
+            "EXPIRED" | "Transaction too old" => Self::Expired,
+            // only the routers check the minimum output amount, the library and the pair revert
+            // with this reason when the output amount is zero
+            "INSUFFICIENT_OUTPUT_AMOUNT" if contract.ends_with("Router") => Self::SlippageExceeded,
+            "INSUFFICIENT_A_AMOUNT"
+            | "INSUFFICIENT_B_AMOUNT"
+            | "EXCESSIVE_INPUT_AMOUNT"
+            | "Too little received"
+            | "Too much requested"
+            | "Price slippage check" => Self::SlippageExceeded,
+            "IDENTICAL_ADDRESSES" => Self::IdenticalAddresses,
+            "ZERO_ADDRESS" => Self::ZeroAddress,
+            "INVALID_PATH" => Self::InvalidPath,
+            "INSUFFICIENT_AMOUNT" => Self::InsufficientAmount,
+            "INSUFFICIENT_INPUT_AMOUNT" | "IIA" => Self::InsufficientInputAmount,
+            "INSUFFICIENT_OUTPUT_AMOUNT" => Self::InsufficientOutputAmount,
+            "INSUFFICIENT_LIQUIDITY"
+            | "INSUFFICIENT_LIQUIDITY_MINTED"
+            | "INSUFFICIENT_LIQUIDITY_BURNED" => Self::InsufficientLiquidity,
+            "TRANSFER_FROM_FAILED"
+            | "TRANSFER_FAILED"
+            | "ETH_TRANSFER_FAILED"
+            | "STF"
+            | "ST"
+            | "STE"
+            | "TF" => Self::TransferFailed,
+            _ if contract.starts_with("TransferHelper") => Self::TransferFailed,
+            _ => Self::Reverted(reason.to_string()),
+        }
+    }
+
+    /// Matches a custom error of the Universal Router.
+    #[cfg(feature = "universal-router")]
+    fn from_universal_router(error: IUniversalRouterErrors, data: &[u8]) -> Self {
+        use IUniversalRouterErrors::*;
+        match error {
+            TransactionDeadlinePassed(_) => Self::Expired,
+            V2TooLittleReceived(_)
+            | V2TooMuchRequested(_)
+            | V3TooLittleReceived(_)
+            | V3TooMuchRequested(_) => Self::SlippageExceeded,
+            InvalidPath(_) | V2InvalidPath(_) => Self::InvalidPath,
+            InvalidReserves(_) => Self::InvalidReserves,
+            ExecutionFailed(e) => Self::CommandFailed {
+                index: e.command_index.low_u64() as usize,
+                error: Box::new(Self::from_revert(e.message)),
+            },
+            _ => Self::CustomError {
+                selector: [data[0], data[1], data[2], data[3]],
+                data: data.to_vec().into(),
+            },
+        }
+    }
+
+    /// Decodes the revert data, or the revert reason, of the message of a JSON-RPC error like
+    /// `(code: 3, message: execution reverted: ..., data: Some(String("0x...")))`.
+    fn from_message(message: &str) -> Option<Self> {
+        let (_, reason) = message.split_once("execution reverted")?;
+        if let Some((_, data)) = message.split_once("data: Some(String(\"0x") {
+            let end = data.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(data.len());
+            if let Ok(data) = ethers_core::utils::hex::decode(&data[..end]) {
+                return Some(Self::from_revert(data));
+            }
+        }
+        let reason = reason.strip_prefix(": ").unwrap_or_default();
+        let reason = reason.split_once(", data: ").map_or(reason, |(reason, _)| reason);
+        Some(if reason.is_empty() {
+            Self::Reverted(String::new())
+        } else {
+            Self::from_reason(reason)
+        })
+    }
+}
+
+/// Substrings of the errors returned by the nodes of the main clients for state they have pruned,
+/// in lowercase.
+#[cfg(any(feature = "v2", feature = "v3"))]
+const MISSING_STATE_MESSAGES: [&str; 5] = [
+    "missing trie node",
+    "historical state",
+    "state is not available",
+    "state not available",
+    "world state unavailable",
+];
+
+/// The Solidity `Panic(uint256)` error.
+#[derive(Clone, Debug, EthError)]
+#[etherror(name = "Panic", abi = "Panic(uint256)")]
+struct Panic(U256);
+
+/// Describes a Solidity panic code.
+fn panic_reason(code: &U256) -> String {
+    let reason = match code.low_u64() {
+        _ if code.bits() > 8 => "unknown",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to an invalid internal function",
+        _ => "unknown",
+    };
+    format!("{reason} ({code:#04x})")
 }
 
 // Workaround for removing generic type in [Error].
 impl<M: Middleware> From<ContractError<M>> for Error {
     fn from(value: ContractError<M>) -> Self {
+        match value {
+            ContractError::ProviderError(e) => e.into(),
+            // the middleware's error is not necessarily `'static`, so it cannot be downcast
+            ContractError::MiddlewareError(e) => {
+                let message = e.to_string();
+                Self::from_message(&message).unwrap_or(Self::ContractError(message))
+            }
+            e => Self::ContractError(e.to_string()),
+        }
+    }
+}
+
+impl From<ProviderError> for Error {
+    fn from(value: ProviderError) -> Self {
+        Self::from_message(&value.to_string()).unwrap_or(Self::ProviderError(value))
+    }
+}
+
+impl From<AbiError> for Error {
+    fn from(value: AbiError) -> Self {
+        Self::ContractError(value.to_string())
+    }
+}
+
+impl From<ethers_core::abi::AbiError> for Error {
+    fn from(value: ethers_core::abi::AbiError) -> Self {
         Self::ContractError(value.to_string())
     }
 }
@@ -85,3 +671,136 @@ impl<M: Middleware> From<MulticallError<M>> for Error {
         Self::MulticallError(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "universal-router")]
+    use crate::contracts::bindings::i_universal_router::{ExecutionFailed, V3TooLittleReceived};
+    use crate::{
+        contracts::bindings::ierc20::{TotalSupplyCall, IERC20},
+        testing::MockDex,
+    };
+    use ethers_core::{
+        abi::AbiEncode,
+        types::{Address, Bytes, TransactionRequest},
+        utils::{
+            __serde_json::{self, json, Value},
+            hex,
+        },
+    };
+    use ethers_providers::HttpClientError;
+    use std::sync::Arc;
+
+    const TOKEN: Address = Address::repeat_byte(0x0a);
+
+    fn revert(reason: &str) -> Vec<u8> {
+        [&[0x08, 0xc3, 0x79, 0xa0][..], &reason.to_string().encode()].concat()
+    }
+
+    /// Returns the error of a contract call which reverts with `data`.
+    async fn mocked_revert(data: impl Into<Bytes>) -> Error {
+        let (provider, dex) = MockDex::mocked();
+        dex.mock_revert(TOKEN, TotalSupplyCall, data);
+        let token = IERC20::new(TOKEN, Arc::new(provider));
+        token.total_supply().call().await.unwrap_err().into()
+    }
+
+    #[tokio::test]
+    async fn test_from_revert() {
+        let cases = [
+            ("UniswapV2Router: EXPIRED", Error::Expired),
+            ("UniswapV2Router: INSUFFICIENT_OUTPUT_AMOUNT", Error::SlippageExceeded),
+            ("UniswapV2Library: INSUFFICIENT_OUTPUT_AMOUNT", Error::InsufficientOutputAmount),
+            ("TransferHelper: TRANSFER_FROM_FAILED", Error::TransferFailed),
+            ("UniswapV2: INSUFFICIENT_LIQUIDITY", Error::InsufficientLiquidity),
+            ("Too little received", Error::SlippageExceeded),
+            ("STF", Error::TransferFailed),
+        ];
+        for (reason, expected) in cases {
+            let error = mocked_revert(revert(reason)).await;
+            assert_eq!(error.to_string(), expected.to_string(), "{reason}");
+        }
+        let error = mocked_revert(revert("UniswapV2: K")).await;
+        assert!(matches!(error, Error::Reverted(reason) if reason == "UniswapV2: K"));
+        let error = mocked_revert(Bytes::default()).await;
+        assert!(matches!(error, Error::Reverted(reason) if reason.is_empty()));
+
+        let panic = [&[0x4e, 0x48, 0x7b, 0x71][..], &U256::from(0x11).encode()].concat();
+        let error = mocked_revert(panic).await;
+        assert!(matches!(error, Error::Panic(code) if code == 0x11.into()));
+        assert_eq!(error.to_string(), "Panic: arithmetic overflow or underflow (0x11)");
+
+        #[cfg(feature = "universal-router")]
+        {
+            let error = mocked_revert(V3TooLittleReceived.encode()).await;
+            assert!(matches!(error, Error::SlippageExceeded));
+            let failed = ExecutionFailed { command_index: 1.into(), message: revert("STF").into() };
+            let error = mocked_revert(failed.encode()).await;
+            assert!(
+                matches!(error, Error::CommandFailed { index: 1, error } if matches!(*error, Error::TransferFailed))
+            );
+        }
+
+        let error = mocked_revert(vec![1, 2, 3, 4, 5]).await;
+        assert!(matches!(error, Error::CustomError { selector: [1, 2, 3, 4], .. }));
+        assert_eq!(error.to_string(), "Execution reverted with custom error 0x01020304");
+    }
+
+    #[test]
+    fn test_from_provider_error() {
+        // `JsonRpcError` is not exported
+        let error = |json: Value| {
+            let error = HttpClientError::JsonRpcError(__serde_json::from_value(json).unwrap());
+            Error::from(ProviderError::from(error))
+        };
+
+        let data = format!("0x{}", hex::encode(revert("UniswapV2Router: EXPIRED")));
+        let message = "execution reverted: UniswapV2Router: EXPIRED";
+        let res = error(json!({ "code": 3, "message": message, "data": data }));
+        assert!(matches!(res, Error::Expired));
+
+        // only the reason
+        let message = "execution reverted: Transaction too old";
+        let res = error(json!({ "code": -32000, "message": message }));
+        assert!(matches!(res, Error::Expired));
+
+        let res = error(json!({ "code": -32000, "message": "nonce too low" }));
+        assert!(matches!(res, Error::ProviderError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_from_mocked_provider_error() {
+        let (provider, dex) = MockDex::mocked();
+        dex.mock_revert_reason(TOKEN, TotalSupplyCall, "UniswapV2Router: EXPIRED");
+        let tx = TransactionRequest::new().to(TOKEN).data(TotalSupplyCall.encode()).into();
+        let res = provider.call(&tx, None).await.unwrap_err();
+        assert!(matches!(Error::from(res), Error::Expired));
+
+        // not a revert: the call is not programmed
+        let tx = TransactionRequest::new().to(Address::zero()).into();
+        let res = provider.call(&tx, None).await.unwrap_err();
+        assert!(matches!(Error::from(res), Error::ProviderError(_)));
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    fn test_archive_required() {
+        let error = |message: &str| {
+            let json = json!({ "code": -32000, "message": message });
+            let error = HttpClientError::JsonRpcError(__serde_json::from_value(json).unwrap());
+            Error::from(ProviderError::from(error))
+        };
+        let block = ethers_core::types::BlockId::from(1_000_000u64);
+
+        let message = "missing trie node 0000000000000000000000000000000000000000000000000000000000000000 (path )";
+        let res = error(message).archive_required(block);
+        assert!(matches!(res, Error::ArchiveRequired { block: b } if b == block));
+        let res = Error::ContractError("World state unavailable".into()).archive_required(block);
+        assert!(matches!(res, Error::ArchiveRequired { .. }));
+
+        let res = error("nonce too low").archive_required(block);
+        assert!(matches!(res, Error::ProviderError(_)));
+        assert!(matches!(Error::Overflow.archive_required(block), Error::Overflow));
+    }
+}
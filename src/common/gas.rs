@@ -0,0 +1,329 @@
+//! Gas and fee estimation.
+
+use super::{blocks::get_block, constants::BPS_U256};
+use crate::errors::{Error, Result};
+use ethers_contract::{builders::ContractCall, ContractError};
+use ethers_core::{
+    abi::Detokenize,
+    types::{
+        transaction::{eip2718::TypedTransaction, eip2930::AccessList},
+        Address, BlockId, BlockNumber, H256, U256,
+    },
+    utils::{
+        eip1559_default_estimator, EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+        EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
+    },
+};
+use ethers_providers::Middleware;
+
+/// The suggested fees of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GasFees {
+    /// The fees of a chain with EIP-1559.
+    Eip1559 {
+        /// The base fee of the latest block.
+        base_fee_per_gas: U256,
+        /// The suggested maximum fee per gas.
+        max_fee_per_gas: U256,
+        /// The suggested maximum priority fee per gas.
+        max_priority_fee_per_gas: U256,
+    },
+    /// The gas price of a chain without EIP-1559, which only supports legacy transactions.
+    Legacy {
+        /// The gas price.
+        gas_price: U256,
+    },
+}
+
+impl GasFees {
+    /// Returns the maximum price paid per unit of gas.
+    #[inline]
+    pub const fn max_fee_per_gas(&self) -> U256 {
+        match *self {
+            Self::Eip1559 { max_fee_per_gas, .. } => max_fee_per_gas,
+            Self::Legacy { gas_price } => gas_price,
+        }
+    }
+}
+
+/// The estimated gas of a call and the suggested fees, returned by [`estimate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GasEstimate {
+    /// The gas estimated by the node.
+    pub gas: U256,
+    /// The estimated gas padded by the safety margin.
+    pub gas_limit: U256,
+    /// The suggested fees.
+    pub fees: GasFees,
+}
+
+impl GasEstimate {
+    /// Returns the maximum cost of the transaction, at the padded gas limit and the maximum fee.
+    #[inline]
+    pub fn max_cost(&self) -> U256 {
+        self.gas_limit * self.fees.max_fee_per_gas()
+    }
+
+    /// Sets the padded gas limit and the fees on `call`, converting it into a legacy transaction
+    /// if the chain does not support EIP-1559.
+    pub fn apply<M: Middleware, D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+    ) -> ContractCall<M, D> {
+        let mut call = call.gas(self.gas_limit);
+        match self.fees {
+            GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
+                match &mut call.tx {
+                    TypedTransaction::Eip1559(tx) => {
+                        tx.max_fee_per_gas = Some(max_fee_per_gas);
+                        tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+                    }
+                    tx => {
+                        tx.set_gas_price(max_fee_per_gas);
+                    }
+                }
+                call
+            }
+            GasFees::Legacy { gas_price } => call.legacy().gas_price(gas_price),
+        }
+    }
+}
+
+/// Returns the suggested fees of the next block.
+///
+/// Chains without EIP-1559, whose blocks do not have a base fee, return the node's gas price.
+/// Otherwise, the fees are estimated with `eth_feeHistory` using ethers'
+/// [default estimator][eip1559_default_estimator].
+pub async fn fees<M: Middleware>(client: &M) -> Result<GasFees> {
+    let block = get_block(client, BlockNumber::Latest).await?;
+    let base_fee_per_gas = match block.base_fee_per_gas {
+        Some(base_fee_per_gas) => base_fee_per_gas,
+        None => {
+            let gas_price =
+                client.get_gas_price().await.map_err(ContractError::<M>::MiddlewareError)?;
+            return Ok(GasFees::Legacy { gas_price });
+        }
+    };
+    let history = client
+        .fee_history(
+            EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+            BlockNumber::Latest,
+            &[EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+        )
+        .await
+        .map_err(ContractError::<M>::MiddlewareError)?;
+    let (max_fee_per_gas, max_priority_fee_per_gas) =
+        eip1559_default_estimator(base_fee_per_gas, history.reward);
+    Ok(GasFees::Eip1559 { base_fee_per_gas, max_fee_per_gas, max_priority_fee_per_gas })
+}
+
+/// Estimates the gas of `call`, padded by `margin_bps` basis points, and the suggested fees. See
+/// [`fees`].
+///
+/// Use [`GasEstimate::apply`] to set them on the call.
+pub async fn estimate<M: Middleware, D: Detokenize>(
+    client: &M,
+    call: &ContractCall<M, D>,
+    margin_bps: u32,
+) -> Result<GasEstimate> {
+    estimate_tx(client, &call.tx, call.block, margin_bps).await
+}
+
+/// Estimates the gas of `tx` at `block`, see [`estimate`].
+async fn estimate_tx<M: Middleware>(
+    client: &M,
+    tx: &TypedTransaction,
+    block: Option<BlockId>,
+    margin_bps: u32,
+) -> Result<GasEstimate> {
+    let gas = client.estimate_gas(tx, block).await.map_err(ContractError::<M>::MiddlewareError)?;
+    let gas_limit = gas * (BPS_U256 + margin_bps) / BPS_U256;
+    let fees = fees(client).await?;
+    Ok(GasEstimate { gas, gas_limit, fees })
+}
+
+/// The access list of a call generated with `eth_createAccessList`, returned by
+/// [`access_list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessListEstimate {
+    /// The accounts and storage slots accessed by the call.
+    pub access_list: AccessList,
+    /// The estimated gas of the call with the access list.
+    pub gas_with: U256,
+    /// The estimated gas of the call without the access list.
+    pub gas_without: U256,
+}
+
+impl AccessListEstimate {
+    /// Returns whether the call is cheaper with the access list.
+    #[inline]
+    pub fn is_cheaper(&self) -> bool {
+        self.gas_with < self.gas_without
+    }
+
+    /// Returns the storage slots of `address` accessed by the call, e.g. to inspect those a token
+    /// accesses when transferred: more than the balances of the sender and of the recipient can
+    /// indicate fee-on-transfer logic.
+    pub fn storage_keys(&self, address: Address) -> Vec<H256> {
+        self.access_list
+            .0
+            .iter()
+            .filter(|item| item.address == address)
+            .flat_map(|item| item.storage_keys.iter().copied())
+            .collect()
+    }
+}
+
+/// Generates the access list of `call` with `eth_createAccessList`, and estimates the gas of the
+/// call with and without it.
+///
+/// Returns None for legacy transactions, which cannot have an access list, and if the provider
+/// does not support `eth_createAccessList`. Reverts are returned as errors.
+pub async fn access_list<M: Middleware, D: Detokenize>(
+    client: &M,
+    call: &ContractCall<M, D>,
+) -> Result<Option<AccessListEstimate>> {
+    if let TypedTransaction::Legacy(_) = call.tx {
+        return Ok(None);
+    }
+    let access_list = match client.create_access_list(&call.tx, None).await {
+        Ok(res) => res.access_list,
+        Err(e) => {
+            return match ContractError::<M>::MiddlewareError(e).into() {
+                // anything other than a revert means that the provider does not support the RPC
+                Error::ContractError(_) | Error::ProviderError(_) => Ok(None),
+                e => Err(e),
+            };
+        }
+    };
+    let gas_without = call.estimate_gas().await?;
+    let mut tx = call.tx.clone();
+    tx.set_access_list(access_list.clone());
+    let gas_with =
+        client.estimate_gas(&tx, None).await.map_err(ContractError::<M>::MiddlewareError)?;
+    Ok(Some(AccessListEstimate { access_list, gas_with, gas_without }))
+}
+
+/// Generates the access list of `call` with [`access_list`], and sets it on the call only if it
+/// makes it cheaper. The generated list is returned in both cases.
+pub async fn with_access_list<M: Middleware, D: Detokenize>(
+    client: &M,
+    mut call: ContractCall<M, D>,
+) -> Result<(ContractCall<M, D>, Option<AccessListEstimate>)> {
+    let estimate = access_list(client, &call).await?;
+    if let Some(estimate) = &estimate {
+        if estimate.is_cheaper() {
+            call.tx.set_access_list(estimate.access_list.clone());
+        }
+    }
+    Ok((call, estimate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::ierc20::IERC20;
+    use ethers_core::types::{
+        transaction::eip2930::{AccessListItem, AccessListWithGasUsed},
+        Block, FeeHistory, TxHash,
+    };
+    use ethers_providers::{MockProvider, Provider};
+    use std::sync::Arc;
+
+    fn push_block(mock: &MockProvider, base_fee_per_gas: Option<U256>) {
+        let block = Block::<TxHash> { base_fee_per_gas, ..Default::default() };
+        mock.push(block).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_estimate() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(Address::repeat_byte(1), client.clone());
+        let call = token.transfer(Address::repeat_byte(2), 100.into());
+
+        // (sorted in reverse, as the mock is LIFO)
+        let history = FeeHistory {
+            base_fee_per_gas: vec![],
+            gas_used_ratio: vec![],
+            oldest_block: 0.into(),
+            reward: vec![vec![U256::exp10(9)]; 10],
+        };
+        mock.push(history).unwrap();
+        push_block(&mock, Some(U256::exp10(10)));
+        mock.push(U256::from(50_000)).unwrap();
+        let res = estimate(client.as_ref(), &call, 2_000).await.unwrap();
+        assert_eq!(res.gas, 50_000.into());
+        assert_eq!(res.gas_limit, 60_000.into());
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            eip1559_default_estimator(U256::exp10(10), vec![vec![U256::exp10(9)]; 10]);
+        let fees = GasFees::Eip1559 {
+            base_fee_per_gas: U256::exp10(10),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        };
+        assert_eq!(res.fees, fees);
+        let tx = res.apply(call.clone()).tx;
+        let tx = tx.as_eip1559_ref().unwrap();
+        assert_eq!(tx.gas, Some(60_000.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(max_fee_per_gas));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(max_priority_fee_per_gas));
+
+        // a chain without EIP-1559
+        mock.push(U256::exp10(9)).unwrap();
+        push_block(&mock, None);
+        mock.push(U256::from(50_000)).unwrap();
+        let res = estimate(client.as_ref(), &call, 0).await.unwrap();
+        assert_eq!(res.gas_limit, 50_000.into());
+        assert_eq!(res.fees, GasFees::Legacy { gas_price: U256::exp10(9) });
+        assert_eq!(res.max_cost(), U256::exp10(9) * 50_000);
+        let tx = res.apply(call).tx;
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+        assert_eq!(tx.gas_price(), Some(U256::exp10(9)));
+    }
+
+    #[tokio::test]
+    async fn test_with_access_list() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(Address::repeat_byte(1), client.clone());
+        let call = token.transfer(Address::repeat_byte(2), 100.into());
+        let item = AccessListItem {
+            address: token.address(),
+            storage_keys: vec![H256::repeat_byte(1), H256::repeat_byte(2)],
+        };
+        let access_list = AccessList(vec![item]);
+        let push_access_list = |gas_with: u64, gas_without: u64| {
+            // (sorted in reverse, as the mock is LIFO)
+            mock.push(U256::from(gas_with)).unwrap();
+            mock.push(U256::from(gas_without)).unwrap();
+            let res =
+                AccessListWithGasUsed { access_list: access_list.clone(), gas_used: 0.into() };
+            mock.push(res).unwrap();
+        };
+
+        push_access_list(49_000, 50_000);
+        let (cheaper, estimate) = with_access_list(client.as_ref(), call.clone()).await.unwrap();
+        let estimate = estimate.unwrap();
+        assert!(estimate.is_cheaper());
+        assert_eq!(cheaper.tx.access_list(), Some(&access_list));
+        assert_eq!(estimate.storage_keys(token.address()).len(), 2);
+        assert!(estimate.storage_keys(Address::zero()).is_empty());
+
+        push_access_list(51_000, 50_000);
+        let (call, estimate) = with_access_list(client.as_ref(), call).await.unwrap();
+        assert!(!estimate.unwrap().is_cheaper());
+        assert_eq!(call.tx.access_list(), Some(&AccessList::default()));
+
+        // a provider which does not support the RPC
+        mock.push(true).unwrap();
+        let (_, estimate) = with_access_list(client.as_ref(), call.clone()).await.unwrap();
+        assert_eq!(estimate, None);
+
+        let (_, estimate) = with_access_list(client.as_ref(), call.legacy()).await.unwrap();
+        assert_eq!(estimate, None);
+    }
+}
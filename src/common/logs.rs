@@ -0,0 +1,223 @@
+//! Log streams which own their client.
+
+use crate::errors::Result;
+use ethers_contract::{EthLogDecode, LogMeta};
+use ethers_core::types::Log;
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+use crate::{errors::Error, RequestPolicy};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_contract::ContractError;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::types::{Filter, U256};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_providers::{FilterKind, Middleware, ProviderError, PubsubClient};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use futures_timer::Delay;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use futures_util::{stream, Stream, StreamExt};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// A decoded event and the metadata of its log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DecodedLog<T> {
+    /// The decoded event.
+    pub event: T,
+
+    /// The metadata of the log. Fields which are not known yet, like the block of a pending log,
+    /// are zero.
+    pub meta: LogMeta,
+
+    /// Whether the log was removed because of a chain reorganization, in which case its effects
+    /// should be reverted.
+    pub removed: bool,
+}
+
+impl<T> DecodedLog<T> {
+    /// Maps the decoded event with `f`, keeping the log's metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> DecodedLog<U> {
+        DecodedLog { event: f(self.event), meta: self.meta, removed: self.removed }
+    }
+}
+
+impl<T: EthLogDecode> DecodedLog<T> {
+    /// Decodes `log`.
+    pub fn decode(log: &Log) -> Result<Self> {
+        let event = T::decode_log(&(log.topics.clone(), log.data.to_vec()).into())?;
+        let meta = LogMeta {
+            address: log.address,
+            block_number: log.block_number.unwrap_or_default(),
+            block_hash: log.block_hash.unwrap_or_default(),
+            transaction_hash: log.transaction_hash.unwrap_or_default(),
+            transaction_index: log.transaction_index.unwrap_or_default(),
+            log_index: log.log_index.unwrap_or_default(),
+        };
+        Ok(Self { event, meta, removed: log.removed.unwrap_or_default() })
+    }
+}
+
+/// Returns the logs matching `filter`, decoded as `T`.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn get_logs<M: Middleware, T: EthLogDecode>(
+    client: &M,
+    filter: &Filter,
+) -> Result<Vec<DecodedLog<T>>> {
+    let logs = client.get_logs(filter).await.map_err(ContractError::<M>::MiddlewareError)?;
+    logs.iter().map(DecodedLog::decode).collect()
+}
+
+/// Queries the logs matching `filter` from `from_block` to `to_block`, inclusive, in ranges of at
+/// most `chunk_size` blocks, calling `f` with the logs and the last block of each range, in
+/// ascending order.
+///
+/// Ranges for which the provider returns too many results are bisected until they succeed, and
+/// throttled queries are retried according to `policy`.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn scan_logs<M: Middleware>(
+    client: &M,
+    filter: &Filter,
+    from_block: u64,
+    to_block: u64,
+    chunk_size: u64,
+    policy: &RequestPolicy,
+    mut f: impl FnMut(Vec<Log>, u64) -> Result<()>,
+) -> Result<()> {
+    let chunk_size = chunk_size.max(1);
+    let mut next_block = from_block;
+    while next_block <= to_block {
+        let chunk_end = next_block.saturating_add(chunk_size - 1).min(to_block);
+
+        // ranges are popped in ascending order
+        let mut ranges = vec![(next_block, chunk_end)];
+        while let Some((start, end)) = ranges.pop() {
+            let filter = filter.clone().from_block(start).to_block(end);
+            let logs = policy.retry(|| async {
+                client
+                    .get_logs(&filter)
+                    .await
+                    .map_err(|e| ContractError::<M>::MiddlewareError(e).into())
+            });
+            let logs = match logs.await {
+                Ok(logs) => logs,
+                Err(e) if start < end && is_too_many_results(&e.to_string()) => {
+                    let mid = start + (end - start) / 2;
+                    ranges.push((mid + 1, end));
+                    ranges.push((start, mid));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            f(logs, end)?;
+        }
+        match chunk_end.checked_add(1) {
+            Some(block) => next_block = block,
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether a provider error's `message` means that a logs query returned too many
+/// results, and should be retried with a smaller block range.
+pub(crate) fn is_too_many_results(message: &str) -> bool {
+    const MESSAGES: [&str; 3] =
+        ["query returned more than", "response size exceeded", "block range is too large"];
+    let message = message.to_lowercase();
+    MESSAGES.iter().any(|m| message.contains(m))
+}
+
+/// Returns a stream of the logs matching `filter`, by installing a filter and polling
+/// `eth_getFilterChanges` at the client's polling interval.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn watch_logs<M: Middleware>(
+    client: Arc<M>,
+    filter: &Filter,
+) -> Result<impl Stream<Item = Result<Log>> + Unpin> {
+    let id = client
+        .new_filter(FilterKind::Logs(filter))
+        .await
+        .map_err(ContractError::<M>::MiddlewareError)?;
+    let interval = client.provider().get_interval();
+
+    let state = (client, VecDeque::new(), false);
+    let stream = stream::unfold(state, move |(client, mut pending, mut polled)| async move {
+        loop {
+            if let Some(log) = pending.pop_front() {
+                return Some((Ok(log), (client, pending, polled)));
+            }
+            if polled {
+                Delay::new(interval).await;
+            }
+            polled = true;
+            match client.get_filter_changes::<_, Log>(id).await {
+                Ok(logs) => pending.extend(logs),
+                Err(e) => {
+                    let e = ContractError::<M>::MiddlewareError(e).into();
+                    return Some((Err(e), (client, pending, polled)));
+                }
+            }
+        }
+    });
+    Ok(Box::pin(stream))
+}
+
+/// Returns a stream of the logs matching `filter`, by subscribing to them with `eth_subscribe`.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn subscribe_logs<M: Middleware>(
+    client: Arc<M>,
+    filter: &Filter,
+) -> Result<LogSubscription<M>>
+where
+    M::Provider: PubsubClient,
+{
+    let provider = client.provider();
+    let id: U256 = provider.request("eth_subscribe", ("logs", filter)).await?;
+    let notifications = provider.as_ref().subscribe(id).map_err(Into::<ProviderError>::into)?;
+    Ok(LogSubscription { client, id, notifications })
+}
+
+/// A stream of logs from an `eth_subscribe` subscription, which is removed when dropped.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) struct LogSubscription<M: Middleware>
+where
+    M::Provider: PubsubClient,
+{
+    client: Arc<M>,
+    id: U256,
+    notifications: <M::Provider as PubsubClient>::NotificationStream,
+}
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+impl<M: Middleware> Stream for LogSubscription<M>
+where
+    M::Provider: PubsubClient,
+{
+    type Item = Result<Log>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.notifications.poll_next_unpin(cx).map(|item| {
+            item.map(|raw| {
+                ethers_core::utils::__serde_json::from_str(raw.get())
+                    .map_err(|e| Error::ProviderError(e.into()))
+            })
+        })
+    }
+}
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+impl<M: Middleware> Drop for LogSubscription<M>
+where
+    M::Provider: PubsubClient,
+{
+    fn drop(&mut self) {
+        // stop receiving notifications, like ethers' `SubscriptionStream`
+        let _ = self.client.provider().as_ref().unsubscribe(self.id);
+    }
+}
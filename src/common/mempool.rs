@@ -0,0 +1,405 @@
+//! Streams of pending swaps from the mempool.
+
+use crate::{
+    contracts::addresses::AddressBook,
+    decode::{classify, DecodedDexCall},
+    errors::{Error, Result},
+};
+use ethers_contract::ContractError;
+use ethers_core::{
+    types::{Address, Transaction, H256, U256},
+    utils::__serde_json::{self, value::RawValue},
+};
+use ethers_providers::{Middleware, ProviderError, PubsubClient};
+use futures_util::{future::BoxFuture, stream::FuturesUnordered, Stream, StreamExt};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// The default maximum number of pending swaps buffered, and of transactions fetched at the same
+/// time, by [PendingSwaps].
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A pending swap through a known router, yielded by [`watch_swaps`].
+///
+/// A transaction which batches several swaps yields one per swap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PendingSwap {
+    /// The hash of the transaction.
+    pub tx_hash: H256,
+    /// The sender of the transaction.
+    pub from: Address,
+    /// The router called by the transaction.
+    pub router: Address,
+    /// The tokens of the swap, from the input to the output token.
+    pub route: Vec<Address>,
+    /// Whether the input amount is the exact one.
+    pub exact_input: bool,
+    /// The exact input amount, or the maximum input amount of an exact output swap.
+    pub amount_in: U256,
+    /// The minimum output amount, or the exact output amount of an exact output swap.
+    pub min_out: U256,
+    /// The gas price of the transaction, or its maximum fee per gas.
+    pub gas_price: Option<U256>,
+}
+
+impl PendingSwap {
+    /// Returns the swaps of `tx` if it is a call to one of the routers of `book`. See
+    /// [`classify`].
+    pub fn from_transaction(tx: &Transaction, book: &AddressBook) -> Vec<Self> {
+        let router = match tx.to {
+            Some(to) => to,
+            None => return Vec::new(),
+        };
+        let mut swaps = Vec::new();
+        if let Some(call) = classify(router, tx.input.clone(), book) {
+            let new = |route, exact_input, amount_in: Option<U256>, min_out| Self {
+                tx_hash: tx.hash,
+                from: tx.from,
+                router,
+                route,
+                exact_input,
+                amount_in: amount_in.unwrap_or(tx.value),
+                min_out,
+                gas_price: tx.gas_price.or(tx.max_fee_per_gas),
+            };
+            flatten(call, &mut |call| match call {
+                DecodedDexCall::SwapExactIn { path, amount_in, min_out, .. } => {
+                    swaps.push(new(path, true, amount_in, min_out))
+                }
+                DecodedDexCall::SwapExactOut { path, amount_out, max_in, .. } => {
+                    swaps.push(new(path, false, max_in, amount_out))
+                }
+                _ => {}
+            });
+        }
+        swaps
+    }
+}
+
+fn flatten(call: DecodedDexCall, f: &mut impl FnMut(DecodedDexCall)) {
+    match call {
+        DecodedDexCall::Batch(calls) => calls.into_iter().for_each(|call| flatten(call, f)),
+        call => f(call),
+    }
+}
+
+/// Returns a stream of the pending swaps through the routers of `book`, including the built-in
+/// ones, by subscribing to pending transactions.
+///
+/// Full transactions are subscribed to if the provider supports it, otherwise each transaction is
+/// fetched from its hash. Transactions which are not calls to a known router are skipped.
+///
+/// See [PendingSwaps] for how the stream behaves under load.
+pub async fn watch_swaps<M: Middleware + 'static>(
+    client: Arc<M>,
+    book: AddressBook,
+) -> Result<PendingSwaps<M>>
+where
+    M::Provider: PubsubClient,
+{
+    let provider = client.provider();
+    let id: U256 = match provider.request("eth_subscribe", ("newPendingTransactions", true)).await {
+        Ok(id) => id,
+        // full transactions are not supported
+        Err(_) => provider.request("eth_subscribe", ["newPendingTransactions"]).await?,
+    };
+    let notifications = provider.as_ref().subscribe(id).map_err(Into::<ProviderError>::into)?;
+    Ok(PendingSwaps {
+        client,
+        book,
+        id,
+        notifications: Some(notifications),
+        fetches: FuturesUnordered::new(),
+        ready: VecDeque::new(),
+        capacity: DEFAULT_CAPACITY,
+        dropped: Default::default(),
+    })
+}
+
+type Fetch = BoxFuture<'static, Result<Option<Transaction>>>;
+
+/// A stream of pending swaps, returned by [`watch_swaps`], which unsubscribes when dropped.
+///
+/// Notifications are drained whenever the stream is polled. If the consumer falls behind, the
+/// transactions which do not fit in the buffer, or which would exceed the number of transactions
+/// being fetched, are dropped and counted in [`dropped`][Self::dropped] instead.
+pub struct PendingSwaps<M: Middleware>
+where
+    M::Provider: PubsubClient,
+{
+    client: Arc<M>,
+    book: AddressBook,
+    id: U256,
+    notifications: Option<<M::Provider as PubsubClient>::NotificationStream>,
+    fetches: FuturesUnordered<Fetch>,
+    ready: VecDeque<Result<PendingSwap>>,
+    capacity: usize,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<M: Middleware> PendingSwaps<M>
+where
+    M::Provider: PubsubClient,
+{
+    /// Sets the maximum number of pending swaps buffered, and of transactions fetched at the same
+    /// time. Defaults to [`DEFAULT_CAPACITY`].
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Returns the number of transactions dropped so far.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the counter of dropped transactions, to be read after moving the stream.
+    pub fn dropped_counter(&self) -> Arc<AtomicU64> {
+        self.dropped.clone()
+    }
+
+    fn push_transaction(&mut self, tx: &Transaction) {
+        let swaps = PendingSwap::from_transaction(tx, &self.book);
+        if self.ready.len() + swaps.len() > self.capacity {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.ready.extend(swaps.into_iter().map(Ok));
+        }
+    }
+}
+
+impl<M: Middleware + 'static> PendingSwaps<M>
+where
+    M::Provider: PubsubClient,
+{
+    fn push_notification(&mut self, raw: &RawValue) {
+        if let Ok(tx) = __serde_json::from_str::<Transaction>(raw.get()) {
+            return self.push_transaction(&tx);
+        }
+        match __serde_json::from_str::<H256>(raw.get()) {
+            Ok(_) if self.fetches.len() >= self.capacity => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(hash) => {
+                let client = self.client.clone();
+                self.fetches.push(Box::pin(async move {
+                    client
+                        .get_transaction(hash)
+                        .await
+                        .map_err(|e| ContractError::<M>::MiddlewareError(e).into())
+                }));
+            }
+            Err(e) => self.ready.push_back(Err(Error::ProviderError(e.into()))),
+        }
+    }
+}
+
+impl<M: Middleware + 'static> Stream for PendingSwaps<M>
+where
+    M::Provider: PubsubClient,
+{
+    type Item = Result<PendingSwap>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while let Some(notifications) = &mut this.notifications {
+            match notifications.poll_next_unpin(cx) {
+                Poll::Ready(Some(raw)) => this.push_notification(&raw),
+                Poll::Ready(None) => this.notifications = None,
+                Poll::Pending => break,
+            }
+        }
+        while let Poll::Ready(Some(res)) = this.fetches.poll_next_unpin(cx) {
+            match res {
+                Ok(Some(tx)) => this.push_transaction(&tx),
+                // already included or replaced
+                Ok(None) => {}
+                Err(e) => this.ready.push_back(Err(e)),
+            }
+        }
+
+        match this.ready.pop_front() {
+            Some(item) => Poll::Ready(Some(item)),
+            None if this.notifications.is_none() && this.fetches.is_empty() => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<M: Middleware> Drop for PendingSwaps<M>
+where
+    M::Provider: PubsubClient,
+{
+    fn drop(&mut self) {
+        // stop receiving notifications, like ethers' `SubscriptionStream`
+        let _ = self.client.provider().as_ref().unsubscribe(self.id);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "universal-router")]
+mod tests {
+    use super::*;
+    use crate::{
+        contracts::{
+            addresses::address,
+            bindings::{
+                i_uniswap_v2_router_02::SwapTokensForExactTokensCall,
+                i_universal_router::ExecuteCall,
+            },
+        },
+        universal_router::CommandBuilder,
+        v3::Path,
+    };
+    use async_trait::async_trait;
+    use ethers_core::{abi::AbiEncode, types::Chain, utils::__serde_json::value::to_raw_value};
+    use ethers_providers::{JsonRpcClient, MockError, MockProvider, Provider};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::{fmt::Debug, sync::Mutex};
+
+    const A: Address = Address::repeat_byte(0x0a);
+    const B: Address = Address::repeat_byte(0x0b);
+    const TO: Address = Address::repeat_byte(0x70);
+
+    /// A mock pubsub transport, which sends all of its notifications at once.
+    #[derive(Debug)]
+    struct MockPubsub {
+        mock: MockProvider,
+        notifications: Mutex<Vec<Box<RawValue>>>,
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for MockPubsub {
+        type Error = MockError;
+
+        async fn request<T, R>(&self, method: &str, params: T) -> Result<R, MockError>
+        where
+            T: Debug + Serialize + Send + Sync,
+            R: DeserializeOwned,
+        {
+            self.mock.request(method, params).await
+        }
+    }
+
+    impl PubsubClient for MockPubsub {
+        type NotificationStream = futures_util::stream::Iter<std::vec::IntoIter<Box<RawValue>>>;
+
+        fn subscribe<T: Into<U256>>(&self, _id: T) -> Result<Self::NotificationStream, MockError> {
+            let notifications = std::mem::take(&mut *self.notifications.lock().unwrap());
+            Ok(futures_util::stream::iter(notifications))
+        }
+
+        fn unsubscribe<T: Into<U256>>(&self, _id: T) -> Result<(), MockError> {
+            Ok(())
+        }
+    }
+
+    fn setup(notifications: &[Box<RawValue>]) -> (Arc<Provider<MockPubsub>>, MockProvider) {
+        let mock = MockProvider::new();
+        let notifications = Mutex::new(notifications.to_vec());
+        let provider = Provider::new(MockPubsub { mock: mock.clone(), notifications });
+        (Arc::new(provider), mock)
+    }
+
+    fn tx(hash: u8, to: Address, input: Vec<u8>) -> Transaction {
+        Transaction {
+            hash: H256::repeat_byte(hash),
+            from: TO,
+            to: Some(to),
+            input: input.into(),
+            value: 5.into(),
+            gas_price: Some(10.into()),
+            ..Default::default()
+        }
+    }
+
+    /// A Universal Router transaction which wraps ETH, then swaps it.
+    fn universal_router_tx(hash: u8) -> Transaction {
+        let path = Path::new(&[A, B], &[500]).unwrap().encode();
+        let mut builder = CommandBuilder::new();
+        builder.wrap_eth(false, TO, 1.into()).v3_swap_exact_in(
+            false,
+            TO,
+            100.into(),
+            90.into(),
+            path,
+            false,
+        );
+        let (commands, inputs) = builder.build();
+        let input = ExecuteCall { commands, inputs }.encode();
+        tx(hash, address("UniversalRouter", Chain::Mainnet), input)
+    }
+
+    #[test]
+    fn test_from_transaction() {
+        let book = AddressBook::new();
+        let swaps = PendingSwap::from_transaction(&universal_router_tx(1), &book);
+        let expected = PendingSwap {
+            tx_hash: H256::repeat_byte(1),
+            from: TO,
+            router: address("UniversalRouter", Chain::Mainnet),
+            route: vec![A, B],
+            exact_input: true,
+            amount_in: 100.into(),
+            min_out: 90.into(),
+            gas_price: Some(10.into()),
+        };
+        assert_eq!(swaps, vec![expected]);
+
+        let call = SwapTokensForExactTokensCall {
+            amount_out: 50.into(),
+            amount_in_max: 60.into(),
+            path: vec![A, B],
+            to: TO,
+            deadline: U256::MAX,
+        };
+        let router = address("UniswapV2Router02", Chain::Mainnet);
+        let swaps = PendingSwap::from_transaction(&tx(2, router, call.clone().encode()), &book);
+        assert_eq!(swaps.len(), 1);
+        assert!(!swaps[0].exact_input);
+        assert_eq!((swaps[0].amount_in, swaps[0].min_out), (60.into(), 50.into()));
+
+        // unknown router
+        assert!(PendingSwap::from_transaction(&tx(3, B, call.encode()), &book).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watch_swaps_full() {
+        let unknown = tx(2, B, vec![0xde, 0xad, 0xbe, 0xef]);
+        let notifications = [universal_router_tx(1), unknown].map(|tx| to_raw_value(&tx).unwrap());
+        let (client, mock) = setup(&notifications);
+        mock.push(U256::one()).unwrap();
+
+        let stream = watch_swaps(client, AddressBook::new()).await.unwrap();
+        let swaps: Vec<_> = stream.collect().await;
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].as_ref().unwrap().tx_hash, H256::repeat_byte(1));
+    }
+
+    #[tokio::test]
+    async fn test_watch_swaps_hashes() {
+        let hashes = [1, 2].map(|hash| to_raw_value(&H256::repeat_byte(hash)).unwrap());
+        let (client, mock) = setup(&hashes);
+        // (sorted in reverse, as the mock is LIFO)
+        mock.push(universal_router_tx(1)).unwrap();
+        mock.push(U256::one()).unwrap();
+        // full transactions are not supported
+        mock.push(true).unwrap();
+
+        // the second hash does not fit while the first transaction is being fetched
+        let stream = watch_swaps(client, AddressBook::new()).await.unwrap().capacity(1);
+        let dropped = stream.dropped_counter();
+        let swaps: Vec<_> = stream.collect().await;
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].as_ref().unwrap().route, vec![A, B]);
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    }
+}
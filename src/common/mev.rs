@@ -0,0 +1,239 @@
+//! Private transaction submission, to protect swaps from being sandwiched in the public mempool.
+
+use crate::errors::{Error, Result};
+use async_trait::async_trait;
+use ethers_core::{
+    types::{Bytes, H256, U64},
+    utils::__serde_json::json,
+};
+use ethers_providers::Middleware;
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+use super::receipt::parse_swap_receipt;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use crate::{utils::require_signer, SwapReceipt};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_contract::{builders::ContractCall, ContractError};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::abi::Detokenize;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use futures_timer::Delay;
+
+/// Submits signed transactions privately, e.g. to a block builder or to a relay.
+#[async_trait]
+pub trait BundleSubmitter: Send + Sync {
+    /// Submits the signed `raw_tx`, which must not be included after `max_block_number`,
+    /// returning its hash.
+    async fn submit(&self, raw_tx: Bytes, max_block_number: U64) -> Result<H256>;
+}
+
+/// A [BundleSubmitter] which uses the Flashbots `eth_sendPrivateTransaction` RPC.
+///
+/// `client` must be connected to the Flashbots relay, and must authenticate its requests with
+/// the `X-Flashbots-Signature` header, which is up to its transport.
+#[derive(Clone, Debug)]
+pub struct Flashbots<M> {
+    client: M,
+}
+
+impl<M> Flashbots<M> {
+    /// Creates a new instance from a client connected to the relay.
+    pub const fn new(client: M) -> Self {
+        Self { client }
+    }
+
+    /// Returns a reference to the relay client.
+    pub const fn client(&self) -> &M {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> BundleSubmitter for Flashbots<M> {
+    async fn submit(&self, raw_tx: Bytes, max_block_number: U64) -> Result<H256> {
+        let params = json!({ "tx": raw_tx, "maxBlockNumber": max_block_number });
+        self.client
+            .provider()
+            .request("eth_sendPrivateTransaction", [params])
+            .await
+            .map_err(Error::from)
+    }
+}
+
+/// Additional options used when submitting a transaction privately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PrivateOptions {
+    /// The number of blocks after the current one in which the transaction can be included.
+    pub max_blocks: u64,
+
+    /// Whether to send the transaction to the public mempool if it was not included privately in
+    /// time.
+    pub fallback_to_public: bool,
+}
+
+impl Default for PrivateOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrivateOptions {
+    /// Creates a new instance with the default options: an expiry of 25 blocks, without
+    /// fallback.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { max_blocks: 25, fallback_to_public: false }
+    }
+
+    /// Sets the number of blocks in which the transaction can be included.
+    #[inline]
+    pub const fn max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Sets whether to fall back to the public mempool.
+    #[inline]
+    pub const fn fallback_to_public(mut self, fallback_to_public: bool) -> Self {
+        self.fallback_to_public = fallback_to_public;
+        self
+    }
+}
+
+/// Simulates `call`, signs it with the client and submits it through `submitter`, then waits for
+/// its inclusion and parses the swap from its receipt.
+///
+/// Returns [`Error::PrivateTransactionExpired`] if the transaction was not included in time,
+/// unless [`fallback_to_public`][PrivateOptions::fallback_to_public] is set, in which case it is
+/// sent to the public mempool.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn execute_swap_private<M: Middleware, D: Detokenize, S: BundleSubmitter>(
+    client: &M,
+    call: ContractCall<M, D>,
+    submitter: &S,
+    options: PrivateOptions,
+) -> Result<SwapReceipt> {
+    let mut tx = call.tx;
+    let from = match tx.from() {
+        Some(from) => *from,
+        None => require_signer(client, "Sending a private swap")?,
+    };
+    tx.set_from(from);
+
+    let map_err = ContractError::<M>::MiddlewareError;
+    // simulate the transaction first, so that reverts are not submitted
+    client.call(&tx, None).await.map_err(map_err)?;
+
+    client.fill_transaction(&mut tx, None).await.map_err(map_err)?;
+    let signature = client.sign_transaction(&tx, from).await.map_err(map_err)?;
+    let raw_tx = tx.rlp_signed(&signature);
+
+    let max_block_number = client.get_block_number().await.map_err(map_err)? + options.max_blocks;
+    let tx_hash = submitter.submit(raw_tx.clone(), max_block_number).await?;
+
+    let interval = client.provider().get_interval();
+    let receipt = loop {
+        if let Some(receipt) = client.get_transaction_receipt(tx_hash).await.map_err(map_err)? {
+            break receipt;
+        }
+        if client.get_block_number().await.map_err(map_err)? > max_block_number {
+            if !options.fallback_to_public {
+                return Err(Error::PrivateTransactionExpired { tx_hash, max_block_number });
+            }
+            let pending = client.send_raw_transaction(raw_tx).await.map_err(map_err)?;
+            break pending.await?.ok_or(Error::TransactionDropped(tx_hash))?;
+        }
+        Delay::new(interval).await;
+    };
+    parse_swap_receipt(client, &tx, &receipt).await
+}
+
+#[cfg(test)]
+#[cfg(feature = "v2")]
+mod tests {
+    use super::*;
+    use crate::{contracts::bindings::i_uniswap_v2_pair::SwapFilter, TxOptions};
+    use ethers_contract::EthEvent;
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Address, Log, TransactionReceipt},
+        utils::keccak256,
+    };
+    use ethers_middleware::SignerMiddleware;
+    use ethers_providers::{MockProvider, Provider};
+    use ethers_signers::LocalWallet;
+    use std::sync::{Arc, Mutex};
+
+    type M = SignerMiddleware<Provider<MockProvider>, LocalWallet>;
+
+    #[derive(Default)]
+    struct MockSubmitter(Mutex<Vec<(Bytes, U64)>>);
+
+    #[async_trait]
+    impl BundleSubmitter for MockSubmitter {
+        async fn submit(&self, raw_tx: Bytes, max_block_number: U64) -> Result<H256> {
+            let tx_hash = keccak256(&raw_tx).into();
+            self.0.lock().unwrap().push((raw_tx, max_block_number));
+            Ok(tx_hash)
+        }
+    }
+
+    fn setup() -> (Arc<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let signer = crate::testing::default_wallet();
+        (Arc::new(SignerMiddleware::new(provider, signer)), mock)
+    }
+
+    fn call(client: Arc<M>) -> ContractCall<M, ()> {
+        let pair = crate::contracts::bindings::i_uniswap_v2_pair::IUniswapV2Pair::new(
+            Address::repeat_byte(1),
+            client,
+        );
+        let options = TxOptions::new()
+            .nonce(0.into())
+            .gas_limit(100_000.into())
+            .max_fee(100.into())
+            .max_priority_fee(1.into());
+        let call = pair.swap(0.into(), 50.into(), Address::repeat_byte(2), Bytes::default());
+        options.apply(call).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_swap_private() {
+        let (client, mock) = setup();
+        let submitter = MockSubmitter::default();
+        let swap_log = Log {
+            topics: vec![SwapFilter::signature(), H256::zero(), H256::zero()],
+            data: abi::encode(&[0u64, 100, 50, 0].map(|a| Token::Uint(a.into()))).into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            status: Some(1.into()),
+            logs: vec![swap_log],
+            ..Default::default()
+        };
+
+        // (sorted in reverse, as the mock is LIFO)
+        mock.push(receipt).unwrap();
+        mock.push(U64::from(100)).unwrap();
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        let options = PrivateOptions::new().max_blocks(10);
+        let res = execute_swap_private(client.as_ref(), call(client.clone()), &submitter, options);
+        let swap = res.await.unwrap();
+        assert_eq!((swap.amount_in(), swap.amount_out()), (100.into(), 50.into()));
+        let submitted = submitter.0.lock().unwrap().pop().unwrap();
+        assert_eq!(submitted.1, 110.into());
+
+        // not included in time
+        mock.push(U64::from(111)).unwrap();
+        mock.push(Option::<TransactionReceipt>::None).unwrap();
+        mock.push(U64::from(100)).unwrap();
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        let res = execute_swap_private(client.as_ref(), call(client.clone()), &submitter, options);
+        let err = res.await.unwrap_err();
+        assert!(
+            matches!(err, Error::PrivateTransactionExpired { max_block_number, .. } if max_block_number == 110.into())
+        );
+    }
+}
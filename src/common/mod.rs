@@ -1,22 +1,123 @@
 mod token;
 pub use token::Erc20;
 
+mod blocks;
+#[cfg(feature = "v2")]
+pub(crate) use blocks::{block_at_timestamp, get_block};
+
 mod call;
-pub use call::{CallExt, CallResult};
+#[cfg(feature = "v2")]
+pub(crate) use call::MethodCall;
+pub use call::{CallBundle, CallExt, CallResult};
+
+mod clock;
+pub use clock::{Clock, SystemClock};
+
+mod deadline;
+pub use deadline::Deadline;
+
+mod logs;
+pub use logs::DecodedLog;
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) use logs::{get_logs, scan_logs, subscribe_logs, watch_logs};
+
+mod multicall;
+#[cfg(any(feature = "v2", feature = "v3", feature = "permit2"))]
+pub(crate) use multicall::call_multi;
+#[cfg(feature = "v3")]
+pub(crate) use multicall::call_multi_chunked;
+pub(crate) use multicall::revert_reason;
+
+mod options;
+pub use options::{LiquidityOptions, PriceLimit, SwapOptions, TxOptions};
+
+mod receipt;
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) use receipt::execute_swap;
+pub use receipt::SwapReceipt;
 
+mod policy;
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub(crate) use policy::Limiter;
+pub use policy::RequestPolicy;
+
+mod quote_cache;
+pub use quote_cache::QuoteCache;
+
+mod recipient;
+pub use recipient::Recipient;
+
+mod route;
+pub use route::Route;
+
+mod simulate;
+#[cfg(feature = "v2")]
+pub(crate) use simulate::simulate;
+pub use simulate::{SimulateOptions, Simulation};
+
+mod slippage;
+pub use slippage::Slippage;
+
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub mod analytics;
+#[cfg(feature = "v2")]
+pub mod arb;
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub mod compare;
 pub mod constants;
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub mod decode;
 pub mod errors;
+pub mod gas;
+#[cfg(all(feature = "addresses", feature = "v2", feature = "v3"))]
+pub mod mempool;
+#[cfg(feature = "mev")]
+pub mod mev;
+#[cfg(all(feature = "addresses", feature = "v2", feature = "v3"))]
+pub mod multichain;
+pub mod permit;
+#[cfg(feature = "permit2")]
+pub mod permit2;
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub mod routing;
+pub mod tokens;
 pub mod utils;
 
-use ethers_core::types::U256;
+pub use permit::Permit;
+
+use crate::{
+    contracts::bindings::ierc20::IERC20,
+    errors::{Error, Result},
+};
+use constants::BPS_U256;
+use ethers_contract::ContractError;
+use ethers_core::types::{Address, U256};
+use ethers_providers::Middleware;
+use std::sync::Arc;
 
 /// A helper enum that wraps a [U256] for determining a swap's input / output amount.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Amount {
     /// Swap exactly {0} `TokenIn` for any amount of `TokenOut`.
     ExactIn(U256),
     /// Swap any amount of `TokenIn` for exactly {0} `TokenOut`.
     ExactOut(U256),
+    /// Swap exactly {0} of the native token for any amount of `TokenOut`.
+    ///
+    /// `TokenIn` must be either the native token or its wrapped version.
+    ExactInNative(U256),
+    /// Swap a percentage of the sender's balance, resolved when building the swap.
+    ///
+    /// If `exact_input` is true, swaps exactly `bps` of the `TokenIn` balance for any amount of
+    /// `TokenOut`, otherwise swaps any amount of `TokenIn` for exactly `bps` of the `TokenOut`
+    /// balance.
+    PercentOfBalance {
+        /// The percentage of the balance, in basis points.
+        bps: u32,
+        /// Whether the percentage is of the input token's balance.
+        exact_input: bool,
+    },
 }
 
 impl Amount {
@@ -31,4 +132,171 @@ impl Amount {
     pub fn exact_out<T: Into<U256>>(amount: T) -> Self {
         Self::ExactOut(amount.into())
     }
+
+    /// Swap exactly `amount`, parsed as a decimal string with `decimals` decimals, `TokenIn` for
+    /// any amount of `TokenOut`. See [`parse_units`][utils::parse_units].
+    #[inline]
+    pub fn exact_in_dec(amount: &str, decimals: u8) -> Result<Self> {
+        utils::parse_units(amount, decimals).map(Self::ExactIn)
+    }
+
+    /// Swap any amount of `TokenIn` for exactly `amount`, parsed as a decimal string with
+    /// `decimals` decimals, `TokenOut`. See [`parse_units`][utils::parse_units].
+    #[inline]
+    pub fn exact_out_dec(amount: &str, decimals: u8) -> Result<Self> {
+        utils::parse_units(amount, decimals).map(Self::ExactOut)
+    }
+
+    /// Swap exactly `amount` of the native token for any amount of `TokenOut`.
+    #[inline]
+    pub fn exact_in_native<T: Into<U256>>(amount: T) -> Self {
+        Self::ExactInNative(amount.into())
+    }
+
+    /// Swap exactly `bps` of the sender's `TokenIn` balance for any amount of `TokenOut`.
+    #[inline]
+    pub const fn percent_of_balance(bps: u32) -> Self {
+        Self::PercentOfBalance { bps, exact_input: true }
+    }
+
+    /// Returns whether the input amount is the exact one.
+    #[inline]
+    pub const fn is_exact_in(&self) -> bool {
+        match self {
+            Self::ExactIn(_) | Self::ExactInNative(_) => true,
+            Self::ExactOut(_) => false,
+            Self::PercentOfBalance { exact_input, .. } => *exact_input,
+        }
+    }
+
+    /// Resolves the amount into either [ExactIn][Self::ExactIn] or [ExactOut][Self::ExactOut].
+    ///
+    /// [PercentOfBalance][Self::PercentOfBalance] queries the balance of the client's default
+    /// sender of the first or last token in `path`, which can be the
+    /// [native token][constants::NATIVE_ADDRESS].
+    ///
+    /// Returns [`Error::InvalidPercentage`] if the percentage is greater than 100%,
+    /// [`Error::SignerRequired`] for a percentage of balance if the client is read-only, and
+    /// [`Error::ZeroAmount`] if the resulting amount is zero.
+    pub async fn resolve<M: Middleware>(self, client: Arc<M>, path: &[Address]) -> Result<Self> {
+        let amount = match self {
+            Self::ExactIn(amount) | Self::ExactInNative(amount) => Self::ExactIn(amount),
+            Self::ExactOut(amount) => Self::ExactOut(amount),
+            Self::PercentOfBalance { bps, exact_input } => {
+                if bps > 10_000 {
+                    return Err(Error::InvalidPercentage);
+                }
+                let token = if exact_input { path.first() } else { path.last() };
+                let token = *token.ok_or(Error::InvalidPath)?;
+                let owner =
+                    utils::require_signer(client.as_ref(), "A percentage of balance amount")?;
+                let balance = if utils::is_native(&token) {
+                    client
+                        .get_balance(owner, None)
+                        .await
+                        .map_err(ContractError::<M>::MiddlewareError)?
+                } else {
+                    IERC20::new(token, client).balance_of(owner).call().await?
+                };
+                let amount = balance * bps / BPS_U256;
+                if exact_input {
+                    Self::ExactIn(amount)
+                } else {
+                    Self::ExactOut(amount)
+                }
+            }
+        };
+        match amount {
+            Self::ExactIn(amount) | Self::ExactOut(amount) if amount.is_zero() => {
+                Err(Error::ZeroAmount)
+            }
+            amount => Ok(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_middleware::SignerMiddleware;
+    use ethers_providers::{MockProvider, Provider};
+    use ethers_signers::LocalWallet;
+
+    type M = SignerMiddleware<Provider<MockProvider>, LocalWallet>;
+
+    const TOKEN: Address = Address::repeat_byte(0x0a);
+
+    fn setup() -> (Arc<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let signer = crate::testing::default_wallet();
+        (Arc::new(SignerMiddleware::new(provider, signer)), mock)
+    }
+
+    fn push_balance(mock: &MockProvider, balance: u64) {
+        let data = abi::encode(&[Token::Uint(balance.into())]);
+        mock.push::<Bytes, _>(Bytes::from(data)).unwrap();
+    }
+
+    #[test]
+    fn test_dec() {
+        assert_eq!(Amount::exact_in_dec("1.5", 6).unwrap(), Amount::exact_in(1_500_000));
+        assert_eq!(Amount::exact_out_dec("2", 0).unwrap(), Amount::exact_out(2));
+        assert!(Amount::exact_in_dec("1.5", 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let (client, mock) = setup();
+        let path = [TOKEN, constants::NATIVE_ADDRESS];
+
+        let amount = Amount::exact_in_native(100);
+        assert_eq!(amount.resolve(client.clone(), &path).await.unwrap(), Amount::exact_in(100));
+
+        push_balance(&mock, 1_000);
+        let amount = Amount::percent_of_balance(2_500);
+        assert_eq!(amount.resolve(client.clone(), &path).await.unwrap(), Amount::exact_in(250));
+
+        mock.push(U256::from(1_000)).unwrap();
+        let amount = Amount::PercentOfBalance { bps: 10_000, exact_input: false };
+        assert_eq!(amount.resolve(client.clone(), &path).await.unwrap(), Amount::exact_out(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_invalid() {
+        let (client, mock) = setup();
+        let path = [TOKEN, constants::NATIVE_ADDRESS];
+
+        let amount = Amount::percent_of_balance(10_001);
+        let res = amount.resolve(client.clone(), &path).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidPercentage));
+
+        push_balance(&mock, 1);
+        let res = Amount::percent_of_balance(5_000).resolve(client.clone(), &path).await;
+        assert!(matches!(res.unwrap_err(), Error::ZeroAmount));
+
+        let res = Amount::exact_out(0).resolve(client, &path).await;
+        assert!(matches!(res.unwrap_err(), Error::ZeroAmount));
+
+        // a read-only client has no balance
+        let (provider, _mock) = Provider::mocked();
+        let res = Amount::percent_of_balance(5_000).resolve(Arc::new(provider), &path).await;
+        assert!(matches!(res.unwrap_err(), Error::SignerRequired(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let amount = Amount::exact_in(1_000);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, r#"{"ExactIn":"0x3e8"}"#);
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+
+        let amount = Amount::PercentOfBalance { bps: 5_000, exact_input: true };
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+    }
 }
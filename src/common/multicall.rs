@@ -0,0 +1,586 @@
+use crate::errors::{Error, Result};
+use ethers_contract::{
+    builders::ContractCall, ContractError, MulticallContract, MULTICALL_ADDRESS,
+    MULTICALL_SUPPORTED_CHAIN_IDS,
+};
+use ethers_core::{
+    abi::{self, AbiDecode, Function, ParamType, Token, Tokenizable},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Block, BlockId, BlockNumber, Bytes, Chain,
+        NameOrAddress, H256, U64,
+    },
+};
+use ethers_providers::Middleware;
+use std::{fmt, sync::Arc};
+
+#[cfg(feature = "v3")]
+use crate::RequestPolicy;
+#[cfg(feature = "v3")]
+use futures_util::{stream, StreamExt, TryStreamExt};
+
+/// The canonical address of [Multicall3](https://github.com/mds1/multicall), which is the same on
+/// every chain it is deployed on.
+pub const MULTICALL3_ADDRESS: Address = MULTICALL_ADDRESS;
+
+/// The default maximum size, in bytes, of the calldata of a single `aggregate3` request.
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 128 * 1024;
+
+/// `aggregate3((address,bool,bytes)[])`
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+/// `getBlockNumber()`
+const GET_BLOCK_NUMBER_SELECTOR: [u8; 4] = [0x42, 0xcb, 0xb1, 0x5c];
+/// `getCurrentBlockTimestamp()`
+const GET_CURRENT_BLOCK_TIMESTAMP_SELECTOR: [u8; 4] = [0x0f, 0x28, 0xc9, 0x7d];
+
+/// The result of a single call of a [Multicall]: its decoded output, or the data it returned if it
+/// reverted or its output could not be decoded.
+pub type CallOutput = std::result::Result<Token, Bytes>;
+
+/// A call added to a [Multicall].
+#[derive(Clone, Debug)]
+enum Call {
+    Contract { tx: Box<TypedTransaction>, function: Function, allow_failure: bool },
+    BlockNumber,
+    BlockTimestamp,
+}
+
+impl Call {
+    fn contract<M, D>(call: ContractCall<M, D>, allow_failure: bool) -> Self {
+        Self::Contract { tx: Box::new(call.tx), function: call.function, allow_failure }
+    }
+}
+
+/// Aggregates contract calls into [Multicall3] `aggregate3` requests.
+///
+/// Calls are added from any [ContractCall] and are decoded with its ABI, so that each result can
+/// be converted into the call's output type with [`Tokenizable::from_token`]. Calls which are
+/// allowed to fail do not revert the whole request, like with `tryAggregate`.
+///
+/// Requests whose calldata would exceed the [maximum payload size][Self::max_payload_size] are
+/// split into several requests, all pinned to the same block. If Multicall3 is not deployed on the
+/// chain, the calls are made sequentially instead, also pinned to the same block.
+///
+/// [Multicall3]: https://github.com/mds1/multicall
+pub struct Multicall<M> {
+    client: Arc<M>,
+    address: Option<Address>,
+    calls: Vec<Call>,
+    max_payload_size: usize,
+    block: Option<BlockId>,
+}
+
+impl<M> Clone for Multicall<M> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            address: self.address,
+            calls: self.calls.clone(),
+            max_payload_size: self.max_payload_size,
+            block: self.block,
+        }
+    }
+}
+
+impl<M> fmt::Debug for Multicall<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multicall")
+            .field("address", &self.address)
+            .field("calls", &self.calls)
+            .field("max_payload_size", &self.max_payload_size)
+            .field("block", &self.block)
+            .finish()
+    }
+}
+
+impl<M: Middleware> Multicall<M> {
+    /// Creates a new instance with the Multicall3 address of `chain` from the addressbook, or
+    /// [`MULTICALL3_ADDRESS`] if the client's chain is known to have it deployed, otherwise the
+    /// calls will be made sequentially.
+    ///
+    /// Queries the client's chain ID if Multicall3 is not in the addressbook for `chain`.
+    #[cfg_attr(not(feature = "addresses"), allow(unused_variables))]
+    pub async fn new(client: Arc<M>, chain: Option<Chain>) -> Result<Self> {
+        #[cfg(feature = "addresses")]
+        let address =
+            chain.and_then(|chain| crate::contracts::addresses::try_address("Multicall3", chain));
+        #[cfg(not(feature = "addresses"))]
+        let address = None;
+
+        let address = match address {
+            Some(address) => Some(address),
+            None => {
+                let chain_id =
+                    client.get_chainid().await.map_err(ContractError::<M>::MiddlewareError)?;
+                MULTICALL_SUPPORTED_CHAIN_IDS.contains(&chain_id).then_some(MULTICALL3_ADDRESS)
+            }
+        };
+        Ok(Self::new_with_address(client, address))
+    }
+
+    /// Creates a new instance with the provided Multicall3 address, or which makes the calls
+    /// sequentially if it is None.
+    pub fn new_with_address(client: Arc<M>, address: Option<Address>) -> Self {
+        Self {
+            client,
+            address,
+            calls: Vec::new(),
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+            block: None,
+        }
+    }
+
+    /// Returns the Multicall3 address, or None if the calls are made sequentially.
+    pub fn address(&self) -> Option<Address> {
+        self.address
+    }
+
+    /// Returns the maximum size, in bytes, of the calldata of a single request.
+    pub fn max_payload_size(&self) -> usize {
+        self.max_payload_size
+    }
+
+    /// Sets the maximum size, in bytes, of the calldata of a single request. Defaults to
+    /// [`DEFAULT_MAX_PAYLOAD_SIZE`].
+    pub fn set_max_payload_size(&mut self, max_payload_size: usize) -> &mut Self {
+        self.max_payload_size = max_payload_size;
+        self
+    }
+
+    /// Sets the block at which the calls are made. Defaults to the latest block.
+    pub fn block(mut self, block: impl Into<BlockId>) -> Self {
+        self.block = Some(block.into());
+        self
+    }
+
+    /// Returns the number of calls added.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether no call has been added.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Adds `call`, whose result is decoded with its ABI.
+    ///
+    /// If `allow_failure` is false, the whole request reverts if the call reverts, otherwise its
+    /// result is the data it reverted with.
+    pub fn add_call<D>(&mut self, call: ContractCall<M, D>, allow_failure: bool) -> &mut Self {
+        self.calls.push(Call::contract(call, allow_failure));
+        self
+    }
+
+    /// Adds a call returning the number of the block, as a `uint256`.
+    pub fn add_get_block_number(&mut self) -> &mut Self {
+        self.calls.push(Call::BlockNumber);
+        self
+    }
+
+    /// Adds a call returning the timestamp of the block, as a `uint256`.
+    pub fn add_get_current_block_timestamp(&mut self) -> &mut Self {
+        self.calls.push(Call::BlockTimestamp);
+        self
+    }
+
+    /// Removes all the calls.
+    pub fn clear_calls(&mut self) -> &mut Self {
+        self.calls.clear();
+        self
+    }
+
+    /// Makes the calls, returning their results, in order, decoded with their ABI.
+    ///
+    /// The result of a call is its returned data if it reverted, or if its output could not be
+    /// decoded, as when calling an address without code. Returns [`Error::MulticallCallFailed`] if
+    /// this happens to a call which is not allowed to fail.
+    ///
+    /// Note: when the calls are made sequentially, the data a call reverted with is not available,
+    /// and is empty.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(multicall = ?self.address, calls = self.calls.len())
+        )
+    )]
+    pub async fn call_raw(&self) -> Result<Vec<CallOutput>> {
+        if self.calls.is_empty() {
+            return Ok(vec![]);
+        }
+        match self.address {
+            Some(address) => self.call_aggregate(address).await,
+            None => self.call_sequential().await,
+        }
+    }
+
+    /// Makes the calls and decodes their outputs as `D`.
+    ///
+    /// Returns [`Error::MulticallCallFailed`] if any of the calls failed.
+    pub async fn call<D: Tokenizable>(&self) -> Result<Vec<D>> {
+        decode_outputs(self.call_raw().await?)
+    }
+
+    /// Makes the calls and decodes their outputs as `D`, or None for the calls which failed.
+    pub async fn try_call<D: Tokenizable>(&self) -> Result<Vec<Option<D>>> {
+        self.call_raw()
+            .await?
+            .into_iter()
+            .map(|output| match output {
+                Ok(token) => Ok(Some(D::from_token(token)?)),
+                Err(_) => Ok(None),
+            })
+            .collect()
+    }
+
+    /// Makes the calls with `aggregate3` requests of at most `max_payload_size` bytes each.
+    async fn call_aggregate(&self, address: Address) -> Result<Vec<CallOutput>> {
+        let chunks = aggregate_chunks(&self.calls, address, self.max_payload_size)?;
+
+        // pin all the requests to the same block
+        let block = match (self.block, chunks.len()) {
+            (Some(block), _) => Some(block),
+            (None, 1) => None,
+            (None, _) => Some(self.block_number().await?.into()),
+        };
+
+        let multicall = MulticallContract::new(address, self.client.clone());
+        let mut results = Vec::with_capacity(self.calls.len());
+        for chunk in chunks {
+            debug!(calls = chunk.len(), ?block, "sending an aggregate3 request");
+            let mut call = multicall
+                .method_hash::<_, Vec<(bool, Bytes)>>(AGGREGATE3_SELECTOR, chunk)
+                .map_err(ContractError::<M>::from)?;
+            call.block = block;
+            results.extend(call.call().await?);
+        }
+
+        aggregate_outputs(&self.calls, results)
+    }
+
+    /// Makes the calls one by one, pinned to the same block.
+    async fn call_sequential(&self) -> Result<Vec<CallOutput>> {
+        let block = match (self.block, self.calls.len()) {
+            (Some(block), _) => Some(block),
+            (None, 1) => None,
+            (None, _) => Some(self.block_number().await?.into()),
+        };
+        debug!(calls = self.calls.len(), ?block, "making the calls sequentially");
+
+        let mut results = Vec::with_capacity(self.calls.len());
+        for (index, call) in self.calls.iter().enumerate() {
+            let data = match call {
+                Call::Contract { tx, allow_failure, .. } => {
+                    match self.client.call(tx, block).await {
+                        Ok(data) => data,
+                        Err(_) if *allow_failure => {
+                            results.push(Err(Bytes::default()));
+                            continue;
+                        }
+                        Err(e) => return Err(ContractError::<M>::MiddlewareError(e).into()),
+                    }
+                }
+                Call::BlockNumber => {
+                    let number = match block {
+                        Some(BlockId::Number(BlockNumber::Number(number))) => number,
+                        _ => self.get_block(block).await?.number.unwrap_or_default(),
+                    };
+                    Bytes::from(abi::encode(&[Token::Uint(number.as_u64().into())]))
+                }
+                Call::BlockTimestamp => {
+                    let timestamp = self.get_block(block).await?.timestamp;
+                    Bytes::from(abi::encode(&[Token::Uint(timestamp)]))
+                }
+            };
+            match decode_output(call, &data) {
+                Some(token) => results.push(Ok(token)),
+                None => results.push(failed(call, index, data)?),
+            }
+        }
+        Ok(results)
+    }
+
+    async fn block_number(&self) -> Result<U64> {
+        Ok(self.client.get_block_number().await.map_err(ContractError::<M>::MiddlewareError)?)
+    }
+
+    async fn get_block(&self, block: Option<BlockId>) -> Result<Block<H256>> {
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+        self.client
+            .get_block(block)
+            .await
+            .map_err(ContractError::<M>::MiddlewareError)?
+            .ok_or_else(|| Error::MulticallError(format!("block {block:?} not found")))
+    }
+}
+
+/// Splits `calls` into the arguments of `aggregate3` requests to the Multicall3 at `address`, of
+/// at most `max_payload_size` bytes each.
+fn aggregate_chunks(
+    calls: &[Call],
+    address: Address,
+    max_payload_size: usize,
+) -> Result<Vec<Vec<(Address, bool, Bytes)>>> {
+    let mut chunks = vec![];
+    let mut chunk = vec![];
+    let mut size = 0;
+    for (index, call) in calls.iter().enumerate() {
+        let (target, allow_failure, data) = match call {
+            Call::Contract { tx, allow_failure, .. } => {
+                let target = match tx.to() {
+                    Some(NameOrAddress::Address(target)) => *target,
+                    _ => {
+                        let reason = format!("call {index} does not have a target address");
+                        return Err(Error::MulticallError(reason));
+                    }
+                };
+                (target, *allow_failure, tx.data().cloned().unwrap_or_default())
+            }
+            Call::BlockNumber => (address, false, Bytes::from(GET_BLOCK_NUMBER_SELECTOR)),
+            Call::BlockTimestamp => {
+                (address, false, Bytes::from(GET_CURRENT_BLOCK_TIMESTAMP_SELECTOR))
+            }
+        };
+        // selector, offset and length, then offset, target, flag, offset, length and data
+        let call_size = 160 + (data.len() + 31) / 32 * 32;
+        if !chunk.is_empty() && 68 + size + call_size > max_payload_size {
+            chunks.push(std::mem::take(&mut chunk));
+            size = 0;
+        }
+        size += call_size;
+        chunk.push((target, allow_failure, data));
+    }
+    chunks.push(chunk);
+    Ok(chunks)
+}
+
+/// Decodes the `results` of the `aggregate3` requests of `calls`.
+fn aggregate_outputs(calls: &[Call], results: Vec<(bool, Bytes)>) -> Result<Vec<CallOutput>> {
+    if results.len() != calls.len() {
+        return Err(Error::MulticallError("missing result".into()));
+    }
+    calls
+        .iter()
+        .zip(results)
+        .enumerate()
+        .map(|(index, (call, (success, data)))| {
+            match if success { decode_output(call, &data) } else { None } {
+                Some(token) => Ok(Ok(token)),
+                None => failed(call, index, data),
+            }
+        })
+        .collect()
+}
+
+/// Converts `outputs` into `D`. Returns [`Error::MulticallCallFailed`] for the first failed call.
+fn decode_outputs<D: Tokenizable>(outputs: Vec<CallOutput>) -> Result<Vec<D>> {
+    outputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, output)| match output {
+            Ok(token) => Ok(D::from_token(token)?),
+            Err(data) => Err(Error::MulticallCallFailed { index, data }),
+        })
+        .collect()
+}
+
+/// Returns the result of `call`, at `index`, which failed with `data`, or an error if it is not
+/// allowed to fail.
+fn failed(call: &Call, index: usize, data: Bytes) -> Result<CallOutput> {
+    match call {
+        Call::Contract { allow_failure: true, .. } => Ok(Err(data)),
+        _ => Err(Error::MulticallCallFailed { index, data }),
+    }
+}
+
+/// Decodes the output of `call`, returning None if `data` is not a valid output.
+fn decode_output(call: &Call, data: &[u8]) -> Option<Token> {
+    let mut tokens = match call {
+        Call::Contract { function, .. } => function.decode_output(data).ok()?,
+        Call::BlockNumber | Call::BlockTimestamp => {
+            abi::decode(&[ParamType::Uint(256)], data).ok()?
+        }
+    };
+    Some(match tokens.len() {
+        0 => Token::Tuple(vec![]),
+        1 => tokens.remove(0),
+        _ => Token::Tuple(tokens),
+    })
+}
+
+/// Decodes the reason of a revert with `Error(string)`.
+pub(crate) fn revert_reason(data: &[u8]) -> Option<String> {
+    match data {
+        [0x08, 0xc3, 0x79, 0xa0, data @ ..] => String::decode(data).ok(),
+        _ => None,
+    }
+}
+
+/// Aggregates `calls` into a single [Multicall] request. Falls back to sequential calls, pinned to
+/// the latest block, if Multicall3 is not deployed on `chain`.
+#[cfg(any(feature = "v2", feature = "v3", feature = "permit2"))]
+pub(crate) async fn call_multi<M: Middleware, D: Tokenizable>(
+    client: Arc<M>,
+    chain: Option<Chain>,
+    calls: Vec<ContractCall<M, D>>,
+) -> Result<Vec<D>> {
+    let calls = calls.into_iter().map(|call| Call::contract(call, false)).collect();
+    decode_outputs(call_multi_raw(client, chain, calls).await?)
+}
+
+#[cfg(any(feature = "v2", feature = "v3", feature = "permit2"))]
+async fn call_multi_raw<M: Middleware>(
+    client: Arc<M>,
+    chain: Option<Chain>,
+    calls: Vec<Call>,
+) -> Result<Vec<CallOutput>> {
+    let mut multicall = match calls.len() {
+        0 => return Ok(vec![]),
+        // avoid multicall for only 1 call
+        1 => Multicall::new_with_address(client, None),
+        _ => Multicall::new(client, chain).await?,
+    };
+    multicall.calls = calls;
+    multicall.call_raw().await
+}
+
+/// Aggregates `calls` into [Multicall] requests of at most `chunk_size` calls each, with at most
+/// [`max_concurrency`][RequestPolicy::max_concurrency] requests in flight and throttled requests
+/// retried according to `policy`, returning the results in order. See [`call_multi`].
+///
+/// Note: the results of different requests may be read at different blocks.
+#[cfg(feature = "v3")]
+pub(crate) async fn call_multi_chunked<M: Middleware, D: Tokenizable>(
+    client: Arc<M>,
+    chain: Option<Chain>,
+    calls: Vec<ContractCall<M, D>>,
+    chunk_size: usize,
+    policy: RequestPolicy,
+) -> Result<Vec<D>> {
+    let mut calls = calls.into_iter();
+    let chunks = std::iter::from_fn(|| {
+        let chunk: Vec<_> = calls.by_ref().take(chunk_size.max(1)).collect();
+        (!chunk.is_empty()).then_some(chunk)
+    });
+    let results: Vec<Vec<D>> = stream::iter(chunks)
+        .map(|calls| {
+            let client = client.clone();
+            async move { policy.retry(|| call_multi(client.clone(), chain, calls.clone())).await }
+        })
+        .buffered(policy.concurrency())
+        .try_collect()
+        .await?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::ierc20::IERC20;
+    use ethers_core::types::U256;
+    use ethers_providers::{MockProvider, Provider};
+
+    const TOKEN: Address = Address::repeat_byte(1);
+
+    fn push_aggregate(mock: &MockProvider, results: &[(bool, Vec<u8>)]) {
+        let results = results
+            .iter()
+            .map(|(success, data)| {
+                Token::Tuple(vec![Token::Bool(*success), Token::Bytes(data.clone())])
+            })
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+    }
+
+    fn uint(value: u64) -> Vec<u8> {
+        abi::encode(&[Token::Uint(value.into())])
+    }
+
+    #[tokio::test]
+    async fn test_call_raw() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(TOKEN, client.clone());
+
+        // (sorted in reverse, as the mock is LIFO)
+        let revert =
+            [&[0x08, 0xc3, 0x79, 0xa0][..], &abi::encode(&[Token::String("no".into())])].concat();
+        push_aggregate(&mock, &[(true, uint(18)), (false, revert.clone()), (true, vec![])]);
+        mock.push(U256::one()).unwrap();
+
+        let mut multicall = Multicall::new(client, None).await.unwrap();
+        assert_eq!(multicall.address(), Some(MULTICALL3_ADDRESS));
+        multicall
+            .add_call(token.decimals(), false)
+            .add_call(token.total_supply(), true)
+            .add_call(token.name(), true);
+        let results = multicall.call_raw().await.unwrap();
+        assert_eq!(results[0], Ok(Token::Uint(18.into())));
+        assert_eq!(results[1], Err(Bytes::from(revert.clone())));
+        assert_eq!(revert_reason(&revert).as_deref(), Some("no"));
+        // an address without code returns no data, which cannot be decoded
+        assert_eq!(results[2], Err(Bytes::default()));
+
+        push_aggregate(&mock, &[(true, uint(18)), (false, revert), (true, vec![])]);
+        let results = multicall.try_call::<Token>().await.unwrap();
+        assert_eq!(results, vec![Some(Token::Uint(18.into())), None, None]);
+
+        push_aggregate(&mock, &[(true, vec![])]);
+        multicall.clear_calls().add_call(token.decimals(), false);
+        let res = multicall.call::<u8>().await;
+        assert!(matches!(res, Err(Error::MulticallCallFailed { index: 0, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_chunks() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(TOKEN, client.clone());
+
+        let mut multicall = Multicall::new_with_address(client, Some(MULTICALL3_ADDRESS));
+        multicall.add_call(token.decimals(), false).add_call(token.total_supply(), false);
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 3. the second chunk
+        push_aggregate(&mock, &[(true, uint(1000))]);
+        // 2. the first chunk
+        push_aggregate(&mock, &[(true, uint(6))]);
+        // 1. the block number the chunks are pinned to
+        mock.push(U256::from(100)).unwrap();
+
+        // room for the header and a single call, whose calldata fits in a word
+        multicall.set_max_payload_size(68 + 192);
+        let results = multicall.call::<U256>().await.unwrap();
+        assert_eq!(results, vec![U256::from(6), U256::from(1000)]);
+    }
+
+    #[tokio::test]
+    async fn test_sequential() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(TOKEN, client.clone());
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 4. decimals of an address without code
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        // 3. decimals
+        mock.push::<Bytes, _>(Bytes::from(uint(6))).unwrap();
+        // 2. the block number the calls are pinned to
+        mock.push(U256::from(100)).unwrap();
+        // 1. a chain without Multicall3
+        mock.push(U256::from(0xdead_beef_u64)).unwrap();
+
+        let mut multicall = Multicall::new(client, None).await.unwrap();
+        assert_eq!(multicall.address(), None);
+        multicall
+            .add_call(token.decimals(), false)
+            .add_call(token.decimals(), true)
+            .add_get_block_number();
+        let results = multicall.call_raw().await.unwrap();
+        assert_eq!(
+            results,
+            vec![Ok(Token::Uint(6.into())), Err(Bytes::default()), Ok(Token::Uint(100.into()))]
+        );
+    }
+}
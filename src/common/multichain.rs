@@ -0,0 +1,281 @@
+//! Registry of the protocols of multiple chains, for quoting the same trade on each of them.
+
+use crate::{
+    compare::{best_quote, CompareOptions, ComparedQuote, QuoteComparison},
+    errors::Result,
+    tokens::TokenList,
+    utils::parse_units,
+    Amount, Protocol, ProtocolType,
+};
+use ethers_core::types::{Address, Chain};
+use ethers_providers::Middleware;
+use futures_util::future;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// The protocols of multiple chains, each with its own client, and the token list used to resolve
+/// tokens by symbol on each chain.
+pub struct MultiChain<M> {
+    /// The protocols of each chain.
+    protocols: HashMap<Chain, Vec<Protocol<M>>>,
+
+    /// The token list used for resolving symbols.
+    pub token_list: TokenList,
+}
+
+impl<M> Clone for MultiChain<M> {
+    fn clone(&self) -> Self {
+        Self { protocols: self.protocols.clone(), token_list: self.token_list.clone() }
+    }
+}
+
+impl<M> fmt::Debug for MultiChain<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiChain")
+            .field("protocols", &self.protocols)
+            .field("token_list", &self.token_list.name)
+            .finish()
+    }
+}
+
+/// The quotes of a trade on a single chain, as returned by [`MultiChain::quote_everywhere`].
+#[derive(Debug)]
+pub struct ChainQuote {
+    /// The chain.
+    pub chain: Chain,
+    /// The addresses of the input and output tokens on the chain, if they were resolved.
+    pub tokens: Option<(Address, Address)>,
+    /// The quotes of the chain's protocols, or the error which prevented quoting on the chain,
+    /// like a symbol not in the token list.
+    pub result: Result<QuoteComparison>,
+}
+
+impl ChainQuote {
+    /// Returns the best reliable quote of the chain, if any. See [`QuoteComparison::best`].
+    pub fn best(&self) -> Option<&ComparedQuote> {
+        self.result.as_ref().ok()?.best()
+    }
+}
+
+impl<M: Middleware> MultiChain<M> {
+    /// Creates a new instance without any chain, resolving symbols with `token_list`.
+    pub fn new(token_list: TokenList) -> Self {
+        Self { protocols: HashMap::new(), token_list }
+    }
+
+    /// Creates a new instance with every protocol whose addresses are found in the [addressbook]
+    /// for each of the chains of `clients`. See [`add_known`][Self::add_known].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    pub fn new_with_clients(
+        token_list: TokenList,
+        clients: impl IntoIterator<Item = (Chain, Arc<M>)>,
+    ) -> Self {
+        let mut this = Self::new(token_list);
+        for (chain, client) in clients {
+            this.add_known(chain, client);
+        }
+        this
+    }
+
+    /// Adds every protocol whose addresses are found in the [addressbook] for `chain`, using
+    /// `client`. See [`ProtocolType::all`].
+    ///
+    /// Only Uniswap V3 itself is included among the V3 protocols, like in
+    /// [`Dex::all_known`][crate::Dex::all_known].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    pub fn add_known(&mut self, chain: Chain, client: Arc<M>) -> &mut Self {
+        let protocols = ProtocolType::all()
+            .into_iter()
+            .filter(|protocol| protocol.is_v2() || *protocol == ProtocolType::UniswapV3)
+            .filter_map(|protocol| Protocol::new_with_chain(client.clone(), chain, protocol, None));
+        self.protocols.entry(chain).or_default().extend(protocols);
+        self
+    }
+
+    /// Adds `protocol` to the protocols of `chain`.
+    pub fn insert(&mut self, chain: Chain, protocol: Protocol<M>) -> &mut Self {
+        self.protocols.entry(chain).or_default().push(protocol);
+        self
+    }
+
+    /// Removes the protocols of `chain`, returning them.
+    pub fn remove(&mut self, chain: Chain) -> Option<Vec<Protocol<M>>> {
+        self.protocols.remove(&chain)
+    }
+
+    /// Returns the protocols of `chain`, empty if it has not been added.
+    pub fn protocols(&self, chain: Chain) -> &[Protocol<M>] {
+        self.protocols.get(&chain).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the chains, sorted by ID.
+    pub fn chains(&self) -> Vec<Chain> {
+        let mut chains: Vec<_> = self.protocols.keys().copied().collect();
+        chains.sort_by_key(|&chain| u64::from(chain));
+        chains
+    }
+
+    /// Quotes swapping exactly `amount`, a decimal string like `"1.5"`, of the token with symbol
+    /// `token_in` for the one with symbol `token_out` on every chain, with [`best_quote`].
+    ///
+    /// The symbols are [resolved][TokenList::resolve] on each chain with the
+    /// [token list][Self::token_list], and `amount` is parsed with the decimals of the input token
+    /// on each chain. The chains are quoted concurrently, and the quotes are returned sorted by
+    /// chain ID.
+    ///
+    /// A chain which cannot be quoted, for example because a symbol is not in the token list or
+    /// because its client fails, does not fail the others: its [`result`][ChainQuote::result] is
+    /// the error, or its protocols are in the comparison's
+    /// [`failed`][QuoteComparison::failed].
+    pub async fn quote_everywhere(
+        &self,
+        token_in: &str,
+        token_out: &str,
+        amount: &str,
+        options: CompareOptions,
+    ) -> Vec<ChainQuote> {
+        let quotes = self.chains().into_iter().map(|chain| async move {
+            let tokens = self.resolve(chain, token_in, token_out, amount);
+            let (tokens, result) = match tokens {
+                Ok((token_in, token_out, amount)) => {
+                    let result = self.quote_chain(chain, amount, token_in, token_out, options);
+                    (Some((token_in, token_out)), result.await)
+                }
+                Err(e) => (None, Err(e)),
+            };
+            ChainQuote { chain, tokens, result }
+        });
+        future::join_all(quotes).await
+    }
+
+    /// Resolves the tokens and the amount of [`quote_everywhere`][Self::quote_everywhere] on
+    /// `chain`.
+    fn resolve(
+        &self,
+        chain: Chain,
+        token_in: &str,
+        token_out: &str,
+        amount: &str,
+    ) -> Result<(Address, Address, Amount)> {
+        let (token_in, decimals) = self.token_list.resolve(token_in, chain)?;
+        let (token_out, _) = self.token_list.resolve(token_out, chain)?;
+        let amount = parse_units(amount, decimals)?;
+        Ok((token_in, token_out, Amount::ExactIn(amount)))
+    }
+
+    /// Quotes the trade with the V2 and V3 protocols of `chain`.
+    async fn quote_chain(
+        &self,
+        chain: Chain,
+        amount: Amount,
+        token_in: Address,
+        token_out: Address,
+        options: CompareOptions,
+    ) -> Result<QuoteComparison> {
+        let (mut v2, mut v3) = (vec![], vec![]);
+        for protocol in self.protocols(chain) {
+            match protocol {
+                Protocol::V2(p) => v2.push(p.clone()),
+                Protocol::V3(p) => v3.push(p.clone()),
+            }
+        }
+        best_quote(&v2, &v3, amount, token_in, token_out, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{errors::Error, tokens::TokenListEntry, v2};
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Bytes, U256},
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    type M = Provider<MockProvider>;
+
+    // sorted, so that the input token is token0
+    const TOKEN_IN: Address = Address::repeat_byte(1);
+    const TOKEN_OUT: Address = Address::repeat_byte(2);
+
+    fn entry(chain: Chain, address: Address, symbol: &str, decimals: u8) -> TokenListEntry {
+        TokenListEntry {
+            chain_id: chain.into(),
+            address,
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals,
+            logo_uri: None,
+        }
+    }
+
+    fn protocol(client: Arc<M>) -> Protocol<M> {
+        let factory = Address::repeat_byte(0xf0);
+        Protocol::V2(v2::Protocol::new(client, factory, Address::zero(), ProtocolType::UniswapV2))
+    }
+
+    #[test]
+    fn test_chains() {
+        let (provider, _mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let mut multichain = MultiChain::new(TokenList::default());
+        multichain
+            .insert(Chain::Optimism, protocol(client.clone()))
+            .insert(Chain::Mainnet, protocol(client.clone()))
+            .insert(Chain::Mainnet, protocol(client));
+        assert_eq!(multichain.chains(), vec![Chain::Mainnet, Chain::Optimism]);
+        assert_eq!(multichain.protocols(Chain::Mainnet).len(), 2);
+        assert!(multichain.protocols(Chain::Arbitrum).is_empty());
+
+        assert_eq!(multichain.remove(Chain::Optimism).unwrap().len(), 1);
+        assert_eq!(multichain.chains(), vec![Chain::Mainnet]);
+    }
+
+    #[tokio::test]
+    async fn test_quote_everywhere() {
+        let token_list = TokenList {
+            name: "test".to_string(),
+            logo_uri: None,
+            tokens: vec![
+                entry(Chain::Mainnet, TOKEN_IN, "IN", 6),
+                entry(Chain::Mainnet, TOKEN_OUT, "OUT", 18),
+                entry(Chain::Optimism, TOKEN_IN, "IN", 18),
+                entry(Chain::Optimism, TOKEN_OUT, "OUT", 18),
+                entry(Chain::Arbitrum, TOKEN_IN, "IN", 18),
+            ],
+        };
+        let mut multichain = MultiChain::new(token_list);
+
+        let (provider, mock) = Provider::mocked();
+        multichain.insert(Chain::Mainnet, protocol(Arc::new(provider)));
+        let reserves = [U256::exp10(21), U256::exp10(21), U256::zero()];
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&reserves.map(Token::Uint)))).unwrap();
+
+        // no response is pushed, so its client fails
+        let (provider, _mock) = Provider::mocked();
+        multichain.insert(Chain::Optimism, protocol(Arc::new(provider)));
+
+        let (provider, _mock) = Provider::mocked();
+        multichain.insert(Chain::Arbitrum, protocol(Arc::new(provider)));
+
+        let quotes = multichain.quote_everywhere("IN", "OUT", "1.5", CompareOptions::new()).await;
+        let chains: Vec<_> = quotes.iter().map(|quote| quote.chain).collect();
+        assert_eq!(chains, vec![Chain::Mainnet, Chain::Optimism, Chain::Arbitrum]);
+
+        // parsed with the decimals of the chain's input token
+        let best = quotes[0].best().unwrap();
+        assert_eq!(best.amount_in, 1_500_000.into());
+        assert!(!best.amount_out.is_zero());
+        assert_eq!(quotes[0].tokens, Some((TOKEN_IN, TOKEN_OUT)));
+
+        let comparison = quotes[1].result.as_ref().unwrap();
+        assert!(comparison.quotes.is_empty());
+        assert_eq!(comparison.failed.len(), 1);
+
+        assert_eq!(quotes[2].tokens, None);
+        let err = quotes[2].result.as_ref().unwrap_err();
+        assert!(matches!(err, Error::UnknownSymbol { chain: Chain::Arbitrum, .. }));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::errors::{Error, Result};
+use ethers_contract::builders::ContractCall;
+use ethers_core::{
+    abi::{Function, StateMutability},
+    types::{transaction::eip2718::TypedTransaction, U256},
+};
+use std::mem;
+
+/// The price at which a Uniswap V3 swap stops, even if the amount is not fully swapped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PriceLimit {
+    /// A raw `sqrtPriceLimitX96`.
+    SqrtPriceX96(U256),
+    /// The price of `token0` in terms of `token1`, adjusted for the tokens' decimals.
+    #[cfg(feature = "v3")]
+    Price {
+        /// The price.
+        price: f64,
+        /// The decimals of `token0`.
+        decimals0: u8,
+        /// The decimals of `token1`.
+        decimals1: u8,
+    },
+}
+
+impl From<U256> for PriceLimit {
+    fn from(sqrt_price_x96: U256) -> Self {
+        Self::SqrtPriceX96(sqrt_price_x96)
+    }
+}
+
+impl PriceLimit {
+    /// Returns the limit as a `sqrtPriceX96`, converting a price with
+    /// `v3::math::price_to_sqrt_ratio`.
+    ///
+    /// Returns [`Error::InvalidPrice`][crate::errors::Error::InvalidPrice] if the price is out of
+    /// range.
+    pub fn sqrt_price_x96(&self) -> Result<U256> {
+        match *self {
+            Self::SqrtPriceX96(sqrt_price_x96) => Ok(sqrt_price_x96),
+            #[cfg(feature = "v3")]
+            Self::Price { price, decimals0, decimals1 } => {
+                crate::v3::math::price_to_sqrt_ratio(price, decimals0, decimals1)
+            }
+        }
+    }
+}
+
+/// Additional options used when building a swap.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SwapOptions {
+    /// The already fetched, sorted reserves of each pair in the swap path.
+    ///
+    /// When set, the swap amounts are computed offline from these instead of fetching the
+    /// reserves from the chain.
+    pub reserves: Option<Vec<(U256, U256)>>,
+
+    /// The maximum price impact allowed for the swap, in basis points.
+    ///
+    /// When set, building the swap fails with [`PriceImpactTooHigh`] if the quoted price impact
+    /// is greater than this value.
+    ///
+    /// [`PriceImpactTooHigh`]: crate::errors::Error::PriceImpactTooHigh
+    pub max_price_impact_bps: Option<u32>,
+
+    /// Whether to check the router's allowance for the input token and return the approval calls
+    /// needed before the swap. See `Dex::swap_with_approvals`.
+    pub auto_approve: bool,
+
+    /// The price at which a single-hop Uniswap V3 swap stops, which must be below the pool's
+    /// price when swapping `token0` for `token1`, and above it otherwise.
+    ///
+    /// When the limit is reached, the swap is partially filled: an exact input swap spends less
+    /// than its amount in, the rest of which is refunded if it is ETH, and an exact output swap
+    /// receives less than its amount out. The quoted and returned amounts are those of the partial
+    /// fill.
+    ///
+    /// Ignored by Uniswap V2 swaps.
+    pub price_limit: Option<PriceLimit>,
+
+    /// Whether each pair in the path of a swap through a Solidly fork is stable, which allows
+    /// routes mixing stable and volatile pairs.
+    ///
+    /// Defaults to the factory's [stable][crate::ProtocolType::stable] flag for every pair. Ignored
+    /// by other protocols.
+    pub stable: Option<Vec<bool>>,
+
+    /// The safety margin added to the estimated gas of the swap, in basis points.
+    ///
+    /// When set, the gas of the swap is [estimated][crate::gas::estimate] when building it, and
+    /// the padded gas limit and the suggested fees are set on the returned call, so that it is
+    /// ready to be sent.
+    pub gas_margin_bps: Option<u32>,
+
+    /// The transaction options applied to the swap, after the estimated gas and fees if
+    /// [`gas_margin_bps`][Self::gas_margin_bps] is set.
+    pub tx: TxOptions,
+}
+
+impl SwapOptions {
+    /// Creates a new instance with the default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            reserves: None,
+            max_price_impact_bps: None,
+            auto_approve: false,
+            price_limit: None,
+            stable: None,
+            gas_margin_bps: None,
+            tx: TxOptions::new(),
+        }
+    }
+
+    /// Sets the already fetched, sorted reserves of each pair in the swap path.
+    #[inline]
+    pub fn reserves(mut self, reserves: Vec<(U256, U256)>) -> Self {
+        self.reserves = Some(reserves);
+        self
+    }
+
+    /// Sets the maximum price impact allowed for the swap, in basis points.
+    #[inline]
+    pub const fn max_price_impact_bps(mut self, max_price_impact_bps: u32) -> Self {
+        self.max_price_impact_bps = Some(max_price_impact_bps);
+        self
+    }
+
+    /// Sets whether to return the approval calls needed before the swap.
+    #[inline]
+    pub const fn auto_approve(mut self, auto_approve: bool) -> Self {
+        self.auto_approve = auto_approve;
+        self
+    }
+
+    /// Sets the price limit of a Uniswap V3 swap, as a `sqrtPriceX96`.
+    #[inline]
+    pub const fn sqrt_price_limit_x96(mut self, sqrt_price_limit_x96: U256) -> Self {
+        self.price_limit = Some(PriceLimit::SqrtPriceX96(sqrt_price_limit_x96));
+        self
+    }
+
+    /// Sets the price limit of a Uniswap V3 swap.
+    #[inline]
+    pub fn price_limit(mut self, price_limit: impl Into<PriceLimit>) -> Self {
+        self.price_limit = Some(price_limit.into());
+        self
+    }
+
+    /// Sets whether each pair in the path of a Solidly swap is stable.
+    #[inline]
+    pub fn stable(mut self, stable: Vec<bool>) -> Self {
+        self.stable = Some(stable);
+        self
+    }
+
+    /// Sets the safety margin added to the estimated gas of the swap, in basis points.
+    #[inline]
+    pub const fn gas_margin_bps(mut self, gas_margin_bps: u32) -> Self {
+        self.gas_margin_bps = Some(gas_margin_bps);
+        self
+    }
+
+    /// Sets the transaction options applied to the swap.
+    #[inline]
+    pub const fn tx(mut self, tx: TxOptions) -> Self {
+        self.tx = tx;
+        self
+    }
+}
+
+/// Additional options used when adding liquidity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LiquidityOptions {
+    /// Whether to check the router's allowance for both tokens and return the approval calls
+    /// needed before adding liquidity. See `Dex::add_liquidity_with_approvals`.
+    pub auto_approve: bool,
+
+    /// The transaction options applied to the call adding liquidity.
+    pub tx: TxOptions,
+}
+
+impl LiquidityOptions {
+    /// Creates a new instance with the default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { auto_approve: false, tx: TxOptions::new() }
+    }
+
+    /// Sets whether to return the approval calls needed before adding liquidity.
+    #[inline]
+    pub const fn auto_approve(mut self, auto_approve: bool) -> Self {
+        self.auto_approve = auto_approve;
+        self
+    }
+
+    /// Sets the transaction options applied to the call adding liquidity.
+    #[inline]
+    pub const fn tx(mut self, tx: TxOptions) -> Self {
+        self.tx = tx;
+        self
+    }
+}
+
+/// Transaction options which override those of a built call. Unset options are left untouched.
+///
+/// Applied with [`TxOptions::apply`] or [`CallExt::tx_options`][crate::CallExt::tx_options], or
+/// through [`SwapOptions::tx`] when building a swap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TxOptions {
+    /// The gas limit.
+    pub gas_limit: Option<U256>,
+
+    /// The nonce.
+    pub nonce: Option<U256>,
+
+    /// Whether to send a legacy transaction instead of an EIP-1559 one.
+    ///
+    /// [`max_fee`][Self::max_fee] is then used as the gas price.
+    pub legacy: bool,
+
+    /// The maximum fee per gas, or the gas price of a legacy transaction.
+    pub max_fee: Option<U256>,
+
+    /// The maximum priority fee per gas. Not supported by legacy transactions.
+    pub max_priority_fee: Option<U256>,
+
+    /// The native token value sent with the call, which must be to a payable function.
+    pub value: Option<U256>,
+}
+
+impl TxOptions {
+    /// Creates a new instance which does not override anything.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            gas_limit: None,
+            nonce: None,
+            legacy: false,
+            max_fee: None,
+            max_priority_fee: None,
+            value: None,
+        }
+    }
+
+    /// Sets the gas limit.
+    #[inline]
+    pub const fn gas_limit(mut self, gas_limit: U256) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the nonce.
+    #[inline]
+    pub const fn nonce(mut self, nonce: U256) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets whether to send a legacy transaction.
+    #[inline]
+    pub const fn legacy(mut self, legacy: bool) -> Self {
+        self.legacy = legacy;
+        self
+    }
+
+    /// Sets the maximum fee per gas, or the gas price of a legacy transaction.
+    #[inline]
+    pub const fn max_fee(mut self, max_fee: U256) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    /// Sets the maximum priority fee per gas.
+    #[inline]
+    pub const fn max_priority_fee(mut self, max_priority_fee: U256) -> Self {
+        self.max_priority_fee = Some(max_priority_fee);
+        self
+    }
+
+    /// Sets the native token value sent with the call.
+    #[inline]
+    pub const fn value(mut self, value: U256) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Checks that the options do not conflict with each other, or with `call`, and applies
+    /// them to it.
+    ///
+    /// Returns [`Error::ConflictingTxOptions`] if a priority fee is set on a legacy transaction or
+    /// is greater than the maximum fee, or if the value differs from the one already set by the
+    /// builder, like the input amount of a swap from the native token, and
+    /// [`Error::NonPayableValue`] if a value is sent to a non-payable function.
+    pub fn apply<M, D>(&self, mut call: ContractCall<M, D>) -> Result<ContractCall<M, D>> {
+        self.apply_tx(&mut call.tx, &call.function)?;
+        Ok(call)
+    }
+
+    /// Applies the options to `tx`, a call of `function`.
+    fn apply_tx(&self, tx: &mut TypedTransaction, function: &Function) -> Result<()> {
+        if let Some(max_priority_fee) = self.max_priority_fee {
+            if self.legacy {
+                return Err(Error::ConflictingTxOptions(
+                    "legacy transactions do not have a priority fee",
+                ));
+            }
+            if self.max_fee.map_or(false, |max_fee| max_priority_fee > max_fee) {
+                return Err(Error::ConflictingTxOptions(
+                    "the priority fee is greater than the maximum fee",
+                ));
+            }
+        }
+        if let Some(value) = self.value {
+            let current = tx.value().copied().unwrap_or_default();
+            if !current.is_zero() && current != value {
+                return Err(Error::ConflictingTxOptions(
+                    "the value differs from the one set by the builder",
+                ));
+            }
+            if !value.is_zero() && function.state_mutability != StateMutability::Payable {
+                return Err(Error::NonPayableValue(function.name.clone()));
+            }
+            tx.set_value(value);
+        }
+
+        if self.legacy {
+            if let TypedTransaction::Eip1559(eip1559) = tx {
+                *tx = TypedTransaction::Legacy(mem::take(eip1559).into());
+            }
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            tx.set_gas(gas_limit);
+        }
+        if let Some(nonce) = self.nonce {
+            tx.set_nonce(nonce);
+        }
+        match tx {
+            TypedTransaction::Eip1559(tx) => {
+                if let Some(max_fee) = self.max_fee {
+                    tx.max_fee_per_gas = Some(max_fee);
+                }
+                if let Some(max_priority_fee) = self.max_priority_fee {
+                    tx.max_priority_fee_per_gas = Some(max_priority_fee);
+                }
+            }
+            tx => {
+                if let Some(max_fee) = self.max_fee {
+                    tx.set_gas_price(max_fee);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::{ierc20::IERC20, iweth::IWETH};
+    use ethers_core::types::Address;
+    use ethers_providers::Provider;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tx_options() {
+        let (provider, _) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(Address::repeat_byte(1), client.clone());
+        let weth = IWETH::new(Address::repeat_byte(2), client);
+
+        let options = TxOptions::new()
+            .gas_limit(100_000.into())
+            .nonce(7.into())
+            .max_fee(100.into())
+            .max_priority_fee(2.into())
+            .value(1.into());
+        let tx = options.apply(weth.deposit()).unwrap().tx;
+        let inner = tx.as_eip1559_ref().unwrap();
+        assert_eq!(inner.gas, Some(100_000.into()));
+        assert_eq!(inner.nonce, Some(7.into()));
+        assert_eq!(inner.max_fee_per_gas, Some(100.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(2.into()));
+        assert_eq!(inner.value, Some(1.into()));
+
+        let options = TxOptions::new().legacy(true).max_fee(100.into());
+        let tx = options.apply(token.transfer(Address::zero(), 1.into())).unwrap().tx;
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+        assert_eq!(tx.gas_price(), Some(100.into()));
+
+        // conflicts
+        let call = token.transfer(Address::zero(), 1.into());
+        let res = TxOptions::new().value(1.into()).apply(call.clone());
+        assert!(matches!(res.unwrap_err(), Error::NonPayableValue(name) if name == "transfer"));
+        assert!(TxOptions::new().value(0.into()).apply(call.clone()).is_ok());
+        let res = TxOptions::new().legacy(true).max_priority_fee(1.into()).apply(call.clone());
+        assert!(matches!(res.unwrap_err(), Error::ConflictingTxOptions(_)));
+        let res = TxOptions::new().max_fee(1.into()).max_priority_fee(2.into()).apply(call);
+        assert!(matches!(res.unwrap_err(), Error::ConflictingTxOptions(_)));
+        let res = TxOptions::new().value(2.into()).apply(weth.deposit().value(1));
+        assert!(matches!(res.unwrap_err(), Error::ConflictingTxOptions(_)));
+    }
+}
@@ -0,0 +1,113 @@
+//! [EIP-2612](https://eips.ethereum.org/EIPS/eip-2612) permits
+
+use crate::errors::{Error, Result};
+use ethers_core::{
+    abi::{self, Token},
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712, Eip712Error},
+        Address, Signature, H256, U256,
+    },
+    utils::keccak256,
+};
+use ethers_signers::Signer;
+
+/// The EIP-712 type of a permit.
+pub const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// An EIP-2612 permit, which approves `spender` to transfer `value` of `owner`'s tokens with a
+/// signature instead of an `approve` transaction.
+///
+/// The EIP-712 domain is only known through its separator, as returned by the token's
+/// `DOMAIN_SEPARATOR` method, so [`Eip712::domain`] always returns an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Permit {
+    /// The token's EIP-712 domain separator.
+    pub domain_separator: H256,
+    /// The owner of the tokens.
+    pub owner: Address,
+    /// The approved spender.
+    pub spender: Address,
+    /// The approved amount.
+    pub value: U256,
+    /// The owner's current permit nonce.
+    pub nonce: U256,
+    /// The timestamp after which the permit is no longer valid.
+    pub deadline: U256,
+}
+
+impl Permit {
+    /// Returns the EIP-712 digest of the permit, which is what gets signed.
+    pub fn digest(&self) -> H256 {
+        let struct_hash = self.struct_hash().expect("infallible");
+        keccak256([&[0x19, 0x01], &self.domain_separator[..], &struct_hash[..]].concat()).into()
+    }
+}
+
+impl Eip712 for Permit {
+    type Error = Eip712Error;
+
+    fn domain_separator(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(self.domain_separator.0)
+    }
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Err(Eip712Error::Message("only the domain separator is known".to_string()))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(PERMIT_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(abi::encode(&[
+            Token::FixedBytes(Self::type_hash()?.to_vec()),
+            Token::Address(self.owner),
+            Token::Address(self.spender),
+            Token::Uint(self.value),
+            Token::Uint(self.nonce),
+            Token::Uint(self.deadline),
+        ])))
+    }
+}
+
+/// Signs `permit` with `signer`.
+pub async fn sign_permit<S: Signer>(signer: &S, permit: &Permit) -> Result<Signature> {
+    signer.sign_typed_data(permit).await.map_err(|e| Error::SignerError(e.to_string()))
+}
+
+/// Splits `signature` into its `(v, r, s)` components, as taken by the `permit` methods.
+pub fn split_signature(signature: &Signature) -> (u8, [u8; 32], [u8; 32]) {
+    let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+    signature.r.to_big_endian(&mut r);
+    signature.s.to_big_endian(&mut s);
+    (signature.v as u8, r, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sign_permit() {
+        let wallet = crate::testing::default_wallet();
+        let permit = Permit {
+            domain_separator: H256::repeat_byte(1),
+            owner: wallet.address(),
+            spender: Address::repeat_byte(2),
+            value: U256::MAX,
+            nonce: U256::zero(),
+            deadline: U256::MAX,
+        };
+        assert_eq!(permit.encode_eip712().unwrap(), permit.digest().0);
+
+        let signature = sign_permit(&wallet, &permit).await.unwrap();
+        assert_eq!(signature.recover(permit.digest()).unwrap(), wallet.address());
+
+        let (v, r, s) = split_signature(&signature);
+        assert!(v == 27 || v == 28);
+        assert_eq!(U256::from_big_endian(&r), signature.r);
+        assert_eq!(U256::from_big_endian(&s), signature.s);
+    }
+}
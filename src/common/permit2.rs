@@ -0,0 +1,460 @@
+//! [Permit2](https://github.com/Uniswap/permit2) allowances and signature-based approvals, as
+//! taken by the Universal Router's `PERMIT2_PERMIT` commands.
+
+use super::{call_multi, Deadline};
+use crate::{
+    contracts::bindings::i_permit_2::IPermit2,
+    errors::{Error, Result},
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::{
+    abi::{self, Token},
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712, Eip712Error},
+        Address, Bytes, H160, H256, U256,
+    },
+    utils::keccak256,
+};
+use ethers_providers::Middleware;
+use ethers_signers::Signer;
+use std::sync::Arc;
+
+pub use crate::contracts::bindings::i_permit_2::{PermitBatch, PermitDetails, PermitSingle};
+
+/// The address of the canonical Permit2 contract, which is the same on every chain.
+pub const PERMIT2_ADDRESS: Address = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0xd4, 0x73, 0x03, 0x0f, 0x11, 0x6d, 0xde, 0xe9, 0xf6, 0xb4,
+    0x3a, 0xc7, 0x8b, 0xa3,
+]);
+
+/// The maximum Permit2 allowance amount, which is a `uint160`.
+pub const MAX_ALLOWANCE_AMOUNT: U256 = U256([u64::MAX, u64::MAX, u32::MAX as u64, 0]);
+
+/// The maximum Permit2 allowance expiration and nonce, which are `uint48`s.
+pub const MAX_UINT48: u64 = (1 << 48) - 1;
+
+/// The EIP-712 type of a permit's details.
+pub const PERMIT_DETAILS_TYPE: &str =
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+
+/// The EIP-712 type of a single permit.
+pub const PERMIT_SINGLE_TYPE: &str = "PermitSingle(PermitDetails details,address spender,uint256 \
+    sigDeadline)PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+
+/// The EIP-712 type of a batch permit.
+pub const PERMIT_BATCH_TYPE: &str = "PermitBatch(PermitDetails[] details,address spender,uint256 \
+    sigDeadline)PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+
+/// A Permit2 permit which can be signed with EIP-712.
+pub trait PermitData {
+    /// The EIP-712 type of the permit.
+    const TYPE: &'static str;
+
+    /// Returns the EIP-712 hash of the permit's fields.
+    fn hash_struct(&self) -> [u8; 32];
+}
+
+impl PermitData for PermitSingle {
+    const TYPE: &'static str = PERMIT_SINGLE_TYPE;
+
+    fn hash_struct(&self) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::FixedBytes(keccak256(Self::TYPE).to_vec()),
+            Token::FixedBytes(hash_details(&self.details).to_vec()),
+            Token::Address(self.spender),
+            Token::Uint(self.sig_deadline),
+        ]))
+    }
+}
+
+impl PermitData for PermitBatch {
+    const TYPE: &'static str = PERMIT_BATCH_TYPE;
+
+    fn hash_struct(&self) -> [u8; 32] {
+        let details: Vec<u8> = self.details.iter().flat_map(hash_details).collect();
+        keccak256(abi::encode(&[
+            Token::FixedBytes(keccak256(Self::TYPE).to_vec()),
+            Token::FixedBytes(keccak256(details).to_vec()),
+            Token::Address(self.spender),
+            Token::Uint(self.sig_deadline),
+        ]))
+    }
+}
+
+/// Returns the EIP-712 hash of a permit's details.
+fn hash_details(details: &PermitDetails) -> [u8; 32] {
+    keccak256(abi::encode(&[
+        Token::FixedBytes(keccak256(PERMIT_DETAILS_TYPE).to_vec()),
+        Token::Address(details.token),
+        Token::Uint(details.amount),
+        Token::Uint(details.expiration.into()),
+        Token::Uint(details.nonce.into()),
+    ]))
+}
+
+/// A Permit2 permit and the EIP-712 domain of the Permit2 contract it is signed for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Permit2Message<T> {
+    /// The permit.
+    pub permit: T,
+    /// The chain ID of the Permit2 contract.
+    pub chain_id: u64,
+    /// The address of the Permit2 contract.
+    pub verifying_contract: Address,
+}
+
+impl<T> Permit2Message<T> {
+    /// Creates a new message for the canonical Permit2 contract on `chain_id`.
+    pub fn new(permit: T, chain_id: u64) -> Self {
+        Self { permit, chain_id, verifying_contract: PERMIT2_ADDRESS }
+    }
+}
+
+impl<T: PermitData + Send + Sync> Permit2Message<T> {
+    /// Returns the EIP-712 digest of the permit, which is what gets signed.
+    pub fn digest(&self) -> H256 {
+        self.encode_eip712().expect("infallible").into()
+    }
+}
+
+impl<T: PermitData + Send + Sync> Eip712 for Permit2Message<T> {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(EIP712Domain {
+            name: Some("Permit2".to_string()),
+            version: None,
+            chain_id: Some(self.chain_id.into()),
+            verifying_contract: Some(self.verifying_contract),
+            salt: None,
+        })
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(T::TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(self.permit.hash_struct())
+    }
+}
+
+/// Signs `message` with `signer`, returning the packed `r || s || v` signature taken by Permit2's
+/// `permit` methods and by the Universal Router's `PERMIT2_PERMIT` commands.
+pub async fn sign_permit2<S: Signer, T: PermitData + Send + Sync>(
+    signer: &S,
+    message: &Permit2Message<T>,
+) -> Result<Bytes> {
+    let signature =
+        signer.sign_typed_data(message).await.map_err(|e| Error::SignerError(e.to_string()))?;
+    Ok(signature.to_vec().into())
+}
+
+/// The Permit2 allowance of a spender over an owner's tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Allowance {
+    /// The allowed amount.
+    pub amount: U256,
+    /// The timestamp after which the allowance is no longer valid.
+    pub expiration: u64,
+    /// The nonce of the next permit.
+    pub nonce: u64,
+}
+
+impl Allowance {
+    /// Returns whether the allowance covers `amount` at `timestamp`, so that no new permit is
+    /// needed.
+    pub fn is_valid_for(&self, amount: U256, timestamp: u64) -> bool {
+        self.amount >= amount && self.expiration >= timestamp
+    }
+}
+
+contract_struct! {
+    /// A Permit2 contract, which holds the token approvals of its users and lets them grant
+    /// time-limited allowances to spenders, either with `approve` transactions or with signed
+    /// permits.
+    pub struct Permit2<M> {
+        /// The Permit2 contract.
+        contract: IPermit2<M>,
+    }
+}
+
+impl<M: Middleware> Permit2<M> {
+    /// Creates a new instance using the provided address.
+    pub fn new(client: Arc<M>, address: Address) -> Self {
+        Self { contract: IPermit2::new(address, client) }
+    }
+
+    /// Creates a new instance of the canonical Permit2 contract, at [`PERMIT2_ADDRESS`].
+    pub fn new_canonical(client: Arc<M>) -> Self {
+        Self::new(client, PERMIT2_ADDRESS)
+    }
+
+    /// Returns the allowance of `spender` over `owner`'s `token`.
+    pub async fn allowance(
+        &self,
+        owner: Address,
+        token: Address,
+        spender: Address,
+    ) -> Result<Allowance> {
+        let (amount, expiration, nonce) =
+            self.contract.allowance(owner, token, spender).call().await?;
+        Ok(Allowance { amount, expiration, nonce })
+    }
+
+    /// Returns the contract call for Permit2's `approve` method, which sets the allowance of
+    /// `spender` over the sender's `token` to `amount` until `expiration`.
+    ///
+    /// An `expiration` of zero is the timestamp of the block in which the call is included.
+    pub fn approve(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+        expiration: u64,
+    ) -> ContractCall<M, ()> {
+        self.contract.approve(token, spender, amount, expiration)
+    }
+
+    /// Returns the contract call for Permit2's `permit` method, which sets the allowance of the
+    /// permit's spender with `owner`'s packed `signature`, as returned by [`sign_permit2`].
+    pub fn permit(
+        &self,
+        owner: Address,
+        permit: PermitSingle,
+        signature: Bytes,
+    ) -> ContractCall<M, ()> {
+        self.contract.permit(owner, permit, signature)
+    }
+
+    /// Returns a permit which allows `spender` to transfer `amount` of `owner`'s `token` until
+    /// `expiration`, signed by `sig_deadline`. See [`permit_batch`][Self::permit_batch].
+    pub async fn permit_single(
+        &self,
+        owner: Address,
+        token: Address,
+        amount: U256,
+        spender: Address,
+        expiration: impl Into<Deadline>,
+        sig_deadline: impl Into<Deadline>,
+    ) -> Result<PermitSingle> {
+        let PermitBatch { mut details, spender, sig_deadline } =
+            self.permit_batch(owner, &[(token, amount)], spender, expiration, sig_deadline).await?;
+        Ok(PermitSingle { details: details.remove(0), spender, sig_deadline })
+    }
+
+    /// Returns a permit which allows `spender` to transfer each amount of `owner`'s tokens until
+    /// `expiration`, signed by `sig_deadline`.
+    ///
+    /// The nonces are read from the current allowances. The deadlines are resolved with
+    /// [`Deadline::resolve`], and any expiration after the maximum `uint48` is clamped to it.
+    ///
+    /// Returns [`Error::Overflow`] if an amount is greater than [`MAX_ALLOWANCE_AMOUNT`], and
+    /// [`Error::DeadlineInPast`] if a deadline has passed.
+    pub async fn permit_batch(
+        &self,
+        owner: Address,
+        tokens: &[(Address, U256)],
+        spender: Address,
+        expiration: impl Into<Deadline>,
+        sig_deadline: impl Into<Deadline>,
+    ) -> Result<PermitBatch> {
+        if tokens.iter().any(|&(_, amount)| amount > MAX_ALLOWANCE_AMOUNT) {
+            return Err(Error::Overflow);
+        }
+        let client = self.client();
+        let expiration = expiration.into().resolve(client.as_ref()).await?;
+        let expiration = expiration.min(MAX_UINT48.into()).as_u64();
+        let sig_deadline = sig_deadline.into().resolve(client.as_ref()).await?;
+
+        let calls = tokens
+            .iter()
+            .map(|&(token, _)| self.contract.allowance(owner, token, spender))
+            .collect();
+        let allowances = call_multi(client, None, calls).await?;
+        let details = tokens
+            .iter()
+            .zip(allowances)
+            .map(|(&(token, amount), (_, _, nonce))| PermitDetails {
+                token,
+                amount,
+                expiration,
+                nonce,
+            })
+            .collect();
+        Ok(PermitBatch { details, spender, sig_deadline })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{types::transaction::eip712::TypedData, utils::__serde_json};
+    use ethers_providers::Provider;
+
+    const OWNER: Address = Address::repeat_byte(0xbb);
+
+    fn details(i: u8) -> PermitDetails {
+        PermitDetails {
+            token: Address::repeat_byte(i),
+            amount: MAX_ALLOWANCE_AMOUNT - i,
+            expiration: 1_700_000_000 + i as u64,
+            nonce: i as u64,
+        }
+    }
+
+    /// The typed data of a permit, as built by the Permit2 SDK's `getPermitData`.
+    fn typed_data(primary_type: &str, message: __serde_json::Value) -> TypedData {
+        let (details, details_type) = match primary_type {
+            "PermitSingle" => ("PermitDetails", "PermitDetails"),
+            _ => ("PermitDetails", "PermitDetails[]"),
+        };
+        __serde_json::from_value(__serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                details: [
+                    { "name": "token", "type": "address" },
+                    { "name": "amount", "type": "uint160" },
+                    { "name": "expiration", "type": "uint48" },
+                    { "name": "nonce", "type": "uint48" },
+                ],
+                primary_type: [
+                    { "name": "details", "type": details_type },
+                    { "name": "spender", "type": "address" },
+                    { "name": "sigDeadline", "type": "uint256" },
+                ],
+            },
+            "primaryType": primary_type,
+            "domain": {
+                "name": "Permit2",
+                "chainId": 1,
+                "verifyingContract": format!("{PERMIT2_ADDRESS:?}"),
+            },
+            "message": message,
+        }))
+        .unwrap()
+    }
+
+    fn details_json(details: &PermitDetails) -> __serde_json::Value {
+        __serde_json::json!({
+            "token": format!("{:?}", details.token),
+            "amount": details.amount.to_string(),
+            "expiration": details.expiration.to_string(),
+            "nonce": details.nonce.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(format!("{PERMIT2_ADDRESS:?}"), "0x000000000022d473030f116ddee9f6b43ac78ba3");
+        assert_eq!(MAX_ALLOWANCE_AMOUNT, (U256::one() << 160) - 1);
+
+        // against ethers' generic encoding of the typed data
+        let spender = Address::repeat_byte(0xcc);
+        let single = PermitSingle { details: details(1), spender, sig_deadline: U256::MAX };
+        let message = Permit2Message::new(single.clone(), 1);
+        let expected = typed_data(
+            "PermitSingle",
+            __serde_json::json!({
+                "details": details_json(&single.details),
+                "spender": format!("{spender:?}"),
+                "sigDeadline": U256::MAX.to_string(),
+            }),
+        );
+        assert_eq!(message.digest().0, expected.encode_eip712().unwrap());
+
+        let batch =
+            PermitBatch { details: vec![details(1), details(2)], spender, sig_deadline: 5.into() };
+        let message = Permit2Message::new(batch.clone(), 1);
+        let expected = typed_data(
+            "PermitBatch",
+            __serde_json::json!({
+                "details": batch.details.iter().map(details_json).collect::<Vec<_>>(),
+                "spender": format!("{spender:?}"),
+                "sigDeadline": "5",
+            }),
+        );
+        assert_eq!(message.digest().0, expected.encode_eip712().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sign_permit2() {
+        let wallet = crate::testing::default_wallet();
+        let permit = PermitSingle { details: details(1), spender: OWNER, sig_deadline: U256::MAX };
+        let message = Permit2Message::new(permit, 1);
+        let signature = sign_permit2(&wallet, &message).await.unwrap();
+        assert_eq!(signature.len(), 65);
+        let signature = ethers_core::types::Signature::try_from(signature.as_ref()).unwrap();
+        assert_eq!(signature.recover(message.digest()).unwrap(), wallet.address());
+    }
+
+    #[tokio::test]
+    async fn test_permit_batch() {
+        let (provider, mock) = Provider::mocked();
+        let permit2 = Permit2::new_canonical(Arc::new(provider));
+        let (token, spender) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        // a single allowance is not aggregated
+        let allowance =
+            abi::encode(&[Token::Uint(1.into()), Token::Uint(2.into()), Token::Uint(7.into())]);
+        mock.push::<Bytes, _>(Bytes::from(allowance)).unwrap();
+        let permit = permit2
+            .permit_single(OWNER, token, 100.into(), spender, U256::MAX, U256::MAX)
+            .await
+            .unwrap();
+        let details = PermitDetails { token, amount: 100.into(), expiration: MAX_UINT48, nonce: 7 };
+        assert_eq!(permit, PermitSingle { details, spender, sig_deadline: U256::MAX });
+
+        let res = permit2.permit_single(OWNER, token, U256::MAX, spender, U256::MAX, U256::MAX);
+        assert!(matches!(res.await.unwrap_err(), Error::Overflow));
+    }
+
+    #[test]
+    fn test_allowance() {
+        let allowance = Allowance { amount: 100.into(), expiration: 10, nonce: 0 };
+        assert!(allowance.is_valid_for(100.into(), 10));
+        assert!(!allowance.is_valid_for(101.into(), 10));
+        assert!(!allowance.is_valid_for(100.into(), 11));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_permit_fork() {
+        use crate::testing::Fork;
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me, chain_id) = (fork.client(), fork.address(), fork.chain_id());
+        let wallet = client.signer().clone();
+        let permit2 = Permit2::new_canonical(client);
+
+        // the digest matches the contract's domain
+        let domain_separator = permit2.contract().domain_separator().call().await.unwrap();
+        let message = Permit2Message::new(PermitSingle::default(), chain_id);
+        assert_eq!(message.domain_separator().unwrap(), domain_separator);
+
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+        let spender = Address::repeat_byte(0x11);
+        for i in 1..=2u64 {
+            let amount = U256::exp10(18) * i;
+            let deadline = Deadline::from_now(std::time::Duration::from_secs(3600));
+            let permit =
+                permit2.permit_single(me, weth, amount, spender, deadline, deadline).await.unwrap();
+            assert_eq!(permit.details.nonce, i - 1);
+            let signature = sign_permit2(&wallet, &Permit2Message::new(permit.clone(), chain_id))
+                .await
+                .unwrap();
+            permit2.permit(me, permit.clone(), signature).send().await.unwrap().await.unwrap();
+
+            let allowance = permit2.allowance(me, weth, spender).await.unwrap();
+            assert_eq!(allowance.amount, amount);
+            assert_eq!(allowance.expiration, permit.details.expiration);
+            assert_eq!(allowance.nonce, i);
+        }
+    }
+}
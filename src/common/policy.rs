@@ -0,0 +1,315 @@
+use super::logs::is_too_many_results;
+use crate::errors::{Error, Result};
+use async_lock::Semaphore;
+use ethers_providers::{HttpClientError, ProviderError};
+use futures_timer::Delay;
+use std::{future::Future, time::Duration};
+
+#[cfg(all(feature = "v2", feature = "v3"))]
+use std::sync::Arc;
+
+/// The HTTP status of throttled requests, "Too Many Requests", which some providers also return as
+/// the code of a JSON-RPC error.
+const TOO_MANY_REQUESTS: u16 = 429;
+
+/// The code of the JSON-RPC error "limit exceeded", returned by Infura for throttled requests.
+const LIMIT_EXCEEDED: i64 = -32005;
+
+/// The messages of the errors returned by providers which throttle requests without a dedicated
+/// code, matched case-insensitively.
+const RATE_LIMIT_MESSAGES: [&str; 4] =
+    ["too many requests", "rate limit", "rate exceeded", "request limit"];
+
+/// The concurrency and retry policy of the requests made by the fan-outs of multiple requests, like
+/// log scans, multicall batches and the quotes of multiple protocols.
+///
+/// At most [`max_concurrency`][Self::max_concurrency] requests of a fan-out are in flight at the
+/// same time, and a request which fails because the provider throttles requests, like with HTTP
+/// status 429 or Infura's `-32005` code, is retried up to [`retries`][Self::retries] times, after
+/// [`backoff`][Self::backoff] doubled after each retry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RequestPolicy {
+    /// The maximum number of requests in flight.
+    pub max_concurrency: usize,
+
+    /// The maximum number of retries of a throttled request.
+    pub retries: u32,
+
+    /// The delay before the first retry, doubled after each retry.
+    pub backoff: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestPolicy {
+    /// Creates a new instance with the default policy: at most 8 requests in flight, and up to 3
+    /// retries after 500ms, 1s and 2s.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { max_concurrency: 8, retries: 3, backoff: Duration::from_millis(500) }
+    }
+
+    /// Sets the maximum number of requests in flight, at least 1.
+    #[inline]
+    pub const fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Sets the maximum number of retries of a throttled request.
+    #[inline]
+    pub const fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    #[inline]
+    pub const fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Returns the maximum number of requests in flight, at least 1.
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    #[inline]
+    pub(crate) fn concurrency(&self) -> usize {
+        self.max_concurrency.max(1)
+    }
+
+    /// Returns the delay before the retry after `attempt` failed attempts.
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(1 << attempt.min(16))
+    }
+
+    /// Calls `f` until it does not fail because of throttling, or up to
+    /// [`retries`][Self::retries] more times.
+    pub(crate) async fn retry<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        retry(self, None, f).await
+    }
+}
+
+/// Limits the requests of a fan-out to its [RequestPolicy], sharing its semaphore between clones,
+/// so that nested fan-outs are limited together.
+#[cfg(all(feature = "v2", feature = "v3"))]
+#[derive(Clone, Debug)]
+pub(crate) struct Limiter {
+    policy: RequestPolicy,
+    semaphore: Arc<Semaphore>,
+}
+
+#[cfg(all(feature = "v2", feature = "v3"))]
+impl Limiter {
+    /// Creates a new limiter of `policy`.
+    pub(crate) fn new(policy: RequestPolicy) -> Self {
+        Self { policy, semaphore: Arc::new(Semaphore::new(policy.concurrency())) }
+    }
+
+    /// Calls `f` once a request slot is free, retrying it like [`RequestPolicy::retry`]. The slot
+    /// is released while waiting before a retry.
+    pub(crate) async fn run<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        retry(&self.policy, Some(&self.semaphore), f).await
+    }
+}
+
+async fn retry<T, F, Fut>(
+    policy: &RequestPolicy,
+    semaphore: Option<&Semaphore>,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        let permit = match semaphore {
+            Some(semaphore) => Some(semaphore.acquire().await),
+            None => None,
+        };
+        match f().await {
+            Err(e) if attempt < policy.retries && is_rate_limited(&e) => {
+                drop(permit);
+                let delay = policy.delay(attempt);
+                debug!(attempt, ?delay, error = %e, "retrying a throttled request");
+                Delay::new(delay).await;
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Returns whether `error` was returned by a provider which throttles requests.
+///
+/// This is decided on the HTTP status or the JSON-RPC error code when they are available, and on
+/// the error message otherwise.
+pub(crate) fn is_rate_limited(error: &Error) -> bool {
+    match error {
+        // the errors of the middlewares are only available as their message
+        Error::ContractError(message) | Error::MulticallError(message) => {
+            match parse_json_rpc_error(message) {
+                Some((code, message)) => is_rate_limited_rpc(code, message),
+                None => is_rate_limit_message(message),
+            }
+        }
+        Error::ProviderError(e) => is_rate_limited_provider(e),
+        _ => false,
+    }
+}
+
+fn is_rate_limited_provider(error: &ProviderError) -> bool {
+    match error {
+        ProviderError::HTTPError(e) => {
+            matches!(e.status(), Some(status) if status.as_u16() == TOO_MANY_REQUESTS)
+        }
+        ProviderError::JsonRpcClientError(e) => {
+            if let Some(HttpClientError::JsonRpcError(e)) = e.downcast_ref() {
+                return is_rate_limited_rpc(e.code, &e.message);
+            }
+            #[cfg(feature = "ws")]
+            if let Some(ethers_providers::WsClientError::JsonRpcError(e)) = e.downcast_ref() {
+                return is_rate_limited_rpc(e.code, &e.message);
+            }
+            is_rate_limit_message(&e.to_string())
+        }
+        _ => false,
+    }
+}
+
+fn is_rate_limited_rpc(code: i64, message: &str) -> bool {
+    // Infura also returns `-32005` for logs queries which return too many results
+    if is_too_many_results(message) {
+        return false;
+    }
+    code == i64::from(TOO_MANY_REQUESTS) || code == LIMIT_EXCEEDED || is_rate_limit_message(message)
+}
+
+fn is_rate_limit_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    RATE_LIMIT_MESSAGES.iter().any(|m| message.contains(m))
+}
+
+/// Parses the code and the message of a JSON-RPC error formatted like
+/// `(code: -32005, message: ..., data: None)`.
+fn parse_json_rpc_error(s: &str) -> Option<(i64, &str)> {
+    let (_, rest) = s.split_once("(code: ")?;
+    let (code, rest) = rest.split_once(", message: ")?;
+    let message = rest.rsplit_once(", data: ").map_or(rest, |(message, _)| message);
+    Some((code.parse().ok()?, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn throttled() -> Error {
+        Error::ContractError("(code: 429, message: Too Many Requests, data: None)".to_string())
+    }
+
+    #[test]
+    fn test_is_rate_limited() {
+        assert!(is_rate_limited(&throttled()));
+        let infura = "(code: -32005, message: project ID request rate exceeded, data: None)";
+        assert!(is_rate_limited(&Error::ContractError(infura.to_string())));
+
+        let too_many =
+            "(code: -32005, message: query returned more than 10000 results, data: None)";
+        assert!(!is_rate_limited(&Error::ContractError(too_many.to_string())));
+        assert!(!is_rate_limited(&Error::ContractError("execution reverted".to_string())));
+        assert!(!is_rate_limited(&Error::InvalidPath));
+
+        // the codes are not matched anywhere in the message
+        let revert =
+            "(code: 3, message: execution reverted: -32005, data: Some(String(\"0x429\")))";
+        assert!(!is_rate_limited(&Error::ContractError(revert.to_string())));
+        assert!(!is_rate_limited(&Error::ContractError("error 429".to_string())));
+        assert!(is_rate_limited(&Error::ContractError("Too Many Requests".to_string())));
+    }
+
+    #[test]
+    fn test_is_rate_limited_provider() {
+        let rpc_error = |code: i64, message: &str| {
+            let error = serde_json::json!({ "code": code, "message": message });
+            let error = HttpClientError::JsonRpcError(serde_json::from_value(error).unwrap());
+            Error::ProviderError(ProviderError::JsonRpcClientError(Box::new(error)))
+        };
+        assert!(is_rate_limited(&rpc_error(-32005, "limit exceeded")));
+        assert!(is_rate_limited(&rpc_error(429, "")));
+        assert!(is_rate_limited(&rpc_error(-32000, "rate limit reached")));
+        assert!(!is_rate_limited(&rpc_error(-32005, "query returned more than 10000 results")));
+        assert!(!is_rate_limited(&rpc_error(-32000, "header not found 429")));
+
+        let custom = ProviderError::CustomError("code 429".to_string());
+        assert!(!is_rate_limited(&Error::ProviderError(custom)));
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RequestPolicy::new().backoff(Duration::from_millis(100));
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(3), Duration::from_millis(800));
+        assert_eq!(RequestPolicy::new().backoff(Duration::MAX).delay(1), Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let policy = RequestPolicy::new().retries(2).backoff(Duration::ZERO);
+        let attempts = AtomicU32::new(0);
+        let res = policy
+            .retry(|| async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    0 | 1 => Err(throttled()),
+                    _ => Ok(1),
+                }
+            })
+            .await;
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        // gives up after the retries, and does not retry other errors
+        attempts.store(0, Ordering::Relaxed);
+        let res: Result<()> = policy.retry(|| async { Err(throttled()) }).await;
+        assert!(is_rate_limited(&res.unwrap_err()));
+        let res: Result<()> = policy
+            .retry(|| async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(Error::InvalidPath)
+            })
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "v2", feature = "v3"))]
+    async fn test_limiter() {
+        let limiter = Limiter::new(RequestPolicy::new().max_concurrency(2));
+        let (in_flight, max_in_flight) = (AtomicU32::new(0), AtomicU32::new(0));
+        let requests = (0..8).map(|_| {
+            limiter.run(|| async {
+                let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(n, Ordering::SeqCst);
+                Delay::new(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            })
+        });
+        futures_util::future::try_join_all(requests).await.unwrap();
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,385 @@
+use super::utils::now;
+use ethers_core::types::{Address, Chain, U256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+#[cfg(feature = "v3")]
+use crate::v3::{PoolState, Quote};
+#[cfg(feature = "v3")]
+use ethers_core::types::Bytes;
+
+/// The kind of a cached value, and the calldata of a quote.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Kind {
+    Reserves,
+    #[cfg(feature = "v3")]
+    PoolState,
+    #[cfg(feature = "v3")]
+    Quote(Bytes),
+}
+
+/// The key of a cached value: its chain, the address of its pair, pool or quoter, the block it was
+/// cached at and its kind.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    chain: Chain,
+    address: Address,
+    block: Option<u64>,
+    kind: Kind,
+}
+
+#[derive(Clone, Debug)]
+enum Value {
+    Reserves(U256, U256),
+    #[cfg(feature = "v3")]
+    PoolState(PoolState),
+    #[cfg(feature = "v3")]
+    Quote(Quote),
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    /// When the entry was cached, since the UNIX epoch.
+    inserted: Duration,
+    /// The position of the entry in the LRU order.
+    tick: u64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The latest block number passed to [`QuoteCache::notify_new_block`].
+    block: Option<u64>,
+    entries: HashMap<Key, Entry>,
+    /// The keys of the entries, from the least to the most recently used.
+    order: BTreeMap<u64, Key>,
+    next_tick: u64,
+}
+
+#[derive(Debug)]
+struct Shared {
+    inner: Mutex<Inner>,
+    capacity: usize,
+    ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// A bounded, least recently used cache of the reserves of V2 pairs, the states of V3 pools and
+/// V3 quotes, keyed by chain, address and block. Shared between its clones.
+///
+/// Entries are keyed by the block last passed to [`notify_new_block`][Self::notify_new_block],
+/// which is meant to be called from a block subscription and evicts the entries of the previous
+/// blocks, and expire after the cache's TTL, if any. Without either, entries are only evicted when
+/// the cache is full.
+///
+/// Can be set on a V2 router for the reserves of its swaps and quotes, and on a V3 quoter and a V3
+/// pool, with their `set_quote_cache` methods, so that repeated quotes within a block do not make
+/// any request.
+#[derive(Clone, Debug)]
+pub struct QuoteCache {
+    shared: Arc<Shared>,
+}
+
+impl QuoteCache {
+    /// Creates a new, empty cache of at most `capacity` entries, at least 1.
+    pub fn new(capacity: usize) -> Self {
+        Self::new_with_ttl(capacity, None)
+    }
+
+    /// Creates a new, empty cache of at most `capacity` entries, at least 1, which expire `ttl`
+    /// after being cached.
+    pub fn with_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self::new_with_ttl(capacity, Some(ttl))
+    }
+
+    fn new_with_ttl(capacity: usize, ttl: Option<Duration>) -> Self {
+        let shared = Shared {
+            inner: Default::default(),
+            capacity: capacity.max(1),
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        };
+        Self { shared: Arc::new(shared) }
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Returns the time after which entries expire, if any.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.shared.ttl
+    }
+
+    /// Returns the latest block number passed to [`notify_new_block`][Self::notify_new_block].
+    pub fn block(&self) -> Option<u64> {
+        self.inner().block
+    }
+
+    /// Sets the block of the new entries to `number`, evicting the entries of the other blocks.
+    ///
+    /// Does nothing if `number` is already the current block.
+    pub fn notify_new_block(&self, number: u64) {
+        let mut inner = self.inner();
+        if inner.block == Some(number) {
+            return;
+        }
+        inner.block = Some(number);
+        let Inner { entries, order, .. } = &mut *inner;
+        entries.retain(|key, entry| {
+            let retain = key.block == Some(number);
+            if !retain {
+                order.remove(&entry.tick);
+            }
+            retain
+        });
+    }
+
+    /// Returns the number of cache hits.
+    pub fn hits(&self) -> u64 {
+        self.shared.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cache misses, including expired entries.
+    pub fn misses(&self) -> u64 {
+        self.shared.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cached entries, including the expired ones which have not been evicted
+    /// yet.
+    pub fn len(&self) -> usize {
+        self.inner().entries.len()
+    }
+
+    /// Returns whether no entry is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evicts all the entries, keeping the current block and the counters.
+    pub fn clear(&self) {
+        let mut inner = self.inner();
+        inner.entries.clear();
+        inner.order.clear();
+    }
+
+    /// Returns the cached reserves of `pair` on `chain`, sorted in the order of its tokens.
+    pub fn reserves(&self, chain: Chain, pair: Address) -> Option<(U256, U256)> {
+        match self.get(chain, pair, Kind::Reserves)? {
+            Value::Reserves(reserve0, reserve1) => Some((reserve0, reserve1)),
+            #[cfg(feature = "v3")]
+            _ => None,
+        }
+    }
+
+    /// Caches the reserves of `pair` on `chain`, sorted in the order of its tokens, at the current
+    /// block.
+    pub fn insert_reserves(&self, chain: Chain, pair: Address, reserves: (U256, U256)) {
+        self.insert(chain, pair, Kind::Reserves, Value::Reserves(reserves.0, reserves.1));
+    }
+
+    /// Returns the cached state of `pool` on `chain`.
+    #[cfg(feature = "v3")]
+    pub fn pool_state(&self, chain: Chain, pool: Address) -> Option<PoolState> {
+        match self.get(chain, pool, Kind::PoolState)? {
+            Value::PoolState(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Caches the state of `pool` on `chain` at the current block.
+    #[cfg(feature = "v3")]
+    pub fn insert_pool_state(&self, chain: Chain, pool: Address, state: PoolState) {
+        self.insert(chain, pool, Kind::PoolState, Value::PoolState(state));
+    }
+
+    /// Returns the cached quote of `quoter` on `chain` for the call with `calldata`.
+    #[cfg(feature = "v3")]
+    pub fn quote(&self, chain: Chain, quoter: Address, calldata: &Bytes) -> Option<Quote> {
+        match self.get(chain, quoter, Kind::Quote(calldata.clone()))? {
+            Value::Quote(quote) => Some(quote),
+            _ => None,
+        }
+    }
+
+    /// Caches the quote of `quoter` on `chain` for the call with `calldata` at the current block.
+    #[cfg(feature = "v3")]
+    pub fn insert_quote(&self, chain: Chain, quoter: Address, calldata: Bytes, quote: Quote) {
+        self.insert(chain, quoter, Kind::Quote(calldata), Value::Quote(quote));
+    }
+
+    fn inner(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.shared.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the value of the key at the current block, marking it as the most recently used,
+    /// and counts the hit or miss.
+    fn get(&self, chain: Chain, address: Address, kind: Kind) -> Option<Value> {
+        let mut inner = self.inner();
+        let key = Key { chain, address, block: inner.block, kind };
+        let tick = inner.next_tick;
+        let Inner { entries, order, .. } = &mut *inner;
+        let value = match entries.get_mut(&key) {
+            Some(entry) if self.is_expired(entry) => {
+                order.remove(&entry.tick);
+                entries.remove(&key);
+                None
+            }
+            Some(entry) => {
+                order.remove(&entry.tick);
+                order.insert(tick, key);
+                entry.tick = tick;
+                Some(entry.value.clone())
+            }
+            None => None,
+        };
+        let counter = match value {
+            Some(_) => {
+                inner.next_tick += 1;
+                &self.shared.hits
+            }
+            None => &self.shared.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// Inserts the value of the key at the current block, evicting the least recently used entry
+    /// if the cache is full.
+    fn insert(&self, chain: Chain, address: Address, kind: Kind, value: Value) {
+        let mut inner = self.inner();
+        let key = Key { chain, address, block: inner.block, kind };
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+        let Inner { entries, order, .. } = &mut *inner;
+        if let Some(entry) = entries.remove(&key) {
+            order.remove(&entry.tick);
+        } else if entries.len() >= self.shared.capacity {
+            if let Some(&lru) = order.keys().next() {
+                if let Some(key) = order.remove(&lru) {
+                    entries.remove(&key);
+                }
+            }
+        }
+        order.insert(tick, key.clone());
+        entries.insert(key, Entry { value, inserted: now(), tick });
+    }
+
+    fn is_expired(&self, entry: &Entry) -> bool {
+        self.shared.ttl.map_or(false, |ttl| now().saturating_sub(entry.inserted) >= ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIR_A: Address = Address::repeat_byte(0xaa);
+    const PAIR_B: Address = Address::repeat_byte(0xbb);
+    const PAIR_C: Address = Address::repeat_byte(0xcc);
+
+    fn reserves(n: u64) -> (U256, U256) {
+        (n.into(), (n * 2).into())
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let cache = QuoteCache::new(16);
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), None);
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(1));
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), Some(reserves(1)));
+        assert_eq!(cache.reserves(Chain::Goerli, PAIR_A), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // shared between clones
+        let clone = cache.clone();
+        assert_eq!(clone.reserves(Chain::Mainnet, PAIR_A), Some(reserves(1)));
+        assert_eq!(cache.hits(), 2);
+    }
+
+    #[test]
+    fn test_notify_new_block() {
+        let cache = QuoteCache::new(16);
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(1));
+
+        cache.notify_new_block(100);
+        assert_eq!(cache.block(), Some(100));
+        assert!(cache.is_empty());
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(2));
+        cache.insert_reserves(Chain::Mainnet, PAIR_B, reserves(3));
+
+        cache.notify_new_block(100);
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), Some(reserves(2)));
+
+        cache.notify_new_block(101);
+        assert!(cache.is_empty());
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), None);
+    }
+
+    #[test]
+    fn test_ttl() {
+        let cache = QuoteCache::with_ttl(16, Duration::ZERO);
+        assert_eq!(cache.ttl(), Some(Duration::ZERO));
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(1));
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), None);
+        assert!(cache.is_empty());
+
+        let cache = QuoteCache::with_ttl(16, Duration::from_secs(60));
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(1));
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), Some(reserves(1)));
+    }
+
+    #[test]
+    fn test_lru() {
+        let cache = QuoteCache::new(2);
+        cache.insert_reserves(Chain::Mainnet, PAIR_A, reserves(1));
+        cache.insert_reserves(Chain::Mainnet, PAIR_B, reserves(2));
+
+        // PAIR_A becomes the most recently used, so PAIR_B is evicted
+        assert!(cache.reserves(Chain::Mainnet, PAIR_A).is_some());
+        cache.insert_reserves(Chain::Mainnet, PAIR_C, reserves(3));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_B), None);
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), Some(reserves(1)));
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_C), Some(reserves(3)));
+
+        // replacing an entry does not evict another one
+        cache.insert_reserves(Chain::Mainnet, PAIR_C, reserves(4));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_C), Some(reserves(4)));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "v3")]
+    fn test_v3() {
+        let cache = QuoteCache::new(16);
+        let state = PoolState { tick: 10, liquidity: 1_000, ..Default::default() };
+        cache.insert_pool_state(Chain::Mainnet, PAIR_A, state);
+        assert_eq!(cache.pool_state(Chain::Mainnet, PAIR_A), Some(state));
+        assert_eq!(cache.reserves(Chain::Mainnet, PAIR_A), None);
+
+        let quote = Quote { amount: 1_000.into(), ..Default::default() };
+        let calldata = Bytes::from(vec![1, 2, 3]);
+        cache.insert_quote(Chain::Mainnet, PAIR_B, calldata.clone(), quote.clone());
+        assert_eq!(cache.quote(Chain::Mainnet, PAIR_B, &calldata), Some(quote));
+        assert_eq!(cache.quote(Chain::Mainnet, PAIR_B, &Bytes::from(vec![1, 2])), None);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<QuoteCache>();
+    }
+}
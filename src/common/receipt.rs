@@ -0,0 +1,200 @@
+#[cfg(feature = "v2")]
+use crate::contracts::bindings::i_uniswap_v2_pair::SwapFilter as V2SwapFilter;
+#[cfg(feature = "v3")]
+use crate::contracts::bindings::i_uniswap_v3_pool::SwapFilter as V3SwapFilter;
+use crate::{
+    errors::{Error, Result},
+    utils::to_f64,
+};
+use ethers_core::{
+    abi::RawLog,
+    types::{TransactionReceipt, H256, U256},
+};
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+use crate::utils::require_signer;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_contract::{builders::ContractCall, ContractError, EthEvent};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::{abi::Detokenize, types::transaction::eip2718::TypedTransaction};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_providers::Middleware;
+
+/// The amounts of an executed swap, parsed from the pair and pool `Swap` events of its transaction
+/// receipt.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SwapReceipt {
+    /// The hash of the transaction.
+    pub tx_hash: H256,
+    /// The input amount of each hop.
+    pub amounts_in: Vec<U256>,
+    /// The output amount of each hop.
+    pub amounts_out: Vec<U256>,
+    /// The output amount of the last hop per input amount of the first hop, in the tokens'
+    /// smallest units.
+    pub effective_price: f64,
+    /// The gas used by the transaction.
+    pub gas_used: U256,
+}
+
+impl SwapReceipt {
+    /// Parses the Uniswap V2 pair and Uniswap V3 pool `Swap` events of `receipt`, in order.
+    ///
+    /// Returns [`Error::NoSwapEvents`] if the receipt does not contain any.
+    pub fn from_receipt(receipt: &TransactionReceipt) -> Result<Self> {
+        let mut amounts_in = Vec::new();
+        let mut amounts_out = Vec::new();
+        for log in &receipt.logs {
+            let log = RawLog::from((log.topics.clone(), log.data.to_vec()));
+            let (amount_in, amount_out) = match swap_amounts(&log) {
+                Some(amounts) => amounts,
+                None => continue,
+            };
+            amounts_in.push(amount_in);
+            amounts_out.push(amount_out);
+        }
+
+        let (first, last) = match (amounts_in.first(), amounts_out.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return Err(Error::NoSwapEvents),
+        };
+        Ok(Self {
+            tx_hash: receipt.transaction_hash,
+            effective_price: to_f64(last) / to_f64(first),
+            amounts_in,
+            amounts_out,
+            gas_used: receipt.gas_used.unwrap_or_default(),
+        })
+    }
+
+    /// The input amount of the first hop.
+    pub fn amount_in(&self) -> U256 {
+        self.amounts_in.first().copied().unwrap_or_default()
+    }
+
+    /// The output amount of the last hop.
+    pub fn amount_out(&self) -> U256 {
+        self.amounts_out.last().copied().unwrap_or_default()
+    }
+}
+
+/// Decodes the input and output amounts of a Uniswap V2 pair or Uniswap V3 pool `Swap` event.
+fn swap_amounts(log: &RawLog) -> Option<(U256, U256)> {
+    #[cfg(feature = "v2")]
+    if let Ok(swap) = V2SwapFilter::decode_log(log) {
+        return Some((swap.amount_0_in + swap.amount_1_in, swap.amount_0_out + swap.amount_1_out));
+    }
+    #[cfg(feature = "v3")]
+    if let Ok(swap) = V3SwapFilter::decode_log(log) {
+        // positive amounts are paid to the pool, negative ones are paid by the pool
+        let (amount_in, amount_out) = if swap.amount_0.is_positive() {
+            (swap.amount_0, swap.amount_1)
+        } else {
+            (swap.amount_1, swap.amount_0)
+        };
+        return Some((amount_in.twos_complement(), amount_out.twos_complement()));
+    }
+    let _ = log;
+    None
+}
+
+/// Sends `call`, waits for `confirmations` confirmations and parses the swap from its receipt.
+///
+/// If the transaction reverts, it is replayed with `eth_call` at the block in which it was
+/// included to decode the revert into an [Error].
+///
+/// Returns [`Error::SignerRequired`] if the call has no sender and the client is read-only.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn execute_swap<M: Middleware, D: Detokenize>(
+    client: &M,
+    call: ContractCall<M, D>,
+    confirmations: usize,
+) -> Result<SwapReceipt> {
+    if call.tx.from().is_none() {
+        require_signer(client, "Executing a swap")?;
+    }
+    let pending = call.send().await?;
+    let tx_hash = *pending;
+    let receipt =
+        pending.confirmations(confirmations).await?.ok_or(Error::TransactionDropped(tx_hash))?;
+    parse_swap_receipt(client, &call.tx, &receipt).await
+}
+
+/// Parses the swap from the `receipt` of `tx`, replaying the transaction to decode its revert if
+/// it failed.
+#[cfg(any(feature = "v2", feature = "v3"))]
+pub(crate) async fn parse_swap_receipt<M: Middleware>(
+    client: &M,
+    tx: &TypedTransaction,
+    receipt: &TransactionReceipt,
+) -> Result<SwapReceipt> {
+    if receipt.status == Some(0.into()) {
+        let mut tx = tx.clone();
+        tx.set_from(receipt.from);
+        let block = receipt.block_number.map(Into::into);
+        return Err(match client.call(&tx, block).await {
+            Err(e) => ContractError::<M>::MiddlewareError(e).into(),
+            // the state of the end of the block may differ from that of the transaction
+            Ok(_) => Error::Reverted(String::new()),
+        });
+    }
+    SwapReceipt::from_receipt(receipt)
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "v2", feature = "v3"))]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Log, I256},
+    };
+
+    fn v2_log(amounts: [u64; 4]) -> Log {
+        Log {
+            topics: vec![V2SwapFilter::signature(), H256::zero(), H256::zero()],
+            data: abi::encode(&amounts.map(|a| Token::Uint(a.into()))).into(),
+            ..Default::default()
+        }
+    }
+
+    fn v3_log(amount_0: i64, amount_1: i64) -> Log {
+        let tokens = [
+            Token::Int(I256::from(amount_0).into_raw()),
+            Token::Int(I256::from(amount_1).into_raw()),
+            Token::Uint(U256::one() << 96),
+            Token::Uint(1_000.into()),
+            Token::Int(0.into()),
+        ];
+        Log {
+            topics: vec![V3SwapFilter::signature(), H256::zero(), H256::zero()],
+            data: abi::encode(&tokens).into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_receipt() {
+        let transfer = Log { topics: vec![H256::repeat_byte(1)], ..Default::default() };
+
+        // A -> B through a pair, B -> C through a pool
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(2),
+            logs: vec![transfer.clone(), v2_log([0, 100, 50, 0]), v3_log(-20, 50)],
+            gas_used: Some(150_000.into()),
+            ..Default::default()
+        };
+        let swap = SwapReceipt::from_receipt(&receipt).unwrap();
+        assert_eq!(swap.tx_hash, H256::repeat_byte(2));
+        assert_eq!(swap.amounts_in, vec![100.into(), 50.into()]);
+        assert_eq!(swap.amounts_out, vec![50.into(), 20.into()]);
+        assert_eq!((swap.amount_in(), swap.amount_out()), (100.into(), 20.into()));
+        assert_eq!(swap.effective_price, 0.2);
+        assert_eq!(swap.gas_used, 150_000.into());
+
+        let receipt = TransactionReceipt { logs: vec![transfer], ..Default::default() };
+        let res = SwapReceipt::from_receipt(&receipt);
+        assert!(matches!(res.unwrap_err(), Error::NoSwapEvents));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::errors::{Error, Result};
+use ethers_contract::ContractError;
+use ethers_core::types::{Address, Chain, U256};
+use ethers_providers::Middleware;
+use std::fmt;
+
+/// The chains on which the ENS registry used by [`Middleware::resolve_name`] is deployed.
+const ENS_CHAINS: [Chain; 5] =
+    [Chain::Mainnet, Chain::Ropsten, Chain::Rinkeby, Chain::Goerli, Chain::Sepolia];
+
+/// The recipient of the output of a swap or of a liquidity operation.
+///
+/// ENS names and the signer are resolved through the client when building the call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Recipient {
+    /// An address.
+    Address(Address),
+    /// An ENS name, like `vitalik.eth`.
+    Ens(String),
+    /// The client's default sender.
+    #[default]
+    Signer,
+}
+
+impl From<Address> for Recipient {
+    fn from(address: Address) -> Self {
+        Self::Address(address)
+    }
+}
+
+/// The address, or the client's default sender if None.
+impl From<Option<Address>> for Recipient {
+    fn from(address: Option<Address>) -> Self {
+        address.map(Self::Address).unwrap_or_default()
+    }
+}
+
+/// A hex address, or an ENS name otherwise.
+impl From<&str> for Recipient {
+    fn from(s: &str) -> Self {
+        match s.parse() {
+            Ok(address) => Self::Address(address),
+            Err(_) => Self::Ens(s.to_string()),
+        }
+    }
+}
+
+/// A hex address, or an ENS name otherwise.
+impl From<String> for Recipient {
+    fn from(s: String) -> Self {
+        match s.parse() {
+            Ok(address) => Self::Address(address),
+            Err(_) => Self::Ens(s),
+        }
+    }
+}
+
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{address:?}"),
+            Self::Ens(name) => f.write_str(name),
+            Self::Signer => f.write_str("signer"),
+        }
+    }
+}
+
+impl Recipient {
+    /// An ENS `name`.
+    #[inline]
+    pub fn ens(name: impl Into<String>) -> Self {
+        Self::Ens(name.into())
+    }
+
+    /// Resolves the recipient into an address. Addresses are returned as is, without making any
+    /// calls.
+    ///
+    /// Returns [`Error::MissingSender`] for the signer if the client does not have a default
+    /// sender, and [`Error::EnsResolutionFailed`] if an ENS name cannot be resolved, or
+    /// [`Error::EnsNotSupported`] if the client's chain does not have ENS.
+    pub async fn resolve<M: Middleware>(&self, client: &M) -> Result<Address> {
+        let name = match self {
+            Self::Address(address) => return Ok(*address),
+            Self::Signer => return client.default_sender().ok_or(Error::MissingSender),
+            Self::Ens(name) => name,
+        };
+        let reason = match client.resolve_name(name).await {
+            Ok(address) if !address.is_zero() => return Ok(address),
+            Ok(_) => "no address is set".to_string(),
+            Err(e) => e.to_string(),
+        };
+        let chain_id = client.get_chainid().await.map_err(ContractError::<M>::MiddlewareError)?;
+        if ENS_CHAINS.iter().any(|&chain| U256::from(u64::from(chain)) == chain_id) {
+            Err(Error::EnsResolutionFailed { name: name.clone(), reason })
+        } else {
+            Err(Error::EnsNotSupported(chain_id))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{abi, abi::Token, types::Bytes};
+    use ethers_middleware::SignerMiddleware;
+    use ethers_providers::{MockProvider, Provider};
+    use ethers_signers::Signer;
+
+    #[test]
+    fn test_from() {
+        let address = Address::repeat_byte(1);
+        assert_eq!(Recipient::from(address), Recipient::Address(address));
+        assert_eq!(Recipient::from(Some(address)), Recipient::Address(address));
+        assert_eq!(Recipient::from(None), Recipient::Signer);
+        let hex = format!("{address:?}");
+        assert_eq!(Recipient::from(hex.as_str()), Recipient::Address(address));
+        assert_eq!(Recipient::from(hex), Recipient::Address(address));
+        assert_eq!(Recipient::from("vitalik.eth"), Recipient::ens("vitalik.eth"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let address = Address::repeat_byte(1);
+        assert_eq!(Recipient::from(address).resolve(&provider).await.unwrap(), address);
+
+        let res = Recipient::Signer.resolve(&provider).await;
+        assert!(matches!(res.unwrap_err(), Error::MissingSender));
+        let wallet = crate::testing::default_wallet();
+        let sender = wallet.address();
+        let client = SignerMiddleware::new(provider.clone(), wallet);
+        assert_eq!(Recipient::Signer.resolve(&client).await.unwrap(), sender);
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 3. `addr` of the resolver
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(address)]))).unwrap();
+        // 2. `supportsInterface` of the resolver
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Bool(true)]))).unwrap();
+        // 1. `resolver` of the registry
+        let resolver = Address::repeat_byte(2);
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(resolver)]))).unwrap();
+        let name = Recipient::ens("vitalik.eth");
+        assert_eq!(name.resolve(&provider).await.unwrap(), address);
+
+        // a chain without the registry, where the call returns no data
+        mock.push(U256::from(u64::from(Chain::Polygon))).unwrap();
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        let res = name.resolve(&provider).await;
+        assert!(matches!(res.unwrap_err(), Error::EnsNotSupported(id) if id == 137.into()));
+
+        // a name without a resolver
+        mock.push(U256::one()).unwrap();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(Address::zero())])))
+            .unwrap();
+        let res = name.resolve(&provider).await;
+        assert!(matches!(res.unwrap_err(), Error::EnsResolutionFailed { .. }));
+    }
+}
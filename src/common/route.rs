@@ -0,0 +1,55 @@
+use ethers_core::types::Address;
+
+/// The path of tokens taken by a swap.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Route {
+    /// An explicit path.
+    Path(Vec<Address>),
+    /// The path with the best output between two tokens, either direct or through the router's
+    /// base tokens.
+    Auto {
+        /// The input token.
+        token_in: Address,
+        /// The output token.
+        token_out: Address,
+    },
+}
+
+impl Route {
+    /// Finds the path with the best output between `token_in` and `token_out`.
+    #[inline]
+    pub const fn auto(token_in: Address, token_out: Address) -> Self {
+        Self::Auto { token_in, token_out }
+    }
+}
+
+impl From<Vec<Address>> for Route {
+    fn from(path: Vec<Address>) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&Vec<Address>> for Route {
+    fn from(path: &Vec<Address>) -> Self {
+        Self::Path(path.clone())
+    }
+}
+
+impl From<&[Address]> for Route {
+    fn from(path: &[Address]) -> Self {
+        Self::Path(path.to_vec())
+    }
+}
+
+impl<const N: usize> From<[Address; N]> for Route {
+    fn from(path: [Address; N]) -> Self {
+        Self::Path(path.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[Address; N]> for Route {
+    fn from(path: &[Address; N]) -> Self {
+        Self::Path(path.to_vec())
+    }
+}
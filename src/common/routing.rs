@@ -0,0 +1,688 @@
+//! Offline best-path search over a graph of Uniswap V2 pairs, and splitting of orders across
+//! parallel pools.
+
+use crate::{
+    common::{call_multi_chunked, watch_logs},
+    contracts::bindings::i_uniswap_v2_pair::{IUniswapV2Pair, SyncFilter},
+    errors::{Error, Result},
+    v2::{Library, PairRegistry},
+    v3::{
+        math::{
+            mul_div,
+            swap::{simulate, TickData},
+        },
+        FeeAmount, PoolState,
+    },
+    DecodedLog, RequestPolicy,
+};
+#[cfg(feature = "universal-router")]
+use crate::{universal_router::Route, v3::Path};
+use ethers_contract::EthEvent;
+use ethers_core::types::{Address, Chain, Filter, I256, U256};
+use ethers_providers::Middleware;
+use futures_util::{Stream, StreamExt};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+
+/// The maximum number of hops searched by [`Graph::best_path`].
+pub const MAX_GRAPH_HOPS: usize = 4;
+
+/// The maximum number of parts an order can be split in by [`split`].
+pub const MAX_SPLIT_PARTS: usize = 100;
+
+/// A pair of a [Graph], with its reserves and swap fee.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Edge {
+    /// The address of the pair.
+    pub pair: Address,
+    /// The first token of the pair, sorted.
+    pub token0: Address,
+    /// The second token of the pair, sorted.
+    pub token1: Address,
+    /// The reserve of `token0`, zero if unknown.
+    pub reserve0: U256,
+    /// The reserve of `token1`, zero if unknown.
+    pub reserve1: U256,
+    /// The swap fee of the pair, in basis points.
+    pub fee_bps: u32,
+    /// The block number and log index of the last `Sync` event applied to the reserves, if any.
+    pub last_sync: Option<(u64, u64)>,
+}
+
+impl Edge {
+    /// Returns the other token of the pair and the reserves in the direction of a swap from
+    /// `token_in`.
+    fn direction(&self, token_in: Address) -> (Address, U256, U256) {
+        if token_in == self.token0 {
+            (self.token1, self.reserve0, self.reserve1)
+        } else {
+            (self.token0, self.reserve1, self.reserve0)
+        }
+    }
+}
+
+/// A path found by [`Graph::best_path`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GraphPath {
+    /// The tokens of the path, from the input to the output token.
+    pub tokens: Vec<Address>,
+    /// The pairs of the path, one per hop.
+    pub pairs: Vec<Address>,
+    /// The amounts of the path, like those of the router's `getAmountsOut`.
+    pub amounts: Vec<U256>,
+}
+
+impl GraphPath {
+    /// Returns the output amount.
+    pub fn amount_out(&self) -> U256 {
+        self.amounts.last().copied().unwrap_or_default()
+    }
+}
+
+/// A graph of Uniswap V2 pairs, in which the tokens are the nodes and the pairs are the edges,
+/// used to quote swaps through many pairs without fetching anything.
+///
+/// The graph is built from one or more [PairRegistry], possibly of different factories. The
+/// reserves are fetched once with [`fetch_reserves`][Self::fetch_reserves], then kept up to date
+/// with [`update_reserves`][Self::update_reserves] from the pairs' `Sync` events, e.g. from
+/// [`watch_syncs`][Self::watch_syncs].
+///
+/// Note: only the constant product invariant is used, so pairs of Solidly stable forks must not
+/// be added.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Graph {
+    /// The edges.
+    edges: Vec<Edge>,
+    /// The indexes of the edges, keyed by pair address.
+    pairs: HashMap<Address, usize>,
+    /// The indexes of the edges of each token.
+    adjacency: HashMap<Address, Vec<usize>>,
+}
+
+impl Graph {
+    /// Creates a new graph from the pairs of `registry`, whose swap fee is `fee_bps`.
+    pub fn from_registry(registry: &PairRegistry, fee_bps: u32) -> Self {
+        let mut graph = Self::default();
+        graph.extend(registry, fee_bps);
+        graph
+    }
+
+    /// Adds the pairs of `registry`, whose swap fee is `fee_bps`. Pairs already in the graph are
+    /// skipped.
+    pub fn extend(&mut self, registry: &PairRegistry, fee_bps: u32) {
+        for pair in registry.iter() {
+            let (token0, token1) = Library::sort_tokens(pair.token0, pair.token1);
+            self.insert(Edge { pair: pair.address, token0, token1, fee_bps, ..Default::default() });
+        }
+    }
+
+    /// Inserts `edge`, returning false if its pair is already in the graph or if its tokens are
+    /// the same.
+    pub fn insert(&mut self, edge: Edge) -> bool {
+        if edge.token0 == edge.token1 || self.pairs.contains_key(&edge.pair) {
+            return false;
+        }
+        let index = self.edges.len();
+        self.pairs.insert(edge.pair, index);
+        self.adjacency.entry(edge.token0).or_default().push(index);
+        self.adjacency.entry(edge.token1).or_default().push(index);
+        self.edges.push(edge);
+        true
+    }
+
+    /// Returns the edge of `pair`.
+    pub fn get(&self, pair: Address) -> Option<&Edge> {
+        self.pairs.get(&pair).map(|&index| &self.edges[index])
+    }
+
+    /// Returns the number of pairs in the graph.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns whether the graph does not contain any pairs.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns an iterator over the edges, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.iter()
+    }
+
+    /// Sets the reserves of `pair`, sorted by its tokens, returning false if it is not in the
+    /// graph.
+    pub fn set_reserves(&mut self, pair: Address, reserve0: U256, reserve1: U256) -> bool {
+        match self.pairs.get(&pair) {
+            Some(&index) => {
+                let edge = &mut self.edges[index];
+                (edge.reserve0, edge.reserve1) = (reserve0, reserve1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies a `Sync` event to the reserves of its pair, returning false if the pair is not in
+    /// the graph or if a later event was already applied.
+    ///
+    /// The reserves of a pair whose event was removed by a chain reorganization are unknown until
+    /// its next event or [fetch][Self::fetch_reserves], so the pair is skipped in the meantime.
+    pub fn update_reserves(&mut self, sync: &DecodedLog<SyncFilter>) -> bool {
+        let edge = match self.pairs.get(&sync.meta.address) {
+            Some(&index) => &mut self.edges[index],
+            None => return false,
+        };
+        let position = (sync.meta.block_number.as_u64(), sync.meta.log_index.as_u64());
+        if sync.removed {
+            (edge.reserve0, edge.reserve1, edge.last_sync) = (U256::zero(), U256::zero(), None);
+            return true;
+        }
+        if edge.last_sync.map_or(false, |last| last >= position) {
+            return false;
+        }
+        edge.reserve0 = sync.event.reserve_0.into();
+        edge.reserve1 = sync.event.reserve_1.into();
+        edge.last_sync = Some(position);
+        true
+    }
+
+    /// Fetches the reserves of all the pairs in [Multicall][crate::utils::Multicall] requests of
+    /// at most `chunk_size` calls each, with at most `concurrency` requests in flight and throttled
+    /// requests retried like with the default [RequestPolicy].
+    pub async fn fetch_reserves<M: Middleware>(
+        &mut self,
+        client: Arc<M>,
+        chain: Option<Chain>,
+        chunk_size: usize,
+        concurrency: usize,
+    ) -> Result<()> {
+        let pair = IUniswapV2Pair::new(Address::zero(), client.clone());
+        let calls = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let mut call = pair.get_reserves();
+                call.tx.set_to(edge.pair);
+                call
+            })
+            .collect();
+        let policy = RequestPolicy::new().max_concurrency(concurrency);
+        let reserves = call_multi_chunked(client, chain, calls, chunk_size, policy).await?;
+        for (edge, (reserve0, reserve1, _)) in self.edges.iter_mut().zip(reserves) {
+            (edge.reserve0, edge.reserve1) = (reserve0.into(), reserve1.into());
+        }
+        Ok(())
+    }
+
+    /// Returns a stream of the `Sync` events of all the pairs in the graph, by polling a filter at
+    /// the client's polling interval, to be applied with
+    /// [`update_reserves`][Self::update_reserves].
+    pub async fn watch_syncs<M: Middleware + 'static>(
+        &self,
+        client: Arc<M>,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<SyncFilter>>> + Unpin> {
+        let addresses: Vec<_> = self.edges.iter().map(|edge| edge.pair).collect();
+        let filter = Filter::new().address(addresses).topic0(SyncFilter::signature());
+        let logs = watch_logs(client, &filter).await?;
+        Ok(logs.map(|log| DecodedLog::decode(&log?)))
+    }
+
+    /// Returns the `k` paths with the best output for `amount_in` from `token_in` to `token_out`,
+    /// the best first, using the reserves in the graph.
+    ///
+    /// All the paths of up to `max_hops` pairs, capped at [`MAX_GRAPH_HOPS`], are searched. Paths
+    /// never visit a token twice, so they contain no cycles. Pairs with unknown reserves or
+    /// insufficient liquidity are skipped. Returns an empty vector if no path is found.
+    pub fn best_path(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        max_hops: usize,
+        k: usize,
+    ) -> Vec<GraphPath> {
+        let mut paths = Vec::new();
+        if token_in == token_out || amount_in.is_zero() || k == 0 {
+            return paths;
+        }
+        let mut path =
+            GraphPath { tokens: vec![token_in], pairs: Vec::new(), amounts: vec![amount_in] };
+        self.search(&mut path, token_out, max_hops.min(MAX_GRAPH_HOPS), &mut paths);
+        paths.sort_by_key(|path| Reverse(path.amount_out()));
+        paths.truncate(k);
+        paths
+    }
+
+    /// Extends `path` with every pair of its last token, depth first, collecting the paths which
+    /// reach `token_out`.
+    fn search(
+        &self,
+        path: &mut GraphPath,
+        token_out: Address,
+        max_hops: usize,
+        paths: &mut Vec<GraphPath>,
+    ) {
+        let (token, amount) = (path.tokens[path.tokens.len() - 1], path.amount_out());
+        let edges = match self.adjacency.get(&token) {
+            Some(edges) => edges,
+            None => return,
+        };
+        for &index in edges {
+            let edge = &self.edges[index];
+            let (next, reserve_in, reserve_out) = edge.direction(token);
+            if path.tokens.contains(&next) {
+                continue;
+            }
+            let amount_out = match Library::get_amount_out_with_fee(
+                amount,
+                reserve_in,
+                reserve_out,
+                edge.fee_bps,
+            ) {
+                Ok(amount_out) if !amount_out.is_zero() => amount_out,
+                _ => continue,
+            };
+
+            path.tokens.push(next);
+            path.pairs.push(edge.pair);
+            path.amounts.push(amount_out);
+            if next == token_out {
+                paths.push(path.clone());
+            } else if path.pairs.len() < max_hops {
+                self.search(path, token_out, max_hops, paths);
+            }
+            path.tokens.pop();
+            path.pairs.pop();
+            path.amounts.pop();
+        }
+    }
+}
+
+/// A pool which swaps the same tokens as the others passed to [`split`], with the state needed
+/// to compute its output offline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PoolQuoteSource {
+    /// A Uniswap V2 pair.
+    V2 {
+        /// The address of the pair.
+        pair: Address,
+        /// The input token.
+        token_in: Address,
+        /// The output token.
+        token_out: Address,
+        /// The reserve of the input token.
+        reserve_in: U256,
+        /// The reserve of the output token.
+        reserve_out: U256,
+        /// The swap fee of the pair, in basis points.
+        fee_bps: u32,
+    },
+    /// A Uniswap V3 pool.
+    V3 {
+        /// The address of the pool.
+        pool: Address,
+        /// The input token.
+        token_in: Address,
+        /// The output token.
+        token_out: Address,
+        /// The fee of the pool.
+        fee: FeeAmount,
+        /// The state of the pool.
+        state: PoolState,
+        /// The initialized ticks of the pool, loaded far enough from the current tick for the
+        /// swapped amounts.
+        ticks: TickData,
+    },
+}
+
+impl PoolQuoteSource {
+    /// Returns the address of the pool.
+    pub fn address(&self) -> Address {
+        match self {
+            Self::V2 { pair, .. } => *pair,
+            Self::V3 { pool, .. } => *pool,
+        }
+    }
+
+    /// Returns the input and output tokens of the pool.
+    pub fn tokens(&self) -> (Address, Address) {
+        match self {
+            Self::V2 { token_in, token_out, .. } | Self::V3 { token_in, token_out, .. } => {
+                (*token_in, *token_out)
+            }
+        }
+    }
+
+    /// Returns the output of a swap of `amount_in` through the pool.
+    ///
+    /// Returns zero for a zero amount, and [`Error::InsufficientLiquidity`] if a V3 pool runs out
+    /// of liquidity before swapping the whole amount.
+    pub fn amount_out(&self, amount_in: U256) -> Result<U256> {
+        if amount_in.is_zero() {
+            return Ok(U256::zero());
+        }
+        match self {
+            Self::V2 { reserve_in, reserve_out, fee_bps, .. } => {
+                Library::get_amount_out_with_fee(amount_in, *reserve_in, *reserve_out, *fee_bps)
+            }
+            Self::V3 { token_in, token_out, fee, state, ticks, .. } => {
+                let amount = I256::try_from(amount_in).map_err(|_| Error::Overflow)?;
+                let zero_for_one = token_in < token_out;
+                let swap = simulate(state, *fee, ticks, amount, zero_for_one, None)?;
+                if swap.amount_in < amount_in {
+                    return Err(Error::InsufficientLiquidity);
+                }
+                Ok(swap.amount_out)
+            }
+        }
+    }
+
+    /// Returns the Universal Router route of a swap through the pool.
+    #[cfg(feature = "universal-router")]
+    pub fn route(&self) -> Route {
+        match self {
+            Self::V2 { token_in, token_out, .. } => Route::V2 { path: vec![*token_in, *token_out] },
+            Self::V3 { token_in, token_out, fee, .. } => {
+                Route::V3 { path: Path::single(*token_in, *token_out, *fee) }
+            }
+        }
+    }
+}
+
+/// The allocation of an order across pools, returned by [`split`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Split {
+    /// The amount swapped through each pool, in the order of the pools, which sum to the order's
+    /// amount.
+    pub allocations: Vec<U256>,
+    /// The output of each pool for its allocation.
+    pub amounts_out: Vec<U256>,
+    /// The combined output of the pools.
+    pub amount_out: U256,
+}
+
+impl Split {
+    /// Returns the route and amount of each pool with a non-zero allocation, for
+    /// [`CommandBuilder::swap_split`][crate::universal_router::CommandBuilder::swap_split].
+    #[cfg(feature = "universal-router")]
+    pub fn legs(&self, pools: &[PoolQuoteSource]) -> Vec<(Route, U256)> {
+        pools
+            .iter()
+            .zip(&self.allocations)
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(pool, amount)| (pool.route(), *amount))
+            .collect()
+    }
+}
+
+/// Returns the allocation of an exact input swap of `amount` across `pools` with the best combined
+/// output, in increments of `1 / parts` of the amount.
+///
+/// Like 1inch's, the search computes the output of each pool for every multiple of the increment,
+/// then finds the best combination with dynamic programming, so it is exact at that granularity
+/// regardless of the shape of the pools' output functions. Pools which run out of liquidity for an
+/// allocation are not allocated it. Any rounding remainder is added to the largest allocation.
+///
+/// Returns [`Error::ZeroAmount`] if `amount` or `parts` is zero, [`Error::InvalidPath`] if there
+/// are no pools or they do not swap the same tokens, and [`Error::InsufficientLiquidity`] if the
+/// pools cannot swap the whole amount. `parts` is capped at [`MAX_SPLIT_PARTS`].
+pub fn split(amount: U256, pools: &[PoolQuoteSource], parts: usize) -> Result<Split> {
+    if amount.is_zero() || parts == 0 {
+        return Err(Error::ZeroAmount);
+    }
+    let tokens = match pools.first() {
+        Some(pool) => pool.tokens(),
+        None => return Err(Error::InvalidPath),
+    };
+    if pools.iter().any(|pool| pool.tokens() != tokens) {
+        return Err(Error::InvalidPath);
+    }
+    let parts = parts.min(MAX_SPLIT_PARTS);
+    let portion = |k: usize| mul_div(amount, k.into(), parts.into());
+
+    // the output of each pool for each number of parts
+    let mut outputs = Vec::with_capacity(pools.len());
+    for pool in pools {
+        let mut row = Vec::with_capacity(parts + 1);
+        for k in 0..=parts {
+            row.push(pool.amount_out(portion(k)?).ok());
+        }
+        outputs.push(row);
+    }
+
+    // best[j]: the best output of `j` parts across the pools so far, and `choices[i][j]` the parts
+    // of pool `i` in it
+    let mut best = outputs[0].clone();
+    let mut choices = vec![(0..=parts).collect::<Vec<_>>()];
+    for row in &outputs[1..] {
+        let mut next = vec![None; parts + 1];
+        let mut choice = vec![0; parts + 1];
+        for j in 0..=parts {
+            for k in 0..=j {
+                let output = match (best[j - k], row[k]) {
+                    (Some(a), Some(b)) => a + b,
+                    _ => continue,
+                };
+                if next[j].map_or(true, |best| output > best) {
+                    next[j] = Some(output);
+                    choice[j] = k;
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+    if best[parts].is_none() {
+        return Err(Error::InsufficientLiquidity);
+    }
+
+    let mut shares = vec![0; pools.len()];
+    let mut remaining = parts;
+    for (i, choice) in choices.iter().enumerate().rev() {
+        shares[i] = choice[remaining];
+        remaining -= shares[i];
+    }
+    let mut allocations = shares.iter().map(|&k| portion(k)).collect::<Result<Vec<_>>>()?;
+    let allocated = allocations.iter().fold(U256::zero(), |total, amount| total + amount);
+    let largest = (0..shares.len()).max_by_key(|&i| shares[i]).unwrap_or_default();
+    allocations[largest] += amount - allocated;
+
+    let amounts_out = pools
+        .iter()
+        .zip(&allocations)
+        .map(|(pool, amount)| pool.amount_out(*amount))
+        .collect::<Result<Vec<_>>>()?;
+    let amount_out = amounts_out.iter().fold(U256::zero(), |total, amount| total + amount);
+    Ok(Split { allocations, amounts_out, amount_out })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{v2::PairInfo, v3::math::Q96};
+    use ethers_core::{
+        abi::{self, Token},
+        types::Log,
+    };
+
+    const A: Address = Address::repeat_byte(1);
+    const B: Address = Address::repeat_byte(2);
+    const C: Address = Address::repeat_byte(3);
+    const D: Address = Address::repeat_byte(4);
+
+    fn e18(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(18)
+    }
+
+    fn pair(token_a: Address, token_b: Address) -> Address {
+        Address::from_low_u64_be(u64::from(token_a[0]) << 8 | u64::from(token_b[0]))
+    }
+
+    /// A -- B -- C -- D, with shallow direct pairs A -- D and A -- C.
+    fn graph() -> Graph {
+        let mut registry = PairRegistry::new(0);
+        let pairs = [(A, B), (B, C), (C, D), (A, D), (A, C)];
+        for (token0, token1) in pairs {
+            let address = pair(token0, token1);
+            registry.insert(PairInfo { token0, token1, address, block_number: 0 });
+        }
+        let mut graph = Graph::from_registry(&registry, 30);
+        for (token0, token1) in &pairs[..3] {
+            graph.set_reserves(pair(*token0, *token1), e18(10_000), e18(10_000));
+        }
+        graph.set_reserves(pair(A, D), e18(10), e18(10));
+        graph.set_reserves(pair(A, C), e18(100), e18(100));
+        graph
+    }
+
+    fn sync(
+        pair: Address,
+        reserves: [u64; 2],
+        block: u64,
+        removed: bool,
+    ) -> DecodedLog<SyncFilter> {
+        let log = Log {
+            address: pair,
+            topics: vec![SyncFilter::signature()],
+            data: abi::encode(&reserves.map(|r| Token::Uint(e18(r)))).into(),
+            block_number: Some(block.into()),
+            log_index: Some(0.into()),
+            removed: Some(removed),
+            ..Default::default()
+        };
+        DecodedLog::decode(&log).unwrap()
+    }
+
+    #[test]
+    fn test_best_path() {
+        let graph = graph();
+        assert_eq!(graph.len(), 5);
+
+        let paths = graph.best_path(A, D, e18(1), 4, 10);
+        let tokens: Vec<_> = paths.iter().map(|path| path.tokens.clone()).collect();
+        assert_eq!(tokens, vec![vec![A, B, C, D], vec![A, C, D], vec![A, D]]);
+        for path in &paths {
+            assert_eq!(path.pairs.len() + 1, path.tokens.len());
+            let reserves: Vec<_> = path
+                .pairs
+                .iter()
+                .zip(&path.tokens)
+                .map(|(&pair, &token)| {
+                    let (_, reserve_in, reserve_out) = graph.get(pair).unwrap().direction(token);
+                    (reserve_in, reserve_out)
+                })
+                .collect();
+            let amounts = Library::get_amounts_out_with_reserves(e18(1), &reserves, 30).unwrap();
+            assert_eq!(path.amounts, amounts);
+        }
+
+        // top-k and max hops
+        let paths = graph.best_path(A, D, e18(1), 2, 1);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].tokens, vec![A, C, D]);
+        assert!(graph.best_path(A, A, e18(1), 4, 10).is_empty());
+        assert!(graph.best_path(A, Address::zero(), e18(1), 4, 10).is_empty());
+    }
+
+    #[test]
+    fn test_update_reserves() {
+        let mut graph = graph();
+        let direct = pair(A, D);
+
+        // the direct pair becomes the deepest
+        assert!(graph.update_reserves(&sync(direct, [1_000_000, 1_000_000], 2, false)));
+        assert_eq!(graph.best_path(A, D, e18(1), 4, 1)[0].tokens, vec![A, D]);
+
+        // stale and unknown events are ignored
+        assert!(!graph.update_reserves(&sync(direct, [10, 10], 1, false)));
+        assert!(!graph.update_reserves(&sync(Address::zero(), [10, 10], 3, false)));
+        assert_eq!(graph.get(direct).unwrap().reserve0, e18(1_000_000));
+
+        // a removed event makes the reserves unknown
+        assert!(graph.update_reserves(&sync(direct, [1_000_000, 1_000_000], 2, true)));
+        assert!(graph.best_path(A, D, e18(1), 4, 10).iter().all(|path| path.tokens != [A, D]));
+    }
+
+    #[test]
+    fn test_split() {
+        let v2 = |pair: u64, reserve: u64| PoolQuoteSource::V2 {
+            pair: Address::from_low_u64_be(pair),
+            token_in: A,
+            token_out: B,
+            reserve_in: e18(reserve),
+            reserve_out: e18(reserve),
+            fee_bps: 30,
+        };
+        // liquidity of 10**22 from tick -6000 to 6000, at a price of 1
+        let mut ticks = TickData::new(60);
+        let liquidity = 10i128.pow(22);
+        for (tick, liquidity_net) in [(-6000, liquidity), (6000, -liquidity)] {
+            let compressed: i32 = tick / 60;
+            let bits = ticks.words.entry(TickData::word_position(tick, 60)).or_default();
+            *bits |= U256::one() << compressed.rem_euclid(256);
+            ticks.liquidity_net.insert(tick, liquidity_net);
+        }
+        let v3 = PoolQuoteSource::V3 {
+            pool: Address::from_low_u64_be(3),
+            token_in: A,
+            token_out: B,
+            fee: FeeAmount::Medium,
+            state: PoolState {
+                sqrt_price_x96: Q96,
+                tick: 0,
+                liquidity: liquidity as u128,
+                ..Default::default()
+            },
+            ticks,
+        };
+        let pools = [v2(1, 10_000), v2(2, 2_000), v3.clone()];
+
+        // 500 ETH is better split than through any single pool
+        let amount = e18(500);
+        let best = split(amount, &pools, 20).unwrap();
+        for pool in &pools {
+            assert!(best.amount_out > pool.amount_out(amount).unwrap());
+        }
+        assert_eq!(best.allocations.iter().fold(U256::zero(), |a, b| a + b), amount);
+        assert!(best.allocations.iter().all(|amount| !amount.is_zero()));
+        for ((pool, amount), amount_out) in
+            pools.iter().zip(&best.allocations).zip(&best.amounts_out)
+        {
+            assert_eq!(pool.amount_out(*amount).unwrap(), *amount_out);
+        }
+        assert_eq!(best.amounts_out.iter().fold(U256::zero(), |a, b| a + b), best.amount_out);
+
+        // a single part is the best single pool
+        let single = split(amount, &pools, 1).unwrap();
+        assert_eq!(single.allocations, [amount, U256::zero(), U256::zero()]);
+        assert_eq!(single.amount_out, pools[0].amount_out(amount).unwrap());
+
+        // the legs map onto the Universal Router
+        #[cfg(feature = "universal-router")]
+        {
+            let legs = best.legs(&pools);
+            assert_eq!(legs.len(), 3);
+            assert_eq!(legs[2], (v3.route(), best.allocations[2]));
+            let mut builder = crate::universal_router::CommandBuilder::new();
+            builder.swap_split(&legs, best.amount_out, C, D).unwrap();
+            assert_eq!(builder.len(), 4);
+        }
+
+        // the V3 pool runs out of loaded ticks for the whole amount
+        let res = split(e18(100_000), &[v3], 10);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientLiquidity));
+        let mut other = v2(4, 10_000);
+        if let PoolQuoteSource::V2 { token_out, .. } = &mut other {
+            *token_out = C;
+        }
+        let res = split(amount, &[pools[0].clone(), other], 10);
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+        assert!(matches!(split(amount, &[], 10).unwrap_err(), Error::InvalidPath));
+        assert!(matches!(split(amount, &pools, 0).unwrap_err(), Error::ZeroAmount));
+    }
+}
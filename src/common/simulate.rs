@@ -0,0 +1,215 @@
+use ethers_core::{
+    abi::{self, Token},
+    types::{Address, H256, U256},
+    utils::keccak256,
+};
+use ethers_providers::call_raw::spoof;
+
+#[cfg(feature = "v2")]
+use crate::errors::{Error, Result};
+#[cfg(feature = "v2")]
+use ethers_contract::{builders::ContractCall, decode_function_data, ContractError};
+#[cfg(feature = "v2")]
+use ethers_core::{
+    abi::Detokenize,
+    types::{BlockId, BlockNumber, Bytes},
+};
+#[cfg(feature = "v2")]
+use ethers_providers::Middleware;
+
+/// Additional options used when simulating a call.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SimulateOptions {
+    /// The sender of the call, instead of the client's default sender.
+    pub from: Option<Address>,
+
+    /// The [state override set] of the call, e.g. to simulate a swap without owning its input
+    /// tokens.
+    ///
+    /// Not all providers support state overrides: if the call fails for a reason other than a
+    /// revert, it is retried without them.
+    ///
+    /// [state override set]: https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-eth#eth-call
+    pub state: Option<spoof::State>,
+}
+
+impl SimulateOptions {
+    /// Creates a new instance with the default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { from: None, state: None }
+    }
+
+    /// Sets the sender of the call.
+    #[inline]
+    pub const fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Sets the state override set of the call.
+    #[inline]
+    pub fn state(mut self, state: spoof::State) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Overrides the native token balance of `account`.
+    pub fn native_balance(mut self, account: Address, balance: U256) -> Self {
+        self.state.get_or_insert_with(Default::default).account(account).balance(balance);
+        self
+    }
+
+    /// Overrides the `token` balance of `owner`, where `slot` is the storage slot of the token's
+    /// `balanceOf` mapping, like `0` for OpenZeppelin's ERC20 or `3` for WETH9.
+    ///
+    /// Note: this assumes the Solidity storage layout of mappings.
+    pub fn balance(mut self, token: Address, owner: Address, slot: u64, balance: U256) -> Self {
+        let key = mapping_key(Token::Address(owner), slot.into());
+        self.store(token, key, balance);
+        self
+    }
+
+    /// Overrides the `token` allowance of `spender` from `owner`, where `slot` is the storage slot
+    /// of the token's `allowance` mapping, like `1` for OpenZeppelin's ERC20 or `4` for WETH9.
+    ///
+    /// Note: this assumes the Solidity storage layout of mappings.
+    pub fn allowance(
+        mut self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        slot: u64,
+        allowance: U256,
+    ) -> Self {
+        let inner = mapping_key(Token::Address(owner), slot.into());
+        let key = mapping_key(Token::Address(spender), U256::from_big_endian(inner.as_bytes()));
+        self.store(token, key, allowance);
+        self
+    }
+
+    fn store(&mut self, account: Address, key: H256, value: U256) {
+        let mut bytes = [0; 32];
+        value.to_big_endian(&mut bytes);
+        let state = self.state.get_or_insert_with(Default::default);
+        state.account(account).store(key, bytes.into());
+    }
+}
+
+/// The storage key of `key` in the mapping at `slot`.
+fn mapping_key(key: Token, slot: U256) -> H256 {
+    keccak256(abi::encode(&[key, Token::Uint(slot)])).into()
+}
+
+/// The result of a simulated call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Simulation<D> {
+    /// The decoded output of the call.
+    pub output: D,
+    /// Whether the state overrides were applied. False if there were none, or if the provider does
+    /// not support them.
+    pub state_overridden: bool,
+}
+
+/// Simulates `call` with `eth_call` at the latest block, applying `options`.
+///
+/// Returns the revert of the call decoded with [`Error::from_revert`].
+#[cfg(feature = "v2")]
+pub(crate) async fn simulate<M: Middleware, D: Detokenize>(
+    client: &M,
+    call: ContractCall<M, D>,
+    options: SimulateOptions,
+) -> Result<Simulation<D>> {
+    let mut tx = call.tx;
+    if let Some(from) = options.from.or_else(|| client.default_sender()) {
+        tx.set_from(from);
+    }
+
+    if let Some(state) = options.state {
+        let block = BlockId::from(BlockNumber::Latest);
+        let res: Result<Bytes> =
+            client.provider().request("eth_call", (&tx, block, state)).await.map_err(Error::from);
+        match res {
+            Ok(bytes) => {
+                let output = decode_function_data(&call.function, bytes, false)?;
+                return Ok(Simulation { output, state_overridden: true });
+            }
+            // anything other than a revert means that the provider does not support overrides
+            Err(Error::ProviderError(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let bytes = client.call(&tx, None).await.map_err(ContractError::<M>::MiddlewareError)?;
+    let output = decode_function_data(&call.function, bytes, false)?;
+    Ok(Simulation { output, state_overridden: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "v2")]
+    use crate::contracts::bindings::ierc20::IERC20;
+    #[cfg(feature = "v2")]
+    use ethers_providers::{MockProvider, Provider};
+    #[cfg(feature = "v2")]
+    use std::sync::Arc;
+
+    #[test]
+    fn test_overrides() {
+        let (token, owner, spender) =
+            (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let options = SimulateOptions::new()
+            .balance(token, owner, 0, 100.into())
+            .allowance(token, owner, spender, 1, U256::MAX)
+            .native_balance(owner, 1.into());
+        let mut state = options.state.unwrap();
+
+        let balance_key = mapping_key(Token::Address(owner), 0.into());
+        let allowance_key = keccak256(
+            [
+                H256::from(spender).as_bytes(),
+                mapping_key(Token::Address(owner), 1.into()).as_bytes(),
+            ]
+            .concat(),
+        );
+        let storage = state.account(token).storage.clone().unwrap();
+        assert_eq!(storage[&balance_key], H256::from_low_u64_be(100));
+        assert_eq!(storage[&allowance_key.into()], H256::repeat_byte(0xff));
+        assert_eq!(state.account(owner).balance, Some(1.into()));
+    }
+
+    #[cfg(feature = "v2")]
+    fn push_uint(mock: &MockProvider, value: u64) {
+        let data = abi::encode(&[Token::Uint(value.into())]);
+        mock.push::<Bytes, _>(Bytes::from(data)).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "v2")]
+    async fn test_simulate() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let token = IERC20::new(Address::repeat_byte(1), client.clone());
+        let owner = Address::repeat_byte(2);
+        let options = SimulateOptions::new().from(owner);
+
+        push_uint(&mock, 100);
+        let res = simulate(client.as_ref(), token.balance_of(owner), options.clone()).await;
+        assert_eq!(res.unwrap(), Simulation { output: 100.into(), state_overridden: false });
+
+        let options = options.balance(token.address(), owner, 0, 200.into());
+        push_uint(&mock, 200);
+        let res = simulate(client.as_ref(), token.balance_of(owner), options.clone()).await;
+        assert_eq!(res.unwrap(), Simulation { output: 200.into(), state_overridden: true });
+
+        // a provider which does not support overrides (sorted in reverse, as the mock is LIFO)
+        push_uint(&mock, 100);
+        mock.push(true).unwrap();
+        let res = simulate(client.as_ref(), token.balance_of(owner), options).await;
+        assert_eq!(res.unwrap(), Simulation { output: 100.into(), state_overridden: false });
+    }
+}
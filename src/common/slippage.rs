@@ -0,0 +1,160 @@
+use crate::errors::{Error, Result};
+use ethers_core::types::{U256, U512};
+
+/// 100% in basis points.
+const MAX_BPS: u32 = 10_000;
+
+/// The maximum price change, in basis points, which may occur while a swap is pending before it
+/// reverts.
+///
+/// All the amount calculations are performed with integer math.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Slippage(u32);
+
+impl Slippage {
+    /// No price change tolerated.
+    pub const ZERO: Self = Self(0);
+
+    /// Any price change tolerated.
+    pub const MAX: Self = Self(MAX_BPS);
+
+    /// 0.5%, the slippage used by default by `SwapBuilder`.
+    pub const HALF_PERCENT: Self = Self(50);
+
+    /// Creates a new slippage of `bps` basis points. Returns [`Error::InvalidSlippage`] if it is
+    /// greater than `10_000` (100%).
+    pub const fn bps(bps: u32) -> Result<Self> {
+        if bps > MAX_BPS {
+            Err(Error::InvalidSlippage)
+        } else {
+            Ok(Self(bps))
+        }
+    }
+
+    /// Creates a new slippage of `percent` percentage points, rounded to the nearest basis point.
+    /// Returns [`Error::InvalidSlippage`] if it is not in range `0.0..=100.0`.
+    pub fn percent(percent: f64) -> Result<Self> {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(Error::InvalidSlippage);
+        }
+        Ok(Self((percent * 100.0).round() as u32))
+    }
+
+    /// Creates a new slippage which does not tolerate any price change.
+    #[inline]
+    pub const fn zero() -> Self {
+        Self::ZERO
+    }
+
+    /// Returns the slippage in basis points.
+    #[inline]
+    pub const fn as_bps(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns whether no price change is tolerated.
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether any price change is tolerated.
+    #[inline]
+    pub const fn is_max(&self) -> bool {
+        self.0 == MAX_BPS
+    }
+
+    /// Returns [`Error::InvalidSlippage`] if `self` was created from an out of range float.
+    #[inline]
+    pub const fn validate(self) -> Result<Self> {
+        Self::bps(self.0)
+    }
+
+    /// Returns the minimum amount out after applying the slippage to the quoted `amount_out`,
+    /// rounded down.
+    pub fn minimum_amount_out(&self, amount_out: U256) -> U256 {
+        let bps = self.0.min(MAX_BPS);
+        let amount = amount_out.full_mul(U256::from(MAX_BPS - bps)) / U512::from(MAX_BPS);
+        // can't overflow since `amount <= amount_out`
+        amount.try_into().expect("overflow")
+    }
+
+    /// Returns the maximum amount in after applying the slippage to the quoted `amount_in`,
+    /// rounded up and saturating at [`U256::MAX`].
+    pub fn maximum_amount_in(&self, amount_in: U256) -> U256 {
+        let bps = self.0.min(MAX_BPS);
+        if bps == MAX_BPS {
+            return U256::MAX;
+        }
+        let denominator = U512::from(MAX_BPS - bps);
+        let amount = (amount_in.full_mul(U256::from(MAX_BPS)) + denominator - 1) / denominator;
+        amount.try_into().unwrap_or(U256::MAX)
+    }
+}
+
+/// Converts percentage points into a [Slippage]. Values out of range `0.0..=100.0` are rejected
+/// with [`Error::InvalidSlippage`] when the slippage is used to build a swap.
+impl From<f32> for Slippage {
+    fn from(percent: f32) -> Self {
+        match Self::percent(percent as f64) {
+            Ok(slippage) => slippage,
+            Err(_) => Self(u32::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Slippage::bps(50).unwrap(), Slippage::percent(0.5).unwrap());
+        assert_eq!(Slippage::bps(0).unwrap(), Slippage::zero());
+        assert_eq!(Slippage::bps(10_000).unwrap(), Slippage::MAX);
+        assert!(matches!(Slippage::bps(10_001).unwrap_err(), Error::InvalidSlippage));
+        assert!(matches!(Slippage::percent(-0.1).unwrap_err(), Error::InvalidSlippage));
+        assert!(matches!(Slippage::percent(100.1).unwrap_err(), Error::InvalidSlippage));
+        assert!(matches!(Slippage::percent(f64::NAN).unwrap_err(), Error::InvalidSlippage));
+
+        assert_eq!(Slippage::from(1.0f32).as_bps(), 100);
+        assert!(Slippage::from(100.0f32).validate().unwrap().is_max());
+        assert!(matches!(Slippage::from(-1.0f32).validate().unwrap_err(), Error::InvalidSlippage));
+        assert!(matches!(Slippage::from(101.0f32).validate().unwrap_err(), Error::InvalidSlippage));
+    }
+
+    #[test]
+    fn test_zero() {
+        let amount = U256::exp10(18);
+        assert_eq!(Slippage::ZERO.minimum_amount_out(amount), amount);
+        assert_eq!(Slippage::ZERO.maximum_amount_in(amount), amount);
+        assert_eq!(Slippage::ZERO.minimum_amount_out(U256::MAX), U256::MAX);
+        assert_eq!(Slippage::ZERO.maximum_amount_in(U256::MAX), U256::MAX);
+    }
+
+    #[test]
+    fn test_max() {
+        let amount = U256::exp10(18);
+        assert_eq!(Slippage::MAX.minimum_amount_out(amount), U256::zero());
+        assert_eq!(Slippage::MAX.maximum_amount_in(amount), U256::MAX);
+        assert_eq!(Slippage::MAX.minimum_amount_out(U256::MAX), U256::zero());
+    }
+
+    #[test]
+    fn test_amounts() {
+        let slippage = Slippage::bps(100).unwrap();
+        let amount = U256::exp10(18);
+        assert_eq!(slippage.minimum_amount_out(amount), U256::exp10(16) * 99);
+        // 1e18 / 0.99, rounded up
+        assert_eq!(slippage.maximum_amount_in(amount), U256::from(1_010_101_010_101_010_102u128));
+
+        let max_out = slippage.minimum_amount_out(U256::MAX);
+        assert_eq!(max_out, U256::MAX / 100 * 99 + U256::MAX % 100 * 99 / 100);
+        assert_eq!(slippage.maximum_amount_in(U256::MAX), U256::MAX);
+        let max_in = slippage.maximum_amount_in(U256::MAX / 2);
+        assert!(max_in < U256::MAX);
+        assert!(slippage.minimum_amount_out(max_in) >= U256::MAX / 2);
+        assert_eq!(slippage.maximum_amount_in(U256::MAX / 100 * 99 + 100), U256::MAX);
+    }
+}
@@ -1,8 +1,13 @@
-use crate::{contracts::bindings::ierc20::IERC20, errors::Result};
-use ethers_contract::{builders::ContractCall, Multicall};
+use crate::{
+    contracts::bindings::ierc20::IERC20,
+    errors::{Error, Result},
+    utils::Multicall,
+    CallExt,
+};
+use ethers_contract::{builders::ContractCall, ContractError};
 use ethers_core::{
-    abi::Tokenizable,
-    types::{Address, Chain},
+    abi::{AbiDecode, InvalidOutputType, Tokenizable},
+    types::{Address, Chain, U256},
 };
 use ethers_providers::Middleware;
 use std::{fmt, sync::Arc};
@@ -94,36 +99,178 @@ impl<M: Middleware> Erc20<M> {
         multicall.add_call(name, true).add_call(symbol, true).add_call(decimals, true)
     }
 
-    /// Syncs the token's name, symbol and decimals.
-    pub async fn sync(&mut self, chain: Chain) -> Result<&mut Self> {
-        let mut multicall = Multicall::new_with_chain_id(self.client(), None, Some(chain))?;
-        self.add_metadata(&mut multicall);
+    /// Fetches the token's name. See [`get_symbol`][Self::get_symbol].
+    pub async fn get_name(&self) -> Result<String> {
+        self.get_string(self.contract.name()).await
+    }
+
+    /// Fetches the token's symbol.
+    ///
+    /// Tokens like MKR, which return a `bytes32` instead of a `string`, are also supported.
+    pub async fn get_symbol(&self) -> Result<String> {
+        self.get_string(self.contract.symbol()).await
+    }
 
-        // TODO: results
-        #[allow(clippy::single_match)]
-        match multicall.call_raw().await {
-            Ok(tokens) => {
-                let mut tokens = tokens.into_iter();
-                macro_rules! assign_if_ok {
-                    ($($var:ident)+) => {$(
-                        if let Some(token) = tokens.next() {
-                            if let Ok((_, var)) = <(bool, _)>::from_token(token) {
-                                self.$var = Some(var);
-                            }
-                        }
-                    )+};
-                }
-                assign_if_ok!(name symbol decimals);
+    /// Fetches the token's decimals.
+    pub async fn get_decimals(&self) -> Result<u8> {
+        self.contract.decimals().call().await.map_err(Into::into)
+    }
+
+    /// Returns the contract call for transferring `amount` tokens to `to`.
+    ///
+    /// The call's output is cleared to support tokens like USDT, which don't return a `bool`.
+    pub fn transfer(&self, to: Address, amount: U256) -> ContractCall<M, ()> {
+        self.contract.transfer(to, amount).clear_output()
+    }
+
+    /// Returns the contract call for transferring `amount` tokens from `from` to `to`.
+    ///
+    /// The call's output is cleared to support tokens like USDT, which don't return a `bool`.
+    pub fn transfer_from(&self, from: Address, to: Address, amount: U256) -> ContractCall<M, ()> {
+        self.contract.transfer_from(from, to, amount).clear_output()
+    }
+
+    /// Returns the contract calls that must be executed, in order, to allow `spender` to transfer
+    /// `amount` of `owner`'s tokens.
+    ///
+    /// Returns no calls if the current allowance is already sufficient. Tokens like USDT, which
+    /// revert when changing a non-zero allowance to another non-zero value, are detected by
+    /// simulating the approval, in which case the allowance is first reset to zero. Errors of the
+    /// simulation other than a revert are returned.
+    pub async fn approve_if_needed(
+        &self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Vec<ContractCall<M, bool>>> {
+        let allowance = self.contract.allowance(owner, spender).call().await?;
+        if allowance >= amount {
+            return Ok(vec![]);
+        }
+
+        let approve = self.contract.approve(spender, amount).from(owner);
+        if allowance.is_zero() {
+            return Ok(vec![approve]);
+        }
+
+        // simulate without decoding the output, since USDT-like tokens don't return a bool
+        let error = match self.client().call(&approve.tx, None).await {
+            Ok(_) => return Ok(vec![approve]),
+            Err(e) => Error::from(ContractError::<M>::MiddlewareError(e)),
+        };
+        match error {
+            // not a revert
+            Error::ContractError(_) | Error::ProviderError(_) => Err(error),
+            // only a revert means that the token requires resetting the allowance first
+            _ => {
+                let reset = self.contract.approve(spender, U256::zero()).from(owner);
+                Ok(vec![reset, approve])
             }
-            Err(_) => { /* TODO */ }
         }
+    }
+
+    /// Syncs the token's name, symbol and decimals with a single [Multicall] request.
+    ///
+    /// The name and symbol are fetched separately if they fail to decode, like the `bytes32` symbol
+    /// of MKR.
+    pub async fn sync(&mut self, chain: Chain) -> Result<&mut Self> {
+        let mut multicall = Multicall::new(self.client(), Some(chain)).await?;
+        self.add_metadata(&mut multicall);
+
+        let mut results = multicall.call_raw().await?.into_iter();
+        let mut next = || results.next().and_then(Result::ok);
+        let (name, symbol, decimals) = (next(), next(), next());
+        self.name = match name.and_then(|token| String::from_token(token).ok()) {
+            Some(name) => Some(name),
+            None => self.get_name().await.ok(),
+        };
+        self.symbol = match symbol.and_then(|token| String::from_token(token).ok()) {
+            Some(symbol) => Some(symbol),
+            None => self.get_symbol().await.ok(),
+        };
+        self.decimals = decimals.and_then(|token| u8::from_token(token).ok());
 
         Ok(self)
     }
+
+    /// Calls a `string` getter without decoding its output with the call's ABI.
+    async fn get_string(&self, call: ContractCall<M, String>) -> Result<String> {
+        let data = self
+            .client()
+            .call(&call.tx, call.block)
+            .await
+            .map_err(ContractError::<M>::MiddlewareError)?;
+        decode_string(&data)
+            .ok_or_else(|| InvalidOutputType(format!("Invalid string: {data}")).into())
+    }
 }
 
-#[cfg(all(test, feature = "addresses"))]
+/// Decodes a `string` or a `bytes32` with its trailing zeros removed.
+pub(super) fn decode_string(data: &[u8]) -> Option<String> {
+    match String::decode(data) {
+        Ok(s) => Some(s),
+        Err(_) if data.len() == 32 => {
+            let end = data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            String::from_utf8(data[..end].to_vec()).ok()
+        }
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
 mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    fn mocked_token() -> (Erc20<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        (Erc20::new(Arc::new(provider), Address::repeat_byte(0x0a)), mock)
+    }
+
+    #[tokio::test]
+    async fn test_string_symbol() {
+        let (token, mock) = mocked_token();
+        let data = abi::encode(&[Token::String("WETH".into())]);
+        mock.push::<Bytes, _>(Bytes::from(data)).unwrap();
+        assert_eq!(token.get_symbol().await.unwrap(), "WETH");
+    }
+
+    #[tokio::test]
+    async fn test_bytes32_symbol() {
+        let (token, mock) = mocked_token();
+        let mut data = [0u8; 32];
+        data[..3].copy_from_slice(b"MKR");
+        mock.push::<Bytes, _>(Bytes::from(data.to_vec())).unwrap();
+        assert_eq!(token.get_symbol().await.unwrap(), "MKR");
+
+        mock.push::<Bytes, _>(Bytes::from(vec![0xff; 31])).unwrap();
+        assert!(token.get_symbol().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_no_return_value() {
+        let (token, mock) = mocked_token();
+        let to = Address::repeat_byte(0x0b);
+
+        // USDT-like: no return data
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        token.transfer(to, 100.into()).call().await.unwrap();
+
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Bool(true)]))).unwrap();
+        token.transfer_from(to, to, 100.into()).call().await.unwrap();
+
+        // the standard binding fails to decode the missing bool
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        assert!(token.contract().transfer(to, 100.into()).call().await.is_err());
+    }
+}
+
+#[cfg(all(test, feature = "addresses"))]
+mod network_tests {
     use super::*;
     use ethers_providers::{Http, Provider, MAINNET};
 
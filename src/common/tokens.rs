@@ -0,0 +1,771 @@
+//! ERC20 metadata and token lists.
+
+use super::{
+    token::decode_string,
+    utils::{format_units, sort_tokens},
+    Erc20,
+};
+use crate::{
+    errors::{Error, Result},
+    utils::{CallOutput, Multicall},
+    RequestPolicy,
+};
+use ethers_core::{
+    abi::Tokenizable,
+    types::{Address, Chain, U256},
+};
+use ethers_providers::Middleware;
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+#[cfg(feature = "v2")]
+use super::{constants::BPS_U256, simulate, SimulateOptions};
+#[cfg(feature = "v2")]
+use crate::{
+    contracts::bindings::{i_uniswap_v2_router_02::IUniswapV2Router02, ierc20::IERC20},
+    utils::MULTICALL3_ADDRESS,
+    v2::Pair,
+};
+#[cfg(feature = "v2")]
+use ethers_contract::{builders::ContractCall, MulticallContract};
+#[cfg(feature = "v2")]
+use ethers_core::{
+    abi::{AbiDecode, Detokenize},
+    types::Bytes,
+};
+
+/// The metadata of an ERC20 token, returned by [`fetch_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TokenInfo {
+    /// The token's address.
+    pub address: Address,
+
+    /// The token's symbol, or None if it could not be fetched.
+    pub symbol: Option<String>,
+
+    /// The token's name, or None if it could not be fetched.
+    pub name: Option<String>,
+
+    /// The token's decimals, or None if they could not be fetched.
+    pub decimals: Option<u8>,
+
+    /// Whether all of the token's getters returned their standard types: false if any of them
+    /// failed, or returned a `bytes32` instead of a `string`, like the symbol of MKR.
+    pub is_standard: bool,
+}
+
+impl TokenInfo {
+    /// Formats `amount` of the token with its decimals and symbol, like `"1.5 WETH"`, or None if
+    /// its decimals are not known.
+    pub fn format_amount(&self, amount: U256) -> Option<String> {
+        let amount = format_units(amount, self.decimals?);
+        Some(match &self.symbol {
+            Some(symbol) => format!("{amount} {symbol}"),
+            None => amount,
+        })
+    }
+}
+
+/// Fetches the symbol, name and decimals of each of `tokens` with a single [Multicall] request.
+///
+/// Each getter is allowed to fail, so that a token whose getters fail or return non-standard types
+/// is returned with [`is_standard`][TokenInfo::is_standard] unset, rather than failing the whole
+/// batch. Symbols and names returned as a `bytes32`, like by MKR, are decoded as well. The request
+/// is retried if throttled, like with the default [RequestPolicy].
+pub async fn fetch_metadata<M: Middleware>(
+    client: Arc<M>,
+    tokens: &[Address],
+) -> Result<Vec<TokenInfo>> {
+    let mut multicall = Multicall::new(client.clone(), None).await?;
+    for &address in tokens {
+        Erc20::new(client.clone(), address).add_metadata(&mut multicall);
+    }
+
+    let results = RequestPolicy::new().retry(|| multicall.call_raw()).await?;
+    let mut results = results.into_iter();
+    let infos = tokens.iter().map(|&address| {
+        let (name, name_standard) = decode_string_result(results.next());
+        let (symbol, symbol_standard) = decode_string_result(results.next());
+        let decimals = results.next().and_then(Result::ok).and_then(|t| u8::from_token(t).ok());
+        let is_standard = name_standard && symbol_standard && decimals.is_some();
+        TokenInfo { address, symbol, name, decimals, is_standard }
+    });
+    Ok(infos.collect())
+}
+
+/// Decodes the result of a `string` getter, falling back to a `bytes32`. Returns whether it was a
+/// `string`.
+fn decode_string_result(result: Option<CallOutput>) -> (Option<String>, bool) {
+    match result {
+        Some(Ok(token)) => match String::from_token(token) {
+            Ok(string) => (Some(string), true),
+            Err(_) => (None, false),
+        },
+        Some(Err(data)) => (decode_string(&data), false),
+        None => (None, false),
+    }
+}
+
+/// A cache of [TokenInfo]s keyed by chain and address, shared between its clones.
+///
+/// Can be set on a V2 router with `Router::set_token_cache` for formatting amounts with the
+/// tokens' decimals.
+#[derive(Clone, Debug, Default)]
+pub struct TokenCache {
+    tokens: Arc<RwLock<HashMap<(Chain, Address), TokenInfo>>>,
+}
+
+impl TokenCache {
+    /// Creates a new, empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached metadata of `address` on `chain`.
+    pub fn get(&self, chain: Chain, address: Address) -> Option<TokenInfo> {
+        self.tokens.read().unwrap_or_else(PoisonError::into_inner).get(&(chain, address)).cloned()
+    }
+
+    /// Caches the metadata of a token on `chain`, replacing any previous one.
+    pub fn insert(&self, chain: Chain, info: TokenInfo) {
+        let mut tokens = self.tokens.write().unwrap_or_else(PoisonError::into_inner);
+        tokens.insert((chain, info.address), info);
+    }
+
+    /// Removes the cached metadata of `address` on `chain`, returning it.
+    pub fn remove(&self, chain: Chain, address: Address) -> Option<TokenInfo> {
+        self.tokens.write().unwrap_or_else(PoisonError::into_inner).remove(&(chain, address))
+    }
+
+    /// Returns the number of cached tokens.
+    pub fn len(&self) -> usize {
+        self.tokens.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns whether no token is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the metadata of each of `tokens` on `chain`, fetching the ones which are not cached
+    /// with [`fetch_metadata`] and caching them.
+    pub async fn fetch<M: Middleware>(
+        &self,
+        client: Arc<M>,
+        chain: Chain,
+        tokens: &[Address],
+    ) -> Result<Vec<TokenInfo>> {
+        let missing: Vec<_> =
+            tokens.iter().copied().filter(|&token| self.get(chain, token).is_none()).collect();
+        if !missing.is_empty() {
+            for info in fetch_metadata(client, &missing).await? {
+                self.insert(chain, info);
+            }
+        }
+        Ok(tokens.iter().filter_map(|&token| self.get(chain, token)).collect())
+    }
+
+    /// Formats `amount` of the token at `address` on `chain` with its cached decimals and symbol.
+    /// See [`TokenInfo::format_amount`].
+    pub fn format_amount(&self, chain: Chain, address: Address, amount: U256) -> Option<String> {
+        self.get(chain, address)?.format_amount(amount)
+    }
+}
+
+/// A [token list](https://tokenlists.org), like the [Uniswap default list](https://tokens.uniswap.org).
+///
+/// Only the fields used for resolving symbols are kept, other fields like `version` and
+/// `keywords` are ignored when parsing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TokenList {
+    /// The name of the list.
+    pub name: String,
+
+    /// The URI of the list's logo.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub logo_uri: Option<String>,
+
+    /// The tokens of the list, on all chains.
+    pub tokens: Vec<TokenListEntry>,
+}
+
+/// A token of a [TokenList].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TokenListEntry {
+    /// The ID of the token's chain.
+    pub chain_id: u64,
+
+    /// The token's address.
+    pub address: Address,
+
+    /// The token's name.
+    pub name: String,
+
+    /// The token's symbol.
+    pub symbol: String,
+
+    /// The token's decimals.
+    pub decimals: u8,
+
+    /// The URI of the token's logo.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub logo_uri: Option<String>,
+}
+
+impl TokenList {
+    /// Parses a token list from its JSON representation.
+    ///
+    /// Returns [`Error::InvalidTokenList`] if it is not a valid token list.
+    #[cfg(feature = "serde")]
+    pub fn from_json<R: std::io::Read>(reader: R) -> Result<Self> {
+        ethers_core::utils::__serde_json::from_reader(reader)
+            .map_err(|e| Error::InvalidTokenList(e.to_string()))
+    }
+
+    /// Returns the tokens of the list on `chain`.
+    pub fn tokens_on(&self, chain: Chain) -> impl Iterator<Item = &TokenListEntry> {
+        self.tokens.iter().filter(move |token| token.chain_id == u64::from(chain))
+    }
+
+    /// Removes the tokens which are not on `chain`.
+    pub fn retain_chain(&mut self, chain: Chain) {
+        self.tokens.retain(|token| token.chain_id == u64::from(chain));
+    }
+
+    /// Returns the address and decimals of the token with `symbol` on `chain`.
+    ///
+    /// Symbols are matched exactly, or case-insensitively if no token's symbol matches exactly.
+    ///
+    /// Returns [`Error::UnknownSymbol`] if no token has the symbol, and
+    /// [`Error::AmbiguousSymbol`] with their addresses if more than one does.
+    pub fn resolve(&self, symbol: &str, chain: Chain) -> Result<(Address, u8)> {
+        let mut candidates: Vec<_> = self.tokens_on(chain).filter(|t| t.symbol == symbol).collect();
+        if candidates.is_empty() {
+            candidates =
+                self.tokens_on(chain).filter(|t| t.symbol.eq_ignore_ascii_case(symbol)).collect();
+        }
+        // the same token can be listed more than once
+        candidates.sort_by_key(|token| token.address);
+        candidates.dedup_by_key(|token| token.address);
+        match candidates[..] {
+            [token] => Ok((token.address, token.decimals)),
+            [] => Err(Error::UnknownSymbol { symbol: symbol.to_string(), chain }),
+            _ => Err(Error::AmbiguousSymbol {
+                symbol: symbol.to_string(),
+                chain,
+                candidates: candidates.iter().map(|token| token.address).collect(),
+            }),
+        }
+    }
+
+    /// Resolves a pair of symbols of the form `BASE/QUOTE`, like `"WETH/USDC"`, on `chain`,
+    /// returning the addresses of the tokens sorted like those of a pair, for
+    /// `v2::Factory::pair_for`. See [`resolve`][Self::resolve].
+    ///
+    /// Returns [`Error::InvalidPairSymbols`] if `pair` is not of the form `BASE/QUOTE`, and
+    /// [`Error::IdenticalAddresses`] if both symbols resolve to the same token.
+    pub fn resolve_pair(&self, pair: &str, chain: Chain) -> Result<(Address, Address)> {
+        let (base, quote) = match pair.split_once('/') {
+            Some((base, quote)) if !base.trim().is_empty() && !quote.trim().is_empty() => {
+                (base.trim(), quote.trim())
+            }
+            _ => return Err(Error::InvalidPairSymbols(pair.to_string())),
+        };
+        let (base, _) = self.resolve(base, chain)?;
+        let (quote, _) = self.resolve(quote, chain)?;
+        if base == quote {
+            return Err(Error::IdenticalAddresses);
+        }
+        Ok(sort_tokens(base, quote))
+    }
+}
+
+/// The sender of the calls of [`probe`], funded with a state override.
+#[cfg(feature = "v2")]
+const PROBE_SENDER: Address = Address::repeat_byte(0x5e);
+
+/// The selector of Multicall3's `aggregate3Value((address,bool,uint256,bytes)[])`.
+#[cfg(feature = "v2")]
+const AGGREGATE3_VALUE_SELECTOR: [u8; 4] = [0x17, 0x4d, 0xea, 0x71];
+
+/// The result of `probe`-ing a token for transfer taxes and sell restrictions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TokenProbe {
+    /// The tax taken when buying, in basis points, or None if the buy failed.
+    pub buy_tax_bps: Option<u32>,
+    /// The tax taken when selling, in basis points, or None if the sell failed or could not be
+    /// attempted.
+    pub sell_tax_bps: Option<u32>,
+    /// Whether the bought tokens could be sold, or None if unknown because the token could not be
+    /// bought or approved, e.g. because it blocks contracts or the simulated sender.
+    pub can_sell: Option<bool>,
+    /// Whether the probe amount was over a maximum transaction amount of the token, in which case
+    /// a hundredth of it was used.
+    pub max_tx_amount_hit: bool,
+}
+
+impl TokenProbe {
+    /// Returns whether the token could be bought but not sold.
+    #[inline]
+    pub fn is_honeypot(&self) -> bool {
+        self.can_sell == Some(false)
+    }
+}
+
+/// Probes `token` for transfer taxes and sell restrictions, by simulating a buy of `amount_in` of
+/// the native token followed by a sell of the received tokens, through the Uniswap V2 `router`
+/// and `base_pair`, the pair of `token` and a base token like WETH.
+///
+/// The swaps are aggregated through [Multicall3][crate::utils::Multicall] with `eth_call`, whose
+/// sender is funded with a state override: no tokens are required and no transactions are sent,
+/// but the provider must support state overrides. The taxes are the differences between the
+/// router's quoted amounts and the received ones. If a swap fails, it is retried with a hundredth
+/// of the amount, in case it is over the token's maximum transaction amount.
+///
+/// Note: the swaps are made by the Multicall3 contract, so tokens which block contracts, or the
+/// simulated sender, cannot be bought and are reported with an unknown
+/// [`can_sell`][TokenProbe::can_sell].
+///
+/// Returns [`Error::TokenNotInPair`] if `token` is not in `base_pair`.
+#[cfg(feature = "v2")]
+pub async fn probe<M: Middleware>(
+    client: Arc<M>,
+    token: Address,
+    base_pair: &Pair<M>,
+    router: Address,
+    amount_in: U256,
+) -> Result<TokenProbe> {
+    let (token0, token1) = base_pair.fetch_tokens().await?;
+    let base = match token {
+        _ if token == token0 => token1,
+        _ if token == token1 => token0,
+        _ => return Err(Error::TokenNotInPair(token)),
+    };
+    let router = IUniswapV2Router02::new(router, client.clone());
+    let weth = router.weth().call().await?;
+    let mut buy_path = vec![weth, base, token];
+    buy_path.dedup();
+    let sell_path: Vec<_> = buy_path.iter().rev().copied().collect();
+
+    let token_contract = IERC20::new(token, client.clone());
+    let weth_contract = IERC20::new(weth, client.clone());
+    let this = MULTICALL3_ADDRESS;
+    let buy = |amount: U256| {
+        let path = buy_path.clone();
+        let call = router.swap_exact_eth_for_tokens_supporting_fee_on_transfer_tokens(
+            U256::zero(),
+            path,
+            this,
+            U256::MAX,
+        );
+        (router.address(), amount, calldata(call))
+    };
+    let sell = |amount: U256| {
+        let path = sell_path.clone();
+        let call = router.swap_exact_tokens_for_tokens_supporting_fee_on_transfer_tokens(
+            amount,
+            U256::zero(),
+            path,
+            this,
+            U256::MAX,
+        );
+        (router.address(), U256::zero(), calldata(call))
+    };
+    let quote = |amount: U256, path: &[Address]| {
+        let call = router.get_amounts_out(amount, path.to_vec());
+        (router.address(), U256::zero(), calldata(call))
+    };
+    let balance =
+        |token: &IERC20<M>| (token.address(), U256::zero(), calldata(token.balance_of(this)));
+
+    // buy, recording the balance before and after
+    let mut result = TokenProbe::default();
+    let mut amount = amount_in;
+    let mut bought = None;
+    for attempt in [amount_in, amount_in / 100] {
+        let calls = vec![
+            quote(attempt, &buy_path),
+            balance(&token_contract),
+            buy(attempt),
+            balance(&token_contract),
+        ];
+        let res = probe_calls(client.clone(), calls).await?;
+        if let [Some(quoted), Some(before), Some(_), Some(after)] = &res[..] {
+            bought = Some((decode_last(quoted)?, decode_diff(before, after)?));
+            amount = attempt;
+            break;
+        }
+        result.max_tx_amount_hit = true;
+    }
+    let (quoted, received) = match bought {
+        Some(bought) => bought,
+        None => return Ok(TokenProbe::default()),
+    };
+    result.buy_tax_bps = Some(tax_bps(quoted, received));
+    if received.is_zero() {
+        return Ok(result);
+    }
+
+    // buy again, then sell all the received tokens or a hundredth of them
+    let approve = token_contract.approve(router.address(), U256::MAX);
+    let calls = vec![
+        buy(amount),
+        (token, U256::zero(), calldata(approve)),
+        quote(received, &sell_path),
+        balance(&weth_contract),
+        sell(received),
+        balance(&weth_contract),
+        quote(received / 100, &sell_path),
+        sell(received / 100),
+        balance(&weth_contract),
+    ];
+    let res = probe_calls(client, calls).await?;
+    let (quoted, before, after) = match &res[..] {
+        [Some(_), Some(_), Some(quoted), Some(before), Some(_), Some(after), ..] => {
+            (quoted, before, after)
+        }
+        [Some(_), Some(_), _, _, _, Some(before), Some(quoted), Some(_), Some(after)] => {
+            result.max_tx_amount_hit = true;
+            (quoted, before, after)
+        }
+        [Some(_), Some(_), ..] => {
+            result.can_sell = Some(false);
+            return Ok(result);
+        }
+        _ => return Ok(result),
+    };
+    result.can_sell = Some(true);
+    result.sell_tax_bps = Some(tax_bps(decode_last(quoted)?, decode_diff(before, after)?));
+    Ok(result)
+}
+
+/// Simulates `calls`, of a target, a value and calldata, through Multicall3's `aggregate3Value`,
+/// returning the data of those which succeeded.
+#[cfg(feature = "v2")]
+async fn probe_calls<M: Middleware>(
+    client: Arc<M>,
+    calls: Vec<(Address, U256, Bytes)>,
+) -> Result<Vec<Option<Bytes>>> {
+    let value = calls.iter().fold(U256::zero(), |acc, (_, value, _)| acc + value);
+    let calls: Vec<_> =
+        calls.into_iter().map(|(target, value, data)| (target, true, value, data)).collect();
+    let call = MulticallContract::new(MULTICALL3_ADDRESS, client.clone())
+        .method_hash::<_, Vec<(bool, Bytes)>>(AGGREGATE3_VALUE_SELECTOR, calls)?
+        .value(value);
+    let options = SimulateOptions::new().from(PROBE_SENDER).native_balance(PROBE_SENDER, value);
+    let results = simulate(client.as_ref(), call, options).await?.output;
+    Ok(results.into_iter().map(|(success, data)| success.then_some(data)).collect())
+}
+
+#[cfg(feature = "v2")]
+fn calldata<M: Middleware, D: Detokenize>(call: ContractCall<M, D>) -> Bytes {
+    call.calldata().unwrap_or_default()
+}
+
+/// Decodes the last amount of the router's `getAmountsOut`.
+#[cfg(feature = "v2")]
+fn decode_last(data: &Bytes) -> Result<U256> {
+    let amounts = Vec::<U256>::decode(data)?;
+    amounts.last().copied().ok_or(Error::InvalidPath)
+}
+
+/// Decodes the increase from the `before` to the `after` balance.
+#[cfg(feature = "v2")]
+fn decode_diff(before: &Bytes, after: &Bytes) -> Result<U256> {
+    Ok(U256::decode(after)?.saturating_sub(U256::decode(before)?))
+}
+
+/// The difference between the `quoted` and the `received` amounts, in basis points.
+#[cfg(feature = "v2")]
+fn tax_bps(quoted: U256, received: U256) -> u32 {
+    if quoted.is_zero() || received >= quoted {
+        return 0;
+    }
+    ((quoted - received) * BPS_U256 / quoted).as_u32()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "v2")]
+    use crate::ProtocolType;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    const WETH: Address = Address::repeat_byte(1);
+    const MKR: Address = Address::repeat_byte(2);
+    const NO_CODE: Address = Address::repeat_byte(3);
+
+    fn push_metadata(mock: &MockProvider) {
+        let string = |s: &str| abi::encode(&[Token::String(s.into())]);
+        let mut mkr = [0u8; 32];
+        mkr[..3].copy_from_slice(b"MKR");
+        let results = [
+            (true, string("Wrapped Ether")),
+            (true, string("WETH")),
+            (true, abi::encode(&[Token::Uint(18.into())])),
+            (true, string("Maker")),
+            (true, mkr.to_vec()),
+            (true, abi::encode(&[Token::Uint(18.into())])),
+            (true, vec![]),
+            (true, vec![]),
+            (true, vec![]),
+        ];
+        let results = results
+            .into_iter()
+            .map(|(success, data)| Token::Tuple(vec![Token::Bool(success), Token::Bytes(data)]))
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        // chain id, queried by the multicall
+        mock.push(U256::one()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metadata() {
+        let (provider, mock) = Provider::mocked();
+        push_metadata(&mock);
+
+        let infos = fetch_metadata(Arc::new(provider), &[WETH, MKR, NO_CODE]).await.unwrap();
+        let weth = TokenInfo {
+            address: WETH,
+            symbol: Some("WETH".into()),
+            name: Some("Wrapped Ether".into()),
+            decimals: Some(18),
+            is_standard: true,
+        };
+        let mkr = TokenInfo {
+            address: MKR,
+            symbol: Some("MKR".into()),
+            name: Some("Maker".into()),
+            decimals: Some(18),
+            is_standard: false,
+        };
+        let no_code = TokenInfo {
+            address: NO_CODE,
+            symbol: None,
+            name: None,
+            decimals: None,
+            is_standard: false,
+        };
+        assert_eq!(infos, vec![weth, mkr, no_code]);
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let cache = TokenCache::new();
+        push_metadata(&mock);
+
+        let infos =
+            cache.fetch(client.clone(), Chain::Mainnet, &[WETH, MKR, NO_CODE]).await.unwrap();
+        assert_eq!(infos.len(), 3);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(Chain::Mainnet, MKR).unwrap().symbol.as_deref(), Some("MKR"));
+        assert_eq!(cache.get(Chain::Goerli, MKR), None);
+
+        // cached tokens are not fetched again, and clones share the cache
+        let clone = cache.clone();
+        let infos = clone.fetch(client, Chain::Mainnet, &[MKR, WETH]).await.unwrap();
+        assert_eq!(infos[1].address, WETH);
+
+        let amount = U256::exp10(17) * 15;
+        assert_eq!(cache.format_amount(Chain::Mainnet, WETH, amount).unwrap(), "1.5 WETH");
+        assert_eq!(cache.format_amount(Chain::Mainnet, NO_CODE, amount), None);
+        assert_eq!(cache.remove(Chain::Mainnet, WETH).unwrap().address, WETH);
+        assert_eq!(cache.format_amount(Chain::Mainnet, WETH, amount), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_token_list() {
+        let json = r#"{
+            "name": "Test List",
+            "logoURI": "ipfs://list",
+            "version": { "major": 1, "minor": 0, "patch": 0 },
+            "tokens": [
+                {
+                    "chainId": 1,
+                    "address": "0x0101010101010101010101010101010101010101",
+                    "name": "Wrapped Ether",
+                    "symbol": "WETH",
+                    "decimals": 18
+                },
+                {
+                    "chainId": 1,
+                    "address": "0x0202020202020202020202020202020202020202",
+                    "name": "USD Coin",
+                    "symbol": "USDC",
+                    "decimals": 6,
+                    "logoURI": "ipfs://usdc",
+                    "extensions": { "bridgeInfo": {} }
+                },
+                {
+                    "chainId": 10,
+                    "address": "0x0303030303030303030303030303030303030303",
+                    "name": "USD Coin",
+                    "symbol": "USDC",
+                    "decimals": 6
+                }
+            ]
+        }"#;
+        let mut list = TokenList::from_json(json.as_bytes()).unwrap();
+        assert_eq!(list.name, "Test List");
+        assert_eq!(list.logo_uri.as_deref(), Some("ipfs://list"));
+        assert_eq!(list.tokens.len(), 3);
+        assert_eq!(list.tokens[1].logo_uri.as_deref(), Some("ipfs://usdc"));
+        assert_eq!(list.tokens_on(Chain::Optimism).count(), 1);
+
+        list.retain_chain(Chain::Mainnet);
+        assert_eq!(list.tokens.len(), 2);
+
+        let res = TokenList::from_json(&br#"{"name": "Test List"}"#[..]);
+        assert!(matches!(res.unwrap_err(), Error::InvalidTokenList(_)));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let entry = |address: Address, symbol: &str, decimals: u8, chain: Chain| TokenListEntry {
+            chain_id: chain.into(),
+            address,
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals,
+            logo_uri: None,
+        };
+        let usdc = Address::repeat_byte(4);
+        let usdc_e = Address::repeat_byte(5);
+        let mut list = TokenList {
+            name: "Test List".into(),
+            logo_uri: None,
+            tokens: vec![
+                entry(WETH, "WETH", 18, Chain::Mainnet),
+                entry(usdc, "USDC", 6, Chain::Mainnet),
+                entry(usdc, "USDC", 6, Chain::Optimism),
+                entry(usdc_e, "USDC", 6, Chain::Optimism),
+            ],
+        };
+
+        assert_eq!(list.resolve("USDC", Chain::Mainnet).unwrap(), (usdc, 6));
+        assert_eq!(list.resolve("weth", Chain::Mainnet).unwrap(), (WETH, 18));
+        let res = list.resolve("DAI", Chain::Mainnet);
+        assert!(matches!(res.unwrap_err(), Error::UnknownSymbol { chain: Chain::Mainnet, .. }));
+        match list.resolve("USDC", Chain::Optimism).unwrap_err() {
+            Error::AmbiguousSymbol { candidates, .. } => assert_eq!(candidates, vec![usdc, usdc_e]),
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // sorted like the tokens of a pair, and listing a token twice is not ambiguous
+        list.tokens.push(entry(usdc, "USDC", 6, Chain::Mainnet));
+        assert_eq!(list.resolve_pair("USDC/WETH", Chain::Mainnet).unwrap(), (WETH, usdc));
+        assert_eq!(list.resolve_pair("WETH / USDC", Chain::Mainnet).unwrap(), (WETH, usdc));
+        for pair in ["WETH", "WETH/", "/USDC"] {
+            let res = list.resolve_pair(pair, Chain::Mainnet);
+            assert!(matches!(res.unwrap_err(), Error::InvalidPairSymbols(_)));
+        }
+        let res = list.resolve_pair("WETH/weth", Chain::Mainnet);
+        assert!(matches!(res.unwrap_err(), Error::IdenticalAddresses));
+    }
+
+    /// Pushes the results of the calls of an `aggregate3Value`, None for failed calls.
+    #[cfg(feature = "v2")]
+    fn push_probe_results(mock: &MockProvider, results: &[Option<Vec<Token>>]) {
+        let results = results
+            .iter()
+            .map(|result| {
+                let data = result.as_deref().map(abi::encode).unwrap_or_default();
+                Token::Tuple(vec![Token::Bool(result.is_some()), Token::Bytes(data)])
+            })
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "v2")]
+    async fn test_probe() {
+        const TOKEN: Address = Address::repeat_byte(4);
+        const ROUTER: Address = Address::repeat_byte(5);
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let pair = Pair::new(client.clone(), Address::repeat_byte(6), ProtocolType::UniswapV2);
+        let uint = |amount: u64| Some(vec![Token::Uint(amount.into())]);
+        let amounts = |amounts: [u64; 2]| {
+            Some(vec![Token::Array(amounts.iter().map(|&a| Token::Uint(a.into())).collect())])
+        };
+        let ok = Some(vec![]);
+        let approved = Some(vec![Token::Bool(true)]);
+        let push_weth = || mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(WETH)])));
+
+        // (sorted in reverse, as the mock is LIFO)
+        // a 5% buy tax and a 10% sell tax
+        push_probe_results(
+            &mock,
+            &[
+                ok.clone(),
+                approved.clone(),
+                amounts([950, 100]),
+                uint(0),
+                ok.clone(),
+                uint(90),
+                amounts([9, 1]),
+                None,
+                uint(90),
+            ],
+        );
+        push_probe_results(&mock, &[amounts([100, 1_000]), uint(0), ok.clone(), uint(950)]);
+        push_weth().unwrap();
+        // the tokens of the pair
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(TOKEN)]))).unwrap();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(WETH)]))).unwrap();
+        let res = probe(client.clone(), TOKEN, &pair, ROUTER, 100.into()).await.unwrap();
+        let expected = TokenProbe {
+            buy_tax_bps: Some(500),
+            sell_tax_bps: Some(1_000),
+            can_sell: Some(true),
+            max_tx_amount_hit: false,
+        };
+        assert_eq!(res, expected);
+
+        // a honeypot, which cannot be sold, over its max transaction amount
+        push_probe_results(
+            &mock,
+            &[ok.clone(), approved, amounts([9, 1]), uint(0), None, uint(0), None, None, uint(0)],
+        );
+        push_probe_results(&mock, &[amounts([1, 10]), uint(0), ok.clone(), uint(10)]);
+        push_probe_results(&mock, &[amounts([100, 1_000]), uint(0), None, uint(0)]);
+        push_weth().unwrap();
+        let res = probe(client.clone(), TOKEN, &pair, ROUTER, 100.into()).await.unwrap();
+        assert!(res.is_honeypot());
+        assert_eq!(res.buy_tax_bps, Some(0));
+        assert!(res.max_tx_amount_hit);
+
+        // a token which blocks the simulated sender
+        push_probe_results(&mock, &[amounts([1, 10]), uint(0), None, uint(0)]);
+        push_probe_results(&mock, &[amounts([100, 1_000]), uint(0), None, uint(0)]);
+        push_weth().unwrap();
+        let res = probe(client.clone(), TOKEN, &pair, ROUTER, 100.into()).await.unwrap();
+        assert_eq!(res.can_sell, None);
+
+        let res = probe(client, Address::repeat_byte(7), &pair, ROUTER, 100.into()).await;
+        assert!(matches!(res.unwrap_err(), Error::TokenNotInPair(_)));
+    }
+}
@@ -1,13 +1,23 @@
 //! Utils
 
-use super::constants::NATIVE_ADDRESS;
+use super::{
+    constants::NATIVE_ADDRESS,
+    errors::{Error, Result},
+    Clock, SystemClock,
+};
 use ethers_core::types::{Address, U256};
-use std::time::{Duration, SystemTime};
+use ethers_providers::Middleware;
+use std::time::Duration;
 
-/// Returns the [Duration] since the UNIX epoch.
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::types::Chain;
+
+pub use super::multicall::{CallOutput, Multicall, DEFAULT_MAX_PAYLOAD_SIZE, MULTICALL3_ADDRESS};
+
+/// Returns the [Duration] since the UNIX epoch, according to the [SystemClock].
 #[inline]
 pub fn now() -> Duration {
-    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap()
+    SystemClock.now()
 }
 
 /// Returns `deadline` + [`now()`][now].
@@ -28,6 +38,16 @@ pub fn is_native(address: &Address) -> bool {
     *address == NATIVE_ADDRESS
 }
 
+/// Returns the default sender of `client`, which signs `operation`.
+///
+/// Returns [`Error::SignerRequired`] if the client is read-only, like a bare [Provider], which
+/// can only be used for quoting and reading the chain.
+///
+/// [Provider]: ethers_providers::Provider
+pub fn require_signer<M: Middleware>(client: &M, operation: &'static str) -> Result<Address> {
+    client.default_sender().ok_or(Error::SignerRequired(operation))
+}
+
 /// Returns `(first_native, last_native)`.
 #[inline]
 pub fn is_native_path(path: &[Address]) -> (bool, bool) {
@@ -37,6 +57,27 @@ pub fn is_native_path(path: &[Address]) -> (bool, bool) {
     )
 }
 
+/// Returns whether [NATIVE_ADDRESS] is in `path`, excluding its first and last elements.
+#[inline]
+pub fn is_native_mid_path(path: &[Address]) -> bool {
+    path.len() > 2 && path[1..path.len() - 1].iter().any(is_native)
+}
+
+/// Returns the tokens sorted like those of a Uniswap V2 pair or Uniswap V3 pool.
+#[inline]
+pub fn sort_tokens(a: Address, b: Address) -> (Address, Address) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Lossy conversion of a [U256] into a [f64].
+pub(crate) fn to_f64(value: U256) -> f64 {
+    value.0.iter().rev().fold(0.0, |acc, &limb| acc * 18_446_744_073_709_551_616.0 + limb as f64)
+}
+
 /// Replaces all [NATIVE_ADDRESS] in `path` with `weth`.
 pub fn map_native(path: &mut [Address], weth: Address) {
     for a in path.iter_mut() {
@@ -45,3 +86,147 @@ pub fn map_native(path: &mut [Address], weth: Address) {
         }
     }
 }
+
+/// Returns `weth`, or the [wrapped native token][wrapped_native] of `chain` if None.
+///
+/// Returns [`Error::WrappedNativeNotFound`] if neither is found and `needed`, since the swap goes
+/// from or to the native token; the zero address is returned otherwise, as it is then unused.
+///
+/// [wrapped_native]: crate::contracts::addresses::wrapped_native
+#[cfg(any(feature = "v2", feature = "v3"))]
+#[cfg_attr(not(feature = "addresses"), allow(unused_variables))]
+pub(crate) fn resolve_weth(
+    weth: Option<Address>,
+    chain: Option<Chain>,
+    needed: bool,
+) -> Result<Address> {
+    #[cfg(feature = "addresses")]
+    let weth = weth.or_else(|| chain.and_then(crate::contracts::addresses::wrapped_native));
+    match weth {
+        Some(weth) => Ok(weth),
+        None if needed => Err(Error::WrappedNativeNotFound(chain)),
+        None => Ok(Address::zero()),
+    }
+}
+
+/// Parses a decimal string, like `"1.5"`, into an integer amount with `decimals` decimals.
+///
+/// Trailing fractional zeros are ignored. Returns [`Error::InvalidUnits`] if `value` is not a valid
+/// decimal number, has more significant fractional digits than `decimals` or overflows a [U256].
+pub fn parse_units(value: &str, decimals: u8) -> Result<U256> {
+    let invalid = || Error::InvalidUnits(value.to_string());
+
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    let frac = frac.trim_end_matches('0');
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (int.is_empty() && frac.is_empty() && !value.contains('0'))
+        || !is_digits(int)
+        || !is_digits(frac)
+        || frac.len() > decimals as usize
+    {
+        return Err(invalid());
+    }
+
+    let digits = format!("{int}{frac:0<width$}", width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_dec_str(digits).map_err(|_| invalid())
+}
+
+/// Formats an integer amount with `decimals` decimals into a decimal string, like `"1.5"`.
+///
+/// Trailing fractional zeros are removed, as well as the decimal point if there is no fractional
+/// part.
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{int}.{frac}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    fn test_resolve_weth() {
+        let weth = Address::repeat_byte(1);
+        assert_eq!(resolve_weth(Some(weth), Some(Chain::Mainnet), true).unwrap(), weth);
+        assert_eq!(resolve_weth(None, None, false).unwrap(), Address::zero());
+        let res = resolve_weth(None, Some(Chain::MoonbeamDev), true);
+        assert!(matches!(res.unwrap_err(), Error::WrappedNativeNotFound(Some(Chain::MoonbeamDev))));
+        #[cfg(feature = "addresses")]
+        assert_eq!(
+            resolve_weth(None, Some(Chain::Mainnet), true).unwrap(),
+            crate::contracts::addresses::address("WETH", Chain::Mainnet)
+        );
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("1.5", 18).unwrap(), U256::exp10(17) * 15);
+        assert_eq!(parse_units("1", 6).unwrap(), U256::exp10(6));
+        assert_eq!(parse_units(".5", 1).unwrap(), 5.into());
+        assert_eq!(parse_units("0", 18).unwrap(), U256::zero());
+        assert_eq!(parse_units("0.0", 18).unwrap(), U256::zero());
+        assert_eq!(parse_units("007", 0).unwrap(), 7.into());
+
+        // trailing zeros
+        assert_eq!(parse_units("1.50000000", 2).unwrap(), 150.into());
+        assert_eq!(parse_units("1.", 2).unwrap(), 100.into());
+
+        for invalid in ["", ".", "1.2.3", "-1", "1e18", " 1", "0x10", "1.234"] {
+            assert!(matches!(parse_units(invalid, 2).unwrap_err(), Error::InvalidUnits(_)));
+        }
+    }
+
+    #[test]
+    fn test_units_zero_decimals() {
+        assert_eq!(parse_units("42", 0).unwrap(), 42.into());
+        assert_eq!(parse_units("42.000", 0).unwrap(), 42.into());
+        assert!(parse_units("42.1", 0).is_err());
+        assert_eq!(format_units(42.into(), 0), "42");
+    }
+
+    #[test]
+    fn test_units_large() {
+        let max = U256::MAX.to_string();
+        assert_eq!(max.len(), 78);
+        assert_eq!(parse_units(&max, 0).unwrap(), U256::MAX);
+
+        let digits = "9".repeat(77);
+        assert_eq!(parse_units(&digits, 0).unwrap(), U256::from_dec_str(&digits).unwrap());
+        assert!(parse_units(&digits, 18).is_err());
+        assert!(parse_units(&format!("{max}0"), 0).is_err());
+
+        let (int, frac) = max.split_at(60);
+        let value = format!("{int}.{frac}");
+        assert_eq!(parse_units(&value, 18).unwrap(), U256::MAX);
+        assert_eq!(format_units(U256::MAX, 18), value);
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(U256::exp10(17) * 15, 18), "1.5");
+        assert_eq!(format_units(U256::exp10(18), 18), "1");
+        assert_eq!(format_units(1.into(), 18), "0.000000000000000001");
+        assert_eq!(format_units(U256::zero(), 6), "0");
+
+        for value in ["1.5", "0.000001", "123456789.123", "0"] {
+            assert_eq!(format_units(parse_units(value, 6).unwrap(), 6), value);
+        }
+    }
+}
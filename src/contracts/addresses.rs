@@ -30,16 +30,33 @@
 //! - Spookyswap: <https://docs.spooky.fi/Resources/contracts>
 //! - Traderjoe: <https://docs.traderjoexyz.com/en/security-and-contracts/contracts>
 
+#[cfg(all(feature = "v2", feature = "v3"))]
+use crate::decode::RouterKind;
+use crate::{
+    errors::{Error, Result},
+    ProtocolType,
+};
 use ethers_contract::Lazy;
-use ethers_core::types::{Address, Chain};
-use serde::Deserialize;
-use std::{borrow::Borrow, collections::HashMap};
+use ethers_core::{
+    types::{Address, Chain, H256},
+    utils::to_checksum,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    str::FromStr,
+    sync::{PoisonError, RwLock},
+};
 
 const ADDRESSES_JSON: &str = include_str!("./addresses.json");
 
 static ADDRESS_BOOK: Lazy<HashMap<String, Contract>> =
     Lazy::new(|| serde_json::from_str(ADDRESSES_JSON).unwrap());
 
+static REGISTRY: Lazy<RwLock<AddressBook>> = Lazy::new(Default::default);
+
 /// Wrapper around a hash map that maps a [Chain] to the contract's deployed address on that chain.
 #[derive(Clone, Debug, Deserialize)]
 pub struct Contract {
@@ -87,6 +104,382 @@ pub fn address<S: Borrow<str>, C: Borrow<Chain>>(name: S, chain: C) -> Address {
     })
 }
 
+/// Returns the addresses of the common base tokens used for routing swaps on `chain`: `WETH`,
+/// `USDC`, `USDT` and `DAI`, if present in the addressbook.
+pub fn base_tokens<C: Borrow<Chain>>(chain: C) -> Vec<Address> {
+    let chain = chain.borrow();
+    ["WETH", "USDC", "USDT", "DAI"]
+        .into_iter()
+        .filter_map(|name| try_address(name, chain))
+        .collect()
+}
+
+/// Returns the address of the ERC20-wrapped native token of `chain`, like WETH on Ethereum, WBNB
+/// on BSC or WMATIC on Polygon, if present in the addressbook.
+pub fn wrapped_native<C: Borrow<Chain>>(chain: C) -> Option<Address> {
+    try_address("WETH", chain)
+}
+
+/// The addresses of a protocol's deployment on a chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractAddresses {
+    /// The factory address.
+    pub factory: Address,
+    /// The router address.
+    pub router: Address,
+    /// The addresses of the protocol's other contracts, by name.
+    ///
+    /// Uniswap V3 protocols read their quoters from `"quoter"` and, optionally, `"quoter_v2"`.
+    pub extra: HashMap<String, Address>,
+    /// The pair code hash override of a Uniswap V2 protocol. See
+    /// `v2::Factory::set_pair_code_hash`.
+    pub init_code_hash: Option<H256>,
+}
+
+impl ContractAddresses {
+    /// Creates a new instance with the provided factory and router addresses.
+    pub fn new(factory: Address, router: Address) -> Self {
+        Self { factory, router, extra: HashMap::new(), init_code_hash: None }
+    }
+
+    /// Sets the pair code hash override.
+    pub fn with_init_code_hash(mut self, init_code_hash: H256) -> Self {
+        self.init_code_hash = Some(init_code_hash);
+        self
+    }
+
+    /// Sets the address of the contract `name`.
+    pub fn with_extra(mut self, name: impl Into<String>, address: Address) -> Self {
+        self.extra.insert(name.into(), address);
+        self
+    }
+
+    /// Returns the address of the contract `name`.
+    pub fn extra(&self, name: &str) -> Option<Address> {
+        self.extra.get(name).copied()
+    }
+}
+
+/// An addressbook of protocol deployments, which can be extended at runtime with new forks or
+/// deployments, like on testnets.
+///
+/// Lookups prefer the inserted entries over the addresses built into the crate.
+///
+/// Entries can be loaded from, and dumped to, JSON or TOML files with the following schema, where
+/// chains are keyed by ID or name, and any other key is an extra address:
+///
+/// ```json
+/// {
+///   "uniswap-v2": {
+///     "1": {
+///       "factory": "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+///       "router": "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+///       "init_code_hash": "0x96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"
+///     }
+///   }
+/// }
+/// ```
+///
+/// See [`register`] to add entries to the process-global addressbook, which is used by the
+/// `new_with_chain` constructors.
+///
+/// # Example
+///
+/// ```
+/// # use uniswap_rs::{contracts::addresses::{AddressBook, ContractAddresses}, prelude::{*, _ethers::*}};
+/// let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+/// let mut book = AddressBook::new();
+/// book.insert(ProtocolType::UniswapV2, Chain::Sepolia, ContractAddresses::new(factory, router));
+/// assert_eq!(
+///     book.try_addresses(ProtocolType::UniswapV2, Chain::Sepolia),
+///     (Some(factory), Some(router))
+/// );
+/// // built-in addresses
+/// assert!(book.try_addresses(ProtocolType::UniswapV2, Chain::Mainnet).0.is_some());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressBook {
+    /// The inserted entries.
+    entries: HashMap<(ProtocolType, Chain), ContractAddresses>,
+}
+
+impl AddressBook {
+    /// Creates a new addressbook with only the built-in addresses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the addresses of `protocol` on `chain`, returning the previous entry.
+    pub fn insert(
+        &mut self,
+        protocol: ProtocolType,
+        chain: Chain,
+        addresses: ContractAddresses,
+    ) -> Option<ContractAddresses> {
+        self.entries.insert((protocol, chain), addresses)
+    }
+
+    /// Removes the inserted addresses of `protocol` on `chain`, returning them.
+    pub fn remove(&mut self, protocol: ProtocolType, chain: Chain) -> Option<ContractAddresses> {
+        self.entries.remove(&(protocol, chain))
+    }
+
+    /// Returns the inserted addresses of `protocol` on `chain`, ignoring the built-in ones.
+    pub fn get(&self, protocol: ProtocolType, chain: Chain) -> Option<&ContractAddresses> {
+        self.entries.get(&(protocol, chain))
+    }
+
+    /// Returns (factory_address, router_address) of `protocol` on `chain`, preferring the
+    /// inserted addresses over the built-in ones.
+    pub fn try_addresses(
+        &self,
+        protocol: ProtocolType,
+        chain: Chain,
+    ) -> (Option<Address>, Option<Address>) {
+        match self.get(protocol, chain) {
+            Some(addresses) => (Some(addresses.factory), Some(addresses.router)),
+            None => builtin_addresses(protocol, chain),
+        }
+    }
+
+    /// Returns the pair code hash override of `protocol` on `chain`, if inserted.
+    pub fn init_code_hash(&self, protocol: ProtocolType, chain: Chain) -> Option<H256> {
+        self.get(protocol, chain).and_then(|addresses| addresses.init_code_hash)
+    }
+
+    /// Returns the kind of the router at `address`, if it is the router of an inserted entry, or a
+    /// built-in Uniswap V2 or V3 router, or the Universal Router.
+    ///
+    /// Solidly routers are not supported.
+    #[cfg(all(feature = "v2", feature = "v3"))]
+    pub fn router_kind(&self, address: Address) -> Option<RouterKind> {
+        let kind = |protocol: ProtocolType| match protocol {
+            _ if protocol.is_solidly() => None,
+            _ if protocol.is_v2() => Some(RouterKind::V2),
+            _ => Some(RouterKind::V3),
+        };
+        if let Some((&(protocol, _), _)) =
+            self.entries.iter().find(|(_, addresses)| addresses.router == address)
+        {
+            return kind(protocol);
+        }
+
+        let is_deployed = |name: &str| {
+            try_contract(name).map_or(false, |c| c.addresses.values().any(|&a| a == address))
+        };
+        if is_deployed("UniversalRouter") {
+            return Some(RouterKind::UniversalRouter);
+        }
+        if is_deployed("UniswapV2Router01") {
+            return Some(RouterKind::V2);
+        }
+        if is_deployed("UniswapV3Router01") {
+            return Some(RouterKind::V3);
+        }
+        ProtocolType::all()
+            .into_iter()
+            .find(|protocol| is_deployed(protocol.contract_names().1))
+            .and_then(kind)
+    }
+
+    /// Inserts all the entries of `other`, overriding the existing ones.
+    pub fn extend(&mut self, other: AddressBook) {
+        self.entries.extend(other.entries)
+    }
+
+    /// Returns a new addressbook with an entry for every built-in deployment of the defined
+    /// protocols, so that it can be [dumped][Self::to_json] along with the inserted entries.
+    pub fn builtin() -> Self {
+        let mut this = Self::new();
+        let protocols =
+            ProtocolType::all().into_iter().chain([ProtocolType::Velodrome { stable: true }]);
+        for protocol in protocols {
+            let (factory_name, _) = protocol.contract_names();
+            let chains = try_contract(factory_name).map(|c| c.addresses.keys().copied().collect());
+            for chain in chains.unwrap_or_else(Vec::new) {
+                if let (Some(factory), Some(router)) = builtin_addresses(protocol, chain) {
+                    this.insert(protocol, chain, ContractAddresses::new(factory, router));
+                }
+            }
+        }
+        this
+    }
+
+    /// Parses an addressbook from JSON. See [this type's documentation][AddressBook] for the
+    /// schema.
+    ///
+    /// Returns [`Error::InvalidAddressBook`] if the JSON is malformed, or if a protocol, chain,
+    /// address or hash is invalid. Mixed-case addresses must have a valid EIP-55 checksum.
+    pub fn from_json<R: Read>(reader: R) -> Result<Self> {
+        let raw: RawAddressBook = serde_json::from_reader(reader).map_err(|e| invalid("", e))?;
+        Self::from_raw(raw)
+    }
+
+    /// Parses an addressbook from TOML. See [`from_json`][Self::from_json] for more details.
+    #[cfg(feature = "toml")]
+    pub fn from_toml<R: Read>(mut reader: R) -> Result<Self> {
+        let mut s = String::new();
+        reader.read_to_string(&mut s).map_err(|e| invalid("", e))?;
+        let raw: RawAddressBook = toml::from_str(&s).map_err(|e| invalid("", e))?;
+        Self::from_raw(raw)
+    }
+
+    /// Serializes the inserted entries to pretty JSON, which can be parsed back with
+    /// [`from_json`][Self::from_json].
+    ///
+    /// Returns [`Error::InvalidAddressBook`] if an entry is of a custom protocol.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.to_raw()?).map_err(|e| invalid("", e))
+    }
+
+    /// Serializes the inserted entries to TOML. See [`to_json`][Self::to_json] for more details.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(&self.to_raw()?).map_err(|e| invalid("", e))
+    }
+
+    fn from_raw(raw: RawAddressBook) -> Result<Self> {
+        let mut this = Self::new();
+        for (protocol_key, chains) in raw {
+            let protocol: ProtocolType =
+                protocol_key.parse().map_err(|e| invalid(&protocol_key, e))?;
+            for (chain_key, entry) in chains {
+                let key = format!("{protocol_key}.{chain_key}");
+                let chain = match chain_key.parse::<u64>() {
+                    Ok(id) => Chain::try_from(id).ok(),
+                    Err(_) => Chain::from_str(&chain_key).ok(),
+                }
+                .ok_or_else(|| invalid(&key, "unknown chain"))?;
+
+                let field = |name: &str| format!("{key}.{name}");
+                let required = |name: &str, value: Option<String>| {
+                    value.ok_or_else(|| invalid(&field(name), "missing address"))
+                };
+                let factory =
+                    parse_address(&field("factory"), &required("factory", entry.factory)?)?;
+                let router = parse_address(&field("router"), &required("router", entry.router)?)?;
+                let mut addresses = ContractAddresses::new(factory, router);
+                if let Some(hash) = entry.init_code_hash {
+                    let hash =
+                        H256::from_str(&hash).map_err(|e| invalid(&field("init_code_hash"), e))?;
+                    addresses.init_code_hash = Some(hash);
+                }
+                for (name, address) in entry.extra {
+                    let address = parse_address(&field(&name), &address)?;
+                    addresses.extra.insert(name, address);
+                }
+                this.insert(protocol, chain, addresses);
+            }
+        }
+        Ok(this)
+    }
+
+    fn to_raw(&self) -> Result<RawAddressBook> {
+        let mut raw = RawAddressBook::new();
+        for (&(protocol, chain), addresses) in &self.entries {
+            let protocol_key = protocol_key(protocol)
+                .ok_or_else(|| invalid(&protocol.to_string(), "custom protocols have no key"))?;
+            let entry = RawEntry {
+                factory: Some(to_checksum(&addresses.factory, None)),
+                router: Some(to_checksum(&addresses.router, None)),
+                init_code_hash: addresses.init_code_hash.map(|hash| format!("{hash:?}")),
+                extra: addresses
+                    .extra
+                    .iter()
+                    .map(|(name, address)| (name.clone(), to_checksum(address, None)))
+                    .collect(),
+            };
+            let chains = raw.entry(protocol_key.to_string()).or_default();
+            chains.insert((chain as u64).to_string(), entry);
+        }
+        Ok(raw)
+    }
+}
+
+/// The file representation of an [`AddressBook`]: protocol key => chain key => entry.
+type RawAddressBook = BTreeMap<String, BTreeMap<String, RawEntry>>;
+
+/// The file representation of a [`ContractAddresses`].
+#[derive(Deserialize, Serialize)]
+struct RawEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    factory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    router: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    init_code_hash: Option<String>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, String>,
+}
+
+fn invalid(key: &str, reason: impl ToString) -> Error {
+    Error::InvalidAddressBook { key: key.to_string(), reason: reason.to_string() }
+}
+
+/// Parses an address, validating its checksum if it is mixed-case.
+fn parse_address(key: &str, s: &str) -> Result<Address> {
+    let address = Address::from_str(s).map_err(|e| invalid(key, e))?;
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    let mixed_case =
+        hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case {
+        let checksummed = to_checksum(&address, None);
+        if checksummed[2..] != *hex {
+            return Err(invalid(key, format!("invalid checksum, expected {checksummed}")));
+        }
+    }
+    Ok(address)
+}
+
+/// Returns the addressbook file key of `protocol`, its name, or None if it is custom.
+fn protocol_key(protocol: ProtocolType) -> Option<&'static str> {
+    match protocol {
+        ProtocolType::Custom(_) => None,
+        protocol => Some(protocol.name()),
+    }
+}
+
+/// Returns (factory_address, router_address) of `protocol` on `chain` from the built-in
+/// addressbook.
+fn builtin_addresses(protocol: ProtocolType, chain: Chain) -> (Option<Address>, Option<Address>) {
+    if let ProtocolType::Custom(custom) = protocol {
+        (Some(custom.factory), Some(custom.router))
+    } else {
+        let (factory_name, router_name) = protocol.contract_names();
+        (try_address(factory_name, chain), try_address(router_name, chain))
+    }
+}
+
+/// Registers the addresses of `protocol` on `chain` in the process-global addressbook, returning
+/// the previously registered entry.
+///
+/// Meant to be called once at startup, so that the `new_with_chain` constructors find the
+/// application's own deployments.
+pub fn register(
+    protocol: ProtocolType,
+    chain: Chain,
+    addresses: ContractAddresses,
+) -> Option<ContractAddresses> {
+    REGISTRY.write().unwrap_or_else(PoisonError::into_inner).insert(protocol, chain, addresses)
+}
+
+/// Removes the addresses of `protocol` on `chain` from the process-global addressbook, returning
+/// them.
+pub fn unregister(protocol: ProtocolType, chain: Chain) -> Option<ContractAddresses> {
+    REGISTRY.write().unwrap_or_else(PoisonError::into_inner).remove(protocol, chain)
+}
+
+/// Registers all the entries of `book` in the process-global addressbook, like one loaded with
+/// [`AddressBook::from_json`]. See [`register`].
+pub fn register_book(book: AddressBook) {
+    REGISTRY.write().unwrap_or_else(PoisonError::into_inner).extend(book)
+}
+
+/// Calls `f` with the process-global addressbook. See [`register`].
+pub fn with_registry<R>(f: impl FnOnce(&AddressBook) -> R) -> R {
+    f(&REGISTRY.read().unwrap_or_else(PoisonError::into_inner))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +499,194 @@ mod tests {
         assert!(try_address("DAI", Chain::Mainnet).is_some());
         assert!(try_address("DAI", Chain::MoonbeamDev).is_none());
     }
+
+    #[test]
+    fn test_address_book() {
+        let protocol = ProtocolType::Sushiswap;
+        let builtin = builtin_addresses(protocol, Chain::Mainnet);
+        let addresses = ContractAddresses::new(Address::repeat_byte(1), Address::repeat_byte(2))
+            .with_extra("quoter", Address::repeat_byte(3));
+        assert_eq!(addresses.extra("quoter"), Some(Address::repeat_byte(3)));
+
+        let mut book = AddressBook::new();
+        assert_eq!(book.try_addresses(protocol, Chain::Mainnet), builtin);
+        assert_eq!(book.try_addresses(protocol, Chain::Sepolia), (None, None));
+
+        // user entries override the built-in ones
+        assert!(book.insert(protocol, Chain::Mainnet, addresses.clone()).is_none());
+        let expected = (Some(Address::repeat_byte(1)), Some(Address::repeat_byte(2)));
+        assert_eq!(book.try_addresses(protocol, Chain::Mainnet), expected);
+        assert_eq!(book.get(protocol, Chain::Mainnet), Some(&addresses));
+        assert_eq!(book.get(ProtocolType::UniswapV2, Chain::Mainnet), None);
+        assert_eq!(book.remove(protocol, Chain::Mainnet), Some(addresses));
+        assert_eq!(book.try_addresses(protocol, Chain::Mainnet), builtin);
+    }
+
+    #[test]
+    fn test_register() {
+        // a chain which no other test uses
+        let (protocol, chain) = (ProtocolType::Traderjoe, Chain::Chiado);
+        let addresses = ContractAddresses::new(Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(protocol.try_addresses(chain), (None, None));
+        assert!(register(protocol, chain, addresses.clone()).is_none());
+        assert_eq!(protocol.addresses(chain), (addresses.factory, addresses.router));
+        assert!(with_registry(|book| book.get(protocol, chain).is_some()));
+        assert_eq!(unregister(protocol, chain), Some(addresses));
+        assert_eq!(protocol.try_addresses(chain), (None, None));
+    }
+
+    #[cfg(any(feature = "v2", feature = "toml"))]
+    const BOOK_JSON: &str = r#"{
+        "uniswap-v2": {
+            "1": {
+                "factory": "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+                "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "init_code_hash": "0x96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"
+            }
+        },
+        "uniswap-v3": {
+            "sepolia": {
+                "factory": "0x0227628f3F023bb0B980b67D528571c95c6DaC1c",
+                "router": "0x3bFA4769FB09eefC5a80d6E87c3B9C650f7Ae48E",
+                "quoter": "0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3"
+            }
+        }
+    }"#;
+
+    #[test]
+    #[cfg(feature = "v2")]
+    fn test_address_book_json() {
+        use crate::v2;
+        use ethers_providers::Provider;
+
+        let book = AddressBook::from_json(BOOK_JSON.as_bytes()).unwrap();
+        let uniswap_v2 = book.get(ProtocolType::UniswapV2, Chain::Mainnet).unwrap();
+        assert_eq!(uniswap_v2.factory, address("UniswapV2Factory", Chain::Mainnet));
+        assert_eq!(uniswap_v2.router, address("UniswapV2Router02", Chain::Mainnet));
+        let uniswap_v3 = book.get(ProtocolType::UniswapV3, Chain::Sepolia).unwrap();
+        assert!(uniswap_v3.extra("quoter").is_some());
+
+        // the loaded pair code hash is used
+        let client = std::sync::Arc::new(Provider::mocked().0);
+        let protocol = ProtocolType::UniswapV2;
+        let v2 = v2::Protocol::new_with_chain(client, Chain::Mainnet, protocol, Some(&book));
+        assert_eq!(v2.unwrap().factory().pair_code_hash(None), uniswap_v2.init_code_hash.unwrap());
+
+        let json = book.to_json().unwrap();
+        assert_eq!(AddressBook::from_json(json.as_bytes()).unwrap(), book);
+
+        // the built-in addresses can be dumped too
+        let builtin = AddressBook::builtin();
+        assert_eq!(
+            builtin.try_addresses(protocol, Chain::Goerli),
+            builtin_addresses(protocol, Chain::Goerli)
+        );
+        let velodrome = ProtocolType::Velodrome { stable: true };
+        assert!(builtin.get(velodrome, Chain::Optimism).is_some());
+        let json = builtin.to_json().unwrap();
+        assert_eq!(AddressBook::from_json(json.as_bytes()).unwrap(), builtin);
+    }
+
+    #[test]
+    fn test_address_book_errors() {
+        let err = |json: &str| match AddressBook::from_json(json.as_bytes()).unwrap_err() {
+            Error::InvalidAddressBook { key, reason } => (key, reason),
+            e => panic!("{e:?}"),
+        };
+        let (a, b) = (
+            "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+            "0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f",
+        );
+
+        assert_eq!(err(r#"{ "uniswap": {} }"#).0, "uniswap");
+        assert_eq!(err(r#"{ "sushiswap": { "0": {} } }"#).0, "sushiswap.0");
+        let json = format!(r#"{{ "sushiswap": {{ "mainnet": {{ "factory": "{a}" }} }} }}"#);
+        assert_eq!(err(&json).0, "sushiswap.mainnet.router");
+        let json = format!(
+            r#"{{ "sushiswap": {{ "1": {{ "factory": "{a}", "router": "{b}", "x": "0x1" }} }} }}"#
+        );
+        assert_eq!(err(&json).0, "sushiswap.1.x");
+        let json = format!(
+            r#"{{ "sushiswap": {{ "1": {{ "factory": "{a}", "router": "{b}", "init_code_hash": "{b}" }} }} }}"#
+        );
+        assert_eq!(err(&json).0, "sushiswap.1.init_code_hash");
+        assert_eq!(err("[]").0, "");
+
+        // mixed-case addresses are checksummed
+        let bad = "0x5c69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f";
+        let json =
+            format!(r#"{{ "sushiswap": {{ "1": {{ "factory": "{bad}", "router": "{b}" }} }} }}"#);
+        let (key, reason) = err(&json);
+        assert_eq!(key, "sushiswap.1.factory");
+        assert!(reason.contains(a), "{reason}");
+
+        let mut book = AddressBook::new();
+        let custom = ProtocolType::new(Address::zero(), Address::zero(), true, H256::zero());
+        book.insert(custom, Chain::Mainnet, ContractAddresses::default());
+        assert!(matches!(book.to_json().unwrap_err(), Error::InvalidAddressBook { .. }));
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_address_book_toml() {
+        let toml = r#"
+            [uniswap-v2.1]
+            factory = "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"
+            router = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D"
+            init_code_hash = "0x96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"
+
+            [uniswap-v3.sepolia]
+            factory = "0x0227628f3F023bb0B980b67D528571c95c6DaC1c"
+            router = "0x3bFA4769FB09eefC5a80d6E87c3B9C650f7Ae48E"
+            quoter = "0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3"
+        "#;
+        let book = AddressBook::from_toml(toml.as_bytes()).unwrap();
+        assert_eq!(book, AddressBook::from_json(BOOK_JSON.as_bytes()).unwrap());
+        let toml = book.to_toml().unwrap();
+        assert_eq!(AddressBook::from_toml(toml.as_bytes()).unwrap(), book);
+    }
+
+    #[test]
+    fn test_wrapped_native() {
+        let chains = [
+            Chain::Mainnet,
+            Chain::Arbitrum,
+            Chain::Optimism,
+            Chain::Polygon,
+            Chain::BinanceSmartChain,
+            Chain::Avalanche,
+            Chain::Fantom,
+            Chain::XDai,
+        ];
+        for chain in chains {
+            assert!(wrapped_native(chain).is_some(), "{chain:?}");
+        }
+        assert_eq!(
+            wrapped_native(Chain::Polygon),
+            Some("0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270".parse().unwrap())
+        );
+        assert_eq!(wrapped_native(Chain::MoonbeamDev), None);
+    }
+
+    /// A typo in a mixed-case address breaks its EIP-55 checksum.
+    #[test]
+    fn test_checksums() {
+        let raw: HashMap<String, HashMap<String, HashMap<String, String>>> =
+            serde_json::from_str(ADDRESSES_JSON).unwrap();
+        for (name, contract) in raw {
+            for (chain, s) in &contract["addresses"] {
+                let address: Address = s.parse().unwrap();
+                if s[2..].chars().any(|c| c.is_ascii_uppercase()) {
+                    assert_eq!(&to_checksum(&address, None), s, "{name}.{chain}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_tokens() {
+        assert_eq!(base_tokens(Chain::Mainnet).len(), 4);
+        assert_eq!(base_tokens(Chain::Mainnet)[0], address("WETH", Chain::Mainnet));
+        assert!(base_tokens(Chain::MoonbeamDev).is_empty());
+    }
 }
@@ -0,0 +1,1551 @@
+pub use i_nonfungible_position_manager::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_nonfungible_position_manager {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "INonfungiblePositionManager was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/INonfungiblePositionManager.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static INONFUNGIBLEPOSITIONMANAGER_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct INonfungiblePositionManager<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for INonfungiblePositionManager<M> {
+        fn clone(&self) -> Self {
+            INonfungiblePositionManager(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for INonfungiblePositionManager<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for INonfungiblePositionManager<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(INonfungiblePositionManager)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> INonfungiblePositionManager<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(
+                address.into(),
+                INONFUNGIBLEPOSITIONMANAGER_ABI.clone(),
+                client,
+            )
+            .into()
+        }
+        #[doc = "Calls the contract's `WETH9` (0x4aa4a4fc) function"]
+        pub fn weth9(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([74, 164, 164, 252], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `approve` (0x095ea7b3) function"]
+        pub fn approve(
+            &self,
+            to: ethers_core::types::Address,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([9, 94, 167, 179], (to, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `balanceOf` (0x70a08231) function"]
+        pub fn balance_of(
+            &self,
+            owner: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([112, 160, 130, 49], owner)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `burn` (0x42966c68) function"]
+        pub fn burn(
+            &self,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([66, 150, 108, 104], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `collect` (0xfc6f7865) function"]
+        pub fn collect(
+            &self,
+            params: CollectParams,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([252, 111, 120, 101], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `createAndInitializePoolIfNecessary` (0x13ead562) function"]
+        pub fn create_and_initialize_pool_if_necessary(
+            &self,
+            token_0: ethers_core::types::Address,
+            token_1: ethers_core::types::Address,
+            fee: u32,
+            sqrt_price_x96: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([19, 234, 213, 98], (token_0, token_1, fee, sqrt_price_x96))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `decreaseLiquidity` (0x0c49ccbe) function"]
+        pub fn decrease_liquidity(
+            &self,
+            params: DecreaseLiquidityParams,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([12, 73, 204, 190], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `factory` (0xc45a0155) function"]
+        pub fn factory(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([196, 90, 1, 85], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getApproved` (0x081812fc) function"]
+        pub fn get_approved(
+            &self,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([8, 24, 18, 252], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `increaseLiquidity` (0x219f5d17) function"]
+        pub fn increase_liquidity(
+            &self,
+            params: IncreaseLiquidityParams,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (u128, ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([33, 159, 93, 23], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `isApprovedForAll` (0xe985e9c5) function"]
+        pub fn is_approved_for_all(
+            &self,
+            owner: ethers_core::types::Address,
+            operator: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, bool> {
+            self.0
+                .method_hash([233, 133, 233, 197], (owner, operator))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `mint` (0x88316456) function"]
+        pub fn mint(
+            &self,
+            params: MintParams,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, u128, ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([136, 49, 100, 86], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0xac9650d8) function"]
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `ownerOf` (0x6352211e) function"]
+        pub fn owner_of(
+            &self,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([99, 82, 33, 30], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `positions` (0x99fbab88) function"]
+        pub fn positions(
+            &self,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (
+                u128,
+                ethers_core::types::Address,
+                ethers_core::types::Address,
+                ethers_core::types::Address,
+                u32,
+                i32,
+                i32,
+                u128,
+                ethers_core::types::U256,
+                ethers_core::types::U256,
+                u128,
+                u128,
+            ),
+        > {
+            self.0
+                .method_hash([153, 251, 171, 136], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `refundETH` (0x12210e8a) function"]
+        pub fn refund_eth(&self) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([18, 33, 14, 138], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `safeTransferFrom` (0x42842e0e) function"]
+        pub fn safe_transfer_from(
+            &self,
+            from: ethers_core::types::Address,
+            to: ethers_core::types::Address,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([66, 132, 46, 14], (from, to, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `safeTransferFrom` (0xb88d4fde) function"]
+        pub fn safe_transfer_from_with_from_and_to_and_data(
+            &self,
+            from: ethers_core::types::Address,
+            to: ethers_core::types::Address,
+            token_id: ethers_core::types::U256,
+            data: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([184, 141, 79, 222], (from, to, token_id, data))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `setApprovalForAll` (0xa22cb465) function"]
+        pub fn set_approval_for_all(
+            &self,
+            operator: ethers_core::types::Address,
+            approved: bool,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([162, 44, 180, 101], (operator, approved))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `sweepToken` (0xdf2ab5bb) function"]
+        pub fn sweep_token(
+            &self,
+            token: ethers_core::types::Address,
+            amount_minimum: ethers_core::types::U256,
+            recipient: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([223, 42, 181, 187], (token, amount_minimum, recipient))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `tokenByIndex` (0x4f6ccce7) function"]
+        pub fn token_by_index(
+            &self,
+            index: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([79, 108, 204, 231], index)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `tokenOfOwnerByIndex` (0x2f745c59) function"]
+        pub fn token_of_owner_by_index(
+            &self,
+            owner: ethers_core::types::Address,
+            index: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([47, 116, 92, 89], (owner, index))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `totalSupply` (0x18160ddd) function"]
+        pub fn total_supply(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([24, 22, 13, 221], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `transferFrom` (0x23b872dd) function"]
+        pub fn transfer_from(
+            &self,
+            from: ethers_core::types::Address,
+            to: ethers_core::types::Address,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([35, 184, 114, 221], (from, to, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `unwrapWETH9` (0x49404b7c) function"]
+        pub fn unwrap_weth9(
+            &self,
+            amount_minimum: ethers_core::types::U256,
+            recipient: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([73, 64, 75, 124], (amount_minimum, recipient))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Gets the contract's `Approval` event"]
+        pub fn approval_filter(&self) -> ethers_contract::builders::Event<M, ApprovalFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `ApprovalForAll` event"]
+        pub fn approval_for_all_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, ApprovalForAllFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `Collect` event"]
+        pub fn collect_filter(&self) -> ethers_contract::builders::Event<M, CollectFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `DecreaseLiquidity` event"]
+        pub fn decrease_liquidity_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, DecreaseLiquidityFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `IncreaseLiquidity` event"]
+        pub fn increase_liquidity_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, IncreaseLiquidityFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `Transfer` event"]
+        pub fn transfer_filter(&self) -> ethers_contract::builders::Event<M, TransferFilter> {
+            self.0.event()
+        }
+        #[doc = r" Returns an [`Event`](#ethers_contract::builders::Event) builder for all events of this contract"]
+        pub fn events(
+            &self,
+        ) -> ethers_contract::builders::Event<M, INonfungiblePositionManagerEvents> {
+            self.0.event_with_filter(Default::default())
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>>
+        for INonfungiblePositionManager<M>
+    {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Approval", abi = "Approval(address,address,uint256)")]
+    pub struct ApprovalFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub approved: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "ApprovalForAll", abi = "ApprovalForAll(address,address,bool)")]
+    pub struct ApprovalForAllFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub operator: ethers_core::types::Address,
+        pub approved: bool,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Collect", abi = "Collect(uint256,address,uint256,uint256)")]
+    pub struct CollectFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethevent(
+        name = "DecreaseLiquidity",
+        abi = "DecreaseLiquidity(uint256,uint128,uint256,uint256)"
+    )]
+    pub struct DecreaseLiquidityFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        pub liquidity: u128,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethevent(
+        name = "IncreaseLiquidity",
+        abi = "IncreaseLiquidity(uint256,uint128,uint256,uint256)"
+    )]
+    pub struct IncreaseLiquidityFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        pub liquidity: u128,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Transfer", abi = "Transfer(address,address,uint256)")]
+    pub struct TransferFilter {
+        #[ethevent(indexed)]
+        pub from: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub to: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum INonfungiblePositionManagerEvents {
+        ApprovalFilter(ApprovalFilter),
+        ApprovalForAllFilter(ApprovalForAllFilter),
+        CollectFilter(CollectFilter),
+        DecreaseLiquidityFilter(DecreaseLiquidityFilter),
+        IncreaseLiquidityFilter(IncreaseLiquidityFilter),
+        TransferFilter(TransferFilter),
+    }
+    impl ethers_contract::EthLogDecode for INonfungiblePositionManagerEvents {
+        fn decode_log(
+            log: &ethers_core::abi::RawLog,
+        ) -> ::std::result::Result<Self, ethers_core::abi::Error>
+        where
+            Self: Sized,
+        {
+            if let Ok(decoded) = ApprovalFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::ApprovalFilter(decoded));
+            }
+            if let Ok(decoded) = ApprovalForAllFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::ApprovalForAllFilter(decoded));
+            }
+            if let Ok(decoded) = CollectFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::CollectFilter(decoded));
+            }
+            if let Ok(decoded) = DecreaseLiquidityFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::DecreaseLiquidityFilter(decoded));
+            }
+            if let Ok(decoded) = IncreaseLiquidityFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::IncreaseLiquidityFilter(decoded));
+            }
+            if let Ok(decoded) = TransferFilter::decode_log(log) {
+                return Ok(INonfungiblePositionManagerEvents::TransferFilter(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData)
+        }
+    }
+    impl ::std::fmt::Display for INonfungiblePositionManagerEvents {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                INonfungiblePositionManagerEvents::ApprovalFilter(element) => element.fmt(f),
+                INonfungiblePositionManagerEvents::ApprovalForAllFilter(element) => element.fmt(f),
+                INonfungiblePositionManagerEvents::CollectFilter(element) => element.fmt(f),
+                INonfungiblePositionManagerEvents::DecreaseLiquidityFilter(element) => {
+                    element.fmt(f)
+                }
+                INonfungiblePositionManagerEvents::IncreaseLiquidityFilter(element) => {
+                    element.fmt(f)
+                }
+                INonfungiblePositionManagerEvents::TransferFilter(element) => element.fmt(f),
+            }
+        }
+    }
+    #[doc = "Container type for all input parameters for the `WETH9` function with signature `WETH9()` and selector `[74, 164, 164, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "WETH9", abi = "WETH9()")]
+    pub struct Weth9Call;
+    #[doc = "Container type for all input parameters for the `approve` function with signature `approve(address,uint256)` and selector `[9, 94, 167, 179]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "approve", abi = "approve(address,uint256)")]
+    pub struct ApproveCall {
+        pub to: ethers_core::types::Address,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `balanceOf` function with signature `balanceOf(address)` and selector `[112, 160, 130, 49]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "balanceOf", abi = "balanceOf(address)")]
+    pub struct BalanceOfCall {
+        pub owner: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `burn` function with signature `burn(uint256)` and selector `[66, 150, 108, 104]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "burn", abi = "burn(uint256)")]
+    pub struct BurnCall {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `collect` function with signature `collect((uint256,address,uint128,uint128))` and selector `[252, 111, 120, 101]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "collect", abi = "collect((uint256,address,uint128,uint128))")]
+    pub struct CollectCall {
+        pub params: CollectParams,
+    }
+    #[doc = "Container type for all input parameters for the `createAndInitializePoolIfNecessary` function with signature `createAndInitializePoolIfNecessary(address,address,uint24,uint160)` and selector `[19, 234, 213, 98]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "createAndInitializePoolIfNecessary",
+        abi = "createAndInitializePoolIfNecessary(address,address,uint24,uint160)"
+    )]
+    pub struct CreateAndInitializePoolIfNecessaryCall {
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+        pub fee: u32,
+        pub sqrt_price_x96: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `decreaseLiquidity` function with signature `decreaseLiquidity((uint256,uint128,uint256,uint256,uint256))` and selector `[12, 73, 204, 190]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "decreaseLiquidity",
+        abi = "decreaseLiquidity((uint256,uint128,uint256,uint256,uint256))"
+    )]
+    pub struct DecreaseLiquidityCall {
+        pub params: DecreaseLiquidityParams,
+    }
+    #[doc = "Container type for all input parameters for the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "factory", abi = "factory()")]
+    pub struct FactoryCall;
+    #[doc = "Container type for all input parameters for the `getApproved` function with signature `getApproved(uint256)` and selector `[8, 24, 18, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getApproved", abi = "getApproved(uint256)")]
+    pub struct GetApprovedCall {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `increaseLiquidity` function with signature `increaseLiquidity((uint256,uint256,uint256,uint256,uint256,uint256))` and selector `[33, 159, 93, 23]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "increaseLiquidity",
+        abi = "increaseLiquidity((uint256,uint256,uint256,uint256,uint256,uint256))"
+    )]
+    pub struct IncreaseLiquidityCall {
+        pub params: IncreaseLiquidityParams,
+    }
+    #[doc = "Container type for all input parameters for the `isApprovedForAll` function with signature `isApprovedForAll(address,address)` and selector `[233, 133, 233, 197]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "isApprovedForAll", abi = "isApprovedForAll(address,address)")]
+    pub struct IsApprovedForAllCall {
+        pub owner: ethers_core::types::Address,
+        pub operator: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `mint` function with signature `mint((address,address,uint24,int24,int24,uint256,uint256,uint256,uint256,address,uint256))` and selector `[136, 49, 100, 86]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "mint",
+        abi = "mint((address,address,uint24,int24,int24,uint256,uint256,uint256,uint256,address,uint256))"
+    )]
+    pub struct MintCall {
+        pub params: MintParams,
+    }
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `ownerOf` function with signature `ownerOf(uint256)` and selector `[99, 82, 33, 30]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "ownerOf", abi = "ownerOf(uint256)")]
+    pub struct OwnerOfCall {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `positions` function with signature `positions(uint256)` and selector `[153, 251, 171, 136]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "positions", abi = "positions(uint256)")]
+    pub struct PositionsCall {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `refundETH` function with signature `refundETH()` and selector `[18, 33, 14, 138]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "refundETH", abi = "refundETH()")]
+    pub struct RefundETHCall;
+    #[doc = "Container type for all input parameters for the `safeTransferFrom` function with signature `safeTransferFrom(address,address,uint256)` and selector `[66, 132, 46, 14]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "safeTransferFrom", abi = "safeTransferFrom(address,address,uint256)")]
+    pub struct SafeTransferFromCall {
+        pub from: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `safeTransferFrom` function with signature `safeTransferFrom(address,address,uint256,bytes)` and selector `[184, 141, 79, 222]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "safeTransferFrom", abi = "safeTransferFrom(address,address,uint256,bytes)")]
+    pub struct SafeTransferFromWithFromAndToAndDataCall {
+        pub from: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub token_id: ethers_core::types::U256,
+        pub data: ethers_core::types::Bytes,
+    }
+    #[doc = "Container type for all input parameters for the `setApprovalForAll` function with signature `setApprovalForAll(address,bool)` and selector `[162, 44, 180, 101]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "setApprovalForAll", abi = "setApprovalForAll(address,bool)")]
+    pub struct SetApprovalForAllCall {
+        pub operator: ethers_core::types::Address,
+        pub approved: bool,
+    }
+    #[doc = "Container type for all input parameters for the `sweepToken` function with signature `sweepToken(address,uint256,address)` and selector `[223, 42, 181, 187]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "sweepToken", abi = "sweepToken(address,uint256,address)")]
+    pub struct SweepTokenCall {
+        pub token: ethers_core::types::Address,
+        pub amount_minimum: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `tokenByIndex` function with signature `tokenByIndex(uint256)` and selector `[79, 108, 204, 231]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "tokenByIndex", abi = "tokenByIndex(uint256)")]
+    pub struct TokenByIndexCall {
+        pub index: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `tokenOfOwnerByIndex` function with signature `tokenOfOwnerByIndex(address,uint256)` and selector `[47, 116, 92, 89]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "tokenOfOwnerByIndex", abi = "tokenOfOwnerByIndex(address,uint256)")]
+    pub struct TokenOfOwnerByIndexCall {
+        pub owner: ethers_core::types::Address,
+        pub index: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `totalSupply` function with signature `totalSupply()` and selector `[24, 22, 13, 221]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "totalSupply", abi = "totalSupply()")]
+    pub struct TotalSupplyCall;
+    #[doc = "Container type for all input parameters for the `transferFrom` function with signature `transferFrom(address,address,uint256)` and selector `[35, 184, 114, 221]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "transferFrom", abi = "transferFrom(address,address,uint256)")]
+    pub struct TransferFromCall {
+        pub from: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `unwrapWETH9` function with signature `unwrapWETH9(uint256,address)` and selector `[73, 64, 75, 124]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "unwrapWETH9", abi = "unwrapWETH9(uint256,address)")]
+    pub struct UnwrapWETH9Call {
+        pub amount_minimum: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum INonfungiblePositionManagerCalls {
+        Weth9(Weth9Call),
+        Approve(ApproveCall),
+        BalanceOf(BalanceOfCall),
+        Burn(BurnCall),
+        Collect(CollectCall),
+        CreateAndInitializePoolIfNecessary(CreateAndInitializePoolIfNecessaryCall),
+        DecreaseLiquidity(DecreaseLiquidityCall),
+        Factory(FactoryCall),
+        GetApproved(GetApprovedCall),
+        IncreaseLiquidity(IncreaseLiquidityCall),
+        IsApprovedForAll(IsApprovedForAllCall),
+        Mint(MintCall),
+        Multicall(MulticallCall),
+        OwnerOf(OwnerOfCall),
+        Positions(PositionsCall),
+        RefundETH(RefundETHCall),
+        SafeTransferFrom(SafeTransferFromCall),
+        SafeTransferFromWithFromAndToAndData(SafeTransferFromWithFromAndToAndDataCall),
+        SetApprovalForAll(SetApprovalForAllCall),
+        SweepToken(SweepTokenCall),
+        TokenByIndex(TokenByIndexCall),
+        TokenOfOwnerByIndex(TokenOfOwnerByIndexCall),
+        TotalSupply(TotalSupplyCall),
+        TransferFrom(TransferFromCall),
+        UnwrapWETH9(UnwrapWETH9Call),
+    }
+    impl ethers_core::abi::AbiDecode for INonfungiblePositionManagerCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) = <Weth9Call as ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                return Ok(INonfungiblePositionManagerCalls::Weth9(decoded));
+            }
+            if let Ok(decoded) = <ApproveCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::Approve(decoded));
+            }
+            if let Ok(decoded) =
+                <BalanceOfCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::BalanceOf(decoded));
+            }
+            if let Ok(decoded) = <BurnCall as ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                return Ok(INonfungiblePositionManagerCalls::Burn(decoded));
+            }
+            if let Ok(decoded) = <CollectCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::Collect(decoded));
+            }
+            if let Ok(decoded) =
+                <CreateAndInitializePoolIfNecessaryCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(INonfungiblePositionManagerCalls::CreateAndInitializePoolIfNecessary(
+                    decoded,
+                ));
+            }
+            if let Ok(decoded) =
+                <DecreaseLiquidityCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::DecreaseLiquidity(decoded));
+            }
+            if let Ok(decoded) = <FactoryCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::Factory(decoded));
+            }
+            if let Ok(decoded) =
+                <GetApprovedCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::GetApproved(decoded));
+            }
+            if let Ok(decoded) =
+                <IncreaseLiquidityCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::IncreaseLiquidity(decoded));
+            }
+            if let Ok(decoded) =
+                <IsApprovedForAllCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::IsApprovedForAll(decoded));
+            }
+            if let Ok(decoded) = <MintCall as ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                return Ok(INonfungiblePositionManagerCalls::Mint(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::Multicall(decoded));
+            }
+            if let Ok(decoded) = <OwnerOfCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::OwnerOf(decoded));
+            }
+            if let Ok(decoded) =
+                <PositionsCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::Positions(decoded));
+            }
+            if let Ok(decoded) =
+                <RefundETHCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::RefundETH(decoded));
+            }
+            if let Ok(decoded) =
+                <SafeTransferFromCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::SafeTransferFrom(decoded));
+            }
+            if let Ok(decoded) =
+                <SafeTransferFromWithFromAndToAndDataCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(INonfungiblePositionManagerCalls::SafeTransferFromWithFromAndToAndData(
+                    decoded,
+                ));
+            }
+            if let Ok(decoded) =
+                <SetApprovalForAllCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::SetApprovalForAll(decoded));
+            }
+            if let Ok(decoded) =
+                <SweepTokenCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::SweepToken(decoded));
+            }
+            if let Ok(decoded) =
+                <TokenByIndexCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::TokenByIndex(decoded));
+            }
+            if let Ok(decoded) =
+                <TokenOfOwnerByIndexCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::TokenOfOwnerByIndex(decoded));
+            }
+            if let Ok(decoded) =
+                <TotalSupplyCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::TotalSupply(decoded));
+            }
+            if let Ok(decoded) =
+                <TransferFromCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::TransferFrom(decoded));
+            }
+            if let Ok(decoded) =
+                <UnwrapWETH9Call as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(INonfungiblePositionManagerCalls::UnwrapWETH9(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for INonfungiblePositionManagerCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                INonfungiblePositionManagerCalls::Weth9(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Approve(element) => element.encode(),
+                INonfungiblePositionManagerCalls::BalanceOf(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Burn(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Collect(element) => element.encode(),
+                INonfungiblePositionManagerCalls::CreateAndInitializePoolIfNecessary(element) => {
+                    element.encode()
+                }
+                INonfungiblePositionManagerCalls::DecreaseLiquidity(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Factory(element) => element.encode(),
+                INonfungiblePositionManagerCalls::GetApproved(element) => element.encode(),
+                INonfungiblePositionManagerCalls::IncreaseLiquidity(element) => element.encode(),
+                INonfungiblePositionManagerCalls::IsApprovedForAll(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Mint(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Multicall(element) => element.encode(),
+                INonfungiblePositionManagerCalls::OwnerOf(element) => element.encode(),
+                INonfungiblePositionManagerCalls::Positions(element) => element.encode(),
+                INonfungiblePositionManagerCalls::RefundETH(element) => element.encode(),
+                INonfungiblePositionManagerCalls::SafeTransferFrom(element) => element.encode(),
+                INonfungiblePositionManagerCalls::SafeTransferFromWithFromAndToAndData(element) => {
+                    element.encode()
+                }
+                INonfungiblePositionManagerCalls::SetApprovalForAll(element) => element.encode(),
+                INonfungiblePositionManagerCalls::SweepToken(element) => element.encode(),
+                INonfungiblePositionManagerCalls::TokenByIndex(element) => element.encode(),
+                INonfungiblePositionManagerCalls::TokenOfOwnerByIndex(element) => element.encode(),
+                INonfungiblePositionManagerCalls::TotalSupply(element) => element.encode(),
+                INonfungiblePositionManagerCalls::TransferFrom(element) => element.encode(),
+                INonfungiblePositionManagerCalls::UnwrapWETH9(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for INonfungiblePositionManagerCalls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                INonfungiblePositionManagerCalls::Weth9(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Approve(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::BalanceOf(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Burn(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Collect(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::CreateAndInitializePoolIfNecessary(element) => {
+                    element.fmt(f)
+                }
+                INonfungiblePositionManagerCalls::DecreaseLiquidity(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Factory(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::GetApproved(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::IncreaseLiquidity(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::IsApprovedForAll(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Mint(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Multicall(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::OwnerOf(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::Positions(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::RefundETH(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::SafeTransferFrom(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::SafeTransferFromWithFromAndToAndData(element) => {
+                    element.fmt(f)
+                }
+                INonfungiblePositionManagerCalls::SetApprovalForAll(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::SweepToken(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::TokenByIndex(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::TokenOfOwnerByIndex(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::TotalSupply(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::TransferFrom(element) => element.fmt(f),
+                INonfungiblePositionManagerCalls::UnwrapWETH9(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<Weth9Call> for INonfungiblePositionManagerCalls {
+        fn from(var: Weth9Call) -> Self {
+            INonfungiblePositionManagerCalls::Weth9(var)
+        }
+    }
+    impl ::std::convert::From<ApproveCall> for INonfungiblePositionManagerCalls {
+        fn from(var: ApproveCall) -> Self {
+            INonfungiblePositionManagerCalls::Approve(var)
+        }
+    }
+    impl ::std::convert::From<BalanceOfCall> for INonfungiblePositionManagerCalls {
+        fn from(var: BalanceOfCall) -> Self {
+            INonfungiblePositionManagerCalls::BalanceOf(var)
+        }
+    }
+    impl ::std::convert::From<BurnCall> for INonfungiblePositionManagerCalls {
+        fn from(var: BurnCall) -> Self {
+            INonfungiblePositionManagerCalls::Burn(var)
+        }
+    }
+    impl ::std::convert::From<CollectCall> for INonfungiblePositionManagerCalls {
+        fn from(var: CollectCall) -> Self {
+            INonfungiblePositionManagerCalls::Collect(var)
+        }
+    }
+    impl ::std::convert::From<CreateAndInitializePoolIfNecessaryCall>
+        for INonfungiblePositionManagerCalls
+    {
+        fn from(var: CreateAndInitializePoolIfNecessaryCall) -> Self {
+            INonfungiblePositionManagerCalls::CreateAndInitializePoolIfNecessary(var)
+        }
+    }
+    impl ::std::convert::From<DecreaseLiquidityCall> for INonfungiblePositionManagerCalls {
+        fn from(var: DecreaseLiquidityCall) -> Self {
+            INonfungiblePositionManagerCalls::DecreaseLiquidity(var)
+        }
+    }
+    impl ::std::convert::From<FactoryCall> for INonfungiblePositionManagerCalls {
+        fn from(var: FactoryCall) -> Self {
+            INonfungiblePositionManagerCalls::Factory(var)
+        }
+    }
+    impl ::std::convert::From<GetApprovedCall> for INonfungiblePositionManagerCalls {
+        fn from(var: GetApprovedCall) -> Self {
+            INonfungiblePositionManagerCalls::GetApproved(var)
+        }
+    }
+    impl ::std::convert::From<IncreaseLiquidityCall> for INonfungiblePositionManagerCalls {
+        fn from(var: IncreaseLiquidityCall) -> Self {
+            INonfungiblePositionManagerCalls::IncreaseLiquidity(var)
+        }
+    }
+    impl ::std::convert::From<IsApprovedForAllCall> for INonfungiblePositionManagerCalls {
+        fn from(var: IsApprovedForAllCall) -> Self {
+            INonfungiblePositionManagerCalls::IsApprovedForAll(var)
+        }
+    }
+    impl ::std::convert::From<MintCall> for INonfungiblePositionManagerCalls {
+        fn from(var: MintCall) -> Self {
+            INonfungiblePositionManagerCalls::Mint(var)
+        }
+    }
+    impl ::std::convert::From<MulticallCall> for INonfungiblePositionManagerCalls {
+        fn from(var: MulticallCall) -> Self {
+            INonfungiblePositionManagerCalls::Multicall(var)
+        }
+    }
+    impl ::std::convert::From<OwnerOfCall> for INonfungiblePositionManagerCalls {
+        fn from(var: OwnerOfCall) -> Self {
+            INonfungiblePositionManagerCalls::OwnerOf(var)
+        }
+    }
+    impl ::std::convert::From<PositionsCall> for INonfungiblePositionManagerCalls {
+        fn from(var: PositionsCall) -> Self {
+            INonfungiblePositionManagerCalls::Positions(var)
+        }
+    }
+    impl ::std::convert::From<RefundETHCall> for INonfungiblePositionManagerCalls {
+        fn from(var: RefundETHCall) -> Self {
+            INonfungiblePositionManagerCalls::RefundETH(var)
+        }
+    }
+    impl ::std::convert::From<SafeTransferFromCall> for INonfungiblePositionManagerCalls {
+        fn from(var: SafeTransferFromCall) -> Self {
+            INonfungiblePositionManagerCalls::SafeTransferFrom(var)
+        }
+    }
+    impl ::std::convert::From<SafeTransferFromWithFromAndToAndDataCall>
+        for INonfungiblePositionManagerCalls
+    {
+        fn from(var: SafeTransferFromWithFromAndToAndDataCall) -> Self {
+            INonfungiblePositionManagerCalls::SafeTransferFromWithFromAndToAndData(var)
+        }
+    }
+    impl ::std::convert::From<SetApprovalForAllCall> for INonfungiblePositionManagerCalls {
+        fn from(var: SetApprovalForAllCall) -> Self {
+            INonfungiblePositionManagerCalls::SetApprovalForAll(var)
+        }
+    }
+    impl ::std::convert::From<SweepTokenCall> for INonfungiblePositionManagerCalls {
+        fn from(var: SweepTokenCall) -> Self {
+            INonfungiblePositionManagerCalls::SweepToken(var)
+        }
+    }
+    impl ::std::convert::From<TokenByIndexCall> for INonfungiblePositionManagerCalls {
+        fn from(var: TokenByIndexCall) -> Self {
+            INonfungiblePositionManagerCalls::TokenByIndex(var)
+        }
+    }
+    impl ::std::convert::From<TokenOfOwnerByIndexCall> for INonfungiblePositionManagerCalls {
+        fn from(var: TokenOfOwnerByIndexCall) -> Self {
+            INonfungiblePositionManagerCalls::TokenOfOwnerByIndex(var)
+        }
+    }
+    impl ::std::convert::From<TotalSupplyCall> for INonfungiblePositionManagerCalls {
+        fn from(var: TotalSupplyCall) -> Self {
+            INonfungiblePositionManagerCalls::TotalSupply(var)
+        }
+    }
+    impl ::std::convert::From<TransferFromCall> for INonfungiblePositionManagerCalls {
+        fn from(var: TransferFromCall) -> Self {
+            INonfungiblePositionManagerCalls::TransferFrom(var)
+        }
+    }
+    impl ::std::convert::From<UnwrapWETH9Call> for INonfungiblePositionManagerCalls {
+        fn from(var: UnwrapWETH9Call) -> Self {
+            INonfungiblePositionManagerCalls::UnwrapWETH9(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `WETH9` function with signature `WETH9()` and selector `[74, 164, 164, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct Weth9Return(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `balanceOf` function with signature `balanceOf(address)` and selector `[112, 160, 130, 49]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct BalanceOfReturn {
+        pub balance: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `collect` function with signature `collect((uint256,address,uint128,uint128))` and selector `[252, 111, 120, 101]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct CollectReturn {
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `createAndInitializePoolIfNecessary` function with signature `createAndInitializePoolIfNecessary(address,address,uint24,uint160)` and selector `[19, 234, 213, 98]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct CreateAndInitializePoolIfNecessaryReturn {
+        pub pool: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `decreaseLiquidity` function with signature `decreaseLiquidity((uint256,uint128,uint256,uint256,uint256))` and selector `[12, 73, 204, 190]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct DecreaseLiquidityReturn {
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct FactoryReturn(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `getApproved` function with signature `getApproved(uint256)` and selector `[8, 24, 18, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetApprovedReturn {
+        pub operator: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `increaseLiquidity` function with signature `increaseLiquidity((uint256,uint256,uint256,uint256,uint256,uint256))` and selector `[33, 159, 93, 23]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct IncreaseLiquidityReturn {
+        pub liquidity: u128,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `isApprovedForAll` function with signature `isApprovedForAll(address,address)` and selector `[233, 133, 233, 197]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct IsApprovedForAllReturn(pub bool);
+    #[doc = "Container type for all return fields from the `mint` function with signature `mint((address,address,uint24,int24,int24,uint256,uint256,uint256,uint256,address,uint256))` and selector `[136, 49, 100, 86]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MintReturn {
+        pub token_id: ethers_core::types::U256,
+        pub liquidity: u128,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all return fields from the `ownerOf` function with signature `ownerOf(uint256)` and selector `[99, 82, 33, 30]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct OwnerOfReturn {
+        pub owner: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `positions` function with signature `positions(uint256)` and selector `[153, 251, 171, 136]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct PositionsReturn {
+        pub nonce: u128,
+        pub operator: ethers_core::types::Address,
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+        pub fee: u32,
+        pub tick_lower: i32,
+        pub tick_upper: i32,
+        pub liquidity: u128,
+        pub fee_growth_inside_0_last_x128: ethers_core::types::U256,
+        pub fee_growth_inside_1_last_x128: ethers_core::types::U256,
+        pub tokens_owed_0: u128,
+        pub tokens_owed_1: u128,
+    }
+    #[doc = "Container type for all return fields from the `tokenByIndex` function with signature `tokenByIndex(uint256)` and selector `[79, 108, 204, 231]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct TokenByIndexReturn(pub ethers_core::types::U256);
+    #[doc = "Container type for all return fields from the `tokenOfOwnerByIndex` function with signature `tokenOfOwnerByIndex(address,uint256)` and selector `[47, 116, 92, 89]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct TokenOfOwnerByIndexReturn {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `totalSupply` function with signature `totalSupply()` and selector `[24, 22, 13, 221]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct TotalSupplyReturn(pub ethers_core::types::U256);
+    #[doc = "`CollectParams(uint256,address,uint128,uint128)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct CollectParams {
+        pub token_id: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+        pub amount_0_max: u128,
+        pub amount_1_max: u128,
+    }
+    #[doc = "`DecreaseLiquidityParams(uint256,uint128,uint256,uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct DecreaseLiquidityParams {
+        pub token_id: ethers_core::types::U256,
+        pub liquidity: u128,
+        pub amount_0_min: ethers_core::types::U256,
+        pub amount_1_min: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "`IncreaseLiquidityParams(uint256,uint256,uint256,uint256,uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct IncreaseLiquidityParams {
+        pub token_id: ethers_core::types::U256,
+        pub amount_0_desired: ethers_core::types::U256,
+        pub amount_1_desired: ethers_core::types::U256,
+        pub amount_0_min: ethers_core::types::U256,
+        pub amount_1_min: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "`MintParams(address,address,uint24,int24,int24,uint256,uint256,uint256,uint256,address,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct MintParams {
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+        pub fee: u32,
+        pub tick_lower: i32,
+        pub tick_upper: i32,
+        pub amount_0_desired: ethers_core::types::U256,
+        pub amount_1_desired: ethers_core::types::U256,
+        pub amount_0_min: ethers_core::types::U256,
+        pub amount_1_min: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+}
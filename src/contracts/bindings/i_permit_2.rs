@@ -0,0 +1,861 @@
+pub use i_permit_2::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_permit_2 {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    pub use super::super::shared_types::*;
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "IPermit2 was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/IPermit2.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static IPERMIT2_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct IPermit2<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for IPermit2<M> {
+        fn clone(&self) -> Self {
+            IPermit2(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for IPermit2<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for IPermit2<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(IPermit2)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> IPermit2<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), IPERMIT2_ABI.clone(), client).into()
+        }
+        #[doc = "Calls the contract's `DOMAIN_SEPARATOR` (0x3644e515) function"]
+        pub fn domain_separator(&self) -> ethers_contract::builders::ContractCall<M, [u8; 32]> {
+            self.0
+                .method_hash([54, 68, 229, 21], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `allowance` (0x927da105) function"]
+        pub fn allowance(
+            &self,
+            user: ethers_core::types::Address,
+            token: ethers_core::types::Address,
+            spender: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, (ethers_core::types::U256, u64, u64)>
+        {
+            self.0
+                .method_hash([146, 125, 161, 5], (user, token, spender))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `approve` (0x87517c45) function"]
+        pub fn approve(
+            &self,
+            token: ethers_core::types::Address,
+            spender: ethers_core::types::Address,
+            amount: ethers_core::types::U256,
+            expiration: u64,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([135, 81, 124, 69], (token, spender, amount, expiration))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `invalidateNonces` (0x65d9723c) function"]
+        pub fn invalidate_nonces(
+            &self,
+            token: ethers_core::types::Address,
+            spender: ethers_core::types::Address,
+            new_nonce: u64,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([101, 217, 114, 60], (token, spender, new_nonce))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `invalidateUnorderedNonces` (0x3ff9dcb1) function"]
+        pub fn invalidate_unordered_nonces(
+            &self,
+            word_pos: ethers_core::types::U256,
+            mask: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([63, 249, 220, 177], (word_pos, mask))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `lockdown` (0xcc53287f) function"]
+        pub fn lockdown(
+            &self,
+            approvals: ::std::vec::Vec<TokenSpenderPair>,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([204, 83, 40, 127], approvals)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `nonceBitmap` (0x4fe02b44) function"]
+        pub fn nonce_bitmap(
+            &self,
+            p0: ethers_core::types::Address,
+            p1: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([79, 224, 43, 68], (p0, p1))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `permit` (0x2b67b570) function"]
+        pub fn permit(
+            &self,
+            owner: ethers_core::types::Address,
+            permit_single: PermitSingle,
+            signature: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([43, 103, 181, 112], (owner, permit_single, signature))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `permit` (0x2a2d80d1) function"]
+        pub fn permit_with_owner_and_permit_batch(
+            &self,
+            owner: ethers_core::types::Address,
+            permit_batch: PermitBatch,
+            signature: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([42, 45, 128, 209], (owner, permit_batch, signature))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `permitTransferFrom` (0x30f28b7a) function"]
+        pub fn permit_transfer_from(
+            &self,
+            permit: PermitBatchTransferFrom,
+            transfer_details: SignatureTransferDetails,
+            owner: ethers_core::types::Address,
+            signature: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([48, 242, 139, 122], (permit, transfer_details, owner, signature))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `permitTransferFrom` (0xedd9444b) function"]
+        pub fn permit_transfer_from_with_permit_and_transfer_details_and_owner_and_signature(
+            &self,
+            permit: PermitBatchTransferFrom,
+            transfer_details: ::std::vec::Vec<SignatureTransferDetails>,
+            owner: ethers_core::types::Address,
+            signature: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([237, 217, 68, 75], (permit, transfer_details, owner, signature))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `transferFrom` (0x0d58b1db) function"]
+        pub fn transfer_from(
+            &self,
+            transfer_details: ::std::vec::Vec<AllowanceTransferDetails>,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([13, 88, 177, 219], transfer_details)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `transferFrom` (0x36c78516) function"]
+        pub fn transfer_from_with_from(
+            &self,
+            from: ethers_core::types::Address,
+            to: ethers_core::types::Address,
+            amount: ethers_core::types::U256,
+            token: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([54, 199, 133, 22], (from, to, amount, token))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Gets the contract's `Approval` event"]
+        pub fn approval_filter(&self) -> ethers_contract::builders::Event<M, ApprovalFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `Lockdown` event"]
+        pub fn lockdown_filter(&self) -> ethers_contract::builders::Event<M, LockdownFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `NonceInvalidation` event"]
+        pub fn nonce_invalidation_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, NonceInvalidationFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `Permit` event"]
+        pub fn permit_filter(&self) -> ethers_contract::builders::Event<M, PermitFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `UnorderedNonceInvalidation` event"]
+        pub fn unordered_nonce_invalidation_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, UnorderedNonceInvalidationFilter> {
+            self.0.event()
+        }
+        #[doc = r" Returns an [`Event`](#ethers_contract::builders::Event) builder for all events of this contract"]
+        pub fn events(&self) -> ethers_contract::builders::Event<M, IPermit2Events> {
+            self.0.event_with_filter(Default::default())
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for IPermit2<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Approval", abi = "Approval(address,address,address,uint160,uint48)")]
+    pub struct ApprovalFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub token: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub spender: ethers_core::types::Address,
+        pub amount: ethers_core::types::U256,
+        pub expiration: u64,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Lockdown", abi = "Lockdown(address,address,address)")]
+    pub struct LockdownFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        pub token: ethers_core::types::Address,
+        pub spender: ethers_core::types::Address,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethevent(
+        name = "NonceInvalidation",
+        abi = "NonceInvalidation(address,address,address,uint48,uint48)"
+    )]
+    pub struct NonceInvalidationFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub token: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub spender: ethers_core::types::Address,
+        pub new_nonce: u64,
+        pub old_nonce: u64,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "Permit", abi = "Permit(address,address,address,uint160,uint48,uint48)")]
+    pub struct PermitFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub token: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub spender: ethers_core::types::Address,
+        pub amount: ethers_core::types::U256,
+        pub expiration: u64,
+        pub nonce: u64,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethevent(
+        name = "UnorderedNonceInvalidation",
+        abi = "UnorderedNonceInvalidation(address,uint256,uint256)"
+    )]
+    pub struct UnorderedNonceInvalidationFilter {
+        #[ethevent(indexed)]
+        pub owner: ethers_core::types::Address,
+        pub word: ethers_core::types::U256,
+        pub mask: ethers_core::types::U256,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IPermit2Events {
+        ApprovalFilter(ApprovalFilter),
+        LockdownFilter(LockdownFilter),
+        NonceInvalidationFilter(NonceInvalidationFilter),
+        PermitFilter(PermitFilter),
+        UnorderedNonceInvalidationFilter(UnorderedNonceInvalidationFilter),
+    }
+    impl ethers_contract::EthLogDecode for IPermit2Events {
+        fn decode_log(
+            log: &ethers_core::abi::RawLog,
+        ) -> ::std::result::Result<Self, ethers_core::abi::Error>
+        where
+            Self: Sized,
+        {
+            if let Ok(decoded) = ApprovalFilter::decode_log(log) {
+                return Ok(IPermit2Events::ApprovalFilter(decoded));
+            }
+            if let Ok(decoded) = LockdownFilter::decode_log(log) {
+                return Ok(IPermit2Events::LockdownFilter(decoded));
+            }
+            if let Ok(decoded) = NonceInvalidationFilter::decode_log(log) {
+                return Ok(IPermit2Events::NonceInvalidationFilter(decoded));
+            }
+            if let Ok(decoded) = PermitFilter::decode_log(log) {
+                return Ok(IPermit2Events::PermitFilter(decoded));
+            }
+            if let Ok(decoded) = UnorderedNonceInvalidationFilter::decode_log(log) {
+                return Ok(IPermit2Events::UnorderedNonceInvalidationFilter(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData)
+        }
+    }
+    impl ::std::fmt::Display for IPermit2Events {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                IPermit2Events::ApprovalFilter(element) => element.fmt(f),
+                IPermit2Events::LockdownFilter(element) => element.fmt(f),
+                IPermit2Events::NonceInvalidationFilter(element) => element.fmt(f),
+                IPermit2Events::PermitFilter(element) => element.fmt(f),
+                IPermit2Events::UnorderedNonceInvalidationFilter(element) => element.fmt(f),
+            }
+        }
+    }
+    #[doc = "Container type for all input parameters for the `DOMAIN_SEPARATOR` function with signature `DOMAIN_SEPARATOR()` and selector `[54, 68, 229, 21]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "DOMAIN_SEPARATOR", abi = "DOMAIN_SEPARATOR()")]
+    pub struct DomainSeparatorCall;
+    #[doc = "Container type for all input parameters for the `allowance` function with signature `allowance(address,address,address)` and selector `[146, 125, 161, 5]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "allowance", abi = "allowance(address,address,address)")]
+    pub struct AllowanceCall {
+        pub user: ethers_core::types::Address,
+        pub token: ethers_core::types::Address,
+        pub spender: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `approve` function with signature `approve(address,address,uint160,uint48)` and selector `[135, 81, 124, 69]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "approve", abi = "approve(address,address,uint160,uint48)")]
+    pub struct ApproveCall {
+        pub token: ethers_core::types::Address,
+        pub spender: ethers_core::types::Address,
+        pub amount: ethers_core::types::U256,
+        pub expiration: u64,
+    }
+    #[doc = "Container type for all input parameters for the `invalidateNonces` function with signature `invalidateNonces(address,address,uint48)` and selector `[101, 217, 114, 60]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "invalidateNonces", abi = "invalidateNonces(address,address,uint48)")]
+    pub struct InvalidateNoncesCall {
+        pub token: ethers_core::types::Address,
+        pub spender: ethers_core::types::Address,
+        pub new_nonce: u64,
+    }
+    #[doc = "Container type for all input parameters for the `invalidateUnorderedNonces` function with signature `invalidateUnorderedNonces(uint256,uint256)` and selector `[63, 249, 220, 177]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "invalidateUnorderedNonces",
+        abi = "invalidateUnorderedNonces(uint256,uint256)"
+    )]
+    pub struct InvalidateUnorderedNoncesCall {
+        pub word_pos: ethers_core::types::U256,
+        pub mask: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `lockdown` function with signature `lockdown((address,address)[])` and selector `[204, 83, 40, 127]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "lockdown", abi = "lockdown((address,address)[])")]
+    pub struct LockdownCall {
+        pub approvals: ::std::vec::Vec<TokenSpenderPair>,
+    }
+    #[doc = "Container type for all input parameters for the `nonceBitmap` function with signature `nonceBitmap(address,uint256)` and selector `[79, 224, 43, 68]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "nonceBitmap", abi = "nonceBitmap(address,uint256)")]
+    pub struct NonceBitmapCall(pub ethers_core::types::Address, pub ethers_core::types::U256);
+    #[doc = "Container type for all input parameters for the `permit` function with signature `permit(address,((address,uint160,uint48,uint48),address,uint256),bytes)` and selector `[43, 103, 181, 112]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "permit",
+        abi = "permit(address,((address,uint160,uint48,uint48),address,uint256),bytes)"
+    )]
+    pub struct PermitCall {
+        pub owner: ethers_core::types::Address,
+        pub permit_single: PermitSingle,
+        pub signature: ethers_core::types::Bytes,
+    }
+    #[doc = "Container type for all input parameters for the `permit` function with signature `permit(address,((address,uint160,uint48,uint48)[],address,uint256),bytes)` and selector `[42, 45, 128, 209]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "permit",
+        abi = "permit(address,((address,uint160,uint48,uint48)[],address,uint256),bytes)"
+    )]
+    pub struct PermitWithOwnerAndPermitBatchCall {
+        pub owner: ethers_core::types::Address,
+        pub permit_batch: PermitBatch,
+        pub signature: ethers_core::types::Bytes,
+    }
+    #[doc = "Container type for all input parameters for the `permitTransferFrom` function with signature `permitTransferFrom(((address,uint256),uint256,uint256),(address,uint256),address,bytes)` and selector `[48, 242, 139, 122]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "permitTransferFrom",
+        abi = "permitTransferFrom(((address,uint256),uint256,uint256),(address,uint256),address,bytes)"
+    )]
+    pub struct PermitTransferFromCall {
+        pub permit: PermitBatchTransferFrom,
+        pub transfer_details: SignatureTransferDetails,
+        pub owner: ethers_core::types::Address,
+        pub signature: ethers_core::types::Bytes,
+    }
+    #[doc = "Container type for all input parameters for the `permitTransferFrom` function with signature `permitTransferFrom(((address,uint256)[],uint256,uint256),(address,uint256)[],address,bytes)` and selector `[237, 217, 68, 75]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "permitTransferFrom",
+        abi = "permitTransferFrom(((address,uint256)[],uint256,uint256),(address,uint256)[],address,bytes)"
+    )]
+    pub struct PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignatureCall {
+        pub permit: PermitBatchTransferFrom,
+        pub transfer_details: ::std::vec::Vec<SignatureTransferDetails>,
+        pub owner: ethers_core::types::Address,
+        pub signature: ethers_core::types::Bytes,
+    }
+    #[doc = "Container type for all input parameters for the `transferFrom` function with signature `transferFrom((address,address,uint160,address)[])` and selector `[13, 88, 177, 219]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "transferFrom", abi = "transferFrom((address,address,uint160,address)[])")]
+    pub struct TransferFromCall {
+        pub transfer_details: ::std::vec::Vec<AllowanceTransferDetails>,
+    }
+    #[doc = "Container type for all input parameters for the `transferFrom` function with signature `transferFrom(address,address,uint160,address)` and selector `[54, 199, 133, 22]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "transferFrom", abi = "transferFrom(address,address,uint160,address)")]
+    pub struct TransferFromWithFromCall {
+        pub from: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub amount: ethers_core::types::U256,
+        pub token: ethers_core::types::Address,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IPermit2Calls {
+        DomainSeparator(DomainSeparatorCall),
+        Allowance(AllowanceCall),
+        Approve(ApproveCall),
+        InvalidateNonces(InvalidateNoncesCall),
+        InvalidateUnorderedNonces(InvalidateUnorderedNoncesCall),
+        Lockdown(LockdownCall),
+        NonceBitmap(NonceBitmapCall),
+        Permit(PermitCall),
+        PermitWithOwnerAndPermitBatch(PermitWithOwnerAndPermitBatchCall),
+        PermitTransferFrom(PermitTransferFromCall),
+        PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignature(
+            PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignatureCall,
+        ),
+        TransferFrom(TransferFromCall),
+        TransferFromWithFrom(TransferFromWithFromCall),
+    }
+    impl ethers_core::abi::AbiDecode for IPermit2Calls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) =
+                <DomainSeparatorCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::DomainSeparator(decoded));
+            }
+            if let Ok(decoded) =
+                <AllowanceCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::Allowance(decoded));
+            }
+            if let Ok(decoded) = <ApproveCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::Approve(decoded));
+            }
+            if let Ok(decoded) =
+                <InvalidateNoncesCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::InvalidateNonces(decoded));
+            }
+            if let Ok(decoded) =
+                <InvalidateUnorderedNoncesCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IPermit2Calls::InvalidateUnorderedNonces(decoded));
+            }
+            if let Ok(decoded) =
+                <LockdownCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::Lockdown(decoded));
+            }
+            if let Ok(decoded) =
+                <NonceBitmapCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::NonceBitmap(decoded));
+            }
+            if let Ok(decoded) = <PermitCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::Permit(decoded));
+            }
+            if let Ok(decoded) =
+                <PermitWithOwnerAndPermitBatchCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IPermit2Calls::PermitWithOwnerAndPermitBatch(decoded));
+            }
+            if let Ok(decoded) =
+                <PermitTransferFromCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::PermitTransferFrom(decoded));
+            }
+            if let Ok (decoded) = < PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignatureCall as ethers_core :: abi :: AbiDecode > :: decode (data . as_ref ()) { return Ok (IPermit2Calls :: PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignature (decoded)) }
+            if let Ok(decoded) =
+                <TransferFromCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::TransferFrom(decoded));
+            }
+            if let Ok(decoded) =
+                <TransferFromWithFromCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IPermit2Calls::TransferFromWithFrom(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for IPermit2Calls {
+        fn encode(self) -> Vec<u8> {
+            match self { IPermit2Calls :: DomainSeparator (element) => element . encode () , IPermit2Calls :: Allowance (element) => element . encode () , IPermit2Calls :: Approve (element) => element . encode () , IPermit2Calls :: InvalidateNonces (element) => element . encode () , IPermit2Calls :: InvalidateUnorderedNonces (element) => element . encode () , IPermit2Calls :: Lockdown (element) => element . encode () , IPermit2Calls :: NonceBitmap (element) => element . encode () , IPermit2Calls :: Permit (element) => element . encode () , IPermit2Calls :: PermitWithOwnerAndPermitBatch (element) => element . encode () , IPermit2Calls :: PermitTransferFrom (element) => element . encode () , IPermit2Calls :: PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignature (element) => element . encode () , IPermit2Calls :: TransferFrom (element) => element . encode () , IPermit2Calls :: TransferFromWithFrom (element) => element . encode () }
+        }
+    }
+    impl ::std::fmt::Display for IPermit2Calls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self { IPermit2Calls :: DomainSeparator (element) => element . fmt (f) , IPermit2Calls :: Allowance (element) => element . fmt (f) , IPermit2Calls :: Approve (element) => element . fmt (f) , IPermit2Calls :: InvalidateNonces (element) => element . fmt (f) , IPermit2Calls :: InvalidateUnorderedNonces (element) => element . fmt (f) , IPermit2Calls :: Lockdown (element) => element . fmt (f) , IPermit2Calls :: NonceBitmap (element) => element . fmt (f) , IPermit2Calls :: Permit (element) => element . fmt (f) , IPermit2Calls :: PermitWithOwnerAndPermitBatch (element) => element . fmt (f) , IPermit2Calls :: PermitTransferFrom (element) => element . fmt (f) , IPermit2Calls :: PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignature (element) => element . fmt (f) , IPermit2Calls :: TransferFrom (element) => element . fmt (f) , IPermit2Calls :: TransferFromWithFrom (element) => element . fmt (f) }
+        }
+    }
+    impl ::std::convert::From<DomainSeparatorCall> for IPermit2Calls {
+        fn from(var: DomainSeparatorCall) -> Self {
+            IPermit2Calls::DomainSeparator(var)
+        }
+    }
+    impl ::std::convert::From<AllowanceCall> for IPermit2Calls {
+        fn from(var: AllowanceCall) -> Self {
+            IPermit2Calls::Allowance(var)
+        }
+    }
+    impl ::std::convert::From<ApproveCall> for IPermit2Calls {
+        fn from(var: ApproveCall) -> Self {
+            IPermit2Calls::Approve(var)
+        }
+    }
+    impl ::std::convert::From<InvalidateNoncesCall> for IPermit2Calls {
+        fn from(var: InvalidateNoncesCall) -> Self {
+            IPermit2Calls::InvalidateNonces(var)
+        }
+    }
+    impl ::std::convert::From<InvalidateUnorderedNoncesCall> for IPermit2Calls {
+        fn from(var: InvalidateUnorderedNoncesCall) -> Self {
+            IPermit2Calls::InvalidateUnorderedNonces(var)
+        }
+    }
+    impl ::std::convert::From<LockdownCall> for IPermit2Calls {
+        fn from(var: LockdownCall) -> Self {
+            IPermit2Calls::Lockdown(var)
+        }
+    }
+    impl ::std::convert::From<NonceBitmapCall> for IPermit2Calls {
+        fn from(var: NonceBitmapCall) -> Self {
+            IPermit2Calls::NonceBitmap(var)
+        }
+    }
+    impl ::std::convert::From<PermitCall> for IPermit2Calls {
+        fn from(var: PermitCall) -> Self {
+            IPermit2Calls::Permit(var)
+        }
+    }
+    impl ::std::convert::From<PermitWithOwnerAndPermitBatchCall> for IPermit2Calls {
+        fn from(var: PermitWithOwnerAndPermitBatchCall) -> Self {
+            IPermit2Calls::PermitWithOwnerAndPermitBatch(var)
+        }
+    }
+    impl ::std::convert::From<PermitTransferFromCall> for IPermit2Calls {
+        fn from(var: PermitTransferFromCall) -> Self {
+            IPermit2Calls::PermitTransferFrom(var)
+        }
+    }
+    impl
+        ::std::convert::From<PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignatureCall>
+        for IPermit2Calls
+    {
+        fn from(
+            var: PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignatureCall,
+        ) -> Self {
+            IPermit2Calls::PermitTransferFromWithPermitAndTransferDetailsAndOwnerAndSignature(var)
+        }
+    }
+    impl ::std::convert::From<TransferFromCall> for IPermit2Calls {
+        fn from(var: TransferFromCall) -> Self {
+            IPermit2Calls::TransferFrom(var)
+        }
+    }
+    impl ::std::convert::From<TransferFromWithFromCall> for IPermit2Calls {
+        fn from(var: TransferFromWithFromCall) -> Self {
+            IPermit2Calls::TransferFromWithFrom(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `DOMAIN_SEPARATOR` function with signature `DOMAIN_SEPARATOR()` and selector `[54, 68, 229, 21]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct DomainSeparatorReturn(pub [u8; 32]);
+    #[doc = "Container type for all return fields from the `allowance` function with signature `allowance(address,address,address)` and selector `[146, 125, 161, 5]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct AllowanceReturn {
+        pub amount: ethers_core::types::U256,
+        pub expiration: u64,
+        pub nonce: u64,
+    }
+    #[doc = "Container type for all return fields from the `nonceBitmap` function with signature `nonceBitmap(address,uint256)` and selector `[79, 224, 43, 68]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct NonceBitmapReturn(pub ethers_core::types::U256);
+    #[doc = "`TokenSpenderPair(address,address)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct TokenSpenderPair {
+        pub token: ethers_core::types::Address,
+        pub spender: ethers_core::types::Address,
+    }
+    #[doc = "`PermitBatchTransferFrom((address,uint256)[],uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct PermitBatchTransferFrom {
+        pub permitted: ::std::vec::Vec<TokenPermissions>,
+        pub nonce: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "`PermitTransferFrom((address,uint256),uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct PermitTransferFrom {
+        pub permitted: TokenPermissions,
+        pub nonce: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "`SignatureTransferDetails(address,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct SignatureTransferDetails {
+        pub to: ethers_core::types::Address,
+        pub requested_amount: ethers_core::types::U256,
+    }
+    #[doc = "`TokenPermissions(address,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct TokenPermissions {
+        pub token: ethers_core::types::Address,
+        pub amount: ethers_core::types::U256,
+    }
+}
@@ -0,0 +1,308 @@
+pub use i_quoter::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_quoter {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "IQuoter was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/IQuoter.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static IQUOTER_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct IQuoter<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for IQuoter<M> {
+        fn clone(&self) -> Self {
+            IQuoter(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for IQuoter<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for IQuoter<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(IQuoter)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> IQuoter<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), IQUOTER_ABI.clone(), client).into()
+        }
+        #[doc = "Calls the contract's `quoteExactInput` (0xcdca1753) function"]
+        pub fn quote_exact_input(
+            &self,
+            path: ethers_core::types::Bytes,
+            amount_in: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([205, 202, 23, 83], (path, amount_in))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `quoteExactInputSingle` (0xf7729d43) function"]
+        pub fn quote_exact_input_single(
+            &self,
+            token_in: ethers_core::types::Address,
+            token_out: ethers_core::types::Address,
+            fee: u32,
+            amount_in: ethers_core::types::U256,
+            sqrt_price_limit_x96: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash(
+                    [247, 114, 157, 67],
+                    (token_in, token_out, fee, amount_in, sqrt_price_limit_x96),
+                )
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `quoteExactOutput` (0x2f80bb1d) function"]
+        pub fn quote_exact_output(
+            &self,
+            path: ethers_core::types::Bytes,
+            amount_out: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([47, 128, 187, 29], (path, amount_out))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `quoteExactOutputSingle` (0x30d07f21) function"]
+        pub fn quote_exact_output_single(
+            &self,
+            token_in: ethers_core::types::Address,
+            token_out: ethers_core::types::Address,
+            fee: u32,
+            amount_out: ethers_core::types::U256,
+            sqrt_price_limit_x96: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash(
+                    [48, 208, 127, 33],
+                    (token_in, token_out, fee, amount_out, sqrt_price_limit_x96),
+                )
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for IQuoter<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `quoteExactInput` function with signature `quoteExactInput(bytes,uint256)` and selector `[205, 202, 23, 83]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "quoteExactInput", abi = "quoteExactInput(bytes,uint256)")]
+    pub struct QuoteExactInputCall {
+        pub path: ethers_core::types::Bytes,
+        pub amount_in: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `quoteExactInputSingle` function with signature `quoteExactInputSingle(address,address,uint24,uint256,uint160)` and selector `[247, 114, 157, 67]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "quoteExactInputSingle",
+        abi = "quoteExactInputSingle(address,address,uint24,uint256,uint160)"
+    )]
+    pub struct QuoteExactInputSingleCall {
+        pub token_in: ethers_core::types::Address,
+        pub token_out: ethers_core::types::Address,
+        pub fee: u32,
+        pub amount_in: ethers_core::types::U256,
+        pub sqrt_price_limit_x96: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `quoteExactOutput` function with signature `quoteExactOutput(bytes,uint256)` and selector `[47, 128, 187, 29]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "quoteExactOutput", abi = "quoteExactOutput(bytes,uint256)")]
+    pub struct QuoteExactOutputCall {
+        pub path: ethers_core::types::Bytes,
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `quoteExactOutputSingle` function with signature `quoteExactOutputSingle(address,address,uint24,uint256,uint160)` and selector `[48, 208, 127, 33]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "quoteExactOutputSingle",
+        abi = "quoteExactOutputSingle(address,address,uint24,uint256,uint160)"
+    )]
+    pub struct QuoteExactOutputSingleCall {
+        pub token_in: ethers_core::types::Address,
+        pub token_out: ethers_core::types::Address,
+        pub fee: u32,
+        pub amount_out: ethers_core::types::U256,
+        pub sqrt_price_limit_x96: ethers_core::types::U256,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IQuoterCalls {
+        QuoteExactInput(QuoteExactInputCall),
+        QuoteExactInputSingle(QuoteExactInputSingleCall),
+        QuoteExactOutput(QuoteExactOutputCall),
+        QuoteExactOutputSingle(QuoteExactOutputSingleCall),
+    }
+    impl ethers_core::abi::AbiDecode for IQuoterCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) =
+                <QuoteExactInputCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IQuoterCalls::QuoteExactInput(decoded));
+            }
+            if let Ok(decoded) =
+                <QuoteExactInputSingleCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IQuoterCalls::QuoteExactInputSingle(decoded));
+            }
+            if let Ok(decoded) =
+                <QuoteExactOutputCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IQuoterCalls::QuoteExactOutput(decoded));
+            }
+            if let Ok(decoded) =
+                <QuoteExactOutputSingleCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IQuoterCalls::QuoteExactOutputSingle(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for IQuoterCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                IQuoterCalls::QuoteExactInput(element) => element.encode(),
+                IQuoterCalls::QuoteExactInputSingle(element) => element.encode(),
+                IQuoterCalls::QuoteExactOutput(element) => element.encode(),
+                IQuoterCalls::QuoteExactOutputSingle(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for IQuoterCalls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                IQuoterCalls::QuoteExactInput(element) => element.fmt(f),
+                IQuoterCalls::QuoteExactInputSingle(element) => element.fmt(f),
+                IQuoterCalls::QuoteExactOutput(element) => element.fmt(f),
+                IQuoterCalls::QuoteExactOutputSingle(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<QuoteExactInputCall> for IQuoterCalls {
+        fn from(var: QuoteExactInputCall) -> Self {
+            IQuoterCalls::QuoteExactInput(var)
+        }
+    }
+    impl ::std::convert::From<QuoteExactInputSingleCall> for IQuoterCalls {
+        fn from(var: QuoteExactInputSingleCall) -> Self {
+            IQuoterCalls::QuoteExactInputSingle(var)
+        }
+    }
+    impl ::std::convert::From<QuoteExactOutputCall> for IQuoterCalls {
+        fn from(var: QuoteExactOutputCall) -> Self {
+            IQuoterCalls::QuoteExactOutput(var)
+        }
+    }
+    impl ::std::convert::From<QuoteExactOutputSingleCall> for IQuoterCalls {
+        fn from(var: QuoteExactOutputSingleCall) -> Self {
+            IQuoterCalls::QuoteExactOutputSingle(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `quoteExactInput` function with signature `quoteExactInput(bytes,uint256)` and selector `[205, 202, 23, 83]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct QuoteExactInputReturn {
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `quoteExactInputSingle` function with signature `quoteExactInputSingle(address,address,uint24,uint256,uint160)` and selector `[247, 114, 157, 67]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct QuoteExactInputSingleReturn {
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `quoteExactOutput` function with signature `quoteExactOutput(bytes,uint256)` and selector `[47, 128, 187, 29]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct QuoteExactOutputReturn {
+        pub amount_in: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `quoteExactOutputSingle` function with signature `quoteExactOutputSingle(address,address,uint24,uint256,uint160)` and selector `[48, 208, 127, 33]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct QuoteExactOutputSingleReturn {
+        pub amount_in: ethers_core::types::U256,
+    }
+}
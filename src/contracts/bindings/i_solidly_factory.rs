@@ -0,0 +1,97 @@
+pub use i_solidly_factory::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_solidly_factory {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "ISolidlyFactory was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/ISolidlyFactory.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static ISOLIDLYFACTORY_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct ISolidlyFactory<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for ISolidlyFactory<M> {
+        fn clone(&self) -> Self {
+            ISolidlyFactory(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for ISolidlyFactory<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for ISolidlyFactory<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(ISolidlyFactory)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> ISolidlyFactory<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), ISOLIDLYFACTORY_ABI.clone(), client)
+                .into()
+        }
+        #[doc = "Calls the contract's `getPair` (0x6801cc30) function"]
+        pub fn get_pair(
+            &self,
+            token_a: ethers_core::types::Address,
+            token_b: ethers_core::types::Address,
+            stable: bool,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([104, 1, 204, 48], (token_a, token_b, stable))
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for ISolidlyFactory<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `getPair` function with signature `getPair(address,address,bool)` and selector `[104, 1, 204, 48]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getPair", abi = "getPair(address,address,bool)")]
+    pub struct GetPairCall {
+        pub token_a: ethers_core::types::Address,
+        pub token_b: ethers_core::types::Address,
+        pub stable: bool,
+    }
+    #[doc = "Container type for all return fields from the `getPair` function with signature `getPair(address,address,bool)` and selector `[104, 1, 204, 48]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetPairReturn(pub ethers_core::types::Address);
+}
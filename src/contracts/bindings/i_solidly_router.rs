@@ -0,0 +1,853 @@
+pub use i_solidly_router::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_solidly_router {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "ISolidlyRouter was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/ISolidlyRouter.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static ISOLIDLYROUTER_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct ISolidlyRouter<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for ISolidlyRouter<M> {
+        fn clone(&self) -> Self {
+            ISolidlyRouter(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for ISolidlyRouter<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for ISolidlyRouter<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(ISolidlyRouter)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> ISolidlyRouter<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), ISOLIDLYROUTER_ABI.clone(), client)
+                .into()
+        }
+        #[doc = "Calls the contract's `factory` (0xc45a0155) function"]
+        pub fn factory(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([196, 90, 1, 85], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getAmountOut` (0x5e1e6325) function"]
+        pub fn get_amount_out(
+            &self,
+            amount_in: ethers_core::types::U256,
+            token_in: ethers_core::types::Address,
+            token_out: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, (ethers_core::types::U256, bool)> {
+            self.0
+                .method_hash([94, 30, 99, 37], (amount_in, token_in, token_out))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getAmountsOut` (0x9881fcb4) function"]
+        pub fn get_amounts_out(
+            &self,
+            amount_in: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::U256>>
+        {
+            self.0
+                .method_hash([152, 129, 252, 180], (amount_in, routes))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getReserves` (0x5e60dab5) function"]
+        pub fn get_reserves(
+            &self,
+            token_a: ethers_core::types::Address,
+            token_b: ethers_core::types::Address,
+            stable: bool,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([94, 96, 218, 181], (token_a, token_b, stable))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `pairFor` (0x4c1ee03e) function"]
+        pub fn pair_for(
+            &self,
+            token_a: ethers_core::types::Address,
+            token_b: ethers_core::types::Address,
+            stable: bool,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([76, 30, 224, 62], (token_a, token_b, stable))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `sortTokens` (0x544caa56) function"]
+        pub fn sort_tokens(
+            &self,
+            token_a: ethers_core::types::Address,
+            token_b: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::Address, ethers_core::types::Address),
+        > {
+            self.0
+                .method_hash([84, 76, 170, 86], (token_a, token_b))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactETHForTokens` (0x67ffb66a) function"]
+        pub fn swap_exact_eth_for_tokens(
+            &self,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::U256>>
+        {
+            self.0
+                .method_hash([103, 255, 182, 106], (amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactETHForTokensSupportingFeeOnTransferTokens` (0x76c72751) function"]
+        pub fn swap_exact_eth_for_tokens_supporting_fee_on_transfer_tokens(
+            &self,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([118, 199, 39, 81], (amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForETH` (0x18a13086) function"]
+        pub fn swap_exact_tokens_for_eth(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::U256>>
+        {
+            self.0
+                .method_hash([24, 161, 48, 134], (amount_in, amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForETHSupportingFeeOnTransferTokens` (0x7af728c8) function"]
+        pub fn swap_exact_tokens_for_eth_supporting_fee_on_transfer_tokens(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([122, 247, 40, 200], (amount_in, amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForTokens` (0xf41766d8) function"]
+        pub fn swap_exact_tokens_for_tokens(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::U256>>
+        {
+            self.0
+                .method_hash([244, 23, 102, 216], (amount_in, amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForTokensSimple` (0x13dcfc59) function"]
+        pub fn swap_exact_tokens_for_tokens_simple(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            token_from: ethers_core::types::Address,
+            token_to: ethers_core::types::Address,
+            stable: bool,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::U256>>
+        {
+            self.0
+                .method_hash(
+                    [19, 220, 252, 89],
+                    (amount_in, amount_out_min, token_from, token_to, stable, to, deadline),
+                )
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForTokensSupportingFeeOnTransferTokens` (0x6cc1ae13) function"]
+        pub fn swap_exact_tokens_for_tokens_supporting_fee_on_transfer_tokens(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            routes: ::std::vec::Vec<Route>,
+            to: ethers_core::types::Address,
+            deadline: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([108, 193, 174, 19], (amount_in, amount_out_min, routes, to, deadline))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `weth` (0x3fc8cef3) function"]
+        pub fn weth(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([63, 200, 206, 243], ())
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for ISolidlyRouter<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "factory", abi = "factory()")]
+    pub struct FactoryCall;
+    #[doc = "Container type for all input parameters for the `getAmountOut` function with signature `getAmountOut(uint256,address,address)` and selector `[94, 30, 99, 37]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getAmountOut", abi = "getAmountOut(uint256,address,address)")]
+    pub struct GetAmountOutCall {
+        pub amount_in: ethers_core::types::U256,
+        pub token_in: ethers_core::types::Address,
+        pub token_out: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `getAmountsOut` function with signature `getAmountsOut(uint256,(address,address,bool)[])` and selector `[152, 129, 252, 180]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getAmountsOut", abi = "getAmountsOut(uint256,(address,address,bool)[])")]
+    pub struct GetAmountsOutCall {
+        pub amount_in: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+    }
+    #[doc = "Container type for all input parameters for the `getReserves` function with signature `getReserves(address,address,bool)` and selector `[94, 96, 218, 181]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getReserves", abi = "getReserves(address,address,bool)")]
+    pub struct GetReservesCall {
+        pub token_a: ethers_core::types::Address,
+        pub token_b: ethers_core::types::Address,
+        pub stable: bool,
+    }
+    #[doc = "Container type for all input parameters for the `pairFor` function with signature `pairFor(address,address,bool)` and selector `[76, 30, 224, 62]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "pairFor", abi = "pairFor(address,address,bool)")]
+    pub struct PairForCall {
+        pub token_a: ethers_core::types::Address,
+        pub token_b: ethers_core::types::Address,
+        pub stable: bool,
+    }
+    #[doc = "Container type for all input parameters for the `sortTokens` function with signature `sortTokens(address,address)` and selector `[84, 76, 170, 86]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "sortTokens", abi = "sortTokens(address,address)")]
+    pub struct SortTokensCall {
+        pub token_a: ethers_core::types::Address,
+        pub token_b: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactETHForTokens` function with signature `swapExactETHForTokens(uint256,(address,address,bool)[],address,uint256)` and selector `[103, 255, 182, 106]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactETHForTokens",
+        abi = "swapExactETHForTokens(uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactETHForTokensCall {
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactETHForTokensSupportingFeeOnTransferTokens` function with signature `swapExactETHForTokensSupportingFeeOnTransferTokens(uint256,(address,address,bool)[],address,uint256)` and selector `[118, 199, 39, 81]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactETHForTokensSupportingFeeOnTransferTokens",
+        abi = "swapExactETHForTokensSupportingFeeOnTransferTokens(uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactETHForTokensSupportingFeeOnTransferTokensCall {
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForETH` function with signature `swapExactTokensForETH(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[24, 161, 48, 134]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForETH",
+        abi = "swapExactTokensForETH(uint256,uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactTokensForETHCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForETHSupportingFeeOnTransferTokens` function with signature `swapExactTokensForETHSupportingFeeOnTransferTokens(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[122, 247, 40, 200]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForETHSupportingFeeOnTransferTokens",
+        abi = "swapExactTokensForETHSupportingFeeOnTransferTokens(uint256,uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactTokensForETHSupportingFeeOnTransferTokensCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForTokens` function with signature `swapExactTokensForTokens(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[244, 23, 102, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForTokens",
+        abi = "swapExactTokensForTokens(uint256,uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactTokensForTokensCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForTokensSimple` function with signature `swapExactTokensForTokensSimple(uint256,uint256,address,address,bool,address,uint256)` and selector `[19, 220, 252, 89]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForTokensSimple",
+        abi = "swapExactTokensForTokensSimple(uint256,uint256,address,address,bool,address,uint256)"
+    )]
+    pub struct SwapExactTokensForTokensSimpleCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub token_from: ethers_core::types::Address,
+        pub token_to: ethers_core::types::Address,
+        pub stable: bool,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForTokensSupportingFeeOnTransferTokens` function with signature `swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[108, 193, 174, 19]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForTokensSupportingFeeOnTransferTokens",
+        abi = "swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,(address,address,bool)[],address,uint256)"
+    )]
+    pub struct SwapExactTokensForTokensSupportingFeeOnTransferTokensCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub routes: ::std::vec::Vec<Route>,
+        pub to: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `weth` function with signature `weth()` and selector `[63, 200, 206, 243]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "weth", abi = "weth()")]
+    pub struct WethCall;
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum ISolidlyRouterCalls {
+        Factory(FactoryCall),
+        GetAmountOut(GetAmountOutCall),
+        GetAmountsOut(GetAmountsOutCall),
+        GetReserves(GetReservesCall),
+        PairFor(PairForCall),
+        SortTokens(SortTokensCall),
+        SwapExactETHForTokens(SwapExactETHForTokensCall),
+        SwapExactETHForTokensSupportingFeeOnTransferTokens(
+            SwapExactETHForTokensSupportingFeeOnTransferTokensCall,
+        ),
+        SwapExactTokensForETH(SwapExactTokensForETHCall),
+        SwapExactTokensForETHSupportingFeeOnTransferTokens(
+            SwapExactTokensForETHSupportingFeeOnTransferTokensCall,
+        ),
+        SwapExactTokensForTokens(SwapExactTokensForTokensCall),
+        SwapExactTokensForTokensSimple(SwapExactTokensForTokensSimpleCall),
+        SwapExactTokensForTokensSupportingFeeOnTransferTokens(
+            SwapExactTokensForTokensSupportingFeeOnTransferTokensCall,
+        ),
+        Weth(WethCall),
+    }
+    impl ethers_core::abi::AbiDecode for ISolidlyRouterCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) = <FactoryCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::Factory(decoded));
+            }
+            if let Ok(decoded) =
+                <GetAmountOutCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::GetAmountOut(decoded));
+            }
+            if let Ok(decoded) =
+                <GetAmountsOutCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::GetAmountsOut(decoded));
+            }
+            if let Ok(decoded) =
+                <GetReservesCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::GetReserves(decoded));
+            }
+            if let Ok(decoded) = <PairForCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::PairFor(decoded));
+            }
+            if let Ok(decoded) =
+                <SortTokensCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::SortTokens(decoded));
+            }
+            if let Ok(decoded) =
+                <SwapExactETHForTokensCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::SwapExactETHForTokens(decoded));
+            }
+            if let Ok (decoded) = < SwapExactETHForTokensSupportingFeeOnTransferTokensCall as ethers_core :: abi :: AbiDecode > :: decode (data . as_ref ()) { return Ok (ISolidlyRouterCalls :: SwapExactETHForTokensSupportingFeeOnTransferTokens (decoded)) }
+            if let Ok(decoded) =
+                <SwapExactTokensForETHCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::SwapExactTokensForETH(decoded));
+            }
+            if let Ok (decoded) = < SwapExactTokensForETHSupportingFeeOnTransferTokensCall as ethers_core :: abi :: AbiDecode > :: decode (data . as_ref ()) { return Ok (ISolidlyRouterCalls :: SwapExactTokensForETHSupportingFeeOnTransferTokens (decoded)) }
+            if let Ok(decoded) =
+                <SwapExactTokensForTokensCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISolidlyRouterCalls::SwapExactTokensForTokens(decoded));
+            }
+            if let Ok(decoded) =
+                <SwapExactTokensForTokensSimpleCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(ISolidlyRouterCalls::SwapExactTokensForTokensSimple(decoded));
+            }
+            if let Ok (decoded) = < SwapExactTokensForTokensSupportingFeeOnTransferTokensCall as ethers_core :: abi :: AbiDecode > :: decode (data . as_ref ()) { return Ok (ISolidlyRouterCalls :: SwapExactTokensForTokensSupportingFeeOnTransferTokens (decoded)) }
+            if let Ok(decoded) = <WethCall as ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                return Ok(ISolidlyRouterCalls::Weth(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for ISolidlyRouterCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                ISolidlyRouterCalls::Factory(element) => element.encode(),
+                ISolidlyRouterCalls::GetAmountOut(element) => element.encode(),
+                ISolidlyRouterCalls::GetAmountsOut(element) => element.encode(),
+                ISolidlyRouterCalls::GetReserves(element) => element.encode(),
+                ISolidlyRouterCalls::PairFor(element) => element.encode(),
+                ISolidlyRouterCalls::SortTokens(element) => element.encode(),
+                ISolidlyRouterCalls::SwapExactETHForTokens(element) => element.encode(),
+                ISolidlyRouterCalls::SwapExactETHForTokensSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.encode(),
+                ISolidlyRouterCalls::SwapExactTokensForETH(element) => element.encode(),
+                ISolidlyRouterCalls::SwapExactTokensForETHSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.encode(),
+                ISolidlyRouterCalls::SwapExactTokensForTokens(element) => element.encode(),
+                ISolidlyRouterCalls::SwapExactTokensForTokensSimple(element) => element.encode(),
+                ISolidlyRouterCalls::SwapExactTokensForTokensSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.encode(),
+                ISolidlyRouterCalls::Weth(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for ISolidlyRouterCalls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                ISolidlyRouterCalls::Factory(element) => element.fmt(f),
+                ISolidlyRouterCalls::GetAmountOut(element) => element.fmt(f),
+                ISolidlyRouterCalls::GetAmountsOut(element) => element.fmt(f),
+                ISolidlyRouterCalls::GetReserves(element) => element.fmt(f),
+                ISolidlyRouterCalls::PairFor(element) => element.fmt(f),
+                ISolidlyRouterCalls::SortTokens(element) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactETHForTokens(element) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactETHForTokensSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactTokensForETH(element) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactTokensForETHSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactTokensForTokens(element) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactTokensForTokensSimple(element) => element.fmt(f),
+                ISolidlyRouterCalls::SwapExactTokensForTokensSupportingFeeOnTransferTokens(
+                    element,
+                ) => element.fmt(f),
+                ISolidlyRouterCalls::Weth(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<FactoryCall> for ISolidlyRouterCalls {
+        fn from(var: FactoryCall) -> Self {
+            ISolidlyRouterCalls::Factory(var)
+        }
+    }
+    impl ::std::convert::From<GetAmountOutCall> for ISolidlyRouterCalls {
+        fn from(var: GetAmountOutCall) -> Self {
+            ISolidlyRouterCalls::GetAmountOut(var)
+        }
+    }
+    impl ::std::convert::From<GetAmountsOutCall> for ISolidlyRouterCalls {
+        fn from(var: GetAmountsOutCall) -> Self {
+            ISolidlyRouterCalls::GetAmountsOut(var)
+        }
+    }
+    impl ::std::convert::From<GetReservesCall> for ISolidlyRouterCalls {
+        fn from(var: GetReservesCall) -> Self {
+            ISolidlyRouterCalls::GetReserves(var)
+        }
+    }
+    impl ::std::convert::From<PairForCall> for ISolidlyRouterCalls {
+        fn from(var: PairForCall) -> Self {
+            ISolidlyRouterCalls::PairFor(var)
+        }
+    }
+    impl ::std::convert::From<SortTokensCall> for ISolidlyRouterCalls {
+        fn from(var: SortTokensCall) -> Self {
+            ISolidlyRouterCalls::SortTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactETHForTokensCall> for ISolidlyRouterCalls {
+        fn from(var: SwapExactETHForTokensCall) -> Self {
+            ISolidlyRouterCalls::SwapExactETHForTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactETHForTokensSupportingFeeOnTransferTokensCall>
+        for ISolidlyRouterCalls
+    {
+        fn from(var: SwapExactETHForTokensSupportingFeeOnTransferTokensCall) -> Self {
+            ISolidlyRouterCalls::SwapExactETHForTokensSupportingFeeOnTransferTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForETHCall> for ISolidlyRouterCalls {
+        fn from(var: SwapExactTokensForETHCall) -> Self {
+            ISolidlyRouterCalls::SwapExactTokensForETH(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForETHSupportingFeeOnTransferTokensCall>
+        for ISolidlyRouterCalls
+    {
+        fn from(var: SwapExactTokensForETHSupportingFeeOnTransferTokensCall) -> Self {
+            ISolidlyRouterCalls::SwapExactTokensForETHSupportingFeeOnTransferTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForTokensCall> for ISolidlyRouterCalls {
+        fn from(var: SwapExactTokensForTokensCall) -> Self {
+            ISolidlyRouterCalls::SwapExactTokensForTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForTokensSimpleCall> for ISolidlyRouterCalls {
+        fn from(var: SwapExactTokensForTokensSimpleCall) -> Self {
+            ISolidlyRouterCalls::SwapExactTokensForTokensSimple(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForTokensSupportingFeeOnTransferTokensCall>
+        for ISolidlyRouterCalls
+    {
+        fn from(var: SwapExactTokensForTokensSupportingFeeOnTransferTokensCall) -> Self {
+            ISolidlyRouterCalls::SwapExactTokensForTokensSupportingFeeOnTransferTokens(var)
+        }
+    }
+    impl ::std::convert::From<WethCall> for ISolidlyRouterCalls {
+        fn from(var: WethCall) -> Self {
+            ISolidlyRouterCalls::Weth(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct FactoryReturn(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `getAmountOut` function with signature `getAmountOut(uint256,address,address)` and selector `[94, 30, 99, 37]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetAmountOutReturn {
+        pub amount: ethers_core::types::U256,
+        pub stable: bool,
+    }
+    #[doc = "Container type for all return fields from the `getAmountsOut` function with signature `getAmountsOut(uint256,(address,address,bool)[])` and selector `[152, 129, 252, 180]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetAmountsOutReturn {
+        pub amounts: ::std::vec::Vec<ethers_core::types::U256>,
+    }
+    #[doc = "Container type for all return fields from the `getReserves` function with signature `getReserves(address,address,bool)` and selector `[94, 96, 218, 181]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetReservesReturn {
+        pub reserve_a: ethers_core::types::U256,
+        pub reserve_b: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `pairFor` function with signature `pairFor(address,address,bool)` and selector `[76, 30, 224, 62]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct PairForReturn {
+        pub pair: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `sortTokens` function with signature `sortTokens(address,address)` and selector `[84, 76, 170, 86]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SortTokensReturn {
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `swapExactETHForTokens` function with signature `swapExactETHForTokens(uint256,(address,address,bool)[],address,uint256)` and selector `[103, 255, 182, 106]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapExactETHForTokensReturn {
+        pub amounts: ::std::vec::Vec<ethers_core::types::U256>,
+    }
+    #[doc = "Container type for all return fields from the `swapExactTokensForETH` function with signature `swapExactTokensForETH(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[24, 161, 48, 134]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapExactTokensForETHReturn {
+        pub amounts: ::std::vec::Vec<ethers_core::types::U256>,
+    }
+    #[doc = "Container type for all return fields from the `swapExactTokensForTokens` function with signature `swapExactTokensForTokens(uint256,uint256,(address,address,bool)[],address,uint256)` and selector `[244, 23, 102, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapExactTokensForTokensReturn {
+        pub amounts: ::std::vec::Vec<ethers_core::types::U256>,
+    }
+    #[doc = "Container type for all return fields from the `swapExactTokensForTokensSimple` function with signature `swapExactTokensForTokensSimple(uint256,uint256,address,address,bool,address,uint256)` and selector `[19, 220, 252, 89]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapExactTokensForTokensSimpleReturn {
+        pub amounts: ::std::vec::Vec<ethers_core::types::U256>,
+    }
+    #[doc = "Container type for all return fields from the `weth` function with signature `weth()` and selector `[63, 200, 206, 243]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct WethReturn(pub ethers_core::types::Address);
+    #[doc = "`Route(address,address,bool)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct Route {
+        pub from: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub stable: bool,
+    }
+}
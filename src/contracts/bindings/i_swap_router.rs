@@ -16,7 +16,7 @@ pub mod i_swap_router {
     use ethers_providers::Middleware;
     #[doc = "ISwapRouter was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
     use std::sync::Arc;
-    # [rustfmt :: skip] const __ABI : & str = "[{\"inputs\":[{\"components\":[{\"internalType\":\"bytes\",\"name\":\"path\",\"type\":\"bytes\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"deadline\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountIn\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountOutMinimum\",\"type\":\"uint256\"}],\"internalType\":\"struct ISwapRouter.ExactInputParams\",\"name\":\"params\",\"type\":\"tuple\"}],\"name\":\"exactInput\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"amountOut\",\"type\":\"uint256\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"tokenIn\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"tokenOut\",\"type\":\"address\"},{\"internalType\":\"uint24\",\"name\":\"fee\",\"type\":\"uint24\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"deadline\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountIn\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountOutMinimum\",\"type\":\"uint256\"},{\"internalType\":\"uint160\",\"name\":\"sqrtPriceLimitX96\",\"type\":\"uint160\"}],\"internalType\":\"struct ISwapRouter.ExactInputSingleParams\",\"name\":\"params\",\"type\":\"tuple\"}],\"name\":\"exactInputSingle\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"amountOut\",\"type\":\"uint256\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"bytes\",\"name\":\"path\",\"type\":\"bytes\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"deadline\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountOut\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountInMaximum\",\"type\":\"uint256\"}],\"internalType\":\"struct ISwapRouter.ExactOutputParams\",\"name\":\"params\",\"type\":\"tuple\"}],\"name\":\"exactOutput\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"amountIn\",\"type\":\"uint256\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"tokenIn\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"tokenOut\",\"type\":\"address\"},{\"internalType\":\"uint24\",\"name\":\"fee\",\"type\":\"uint24\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"deadline\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountOut\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"amountInMaximum\",\"type\":\"uint256\"},{\"internalType\":\"uint160\",\"name\":\"sqrtPriceLimitX96\",\"type\":\"uint160\"}],\"internalType\":\"struct ISwapRouter.ExactOutputSingleParams\",\"name\":\"params\",\"type\":\"tuple\"}],\"name\":\"exactOutputSingle\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"amountIn\",\"type\":\"uint256\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"int256\",\"name\":\"amount0Delta\",\"type\":\"int256\"},{\"internalType\":\"int256\",\"name\":\"amount1Delta\",\"type\":\"int256\"},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\"}],\"name\":\"uniswapV3SwapCallback\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]\n" ;
+    const __ABI: &str = include_str!("../../../abi/ISwapRouter.json");
     #[doc = r" The parsed JSON-ABI of the contract."]
     pub static ISWAPROUTER_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
         ethers_contract::Lazy::new(|| {
@@ -49,6 +49,14 @@ pub mod i_swap_router {
         ) -> Self {
             ethers_contract::Contract::new(address.into(), ISWAPROUTER_ABI.clone(), client).into()
         }
+        #[doc = "Calls the contract's `WETH9` (0x4aa4a4fc) function"]
+        pub fn weth9(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([74, 164, 164, 252], ())
+                .expect("method not found (this should never happen)")
+        }
         #[doc = "Calls the contract's `exactInput` (0xc04b8d59) function"]
         pub fn exact_input(
             &self,
@@ -85,6 +93,41 @@ pub mod i_swap_router {
                 .method_hash([219, 62, 33, 152], (params,))
                 .expect("method not found (this should never happen)")
         }
+        #[doc = "Calls the contract's `factory` (0xc45a0155) function"]
+        pub fn factory(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([196, 90, 1, 85], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0xac9650d8) function"]
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `refundETH` (0x12210e8a) function"]
+        pub fn refund_eth(&self) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([18, 33, 14, 138], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `sweepToken` (0xdf2ab5bb) function"]
+        pub fn sweep_token(
+            &self,
+            token: ethers_core::types::Address,
+            amount_minimum: ethers_core::types::U256,
+            recipient: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([223, 42, 181, 187], (token, amount_minimum, recipient))
+                .expect("method not found (this should never happen)")
+        }
         #[doc = "Calls the contract's `uniswapV3SwapCallback` (0xfa461e33) function"]
         pub fn uniswap_v3_swap_callback(
             &self,
@@ -96,12 +139,34 @@ pub mod i_swap_router {
                 .method_hash([250, 70, 30, 51], (amount_0_delta, amount_1_delta, data))
                 .expect("method not found (this should never happen)")
         }
+        #[doc = "Calls the contract's `unwrapWETH9` (0x49404b7c) function"]
+        pub fn unwrap_weth9(
+            &self,
+            amount_minimum: ethers_core::types::U256,
+            recipient: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([73, 64, 75, 124], (amount_minimum, recipient))
+                .expect("method not found (this should never happen)")
+        }
     }
     impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for ISwapRouter<M> {
         fn from(contract: ethers_contract::Contract<M>) -> Self {
             Self(contract)
         }
     }
+    #[doc = "Container type for all input parameters for the `WETH9` function with signature `WETH9()` and selector `[74, 164, 164, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "WETH9", abi = "WETH9()")]
+    pub struct Weth9Call;
     #[doc = "Container type for all input parameters for the `exactInput` function with signature `exactInput((bytes,address,uint256,uint256,uint256))` and selector `[192, 75, 141, 89]`"]
     #[derive(
         Clone,
@@ -164,6 +229,60 @@ pub mod i_swap_router {
     pub struct ExactOutputSingleCall {
         pub params: ExactOutputSingleParams,
     }
+    #[doc = "Container type for all input parameters for the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "factory", abi = "factory()")]
+    pub struct FactoryCall;
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `refundETH` function with signature `refundETH()` and selector `[18, 33, 14, 138]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "refundETH", abi = "refundETH()")]
+    pub struct RefundETHCall;
+    #[doc = "Container type for all input parameters for the `sweepToken` function with signature `sweepToken(address,uint256,address)` and selector `[223, 42, 181, 187]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "sweepToken", abi = "sweepToken(address,uint256,address)")]
+    pub struct SweepTokenCall {
+        pub token: ethers_core::types::Address,
+        pub amount_minimum: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+    }
     #[doc = "Container type for all input parameters for the `uniswapV3SwapCallback` function with signature `uniswapV3SwapCallback(int256,int256,bytes)` and selector `[250, 70, 30, 51]`"]
     #[derive(
         Clone,
@@ -180,18 +299,42 @@ pub mod i_swap_router {
         pub amount_1_delta: I256,
         pub data: ethers_core::types::Bytes,
     }
+    #[doc = "Container type for all input parameters for the `unwrapWETH9` function with signature `unwrapWETH9(uint256,address)` and selector `[73, 64, 75, 124]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "unwrapWETH9", abi = "unwrapWETH9(uint256,address)")]
+    pub struct UnwrapWETH9Call {
+        pub amount_minimum: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+    }
     #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
     pub enum ISwapRouterCalls {
+        Weth9(Weth9Call),
         ExactInput(ExactInputCall),
         ExactInputSingle(ExactInputSingleCall),
         ExactOutput(ExactOutputCall),
         ExactOutputSingle(ExactOutputSingleCall),
+        Factory(FactoryCall),
+        Multicall(MulticallCall),
+        RefundETH(RefundETHCall),
+        SweepToken(SweepTokenCall),
         UniswapV3SwapCallback(UniswapV3SwapCallbackCall),
+        UnwrapWETH9(UnwrapWETH9Call),
     }
     impl ethers_core::abi::AbiDecode for ISwapRouterCalls {
         fn decode(
             data: impl AsRef<[u8]>,
         ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) = <Weth9Call as ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                return Ok(ISwapRouterCalls::Weth9(decoded));
+            }
             if let Ok(decoded) =
                 <ExactInputCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
             {
@@ -212,36 +355,77 @@ pub mod i_swap_router {
             {
                 return Ok(ISwapRouterCalls::ExactOutputSingle(decoded));
             }
+            if let Ok(decoded) = <FactoryCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouterCalls::Factory(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouterCalls::Multicall(decoded));
+            }
+            if let Ok(decoded) =
+                <RefundETHCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouterCalls::RefundETH(decoded));
+            }
+            if let Ok(decoded) =
+                <SweepTokenCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouterCalls::SweepToken(decoded));
+            }
             if let Ok(decoded) =
                 <UniswapV3SwapCallbackCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
             {
                 return Ok(ISwapRouterCalls::UniswapV3SwapCallback(decoded));
             }
+            if let Ok(decoded) =
+                <UnwrapWETH9Call as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouterCalls::UnwrapWETH9(decoded));
+            }
             Err(ethers_core::abi::Error::InvalidData.into())
         }
     }
     impl ethers_core::abi::AbiEncode for ISwapRouterCalls {
         fn encode(self) -> Vec<u8> {
             match self {
+                ISwapRouterCalls::Weth9(element) => element.encode(),
                 ISwapRouterCalls::ExactInput(element) => element.encode(),
                 ISwapRouterCalls::ExactInputSingle(element) => element.encode(),
                 ISwapRouterCalls::ExactOutput(element) => element.encode(),
                 ISwapRouterCalls::ExactOutputSingle(element) => element.encode(),
+                ISwapRouterCalls::Factory(element) => element.encode(),
+                ISwapRouterCalls::Multicall(element) => element.encode(),
+                ISwapRouterCalls::RefundETH(element) => element.encode(),
+                ISwapRouterCalls::SweepToken(element) => element.encode(),
                 ISwapRouterCalls::UniswapV3SwapCallback(element) => element.encode(),
+                ISwapRouterCalls::UnwrapWETH9(element) => element.encode(),
             }
         }
     }
     impl ::std::fmt::Display for ISwapRouterCalls {
         fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
             match self {
+                ISwapRouterCalls::Weth9(element) => element.fmt(f),
                 ISwapRouterCalls::ExactInput(element) => element.fmt(f),
                 ISwapRouterCalls::ExactInputSingle(element) => element.fmt(f),
                 ISwapRouterCalls::ExactOutput(element) => element.fmt(f),
                 ISwapRouterCalls::ExactOutputSingle(element) => element.fmt(f),
+                ISwapRouterCalls::Factory(element) => element.fmt(f),
+                ISwapRouterCalls::Multicall(element) => element.fmt(f),
+                ISwapRouterCalls::RefundETH(element) => element.fmt(f),
+                ISwapRouterCalls::SweepToken(element) => element.fmt(f),
                 ISwapRouterCalls::UniswapV3SwapCallback(element) => element.fmt(f),
+                ISwapRouterCalls::UnwrapWETH9(element) => element.fmt(f),
             }
         }
     }
+    impl ::std::convert::From<Weth9Call> for ISwapRouterCalls {
+        fn from(var: Weth9Call) -> Self {
+            ISwapRouterCalls::Weth9(var)
+        }
+    }
     impl ::std::convert::From<ExactInputCall> for ISwapRouterCalls {
         fn from(var: ExactInputCall) -> Self {
             ISwapRouterCalls::ExactInput(var)
@@ -262,11 +446,47 @@ pub mod i_swap_router {
             ISwapRouterCalls::ExactOutputSingle(var)
         }
     }
+    impl ::std::convert::From<FactoryCall> for ISwapRouterCalls {
+        fn from(var: FactoryCall) -> Self {
+            ISwapRouterCalls::Factory(var)
+        }
+    }
+    impl ::std::convert::From<MulticallCall> for ISwapRouterCalls {
+        fn from(var: MulticallCall) -> Self {
+            ISwapRouterCalls::Multicall(var)
+        }
+    }
+    impl ::std::convert::From<RefundETHCall> for ISwapRouterCalls {
+        fn from(var: RefundETHCall) -> Self {
+            ISwapRouterCalls::RefundETH(var)
+        }
+    }
+    impl ::std::convert::From<SweepTokenCall> for ISwapRouterCalls {
+        fn from(var: SweepTokenCall) -> Self {
+            ISwapRouterCalls::SweepToken(var)
+        }
+    }
     impl ::std::convert::From<UniswapV3SwapCallbackCall> for ISwapRouterCalls {
         fn from(var: UniswapV3SwapCallbackCall) -> Self {
             ISwapRouterCalls::UniswapV3SwapCallback(var)
         }
     }
+    impl ::std::convert::From<UnwrapWETH9Call> for ISwapRouterCalls {
+        fn from(var: UnwrapWETH9Call) -> Self {
+            ISwapRouterCalls::UnwrapWETH9(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `WETH9` function with signature `WETH9()` and selector `[74, 164, 164, 252]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct Weth9Return(pub ethers_core::types::Address);
     #[doc = "Container type for all return fields from the `exactInput` function with signature `exactInput((bytes,address,uint256,uint256,uint256))` and selector `[192, 75, 141, 89]`"]
     #[derive(
         Clone,
@@ -319,6 +539,30 @@ pub mod i_swap_router {
     pub struct ExactOutputSingleReturn {
         pub amount_in: ethers_core::types::U256,
     }
+    #[doc = "Container type for all return fields from the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct FactoryReturn(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
     #[doc = "`ExactInputParams(bytes,address,uint256,uint256,uint256)`"]
     #[derive(
         Clone,
@@ -0,0 +1,624 @@
+pub use i_swap_router_02::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_swap_router_02 {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "ISwapRouter02 was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/ISwapRouter02.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static ISWAPROUTER02_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct ISwapRouter02<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for ISwapRouter02<M> {
+        fn clone(&self) -> Self {
+            ISwapRouter02(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for ISwapRouter02<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for ISwapRouter02<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(ISwapRouter02)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> ISwapRouter02<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), ISWAPROUTER02_ABI.clone(), client).into()
+        }
+        #[doc = "Calls the contract's `exactInput` (0xb858183f) function"]
+        pub fn exact_input(
+            &self,
+            params: ExactInputParams,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([184, 88, 24, 63], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `exactInputSingle` (0x04e45aaf) function"]
+        pub fn exact_input_single(
+            &self,
+            params: ExactInputSingleParams,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([4, 228, 90, 175], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `exactOutput` (0x09b81346) function"]
+        pub fn exact_output(
+            &self,
+            params: ExactOutputParams,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([9, 184, 19, 70], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `exactOutputSingle` (0x5023b4df) function"]
+        pub fn exact_output_single(
+            &self,
+            params: ExactOutputSingleParams,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([80, 35, 180, 223], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0x5ae401dc) function"]
+        pub fn multicall_with_deadline(
+            &self,
+            deadline: ethers_core::types::U256,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([90, 228, 1, 220], (deadline, data))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0x1f0464d1) function"]
+        pub fn multicall_with_previous_blockhash(
+            &self,
+            previous_blockhash: [u8; 32],
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([31, 4, 100, 209], (previous_blockhash, data))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0xac9650d8) function"]
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapExactTokensForTokens` (0x472b43f3) function"]
+        pub fn swap_exact_tokens_for_tokens(
+            &self,
+            amount_in: ethers_core::types::U256,
+            amount_out_min: ethers_core::types::U256,
+            path: ::std::vec::Vec<ethers_core::types::Address>,
+            to: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([71, 43, 67, 243], (amount_in, amount_out_min, path, to))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `swapTokensForExactTokens` (0x42712a67) function"]
+        pub fn swap_tokens_for_exact_tokens(
+            &self,
+            amount_out: ethers_core::types::U256,
+            amount_in_max: ethers_core::types::U256,
+            path: ::std::vec::Vec<ethers_core::types::Address>,
+            to: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([66, 113, 42, 103], (amount_out, amount_in_max, path, to))
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for ISwapRouter02<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `exactInput` function with signature `exactInput((bytes,address,uint256,uint256))` and selector `[184, 88, 24, 63]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "exactInput", abi = "exactInput((bytes,address,uint256,uint256))")]
+    pub struct ExactInputCall {
+        pub params: ExactInputParams,
+    }
+    #[doc = "Container type for all input parameters for the `exactInputSingle` function with signature `exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))` and selector `[4, 228, 90, 175]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "exactInputSingle",
+        abi = "exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))"
+    )]
+    pub struct ExactInputSingleCall {
+        pub params: ExactInputSingleParams,
+    }
+    #[doc = "Container type for all input parameters for the `exactOutput` function with signature `exactOutput((bytes,address,uint256,uint256))` and selector `[9, 184, 19, 70]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "exactOutput", abi = "exactOutput((bytes,address,uint256,uint256))")]
+    pub struct ExactOutputCall {
+        pub params: ExactOutputParams,
+    }
+    #[doc = "Container type for all input parameters for the `exactOutputSingle` function with signature `exactOutputSingle((address,address,uint24,address,uint256,uint256,uint160))` and selector `[80, 35, 180, 223]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "exactOutputSingle",
+        abi = "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint160))"
+    )]
+    pub struct ExactOutputSingleCall {
+        pub params: ExactOutputSingleParams,
+    }
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(uint256,bytes[])` and selector `[90, 228, 1, 220]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(uint256,bytes[])")]
+    pub struct MulticallWithDeadlineCall {
+        pub deadline: ethers_core::types::U256,
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes32,bytes[])` and selector `[31, 4, 100, 209]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes32,bytes[])")]
+    pub struct MulticallWithPreviousBlockhashCall {
+        pub previous_blockhash: [u8; 32],
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `swapExactTokensForTokens` function with signature `swapExactTokensForTokens(uint256,uint256,address[],address)` and selector `[71, 43, 67, 243]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapExactTokensForTokens",
+        abi = "swapExactTokensForTokens(uint256,uint256,address[],address)"
+    )]
+    pub struct SwapExactTokensForTokensCall {
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_min: ethers_core::types::U256,
+        pub path: ::std::vec::Vec<ethers_core::types::Address>,
+        pub to: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `swapTokensForExactTokens` function with signature `swapTokensForExactTokens(uint256,uint256,address[],address)` and selector `[66, 113, 42, 103]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "swapTokensForExactTokens",
+        abi = "swapTokensForExactTokens(uint256,uint256,address[],address)"
+    )]
+    pub struct SwapTokensForExactTokensCall {
+        pub amount_out: ethers_core::types::U256,
+        pub amount_in_max: ethers_core::types::U256,
+        pub path: ::std::vec::Vec<ethers_core::types::Address>,
+        pub to: ethers_core::types::Address,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum ISwapRouter02Calls {
+        ExactInput(ExactInputCall),
+        ExactInputSingle(ExactInputSingleCall),
+        ExactOutput(ExactOutputCall),
+        ExactOutputSingle(ExactOutputSingleCall),
+        MulticallWithDeadline(MulticallWithDeadlineCall),
+        MulticallWithPreviousBlockhash(MulticallWithPreviousBlockhashCall),
+        Multicall(MulticallCall),
+        SwapExactTokensForTokens(SwapExactTokensForTokensCall),
+        SwapTokensForExactTokens(SwapTokensForExactTokensCall),
+    }
+    impl ethers_core::abi::AbiDecode for ISwapRouter02Calls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) =
+                <ExactInputCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::ExactInput(decoded));
+            }
+            if let Ok(decoded) =
+                <ExactInputSingleCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::ExactInputSingle(decoded));
+            }
+            if let Ok(decoded) =
+                <ExactOutputCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::ExactOutput(decoded));
+            }
+            if let Ok(decoded) =
+                <ExactOutputSingleCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::ExactOutputSingle(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallWithDeadlineCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::MulticallWithDeadline(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallWithPreviousBlockhashCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(ISwapRouter02Calls::MulticallWithPreviousBlockhash(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::Multicall(decoded));
+            }
+            if let Ok(decoded) =
+                <SwapExactTokensForTokensCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::SwapExactTokensForTokens(decoded));
+            }
+            if let Ok(decoded) =
+                <SwapTokensForExactTokensCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(ISwapRouter02Calls::SwapTokensForExactTokens(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for ISwapRouter02Calls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                ISwapRouter02Calls::ExactInput(element) => element.encode(),
+                ISwapRouter02Calls::ExactInputSingle(element) => element.encode(),
+                ISwapRouter02Calls::ExactOutput(element) => element.encode(),
+                ISwapRouter02Calls::ExactOutputSingle(element) => element.encode(),
+                ISwapRouter02Calls::MulticallWithDeadline(element) => element.encode(),
+                ISwapRouter02Calls::MulticallWithPreviousBlockhash(element) => element.encode(),
+                ISwapRouter02Calls::Multicall(element) => element.encode(),
+                ISwapRouter02Calls::SwapExactTokensForTokens(element) => element.encode(),
+                ISwapRouter02Calls::SwapTokensForExactTokens(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for ISwapRouter02Calls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                ISwapRouter02Calls::ExactInput(element) => element.fmt(f),
+                ISwapRouter02Calls::ExactInputSingle(element) => element.fmt(f),
+                ISwapRouter02Calls::ExactOutput(element) => element.fmt(f),
+                ISwapRouter02Calls::ExactOutputSingle(element) => element.fmt(f),
+                ISwapRouter02Calls::MulticallWithDeadline(element) => element.fmt(f),
+                ISwapRouter02Calls::MulticallWithPreviousBlockhash(element) => element.fmt(f),
+                ISwapRouter02Calls::Multicall(element) => element.fmt(f),
+                ISwapRouter02Calls::SwapExactTokensForTokens(element) => element.fmt(f),
+                ISwapRouter02Calls::SwapTokensForExactTokens(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<ExactInputCall> for ISwapRouter02Calls {
+        fn from(var: ExactInputCall) -> Self {
+            ISwapRouter02Calls::ExactInput(var)
+        }
+    }
+    impl ::std::convert::From<ExactInputSingleCall> for ISwapRouter02Calls {
+        fn from(var: ExactInputSingleCall) -> Self {
+            ISwapRouter02Calls::ExactInputSingle(var)
+        }
+    }
+    impl ::std::convert::From<ExactOutputCall> for ISwapRouter02Calls {
+        fn from(var: ExactOutputCall) -> Self {
+            ISwapRouter02Calls::ExactOutput(var)
+        }
+    }
+    impl ::std::convert::From<ExactOutputSingleCall> for ISwapRouter02Calls {
+        fn from(var: ExactOutputSingleCall) -> Self {
+            ISwapRouter02Calls::ExactOutputSingle(var)
+        }
+    }
+    impl ::std::convert::From<MulticallWithDeadlineCall> for ISwapRouter02Calls {
+        fn from(var: MulticallWithDeadlineCall) -> Self {
+            ISwapRouter02Calls::MulticallWithDeadline(var)
+        }
+    }
+    impl ::std::convert::From<MulticallWithPreviousBlockhashCall> for ISwapRouter02Calls {
+        fn from(var: MulticallWithPreviousBlockhashCall) -> Self {
+            ISwapRouter02Calls::MulticallWithPreviousBlockhash(var)
+        }
+    }
+    impl ::std::convert::From<MulticallCall> for ISwapRouter02Calls {
+        fn from(var: MulticallCall) -> Self {
+            ISwapRouter02Calls::Multicall(var)
+        }
+    }
+    impl ::std::convert::From<SwapExactTokensForTokensCall> for ISwapRouter02Calls {
+        fn from(var: SwapExactTokensForTokensCall) -> Self {
+            ISwapRouter02Calls::SwapExactTokensForTokens(var)
+        }
+    }
+    impl ::std::convert::From<SwapTokensForExactTokensCall> for ISwapRouter02Calls {
+        fn from(var: SwapTokensForExactTokensCall) -> Self {
+            ISwapRouter02Calls::SwapTokensForExactTokens(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `exactInput` function with signature `exactInput((bytes,address,uint256,uint256))` and selector `[184, 88, 24, 63]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct ExactInputReturn {
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `exactInputSingle` function with signature `exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))` and selector `[4, 228, 90, 175]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct ExactInputSingleReturn {
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `exactOutput` function with signature `exactOutput((bytes,address,uint256,uint256))` and selector `[9, 184, 19, 70]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct ExactOutputReturn {
+        pub amount_in: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `exactOutputSingle` function with signature `exactOutputSingle((address,address,uint24,address,uint256,uint256,uint160))` and selector `[80, 35, 180, 223]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct ExactOutputSingleReturn {
+        pub amount_in: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(uint256,bytes[])` and selector `[90, 228, 1, 220]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallWithDeadlineReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes32,bytes[])` and selector `[31, 4, 100, 209]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallWithPreviousBlockhashReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all return fields from the `swapExactTokensForTokens` function with signature `swapExactTokensForTokens(uint256,uint256,address[],address)` and selector `[71, 43, 67, 243]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapExactTokensForTokensReturn {
+        pub amount_out: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `swapTokensForExactTokens` function with signature `swapTokensForExactTokens(uint256,uint256,address[],address)` and selector `[66, 113, 42, 103]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct SwapTokensForExactTokensReturn {
+        pub amount_in: ethers_core::types::U256,
+    }
+    #[doc = "`ExactInputParams(bytes,address,uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct ExactInputParams {
+        pub path: ethers_core::types::Bytes,
+        pub recipient: ethers_core::types::Address,
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_minimum: ethers_core::types::U256,
+    }
+    #[doc = "`ExactInputSingleParams(address,address,uint24,address,uint256,uint256,uint160)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct ExactInputSingleParams {
+        pub token_in: ethers_core::types::Address,
+        pub token_out: ethers_core::types::Address,
+        pub fee: u32,
+        pub recipient: ethers_core::types::Address,
+        pub amount_in: ethers_core::types::U256,
+        pub amount_out_minimum: ethers_core::types::U256,
+        pub sqrt_price_limit_x96: ethers_core::types::U256,
+    }
+    #[doc = "`ExactOutputParams(bytes,address,uint256,uint256)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct ExactOutputParams {
+        pub path: ethers_core::types::Bytes,
+        pub recipient: ethers_core::types::Address,
+        pub amount_out: ethers_core::types::U256,
+        pub amount_in_maximum: ethers_core::types::U256,
+    }
+    #[doc = "`ExactOutputSingleParams(address,address,uint24,address,uint256,uint256,uint160)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct ExactOutputSingleParams {
+        pub token_in: ethers_core::types::Address,
+        pub token_out: ethers_core::types::Address,
+        pub fee: u32,
+        pub recipient: ethers_core::types::Address,
+        pub amount_out: ethers_core::types::U256,
+        pub amount_in_maximum: ethers_core::types::U256,
+        pub sqrt_price_limit_x96: ethers_core::types::U256,
+    }
+}
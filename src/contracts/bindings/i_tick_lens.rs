@@ -0,0 +1,111 @@
+pub use i_tick_lens::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_tick_lens {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "ITickLens was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/ITickLens.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static ITICKLENS_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct ITickLens<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for ITickLens<M> {
+        fn clone(&self) -> Self {
+            ITickLens(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for ITickLens<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for ITickLens<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(ITickLens)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> ITickLens<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), ITICKLENS_ABI.clone(), client).into()
+        }
+        #[doc = "Calls the contract's `getPopulatedTicksInWord` (0x351fb478) function"]
+        pub fn get_populated_ticks_in_word(
+            &self,
+            pool: ethers_core::types::Address,
+            tick_bitmap_index: i16,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<PopulatedTick>> {
+            self.0
+                .method_hash([53, 31, 180, 120], (pool, tick_bitmap_index))
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for ITickLens<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `getPopulatedTicksInWord` function with signature `getPopulatedTicksInWord(address,int16)` and selector `[53, 31, 180, 120]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "getPopulatedTicksInWord", abi = "getPopulatedTicksInWord(address,int16)")]
+    pub struct GetPopulatedTicksInWordCall {
+        pub pool: ethers_core::types::Address,
+        pub tick_bitmap_index: i16,
+    }
+    #[doc = "Container type for all return fields from the `getPopulatedTicksInWord` function with signature `getPopulatedTicksInWord(address,int16)` and selector `[53, 31, 180, 120]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetPopulatedTicksInWordReturn {
+        pub populated_ticks: ::std::vec::Vec<PopulatedTick>,
+    }
+    #[doc = "`PopulatedTick(int24,int128,uint128)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct PopulatedTick {
+        pub tick: i32,
+        pub liquidity_net: i128,
+        pub liquidity_gross: u128,
+    }
+}
@@ -0,0 +1,88 @@
+pub use i_uniswap_v2_callee::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_uniswap_v2_callee {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "IUniswapV2Callee was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/IUniswapV2Callee.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static IUNISWAPV2CALLEE_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct IUniswapV2Callee<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for IUniswapV2Callee<M> {
+        fn clone(&self) -> Self {
+            IUniswapV2Callee(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for IUniswapV2Callee<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for IUniswapV2Callee<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(IUniswapV2Callee)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> IUniswapV2Callee<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), IUNISWAPV2CALLEE_ABI.clone(), client)
+                .into()
+        }
+        #[doc = "Calls the contract's `uniswapV2Call` (0x10d1e85c) function"]
+        pub fn uniswap_v2_call(
+            &self,
+            sender: ethers_core::types::Address,
+            amount_0: ethers_core::types::U256,
+            amount_1: ethers_core::types::U256,
+            data: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([16, 209, 232, 92], (sender, amount_0, amount_1, data))
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for IUniswapV2Callee<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `uniswapV2Call` function with signature `uniswapV2Call(address,uint256,uint256,bytes)` and selector `[16, 209, 232, 92]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "uniswapV2Call", abi = "uniswapV2Call(address,uint256,uint256,bytes)")]
+    pub struct UniswapV2CallCall {
+        pub sender: ethers_core::types::Address,
+        pub amount_0: ethers_core::types::U256,
+        pub amount_1: ethers_core::types::U256,
+        pub data: ethers_core::types::Bytes,
+    }
+}
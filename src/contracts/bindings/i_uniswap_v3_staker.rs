@@ -0,0 +1,1097 @@
+pub use i_uniswap_v3_staker::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod i_uniswap_v3_staker {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "IUniswapV3Staker was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/IUniswapV3Staker.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static IUNISWAPV3STAKER_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct IUniswapV3Staker<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for IUniswapV3Staker<M> {
+        fn clone(&self) -> Self {
+            IUniswapV3Staker(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for IUniswapV3Staker<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for IUniswapV3Staker<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(IUniswapV3Staker)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> IUniswapV3Staker<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), IUNISWAPV3STAKER_ABI.clone(), client)
+                .into()
+        }
+        #[doc = "Calls the contract's `claimReward` (0x2f2d783d) function"]
+        pub fn claim_reward(
+            &self,
+            reward_token: ethers_core::types::Address,
+            to: ethers_core::types::Address,
+            amount_requested: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([47, 45, 120, 61], (reward_token, to, amount_requested))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `createIncentive` (0x5cc5e3d9) function"]
+        pub fn create_incentive(
+            &self,
+            key: IncentiveKey,
+            reward: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([92, 197, 227, 217], (key, reward))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `deposits` (0xb02c43d0) function"]
+        pub fn deposits(
+            &self,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, (ethers_core::types::Address, u64, i32, i32)>
+        {
+            self.0
+                .method_hash([176, 44, 67, 208], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `endIncentive` (0xb5ada6e4) function"]
+        pub fn end_incentive(
+            &self,
+            key: IncentiveKey,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([181, 173, 166, 228], (key,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `factory` (0xc45a0155) function"]
+        pub fn factory(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([196, 90, 1, 85], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getRewardInfo` (0xd953186e) function"]
+        pub fn get_reward_info(
+            &self,
+            key: IncentiveKey,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, ethers_core::types::U256),
+        > {
+            self.0
+                .method_hash([217, 83, 24, 110], (key, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `incentives` (0x60777795) function"]
+        pub fn incentives(
+            &self,
+            incentive_id: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, ethers_core::types::U256, u128),
+        > {
+            self.0
+                .method_hash([96, 119, 119, 149], incentive_id)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `maxIncentiveDuration` (0x3dc0714b) function"]
+        pub fn max_incentive_duration(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([61, 192, 113, 75], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `maxIncentiveStartLeadTime` (0x01b75440) function"]
+        pub fn max_incentive_start_lead_time(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([1, 183, 84, 64], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0xac9650d8) function"]
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `nonfungiblePositionManager` (0xb44a2722) function"]
+        pub fn nonfungible_position_manager(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([180, 74, 39, 34], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `rewards` (0xe70b9e27) function"]
+        pub fn rewards(
+            &self,
+            reward_token: ethers_core::types::Address,
+            owner: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([231, 11, 158, 39], (reward_token, owner))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `stakeToken` (0xf2d2909b) function"]
+        pub fn stake_token(
+            &self,
+            key: IncentiveKey,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([242, 210, 144, 155], (key, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `stakes` (0xc36c1ea5) function"]
+        pub fn stakes(
+            &self,
+            token_id: ethers_core::types::U256,
+            incentive_id: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<M, (ethers_core::types::U256, u128)> {
+            self.0
+                .method_hash([195, 108, 30, 165], (token_id, incentive_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `transferDeposit` (0x26bfee04) function"]
+        pub fn transfer_deposit(
+            &self,
+            token_id: ethers_core::types::U256,
+            to: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([38, 191, 238, 4], (token_id, to))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `unstakeToken` (0xf549ab42) function"]
+        pub fn unstake_token(
+            &self,
+            key: IncentiveKey,
+            token_id: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([245, 73, 171, 66], (key, token_id))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `withdrawToken` (0x3c423f0b) function"]
+        pub fn withdraw_token(
+            &self,
+            token_id: ethers_core::types::U256,
+            to: ethers_core::types::Address,
+            data: ethers_core::types::Bytes,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([60, 66, 63, 11], (token_id, to, data))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Gets the contract's `DepositTransferred` event"]
+        pub fn deposit_transferred_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, DepositTransferredFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `IncentiveCreated` event"]
+        pub fn incentive_created_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, IncentiveCreatedFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `IncentiveEnded` event"]
+        pub fn incentive_ended_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, IncentiveEndedFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `RewardClaimed` event"]
+        pub fn reward_claimed_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, RewardClaimedFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `TokenStaked` event"]
+        pub fn token_staked_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, TokenStakedFilter> {
+            self.0.event()
+        }
+        #[doc = "Gets the contract's `TokenUnstaked` event"]
+        pub fn token_unstaked_filter(
+            &self,
+        ) -> ethers_contract::builders::Event<M, TokenUnstakedFilter> {
+            self.0.event()
+        }
+        #[doc = r" Returns an [`Event`](#ethers_contract::builders::Event) builder for all events of this contract"]
+        pub fn events(&self) -> ethers_contract::builders::Event<M, IUniswapV3StakerEvents> {
+            self.0.event_with_filter(Default::default())
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for IUniswapV3Staker<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "DepositTransferred", abi = "DepositTransferred(uint256,address,address)")]
+    pub struct DepositTransferredFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        #[ethevent(indexed)]
+        pub old_owner: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub new_owner: ethers_core::types::Address,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethevent(
+        name = "IncentiveCreated",
+        abi = "IncentiveCreated(address,address,uint256,uint256,address,uint256)"
+    )]
+    pub struct IncentiveCreatedFilter {
+        #[ethevent(indexed)]
+        pub reward_token: ethers_core::types::Address,
+        #[ethevent(indexed)]
+        pub pool: ethers_core::types::Address,
+        pub start_time: ethers_core::types::U256,
+        pub end_time: ethers_core::types::U256,
+        pub refundee: ethers_core::types::Address,
+        pub reward: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "IncentiveEnded", abi = "IncentiveEnded(bytes32,uint256)")]
+    pub struct IncentiveEndedFilter {
+        #[ethevent(indexed)]
+        pub incentive_id: [u8; 32],
+        pub refund: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "RewardClaimed", abi = "RewardClaimed(address,uint256)")]
+    pub struct RewardClaimedFilter {
+        #[ethevent(indexed)]
+        pub to: ethers_core::types::Address,
+        pub reward: ethers_core::types::U256,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "TokenStaked", abi = "TokenStaked(uint256,bytes32,uint128)")]
+    pub struct TokenStakedFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        #[ethevent(indexed)]
+        pub incentive_id: [u8; 32],
+        pub liquidity: u128,
+    }
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthEvent,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    #[ethevent(name = "TokenUnstaked", abi = "TokenUnstaked(uint256,bytes32)")]
+    pub struct TokenUnstakedFilter {
+        #[ethevent(indexed)]
+        pub token_id: ethers_core::types::U256,
+        #[ethevent(indexed)]
+        pub incentive_id: [u8; 32],
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IUniswapV3StakerEvents {
+        DepositTransferredFilter(DepositTransferredFilter),
+        IncentiveCreatedFilter(IncentiveCreatedFilter),
+        IncentiveEndedFilter(IncentiveEndedFilter),
+        RewardClaimedFilter(RewardClaimedFilter),
+        TokenStakedFilter(TokenStakedFilter),
+        TokenUnstakedFilter(TokenUnstakedFilter),
+    }
+    impl ethers_contract::EthLogDecode for IUniswapV3StakerEvents {
+        fn decode_log(
+            log: &ethers_core::abi::RawLog,
+        ) -> ::std::result::Result<Self, ethers_core::abi::Error>
+        where
+            Self: Sized,
+        {
+            if let Ok(decoded) = DepositTransferredFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::DepositTransferredFilter(decoded));
+            }
+            if let Ok(decoded) = IncentiveCreatedFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::IncentiveCreatedFilter(decoded));
+            }
+            if let Ok(decoded) = IncentiveEndedFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::IncentiveEndedFilter(decoded));
+            }
+            if let Ok(decoded) = RewardClaimedFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::RewardClaimedFilter(decoded));
+            }
+            if let Ok(decoded) = TokenStakedFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::TokenStakedFilter(decoded));
+            }
+            if let Ok(decoded) = TokenUnstakedFilter::decode_log(log) {
+                return Ok(IUniswapV3StakerEvents::TokenUnstakedFilter(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData)
+        }
+    }
+    impl ::std::fmt::Display for IUniswapV3StakerEvents {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                IUniswapV3StakerEvents::DepositTransferredFilter(element) => element.fmt(f),
+                IUniswapV3StakerEvents::IncentiveCreatedFilter(element) => element.fmt(f),
+                IUniswapV3StakerEvents::IncentiveEndedFilter(element) => element.fmt(f),
+                IUniswapV3StakerEvents::RewardClaimedFilter(element) => element.fmt(f),
+                IUniswapV3StakerEvents::TokenStakedFilter(element) => element.fmt(f),
+                IUniswapV3StakerEvents::TokenUnstakedFilter(element) => element.fmt(f),
+            }
+        }
+    }
+    #[doc = "Container type for all input parameters for the `claimReward` function with signature `claimReward(address,address,uint256)` and selector `[47, 45, 120, 61]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "claimReward", abi = "claimReward(address,address,uint256)")]
+    pub struct ClaimRewardCall {
+        pub reward_token: ethers_core::types::Address,
+        pub to: ethers_core::types::Address,
+        pub amount_requested: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `createIncentive` function with signature `createIncentive((address,address,uint256,uint256,address),uint256)` and selector `[92, 197, 227, 217]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "createIncentive",
+        abi = "createIncentive((address,address,uint256,uint256,address),uint256)"
+    )]
+    pub struct CreateIncentiveCall {
+        pub key: IncentiveKey,
+        pub reward: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `deposits` function with signature `deposits(uint256)` and selector `[176, 44, 67, 208]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "deposits", abi = "deposits(uint256)")]
+    pub struct DepositsCall {
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `endIncentive` function with signature `endIncentive((address,address,uint256,uint256,address))` and selector `[181, 173, 166, 228]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "endIncentive",
+        abi = "endIncentive((address,address,uint256,uint256,address))"
+    )]
+    pub struct EndIncentiveCall {
+        pub key: IncentiveKey,
+    }
+    #[doc = "Container type for all input parameters for the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "factory", abi = "factory()")]
+    pub struct FactoryCall;
+    #[doc = "Container type for all input parameters for the `getRewardInfo` function with signature `getRewardInfo((address,address,uint256,uint256,address),uint256)` and selector `[217, 83, 24, 110]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "getRewardInfo",
+        abi = "getRewardInfo((address,address,uint256,uint256,address),uint256)"
+    )]
+    pub struct GetRewardInfoCall {
+        pub key: IncentiveKey,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `incentives` function with signature `incentives(bytes32)` and selector `[96, 119, 119, 149]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "incentives", abi = "incentives(bytes32)")]
+    pub struct IncentivesCall {
+        pub incentive_id: [u8; 32],
+    }
+    #[doc = "Container type for all input parameters for the `maxIncentiveDuration` function with signature `maxIncentiveDuration()` and selector `[61, 192, 113, 75]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "maxIncentiveDuration", abi = "maxIncentiveDuration()")]
+    pub struct MaxIncentiveDurationCall;
+    #[doc = "Container type for all input parameters for the `maxIncentiveStartLeadTime` function with signature `maxIncentiveStartLeadTime()` and selector `[1, 183, 84, 64]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "maxIncentiveStartLeadTime", abi = "maxIncentiveStartLeadTime()")]
+    pub struct MaxIncentiveStartLeadTimeCall;
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `nonfungiblePositionManager` function with signature `nonfungiblePositionManager()` and selector `[180, 74, 39, 34]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "nonfungiblePositionManager", abi = "nonfungiblePositionManager()")]
+    pub struct NonfungiblePositionManagerCall;
+    #[doc = "Container type for all input parameters for the `rewards` function with signature `rewards(address,address)` and selector `[231, 11, 158, 39]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "rewards", abi = "rewards(address,address)")]
+    pub struct RewardsCall {
+        pub reward_token: ethers_core::types::Address,
+        pub owner: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `stakeToken` function with signature `stakeToken((address,address,uint256,uint256,address),uint256)` and selector `[242, 210, 144, 155]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "stakeToken",
+        abi = "stakeToken((address,address,uint256,uint256,address),uint256)"
+    )]
+    pub struct StakeTokenCall {
+        pub key: IncentiveKey,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `stakes` function with signature `stakes(uint256,bytes32)` and selector `[195, 108, 30, 165]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "stakes", abi = "stakes(uint256,bytes32)")]
+    pub struct StakesCall {
+        pub token_id: ethers_core::types::U256,
+        pub incentive_id: [u8; 32],
+    }
+    #[doc = "Container type for all input parameters for the `transferDeposit` function with signature `transferDeposit(uint256,address)` and selector `[38, 191, 238, 4]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "transferDeposit", abi = "transferDeposit(uint256,address)")]
+    pub struct TransferDepositCall {
+        pub token_id: ethers_core::types::U256,
+        pub to: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all input parameters for the `unstakeToken` function with signature `unstakeToken((address,address,uint256,uint256,address),uint256)` and selector `[245, 73, 171, 66]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "unstakeToken",
+        abi = "unstakeToken((address,address,uint256,uint256,address),uint256)"
+    )]
+    pub struct UnstakeTokenCall {
+        pub key: IncentiveKey,
+        pub token_id: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `withdrawToken` function with signature `withdrawToken(uint256,address,bytes)` and selector `[60, 66, 63, 11]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "withdrawToken", abi = "withdrawToken(uint256,address,bytes)")]
+    pub struct WithdrawTokenCall {
+        pub token_id: ethers_core::types::U256,
+        pub to: ethers_core::types::Address,
+        pub data: ethers_core::types::Bytes,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IUniswapV3StakerCalls {
+        ClaimReward(ClaimRewardCall),
+        CreateIncentive(CreateIncentiveCall),
+        Deposits(DepositsCall),
+        EndIncentive(EndIncentiveCall),
+        Factory(FactoryCall),
+        GetRewardInfo(GetRewardInfoCall),
+        Incentives(IncentivesCall),
+        MaxIncentiveDuration(MaxIncentiveDurationCall),
+        MaxIncentiveStartLeadTime(MaxIncentiveStartLeadTimeCall),
+        Multicall(MulticallCall),
+        NonfungiblePositionManager(NonfungiblePositionManagerCall),
+        Rewards(RewardsCall),
+        StakeToken(StakeTokenCall),
+        Stakes(StakesCall),
+        TransferDeposit(TransferDepositCall),
+        UnstakeToken(UnstakeTokenCall),
+        WithdrawToken(WithdrawTokenCall),
+    }
+    impl ethers_core::abi::AbiDecode for IUniswapV3StakerCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) =
+                <ClaimRewardCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::ClaimReward(decoded));
+            }
+            if let Ok(decoded) =
+                <CreateIncentiveCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::CreateIncentive(decoded));
+            }
+            if let Ok(decoded) =
+                <DepositsCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Deposits(decoded));
+            }
+            if let Ok(decoded) =
+                <EndIncentiveCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::EndIncentive(decoded));
+            }
+            if let Ok(decoded) = <FactoryCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Factory(decoded));
+            }
+            if let Ok(decoded) =
+                <GetRewardInfoCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::GetRewardInfo(decoded));
+            }
+            if let Ok(decoded) =
+                <IncentivesCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Incentives(decoded));
+            }
+            if let Ok(decoded) =
+                <MaxIncentiveDurationCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::MaxIncentiveDuration(decoded));
+            }
+            if let Ok(decoded) =
+                <MaxIncentiveStartLeadTimeCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IUniswapV3StakerCalls::MaxIncentiveStartLeadTime(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Multicall(decoded));
+            }
+            if let Ok(decoded) =
+                <NonfungiblePositionManagerCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IUniswapV3StakerCalls::NonfungiblePositionManager(decoded));
+            }
+            if let Ok(decoded) = <RewardsCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Rewards(decoded));
+            }
+            if let Ok(decoded) =
+                <StakeTokenCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::StakeToken(decoded));
+            }
+            if let Ok(decoded) = <StakesCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::Stakes(decoded));
+            }
+            if let Ok(decoded) =
+                <TransferDepositCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::TransferDeposit(decoded));
+            }
+            if let Ok(decoded) =
+                <UnstakeTokenCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::UnstakeToken(decoded));
+            }
+            if let Ok(decoded) =
+                <WithdrawTokenCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IUniswapV3StakerCalls::WithdrawToken(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for IUniswapV3StakerCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                IUniswapV3StakerCalls::ClaimReward(element) => element.encode(),
+                IUniswapV3StakerCalls::CreateIncentive(element) => element.encode(),
+                IUniswapV3StakerCalls::Deposits(element) => element.encode(),
+                IUniswapV3StakerCalls::EndIncentive(element) => element.encode(),
+                IUniswapV3StakerCalls::Factory(element) => element.encode(),
+                IUniswapV3StakerCalls::GetRewardInfo(element) => element.encode(),
+                IUniswapV3StakerCalls::Incentives(element) => element.encode(),
+                IUniswapV3StakerCalls::MaxIncentiveDuration(element) => element.encode(),
+                IUniswapV3StakerCalls::MaxIncentiveStartLeadTime(element) => element.encode(),
+                IUniswapV3StakerCalls::Multicall(element) => element.encode(),
+                IUniswapV3StakerCalls::NonfungiblePositionManager(element) => element.encode(),
+                IUniswapV3StakerCalls::Rewards(element) => element.encode(),
+                IUniswapV3StakerCalls::StakeToken(element) => element.encode(),
+                IUniswapV3StakerCalls::Stakes(element) => element.encode(),
+                IUniswapV3StakerCalls::TransferDeposit(element) => element.encode(),
+                IUniswapV3StakerCalls::UnstakeToken(element) => element.encode(),
+                IUniswapV3StakerCalls::WithdrawToken(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for IUniswapV3StakerCalls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                IUniswapV3StakerCalls::ClaimReward(element) => element.fmt(f),
+                IUniswapV3StakerCalls::CreateIncentive(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Deposits(element) => element.fmt(f),
+                IUniswapV3StakerCalls::EndIncentive(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Factory(element) => element.fmt(f),
+                IUniswapV3StakerCalls::GetRewardInfo(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Incentives(element) => element.fmt(f),
+                IUniswapV3StakerCalls::MaxIncentiveDuration(element) => element.fmt(f),
+                IUniswapV3StakerCalls::MaxIncentiveStartLeadTime(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Multicall(element) => element.fmt(f),
+                IUniswapV3StakerCalls::NonfungiblePositionManager(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Rewards(element) => element.fmt(f),
+                IUniswapV3StakerCalls::StakeToken(element) => element.fmt(f),
+                IUniswapV3StakerCalls::Stakes(element) => element.fmt(f),
+                IUniswapV3StakerCalls::TransferDeposit(element) => element.fmt(f),
+                IUniswapV3StakerCalls::UnstakeToken(element) => element.fmt(f),
+                IUniswapV3StakerCalls::WithdrawToken(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<ClaimRewardCall> for IUniswapV3StakerCalls {
+        fn from(var: ClaimRewardCall) -> Self {
+            IUniswapV3StakerCalls::ClaimReward(var)
+        }
+    }
+    impl ::std::convert::From<CreateIncentiveCall> for IUniswapV3StakerCalls {
+        fn from(var: CreateIncentiveCall) -> Self {
+            IUniswapV3StakerCalls::CreateIncentive(var)
+        }
+    }
+    impl ::std::convert::From<DepositsCall> for IUniswapV3StakerCalls {
+        fn from(var: DepositsCall) -> Self {
+            IUniswapV3StakerCalls::Deposits(var)
+        }
+    }
+    impl ::std::convert::From<EndIncentiveCall> for IUniswapV3StakerCalls {
+        fn from(var: EndIncentiveCall) -> Self {
+            IUniswapV3StakerCalls::EndIncentive(var)
+        }
+    }
+    impl ::std::convert::From<FactoryCall> for IUniswapV3StakerCalls {
+        fn from(var: FactoryCall) -> Self {
+            IUniswapV3StakerCalls::Factory(var)
+        }
+    }
+    impl ::std::convert::From<GetRewardInfoCall> for IUniswapV3StakerCalls {
+        fn from(var: GetRewardInfoCall) -> Self {
+            IUniswapV3StakerCalls::GetRewardInfo(var)
+        }
+    }
+    impl ::std::convert::From<IncentivesCall> for IUniswapV3StakerCalls {
+        fn from(var: IncentivesCall) -> Self {
+            IUniswapV3StakerCalls::Incentives(var)
+        }
+    }
+    impl ::std::convert::From<MaxIncentiveDurationCall> for IUniswapV3StakerCalls {
+        fn from(var: MaxIncentiveDurationCall) -> Self {
+            IUniswapV3StakerCalls::MaxIncentiveDuration(var)
+        }
+    }
+    impl ::std::convert::From<MaxIncentiveStartLeadTimeCall> for IUniswapV3StakerCalls {
+        fn from(var: MaxIncentiveStartLeadTimeCall) -> Self {
+            IUniswapV3StakerCalls::MaxIncentiveStartLeadTime(var)
+        }
+    }
+    impl ::std::convert::From<MulticallCall> for IUniswapV3StakerCalls {
+        fn from(var: MulticallCall) -> Self {
+            IUniswapV3StakerCalls::Multicall(var)
+        }
+    }
+    impl ::std::convert::From<NonfungiblePositionManagerCall> for IUniswapV3StakerCalls {
+        fn from(var: NonfungiblePositionManagerCall) -> Self {
+            IUniswapV3StakerCalls::NonfungiblePositionManager(var)
+        }
+    }
+    impl ::std::convert::From<RewardsCall> for IUniswapV3StakerCalls {
+        fn from(var: RewardsCall) -> Self {
+            IUniswapV3StakerCalls::Rewards(var)
+        }
+    }
+    impl ::std::convert::From<StakeTokenCall> for IUniswapV3StakerCalls {
+        fn from(var: StakeTokenCall) -> Self {
+            IUniswapV3StakerCalls::StakeToken(var)
+        }
+    }
+    impl ::std::convert::From<StakesCall> for IUniswapV3StakerCalls {
+        fn from(var: StakesCall) -> Self {
+            IUniswapV3StakerCalls::Stakes(var)
+        }
+    }
+    impl ::std::convert::From<TransferDepositCall> for IUniswapV3StakerCalls {
+        fn from(var: TransferDepositCall) -> Self {
+            IUniswapV3StakerCalls::TransferDeposit(var)
+        }
+    }
+    impl ::std::convert::From<UnstakeTokenCall> for IUniswapV3StakerCalls {
+        fn from(var: UnstakeTokenCall) -> Self {
+            IUniswapV3StakerCalls::UnstakeToken(var)
+        }
+    }
+    impl ::std::convert::From<WithdrawTokenCall> for IUniswapV3StakerCalls {
+        fn from(var: WithdrawTokenCall) -> Self {
+            IUniswapV3StakerCalls::WithdrawToken(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `claimReward` function with signature `claimReward(address,address,uint256)` and selector `[47, 45, 120, 61]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct ClaimRewardReturn {
+        pub reward: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `deposits` function with signature `deposits(uint256)` and selector `[176, 44, 67, 208]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct DepositsReturn {
+        pub owner: ethers_core::types::Address,
+        pub number_of_stakes: u64,
+        pub tick_lower: i32,
+        pub tick_upper: i32,
+    }
+    #[doc = "Container type for all return fields from the `endIncentive` function with signature `endIncentive((address,address,uint256,uint256,address))` and selector `[181, 173, 166, 228]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct EndIncentiveReturn {
+        pub refund: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `factory` function with signature `factory()` and selector `[196, 90, 1, 85]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct FactoryReturn(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `getRewardInfo` function with signature `getRewardInfo((address,address,uint256,uint256,address),uint256)` and selector `[217, 83, 24, 110]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct GetRewardInfoReturn {
+        pub reward: ethers_core::types::U256,
+        pub seconds_inside_x128: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `incentives` function with signature `incentives(bytes32)` and selector `[96, 119, 119, 149]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct IncentivesReturn {
+        pub total_reward_unclaimed: ethers_core::types::U256,
+        pub total_seconds_claimed_x128: ethers_core::types::U256,
+        pub number_of_stakes: u128,
+    }
+    #[doc = "Container type for all return fields from the `maxIncentiveDuration` function with signature `maxIncentiveDuration()` and selector `[61, 192, 113, 75]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MaxIncentiveDurationReturn(pub ethers_core::types::U256);
+    #[doc = "Container type for all return fields from the `maxIncentiveStartLeadTime` function with signature `maxIncentiveStartLeadTime()` and selector `[1, 183, 84, 64]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MaxIncentiveStartLeadTimeReturn(pub ethers_core::types::U256);
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all return fields from the `nonfungiblePositionManager` function with signature `nonfungiblePositionManager()` and selector `[180, 74, 39, 34]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct NonfungiblePositionManagerReturn(pub ethers_core::types::Address);
+    #[doc = "Container type for all return fields from the `rewards` function with signature `rewards(address,address)` and selector `[231, 11, 158, 39]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct RewardsReturn {
+        pub rewards_owed: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all return fields from the `stakes` function with signature `stakes(uint256,bytes32)` and selector `[195, 108, 30, 165]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct StakesReturn {
+        pub seconds_per_liquidity_inside_initial_x128: ethers_core::types::U256,
+        pub liquidity: u128,
+    }
+    #[doc = "`IncentiveKey(address,address,uint256,uint256,address)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct IncentiveKey {
+        pub reward_token: ethers_core::types::Address,
+        pub pool: ethers_core::types::Address,
+        pub start_time: ethers_core::types::U256,
+        pub end_time: ethers_core::types::U256,
+        pub refundee: ethers_core::types::Address,
+    }
+}
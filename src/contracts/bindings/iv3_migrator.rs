@@ -0,0 +1,451 @@
+pub use iv3_migrator::*;
+#[allow(clippy::too_many_arguments, non_camel_case_types)]
+pub mod iv3_migrator {
+    #![allow(clippy::enum_variant_names)]
+    #![allow(dead_code)]
+    #![allow(clippy::type_complexity)]
+    #![allow(unused_imports)]
+    use ethers_contract::{
+        builders::{ContractCall, Event},
+        Contract, Lazy,
+    };
+    use ethers_core::{
+        abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
+        types::*,
+    };
+    use ethers_providers::Middleware;
+    #[doc = "IV3Migrator was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
+    use std::sync::Arc;
+    const __ABI: &str = include_str!("../../../abi/IV3Migrator.json");
+    #[doc = r" The parsed JSON-ABI of the contract."]
+    pub static IV3MIGRATOR_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            ethers_core::utils::__serde_json::from_str(__ABI).expect("invalid abi")
+        });
+    pub struct IV3Migrator<M>(ethers_contract::Contract<M>);
+    impl<M> Clone for IV3Migrator<M> {
+        fn clone(&self) -> Self {
+            IV3Migrator(self.0.clone())
+        }
+    }
+    impl<M> std::ops::Deref for IV3Migrator<M> {
+        type Target = ethers_contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> std::fmt::Debug for IV3Migrator<M> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_tuple(stringify!(IV3Migrator)).field(&self.address()).finish()
+        }
+    }
+    impl<M: ethers_providers::Middleware> IV3Migrator<M> {
+        #[doc = r" Creates a new contract instance with the specified `ethers`"]
+        #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
+        #[doc = r" object"]
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            ethers_contract::Contract::new(address.into(), IV3MIGRATOR_ABI.clone(), client).into()
+        }
+        #[doc = "Calls the contract's `createAndInitializePoolIfNecessary` (0x13ead562) function"]
+        pub fn create_and_initialize_pool_if_necessary(
+            &self,
+            token_0: ethers_core::types::Address,
+            token_1: ethers_core::types::Address,
+            fee: u32,
+            sqrt_price_x96: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([19, 234, 213, 98], (token_0, token_1, fee, sqrt_price_x96))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `migrate` (0xd44f2bf2) function"]
+        pub fn migrate(
+            &self,
+            params: MigrateParams,
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([212, 79, 43, 242], (params,))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `multicall` (0xac9650d8) function"]
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<ethers_core::types::Bytes>,
+        ) -> ethers_contract::builders::ContractCall<M, ::std::vec::Vec<ethers_core::types::Bytes>>
+        {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `selfPermit` (0xf3995c67) function"]
+        pub fn self_permit(
+            &self,
+            token: ethers_core::types::Address,
+            value: ethers_core::types::U256,
+            deadline: ethers_core::types::U256,
+            v: u8,
+            r: [u8; 32],
+            s: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([243, 153, 92, 103], (token, value, deadline, v, r, s))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `selfPermitAllowed` (0x4659a494) function"]
+        pub fn self_permit_allowed(
+            &self,
+            token: ethers_core::types::Address,
+            nonce: ethers_core::types::U256,
+            expiry: ethers_core::types::U256,
+            v: u8,
+            r: [u8; 32],
+            s: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([70, 89, 164, 148], (token, nonce, expiry, v, r, s))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `selfPermitAllowedIfNecessary` (0xa4a78f0c) function"]
+        pub fn self_permit_allowed_if_necessary(
+            &self,
+            token: ethers_core::types::Address,
+            nonce: ethers_core::types::U256,
+            expiry: ethers_core::types::U256,
+            v: u8,
+            r: [u8; 32],
+            s: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([164, 167, 143, 12], (token, nonce, expiry, v, r, s))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `selfPermitIfNecessary` (0xc2e3140a) function"]
+        pub fn self_permit_if_necessary(
+            &self,
+            token: ethers_core::types::Address,
+            value: ethers_core::types::U256,
+            deadline: ethers_core::types::U256,
+            v: u8,
+            r: [u8; 32],
+            s: [u8; 32],
+        ) -> ethers_contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([194, 227, 20, 10], (token, value, deadline, v, r, s))
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for IV3Migrator<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    #[doc = "Container type for all input parameters for the `createAndInitializePoolIfNecessary` function with signature `createAndInitializePoolIfNecessary(address,address,uint24,uint160)` and selector `[19, 234, 213, 98]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "createAndInitializePoolIfNecessary",
+        abi = "createAndInitializePoolIfNecessary(address,address,uint24,uint160)"
+    )]
+    pub struct CreateAndInitializePoolIfNecessaryCall {
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+        pub fee: u32,
+        pub sqrt_price_x96: ethers_core::types::U256,
+    }
+    #[doc = "Container type for all input parameters for the `migrate` function with signature `migrate((address,uint256,uint8,address,address,uint24,int24,int24,uint256,uint256,address,uint256,bool))` and selector `[212, 79, 43, 242]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "migrate",
+        abi = "migrate((address,uint256,uint8,address,address,uint24,int24,int24,uint256,uint256,address,uint256,bool))"
+    )]
+    pub struct MigrateCall {
+        pub params: MigrateParams,
+    }
+    #[doc = "Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "Container type for all input parameters for the `selfPermit` function with signature `selfPermit(address,uint256,uint256,uint8,bytes32,bytes32)` and selector `[243, 153, 92, 103]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "selfPermit",
+        abi = "selfPermit(address,uint256,uint256,uint8,bytes32,bytes32)"
+    )]
+    pub struct SelfPermitCall {
+        pub token: ethers_core::types::Address,
+        pub value: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+        pub v: u8,
+        pub r: [u8; 32],
+        pub s: [u8; 32],
+    }
+    #[doc = "Container type for all input parameters for the `selfPermitAllowed` function with signature `selfPermitAllowed(address,uint256,uint256,uint8,bytes32,bytes32)` and selector `[70, 89, 164, 148]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "selfPermitAllowed",
+        abi = "selfPermitAllowed(address,uint256,uint256,uint8,bytes32,bytes32)"
+    )]
+    pub struct SelfPermitAllowedCall {
+        pub token: ethers_core::types::Address,
+        pub nonce: ethers_core::types::U256,
+        pub expiry: ethers_core::types::U256,
+        pub v: u8,
+        pub r: [u8; 32],
+        pub s: [u8; 32],
+    }
+    #[doc = "Container type for all input parameters for the `selfPermitAllowedIfNecessary` function with signature `selfPermitAllowedIfNecessary(address,uint256,uint256,uint8,bytes32,bytes32)` and selector `[164, 167, 143, 12]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "selfPermitAllowedIfNecessary",
+        abi = "selfPermitAllowedIfNecessary(address,uint256,uint256,uint8,bytes32,bytes32)"
+    )]
+    pub struct SelfPermitAllowedIfNecessaryCall {
+        pub token: ethers_core::types::Address,
+        pub nonce: ethers_core::types::U256,
+        pub expiry: ethers_core::types::U256,
+        pub v: u8,
+        pub r: [u8; 32],
+        pub s: [u8; 32],
+    }
+    #[doc = "Container type for all input parameters for the `selfPermitIfNecessary` function with signature `selfPermitIfNecessary(address,uint256,uint256,uint8,bytes32,bytes32)` and selector `[194, 227, 20, 10]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthCall,
+        ethers_contract :: EthDisplay,
+        Default,
+    )]
+    #[ethcall(
+        name = "selfPermitIfNecessary",
+        abi = "selfPermitIfNecessary(address,uint256,uint256,uint8,bytes32,bytes32)"
+    )]
+    pub struct SelfPermitIfNecessaryCall {
+        pub token: ethers_core::types::Address,
+        pub value: ethers_core::types::U256,
+        pub deadline: ethers_core::types::U256,
+        pub v: u8,
+        pub r: [u8; 32],
+        pub s: [u8; 32],
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, ethers_contract :: EthAbiType)]
+    pub enum IV3MigratorCalls {
+        CreateAndInitializePoolIfNecessary(CreateAndInitializePoolIfNecessaryCall),
+        Migrate(MigrateCall),
+        Multicall(MulticallCall),
+        SelfPermit(SelfPermitCall),
+        SelfPermitAllowed(SelfPermitAllowedCall),
+        SelfPermitAllowedIfNecessary(SelfPermitAllowedIfNecessaryCall),
+        SelfPermitIfNecessary(SelfPermitIfNecessaryCall),
+    }
+    impl ethers_core::abi::AbiDecode for IV3MigratorCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::std::result::Result<Self, ethers_core::abi::AbiError> {
+            if let Ok(decoded) =
+                <CreateAndInitializePoolIfNecessaryCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IV3MigratorCalls::CreateAndInitializePoolIfNecessary(decoded));
+            }
+            if let Ok(decoded) = <MigrateCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IV3MigratorCalls::Migrate(decoded));
+            }
+            if let Ok(decoded) =
+                <MulticallCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IV3MigratorCalls::Multicall(decoded));
+            }
+            if let Ok(decoded) =
+                <SelfPermitCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IV3MigratorCalls::SelfPermit(decoded));
+            }
+            if let Ok(decoded) =
+                <SelfPermitAllowedCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IV3MigratorCalls::SelfPermitAllowed(decoded));
+            }
+            if let Ok(decoded) =
+                <SelfPermitAllowedIfNecessaryCall as ethers_core::abi::AbiDecode>::decode(
+                    data.as_ref(),
+                )
+            {
+                return Ok(IV3MigratorCalls::SelfPermitAllowedIfNecessary(decoded));
+            }
+            if let Ok(decoded) =
+                <SelfPermitIfNecessaryCall as ethers_core::abi::AbiDecode>::decode(data.as_ref())
+            {
+                return Ok(IV3MigratorCalls::SelfPermitIfNecessary(decoded));
+            }
+            Err(ethers_core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ethers_core::abi::AbiEncode for IV3MigratorCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                IV3MigratorCalls::CreateAndInitializePoolIfNecessary(element) => element.encode(),
+                IV3MigratorCalls::Migrate(element) => element.encode(),
+                IV3MigratorCalls::Multicall(element) => element.encode(),
+                IV3MigratorCalls::SelfPermit(element) => element.encode(),
+                IV3MigratorCalls::SelfPermitAllowed(element) => element.encode(),
+                IV3MigratorCalls::SelfPermitAllowedIfNecessary(element) => element.encode(),
+                IV3MigratorCalls::SelfPermitIfNecessary(element) => element.encode(),
+            }
+        }
+    }
+    impl ::std::fmt::Display for IV3MigratorCalls {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                IV3MigratorCalls::CreateAndInitializePoolIfNecessary(element) => element.fmt(f),
+                IV3MigratorCalls::Migrate(element) => element.fmt(f),
+                IV3MigratorCalls::Multicall(element) => element.fmt(f),
+                IV3MigratorCalls::SelfPermit(element) => element.fmt(f),
+                IV3MigratorCalls::SelfPermitAllowed(element) => element.fmt(f),
+                IV3MigratorCalls::SelfPermitAllowedIfNecessary(element) => element.fmt(f),
+                IV3MigratorCalls::SelfPermitIfNecessary(element) => element.fmt(f),
+            }
+        }
+    }
+    impl ::std::convert::From<CreateAndInitializePoolIfNecessaryCall> for IV3MigratorCalls {
+        fn from(var: CreateAndInitializePoolIfNecessaryCall) -> Self {
+            IV3MigratorCalls::CreateAndInitializePoolIfNecessary(var)
+        }
+    }
+    impl ::std::convert::From<MigrateCall> for IV3MigratorCalls {
+        fn from(var: MigrateCall) -> Self {
+            IV3MigratorCalls::Migrate(var)
+        }
+    }
+    impl ::std::convert::From<MulticallCall> for IV3MigratorCalls {
+        fn from(var: MulticallCall) -> Self {
+            IV3MigratorCalls::Multicall(var)
+        }
+    }
+    impl ::std::convert::From<SelfPermitCall> for IV3MigratorCalls {
+        fn from(var: SelfPermitCall) -> Self {
+            IV3MigratorCalls::SelfPermit(var)
+        }
+    }
+    impl ::std::convert::From<SelfPermitAllowedCall> for IV3MigratorCalls {
+        fn from(var: SelfPermitAllowedCall) -> Self {
+            IV3MigratorCalls::SelfPermitAllowed(var)
+        }
+    }
+    impl ::std::convert::From<SelfPermitAllowedIfNecessaryCall> for IV3MigratorCalls {
+        fn from(var: SelfPermitAllowedIfNecessaryCall) -> Self {
+            IV3MigratorCalls::SelfPermitAllowedIfNecessary(var)
+        }
+    }
+    impl ::std::convert::From<SelfPermitIfNecessaryCall> for IV3MigratorCalls {
+        fn from(var: SelfPermitIfNecessaryCall) -> Self {
+            IV3MigratorCalls::SelfPermitIfNecessary(var)
+        }
+    }
+    #[doc = "Container type for all return fields from the `createAndInitializePoolIfNecessary` function with signature `createAndInitializePoolIfNecessary(address,address,uint24,uint160)` and selector `[19, 234, 213, 98]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct CreateAndInitializePoolIfNecessaryReturn {
+        pub pool: ethers_core::types::Address,
+    }
+    #[doc = "Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `[172, 150, 80, 216]`"]
+    #[derive(
+        Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+        Default,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<ethers_core::types::Bytes>,
+    }
+    #[doc = "`MigrateParams(address,uint256,uint8,address,address,uint24,int24,int24,uint256,uint256,address,uint256,bool)`"]
+    #[derive(
+        Clone,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        ethers_contract :: EthAbiType,
+        ethers_contract :: EthAbiCodec,
+    )]
+    pub struct MigrateParams {
+        pub pair: ethers_core::types::Address,
+        pub liquidity_to_migrate: ethers_core::types::U256,
+        pub percentage_to_migrate: u8,
+        pub token_0: ethers_core::types::Address,
+        pub token_1: ethers_core::types::Address,
+        pub fee: u32,
+        pub tick_lower: i32,
+        pub tick_upper: i32,
+        pub amount_0_min: ethers_core::types::U256,
+        pub amount_1_min: ethers_core::types::U256,
+        pub recipient: ethers_core::types::Address,
+        pub deadline: ethers_core::types::U256,
+        pub refund_as_eth: bool,
+    }
+}
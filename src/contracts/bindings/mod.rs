@@ -3,13 +3,45 @@
 //! This is autogenerated code.
 //! Do not manually edit these files.
 //! These files may be overwritten by the codegen system at any time.
+#[cfg(feature = "v3")]
+pub mod i_nonfungible_position_manager;
+#[cfg(feature = "permit2")]
+pub mod i_permit_2;
+#[cfg(feature = "v3")]
+pub mod i_quoter;
+#[cfg(feature = "v3")]
+pub mod i_quoter_v2;
+#[cfg(feature = "v2")]
+pub mod i_solidly_factory;
+#[cfg(feature = "v2")]
+pub mod i_solidly_router;
+#[cfg(feature = "v3")]
 pub mod i_swap_router;
+#[cfg(feature = "v3")]
+pub mod i_swap_router_02;
+#[cfg(feature = "v3")]
+pub mod i_tick_lens;
+#[cfg(feature = "v2")]
+pub mod i_uniswap_v2_callee;
+#[cfg(feature = "v2")]
 pub mod i_uniswap_v2_factory;
+#[cfg(feature = "v2")]
 pub mod i_uniswap_v2_pair;
+#[cfg(feature = "v2")]
 pub mod i_uniswap_v2_router_02;
+#[cfg(feature = "v3")]
 pub mod i_uniswap_v3_factory;
+#[cfg(feature = "v3")]
 pub mod i_uniswap_v3_pool;
+#[cfg(feature = "staker")]
+pub mod i_uniswap_v3_staker;
+#[cfg(feature = "universal-router")]
 pub mod i_universal_router;
+#[cfg(feature = "universal-router")]
 pub mod i_universal_router_commands;
 pub mod ierc20;
+#[cfg(feature = "v3")]
+pub mod iv3_migrator;
 pub mod iweth;
+#[cfg(feature = "permit2")]
+pub mod shared_types;
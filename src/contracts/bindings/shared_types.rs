@@ -0,0 +1,62 @@
+#[doc = "`AllowanceTransferDetails(address,address,uint160,address)`"]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    ethers_contract :: EthAbiType,
+    ethers_contract :: EthAbiCodec,
+)]
+pub struct AllowanceTransferDetails {
+    pub from: ethers_core::types::Address,
+    pub to: ethers_core::types::Address,
+    pub amount: ethers_core::types::U256,
+    pub token: ethers_core::types::Address,
+}
+#[doc = "`PermitBatch((address,uint160,uint48,uint48)[],address,uint256)`"]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    ethers_contract :: EthAbiType,
+    ethers_contract :: EthAbiCodec,
+)]
+pub struct PermitBatch {
+    pub details: ::std::vec::Vec<PermitDetails>,
+    pub spender: ethers_core::types::Address,
+    pub sig_deadline: ethers_core::types::U256,
+}
+#[doc = "`PermitDetails(address,uint160,uint48,uint48)`"]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    ethers_contract :: EthAbiType,
+    ethers_contract :: EthAbiCodec,
+)]
+pub struct PermitDetails {
+    pub token: ethers_core::types::Address,
+    pub amount: ethers_core::types::U256,
+    pub expiration: u64,
+    pub nonce: u64,
+}
+#[doc = "`PermitSingle((address,uint160,uint48,uint48),address,uint256)`"]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    ethers_contract :: EthAbiType,
+    ethers_contract :: EthAbiCodec,
+)]
+pub struct PermitSingle {
+    pub details: PermitDetails,
+    pub spender: ethers_core::types::Address,
+    pub sig_deadline: ethers_core::types::U256,
+}
@@ -12,14 +12,34 @@ pub mod addresses;
 pub mod bindings {
     #[path = "../bindings/mod.rs"]
     #[doc(hidden)]
+    #[allow(unknown_lints, mismatched_lifetime_syntaxes)]
     mod _bindings;
 
+    pub use _bindings::{ierc20, iweth};
+
+    #[cfg(feature = "v2")]
+    pub use _bindings::{
+        i_solidly_factory, i_solidly_router, i_uniswap_v2_callee, i_uniswap_v2_factory,
+        i_uniswap_v2_pair, i_uniswap_v2_router_02,
+    };
+
+    #[cfg(feature = "v3")]
     pub use _bindings::{
-        i_swap_router, i_uniswap_v2_factory, i_uniswap_v2_pair, i_uniswap_v2_router_02,
-        i_uniswap_v3_factory, i_uniswap_v3_pool, i_universal_router, ierc20, iweth,
+        i_nonfungible_position_manager, i_quoter, i_quoter_v2, i_swap_router, i_swap_router_02,
+        i_tick_lens, i_uniswap_v3_factory, i_uniswap_v3_pool, iv3_migrator,
     };
 
+    #[cfg(feature = "staker")]
+    pub use _bindings::i_uniswap_v3_staker;
+
+    #[cfg(feature = "permit2")]
+    pub use _bindings::{i_permit_2, shared_types};
+
+    #[cfg(feature = "universal-router")]
+    pub use _bindings::i_universal_router;
+
     // should not be used directly as it's not a valid contract
+    #[cfg(feature = "universal-router")]
     #[doc(hidden)]
     pub use _bindings::i_universal_router_commands;
 }
@@ -0,0 +1,477 @@
+//! Deploys the Uniswap V2 and V3 contracts on chains on which they are not deployed, like a
+//! private devnet or a bare [Anvil](https://github.com/foundry-rs/foundry) node.
+//!
+//! The contracts are deployed from their creation bytecode: the canonical one, embedded from the
+//! build artifacts of the `@uniswap/v2-core`, `@uniswap/v2-periphery`, `@uniswap/v3-core` and
+//! `@uniswap/v3-periphery` packages, with [`V2Bytecode::canonical`] and
+//! [`V3Bytecode::canonical`], or any other read from build artifacts with
+//! [`V2Bytecode::from_artifacts`] and [`V3Bytecode::from_artifacts`]. The pair and pool code
+//! hashes are computed from the bytecode and set on the returned factories, so that the addresses
+//! of the pairs and pools are computed correctly even if the bytecode is not the canonical one.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use uniswap_rs::{deploy::{self, V2Bytecode}, ethers_providers::Middleware};
+//! # async fn foo<M: Middleware + 'static>(client: Arc<M>) -> uniswap_rs::errors::Result<()> {
+//! let bytecode = V2Bytecode::canonical()?;
+//! let deployment = deploy::v2(client, &bytecode).await?;
+//! let protocol = deployment.protocol();
+//! # Ok(()) }
+//! ```
+//!
+//! Note: the V2 router and the V3 periphery compute the addresses of the pairs and pools with the
+//! canonical code hashes, so the factories' bytecode must be the canonical one for swaps through
+//! the routers to work.
+
+use crate::{
+    errors::{Error, Result},
+    pair_code_hashes::{UNISWAP_V2_PAIR_CODE_HASH, UNISWAP_V3_POOL_CODE_HASH},
+};
+use ethers_core::{
+    types::{Bytes, H256},
+    utils::keccak256,
+};
+use std::{fs, path::Path};
+
+#[cfg(feature = "v3")]
+use crate::v3;
+#[cfg(feature = "v2")]
+use crate::{v2, CustomProtocol, Protocol};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_contract::ContractError;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::{
+    abi::{self, Token},
+    types::{Address, TransactionRequest},
+};
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_providers::Middleware;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use std::sync::Arc;
+
+/// The name of WETH9's artifact.
+const WETH9: &str = "WETH9";
+
+/// The canonical creation bytecode, vendored from the Uniswap npm packages by
+/// `scripts/vendor_bytecode.js`.
+mod canonical {
+    pub(super) const WETH9: &str = include_str!("../bytecode/WETH9.hex");
+    pub(super) const V2_FACTORY: &str = include_str!("../bytecode/UniswapV2Factory.hex");
+    pub(super) const V2_ROUTER: &str = include_str!("../bytecode/UniswapV2Router02.hex");
+    pub(super) const V2_PAIR: &str = include_str!("../bytecode/UniswapV2Pair.hex");
+    pub(super) const V3_FACTORY: &str = include_str!("../bytecode/UniswapV3Factory.hex");
+    pub(super) const V3_ROUTER: &str = include_str!("../bytecode/SwapRouter.hex");
+    pub(super) const V3_POSITION_MANAGER: &str =
+        include_str!("../bytecode/NonfungiblePositionManager.hex");
+    pub(super) const V3_QUOTER: &str = include_str!("../bytecode/Quoter.hex");
+    pub(super) const V3_POOL: &str = include_str!("../bytecode/UniswapV3Pool.hex");
+}
+
+/// The creation bytecode of the Uniswap V2 contracts, deployed with `deploy::v2`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct V2Bytecode {
+    /// The creation bytecode of WETH9.
+    pub weth: Bytes,
+    /// The creation bytecode of `UniswapV2Factory`.
+    pub factory: Bytes,
+    /// The creation bytecode of `UniswapV2Router02`.
+    pub router: Bytes,
+    /// The creation bytecode of `UniswapV2Pair`, whose hash is the pair code hash.
+    pub pair: Bytes,
+}
+
+impl V2Bytecode {
+    /// Returns the embedded bytecode of the canonical WETH9 and Uniswap V2 contracts, whose pair
+    /// code hash is the one of Uniswap V2 on mainnet.
+    ///
+    /// Returns [`Error::InvalidArtifact`] if the bytecode was not vendored, or does not hash to
+    /// the canonical pair code hash.
+    pub fn canonical() -> Result<Self> {
+        let bytecode = Self {
+            weth: embedded(WETH9, canonical::WETH9)?,
+            factory: embedded("UniswapV2Factory", canonical::V2_FACTORY)?,
+            router: embedded("UniswapV2Router02", canonical::V2_ROUTER)?,
+            pair: embedded("UniswapV2Pair", canonical::V2_PAIR)?,
+        };
+        if bytecode.pair_code_hash() != UNISWAP_V2_PAIR_CODE_HASH {
+            return Err(not_canonical("UniswapV2Pair"));
+        }
+        Ok(bytecode)
+    }
+
+    /// Reads the bytecode from the `WETH9`, `UniswapV2Factory`, `UniswapV2Router02` and
+    /// `UniswapV2Pair` JSON artifacts in `dir`. See [`read_artifact`] for the supported formats.
+    pub fn from_artifacts(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            weth: read_artifact(dir, WETH9)?,
+            factory: read_artifact(dir, "UniswapV2Factory")?,
+            router: read_artifact(dir, "UniswapV2Router02")?,
+            pair: read_artifact(dir, "UniswapV2Pair")?,
+        })
+    }
+
+    /// Returns the hash of the pair's creation bytecode.
+    pub fn pair_code_hash(&self) -> H256 {
+        H256(keccak256(&self.pair))
+    }
+}
+
+/// The creation bytecode of the Uniswap V3 contracts, deployed with `deploy::v3`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct V3Bytecode {
+    /// The creation bytecode of WETH9.
+    pub weth: Bytes,
+    /// The creation bytecode of `UniswapV3Factory`.
+    pub factory: Bytes,
+    /// The creation bytecode of `SwapRouter`.
+    pub router: Bytes,
+    /// The creation bytecode of `NonfungiblePositionManager`.
+    pub position_manager: Bytes,
+    /// The creation bytecode of `Quoter`.
+    pub quoter: Bytes,
+    /// The creation bytecode of `UniswapV3Pool`, whose hash is the pool code hash.
+    pub pool: Bytes,
+}
+
+impl V3Bytecode {
+    /// Returns the embedded bytecode of the canonical WETH9 and Uniswap V3 contracts, whose pool
+    /// code hash is the one of Uniswap V3 on mainnet.
+    ///
+    /// Returns [`Error::InvalidArtifact`] if the bytecode was not vendored, or does not hash to
+    /// the canonical pool code hash.
+    pub fn canonical() -> Result<Self> {
+        let bytecode = Self {
+            weth: embedded(WETH9, canonical::WETH9)?,
+            factory: embedded("UniswapV3Factory", canonical::V3_FACTORY)?,
+            router: embedded("SwapRouter", canonical::V3_ROUTER)?,
+            position_manager: embedded(
+                "NonfungiblePositionManager",
+                canonical::V3_POSITION_MANAGER,
+            )?,
+            quoter: embedded("Quoter", canonical::V3_QUOTER)?,
+            pool: embedded("UniswapV3Pool", canonical::V3_POOL)?,
+        };
+        if bytecode.pool_code_hash() != UNISWAP_V3_POOL_CODE_HASH {
+            return Err(not_canonical("UniswapV3Pool"));
+        }
+        Ok(bytecode)
+    }
+
+    /// Reads the bytecode from the `WETH9`, `UniswapV3Factory`, `SwapRouter`,
+    /// `NonfungiblePositionManager`, `Quoter` and `UniswapV3Pool` JSON artifacts in `dir`. See
+    /// [`read_artifact`] for the supported formats.
+    pub fn from_artifacts(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            weth: read_artifact(dir, WETH9)?,
+            factory: read_artifact(dir, "UniswapV3Factory")?,
+            router: read_artifact(dir, "SwapRouter")?,
+            position_manager: read_artifact(dir, "NonfungiblePositionManager")?,
+            quoter: read_artifact(dir, "Quoter")?,
+            pool: read_artifact(dir, "UniswapV3Pool")?,
+        })
+    }
+
+    /// Returns the hash of the pool's creation bytecode.
+    pub fn pool_code_hash(&self) -> H256 {
+        H256(keccak256(&self.pool))
+    }
+}
+
+/// Uniswap V2 contracts deployed with [`v2()`].
+#[cfg(feature = "v2")]
+#[derive(Debug)]
+pub struct V2Deployment<M> {
+    /// The factory, whose fee setter is the deployer and whose pair code hash is set.
+    pub factory: v2::Factory<M>,
+    /// The router.
+    pub router: v2::Router<M>,
+    /// The address of the deployed WETH9.
+    pub weth: Address,
+}
+
+#[cfg(feature = "v2")]
+impl<M: Middleware> V2Deployment<M> {
+    /// Returns the deployment as a [custom protocol][CustomProtocol].
+    pub fn custom_protocol(&self) -> CustomProtocol {
+        let pair_code_hash = self.factory.pair_code_hash(None);
+        CustomProtocol::new(self.factory.address(), self.router.address(), true, pair_code_hash)
+    }
+
+    /// Returns the protocol of the deployment.
+    pub fn protocol(&self) -> Protocol<M> {
+        Protocol::new_custom(self.factory.client(), self.custom_protocol())
+    }
+}
+
+/// Uniswap V3 contracts deployed with [`v3()`].
+#[cfg(feature = "v3")]
+#[derive(Debug)]
+pub struct V3Deployment<M> {
+    /// The factory, whose owner is the deployer and whose pool code hash is set.
+    pub factory: v3::Factory<M>,
+    /// The `SwapRouter`.
+    pub router: v3::Router<M>,
+    /// The position manager, without a token descriptor.
+    pub position_manager: v3::PositionManager<M>,
+    /// The `QuoterV1`.
+    pub quoter: v3::Quoter<M>,
+    /// The address of the deployed WETH9.
+    pub weth: Address,
+}
+
+#[cfg(feature = "v3")]
+impl<M: Middleware> V3Deployment<M> {
+    /// Returns the protocol of the deployment.
+    pub fn protocol(&self) -> v3::Protocol<M> {
+        let mut protocol = v3::Protocol::new(
+            self.factory.client(),
+            self.factory.address(),
+            self.router.address(),
+            self.quoter.address(),
+        );
+        protocol.set_factory(self.factory.clone());
+        protocol
+    }
+}
+
+/// Deploys WETH9 and a Uniswap V2 factory and router from `bytecode`, with the client's default
+/// sender as the factory's fee setter.
+#[cfg(feature = "v2")]
+pub async fn v2<M: Middleware>(client: Arc<M>, bytecode: &V2Bytecode) -> Result<V2Deployment<M>> {
+    let me = client.default_sender().ok_or(Error::MissingSender)?;
+    let weth = deploy(client.as_ref(), &bytecode.weth, &[]).await?;
+    let factory = deploy(client.as_ref(), &bytecode.factory, &[Token::Address(me)]).await?;
+    let args = [Token::Address(factory), Token::Address(weth)];
+    let router = deploy(client.as_ref(), &bytecode.router, &args).await?;
+
+    let factory = v2::Factory::new(client.clone(), factory, crate::ProtocolType::UniswapV2)
+        .with_pair_code_hash(bytecode.pair_code_hash());
+    let router = v2::Router::new(client, router);
+    Ok(V2Deployment { factory, router, weth })
+}
+
+/// Deploys WETH9 and a Uniswap V3 factory, `SwapRouter`, `NonfungiblePositionManager` and
+/// `QuoterV1` from `bytecode`, with the client's default sender as the factory's owner.
+///
+/// The position manager is deployed without a token descriptor, so its `tokenURI` method reverts.
+#[cfg(feature = "v3")]
+pub async fn v3<M: Middleware>(client: Arc<M>, bytecode: &V3Bytecode) -> Result<V3Deployment<M>> {
+    let weth = deploy(client.as_ref(), &bytecode.weth, &[]).await?;
+    let factory = deploy(client.as_ref(), &bytecode.factory, &[]).await?;
+    let args = [Token::Address(factory), Token::Address(weth)];
+    let router = deploy(client.as_ref(), &bytecode.router, &args).await?;
+    let quoter = deploy(client.as_ref(), &bytecode.quoter, &args).await?;
+    let args = [Token::Address(factory), Token::Address(weth), Token::Address(Address::zero())];
+    let position_manager = deploy(client.as_ref(), &bytecode.position_manager, &args).await?;
+
+    let mut factory = v3::Factory::new(client.clone(), factory, crate::ProtocolType::UniswapV3);
+    factory.set_pool_code_hash(bytecode.pool_code_hash());
+    let mut position_manager =
+        v3::PositionManager::new(client.clone(), position_manager, factory.address(), weth);
+    position_manager.set_factory(factory.clone());
+    let router = v3::Router::new(client.clone(), router);
+    let quoter = v3::Quoter::new(client, quoter);
+    Ok(V3Deployment { factory, router, position_manager, quoter, weth })
+}
+
+/// Reads the creation bytecode from the JSON artifact `{name}.json` in `dir`.
+///
+/// The bytecode is read from the `bytecode` string of Hardhat and Waffle artifacts, like those
+/// published in the Uniswap npm packages, or from the `bytecode.object` or `evm.bytecode.object`
+/// string of Foundry and `solc` artifacts.
+pub fn read_artifact(dir: &Path, name: &str) -> Result<Bytes> {
+    let path = dir.join(name).with_extension("json");
+    let invalid = |reason: String| Error::InvalidArtifact(format!("{}: {reason}", path.display()));
+    let json = fs::read_to_string(&path).map_err(|e| invalid(e.to_string()))?;
+    let artifact: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| invalid(e.to_string()))?;
+    let bytecode = artifact["bytecode"]
+        .as_str()
+        .or_else(|| artifact["bytecode"]["object"].as_str())
+        .or_else(|| artifact["evm"]["bytecode"]["object"].as_str())
+        .ok_or_else(|| invalid("missing bytecode".to_string()))?;
+    if bytecode.contains("__") {
+        return Err(invalid("bytecode has unlinked libraries".to_string()));
+    }
+    bytecode.parse::<Bytes>().map_err(|e| invalid(e.to_string()))
+}
+
+/// Parses the embedded, hex-encoded creation bytecode of `name`.
+fn embedded(name: &str, hex: &str) -> Result<Bytes> {
+    let hex = hex.trim();
+    if hex.is_empty() {
+        let reason = "the canonical bytecode is not vendored, see `scripts/vendor_bytecode.js`";
+        return Err(Error::InvalidArtifact(format!("{name}: {reason}")));
+    }
+    hex.parse().map_err(|e| Error::InvalidArtifact(format!("{name}: {e}")))
+}
+
+fn not_canonical(name: &str) -> Error {
+    Error::InvalidArtifact(format!("{name}: the bytecode does not hash to the canonical code hash"))
+}
+
+/// Deploys `bytecode` with the ABI-encoded constructor `args` from the client's default sender,
+/// returning the address of the contract.
+#[cfg(any(feature = "v2", feature = "v3"))]
+async fn deploy<M: Middleware>(client: &M, bytecode: &Bytes, args: &[Token]) -> Result<Address> {
+    let from = client.default_sender().ok_or(Error::MissingSender)?;
+    let mut data = bytecode.to_vec();
+    data.extend(abi::encode(args));
+    let tx = TransactionRequest::new().from(from).data(data);
+    let pending =
+        client.send_transaction(tx, None).await.map_err(ContractError::<M>::MiddlewareError)?;
+    let tx_hash = *pending;
+    let receipt = pending.await?.ok_or(Error::TransactionDropped(tx_hash))?;
+    if receipt.status == Some(0.into()) {
+        return Err(Error::ContractError(format!("Deployment {tx_hash:?} reverted")));
+    }
+    receipt.contract_address.ok_or(Error::TransactionDropped(tx_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_artifact(dir: &Path, name: &str, json: &str) {
+        fs::write(dir.join(name).with_extension("json"), json).unwrap();
+    }
+
+    #[test]
+    fn reads_artifact_formats() {
+        let dir = std::env::temp_dir().join(format!("uniswap-rs-artifacts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write_artifact(&dir, "Hardhat", r#"{"bytecode":"0x6080"}"#);
+        write_artifact(&dir, "Waffle", r#"{"bytecode":"6080"}"#);
+        write_artifact(&dir, "Foundry", r#"{"bytecode":{"object":"0x6080"}}"#);
+        write_artifact(&dir, "Solc", r#"{"evm":{"bytecode":{"object":"6080"}}}"#);
+        write_artifact(&dir, "Linked", r#"{"bytecode":"0x6080__$abcd$__"}"#);
+        write_artifact(&dir, "Abi", r#"{"abi":[]}"#);
+
+        for name in ["Hardhat", "Waffle", "Foundry", "Solc"] {
+            assert_eq!(read_artifact(&dir, name).unwrap(), Bytes::from(vec![0x60, 0x80]), "{name}");
+        }
+        for name in ["Linked", "Abi", "Missing"] {
+            assert!(matches!(read_artifact(&dir, name), Err(Error::InvalidArtifact(_))), "{name}");
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn embedded_bytecode() {
+        assert_eq!(embedded("Hex", "0x6080\n").unwrap(), Bytes::from(vec![0x60, 0x80]));
+        for hex in ["", "\n", "0xzz"] {
+            assert!(matches!(embedded("Hex", hex), Err(Error::InvalidArtifact(_))), "{hex:?}");
+        }
+
+        // either vendored with the canonical code hashes, or not vendored at all
+        match V2Bytecode::canonical() {
+            Ok(bytecode) => assert_eq!(bytecode.pair_code_hash(), UNISWAP_V2_PAIR_CODE_HASH),
+            Err(e) => assert!(e.to_string().contains("not vendored"), "{e}"),
+        }
+        match V3Bytecode::canonical() {
+            Ok(bytecode) => assert_eq!(bytecode.pool_code_hash(), UNISWAP_V3_POOL_CODE_HASH),
+            Err(e) => assert!(e.to_string().contains("not vendored"), "{e}"),
+        }
+    }
+
+    #[test]
+    fn code_hashes() {
+        let bytecode = V2Bytecode { pair: vec![0x60, 0x80].into(), ..Default::default() };
+        assert_eq!(bytecode.pair_code_hash(), H256(keccak256([0x60, 0x80])));
+        let bytecode = V3Bytecode { pool: vec![0x60, 0x80].into(), ..Default::default() };
+        assert_eq!(bytecode.pool_code_hash(), H256(keccak256([0x60, 0x80])));
+    }
+
+    #[cfg(feature = "v2")]
+    #[tokio::test]
+    #[ignore = "needs anvil"]
+    async fn v2_deployment_swaps() {
+        use crate::{
+            contracts::bindings::iweth::IWETH, testing::Fork, Amount, SwapOptions, NATIVE_ADDRESS,
+        };
+        use ethers_core::types::U256;
+
+        let bytecode = V2Bytecode::canonical().unwrap();
+        let fork = Fork::spawn(None).unwrap();
+        let (client, me) = (fork.client(), fork.address());
+        let deployment = fork.deploy_v2(&bytecode).await.unwrap();
+        let (factory, router) = (&deployment.factory, &deployment.router);
+
+        // another WETH9 is an ERC20 which can be minted with ether
+        let token = deploy(client.as_ref(), &bytecode.weth, &[]).await.unwrap();
+        let token_contract = IWETH::new(token, client.clone());
+        let amount = U256::exp10(20);
+        token_contract.deposit().value(amount).send().await.unwrap().await.unwrap();
+        token_contract.approve(router.address(), amount).send().await.unwrap().await.unwrap();
+
+        let deadline = U256::MAX;
+        let call = router
+            .add_liquidity(NATIVE_ADDRESS, token, amount, amount, 0.into(), 0.into(), me, deadline)
+            .await
+            .unwrap();
+        call.send().await.unwrap().await.unwrap();
+
+        // checks the address of the pair against the pair code hash
+        factory.get_pair_checked(deployment.weth, token).await.unwrap();
+
+        let balance = token_contract.balance_of(me).call().await.unwrap();
+        let path = [NATIVE_ADDRESS, token];
+        let amount = Amount::ExactInNative(U256::exp10(18));
+        let call = router
+            .swap(factory, amount, 1.0, &path, me, deadline, deployment.weth, SwapOptions::new())
+            .await
+            .unwrap();
+        call.send().await.unwrap().await.unwrap();
+        assert!(token_contract.balance_of(me).call().await.unwrap() > balance);
+    }
+
+    #[cfg(feature = "v3")]
+    #[tokio::test]
+    #[ignore = "needs anvil"]
+    async fn v3_deployment_quotes() {
+        use crate::{
+            contracts::bindings::{i_uniswap_v3_pool::IUniswapV3Pool, iweth::IWETH},
+            testing::Fork,
+            v3::{math::Q96, FeeAmount, MintParams},
+        };
+        use ethers_core::types::U256;
+
+        let bytecode = V3Bytecode::canonical().unwrap();
+        let fork = Fork::spawn(None).unwrap();
+        let client = fork.client();
+        let deployment = fork.deploy_v3(&bytecode).await.unwrap();
+        let (factory, manager) = (&deployment.factory, &deployment.position_manager);
+
+        // two more WETH9s are ERC20s which can be minted with ether
+        let amount = U256::exp10(20);
+        let mut tokens = vec![];
+        for _ in 0..2 {
+            let token = deploy(client.as_ref(), &bytecode.weth, &[]).await.unwrap();
+            let contract = IWETH::new(token, client.clone());
+            contract.deposit().value(amount).send().await.unwrap().await.unwrap();
+            contract.approve(manager.address(), amount).send().await.unwrap().await.unwrap();
+            tokens.push(token);
+        }
+        let (token_a, token_b, fee) = (tokens[0], tokens[1], FeeAmount::Medium);
+
+        factory.create_pool(token_a, token_b, fee).send().await.unwrap().await.unwrap();
+        // checks the address of the pool against the pool code hash
+        let pool = factory.get_pool(token_a, token_b, fee).call().await.unwrap();
+        assert_eq!(pool, factory.pool_address(token_a, token_b, fee).unwrap());
+        let pool = IUniswapV3Pool::new(pool, client.clone());
+        pool.initialize(Q96).send().await.unwrap().await.unwrap();
+
+        let params = MintParams::new(token_a, token_b, fee, -600, 600, amount, amount);
+        manager.mint(params).await.unwrap().send().await.unwrap().await.unwrap();
+
+        let amount_in = U256::exp10(18);
+        let quote = deployment
+            .quoter
+            .quote_exact_input_single(token_a, token_b, fee, amount_in, U256::zero())
+            .await
+            .unwrap();
+        assert!(!quote.amount.is_zero() && quote.amount < amount_in);
+    }
+}
@@ -1,6 +1,79 @@
 //! # uniswap-rs
 //!
 //! Unofficial Rust SDK library for Uniswap smart contracts.
+//!
+//! The frequently used types and traits can be imported with [`prelude`]:
+//!
+//! ```
+//! use uniswap_rs::prelude::*;
+//! ```
+//!
+//! The `ethers` crates used in the public API are re-exported, so that their types can be named
+//! without depending on them directly, like [`ethers_core::types::TransactionReceipt`].
+//!
+//! The contract bindings, and the modules built on them, are split behind features:
+//!
+//! - `v2` (default): Uniswap V2 and its forks.
+//! - `v3` (default): Uniswap V3. `v3::Migrator` also requires `v2`.
+//! - `universal-router`: the Universal Router, enables `v2`, `v3` and `permit2`.
+//! - `permit2`: Permit2.
+//! - `staker`: the Uniswap V3 staker, enables `v3`.
+//! - `blocking`: synchronous wrappers of the protocols in the `blocking` module, enables `v2`.
+//! - `deploy`: deploying the Uniswap contracts from their bytecode in the `deploy` module.
+//! - `test-utils`: the Anvil-backed test harness in the `testing` module, enables `deploy`.
+//!
+//! The modules which combine both protocols, like `routing` and `analytics`, require both `v2`
+//! and `v3`.
+//!
+//! ## WASM
+//!
+//! The crate builds for `wasm32-unknown-unknown` without the default features, which enable the
+//! `rustls` and `ws` features of `ethers-providers`, and with the `wasm` feature, which reads the
+//! time from JavaScript's `Date.now()` (see [SystemClock]) and drives timers with the browser's or
+//! Node's `setTimeout`:
+//!
+//! ```sh
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features v2,wasm
+//! ```
+//!
+//! ## Tracing
+//!
+//! With the `tracing` feature, the routers' `swap` methods, the V2 reserve fetches and route
+//! search, the V3 quoter and the [Multicall][utils::Multicall] requests are instrumented with
+//! [`tracing`](https://docs.rs/tracing) spans and events. The addresses, like the path of a swap,
+//! are recorded at the `INFO` level, and the amounts, like the fetched reserves and the minimum
+//! output computed from them, at the `DEBUG` level. Without the feature, nothing is recorded.
+//!
+//! ```
+//! # use uniswap_rs::prelude::{*, _ethers::*};
+//! # use std::sync::Arc;
+//! use tracing_subscriber::filter::LevelFilter;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! tracing_subscriber::fmt().with_max_level(LevelFilter::DEBUG).init();
+//!
+//! let (provider, mock) = Provider::mocked();
+//! let client = Arc::new(provider);
+//! let protocol = ProtocolType::UniswapV2;
+//! let factory = V2Factory::new(client.clone(), Address::repeat_byte(0x11), protocol);
+//! let router = V2Router::new(client, Address::repeat_byte(0x22));
+//! let (token_a, token_b) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+//! let (recipient, weth) = (Address::repeat_byte(0xbb), Address::repeat_byte(0xaa));
+//!
+//! // the reserves of the pair
+//! let reserves = [U256::exp10(21), U256::exp10(21), U256::zero()];
+//! mock.push::<Bytes, _>(Bytes::from(abi::encode(&reserves.map(abi::Token::Uint))))?;
+//!
+//! // records the path, the reserves, the minimum output and the selector of the router's method
+//! let amount = Amount::ExactIn(U256::exp10(18));
+//! let path = [token_a, token_b];
+//! let call = router
+//!     .swap(&factory, amount, 0.5, &path, recipient, U256::MAX, weth, SwapOptions::new())
+//!     .await?;
+//! # let _ = call;
+//! # Ok(()) }
+//! ```
 
 #![allow(clippy::too_many_arguments)]
 #![warn(missing_docs, unreachable_pub)]
@@ -11,38 +84,98 @@
 mod macros;
 
 mod common;
+#[cfg(feature = "v2")]
 mod dex;
 mod protocol;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod contracts;
+#[cfg(feature = "deploy")]
+pub mod deploy;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+#[cfg(feature = "universal-router")]
 pub mod universal_router;
+#[cfg(feature = "v2")]
 pub mod v2;
+#[cfg(feature = "v3")]
 pub mod v3;
 
-pub use common::{constants, errors, utils, Amount, CallExt, CallResult, Erc20};
-pub use constants::NATIVE_ADDRESS;
+#[cfg(feature = "v2")]
+pub use common::arb;
+#[cfg(feature = "mev")]
+pub use common::mev;
+#[cfg(feature = "permit2")]
+pub use common::permit2;
+#[cfg(all(feature = "v2", feature = "v3"))]
+pub use common::{analytics, compare, decode, routing};
+pub use common::{
+    constants, errors, gas, permit, tokens, utils, Amount, CallBundle, CallExt, CallResult, Clock,
+    Deadline, DecodedLog, Erc20, LiquidityOptions, Permit, PriceLimit, QuoteCache, Recipient,
+    RequestPolicy, Route, SimulateOptions, Simulation, Slippage, SwapOptions, SwapReceipt,
+    SystemClock, TxOptions,
+};
+#[cfg(all(feature = "addresses", feature = "v2", feature = "v3"))]
+pub use common::{mempool, multichain};
+pub use constants::{NATIVE, NATIVE_ADDRESS};
+pub use contracts::bindings;
+#[cfg(feature = "v2")]
 pub use dex::Dex;
-pub use protocol::{pair_code_hashes, Protocol, ProtocolType};
+pub use protocol::{pair_code_hashes, verify, CustomProtocol, ProtocolType};
+#[cfg(feature = "v2")]
+pub use protocol::{Protocol, SwapBuilder, DEFAULT_SWAP_DEADLINE};
+
+#[doc(no_inline)]
+pub use ethers_contract;
+#[doc(no_inline)]
+pub use ethers_core;
+#[doc(no_inline)]
+pub use ethers_providers;
+#[doc(no_inline)]
+pub use ethers_signers;
 
 /// Easy imports of frequently used type definitions and traits.
-#[doc(hidden)]
+///
+/// The factories, routers and protocols of Uniswap V2 and V3 are prefixed with their version, like
+/// `V2Factory` and `V3Router`.
 pub mod prelude {
     pub use super::{
-        common::{Amount, CallExt, CallResult, Erc20},
-        constants::NATIVE_ADDRESS,
+        common::{
+            Amount, CallBundle, CallExt, CallResult, Deadline, Erc20, Permit, PriceLimit,
+            QuoteCache, Recipient, Route, SimulateOptions, Simulation, Slippage, SwapOptions,
+            SwapReceipt, TxOptions,
+        },
+        constants::{NATIVE, NATIVE_ADDRESS},
+        errors::{Error, Result},
+        protocol::{CustomProtocol, ProtocolType},
+    };
+
+    #[cfg(feature = "universal-router")]
+    pub use super::universal_router::{Command, UniversalRouter};
+    #[cfg(feature = "v3")]
+    pub use super::v3::{
+        Factory as V3Factory, FeeAmount, Pool as V3Pool, Protocol as V3Protocol,
+        Quoter as V3Quoter, Router as V3Router,
+    };
+    #[cfg(feature = "v2")]
+    pub use super::{
         dex::Dex,
-        protocol::{Protocol, ProtocolType},
-        universal_router::{Command, UniversalRouter},
+        protocol::{Protocol, SwapBuilder},
         v2::{
             Factory as V2Factory, Library as V2Library, Pair as V2Pair, Protocol as V2Protocol,
             Router as V2Router,
         },
-        v3::{Factory as V3Factory, FeeAmount, Pool as V3Pool},
     };
 
     #[cfg(feature = "addresses")]
     pub use super::contracts::addresses::{address, contract, try_address, try_contract};
 
+    #[doc(no_inline)]
+    pub use ethers_core::types::{Address, Chain, U256};
+    #[doc(no_inline)]
+    pub use ethers_providers::Middleware;
+
     // convenience re-export of all the imported ethers_* as one module.
     #[doc(hidden)]
     pub mod _ethers {
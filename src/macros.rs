@@ -66,3 +66,22 @@ macro_rules! contract_struct {
         }
     };
 }
+
+/// Emits a `tracing` event at the `DEBUG` level with the `tracing` feature, otherwise does
+/// nothing.
+macro_rules! debug {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($args)*);
+    };
+}
+
+/// Emits a `tracing` event at the `INFO` level with the `tracing` feature, otherwise does
+/// nothing.
+#[allow(unused_macros)]
+macro_rules! info {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::info!($($args)*);
+    };
+}
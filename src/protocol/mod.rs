@@ -1,66 +1,224 @@
 pub mod pair_code_hashes;
+pub mod verify;
 
 mod protocol_type;
 pub use protocol_type::*;
 
+#[cfg(feature = "v2")]
+mod swap_builder;
+#[cfg(feature = "v2")]
+pub use swap_builder::*;
+
+#[cfg(feature = "v2")]
 use crate::{
+    common::{
+        execute_swap, gas,
+        gas::{AccessListEstimate, GasEstimate},
+    },
     errors::Result,
-    v2::{Pair as V2Pair, Protocol as V2Protocol},
-    Amount,
+    v2::{LiquidityPosition, Pair as V2Pair, Protocol as V2Protocol},
+    Amount, Deadline, Recipient, RequestPolicy, Route, SimulateOptions, Simulation, Slippage,
+    SwapOptions, SwapReceipt,
 };
+#[cfg(feature = "v2")]
 use ethers_contract::builders::ContractCall;
-use ethers_core::types::{Address, Chain, H256, U256};
+#[cfg(feature = "v2")]
+use ethers_core::{
+    abi::Detokenize,
+    types::{Address, Chain, H256, U256},
+};
+#[cfg(feature = "v2")]
 use ethers_providers::Middleware;
+#[cfg(feature = "v2")]
 use std::{fmt, sync::Arc};
+#[cfg(feature = "v2")]
+use verify::VerificationReport;
+
+#[cfg(all(feature = "addresses", feature = "v2"))]
+use crate::contracts::addresses::AddressBook;
+#[cfg(all(feature = "v2", feature = "v3"))]
+use crate::errors::Error;
+#[cfg(all(feature = "v2", feature = "v3"))]
+use crate::v3::Protocol as V3Protocol;
 
 /// An Uniswap V2 or V3 protocol.
 ///
-/// For Universal Router, see [UniversalRouter][crate::universal_router::UniversalRouter].
+/// For Universal Router, see `universal_router::UniversalRouter`.
+#[cfg(feature = "v2")]
 pub enum Protocol<M> {
     /// A Uniswap V2 protocol.
     V2(V2Protocol<M>),
 
     /// A Uniswap V3 protocol. Work in progress.
-    V3,
+    #[cfg(feature = "v3")]
+    V3(V3Protocol<M>),
 }
 
+#[cfg(feature = "v2")]
 impl<M> Clone for Protocol<M> {
     fn clone(&self) -> Self {
         match self {
             Self::V2(v2) => Self::V2(v2.clone()),
-            Self::V3 => Self::V3,
+            #[cfg(feature = "v3")]
+            Self::V3(v3) => Self::V3(v3.clone()),
         }
     }
 }
 
+#[cfg(feature = "v2")]
 impl<M> fmt::Debug for Protocol<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::V2(v2) => f.debug_tuple("V2").field(&v2).finish(),
-            Self::V3 => f.pad("V3"),
+            #[cfg(feature = "v3")]
+            Self::V3(v3) => f.debug_tuple("V3").field(&v3).finish(),
         }
     }
 }
 
+#[cfg(feature = "v2")]
 impl<M: Middleware> Protocol<M> {
     /// Creates a new instance using the provided client, factory and router addresses.
+    ///
+    /// V3 protocols use the canonical `QuoterV1` at `v3::QUOTER_ADDRESS`, which can be replaced
+    /// with `v3::Protocol::set_quoter`.
+    ///
+    /// # Panics
+    ///
+    /// If `protocol` is a V3 protocol and the `v3` feature is not enabled.
     pub fn new(client: Arc<M>, factory: Address, router: Address, protocol: ProtocolType) -> Self {
         match protocol {
             p if p.is_v2() => Self::V2(V2Protocol::new(client, factory, router, protocol)),
-            p if p.is_v3() => todo_v3(),
+            #[cfg(feature = "v3")]
+            p if p.is_v3() => {
+                let mut v3 =
+                    V3Protocol::new(client.clone(), factory, router, crate::v3::QUOTER_ADDRESS);
+                v3.set_factory(crate::v3::Factory::new(client, factory, p));
+                Self::V3(v3)
+            }
+            #[cfg(not(feature = "v3"))]
+            p if p.is_v3() => panic!("{p} requires the `v3` feature"),
             _ => unreachable!(),
         }
     }
 
-    /// Creates a new instance by searching for the required addresses in the [addressbook].
+    /// Creates a new instance of a [custom protocol][ProtocolType::Custom], using its factory and
+    /// router addresses.
+    pub fn new_custom(client: Arc<M>, protocol: CustomProtocol) -> Self {
+        Self::new(client, protocol.factory, protocol.router, protocol.into())
+    }
+
+    /// Creates a new instance by searching for the required addresses in `address_book`, or in
+    /// the process-global [addressbook] if None.
     ///
     /// [addressbook]: crate::contracts::addresses
     #[cfg(feature = "addresses")]
-    pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
+    pub fn new_with_chain(
+        client: Arc<M>,
+        chain: Chain,
+        protocol: ProtocolType,
+        address_book: Option<&AddressBook>,
+    ) -> Option<Self> {
         match protocol {
-            p if p.is_v2() => V2Protocol::new_with_chain(client, chain, protocol).map(Self::V2),
-            p if p.is_v3() => todo_v3(),
-            _ => unreachable!(),
+            p if p.is_v2() => {
+                V2Protocol::new_with_chain(client, chain, protocol, address_book).map(Self::V2)
+            }
+            #[cfg(feature = "v3")]
+            ProtocolType::UniswapV3 => {
+                V3Protocol::new_with_chain(client, chain, address_book).map(Self::V3)
+            }
+            // other forks have no canonical quoter, so their entry must contain one
+            #[cfg(feature = "v3")]
+            p if p.is_v3() => {
+                let entry = match address_book {
+                    Some(book) => book.get(p, chain).cloned(),
+                    None => crate::contracts::addresses::with_registry(|book| {
+                        book.get(p, chain).cloned()
+                    }),
+                }?;
+                let mut v3 = V3Protocol::new(
+                    client.clone(),
+                    entry.factory,
+                    entry.router,
+                    entry.extra("quoter")?,
+                );
+                let mut factory = crate::v3::Factory::new(client, entry.factory, p);
+                factory.set_chain(chain);
+                v3.set_factory(factory);
+                Some(Self::V3(v3))
+            }
+            _ => None,
+        }
+    }
+
+    /// Creates a new instance like [`new_with_chain`][Self::new_with_chain], then
+    /// [verifies][Self::verify] its addresses against the chain.
+    ///
+    /// Returns None if the addresses are not found, and [`Error::VerificationFailed`] with the
+    /// report if any check fails.
+    ///
+    /// [`Error::VerificationFailed`]: crate::errors::Error::VerificationFailed
+    #[cfg(feature = "addresses")]
+    pub async fn new_with_chain_verified(
+        client: Arc<M>,
+        chain: Chain,
+        protocol: ProtocolType,
+        address_book: Option<&AddressBook>,
+    ) -> Result<Option<Self>> {
+        let this = match Self::new_with_chain(client, chain, protocol, address_book) {
+            Some(this) => this,
+            None => return Ok(None),
+        };
+        let report = this.verify().await;
+        if !report.is_ok() {
+            return Err(crate::errors::Error::VerificationFailed(Box::new(report)));
+        }
+        Ok(Some(this))
+    }
+
+    /// Creates a new instance on the chain of `client`, by searching for the required addresses in
+    /// the process-global [addressbook].
+    ///
+    /// A [custom protocol][ProtocolType::Custom] can be on a chain which is not a [`Chain`], its
+    /// factory then carries the raw chain ID, see [`V2Factory::set_chain_id`].
+    ///
+    /// Returns [`Error::UnknownChainId`] if the chain ID is not a [`Chain`], and
+    /// [`Error::ProtocolNotFound`] if the addresses are not found.
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    /// [`V2Factory::set_chain_id`]: crate::v2::Factory::set_chain_id
+    /// [`Error::UnknownChainId`]: crate::errors::Error::UnknownChainId
+    /// [`Error::ProtocolNotFound`]: crate::errors::Error::ProtocolNotFound
+    #[cfg(feature = "addresses")]
+    pub async fn new_from_client(client: Arc<M>, protocol: ProtocolType) -> Result<Self> {
+        use crate::errors::Error;
+        use ethers_contract::ContractError;
+
+        let id = client.get_chainid().await.map_err(ContractError::<M>::MiddlewareError)?;
+        let chain_id = u64::try_from(id).map_err(|_| Error::UnknownChainId(id))?;
+        match (Chain::try_from(chain_id), protocol) {
+            (Ok(chain), _) => Self::new_with_chain(client, chain, protocol, None)
+                .ok_or(Error::ProtocolNotFound { protocol, chain }),
+            (Err(_), ProtocolType::Custom(custom)) => {
+                let mut this = Self::new_custom(client, custom);
+                #[allow(irrefutable_let_patterns)]
+                if let Self::V2(v2) = &mut this {
+                    v2.set_chain_id(chain_id);
+                }
+                Ok(this)
+            }
+            (Err(_), _) => Err(Error::UnknownChainId(id)),
+        }
+    }
+
+    /// Checks the protocol's addresses against the chain, like that its contracts are deployed and
+    /// that its router points to its factory. See [`V2Protocol::verify`] and
+    /// `v3::Protocol::verify` for the checks made.
+    pub async fn verify(&self) -> VerificationReport {
+        match self {
+            Self::V2(p) => p.verify().await,
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.verify().await,
         }
     }
 
@@ -69,15 +227,28 @@ impl<M: Middleware> Protocol<M> {
     pub fn client(&self) -> Arc<M> {
         match self {
             Self::V2(p) => p.client(),
-            Self::V3 => todo_v3(),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.client(),
         }
     }
 
+    /// Returns whether the client is read-only, that is it does not have a default sender.
+    ///
+    /// A read-only client, like a bare [Provider][ethers_providers::Provider], supports the whole
+    /// quoting and reading surface of the protocol, but the operations which must be signed, like
+    /// executing a swap, approving a spender or resolving an [`Amount::PercentOfBalance`], return
+    /// [`Error::SignerRequired`][crate::errors::Error::SignerRequired].
+    #[inline(always)]
+    pub fn is_read_only(&self) -> bool {
+        self.client().default_sender().is_none()
+    }
+
     /// Returns a reference to the wrapped [V2Protocol].
     pub fn as_v2(&self) -> Option<&V2Protocol<M>> {
         match self {
             Self::V2(v2) => Some(v2),
-            Self::V3 => None,
+            #[cfg(feature = "v3")]
+            Self::V3(_) => None,
         }
     }
 
@@ -85,7 +256,8 @@ impl<M: Middleware> Protocol<M> {
     pub fn as_v2_mut(&mut self) -> Option<&mut V2Protocol<M>> {
         match self {
             Self::V2(v2) => Some(v2),
-            Self::V3 => None,
+            #[cfg(feature = "v3")]
+            Self::V3(_) => None,
         }
     }
 
@@ -93,31 +265,35 @@ impl<M: Middleware> Protocol<M> {
     pub fn into_v2(self) -> Option<V2Protocol<M>> {
         match self {
             Self::V2(v2) => Some(v2),
-            Self::V3 => None,
+            #[cfg(feature = "v3")]
+            Self::V3(_) => None,
         }
     }
 
-    /// Returns a reference to the wrapped V3Protocol.
-    pub fn as_v3(&self) -> Option<()> {
+    /// Returns a reference to the wrapped [V3Protocol].
+    #[cfg(feature = "v3")]
+    pub fn as_v3(&self) -> Option<&V3Protocol<M>> {
         match self {
             Self::V2(_) => None,
-            Self::V3 => Some(()),
+            Self::V3(v3) => Some(v3),
         }
     }
 
-    /// Returns a mutable reference to the wrapped V3Protocol.
-    pub fn as_v3_mut(&mut self) -> Option<()> {
+    /// Returns a mutable reference to the wrapped [V3Protocol].
+    #[cfg(feature = "v3")]
+    pub fn as_v3_mut(&mut self) -> Option<&mut V3Protocol<M>> {
         match self {
             Self::V2(_) => None,
-            Self::V3 => Some(()),
+            Self::V3(v3) => Some(v3),
         }
     }
 
-    /// Returns the wrapped V3Protocol.
-    pub fn into_v3(self) -> Option<()> {
+    /// Returns the wrapped [V3Protocol].
+    #[cfg(feature = "v3")]
+    pub fn into_v3(self) -> Option<V3Protocol<M>> {
         match self {
             Self::V2(_) => None,
-            Self::V3 => Some(()),
+            Self::V3(v3) => Some(v3),
         }
     }
 
@@ -128,34 +304,69 @@ impl<M: Middleware> Protocol<M> {
     pub fn factory_address(&self) -> Address {
         match self {
             Self::V2(p) => p.factory().address(),
-            Self::V3 => todo_v3(),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.factory().address(),
         }
     }
 
-    /// The factory's `pair_codehash` method.
+    /// Returns the policy of the factory's batched requests and log scans.
+    #[inline(always)]
+    pub fn request_policy(&self) -> RequestPolicy {
+        match self {
+            Self::V2(p) => p.request_policy(),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.request_policy(),
+        }
+    }
+
+    /// Sets the policy of the factory's batched requests and log scans, like pair and pool
+    /// enumeration.
+    #[inline(always)]
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) {
+        match self {
+            Self::V2(p) => p.set_request_policy(policy),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.set_request_policy(policy),
+        }
+    }
+
+    /// The factory's `pair_codehash` method, or its pool's code hash for Uniswap V3.
     #[inline(always)]
     pub fn pair_codehash(&self, chain: Option<Chain>) -> H256 {
         match self {
             Self::V2(p) => p.pair_codehash(chain),
-            Self::V3 => todo_v3(),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.factory().pair_code_hash(chain),
         }
     }
 
     /// The factory's `create_pair` method.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
     #[inline(always)]
-    pub fn create_pair(&self, token_a: Address, token_b: Address) -> ContractCall<M, Address> {
+    pub fn create_pair(
+        &self,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<ContractCall<M, Address>> {
         match self {
-            Self::V2(p) => p.create_pair(token_a, token_b),
-            Self::V3 => todo_v3(),
+            Self::V2(p) => Ok(p.create_pair(token_a, token_b)),
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
         }
     }
 
     /// The factory's `pair_for` method.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
     #[inline(always)]
-    pub fn pair_for(&self, token_a: Address, token_b: Address) -> V2Pair<M> {
+    pub fn pair_for(&self, token_a: Address, token_b: Address) -> Result<V2Pair<M>> {
         match self {
-            Self::V2(p) => p.pair_for(token_a, token_b),
-            Self::V3 => todo_v3(),
+            Self::V2(p) => Ok(p.pair_for(token_a, token_b)),
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
         }
     }
 
@@ -166,13 +377,33 @@ impl<M: Middleware> Protocol<M> {
     pub fn router_address(&self) -> Address {
         match self {
             Self::V2(p) => p.router().address(),
-            Self::V3 => todo_v3(),
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.router().address(),
+        }
+    }
+
+    /// Returns the approval calls needed for `spender` to transfer `amount` of `token` from the
+    /// client's default sender.
+    #[inline(always)]
+    pub async fn approve_if_needed(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Vec<ContractCall<M, bool>>> {
+        match self {
+            Self::V2(p) => p.approve_if_needed(token, spender, amount).await,
+            #[cfg(feature = "v3")]
+            Self::V3(p) => p.approve_if_needed(token, spender, amount).await,
         }
     }
 
     /// The router's `add_liquidity` method.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
     #[inline(always)]
-    pub fn add_liquidity(
+    pub async fn add_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
@@ -180,68 +411,400 @@ impl<M: Middleware> Protocol<M> {
         amount_b_desired: U256,
         amount_a_min: U256,
         amount_b_min: U256,
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
     ) -> Result<ContractCall<M, (U256, U256, U256)>> {
         match self {
-            Self::V2(p) => p.add_liquidity(
-                token_a,
-                token_b,
-                amount_a_desired,
-                amount_b_desired,
-                amount_a_min,
-                amount_b_min,
-                to,
-                deadline,
-            ),
-            Self::V3 => todo_v3(),
+            Self::V2(p) => {
+                p.add_liquidity(
+                    token_a,
+                    token_b,
+                    amount_a_desired,
+                    amount_b_desired,
+                    amount_a_min,
+                    amount_b_min,
+                    to,
+                    deadline,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
         }
     }
 
     /// The router's `remove_liquidity` method.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
     #[inline(always)]
-    pub fn remove_liquidity(
+    pub async fn remove_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
         liquidity: U256,
         amount_a_min: U256,
         amount_b_min: U256,
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
     ) -> Result<ContractCall<M, (U256, U256)>> {
         match self {
-            Self::V2(p) => p.remove_liquidity(
-                token_a,
-                token_b,
-                liquidity,
-                amount_a_min,
-                amount_b_min,
-                to,
-                deadline,
-            ),
-            Self::V3 => todo_v3(),
+            Self::V2(p) => {
+                p.remove_liquidity(
+                    token_a,
+                    token_b,
+                    liquidity,
+                    amount_a_min,
+                    amount_b_min,
+                    to,
+                    deadline,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// The router's `remove_liquidity_position` method.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
+    #[inline(always)]
+    pub async fn remove_liquidity_position(
+        &self,
+        position: &LiquidityPosition,
+        slippage_tolerance: impl Into<Slippage>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        match self {
+            Self::V2(p) => {
+                p.remove_liquidity_position(position, slippage_tolerance, to, deadline).await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
         }
     }
 
-    /// The router's `swap` method.
+    /// Returns a [SwapBuilder] of a swap from `token_in` to `token_out`, through the path with the
+    /// best output found by the router. See [`Route::Auto`].
+    pub fn swap_builder(&self, token_in: Address, token_out: Address) -> SwapBuilder<'_, M> {
+        SwapBuilder::new(self, Route::auto(token_in, token_out))
+    }
+
+    /// Returns a [SwapBuilder] of a swap through `path`.
+    pub fn swap_path(&self, path: impl Into<Route>) -> SwapBuilder<'_, M> {
+        SwapBuilder::new(self, path)
+    }
+
+    /// The router's `swap` method. `weth` overrides the chain's wrapped native token.
+    ///
+    /// Prefer [`swap_builder`][Self::swap_builder], of which this is a wrapper.
     #[inline(always)]
     pub async fn swap(
         &self,
         amount: Amount,
-        slippage_tolerance: f32,
-        path: &[Address],
-        to: Address,
-        deadline: U256,
-        weth: Address,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<U256>>> {
+        let mut builder = self
+            .swap_path(route)
+            .amount(amount)
+            .slippage(slippage_tolerance)
+            .recipient(to)
+            .deadline(deadline)
+            .options(options);
+        if let Some(weth) = weth {
+            builder = builder.weth(weth);
+        }
+        builder.build().await
+    }
+
+    /// The router's `swap` method, with a decimal input amount.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
+    #[inline(always)]
+    pub async fn swap_dec(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: &str,
+        slippage_tolerance: impl Into<Slippage>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
     ) -> Result<ContractCall<M, Vec<U256>>> {
         match self {
-            Self::V2(p) => p.swap(amount, slippage_tolerance, path, to, deadline, weth).await,
-            Self::V3 => todo_v3(),
+            Self::V2(p) => {
+                p.swap_dec(
+                    token_in,
+                    token_out,
+                    amount_in,
+                    slippage_tolerance,
+                    to,
+                    deadline,
+                    weth,
+                    options,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// The router's `swap` method, simulated with `eth_call`. See
+    /// [`V2Protocol::simulate_swap`].
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
+    #[inline(always)]
+    pub async fn simulate_swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+        simulate: SimulateOptions,
+    ) -> Result<Simulation<Vec<U256>>> {
+        match self {
+            Self::V2(p) => {
+                p.simulate_swap(
+                    amount,
+                    slippage_tolerance,
+                    route,
+                    to,
+                    deadline,
+                    weth,
+                    options,
+                    simulate,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// The router's `swap` method's estimated gas and suggested fees. See
+    /// [`V2Protocol::estimate_gas_swap`].
+    ///
+    /// Returns [`Error::UnsupportedProtocol`][crate::errors::Error::UnsupportedProtocol] for Uniswap
+    /// V3.
+    #[inline(always)]
+    pub async fn estimate_gas_swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+        margin_bps: u32,
+    ) -> Result<GasEstimate> {
+        match self {
+            Self::V2(p) => {
+                p.estimate_gas_swap(
+                    amount,
+                    slippage_tolerance,
+                    route,
+                    to,
+                    deadline,
+                    weth,
+                    options,
+                    margin_bps,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Self::V3(_) => Err(Error::UnsupportedProtocol),
         }
     }
+
+    /// Sends a swap built with [`swap`][Self::swap], waits for `confirmations` confirmations and
+    /// parses the amounts of the swap from the transaction receipt.
+    ///
+    /// If the transaction reverts, the revert is decoded by replaying it at the block in which it
+    /// was included, e.g. into [`Error::SlippageExceeded`][crate::errors::Error::SlippageExceeded].
+    /// Use the call directly to manage the transaction yourself.
+    pub async fn execute_swap<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+        confirmations: usize,
+    ) -> Result<SwapReceipt> {
+        execute_swap(self.client().as_ref(), call, confirmations).await
+    }
+
+    /// Sends a swap built with [`swap`][Self::swap] privately through `submitter`, like the
+    /// [Flashbots][crate::mev::Flashbots] relay, instead of the public mempool, where it could be
+    /// sandwiched.
+    ///
+    /// The transaction is simulated, signed by the client and submitted with an expiry of
+    /// [`options.max_blocks`][crate::mev::PrivateOptions::max_blocks] blocks. The amounts of the
+    /// swap are then parsed from its receipt, as in [`execute_swap`][Self::execute_swap].
+    #[cfg(feature = "mev")]
+    pub async fn execute_swap_private<D: Detokenize, S: crate::mev::BundleSubmitter>(
+        &self,
+        call: ContractCall<M, D>,
+        submitter: &S,
+        options: crate::mev::PrivateOptions,
+    ) -> Result<SwapReceipt> {
+        crate::mev::execute_swap_private(self.client().as_ref(), call, submitter, options).await
+    }
+
+    /// Generates the access list of `call` with `eth_createAccessList`, and sets it on the call
+    /// only if it lowers its estimated gas. See [`gas::with_access_list`].
+    ///
+    /// The generated list is returned in both cases, or None if the provider does not support the
+    /// RPC.
+    pub async fn with_access_list<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+    ) -> Result<(ContractCall<M, D>, Option<AccessListEstimate>)> {
+        gas::with_access_list(self.client().as_ref(), call).await
+    }
 }
 
-fn todo_v3() -> ! {
-    todo!("v3 is not yet implemented")
+#[cfg(test)]
+#[cfg(all(feature = "addresses", feature = "v2"))]
+mod tests {
+    use super::*;
+    use crate::errors::Error;
+    use ethers_providers::{MockProvider, Provider};
+
+    fn mocked(chain_id: u64) -> Arc<Provider<MockProvider>> {
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(chain_id)).unwrap();
+        Arc::new(provider)
+    }
+
+    #[tokio::test]
+    async fn test_new_from_client() {
+        let client = mocked(1);
+        let protocol = Protocol::new_from_client(client, ProtocolType::UniswapV2).await.unwrap();
+        let v2 = protocol.as_v2().unwrap();
+        assert_eq!(v2.chain(), Some(Chain::Mainnet));
+        assert_eq!(v2.factory().address(), ProtocolType::UniswapV2.addresses(Chain::Mainnet).0);
+
+        let res = Protocol::new_from_client(mocked(1), ProtocolType::Spookyswap).await;
+        let err = res.unwrap_err();
+        assert!(matches!(err, Error::ProtocolNotFound { chain: Chain::Mainnet, .. }), "{err}");
+
+        // an unlisted chain ID is only supported by custom protocols
+        let chain_id = 0x5eed_5eed;
+        let res = Protocol::new_from_client(mocked(chain_id), ProtocolType::UniswapV2).await;
+        assert!(matches!(res.unwrap_err(), Error::UnknownChainId(id) if id == chain_id.into()));
+
+        let custom = CustomProtocol::new(
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            true,
+            H256::repeat_byte(3),
+        );
+        let protocol = Protocol::new_from_client(mocked(chain_id), custom.into()).await.unwrap();
+        let factory = protocol.as_v2().unwrap().factory();
+        assert_eq!(factory.chain(), None);
+        assert_eq!(factory.chain_id(), Some(chain_id));
+        assert_eq!(factory.pair_code_hash(None), H256::repeat_byte(3));
+    }
+
+    #[test]
+    #[cfg(feature = "v3")]
+    fn test_new_v3() {
+        let (provider, _mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+
+        let protocol = Protocol::new(client.clone(), factory, router, ProtocolType::UniswapV3);
+        let v3 = protocol.as_v3().unwrap();
+        assert_eq!(v3.factory().address(), factory);
+        assert_eq!(v3.router().address(), router);
+        assert_eq!(v3.quoter().address(), crate::v3::QUOTER_ADDRESS);
+
+        let protocol = Protocol::new(client, factory, router, ProtocolType::PancakeswapV3);
+        let code_hash = ProtocolType::PancakeswapV3.pair_code_hash(None);
+        assert_eq!(protocol.pair_codehash(None), code_hash);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "v3")]
+    async fn test_v3_unsupported() {
+        let (provider, _mock) = Provider::mocked();
+        let (factory, router, quoter) =
+            (Address::repeat_byte(0x11), Address::repeat_byte(0x22), Address::repeat_byte(0x33));
+        let v3 = V3Protocol::new(Arc::new(provider), factory, router, quoter);
+        let protocol = Protocol::V3(v3);
+        let (token_in, token_out) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        let to = Address::repeat_byte(0x0c);
+
+        assert!(matches!(protocol.pair_for(token_in, token_out), Err(Error::UnsupportedProtocol)));
+        let res = protocol.create_pair(token_in, token_out);
+        assert!(matches!(res, Err(Error::UnsupportedProtocol)));
+
+        let res = protocol
+            .add_liquidity(
+                token_in,
+                token_out,
+                100.into(),
+                100.into(),
+                0.into(),
+                0.into(),
+                to,
+                U256::MAX,
+            )
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+        let res = protocol
+            .remove_liquidity(token_in, token_out, 100.into(), 0.into(), 0.into(), to, U256::MAX)
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+
+        let res = protocol
+            .swap_dec(token_in, token_out, "1.5", 1.0, to, U256::MAX, None, SwapOptions::new())
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+
+        let position = LiquidityPosition {
+            token0: token_in,
+            token1: token_out,
+            liquidity: 1_000.into(),
+            share_bps: 1_000,
+            amount0: 100.into(),
+            amount1: 100.into(),
+        };
+        let res = protocol.remove_liquidity_position(&position, 1.0, to, U256::MAX).await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+
+        let amount = Amount::exact_in(U256::exp10(18));
+        let path = [token_in, token_out];
+        let res = protocol
+            .simulate_swap(
+                amount,
+                1.0,
+                path,
+                to,
+                U256::MAX,
+                None,
+                SwapOptions::new(),
+                SimulateOptions::new(),
+            )
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+
+        let res = protocol
+            .estimate_gas_swap(amount, 1.0, path, to, U256::MAX, None, SwapOptions::new(), 1_000)
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+    }
 }
@@ -15,6 +15,15 @@ pub const UNISWAP_V3_POOL_CODE_HASH: H256 = H256([
     0x7d, 0x24, 0x4f, 0x78, 0xa3, 0x29, 0x7e, 0xa8, 0x93, 0x25, 0xf8, 0x43, 0xf8, 0x7b, 0x8b, 0x54,
 ]);
 
+/// [0x6ce8eb472fa82df5469c6ab6d485f17c3ad13c8cd7af59b3d4a8026c5ce0f7e2](https://bscscan.com/address/0x41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9#code)
+///
+/// PancakeSwap V3 pools are deployed by a separate pool deployer contract rather than by the
+/// factory, see [`ProtocolType::pool_deployer`][crate::ProtocolType::pool_deployer].
+pub const PANCAKESWAP_V3_POOL_CODE_HASH: H256 = H256([
+    0x6c, 0xe8, 0xeb, 0x47, 0x2f, 0xa8, 0x2d, 0xf5, 0x46, 0x9c, 0x6a, 0xb6, 0xd4, 0x85, 0xf1, 0x7c,
+    0x3a, 0xd1, 0x3c, 0x8c, 0xd7, 0xaf, 0x59, 0xb3, 0xd4, 0xa8, 0x02, 0x6c, 0x5c, 0xe0, 0xf7, 0xe2,
+]);
+
 /// [0xe18a34eb0e04b04f7a0ac29a6e80748dca96319b42c54d679cb821dca90c6303](https://github.com/sushiswap/sushiswap/blob/96eb88dee945c14c4bf90130c7f2e58e21c6f093/protocols/sushiswap/contracts/libraries/UniswapV2Library.sol#L26)
 pub const SUSHISWAP_PAIR_CODE_HASH: H256 = H256([
     0xe1, 0x8a, 0x34, 0xeb, 0x0e, 0x04, 0xb0, 0x4f, 0x7a, 0x0a, 0xc2, 0x9a, 0x6e, 0x80, 0x74, 0x8d,
@@ -30,6 +39,14 @@ pub const PANCAKESWAP_PAIR_CODE_HASH: H256 = H256([
     0x06, 0x8e, 0x8f, 0xf6, 0x7d, 0x07, 0x81, 0x48, 0xa3, 0xfa, 0x3f, 0x4a, 0x84, 0xf6, 0x9b, 0xd5,
 ]);
 
+/// [0x57224589c67f3f30a6b0d7a1b54cf3153ab84563bc609ef41dfb34f8b2974d2d](https://etherscan.io/address/0x1097053Fd2ea711dad45caCcc45EfF7548fCB362#readContract)
+///
+/// PancakeSwap V2's factories outside of Binance Smart Chain deploy a different pair bytecode.
+pub const PANCAKESWAP_ETHEREUM_PAIR_CODE_HASH: H256 = H256([
+    0x57, 0x22, 0x45, 0x89, 0xc6, 0x7f, 0x3f, 0x30, 0xa6, 0xb0, 0xd7, 0xa1, 0xb5, 0x4c, 0xf3, 0x15,
+    0x3a, 0xb8, 0x45, 0x63, 0xbc, 0x60, 0x9e, 0xf4, 0x1d, 0xfb, 0x34, 0xf8, 0xb2, 0x97, 0x4d, 0x2d,
+]);
+
 /// [0xd0d4c4cd0848c93cb4fd1f498d7013ee6bfb25783ea21593d5834f5d250ece66](https://testnet.bscscan.com/address/0x6725f303b657a9451d8ba641348b6761a6cc7a17#readContract)
 pub const PANCAKESWAP_TESTNET_PAIR_CODE_HASH: H256 = H256([
     0xd0, 0xd4, 0xc4, 0xcd, 0x08, 0x48, 0xc9, 0x3c, 0xb4, 0xfd, 0x1f, 0x49, 0x8d, 0x70, 0x13, 0xee,
@@ -65,3 +82,34 @@ pub const TRADERJOE_TESTNET_PAIR_CODE_HASH: H256 = H256([
     0x6c, 0x67, 0xac, 0x67, 0xd0, 0xda, 0xd5, 0x4b, 0xe7, 0xb0, 0x66, 0xed, 0xd9, 0xb4, 0x15, 0x4f,
     0xb5, 0xa0, 0xab, 0x7d, 0x01, 0x23, 0x22, 0x59, 0xb9, 0xff, 0x26, 0xeb, 0xc1, 0x73, 0x9b, 0xa2,
 ]);
+
+/// [0xc1ac28b1c4ebe53c0cff67bab5878c4eb68759bb1e9f73977cd266b247d149f0](https://optimistic.etherscan.io/address/0x9c12939390052919aF3155f41Bf4160Fd3666A6f#readContract)
+pub const VELODROME_PAIR_CODE_HASH: H256 = H256([
+    0xc1, 0xac, 0x28, 0xb1, 0xc4, 0xeb, 0xe5, 0x3c, 0x0c, 0xff, 0x67, 0xba, 0xb5, 0x87, 0x8c, 0x4e,
+    0xb6, 0x87, 0x59, 0xbb, 0x1e, 0x9f, 0x73, 0x97, 0x7c, 0xd2, 0x66, 0xb2, 0x47, 0xd1, 0x49, 0xf0,
+]);
+
+/// [0xa856464ae65f7619087bc369daaf7e387dae1e5af69cfa7935850ebf754b04c1](https://arbiscan.io/address/0x6EcCab422D763aC031210895C81787E87B43A652#code)
+///
+/// Camelot has no [`ProtocolType`](crate::ProtocolType) as its router takes a referrer, use a
+/// [custom protocol](crate::ProtocolType::new) with its factory to read its pairs.
+pub const CAMELOT_PAIR_CODE_HASH: H256 = H256([
+    0xa8, 0x56, 0x46, 0x4a, 0xe6, 0x5f, 0x76, 0x19, 0x08, 0x7b, 0xc3, 0x69, 0xda, 0xaf, 0x7e, 0x38,
+    0x7d, 0xae, 0x1e, 0x5a, 0xf6, 0x9c, 0xfa, 0x79, 0x35, 0x85, 0x0e, 0xbf, 0x75, 0x4b, 0x04, 0xc1,
+]);
+
+/// All the Uniswap V2 pair code hashes above, tried first by
+/// `v2::Factory::detect_pair_code_hash`.
+pub const PAIR_CODE_HASHES: [H256; 11] = [
+    UNISWAP_V2_PAIR_CODE_HASH,
+    SUSHISWAP_PAIR_CODE_HASH,
+    PANCAKESWAP_PAIR_CODE_HASH,
+    PANCAKESWAP_ETHEREUM_PAIR_CODE_HASH,
+    PANCAKESWAP_TESTNET_PAIR_CODE_HASH,
+    QUICKSWAP_PAIR_CODE_HASH,
+    SPOOKYSWAP_PAIR_CODE_HASH,
+    SPOOKYSWAP_TESTNET_PAIR_CODE_HASH,
+    TRADERJOE_PAIR_CODE_HASH,
+    TRADERJOE_TESTNET_PAIR_CODE_HASH,
+    CAMELOT_PAIR_CODE_HASH,
+];
@@ -1,9 +1,16 @@
 use super::pair_code_hashes::*;
-use ethers_core::types::{Address, Chain, H256};
-use std::fmt;
+use crate::errors::{Error, Result};
+use ethers_core::types::{Address, Chain, H160, H256};
+use std::{fmt, str::FromStr};
+
+/// The address of PancakeSwap V3's pool deployer, the same on all chains.
+const PANCAKESWAP_V3_POOL_DEPLOYER: Address = H160([
+    0x41, 0xff, 0x9a, 0xa7, 0xe1, 0x6b, 0x8b, 0x1a, 0x8a, 0x8d, 0xc4, 0xf0, 0xef, 0xac, 0xd9, 0x3d,
+    0x02, 0xd0, 0x71, 0xc9,
+]);
 
 #[cfg(feature = "addresses")]
-use crate::contracts::addresses::{address, try_address};
+use crate::contracts::addresses::{address, with_registry};
 
 /// A type of protocol that is, or is a fork of, Uniswap V2 or V3.
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
@@ -13,19 +20,26 @@ use crate::contracts::addresses::{address, try_address};
     serde(rename_all = "lowercase")
 )]
 pub enum ProtocolType {
-    /// Deployed on Ethereum and its testnets.
+    /// Deployed on Ethereum, Optimism, Arbitrum, Polygon, Binance Smart Chain, Avalanche and
+    /// testnets.
     #[default]
     UniswapV2,
 
-    /// Deployed on Ethereum, Polygon, Celo and testnets.
+    /// Deployed on Ethereum, Optimism, Arbitrum, Polygon, Binance Smart Chain, Avalanche, Celo
+    /// and testnets.
     UniswapV3,
 
     /// Deployed on most chains.
     Sushiswap,
 
-    /// Deployed only on Binance Smart Chain and its testnet.
+    /// Deployed on Binance Smart Chain, its testnet and Ethereum.
     Pancakeswap,
 
+    /// PancakeSwap's Uniswap V3 fork, deployed on Binance Smart Chain and Ethereum.
+    ///
+    /// Its pools are deployed by a separate [pool deployer][ProtocolType::pool_deployer].
+    PancakeswapV3,
+
     /// Deployed only on Polygon and its Mumbai testnet.
     Quickswap,
 
@@ -35,59 +49,185 @@ pub enum ProtocolType {
     /// Deployed only on Avalanche and its Fuji testnet.
     Traderjoe,
 
-    /// Custom protocol.
-    Custom {
-        /// The protocol's factory address.
-        factory: Address,
+    /// A [Solidly] fork, deployed only on Optimism.
+    ///
+    /// Solidly pairs are keyed by `(token0, token1, stable)`: stable pairs use the `x³y + y³x`
+    /// invariant, while volatile pairs use Uniswap V2's `xy`. Only exact input swaps are
+    /// supported.
+    ///
+    /// [Solidly]: https://github.com/solidlyexchange/solidly
+    Velodrome {
+        /// Whether the pairs are stable or volatile.
+        stable: bool,
+    },
 
-        /// The protocol's router address.
-        router: Address,
+    /// A custom protocol, like a private fork or a deployment on a chain which is not in the
+    /// addressbook.
+    Custom(CustomProtocol),
+}
 
-        /// Whether the protocol is Uniswap v2 or v3.
-        is_v2: bool,
+/// The addresses and parameters of a [custom protocol][ProtocolType::Custom].
+///
+/// Two custom protocols are equal if all of their fields except for the name are equal.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CustomProtocol {
+    /// The protocol's factory address.
+    pub factory: Address,
 
-        /// The hash of the deployment code of the pair that the factory creates.
-        pair_code_hash: H256,
-    },
+    /// The protocol's router address.
+    pub router: Address,
+
+    /// Whether the protocol is Uniswap v2 or v3.
+    pub is_v2: bool,
+
+    /// The hash of the deployment code of the pair that the factory creates.
+    pub pair_code_hash: H256,
+
+    /// The fee taken on each swap, in basis points.
+    pub swap_fee_bps: u32,
+
+    /// The name of the protocol, used by [`Display`][fmt::Display].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub name: Option<&'static str>,
+}
+
+impl CustomProtocol {
+    /// Creates a new custom protocol with Uniswap's 0.3% swap fee and no name.
+    pub const fn new(factory: Address, router: Address, is_v2: bool, pair_code_hash: H256) -> Self {
+        Self { factory, router, is_v2, pair_code_hash, swap_fee_bps: 30, name: None }
+    }
+
+    /// Sets the fee taken on each swap, in basis points.
+    pub const fn with_swap_fee_bps(self, swap_fee_bps: u32) -> Self {
+        Self { swap_fee_bps, ..self }
+    }
+
+    /// Sets the name of the protocol.
+    pub const fn with_name(self, name: &'static str) -> Self {
+        Self { name: Some(name), ..self }
+    }
+
+    fn key(&self) -> (Address, Address, bool, H256, u32) {
+        (self.factory, self.router, self.is_v2, self.pair_code_hash, self.swap_fee_bps)
+    }
+}
+
+impl PartialEq for CustomProtocol {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for CustomProtocol {}
+
+impl std::hash::Hash for CustomProtocol {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl From<CustomProtocol> for ProtocolType {
+    fn from(custom: CustomProtocol) -> Self {
+        Self::Custom(custom)
+    }
 }
 
 impl fmt::Display for ProtocolType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Custom { .. } => f.pad("Custom"),
-            _ => fmt::Debug::fmt(self, f),
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for ProtocolType {
+    type Err = Error;
+
+    /// Parses a built-in protocol from its [name][ProtocolType::name], ignoring case and
+    /// separators, so that `"UniswapV2"` and `"uniswap_v2"` are also parsed as `uniswap-v2`.
+    /// `"pancakeswap-v2"` is parsed as [`Pancakeswap`][ProtocolType::Pancakeswap].
+    ///
+    /// Returns [`Error::UnknownProtocol`] if it is not the name of a built-in protocol.
+    fn from_str(s: &str) -> Result<Self> {
+        let normalize = |s: &str| -> String {
+            s.chars().filter(|c| !matches!(c, '-' | '_' | ' ')).collect::<String>().to_lowercase()
+        };
+        let name = normalize(s);
+        if name == "pancakeswapv2" {
+            return Ok(Self::Pancakeswap);
         }
+        builtins()
+            .find(|protocol| normalize(protocol.name()) == name)
+            .ok_or_else(|| Error::UnknownProtocol(s.to_string()))
     }
 }
 
 impl ProtocolType {
-    /// Instantiates a new custom protocol type.
+    /// Instantiates a new custom protocol type, see [`CustomProtocol::new`].
     pub const fn new(factory: Address, router: Address, is_v2: bool, pair_code_hash: H256) -> Self {
-        Self::Custom { factory, router, is_v2, pair_code_hash }
+        Self::Custom(CustomProtocol::new(factory, router, is_v2, pair_code_hash))
     }
 
     /// Returns all of the defined protocols.
-    pub const fn all() -> [Self; 7] {
+    ///
+    /// Solidly forks are returned only once, for their volatile pairs.
+    pub const fn all() -> [Self; 9] {
         use ProtocolType::*;
-        [UniswapV2, UniswapV3, Sushiswap, Pancakeswap, Quickswap, Spookyswap, Traderjoe]
+        [
+            UniswapV2,
+            UniswapV3,
+            Sushiswap,
+            Pancakeswap,
+            PancakeswapV3,
+            Quickswap,
+            Spookyswap,
+            Traderjoe,
+            Velodrome { stable: false },
+        ]
+    }
+
+    /// Returns the name of the protocol, like `"uniswap-v2"`, used by [`Display`][fmt::Display]
+    /// and parsed by [`FromStr`].
+    ///
+    /// Custom protocols are named by [`CustomProtocol::with_name`], or `"Custom"` by default.
+    pub const fn name(&self) -> &'static str {
+        use ProtocolType::*;
+        match self {
+            UniswapV2 => "uniswap-v2",
+            UniswapV3 => "uniswap-v3",
+            Sushiswap => "sushiswap",
+            Pancakeswap => "pancakeswap",
+            PancakeswapV3 => "pancakeswap-v3",
+            Quickswap => "quickswap",
+            Spookyswap => "spookyswap",
+            Traderjoe => "traderjoe",
+            Velodrome { stable: false } => "velodrome",
+            Velodrome { stable: true } => "velodrome-stable",
+            Custom(CustomProtocol { name: Some(name), .. }) => name,
+            Custom(_) => "Custom",
+        }
     }
 
     /// Returns (factory_address, router_address), returning None if not found.
+    ///
+    /// The addresses [registered][register] at runtime are preferred over the built-in ones.
+    ///
+    /// [register]: crate::contracts::addresses::register
     #[cfg(feature = "addresses")]
     pub fn try_addresses(&self, chain: Chain) -> (Option<Address>, Option<Address>) {
-        if let Self::Custom { factory, router, .. } = self {
-            (Some(*factory), Some(*router))
-        } else {
-            let (factory_name, router_name) = self.contract_names();
-            (try_address(factory_name, chain), try_address(router_name, chain))
-        }
+        with_registry(|book| book.try_addresses(*self, chain))
     }
 
     /// Returns (factory_address, router_address), panicking if not found.
+    ///
+    /// The addresses [registered][register] at runtime are preferred over the built-in ones.
+    ///
+    /// [register]: crate::contracts::addresses::register
     #[cfg(feature = "addresses")]
     pub fn addresses(&self, chain: Chain) -> (Address, Address) {
-        if let Self::Custom { factory, router, .. } = self {
-            (*factory, *router)
+        if let Some(addresses) = with_registry(|book| book.get(*self, chain).cloned()) {
+            (addresses.factory, addresses.router)
+        } else if let Self::Custom(custom) = self {
+            (custom.factory, custom.router)
         } else {
             let (factory_name, router_name) = self.contract_names();
             (address(factory_name, chain), address(router_name, chain))
@@ -102,10 +242,12 @@ impl ProtocolType {
             UniswapV3 => ("UniswapV3Factory", "UniswapV3Router02"),
             Sushiswap => ("SushiV2Factory", "SushiSwapRouter"),
             Pancakeswap => ("PancakeFactory", "PancakeRouter"),
+            PancakeswapV3 => ("PancakeV3Factory", "PancakeV3Router"),
             Quickswap => ("QuickFactory", "QuickRouter"),
             Spookyswap => ("SpookyFactory", "SpookyRouter"),
             Traderjoe => ("JoeFactory", "JoeRouter"),
-            Custom { .. } => ("CustomFactory", "CustomRouter"),
+            Velodrome { .. } => ("VelodromeFactory", "VelodromeRouter"),
+            Custom(_) => ("CustomFactory", "CustomRouter"),
         }
     }
 
@@ -118,12 +260,18 @@ impl ProtocolType {
         match self {
             UniswapV2 => UNISWAP_V2_PAIR_CODE_HASH,
             UniswapV3 => UNISWAP_V3_POOL_CODE_HASH,
+            PancakeswapV3 => PANCAKESWAP_V3_POOL_CODE_HASH,
             Sushiswap => SUSHISWAP_PAIR_CODE_HASH,
             Pancakeswap => chain_or(
                 chain,
                 Chain::BinanceSmartChainTestnet,
                 PANCAKESWAP_TESTNET_PAIR_CODE_HASH,
-                PANCAKESWAP_PAIR_CODE_HASH,
+                chain_or(
+                    chain,
+                    Chain::Mainnet,
+                    PANCAKESWAP_ETHEREUM_PAIR_CODE_HASH,
+                    PANCAKESWAP_PAIR_CODE_HASH,
+                ),
             ),
             Quickswap => QUICKSWAP_PAIR_CODE_HASH,
             Spookyswap => chain_or(
@@ -138,17 +286,62 @@ impl ProtocolType {
                 TRADERJOE_TESTNET_PAIR_CODE_HASH,
                 TRADERJOE_PAIR_CODE_HASH,
             ),
-            Custom { pair_code_hash, .. } => *pair_code_hash,
+            Velodrome { .. } => VELODROME_PAIR_CODE_HASH,
+            Custom(custom) => custom.pair_code_hash,
+        }
+    }
+
+    /// Returns the fee taken on each swap, in basis points.
+    ///
+    /// Note: Uniswap V3 pools each have their own fee, the default 0.3% is returned for V3.
+    /// Solidly forks' fees can be changed by their factory's fee manager, the initial 0.02% is
+    /// returned for them.
+    pub const fn swap_fee_bps(&self) -> u32 {
+        use ProtocolType::*;
+        match self {
+            UniswapV2 | UniswapV3 | Sushiswap | Quickswap | Traderjoe => 30,
+            Pancakeswap | PancakeswapV3 => 25,
+            Spookyswap => 20,
+            Velodrome { .. } => 2,
+            Custom(custom) => custom.swap_fee_bps,
+        }
+    }
+
+    /// Returns the address of the contract which deploys the protocol's Uniswap V3 pools, if it is
+    /// not the factory.
+    pub const fn pool_deployer(&self) -> Option<Address> {
+        match self {
+            Self::PancakeswapV3 => Some(PANCAKESWAP_V3_POOL_DEPLOYER),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the protocol's pairs are stable if it is a Solidly fork, otherwise None.
+    pub const fn stable(&self) -> Option<bool> {
+        match self {
+            Self::Velodrome { stable } => Some(*stable),
+            _ => None,
         }
     }
 
+    /// Returns whether the protocol is a Solidly fork.
+    pub const fn is_solidly(&self) -> bool {
+        self.stable().is_some()
+    }
+
     /// Returns whether the protocol is, or is a fork of, Uniswap V2.
     pub const fn is_v2(&self) -> bool {
         use ProtocolType::*;
         match self {
-            UniswapV2 | Sushiswap | Pancakeswap | Quickswap | Spookyswap | Traderjoe => true,
-            UniswapV3 => false,
-            Custom { is_v2, .. } => *is_v2,
+            UniswapV2
+            | Sushiswap
+            | Pancakeswap
+            | Quickswap
+            | Spookyswap
+            | Traderjoe
+            | Velodrome { .. } => true,
+            UniswapV3 | PancakeswapV3 => false,
+            Custom(custom) => custom.is_v2,
         }
     }
 
@@ -168,6 +361,16 @@ const fn chain_or(c1: Option<Chain>, c2: Chain, hash: H256, default: H256) -> H2
     default
 }
 
+/// Returns all of the built-in protocols, including the stable pairs of Solidly forks.
+fn builtins() -> impl Iterator<Item = ProtocolType> {
+    ProtocolType::all().into_iter().chain([ProtocolType::Velodrome { stable: true }])
+}
+
+/// Returns the comma-separated names of the built-in protocols, for [`Error::UnknownProtocol`].
+pub(crate) fn protocol_names() -> String {
+    builtins().map(|protocol| protocol.name()).collect::<Vec<_>>().join(", ")
+}
+
 #[cfg(test)]
 #[allow(unused)]
 mod tests {
@@ -177,8 +380,17 @@ mod tests {
 
     #[test]
     fn test_versions() {
-        let v2s = [UniswapV2, Sushiswap, Pancakeswap, Quickswap, Spookyswap, Traderjoe];
-        let v3s = [UniswapV3];
+        let v2s = [
+            UniswapV2,
+            Sushiswap,
+            Pancakeswap,
+            Quickswap,
+            Spookyswap,
+            Traderjoe,
+            Velodrome { stable: false },
+            Velodrome { stable: true },
+        ];
+        let v3s = [UniswapV3, PancakeswapV3];
 
         for v2 in v2s {
             assert!(v2.is_v2());
@@ -192,10 +404,44 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "addresses")]
-    fn test_addresses() {
-        let protocols = ProtocolType::all();
+    fn test_solidly() {
+        assert_eq!(Velodrome { stable: true }.stable(), Some(true));
+        assert_eq!(Velodrome { stable: false }.stable(), Some(false));
+        assert!(Velodrome { stable: true }.is_solidly());
+        assert!(!UniswapV2.is_solidly());
+        assert_eq!(Sushiswap.stable(), None);
+    }
 
+    #[test]
+    fn test_custom() {
+        let (factory, router) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let custom = CustomProtocol::new(factory, router, true, H256::repeat_byte(3));
+        let named = ProtocolType::from(custom.with_name("MySwap"));
+        assert_eq!(ProtocolType::from(custom), named);
+        assert_ne!(ProtocolType::from(custom.with_swap_fee_bps(25)), named);
+        assert_eq!(ProtocolType::from(custom).to_string(), "Custom");
+        assert_eq!(named.to_string(), "MySwap");
+
+        assert_eq!(named.swap_fee_bps(), 30);
+        assert_eq!(ProtocolType::from(custom.with_swap_fee_bps(25)).swap_fee_bps(), 25);
+        assert_eq!(named.pair_code_hash(Some(Mainnet)), H256::repeat_byte(3));
+        assert!(named.is_v2());
+        #[cfg(feature = "addresses")]
+        assert_eq!(named.try_addresses(Chain::Dev), (Some(factory), Some(router)));
+    }
+
+    #[test]
+    fn test_pool_deployer() {
+        assert_eq!(
+            PancakeswapV3.pool_deployer(),
+            Some("0x41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9".parse().unwrap())
+        );
+        assert_eq!(UniswapV3.pool_deployer(), None);
+    }
+
+    /// The chains of each protocol in [`ProtocolType::all`] in the addressbook.
+    #[rustfmt::skip]
+    fn deployments() -> Vec<Vec<Chain>> {
         let mainnet = || vec![Mainnet, Rinkeby, Ropsten, Goerli, Kovan];
         let l2 = || vec![Optimism, Arbitrum];
         let l2_t = || vec![OptimismGoerli, OptimismKovan, ArbitrumTestnet];
@@ -203,16 +449,24 @@ mod tests {
         let polygon = || vec![Polygon, PolygonMumbai];
         let avax = || vec![Avalanche, AvalancheFuji];
 
-        #[rustfmt::skip]
-        let chains = vec![
-            /*  UniswapV2   */ mainnet(),
-            /*  UniswapV3   */ vec![mainnet(), polygon(), l2(), l2_t()].concat(),
-            /*  Sushiswap   */ vec![vec![Fantom, Moonriver, Moonbeam, XDai], mainnet(), polygon(), bsc(), avax()].concat(),
-            /* Pancakeswap  */ bsc(),
+        vec![
+            /*  UniswapV2   */ [mainnet(), l2(), vec![Polygon, BinanceSmartChain, Avalanche]].concat(),
+            /*  UniswapV3   */ [mainnet(), polygon(), l2(), l2_t(), vec![BinanceSmartChain, Avalanche]].concat(),
+            /*  Sushiswap   */ [vec![Fantom, Moonriver, Moonbeam, XDai], mainnet(), polygon(), bsc(), avax()].concat(),
+            /* Pancakeswap  */ [bsc(), vec![Mainnet]].concat(),
+            /*PancakeswapV3 */ vec![BinanceSmartChain, Mainnet],
             /*  Quickswap   */ polygon(),
             /*  Spookyswap  */ vec![Fantom, FantomTestnet],
             /*  Traderjoe   */ avax(),
-        ];
+            /*  Velodrome   */ vec![Optimism],
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "addresses")]
+    fn test_addresses() {
+        let protocols = ProtocolType::all();
+        let chains = deployments();
 
         assert_eq!(protocols.len(), chains.len());
 
@@ -224,4 +478,58 @@ mod tests {
             }
         }
     }
+
+    /// Checks that the factory and router are deployed, and that the pair code hash of Uniswap V2
+    /// forks matches the pairs created by the factory, on the chains whose RPC URL is set in
+    /// `UNISWAP_RS_RPC_URL_<chain id>`, like `UNISWAP_RS_RPC_URL_42161` for Arbitrum.
+    #[tokio::test]
+    #[ignore = "needs the RPC URLs of the chains to check"]
+    #[cfg(all(feature = "addresses", feature = "v2"))]
+    async fn test_deployments_fork() {
+        use crate::v2::Factory;
+        use ethers_providers::{Http, Middleware, Provider};
+        use std::sync::Arc;
+
+        let mut checked = 0;
+        for (protocol, chains) in ProtocolType::all().into_iter().zip(deployments()) {
+            for chain in chains {
+                let url = match std::env::var(format!("UNISWAP_RS_RPC_URL_{}", chain as u64)) {
+                    Ok(url) => url,
+                    Err(_) => continue,
+                };
+                let client = Arc::new(Provider::<Http>::try_from(url).unwrap());
+                let (factory, router) = protocol.addresses(chain);
+                for address in [factory, router] {
+                    let code = client.get_code(address, None).await.unwrap();
+                    assert!(!code.is_empty(), "{protocol} on {chain}: no code at {address:?}");
+                }
+
+                // Solidly pairs are salted with their stability too
+                if protocol.is_v2() && !protocol.is_solidly() {
+                    let factory = Factory::new_with_chain(client, chain, protocol).unwrap();
+                    let hash = factory.detect_pair_code_hash().await.unwrap();
+                    assert_eq!(hash, protocol.pair_code_hash(Some(chain)), "{protocol} on {chain}");
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 0, "no RPC URL is set");
+    }
+
+    #[test]
+    fn test_names() {
+        for protocol in builtins() {
+            assert_eq!(protocol.to_string().parse::<ProtocolType>().unwrap(), protocol);
+        }
+        assert_eq!(UniswapV2.to_string(), "uniswap-v2");
+        assert_eq!(Velodrome { stable: true }.to_string(), "velodrome-stable");
+        assert_eq!("UniswapV3".parse::<ProtocolType>().unwrap(), UniswapV3);
+        assert_eq!("PANCAKESWAP_V3".parse::<ProtocolType>().unwrap(), PancakeswapV3);
+        assert_eq!("pancakeswap-v2".parse::<ProtocolType>().unwrap(), Pancakeswap);
+
+        let err = "uniswap-v4".parse::<ProtocolType>().unwrap_err();
+        assert!(matches!(&err, Error::UnknownProtocol(name) if name == "uniswap-v4"));
+        assert!(err.to_string().contains("uniswap-v2, uniswap-v3, sushiswap"));
+        assert!(err.to_string().ends_with("velodrome, velodrome-stable"));
+    }
 }
@@ -0,0 +1,380 @@
+use super::Protocol;
+use crate::{
+    common::execute_swap,
+    errors::{Error, Result},
+    v2::Trade,
+    Amount, Deadline, Recipient, Route, Slippage, SwapOptions, SwapReceipt, TxOptions,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::types::{Address, U256};
+use ethers_providers::Middleware;
+use std::{fmt, time::Duration};
+
+/// The deadline used by default by [SwapBuilder]: 20 minutes after the latest block.
+pub const DEFAULT_SWAP_DEADLINE: Duration = Duration::from_secs(20 * 60);
+
+/// A builder of a swap through a [Protocol], created with [`Protocol::swap_builder`] or
+/// [`Protocol::swap_path`].
+///
+/// Only the amount must be set. By default, the output is sent to the client's default sender,
+/// the deadline is [20 minutes][DEFAULT_SWAP_DEADLINE] after the latest block, and the slippage
+/// is [0.5%][Slippage::HALF_PERCENT]. The native token is replaced with the chain's wrapped native
+/// token, unless [overridden][Self::weth].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use uniswap_rs::{ethers_core::types::Address, Amount, Protocol};
+/// # async fn foo<M: uniswap_rs::ethers_providers::Middleware>(
+/// #     protocol: Protocol<M>,
+/// #     token_in: Address,
+/// #     token_out: Address,
+/// # ) -> uniswap_rs::errors::Result<()> {
+/// let receipt = protocol
+///     .swap_builder(token_in, token_out)
+///     .amount(Amount::exact_in(1_000_000))
+///     .slippage(1.0)
+///     .max_price_impact_bps(300)
+///     .execute()
+///     .await?;
+/// # Ok(()) }
+/// ```
+#[must_use = "builders do nothing unless built"]
+pub struct SwapBuilder<'a, M> {
+    protocol: &'a Protocol<M>,
+    route: Route,
+    amount: Option<Amount>,
+    slippage: Slippage,
+    recipient: Recipient,
+    deadline: Deadline,
+    weth: Option<Address>,
+    options: SwapOptions,
+    confirmations: usize,
+}
+
+impl<M> fmt::Debug for SwapBuilder<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwapBuilder")
+            .field("protocol", &self.protocol)
+            .field("route", &self.route)
+            .field("amount", &self.amount)
+            .field("slippage", &self.slippage)
+            .field("recipient", &self.recipient)
+            .field("deadline", &self.deadline)
+            .field("weth", &self.weth)
+            .field("options", &self.options)
+            .field("confirmations", &self.confirmations)
+            .finish()
+    }
+}
+
+impl<'a, M: Middleware> SwapBuilder<'a, M> {
+    /// Creates a new builder of a swap through `route`, with the default parameters.
+    pub fn new(protocol: &'a Protocol<M>, route: impl Into<Route>) -> Self {
+        Self {
+            protocol,
+            route: route.into(),
+            amount: None,
+            slippage: Slippage::HALF_PERCENT,
+            recipient: Recipient::Signer,
+            deadline: Deadline::FromNow(DEFAULT_SWAP_DEADLINE),
+            weth: None,
+            options: SwapOptions::new(),
+            confirmations: 1,
+        }
+    }
+
+    /// Sets the amount to swap.
+    pub fn amount(mut self, amount: Amount) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Sets the slippage tolerance. Floats are interpreted as percentage points.
+    pub fn slippage(mut self, slippage_tolerance: impl Into<Slippage>) -> Self {
+        self.slippage = slippage_tolerance.into();
+        self
+    }
+
+    /// Sets the recipient of the output.
+    pub fn recipient(mut self, to: impl Into<Recipient>) -> Self {
+        self.recipient = to.into();
+        self
+    }
+
+    /// Sets the deadline.
+    pub fn deadline(mut self, deadline: impl Into<Deadline>) -> Self {
+        self.deadline = deadline.into();
+        self
+    }
+
+    /// Sets the wrapped native token which replaces the native token, instead of the chain's.
+    pub fn weth(mut self, weth: Address) -> Self {
+        self.weth = Some(weth);
+        self
+    }
+
+    /// Sets the maximum price impact allowed for the swap, in basis points. See
+    /// [`SwapOptions::max_price_impact_bps`].
+    pub fn max_price_impact_bps(mut self, max_price_impact_bps: u32) -> Self {
+        self.options.max_price_impact_bps = Some(max_price_impact_bps);
+        self
+    }
+
+    /// Sets the transaction options applied to the swap. See [`SwapOptions::tx`].
+    pub fn tx(mut self, tx: TxOptions) -> Self {
+        self.options.tx = tx;
+        self
+    }
+
+    /// Replaces all the swap options, including the ones set with the other setters.
+    pub fn options(mut self, options: SwapOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the number of confirmations waited for by [`execute`][Self::execute], `1` by default.
+    pub fn confirmations(mut self, confirmations: usize) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Returns the contract call of the swap. See [`V2Protocol::swap`] for more details.
+    ///
+    /// Returns [`Error::MissingAmount`] if the amount is not set, and
+    /// [`Error::UnsupportedProtocol`] for Uniswap V3.
+    ///
+    /// [`V2Protocol::swap`]: crate::v2::Protocol::swap
+    pub async fn build(self) -> Result<ContractCall<M, Vec<U256>>> {
+        let amount = self.amount.ok_or(Error::MissingAmount)?;
+        match self.protocol {
+            Protocol::V2(p) => {
+                p.swap(
+                    amount,
+                    self.slippage,
+                    self.route,
+                    self.recipient,
+                    self.deadline,
+                    self.weth,
+                    self.options,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Protocol::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// Returns the contract call of the swap through the router's `SupportingFeeOnTransferTokens`
+    /// swap methods, for tokens which take a fee on transfer. See
+    /// [`V2Protocol::swap_supporting_fee_on_transfer`] for more details.
+    ///
+    /// Returns [`Error::MissingAmount`] if the amount is not set, and
+    /// [`Error::UnsupportedProtocol`] for Uniswap V3.
+    ///
+    /// [`V2Protocol::swap_supporting_fee_on_transfer`]: crate::v2::Protocol::swap_supporting_fee_on_transfer
+    pub async fn build_supporting_fee_on_transfer(self) -> Result<ContractCall<M, ()>> {
+        let amount = self.amount.ok_or(Error::MissingAmount)?;
+        match self.protocol {
+            Protocol::V2(p) => {
+                p.swap_supporting_fee_on_transfer(
+                    amount,
+                    self.slippage,
+                    self.route,
+                    self.recipient,
+                    self.deadline,
+                    self.weth,
+                    self.options,
+                )
+                .await
+            }
+            #[cfg(feature = "v3")]
+            Protocol::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// Quotes the swap, returning the resulting [Trade] with its price impact.
+    ///
+    /// Returns [`Error::MissingAmount`] if the amount is not set, and
+    /// [`Error::UnsupportedProtocol`] for Uniswap V3.
+    pub async fn quote(self) -> Result<Trade> {
+        let amount = self.amount.ok_or(Error::MissingAmount)?;
+        match self.protocol {
+            Protocol::V2(p) => p.quote_swap(amount, self.route, self.weth).await,
+            #[cfg(feature = "v3")]
+            Protocol::V3(_) => Err(Error::UnsupportedProtocol),
+        }
+    }
+
+    /// Builds the swap, sends it and waits for its confirmations. See
+    /// [`Protocol::execute_swap`].
+    pub async fn execute(self) -> Result<SwapReceipt> {
+        let (protocol, confirmations) = (self.protocol, self.confirmations);
+        let call = self.build().await?;
+        execute_swap(protocol.client().as_ref(), call, confirmations).await
+    }
+
+    /// Builds the swap with
+    /// [`build_supporting_fee_on_transfer`][Self::build_supporting_fee_on_transfer], sends it and
+    /// waits for its confirmations. See [`Protocol::execute_swap`].
+    pub async fn execute_supporting_fee_on_transfer(self) -> Result<SwapReceipt> {
+        let (protocol, confirmations) = (self.protocol, self.confirmations);
+        let call = self.build_supporting_fee_on_transfer().await?;
+        execute_swap(protocol.client().as_ref(), call, confirmations).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contracts::bindings::i_uniswap_v2_router_02::{
+            SwapExactTokensForTokensCall, SwapExactTokensForTokensSupportingFeeOnTransferTokensCall,
+        },
+        testing::MockDex,
+        ProtocolType,
+    };
+    use ethers_core::abi::AbiDecode;
+    use ethers_providers::Provider;
+    use std::sync::Arc;
+
+    const TOKEN_A: Address = Address::repeat_byte(0x0a);
+    const TOKEN_B: Address = Address::repeat_byte(0x0b);
+    const SENDER: Address = Address::repeat_byte(0x99);
+
+    fn setup_with(sender: Option<Address>) -> (Protocol<Provider<MockDex>>, MockDex) {
+        let (mut provider, dex) = MockDex::mocked();
+        if let Some(sender) = sender {
+            provider = provider.with_sender(sender);
+        }
+        let client = Arc::new(provider);
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let protocol = Protocol::new(client, factory, router, ProtocolType::UniswapV2);
+        let factory = protocol.as_v2().unwrap().factory();
+        dex.mock_pair(factory, TOKEN_A, TOKEN_B, U256::exp10(21), U256::exp10(24));
+        dex.set_block(100, 1_000);
+        (protocol, dex)
+    }
+
+    fn setup() -> (Protocol<Provider<MockDex>>, MockDex) {
+        setup_with(Some(SENDER))
+    }
+
+    #[tokio::test]
+    async fn test_defaults() {
+        let (protocol, _dex) = setup();
+        let amount = Amount::exact_in(U256::exp10(18));
+
+        let res = protocol.swap_builder(TOKEN_A, TOKEN_B).build().await;
+        assert!(matches!(res.unwrap_err(), Error::MissingAmount));
+
+        let trade = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).quote().await.unwrap();
+        assert_eq!(trade.amounts.len(), 2);
+
+        let call = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).build().await.unwrap();
+        let call = SwapExactTokensForTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(call.path, vec![TOKEN_A, TOKEN_B]);
+        assert_eq!(call.to, SENDER);
+        assert_eq!(call.deadline, U256::from(1_000 + 20 * 60));
+        assert_eq!(
+            call.amount_out_min,
+            Slippage::HALF_PERCENT.minimum_amount_out(trade.amounts[1])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setters() {
+        let (protocol, _dex) = setup();
+        let to = Address::repeat_byte(0x0c);
+
+        let call = protocol
+            .swap_path([TOKEN_A, TOKEN_B])
+            .amount(Amount::exact_in(U256::exp10(18)))
+            .slippage(100.0)
+            .recipient(to)
+            .deadline(U256::from(2_000))
+            .tx(TxOptions::new().gas_limit(500_000.into()))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(call.tx.gas(), Some(&U256::from(500_000)));
+        let call = SwapExactTokensForTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(call.to, to);
+        assert_eq!(call.deadline, U256::from(2_000));
+        assert_eq!(call.amount_out_min, U256::zero());
+
+        let res = protocol
+            .swap_path([TOKEN_A, TOKEN_B])
+            .amount(Amount::exact_in(U256::exp10(20)))
+            .max_price_impact_bps(100)
+            .build()
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::PriceImpactTooHigh { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_fee_on_transfer() {
+        let (protocol, _dex) = setup();
+        let amount = Amount::exact_in(U256::exp10(18));
+
+        let trade = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).quote().await.unwrap();
+        let call: ContractCall<_, ()> = protocol
+            .swap_builder(TOKEN_A, TOKEN_B)
+            .amount(amount)
+            .build_supporting_fee_on_transfer()
+            .await
+            .unwrap();
+        let calldata = call.calldata().unwrap();
+        let call = SwapExactTokensForTokensSupportingFeeOnTransferTokensCall::decode(calldata);
+        let call = call.unwrap();
+        assert_eq!(call.path, vec![TOKEN_A, TOKEN_B]);
+        assert_eq!(
+            call.amount_out_min,
+            Slippage::HALF_PERCENT.minimum_amount_out(trade.amounts[1])
+        );
+
+        let res = protocol
+            .swap_builder(TOKEN_A, TOKEN_B)
+            .amount(Amount::ExactOut(U256::exp10(18)))
+            .build_supporting_fee_on_transfer()
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::FeeOnTransferExactOut));
+    }
+
+    #[tokio::test]
+    async fn test_read_only() {
+        let (protocol, _dex) = setup_with(None);
+        assert!(protocol.is_read_only());
+        let amount = Amount::exact_in(U256::exp10(18));
+
+        let trade = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).quote().await.unwrap();
+        assert_eq!(trade.amounts.len(), 2);
+
+        let to = Address::repeat_byte(0x0c);
+        let call = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).recipient(to).build();
+        assert!(call.await.is_ok());
+
+        let res = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).recipient(to).execute();
+        assert!(matches!(res.await.unwrap_err(), Error::SignerRequired(_)));
+
+        let router = protocol.router_address();
+        let res = protocol.approve_if_needed(TOKEN_A, router, U256::exp10(18));
+        assert!(matches!(res.await.unwrap_err(), Error::SignerRequired(_)));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "v3")]
+    async fn test_v3_unsupported() {
+        let (provider, _dex) = MockDex::mocked();
+        let (factory, router, quoter) =
+            (Address::repeat_byte(0x11), Address::repeat_byte(0x22), Address::repeat_byte(0x33));
+        let v3 = crate::v3::Protocol::new(Arc::new(provider), factory, router, quoter);
+        let protocol = Protocol::V3(v3);
+        let amount = Amount::exact_in(U256::exp10(18));
+
+        let res = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).quote().await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+        let res = protocol.swap_builder(TOKEN_A, TOKEN_B).amount(amount).build().await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+    }
+}
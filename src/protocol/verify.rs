@@ -0,0 +1,142 @@
+//! Checks of a protocol's configured addresses against the chain.
+
+use std::fmt;
+
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_contract::builders::ContractCall;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_core::types::Address;
+#[cfg(any(feature = "v2", feature = "v3"))]
+use ethers_providers::Middleware;
+
+/// The outcome of a [`Check`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CheckStatus {
+    /// The deployment is as configured.
+    Passed,
+
+    /// The deployment is not as configured, or could not be queried.
+    Failed,
+
+    /// The check does not apply, like when the expected value is not known.
+    Skipped,
+}
+
+/// A single check of a protocol's deployment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Check {
+    /// The name of the check.
+    pub name: &'static str,
+
+    /// The outcome of the check.
+    pub status: CheckStatus,
+
+    /// What was checked, or why it failed or was skipped.
+    pub details: String,
+}
+
+/// The report of checking a protocol's configured addresses against the chain, returned by
+/// `Protocol::verify`.
+///
+/// Its [`Display`][fmt::Display] implementation prints one check per line, for logging.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VerificationReport {
+    /// The checks, in the order they were made.
+    pub checks: Vec<Check>,
+}
+
+impl VerificationReport {
+    /// Returns whether no check failed.
+    pub fn is_ok(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    /// Returns the checks which failed.
+    pub fn failed(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|check| check.status == CheckStatus::Failed)
+    }
+
+    /// Returns the check named `name`.
+    pub fn get(&self, name: &str) -> Option<&Check> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    pub(crate) fn push(&mut self, name: &'static str, status: CheckStatus, details: String) {
+        self.checks.push(Check { name, status, details });
+    }
+
+    /// Checks that `address` has code deployed.
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    pub(crate) async fn check_code<M: Middleware>(
+        &mut self,
+        client: &M,
+        name: &'static str,
+        address: Address,
+    ) {
+        match client.get_code(address, None).await {
+            Ok(code) if code.is_empty() => {
+                self.push(name, CheckStatus::Failed, format!("no code at {address:?}"))
+            }
+            Ok(code) => self.push(
+                name,
+                CheckStatus::Passed,
+                format!("{} bytes of code at {address:?}", code.len()),
+            ),
+            Err(e) => self.push(name, CheckStatus::Failed, e.to_string()),
+        }
+    }
+
+    /// Checks that `call` returns `expected`, skipping the check if it is not known.
+    #[cfg(any(feature = "v2", feature = "v3"))]
+    pub(crate) async fn check_call<M: Middleware>(
+        &mut self,
+        name: &'static str,
+        call: ContractCall<M, Address>,
+        expected: Option<Address>,
+    ) {
+        let expected = match expected {
+            Some(expected) => expected,
+            None => {
+                let details = "expected address is not known".to_string();
+                return self.push(name, CheckStatus::Skipped, details);
+            }
+        };
+        match call.call().await {
+            Ok(actual) if actual == expected => {
+                self.push(name, CheckStatus::Passed, format!("{actual:?}"))
+            }
+            Ok(actual) => self.push(
+                name,
+                CheckStatus::Failed,
+                format!("expected {expected:?}, got {actual:?}"),
+            ),
+            Err(e) => self.push(name, CheckStatus::Failed, e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Passed => "ok",
+            Self::Failed => "FAILED",
+            Self::Skipped => "skipped",
+        })
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, check) in self.checks.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[{}] {}: {}", check.status, check.name, check.details)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    contracts::bindings::ierc20::IERC20,
+    errors::{Error, Result},
+};
+use ethers_core::{
+    types::{Address, TransactionReceipt, U256},
+    utils::{Anvil, AnvilInstance},
+};
+use ethers_middleware::SignerMiddleware;
+use ethers_providers::{Http, Middleware, PendingTransaction, Provider, ProviderError};
+use ethers_signers::{LocalWallet, Signer};
+use std::{fmt, sync::Arc};
+
+#[cfg(all(feature = "deploy", feature = "v2"))]
+use crate::deploy::{V2Bytecode, V2Deployment};
+#[cfg(all(feature = "deploy", feature = "v3"))]
+use crate::deploy::{V3Bytecode, V3Deployment};
+#[cfg(all(any(feature = "addresses", feature = "deploy"), feature = "v2"))]
+use crate::Protocol;
+#[cfg(all(feature = "addresses", feature = "v2"))]
+use crate::ProtocolType;
+
+/// The private key of the first default Anvil account.
+pub(crate) const DEFAULT_KEY: &str =
+    "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Returns a wallet of the first default Anvil account, without a chain id.
+pub(crate) fn default_wallet() -> LocalWallet {
+    DEFAULT_KEY.parse().expect("valid private key")
+}
+
+/// The environment variable read by [`Fork::from_env`].
+pub const FORK_URL_VAR: &str = "UNISWAP_RS_FORK_URL";
+
+/// The client of a [Fork], signing with one of its funded accounts.
+pub type ForkClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// An Anvil node, spawned by the harness or already running, and a client signing with one of its
+/// funded accounts.
+///
+/// A spawned node is killed when the fork is dropped.
+pub struct Fork {
+    /// The client.
+    client: Arc<ForkClient>,
+
+    /// The spawned node, if any.
+    anvil: Option<AnvilInstance>,
+}
+
+impl fmt::Debug for Fork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fork")
+            .field("address", &self.address())
+            .field("endpoint", &self.anvil.as_ref().map(AnvilInstance::endpoint))
+            .finish()
+    }
+}
+
+impl Fork {
+    /// Spawns an Anvil node, forking `fork_url` if provided, or starting from an empty chain.
+    ///
+    /// # Panics
+    ///
+    /// If the `anvil` binary is not found in `PATH`, or if the node fails to start.
+    pub fn spawn(fork_url: Option<&str>) -> Result<Self> {
+        let anvil = match fork_url {
+            Some(url) => Anvil::new().fork(url),
+            None => Anvil::new(),
+        };
+        Self::spawn_with(anvil)
+    }
+
+    /// Spawns a node configured with `anvil`, like forking at a given block, signing with its first
+    /// account.
+    ///
+    /// # Panics
+    ///
+    /// If the `anvil` binary is not found, or if the node fails to start.
+    pub fn spawn_with(anvil: Anvil) -> Result<Self> {
+        let anvil = anvil.spawn();
+        let provider = http_provider(&anvil.endpoint())?;
+        let wallet = LocalWallet::from(anvil.keys()[0].clone()).with_chain_id(anvil.chain_id());
+        let client = Arc::new(SignerMiddleware::new(provider, wallet));
+        Ok(Self { client, anvil: Some(anvil) })
+    }
+
+    /// Connects to the Anvil node at `url`, already running, signing with its first default
+    /// account.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let provider = http_provider(url)?;
+        let chain_id = provider.get_chainid().await?.as_u64();
+        let wallet = default_wallet();
+        let client = Arc::new(SignerMiddleware::new(provider, wallet.with_chain_id(chain_id)));
+        Ok(Self { client, anvil: None })
+    }
+
+    /// [Connects][Self::connect] to the node at the URL in the [`UNISWAP_RS_FORK_URL`] environment
+    /// variable, usually a local mainnet fork, or [spawns][Self::spawn] a node from an empty chain
+    /// if the variable is not set.
+    ///
+    /// # Panics
+    ///
+    /// If the variable is not set and the node fails to spawn, like [`spawn`][Self::spawn].
+    ///
+    /// [`UNISWAP_RS_FORK_URL`]: FORK_URL_VAR
+    pub async fn from_env() -> Result<Self> {
+        match std::env::var(FORK_URL_VAR) {
+            Ok(url) => Self::connect(&url).await,
+            Err(_) => Self::spawn(None),
+        }
+    }
+
+    /// Returns a pointer to the client.
+    pub fn client(&self) -> Arc<ForkClient> {
+        self.client.clone()
+    }
+
+    /// Returns a reference to the provider, which does not sign.
+    pub fn provider(&self) -> &Provider<Http> {
+        self.client.inner()
+    }
+
+    /// Returns the address of the signer.
+    pub fn address(&self) -> Address {
+        self.client.address()
+    }
+
+    /// Returns the chain ID of the node.
+    pub fn chain_id(&self) -> u64 {
+        self.client.signer().chain_id()
+    }
+
+    /// Returns the spawned node, if the fork was not [connected][Self::connect] to an existing
+    /// one.
+    pub fn anvil(&self) -> Option<&AnvilInstance> {
+        self.anvil.as_ref()
+    }
+
+    /// Sets the ether balance of `address` to `amount`.
+    pub async fn fund(&self, address: Address, amount: U256) -> Result<()> {
+        self.provider().request::<_, ()>("anvil_setBalance", (address, amount)).await?;
+        Ok(())
+    }
+
+    /// Allows sending transactions from `address` without its private key, through the
+    /// [provider][Self::provider].
+    pub async fn impersonate(&self, address: Address) -> Result<()> {
+        self.provider().request::<_, ()>("anvil_impersonateAccount", [address]).await?;
+        Ok(())
+    }
+
+    /// Stops [impersonating][Self::impersonate] `address`.
+    pub async fn stop_impersonating(&self, address: Address) -> Result<()> {
+        self.provider().request::<_, ()>("anvil_stopImpersonatingAccount", [address]).await?;
+        Ok(())
+    }
+
+    /// Transfers `amount` of `token` from `holder` to `to` by impersonating `holder`, like a whale
+    /// or a pair of the token, which is also funded with ether for the gas.
+    pub async fn seed_token(
+        &self,
+        token: Address,
+        holder: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<()> {
+        let provider = Arc::new(self.provider().clone());
+        if provider.get_balance(holder, None).await? < U256::exp10(18) {
+            self.fund(holder, U256::exp10(18)).await?;
+        }
+        self.impersonate(holder).await?;
+        let call = IERC20::new(token, provider.clone()).transfer(to, amount).from(holder);
+        confirm(call.send().await?).await?;
+        self.stop_impersonating(holder).await
+    }
+
+    /// Returns the protocol whose addresses are found in the [addressbook] for the forked chain.
+    /// See [`Protocol::new_from_client`].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(all(feature = "addresses", feature = "v2"))]
+    pub async fn protocol(&self, protocol: ProtocolType) -> Result<Protocol<ForkClient>> {
+        Protocol::new_from_client(self.client(), protocol).await
+    }
+
+    /// Deploys WETH9 and the Uniswap V2 contracts from `bytecode`, for testing on a chain on which
+    /// they are not deployed, like one [spawned][Self::spawn] without a fork URL. See
+    /// [`deploy::v2`][crate::deploy::v2].
+    #[cfg(all(feature = "deploy", feature = "v2"))]
+    pub async fn deploy_v2(&self, bytecode: &V2Bytecode) -> Result<V2Deployment<ForkClient>> {
+        crate::deploy::v2(self.client(), bytecode).await
+    }
+
+    /// Returns a ready Uniswap V2 protocol: the one of the [addressbook] if it is deployed on the
+    /// node's chain, like on a mainnet fork, or one [deployed][Self::deploy_v2] from the
+    /// [canonical bytecode][V2Bytecode::canonical] otherwise.
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(all(feature = "deploy", feature = "v2"))]
+    pub async fn uniswap_v2(&self) -> Result<Protocol<ForkClient>> {
+        #[cfg(feature = "addresses")]
+        if let Ok(protocol) = self.protocol(ProtocolType::UniswapV2).await {
+            let code = self.provider().get_code(protocol.factory_address(), None).await?;
+            if !code.is_empty() {
+                return Ok(protocol);
+            }
+        }
+        Ok(self.deploy_v2(&V2Bytecode::canonical()?).await?.protocol())
+    }
+
+    /// Deploys WETH9 and the Uniswap V3 contracts from `bytecode`. See
+    /// [`deploy::v3`][crate::deploy::v3].
+    #[cfg(all(feature = "deploy", feature = "v3"))]
+    pub async fn deploy_v3(&self, bytecode: &V3Bytecode) -> Result<V3Deployment<ForkClient>> {
+        crate::deploy::v3(self.client(), bytecode).await
+    }
+}
+
+fn http_provider(url: &str) -> Result<Provider<Http>> {
+    Provider::<Http>::try_from(url).map_err(|e| ProviderError::CustomError(e.to_string()).into())
+}
+
+/// Waits for `pending` to be included, returning an error if it reverted.
+async fn confirm(pending: PendingTransaction<'_, Http>) -> Result<TransactionReceipt> {
+    let tx_hash = *pending;
+    let receipt = pending.await?.ok_or(Error::TransactionDropped(tx_hash))?;
+    if receipt.status == Some(0.into()) {
+        return Err(Error::ContractError(format!("Transaction {tx_hash:?} reverted")));
+    }
+    Ok(receipt)
+}
@@ -0,0 +1,479 @@
+use async_trait::async_trait;
+use ethers_contract::EthCall;
+use ethers_core::{
+    abi::{self, ParamType, Token, Tokenize},
+    types::{Address, Block, Bytes, TxHash, U256, U64},
+    utils::{hex, id},
+};
+use ethers_providers::{JsonRpcClient, MockError, MockProvider, Provider, ProviderError};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+#[cfg(feature = "v3")]
+use crate::{
+    contracts::bindings::{i_quoter::QuoteExactInputSingleCall, i_uniswap_v3_pool::Slot0Call},
+    v3::FeeAmount,
+};
+#[cfg(feature = "v2")]
+use crate::{
+    contracts::bindings::{
+        i_solidly_factory::GetPairCall as SolidlyGetPairCall, i_uniswap_v2_factory::GetPairCall,
+        i_uniswap_v2_pair::GetReservesCall,
+    },
+    v2::{Factory as V2Factory, Library},
+};
+#[cfg(feature = "v2")]
+use ethers_providers::Middleware;
+
+/// `aggregate3((address,bool,bytes)[])`
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// The selector of `Error(string)`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The data returned by a mocked call, or the data it reverts with.
+type Response = std::result::Result<Bytes, Bytes>;
+
+/// The state shared by the clones of a [MockDex].
+#[derive(Debug)]
+struct State {
+    /// The responses of the mocked calls, keyed by target and calldata.
+    calls: HashMap<(Address, Bytes), Response>,
+    /// The chain ID.
+    chain_id: u64,
+    /// The number of the latest block.
+    block_number: u64,
+    /// The timestamp of the latest block.
+    timestamp: u64,
+}
+
+/// A mock JSON-RPC client which answers `eth_call` requests from responses programmed by call,
+/// rather than in the order of the requests like [MockProvider].
+///
+/// The responses are keyed by the target of the call and its calldata, that is its selector and
+/// its ABI-encoded arguments, so that they do not depend on the order in which the calls are made,
+/// nor on whether they are aggregated into a Multicall3 `aggregate3` request: the calls of an
+/// `aggregate3` request are answered one by one, like the Multicall3 contract would.
+///
+/// `eth_chainId`, `eth_blockNumber` and the latest block are answered from the chain ID and block
+/// set on the client, mainnet and block 1 by default. Any other request, including calls which are not
+/// programmed, is forwarded to an inner [MockProvider], whose responses are [pushed][Self::push]
+/// in LIFO order.
+///
+/// Reverts are returned like a node's JSON-RPC errors, so that they are decoded into the
+/// crate's [errors][crate::errors::Error] like the reverts of a real node.
+#[derive(Clone, Debug)]
+pub struct MockDex {
+    /// The shared state.
+    state: Arc<Mutex<State>>,
+
+    /// The provider which answers the other requests.
+    mock: MockProvider,
+}
+
+impl Default for MockDex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The errors of a [MockDex].
+#[derive(Debug, thiserror::Error)]
+pub enum MockDexError {
+    /// A programmed call reverted. Formatted like a node's JSON-RPC error.
+    #[error("(code: 3, message: execution reverted, data: Some(String(\"0x{}\")))", hex::encode(.0))]
+    Reverted(Bytes),
+
+    /// The request could not be parsed, or its response could not be deserialized.
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
+    /// The request was forwarded to the inner [MockProvider], which failed.
+    #[error(transparent)]
+    Mock(#[from] MockError),
+}
+
+impl From<MockDexError> for ProviderError {
+    fn from(value: MockDexError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(value))
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for MockDex {
+    type Error = MockDexError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, MockDexError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let response = match method {
+            "eth_chainId" => Some(Ok(serde_json::to_value(U256::from(self.lock().chain_id))?)),
+            "eth_blockNumber" => {
+                Some(Ok(serde_json::to_value(U64::from(self.lock().block_number))?))
+            }
+            "eth_getBlockByNumber" if serde_json::to_value(&params)?[0] == "latest" => {
+                let state = self.lock();
+                let block = Block::<TxHash> {
+                    number: Some(state.block_number.into()),
+                    timestamp: state.timestamp.into(),
+                    ..Default::default()
+                };
+                Some(Ok(serde_json::to_value(block)?))
+            }
+            "eth_call" => {
+                let params = serde_json::to_value(&params)?;
+                let tx = &params[0];
+                let to: Address = serde_json::from_value(tx["to"].clone())?;
+                let data = match tx.get("data").or_else(|| tx.get("input")) {
+                    Some(data) => serde_json::from_value(data.clone())?,
+                    None => Bytes::default(),
+                };
+                match self.answer(to, &data) {
+                    Some(Ok(output)) => Some(Ok(serde_json::to_value(output)?)),
+                    Some(Err(revert)) => Some(Err(MockDexError::Reverted(revert))),
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        match response {
+            Some(Ok(value)) => Ok(serde_json::from_value(value)?),
+            Some(Err(e)) => Err(e),
+            None => Ok(self.mock.request(method, params).await?),
+        }
+    }
+}
+
+impl MockDex {
+    /// Creates a new instance on mainnet, at block 1.
+    pub fn new() -> Self {
+        let state = State { calls: HashMap::new(), chain_id: 1, block_number: 1, timestamp: 0 };
+        Self { state: Arc::new(Mutex::new(state)), mock: MockProvider::new() }
+    }
+
+    /// Returns a provider backed by a new instance, and the instance with which to program its
+    /// responses.
+    pub fn mocked() -> (Provider<Self>, Self) {
+        let dex = Self::new();
+        (Provider::new(dex.clone()), dex)
+    }
+
+    /// Returns the inner [MockProvider], which answers the requests which are not programmed.
+    pub fn mock(&self) -> &MockProvider {
+        &self.mock
+    }
+
+    /// Pushes a response to the inner [MockProvider]. See [`MockProvider::push`].
+    pub fn push<T: Serialize + Send + Sync, K: Borrow<T>>(&self, data: K) -> Result<(), MockError> {
+        self.mock.push(data)
+    }
+
+    /// Sets the chain ID.
+    pub fn set_chain_id(&self, chain_id: u64) -> &Self {
+        self.lock().chain_id = chain_id;
+        self
+    }
+
+    /// Sets the number and timestamp of the latest block, also returned by the Multicall3
+    /// `getBlockNumber` and `getCurrentBlockTimestamp` methods.
+    pub fn set_block(&self, number: u64, timestamp: u64) -> &Self {
+        let mut state = self.lock();
+        state.block_number = number;
+        state.timestamp = timestamp;
+        drop(state);
+        self
+    }
+
+    /// Programs `call` to `to` to return `output`.
+    pub fn mock_call<C: EthCall>(&self, to: Address, call: C, output: impl Tokenize) -> &Self {
+        let output = abi::encode(&output.into_tokens());
+        self.insert(to, call.encode().into(), Ok(output.into()))
+    }
+
+    /// Programs `call` to `to` to revert with `data`, like a custom error.
+    pub fn mock_revert<C: EthCall>(&self, to: Address, call: C, data: impl Into<Bytes>) -> &Self {
+        self.insert(to, call.encode().into(), Err(data.into()))
+    }
+
+    /// Programs `call` to `to` to revert with the `reason` string.
+    pub fn mock_revert_reason<C: EthCall>(&self, to: Address, call: C, reason: &str) -> &Self {
+        let data = [&ERROR_SELECTOR[..], &abi::encode(&[Token::String(reason.into())])].concat();
+        self.mock_revert(to, call, data)
+    }
+
+    /// Programs the `name`, `symbol` and `decimals` methods of the ERC20 `token`.
+    pub fn mock_token(&self, token: Address, name: &str, symbol: &str, decimals: u8) -> &Self {
+        use crate::contracts::bindings::ierc20::{DecimalsCall, NameCall, SymbolCall};
+
+        self.mock_call(token, NameCall, name.to_string());
+        self.mock_call(token, SymbolCall, symbol.to_string());
+        self.mock_call(token, DecimalsCall, decimals)
+    }
+
+    /// Programs the `getPair` method of the Uniswap V2 `factory` to return `pair` for the tokens,
+    /// in both orders.
+    #[cfg(feature = "v2")]
+    pub fn mock_get_pair(
+        &self,
+        factory: Address,
+        token_a: Address,
+        token_b: Address,
+        pair: Address,
+    ) -> &Self {
+        self.mock_call(factory, GetPairCall { token_a, token_b }, pair);
+        self.mock_call(factory, GetPairCall { token_a: token_b, token_b: token_a }, pair)
+    }
+
+    /// Programs the `getPair(tokenA, tokenB, stable)` method of the Solidly `factory` to return
+    /// `pair` for the tokens, in both orders.
+    #[cfg(feature = "v2")]
+    pub fn mock_get_pair_solidly(
+        &self,
+        factory: Address,
+        token_a: Address,
+        token_b: Address,
+        stable: bool,
+        pair: Address,
+    ) -> &Self {
+        self.mock_call(factory, SolidlyGetPairCall { token_a, token_b, stable }, pair);
+        let call = SolidlyGetPairCall { token_a: token_b, token_b: token_a, stable };
+        self.mock_call(factory, call, pair)
+    }
+
+    /// Programs the `getReserves` method of the Uniswap V2 `pair` to return the reserves of its
+    /// sorted tokens, last updated at timestamp zero.
+    #[cfg(feature = "v2")]
+    pub fn mock_reserves(&self, pair: Address, reserve_0: U256, reserve_1: U256) -> &Self {
+        self.mock_call(pair, GetReservesCall, (reserve_0, reserve_1, 0u32))
+    }
+
+    /// Programs the pair of two tokens of `factory`, at the address computed with its pair code
+    /// hash, with `reserve_a` of `token_a` and `reserve_b` of `token_b`. Returns the address of
+    /// the pair.
+    ///
+    /// The pair of a Solidly fork is the one of the factory's stable flag.
+    #[cfg(feature = "v2")]
+    pub fn mock_pair<M: Middleware>(
+        &self,
+        factory: &V2Factory<M>,
+        token_a: Address,
+        token_b: Address,
+        reserve_a: U256,
+        reserve_b: U256,
+    ) -> Address {
+        if let Some(stable) = factory.protocol().stable() {
+            return self.mock_solidly_pair(factory, token_a, token_b, stable, reserve_a, reserve_b);
+        }
+        let pair = Library::pair_for(factory, token_a, token_b);
+        self.mock_get_pair(factory.address(), token_a, token_b, pair);
+        self.mock_sorted_reserves(pair, token_a, token_b, reserve_a, reserve_b);
+        pair
+    }
+
+    /// Programs the `stable` or volatile pair of two tokens of the Solidly `factory`, like
+    /// [`mock_pair`][Self::mock_pair].
+    #[cfg(feature = "v2")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn mock_solidly_pair<M: Middleware>(
+        &self,
+        factory: &V2Factory<M>,
+        token_a: Address,
+        token_b: Address,
+        stable: bool,
+        reserve_a: U256,
+        reserve_b: U256,
+    ) -> Address {
+        let pair = Library::pair_for_solidly(factory, token_a, token_b, stable);
+        self.mock_get_pair_solidly(factory.address(), token_a, token_b, stable, pair);
+        self.mock_sorted_reserves(pair, token_a, token_b, reserve_a, reserve_b);
+        pair
+    }
+
+    #[cfg(feature = "v2")]
+    fn mock_sorted_reserves(
+        &self,
+        pair: Address,
+        token_a: Address,
+        token_b: Address,
+        reserve_a: U256,
+        reserve_b: U256,
+    ) {
+        let (reserve_0, reserve_1) = if Library::sort_tokens(token_a, token_b).0 == token_a {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        self.mock_reserves(pair, reserve_0, reserve_1);
+    }
+
+    /// Programs the `slot0` method of the Uniswap V3 `pool`, which is unlocked, with an
+    /// observation cardinality of one and no protocol fee.
+    #[cfg(feature = "v3")]
+    pub fn mock_slot0(&self, pool: Address, sqrt_price_x96: U256, tick: i32) -> &Self {
+        let tick = ethers_core::types::I256::from(tick).into_raw();
+        let output = (sqrt_price_x96, Token::Int(tick), 0u16, 1u16, 1u16, 0u8, true);
+        self.mock_call(pool, Slot0Call, output)
+    }
+
+    /// Programs the `quoteExactInputSingle` method of the Uniswap V3 `QuoterV1` at `quoter` to
+    /// return `amount_out`.
+    #[cfg(feature = "v3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn mock_quote_exact_input_single(
+        &self,
+        quoter: Address,
+        token_in: Address,
+        token_out: Address,
+        fee: FeeAmount,
+        amount_in: U256,
+        sqrt_price_limit_x96: U256,
+        amount_out: U256,
+    ) -> &Self {
+        let call = QuoteExactInputSingleCall {
+            token_in,
+            token_out,
+            fee: fee.as_u32(),
+            amount_in,
+            sqrt_price_limit_x96,
+        };
+        self.mock_call(quoter, call, amount_out)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn insert(&self, to: Address, data: Bytes, response: Response) -> &Self {
+        self.lock().calls.insert((to, data), response);
+        self
+    }
+
+    /// Answers the call of `data` to `to`, or returns None if it is not programmed.
+    fn answer(&self, to: Address, data: &Bytes) -> Option<Response> {
+        let state = self.lock();
+        if let Some(response) = state.calls.get(&(to, data.clone())) {
+            return Some(response.clone());
+        }
+        let uint = |value: u64| Some(Ok(abi::encode(&[Token::Uint(value.into())]).into()));
+        match data.get(..4)? {
+            selector if selector == id("getBlockNumber()") => uint(state.block_number),
+            selector if selector == id("getCurrentBlockTimestamp()") => uint(state.timestamp),
+            selector if selector == AGGREGATE3_SELECTOR => {
+                drop(state);
+                self.aggregate3(&data[4..])
+            }
+            _ => None,
+        }
+    }
+
+    /// Answers the calls of an `aggregate3` request, or returns None if any of them is not
+    /// programmed.
+    fn aggregate3(&self, data: &[u8]) -> Option<Response> {
+        let call = ParamType::Tuple(vec![ParamType::Address, ParamType::Bool, ParamType::Bytes]);
+        let param = ParamType::Array(Box::new(call));
+        let calls = match abi::decode(&[param], data).ok()?.pop()? {
+            Token::Array(calls) => calls,
+            _ => return None,
+        };
+        let mut results = Vec::with_capacity(calls.len());
+        for call in calls {
+            let (target, allow_failure, data) = match call {
+                Token::Tuple(call) => match &call[..] {
+                    [Token::Address(target), Token::Bool(allow_failure), Token::Bytes(data)] => {
+                        (*target, *allow_failure, Bytes::from(data.clone()))
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let (success, output) = match self.answer(target, &data)? {
+                Ok(output) => (true, output),
+                Err(revert) if allow_failure => (false, revert),
+                Err(_) => {
+                    let reason = abi::encode(&[Token::String("Multicall3: call failed".into())]);
+                    return Some(Err([&ERROR_SELECTOR[..], &reason].concat().into()));
+                }
+            };
+            results.push(Token::Tuple(vec![Token::Bool(success), Token::Bytes(output.to_vec())]));
+        }
+        Some(Ok(abi::encode(&[Token::Array(results)]).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contracts::bindings::ierc20::{DecimalsCall, IERC20},
+        errors::Error,
+        utils::Multicall,
+    };
+    use ethers_contract::MULTICALL_ADDRESS;
+
+    const TOKEN: Address = Address::repeat_byte(0x0a);
+
+    #[tokio::test]
+    async fn answers_by_call() {
+        let (provider, dex) = MockDex::mocked();
+        let client = Arc::new(provider);
+        dex.mock_token(TOKEN, "Token", "TKN", 6);
+
+        let token = IERC20::new(TOKEN, client.clone());
+        // in any order, any number of times
+        assert_eq!(token.decimals().call().await.unwrap(), 6);
+        assert_eq!(token.symbol().call().await.unwrap(), "TKN");
+        assert_eq!(token.decimals().call().await.unwrap(), 6);
+
+        // not programmed: forwarded to the mock provider
+        dex.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::String("Other".into())]))).unwrap();
+        let other = IERC20::new(Address::repeat_byte(0x0b), client);
+        assert_eq!(other.name().call().await.unwrap(), "Other");
+        assert!(other.name().call().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn answers_aggregate3() {
+        let (provider, dex) = MockDex::mocked();
+        let client = Arc::new(provider);
+        dex.mock_token(TOKEN, "Token", "TKN", 6);
+        let other = Address::repeat_byte(0x0b);
+        dex.mock_revert(other, DecimalsCall, vec![1, 2, 3, 4]);
+        dex.set_block(100, 1_000);
+
+        let mut multicall = Multicall::new_with_address(client.clone(), Some(MULTICALL_ADDRESS));
+        multicall
+            .add_call(IERC20::new(TOKEN, client.clone()).decimals(), false)
+            .add_call(IERC20::new(other, client.clone()).decimals(), true)
+            .add_get_block_number()
+            .add_get_current_block_timestamp();
+        let results = multicall.call_raw().await.unwrap();
+        assert_eq!(results[0], Ok(Token::Uint(6.into())));
+        assert_eq!(results[1], Err(Bytes::from(vec![1, 2, 3, 4])));
+        assert_eq!(results[2], Ok(Token::Uint(100.into())));
+        assert_eq!(results[3], Ok(Token::Uint(1_000.into())));
+
+        // a call which is not allowed to fail reverts the whole request
+        multicall.clear_calls().add_call(IERC20::new(other, client).decimals(), false);
+        multicall.add_get_block_number();
+        let res = multicall.call_raw().await;
+        assert!(
+            matches!(res.unwrap_err(), Error::Reverted(reason) if reason == "Multicall3: call failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn reverts_are_decoded() {
+        let (provider, dex) = MockDex::mocked();
+        dex.mock_revert_reason(TOKEN, DecimalsCall, "UniswapV2Router: EXPIRED");
+        let token = IERC20::new(TOKEN, Arc::new(provider));
+        let res: crate::errors::Result<u8> = token.decimals().call().await.map_err(Into::into);
+        assert!(matches!(res.unwrap_err(), Error::Expired));
+    }
+}
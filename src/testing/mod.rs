@@ -0,0 +1,41 @@
+//! Test fixtures for code built on the protocols.
+//!
+//! [MockDex] answers the calls of the protocols from canned responses keyed by their target and
+//! arguments, for fast and deterministic unit tests:
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use uniswap_rs::{
+//! #     ethers_core::types::{Address, U256}, testing::MockDex, v2::Factory, ProtocolType,
+//! # };
+//! # async fn foo() -> uniswap_rs::errors::Result<()> {
+//! let (provider, dex) = MockDex::mocked();
+//! let address = Address::repeat_byte(0x11);
+//! let factory = Factory::new(Arc::new(provider), address, ProtocolType::UniswapV2);
+//! let (token_a, token_b) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb));
+//! dex.mock_pair(&factory, token_a, token_b, U256::exp10(21), U256::exp10(24));
+//! let reserves = factory.get_reserves_multi(&[(token_a, token_b)]).await?;
+//! assert_eq!(reserves[0].0, U256::exp10(21));
+//! # Ok(()) }
+//! ```
+//!
+//! [Fork] spawns, or connects to, an [Anvil](https://github.com/foundry-rs/foundry) node with a
+//! funded signer for integration tests, and provides the cheatcodes commonly needed to set up a
+//! swap: funding accounts with ether, seeding token balances by impersonating holders, and getting
+//! a ready protocol either from the addressbook of a forked chain or by
+//! [deploying][crate::deploy] fresh Uniswap contracts.
+//!
+//! ```no_run
+//! # use uniswap_rs::{ethers_core::types::U256, testing::Fork};
+//! # async fn foo() -> uniswap_rs::errors::Result<()> {
+//! let fork = Fork::spawn(Some("https://eth.llamarpc.com"))?;
+//! fork.fund(fork.address(), U256::exp10(21)).await?;
+//! let client = fork.client();
+//! # Ok(()) }
+//! ```
+
+mod fork;
+pub use fork::*;
+
+mod mock;
+pub use mock::*;
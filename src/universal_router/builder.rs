@@ -0,0 +1,662 @@
+use super::{Command, MixedRoute, Route, ADDRESS_THIS, CONTRACT_BALANCE, MSG_SENDER};
+use crate::{
+    contracts::bindings::i_universal_router_commands::*,
+    errors::{Error, Result},
+    utils::map_native,
+    Amount, NATIVE_ADDRESS,
+};
+use ethers_core::{
+    abi::{self, Token, Tokenizable, Tokenize},
+    types::{Address, Bytes, U256},
+};
+use std::mem;
+
+/// A builder of Universal Router commands and their inputs, which are passed to the router's
+/// `execute` method.
+///
+/// [`UniversalRouter`][super::UniversalRouter] wraps a builder and its methods, and builds the
+/// call to its contract.
+///
+/// # Example
+///
+/// ```
+/// # use uniswap_rs::{prelude::{*, _ethers::*}, universal_router::{CommandBuilder, ADDRESS_THIS}};
+/// let mut builder = CommandBuilder::new();
+/// let (token, recipient) = (Address::repeat_byte(0x22), Address::repeat_byte(0x33));
+/// let weth = Address::repeat_byte(0x44);
+/// let value = U256::exp10(18);
+/// builder
+///     .wrap_eth(false, ADDRESS_THIS, value)
+///     .v2_swap_exact_in(false, recipient, value, U256::zero(), vec![weth, token], false);
+/// let (commands, inputs) = builder.build();
+/// assert_eq!(commands.len(), 2);
+/// assert_eq!(inputs.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandBuilder {
+    /// The raw command bytes.
+    commands: Vec<u8>,
+
+    /// The raw command inputs.
+    inputs: Vec<Bytes>,
+}
+
+impl CommandBuilder {
+    /// Creates a new empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves capacity for at least additional more elements to be inserted.
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        self.commands.reserve(additional);
+        self.inputs.reserve(additional);
+        self
+    }
+
+    /// Clears the internal buffers, removing all values.
+    pub fn clear(&mut self) -> &mut Self {
+        self.commands.clear();
+        self.inputs.clear();
+        self
+    }
+
+    /// Returns the number of commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns whether the builder does not contain any commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the raw command bytes.
+    pub fn commands(&self) -> &[u8] {
+        &self.commands
+    }
+
+    /// Returns the raw command inputs.
+    pub fn inputs(&self) -> &[Bytes] {
+        &self.inputs
+    }
+
+    /// Add a command binding, and whether it is allowed to revert.
+    pub fn add_command_from_bindings(
+        &mut self,
+        command: IUniversalRouterCommandsCalls,
+        allow_revert: bool,
+    ) -> &mut Self {
+        let command_type = Command::from(&command);
+        let tokens = command.into_tokens();
+        self.add_command(command_type, allow_revert, &tokens)
+    }
+
+    /// Add a command, its arguments, and whether it is allowed to revert.
+    ///
+    /// **Important**: this method is not type-safe. It is recommended to use the `command_name`
+    /// methods instead.
+    pub fn add_command(
+        &mut self,
+        command: Command,
+        allow_revert: bool,
+        args: &[Token],
+    ) -> &mut Self {
+        let command_byte = command.encode(allow_revert);
+        let input = abi::encode(args).into();
+        self.add_command_raw(command_byte, input)
+    }
+
+    /// Add a command, its input bytes, and whether it is allowed to revert.
+    ///
+    /// **Important**: this method is not type-safe. It is recommended to use the `command_name`
+    /// methods instead.
+    pub fn add_command_raw(&mut self, command_byte: u8, input: Bytes) -> &mut Self {
+        self.commands.push(command_byte);
+        self.inputs.push(input);
+        self
+    }
+
+    /// Consumes the internal buffers to build the `commands` and `inputs` arguments of the
+    /// router's `execute` method.
+    pub fn build(&mut self) -> (Bytes, Vec<Bytes>) {
+        let commands = mem::take(&mut self.commands);
+        let inputs = mem::take(&mut self.inputs);
+        (commands.into(), inputs)
+    }
+
+    /// Decodes the `commands` and `inputs` arguments of the router's `execute` method into the
+    /// bindings of each command, and whether it is allowed to revert.
+    ///
+    /// Returns [`Error::CommandInputsMismatch`] if there are not as many commands as inputs, and
+    /// [`Error::InvalidCommand`] if a command is not supported.
+    pub fn decode(
+        commands: &[u8],
+        inputs: &[Bytes],
+    ) -> Result<Vec<(IUniversalRouterCommandsCalls, bool)>> {
+        if commands.len() != inputs.len() {
+            return Err(Error::CommandInputsMismatch {
+                commands: commands.len(),
+                inputs: inputs.len(),
+            });
+        }
+        commands
+            .iter()
+            .zip(inputs)
+            .map(|(&byte, input)| {
+                let (command, allow_revert) = Command::decode(byte);
+                if command.is_invalid() {
+                    return Err(Error::InvalidCommand(byte));
+                }
+                Ok((command.decode_input(input)?, allow_revert))
+            })
+            .collect()
+    }
+
+    /// Appends the commands of a swap of `amount` through `route` to `recipient`.
+    ///
+    /// `amount_limit` is the minimum amount out of an exact input swap, or the maximum amount in
+    /// of an exact output swap.
+    ///
+    /// A path which starts with [`NATIVE_ADDRESS`], or an [`Amount::ExactInNative`] amount,
+    /// wraps the ether sent with the call into `weth` first, which the router then pays the
+    /// swap with; the ether not spent by an exact output swap is refunded to the sender. A path
+    /// which ends with [`NATIVE_ADDRESS`] swaps to the router, which then unwraps the output to
+    /// `recipient`. Otherwise, the sender pays the swap with a Permit2 allowance.
+    ///
+    /// Returns [`Error::InvalidPath`] if the path has fewer than 2 tokens,
+    /// [`Error::NativeMidPath`] if [`NATIVE_ADDRESS`] is in the middle of the path,
+    /// [`Error::SwapToSelf`] if it is at both ends, and [`Error::UnresolvedAmount`] if `amount`
+    /// is a percentage of a balance.
+    pub fn swap(
+        &mut self,
+        route: &Route,
+        amount: Amount,
+        amount_limit: U256,
+        recipient: Address,
+        weth: Address,
+    ) -> Result<&mut Self> {
+        let tokens = route.tokens();
+        if tokens.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        if tokens[1..tokens.len() - 1].contains(&NATIVE_ADDRESS) {
+            return Err(Error::NativeMidPath);
+        }
+        let (amount, exact_input, native_in) = match amount {
+            Amount::ExactIn(amount) => (amount, true, route.token_in() == NATIVE_ADDRESS),
+            Amount::ExactInNative(amount) => (amount, true, true),
+            Amount::ExactOut(amount) => (amount, false, route.token_in() == NATIVE_ADDRESS),
+            Amount::PercentOfBalance { .. } => return Err(Error::UnresolvedAmount),
+        };
+        let native_out = route.token_out() == NATIVE_ADDRESS;
+        if native_in && native_out {
+            return Err(Error::SwapToSelf);
+        }
+
+        let (amount_in, amount_out) =
+            if exact_input { (amount, amount_limit) } else { (amount_limit, amount) };
+        if native_in {
+            self.wrap_eth(false, ADDRESS_THIS, amount_in);
+        }
+        let payer_is_user = !native_in;
+        let to = if native_out { ADDRESS_THIS } else { recipient };
+        match route {
+            Route::V2 { path } => {
+                let mut path = path.clone();
+                map_native(&mut path, weth);
+                if exact_input {
+                    self.v2_swap_exact_in(false, to, amount, amount_limit, path, payer_is_user)
+                } else {
+                    self.v2_swap_exact_out(false, to, amount, amount_limit, path, payer_is_user)
+                }
+            }
+            Route::V3 { path } => {
+                let mut path = path.clone();
+                map_native(path.tokens_mut(), weth);
+                if exact_input {
+                    let path = path.encode();
+                    self.v3_swap_exact_in(false, to, amount, amount_limit, path, payer_is_user)
+                } else {
+                    // exact output paths are reversed
+                    let path = path.reverse().encode();
+                    self.v3_swap_exact_out(false, to, amount, amount_limit, path, payer_is_user)
+                }
+            }
+        };
+        if native_out {
+            self.unwrap_weth(false, recipient, amount_out);
+        }
+        if native_in && !exact_input {
+            self.unwrap_weth(false, MSG_SENDER, U256::zero());
+        }
+        Ok(self)
+    }
+
+    /// Appends the commands of a swap of `amount` through each leg of `route` to `recipient`.
+    ///
+    /// The router holds the output of each leg, and swaps its whole balance of it in the next
+    /// leg. It then sweeps the output of the last leg to `recipient`, or unwraps it if the route
+    /// ends with [`NATIVE_ADDRESS`], so `amount_out_min` only applies to the final output.
+    ///
+    /// A route of a single leg is the same as [`swap`][Self::swap].
+    ///
+    /// Returns [`Error::ExactOutUnsupported`] for exact output amounts, and the errors of
+    /// [`swap`][Self::swap] otherwise.
+    pub fn swap_mixed(
+        &mut self,
+        route: &MixedRoute,
+        amount: Amount,
+        amount_out_min: U256,
+        recipient: Address,
+        weth: Address,
+    ) -> Result<&mut Self> {
+        let (amount_in, native_in) = match amount {
+            Amount::ExactIn(amount) => (amount, route.token_in() == NATIVE_ADDRESS),
+            Amount::ExactInNative(amount) => (amount, true),
+            Amount::ExactOut(_) => return Err(Error::ExactOutUnsupported),
+            Amount::PercentOfBalance { .. } => return Err(Error::UnresolvedAmount),
+        };
+        if let [leg] = route.legs() {
+            return self.swap(leg, amount, amount_out_min, recipient, weth);
+        }
+        let token_out = route.token_out();
+        let native_out = token_out == NATIVE_ADDRESS;
+        if native_in && native_out {
+            return Err(Error::SwapToSelf);
+        }
+
+        if native_in {
+            self.wrap_eth(false, ADDRESS_THIS, amount_in);
+        }
+        for (i, leg) in route.legs().iter().enumerate() {
+            let (amount, payer_is_user) =
+                if i == 0 { (amount_in, !native_in) } else { (CONTRACT_BALANCE, false) };
+            match leg {
+                Route::V2 { path } => {
+                    let mut path = path.clone();
+                    map_native(&mut path, weth);
+                    let min = U256::zero();
+                    self.v2_swap_exact_in(false, ADDRESS_THIS, amount, min, path, payer_is_user)
+                }
+                Route::V3 { path } => {
+                    let mut path = path.clone();
+                    map_native(path.tokens_mut(), weth);
+                    let (path, min) = (path.encode(), U256::zero());
+                    self.v3_swap_exact_in(false, ADDRESS_THIS, amount, min, path, payer_is_user)
+                }
+            };
+        }
+        if native_out {
+            self.unwrap_weth(false, recipient, amount_out_min)
+        } else {
+            self.sweep(false, token_out, recipient, amount_out_min)
+        };
+        Ok(self)
+    }
+
+    /// Appends the commands of an exact input swap split across `legs`, each swapping its amount
+    /// in through its route, to `recipient`.
+    ///
+    /// The router holds the output of each leg, then sweeps it to `recipient`, or unwraps it if
+    /// the routes end with [`NATIVE_ADDRESS`], so `amount_out_min` applies to the combined
+    /// output. Routes which start with [`NATIVE_ADDRESS`] wrap the ether sent with the call into
+    /// `weth` first, for the sum of the amounts.
+    ///
+    /// Returns [`Error::InvalidPath`] if there are no legs or if their routes do not share their
+    /// input and output tokens, and the errors of [`swap`][Self::swap] otherwise.
+    pub fn swap_split(
+        &mut self,
+        legs: &[(Route, U256)],
+        amount_out_min: U256,
+        recipient: Address,
+        weth: Address,
+    ) -> Result<&mut Self> {
+        let (token_in, token_out) = match legs.first() {
+            Some((route, _)) if route.tokens().len() >= 2 => (route.token_in(), route.token_out()),
+            _ => return Err(Error::InvalidPath),
+        };
+        for (route, _) in legs {
+            let tokens = route.tokens();
+            if tokens.len() < 2 || route.token_in() != token_in || route.token_out() != token_out {
+                return Err(Error::InvalidPath);
+            }
+            if tokens[1..tokens.len() - 1].contains(&NATIVE_ADDRESS) {
+                return Err(Error::NativeMidPath);
+            }
+        }
+        let native_in = token_in == NATIVE_ADDRESS;
+        let native_out = token_out == NATIVE_ADDRESS;
+        if native_in && native_out {
+            return Err(Error::SwapToSelf);
+        }
+
+        if native_in {
+            let total = legs
+                .iter()
+                .try_fold(U256::zero(), |total, (_, amount)| total.checked_add(*amount))
+                .ok_or(Error::Overflow)?;
+            self.wrap_eth(false, ADDRESS_THIS, total);
+        }
+        for (leg, amount) in legs {
+            let (amount, min, payer_is_user) = (*amount, U256::zero(), !native_in);
+            match leg {
+                Route::V2 { path } => {
+                    let mut path = path.clone();
+                    map_native(&mut path, weth);
+                    self.v2_swap_exact_in(false, ADDRESS_THIS, amount, min, path, payer_is_user)
+                }
+                Route::V3 { path } => {
+                    let mut path = path.clone();
+                    map_native(path.tokens_mut(), weth);
+                    let path = path.encode();
+                    self.v3_swap_exact_in(false, ADDRESS_THIS, amount, min, path, payer_is_user)
+                }
+            };
+        }
+        if native_out {
+            self.unwrap_weth(false, recipient, amount_out_min)
+        } else {
+            self.sweep(false, token_out, recipient, amount_out_min)
+        };
+        Ok(self)
+    }
+}
+
+// implement commands in a new block so as to not "pollute" docs
+impl CommandBuilder {
+    command_fns!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contracts::bindings::i_universal_router::ExecuteWithCommandsAndInputsCall,
+        v3::{FeeAmount, Path},
+    };
+    use ethers_core::abi::{AbiDecode, AbiEncode};
+
+    const TOKEN: Address = Address::repeat_byte(0x22);
+    const RECIPIENT: Address = Address::repeat_byte(0x33);
+    const WETH: Address = Address::repeat_byte(0x44);
+
+    fn decode(builder: &mut CommandBuilder) -> Vec<IUniversalRouterCommandsCalls> {
+        let (commands, inputs) = builder.build();
+        let calls = CommandBuilder::decode(&commands, &inputs).unwrap();
+        assert!(calls.iter().all(|(_, allow_revert)| !allow_revert));
+        calls.into_iter().map(|(call, _)| call).collect()
+    }
+
+    #[test]
+    fn test_swap() {
+        use IUniversalRouterCommandsCalls::*;
+
+        let mut builder = CommandBuilder::new();
+        let (amount, limit) = (U256::from(100), U256::from(90));
+
+        // ETH -> token on V2, exact input
+        let route = Route::from(vec![NATIVE_ADDRESS, TOKEN]);
+        builder.swap(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH).unwrap();
+        assert_eq!(
+            decode(&mut builder),
+            [
+                WrapEth(WrapEthCall { recipient: ADDRESS_THIS, amount_min: amount }),
+                V2SwapExactIn(V2SwapExactInCall {
+                    recipient: RECIPIENT,
+                    amount_in: amount,
+                    amount_out_min: limit,
+                    path: vec![WETH, TOKEN],
+                    payer_is_user: false,
+                }),
+            ]
+        );
+
+        // token -> ETH on V3, exact output: the path is reversed and the output unwrapped
+        let path = Path::new(&[TOKEN, NATIVE_ADDRESS], &[500]).unwrap();
+        builder.swap(&path.into(), Amount::ExactOut(amount), limit, RECIPIENT, WETH).unwrap();
+        assert_eq!(
+            decode(&mut builder),
+            [
+                V3SwapExactOut(V3SwapExactOutCall {
+                    recipient: ADDRESS_THIS,
+                    amount_out: amount,
+                    amount_in_max: limit,
+                    path: Path::single(WETH, TOKEN, FeeAmount::Low).encode(),
+                    payer_is_user: true,
+                }),
+                UnwrapWeth(UnwrapWethCall { recipient: RECIPIENT, amount_min: amount }),
+            ]
+        );
+
+        // ETH -> token on V3, exact output: the unspent ETH is refunded
+        let route = Route::from(Path::single(NATIVE_ADDRESS, TOKEN, FeeAmount::Low));
+        builder.swap(&route, Amount::ExactOut(amount), limit, RECIPIENT, WETH).unwrap();
+        let calls = decode(&mut builder);
+        assert_eq!(calls[0], WrapEth(WrapEthCall { recipient: ADDRESS_THIS, amount_min: limit }));
+        assert_eq!(
+            calls[2],
+            UnwrapWeth(UnwrapWethCall { recipient: MSG_SENDER, amount_min: 0.into() })
+        );
+
+        let route = Route::from(vec![TOKEN, NATIVE_ADDRESS, WETH]);
+        let res = builder.swap(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH);
+        assert!(matches!(res.unwrap_err(), Error::NativeMidPath));
+        let route = Route::from(vec![NATIVE_ADDRESS, NATIVE_ADDRESS]);
+        let res = builder.swap(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH);
+        assert!(matches!(res.unwrap_err(), Error::SwapToSelf));
+        let res = builder.swap(
+            &route,
+            Amount::PercentOfBalance { bps: 1, exact_input: true },
+            limit,
+            RECIPIENT,
+            WETH,
+        );
+        assert!(matches!(res.unwrap_err(), Error::UnresolvedAmount));
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn test_swap_mixed() {
+        use IUniversalRouterCommandsCalls::*;
+
+        let mut builder = CommandBuilder::new();
+        let (amount, limit) = (U256::from(100), U256::from(90));
+        let usdc = Address::repeat_byte(0x55);
+
+        // ETH -> USDC on V3, then USDC -> token on V2: the router holds the tokens in between
+        let v3 = Route::from(Path::single(NATIVE_ADDRESS, usdc, FeeAmount::Low));
+        let route = MixedRoute::new(vec![v3, vec![usdc, TOKEN].into()]).unwrap();
+        assert!(route.is_mixed());
+        assert_eq!(route.tokens(), [NATIVE_ADDRESS, usdc, TOKEN]);
+        builder.swap_mixed(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH).unwrap();
+        assert_eq!(
+            decode(&mut builder),
+            [
+                WrapEth(WrapEthCall { recipient: ADDRESS_THIS, amount_min: amount }),
+                V3SwapExactIn(V3SwapExactInCall {
+                    recipient: ADDRESS_THIS,
+                    amount_in: amount,
+                    amount_out_min: 0.into(),
+                    path: Path::single(WETH, usdc, FeeAmount::Low).encode(),
+                    payer_is_user: false,
+                }),
+                V2SwapExactIn(V2SwapExactInCall {
+                    recipient: ADDRESS_THIS,
+                    amount_in: CONTRACT_BALANCE,
+                    amount_out_min: 0.into(),
+                    path: vec![usdc, TOKEN],
+                    payer_is_user: false,
+                }),
+                Sweep(SweepCall { token: TOKEN, recipient: RECIPIENT, amount_min: limit }),
+            ]
+        );
+
+        // token -> USDC on V2, then USDC -> ETH on V3: the user pays and the output is unwrapped
+        let v3 = Route::from(Path::single(usdc, NATIVE_ADDRESS, FeeAmount::Low));
+        let route = MixedRoute::new(vec![vec![TOKEN, usdc].into(), v3]).unwrap();
+        builder.swap_mixed(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH).unwrap();
+        let calls = decode(&mut builder);
+        assert!(matches!(&calls[0], V2SwapExactIn(call) if call.payer_is_user));
+        assert!(matches!(&calls[1], V3SwapExactIn(call) if !call.payer_is_user));
+        assert_eq!(
+            calls[2],
+            UnwrapWeth(UnwrapWethCall { recipient: RECIPIENT, amount_min: limit })
+        );
+
+        // a single leg is a plain swap
+        let route = MixedRoute::from(Route::from(vec![TOKEN, usdc]));
+        assert!(!route.is_mixed());
+        builder.swap_mixed(&route, Amount::ExactIn(amount), limit, RECIPIENT, WETH).unwrap();
+        assert!(
+            matches!(&decode(&mut builder)[..], [V2SwapExactIn(call)] if call.recipient == RECIPIENT)
+        );
+
+        let res = builder.swap_mixed(&route, Amount::ExactOut(amount), limit, RECIPIENT, WETH);
+        assert!(matches!(res.unwrap_err(), Error::ExactOutUnsupported));
+        let res = MixedRoute::new(vec![vec![TOKEN, usdc].into(), vec![WETH, TOKEN].into()]);
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+        let res = MixedRoute::new(vec![
+            vec![TOKEN, NATIVE_ADDRESS].into(),
+            vec![NATIVE_ADDRESS, usdc].into(),
+        ]);
+        assert!(matches!(res.unwrap_err(), Error::NativeMidPath));
+        assert!(matches!(MixedRoute::new(vec![]).unwrap_err(), Error::InvalidPath));
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn test_swap_split() {
+        use IUniversalRouterCommandsCalls::*;
+
+        let mut builder = CommandBuilder::new();
+        let limit = U256::from(90);
+
+        // ETH -> token on V2 and V3: the router wraps the total and sweeps the combined output
+        let v3 = Route::from(Path::single(NATIVE_ADDRESS, TOKEN, FeeAmount::Low));
+        let legs = [(vec![NATIVE_ADDRESS, TOKEN].into(), 60.into()), (v3, 40.into())];
+        builder.swap_split(&legs, limit, RECIPIENT, WETH).unwrap();
+        assert_eq!(
+            decode(&mut builder),
+            [
+                WrapEth(WrapEthCall { recipient: ADDRESS_THIS, amount_min: 100.into() }),
+                V2SwapExactIn(V2SwapExactInCall {
+                    recipient: ADDRESS_THIS,
+                    amount_in: 60.into(),
+                    amount_out_min: 0.into(),
+                    path: vec![WETH, TOKEN],
+                    payer_is_user: false,
+                }),
+                V3SwapExactIn(V3SwapExactInCall {
+                    recipient: ADDRESS_THIS,
+                    amount_in: 40.into(),
+                    amount_out_min: 0.into(),
+                    path: Path::single(WETH, TOKEN, FeeAmount::Low).encode(),
+                    payer_is_user: false,
+                }),
+                Sweep(SweepCall { token: TOKEN, recipient: RECIPIENT, amount_min: limit }),
+            ]
+        );
+
+        // token -> ETH: the user pays each leg and the output is unwrapped
+        let legs = [(vec![TOKEN, NATIVE_ADDRESS].into(), 60.into())];
+        builder.swap_split(&legs, limit, RECIPIENT, WETH).unwrap();
+        let calls = decode(&mut builder);
+        assert!(matches!(&calls[0], V2SwapExactIn(call) if call.payer_is_user));
+        assert_eq!(
+            calls[1],
+            UnwrapWeth(UnwrapWethCall { recipient: RECIPIENT, amount_min: limit })
+        );
+
+        let legs = [(vec![TOKEN, WETH].into(), 60.into()), (vec![WETH, TOKEN].into(), 40.into())];
+        let res = builder.swap_split(&legs, limit, RECIPIENT, WETH);
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+        let res = builder.swap_split(&[], limit, RECIPIENT, WETH);
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn test_decode() {
+        let bad = [Command::Invalid.encode(false) | 0x30];
+        let res = CommandBuilder::decode(&bad, &[Bytes::default()]);
+        assert!(matches!(res.unwrap_err(), Error::InvalidCommand(0x30)));
+        let res = CommandBuilder::decode(&[], &[Bytes::default()]);
+        assert!(matches!(
+            res.unwrap_err(),
+            Error::CommandInputsMismatch { commands: 0, inputs: 1 }
+        ));
+    }
+
+    /// Hand-encoded `execute` calls, not taken from mainnet transactions, in the shape of those sent
+    /// by the Uniswap interface, hex-encoded independently of the bindings and the builder: 0.1 ETH
+    /// wrapped and swapped for USDC through the V3 0.05% pool, and 250 USDC swapped for WETH
+    /// through it and unwrapped. The real transactions of the latest mainnet blocks are re-encoded
+    /// by the ignored `test_reencode_fork`.
+    const EXECUTE_CALLS: [&str; 2] = [
+        concat!(
+            "3593564c",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "000000000000000000000000000000000000000000000000000000006553f100",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0b00000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "000000000000000000000000000000000000000000000000016345785d8a0000",
+            "0000000000000000000000000000000000000000000000000000000000000100",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "000000000000000000000000000000000000000000000000016345785d8a0000",
+            "000000000000000000000000000000000000000000000000000000000abc7740",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "000000000000000000000000000000000000000000000000000000000000002b",
+            "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f4a0b86991c6218b36c1",
+            "d19d4a2e9eb0ce3606eb48000000000000000000000000000000000000000000",
+        ),
+        concat!(
+            "3593564c",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "000000000000000000000000000000000000000000000000000000006553f358",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "000c000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000160",
+            "0000000000000000000000000000000000000000000000000000000000000100",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "000000000000000000000000000000000000000000000000000000000ee6b280",
+            "00000000000000000000000000000000000000000000000001cdda4faccd0000",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "000000000000000000000000000000000000000000000000000000000000002b",
+            "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480001f4c02aaa39b223fe8d0a",
+            "0e5c4f27ead9083c756cc2000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "00000000000000000000000000000000000000000000000001cdda4faccd0000",
+        ),
+    ];
+
+    #[test]
+    fn test_reencode() {
+        for calldata in EXECUTE_CALLS {
+            let calldata = ethers_core::utils::hex::decode(calldata).unwrap();
+            let decoded = ExecuteWithCommandsAndInputsCall::decode(&calldata).unwrap();
+            let calls = CommandBuilder::decode(&decoded.commands, &decoded.inputs).unwrap();
+            assert_eq!(calls.len(), 2);
+            let mut builder = CommandBuilder::new();
+            for (call, allow_revert) in calls {
+                builder.add_command_from_bindings(call, allow_revert);
+            }
+            let (commands, inputs) = builder.build();
+            let reencoded =
+                ExecuteWithCommandsAndInputsCall { commands, inputs, deadline: decoded.deadline };
+            assert_eq!(reencoded.encode(), calldata);
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use crate::contracts::bindings::i_universal_router_commands::IUniversalRouterCommandsCalls;
+use crate::{
+    contracts::bindings::i_universal_router_commands::{
+        self as bindings, IUniversalRouterCommandsCalls,
+    },
+    errors::{Error, Result},
+};
+use ethers_contract::EthCall;
+use ethers_core::{abi::AbiDecode, types::Selector};
 
 const MAX_COMMAND: u8 = Command::MASK + 1;
 
@@ -19,6 +26,7 @@ macro_rules! invalid {
 /// A [Universal Router command](https://docs.uniswap.org/contracts/universal-router/technical-reference#command).
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[repr(u8)]
 pub enum Command {
     // 0x00..0x08
@@ -160,11 +168,65 @@ impl Command {
             // outside of valid range
             invalid!() => Self::Invalid,
             // SAFETY: All invalid values are covered in the match arms above.
-            _ => unsafe { std::mem::transmute(byte) },
+            _ => unsafe { std::mem::transmute::<u8, Self>(byte) },
         };
         (command, allow_revert)
     }
 
+    /// Decodes the ABI-encoded `input` of the command into its contract bindings.
+    ///
+    /// Returns [`Error::InvalidCommand`] if the command is [`Command::Invalid`].
+    pub fn decode_input(self, input: impl AsRef<[u8]>) -> Result<IUniversalRouterCommandsCalls> {
+        let selector = self.selector().ok_or(Error::InvalidCommand(self as u8))?;
+        let data = [&selector[..], input.as_ref()].concat();
+        Ok(IUniversalRouterCommandsCalls::decode(data)?)
+    }
+
+    /// Returns the selector of the command's binding, or `None` if it is [`Command::Invalid`].
+    fn selector(self) -> Option<Selector> {
+        use bindings::*;
+
+        let selector = match self {
+            // 0x00..0x08
+            Self::V3SwapExactIn => V3SwapExactInCall::selector(),
+            Self::V3SwapExactOut => V3SwapExactOutCall::selector(),
+            Self::Permit2TransferFrom => Permit2TransferFromCall::selector(),
+            Self::Permit2PermitBatch => Permit2PermitBatchCall::selector(),
+            Self::Sweep => SweepCall::selector(),
+            Self::Transfer => TransferCall::selector(),
+            Self::PayPortion => PayPortionCall::selector(),
+
+            // 0x08..0x10
+            Self::V2SwapExactIn => V2SwapExactInCall::selector(),
+            Self::V2SwapExactOut => V2SwapExactOutCall::selector(),
+            Self::Permit2Permit => Permit2PermitCall::selector(),
+            Self::WrapEth => WrapEthCall::selector(),
+            Self::UnwrapWeth => UnwrapWethCall::selector(),
+            Self::Permit2TransferFromBatch => Permit2TransferFromBatchCall::selector(),
+
+            // 0x10..0x18
+            Self::Seaport => SeaportCall::selector(),
+            Self::LooksRare721 => LooksRare721Call::selector(),
+            Self::Nftx => NftxCall::selector(),
+            Self::Cryptopunks => CryptopunksCall::selector(),
+            Self::LooksRare1155 => LooksRare1155Call::selector(),
+            Self::OwnerCheck721 => OwnerCheck721Call::selector(),
+            Self::OwnerCheck1155 => OwnerCheck1155Call::selector(),
+            Self::SweepErc721 => SweepErc721Call::selector(),
+
+            // 0x18..0x20
+            Self::X2Y2721 => X2Y2721Call::selector(),
+            Self::Sudoswap => SudoswapCall::selector(),
+            Self::Nft20 => Nft20Call::selector(),
+            Self::X2Y21155 => X2Y21155Call::selector(),
+            Self::Foundation => FoundationCall::selector(),
+            Self::SweepErc1155 => SweepErc1155Call::selector(),
+
+            Self::Invalid => return None,
+        };
+        Some(selector)
+    }
+
     /// Returns whether this command is valid.
     #[allow(clippy::match_like_matches_macro)]
     pub const fn is_valid(&self) -> bool {
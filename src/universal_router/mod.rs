@@ -1,28 +1,39 @@
 //! The Uniswap [Universal Router](https://docs.uniswap.org/contracts/universal-router/overview).
 
-mod command;
-pub use command::Command;
-
 use crate::{
     contracts::bindings::{
         i_universal_router::{ExecuteCall, ExecuteWithCommandsAndInputsCall, IUniversalRouter},
         i_universal_router_commands::*,
     },
+    errors::Result,
     utils::get_deadline,
+    Amount,
 };
 use ethers_contract::builders::ContractCall;
 use ethers_core::{
-    abi::{self, Token, Tokenizable, Tokenize},
-    types::{Address, Bytes, U256},
+    abi::{Token, Tokenizable},
+    types::{Address, Bytes, H160, U256},
 };
 use ethers_providers::Middleware;
-use std::{mem, sync::Arc};
+use std::sync::Arc;
 
 #[cfg(feature = "addresses")]
 use crate::contracts::addresses::try_address;
 #[cfg(feature = "addresses")]
 use ethers_core::types::Chain;
 
+/// A recipient which the router replaces with the sender of the transaction.
+pub const MSG_SENDER: Address = H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+/// A recipient which the router replaces with its own address, to hold the tokens for the next
+/// commands.
+pub const ADDRESS_THIS: Address =
+    H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+
+/// An amount which the router replaces with its whole balance of the token, to swap or pay the
+/// output of the previous commands.
+pub const CONTRACT_BALANCE: U256 = U256([0, 0, 0, 1 << 63]);
+
 macro_rules! add_command_fns {
     ($(
         $name:ident => pub fn $fn_name:ident ($($arg:ident : $ty:ty $(,)?)+) ;
@@ -40,6 +51,171 @@ macro_rules! add_command_fns {
     )+};
 }
 
+/// Implements the `command_name` methods of every command, on a type with an `add_command`
+/// method.
+macro_rules! command_fns {
+    () => {
+        add_command_fns! {
+            V3SwapExactIn => pub fn v3_swap_exact_in(
+                recipient: Address,
+                amount_in: U256,
+                amount_out_min: U256,
+                path: Bytes,
+                payer_is_user: bool
+            );
+            V3SwapExactOut => pub fn v3_swap_exact_out(
+                recipient: Address,
+                amount_out: U256,
+                amount_in_max: U256,
+                path: Bytes,
+                payer_is_user: bool
+            );
+            Permit2TransferFrom => pub fn permit2_transfer_from(
+                token: Address,
+                recipient: Address,
+                amount: U256
+            );
+            Permit2PermitBatch => pub fn permit2_permit_batch(
+                permit_batch: PermitBatch,
+                data: Bytes
+            );
+            Sweep => pub fn sweep(
+                token: Address,
+                recipient: Address,
+                amount_min: U256
+            );
+            Transfer => pub fn transfer(
+                token: Address,
+                recipient: Address,
+                value: U256
+            );
+            PayPortion => pub fn pay_portion(
+                token: Address,
+                recipient: Address,
+                bips: U256
+            );
+            V2SwapExactIn => pub fn v2_swap_exact_in(
+                recipient: Address,
+                amount_in: U256,
+                amount_out_min: U256,
+                path: Vec<Address>,
+                payer_is_user: bool
+            );
+            V2SwapExactOut => pub fn v2_swap_exact_out(
+                recipient: Address,
+                amount_out: U256,
+                amount_in_max: U256,
+                path: Vec<Address>,
+                payer_is_user: bool
+            );
+            Permit2Permit => pub fn permit2_permit(
+                permit_single: PermitSingle,
+                data: Bytes
+            );
+            WrapEth => pub fn wrap_eth(
+                recipient: Address,
+                amount_min: U256
+            );
+            UnwrapWeth => pub fn unwrap_weth(
+                recipient: Address,
+                amount_min: U256
+            );
+            Permit2TransferFromBatch => pub fn permit2_transfer_from_batch(
+                batch_details: Vec<AllowanceTransferDetails>
+            );
+            Seaport => pub fn seaport(
+                value: U256,
+                data: Bytes
+            );
+            LooksRare721 => pub fn looks_rare_721(
+                value: U256,
+                data: Bytes,
+                recipient: Address,
+                token: Address,
+                id: U256
+            );
+            Nftx => pub fn nftx(
+                value: U256,
+                data: Bytes
+            );
+            Cryptopunks => pub fn cryptopunks(
+                punk_id: U256,
+                recipient: Address,
+                value: U256
+            );
+            LooksRare1155 => pub fn looks_rare_1155(
+                value: U256,
+                data: Bytes,
+                recipient: Address,
+                token: Address,
+                id: U256,
+                amount: U256
+            );
+            OwnerCheck721 => pub fn owner_check_721(
+                owner: Address,
+                token: Address,
+                id: U256
+            );
+            OwnerCheck1155 => pub fn owner_check_1155(
+                owner: Address,
+                token: Address,
+                id: U256,
+                min_balance: U256
+            );
+            SweepErc721 => pub fn sweep_erc721(
+                token: Address,
+                recipient: Address,
+                id: U256
+            );
+            X2Y2721 => pub fn x2y2_721(
+                value: U256,
+                data: Bytes,
+                recipient: Address,
+                token: Address,
+                id: U256
+            );
+            Sudoswap => pub fn sudoswap(
+                value: U256,
+                data: Bytes
+            );
+            Nft20 => pub fn nft20(
+                value: U256,
+                data: Bytes
+            );
+            X2Y21155 => pub fn x2y2_1155(
+                value: U256,
+                data: Bytes,
+                recipient: Address,
+                token: Address,
+                id: U256,
+                amount: U256
+            );
+            Foundation => pub fn foundation(
+                value: U256,
+                data: Bytes,
+                recipient: Address,
+                token: Address,
+                id: U256
+            );
+            SweepErc1155 => pub fn sweep_erc1155(
+                token: Address,
+                recipient: Address,
+                id: U256,
+                amount: U256
+            );
+        }
+    };
+}
+
+mod builder;
+mod command;
+mod quote;
+mod route;
+pub use builder::CommandBuilder;
+pub use command::Command;
+pub use quote::RouteQuoter;
+pub use route::{MixedRoute, Route};
+
 contract_struct! {
     /// A Universal Router.
     ///
@@ -77,11 +253,8 @@ contract_struct! {
         /// The router contract.
         contract: IUniversalRouter<M>,
 
-        /// The raw command bytes.
-        commands: Vec<u8>,
-
-        /// The raw command inputs.
-        inputs: Vec<Bytes>,
+        /// The commands and their inputs.
+        builder: CommandBuilder,
     }
 }
 
@@ -89,7 +262,7 @@ impl<M: Middleware> UniversalRouter<M> {
     /// Creates a new instance using the provided address.
     pub fn new(client: Arc<M>, address: Address) -> Self {
         let contract = IUniversalRouter::new(address, client);
-        Self { contract, commands: Default::default(), inputs: Default::default() }
+        Self { contract, builder: CommandBuilder::new() }
     }
 
     /// Creates a new instance by searching for the required addresses in the [addressbook].
@@ -105,8 +278,7 @@ impl<M: Middleware> UniversalRouter<M> {
     /// After calling `reserve`, capacity will be greater than or equal to `self.len() +
     /// additional`. Does nothing if capacity is already sufficient.
     pub fn reserve(&mut self, additional: usize) -> &mut Self {
-        self.commands.reserve(additional);
-        self.inputs.reserve(additional);
+        self.builder.reserve(additional);
         self
     }
 
@@ -114,11 +286,22 @@ impl<M: Middleware> UniversalRouter<M> {
     ///
     /// Note that this method has no effect on the allocated capacity of the buffers.
     pub fn clear(&mut self) -> &mut Self {
-        self.commands.clear();
-        self.inputs.clear();
+        self.builder.clear();
         self
     }
 
+    /// Returns a reference to the builder of the commands.
+    #[inline(always)]
+    pub fn builder(&self) -> &CommandBuilder {
+        &self.builder
+    }
+
+    /// Returns a mutable reference to the builder of the commands.
+    #[inline(always)]
+    pub fn builder_mut(&mut self) -> &mut CommandBuilder {
+        &mut self.builder
+    }
+
     /// Add a command binding, and whether it is allowed to revert to the call.
     ///
     /// # Example
@@ -139,9 +322,8 @@ impl<M: Middleware> UniversalRouter<M> {
         command: IUniversalRouterCommandsCalls,
         allow_revert: bool,
     ) -> &mut Self {
-        let command_type = Command::from(&command);
-        let tokens = command.into_tokens();
-        self.add_command(command_type, allow_revert, &tokens)
+        self.builder.add_command_from_bindings(command, allow_revert);
+        self
     }
 
     /// Add a command, its arguments, and whether it is allowed to revert to the call.
@@ -166,9 +348,8 @@ impl<M: Middleware> UniversalRouter<M> {
         allow_revert: bool,
         args: &[Token],
     ) -> &mut Self {
-        let command_byte = command.encode(allow_revert);
-        let input = abi::encode(args).into();
-        self.add_command_raw(command_byte, input)
+        self.builder.add_command(command, allow_revert, args);
+        self
     }
 
     /// Add a command, its input bytes, and whether it is allowed to revert to the call.
@@ -176,23 +357,63 @@ impl<M: Middleware> UniversalRouter<M> {
     /// **Important**: this method is not type-safe. It is recommended to use the `command_name`
     /// methods instead. See [this type's documentation][UniversalRouter] on how to do this.
     pub fn add_command_raw(&mut self, command_byte: u8, input: Bytes) -> &mut Self {
-        self.commands.push(command_byte);
-        self.inputs.push(input);
+        self.builder.add_command_raw(command_byte, input);
         self
     }
 
+    /// Appends the commands of a swap of `amount` through `route`, either through Uniswap V2 or
+    /// V3, to `recipient`. See [`CommandBuilder::swap`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use uniswap_rs::{prelude::{*, _ethers::*}, universal_router::Route, v3::Path};
+    /// # let mut router = UniversalRouter::new(Provider::<Http>::try_from("http://example.com").unwrap().into(), Address::zero());
+    /// let (token, weth) = (Address::repeat_byte(0x22), Address::repeat_byte(0x44));
+    /// let recipient = Address::repeat_byte(0x33);
+    /// let value = U256::exp10(18);
+    /// let route = Route::from(Path::single(NATIVE_ADDRESS, token, FeeAmount::Low));
+    /// let amount = Amount::ExactIn(value);
+    /// let call = router.swap(&route, amount, U256::zero(), recipient, weth)?.call(Some(300));
+    /// // call.value(value).send().await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn swap(
+        &mut self,
+        route: &Route,
+        amount: Amount,
+        amount_limit: U256,
+        recipient: Address,
+        weth: Address,
+    ) -> Result<&mut Self> {
+        self.builder.swap(route, amount, amount_limit, recipient, weth)?;
+        Ok(self)
+    }
+
+    /// Appends the commands of a swap of `amount` through each leg of `route` to `recipient`.
+    /// See [`CommandBuilder::swap_mixed`] for more details.
+    pub fn swap_mixed(
+        &mut self,
+        route: &MixedRoute,
+        amount: Amount,
+        amount_out_min: U256,
+        recipient: Address,
+        weth: Address,
+    ) -> Result<&mut Self> {
+        self.builder.swap_mixed(route, amount, amount_out_min, recipient, weth)?;
+        Ok(self)
+    }
+
     /// Consumes the internal buffers to build into [`ExecuteWithCommandsAndInputsCall`].
     pub fn build(&mut self, deadline: U256) -> ExecuteWithCommandsAndInputsCall {
-        let commands = mem::take(&mut self.commands);
-        let inputs = mem::take(&mut self.inputs);
-        ExecuteWithCommandsAndInputsCall { commands: commands.into(), inputs, deadline }
+        let (commands, inputs) = self.builder.build();
+        ExecuteWithCommandsAndInputsCall { commands, inputs, deadline }
     }
 
     /// Consumes the internal buffers to build into [`ExecuteCall`].
     pub fn build_no_deadline(&mut self) -> ExecuteCall {
-        let commands = mem::take(&mut self.commands);
-        let inputs = mem::take(&mut self.inputs);
-        ExecuteCall { commands: commands.into(), inputs }
+        let (commands, inputs) = self.builder.build();
+        ExecuteCall { commands, inputs }
     }
 
     /// Consumes the internal buffers to create a call to the [`router`][IUniversalRouter]'s
@@ -216,8 +437,7 @@ impl<M: Middleware> UniversalRouter<M> {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
     pub fn call(&mut self, deadline: Option<u64>) -> ContractCall<M, ()> {
-        let commands: Bytes = mem::take(&mut self.commands).into();
-        let inputs = mem::take(&mut self.inputs);
+        let (commands, inputs) = self.builder.build();
         match deadline {
             Some(deadline) => {
                 let deadline = get_deadline(deadline);
@@ -230,155 +450,7 @@ impl<M: Middleware> UniversalRouter<M> {
 
 // implement commands in a new block so as to not "pollute" docs
 impl<M: Middleware> UniversalRouter<M> {
-    add_command_fns! {
-        V3SwapExactIn => pub fn v3_swap_exact_in(
-            recipient: Address,
-            amount_in: U256,
-            amount_out_min: U256,
-            path: Bytes,
-            payer_is_user: bool
-        );
-        V3SwapExactOut => pub fn v3_swap_exact_out(
-            recipient: Address,
-            amount_out: U256,
-            amount_in_max: U256,
-            path: Bytes,
-            payer_is_user: bool
-        );
-        Permit2TransferFrom => pub fn permit2_transfer_from(
-            token: Address,
-            recipient: Address,
-            amount: U256
-        );
-        Permit2PermitBatch => pub fn permit2_permit_batch(
-            permit_batch: PermitBatch,
-            data: Bytes
-        );
-        Sweep => pub fn sweep(
-            token: Address,
-            recipient: Address,
-            amount_min: U256
-        );
-        Transfer => pub fn transfer(
-            token: Address,
-            recipient: Address,
-            value: U256
-        );
-        PayPortion => pub fn pay_portion(
-            token: Address,
-            recipient: Address,
-            bips: U256
-        );
-        V2SwapExactIn => pub fn v2_swap_exact_in(
-            recipient: Address,
-            amount_in: U256,
-            amount_out_min: U256,
-            path: Vec<Address>,
-            payer_is_user: bool
-        );
-        V2SwapExactOut => pub fn v2_swap_exact_out(
-            recipient: Address,
-            amount_out: U256,
-            amount_in_max: U256,
-            path: Vec<Address>,
-            payer_is_user: bool
-        );
-        Permit2Permit => pub fn permit2_permit(
-            permit_single: PermitSingle,
-            data: Bytes
-        );
-        WrapEth => pub fn wrap_eth(
-            recipient: Address,
-            amount_min: U256
-        );
-        UnwrapWeth => pub fn unwrap_weth(
-            recipient: Address,
-            amount_min: U256
-        );
-        Permit2TransferFromBatch => pub fn permit2_transfer_from_batch(
-            batch_details: Vec<AllowanceTransferDetails>
-        );
-        Seaport => pub fn seaport(
-            value: U256,
-            data: Bytes
-        );
-        LooksRare721 => pub fn looks_rare_721(
-            value: U256,
-            data: Bytes,
-            recipient: Address,
-            token: Address,
-            id: U256
-        );
-        Nftx => pub fn nftx(
-            value: U256,
-            data: Bytes
-        );
-        Cryptopunks => pub fn cryptopunks(
-            punk_id: U256,
-            recipient: Address,
-            value: U256
-        );
-        LooksRare1155 => pub fn looks_rare_1155(
-            value: U256,
-            data: Bytes,
-            recipient: Address,
-            token: Address,
-            id: U256,
-            amount: U256
-        );
-        OwnerCheck721 => pub fn owner_check_721(
-            owner: Address,
-            token: Address,
-            id: U256
-        );
-        OwnerCheck1155 => pub fn owner_check_1155(
-            owner: Address,
-            token: Address,
-            id: U256,
-            min_balance: U256
-        );
-        SweepErc721 => pub fn sweep_erc721(
-            token: Address,
-            recipient: Address,
-            id: U256
-        );
-        X2Y2721 => pub fn x2y2_721(
-            value: U256,
-            data: Bytes,
-            recipient: Address,
-            token: Address,
-            id: U256
-        );
-        Sudoswap => pub fn sudoswap(
-            value: U256,
-            data: Bytes
-        );
-        Nft20 => pub fn nft20(
-            value: U256,
-            data: Bytes
-        );
-        X2Y21155 => pub fn x2y2_1155(
-            value: U256,
-            data: Bytes,
-            recipient: Address,
-            token: Address,
-            id: U256,
-            amount: U256
-        );
-        Foundation => pub fn foundation(
-            value: U256,
-            data: Bytes,
-            recipient: Address,
-            token: Address,
-            id: U256
-        );
-        SweepErc1155 => pub fn sweep_erc1155(
-            token: Address,
-            recipient: Address,
-            id: U256,
-            amount: U256
-        );
-    }
+    command_fns!();
 }
 
 #[cfg(test)]
@@ -409,13 +481,72 @@ mod tests {
         let mut router = UniversalRouter::new(provider, Address::zero());
 
         router.add_command(Command::Sweep, allow_revert, &tokens);
-        assert_eq!(router.commands, e_commands);
-        assert_eq!(router.inputs, e_inputs);
+        assert_eq!(router.builder.commands(), &e_commands[..]);
+        assert_eq!(router.builder.inputs(), e_inputs);
 
         router.clear();
 
         router.add_command_from_bindings(command.into(), allow_revert);
-        assert_eq!(router.commands, e_commands);
-        assert_eq!(router.inputs, e_inputs);
+        assert_eq!(router.builder.commands(), &e_commands[..]);
+        assert_eq!(router.builder.inputs(), e_inputs);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_reencode_fork() {
+        use crate::{
+            contracts::bindings::i_universal_router::IUniversalRouterCalls, testing::Fork,
+        };
+        use ethers_core::abi::{AbiDecode, AbiEncode};
+
+        let fork = Fork::from_env().await.unwrap();
+        let provider = fork.provider().clone();
+        let routers: [Address; 2] = [
+            "0xEf1c6E67703c7BD7107eed8303Fbe6EC2554BF6B".parse().unwrap(),
+            "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD".parse().unwrap(),
+        ];
+
+        // re-encode the swaps of the latest blocks which only use the supported swap and payment
+        // commands, which all versions of the router share
+        let latest = provider.get_block_number().await.unwrap().as_u64();
+        let mut checked = 0;
+        for number in latest.saturating_sub(20)..=latest {
+            let block = provider.get_block_with_txs(number).await.unwrap().unwrap();
+            for tx in block.transactions {
+                if !tx.to.map_or(false, |to| routers.contains(&to)) {
+                    continue;
+                }
+                let (commands, inputs, deadline) = match IUniversalRouterCalls::decode(&tx.input) {
+                    Ok(IUniversalRouterCalls::ExecuteWithCommandsAndInputs(call)) => {
+                        (call.commands, call.inputs, Some(call.deadline))
+                    }
+                    Ok(IUniversalRouterCalls::Execute(call)) => (call.commands, call.inputs, None),
+                    _ => continue,
+                };
+                let unsupported = |&byte: &u8| {
+                    byte & Command::MASK >= Command::Seaport as u8
+                        || Command::decode(byte).0.is_invalid()
+                };
+                if commands.iter().any(unsupported) {
+                    continue;
+                }
+
+                let mut builder = CommandBuilder::new();
+                for (call, allow_revert) in CommandBuilder::decode(&commands, &inputs).unwrap() {
+                    builder.add_command_from_bindings(call, allow_revert);
+                }
+                let (commands, inputs) = builder.build();
+                let reencoded = match deadline {
+                    Some(deadline) => {
+                        ExecuteWithCommandsAndInputsCall { commands, inputs, deadline }.encode()
+                    }
+                    None => ExecuteCall { commands, inputs }.encode(),
+                };
+                // ignore any data appended to the calldata, like a front-end's tracking id
+                assert!(tx.input.starts_with(&reencoded), "{:?}", tx.hash);
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
     }
 }
@@ -0,0 +1,197 @@
+use super::{MixedRoute, Route};
+use crate::{errors::Result, utils::map_native, v2, v3};
+use ethers_core::types::{Address, U256};
+use ethers_providers::Middleware;
+use std::fmt;
+
+/// Quotes swaps through the legs of a [`MixedRoute`], with the reserves of the Uniswap V2 pairs
+/// and the Uniswap V3 quoter.
+///
+/// # Example
+///
+/// ```no_run
+/// # use uniswap_rs::{prelude::{*, _ethers::*}, v2, v3::{Path, Quoter}};
+/// use uniswap_rs::universal_router::{MixedRoute, Route, RouteQuoter, MSG_SENDER};
+/// # async fn foo<M: Middleware + 'static>(client: std::sync::Arc<M>) -> Result<(), Box<dyn std::error::Error>> {
+/// # let (weth, usdc, shib) = (Address::zero(), Address::zero(), Address::zero());
+/// let factory = v2::Factory::new_with_chain(client.clone(), Chain::Mainnet, ProtocolType::UniswapV2).unwrap();
+/// let quoter = Quoter::new_v2(client.clone(), "0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse()?);
+/// let quoter = RouteQuoter::new(factory, quoter, weth);
+///
+/// let routes = [
+///     MixedRoute::from(Route::from(vec![weth, usdc, shib])),
+///     MixedRoute::from(Route::from(Path::new(&[weth, usdc, shib], &[500, 3000])?)),
+///     MixedRoute::new(vec![
+///         Path::single(weth, usdc, FeeAmount::Low).into(),
+///         vec![usdc, shib].into(),
+///     ])?,
+/// ];
+/// let amount_in = U256::exp10(18);
+/// let (best, amount_out) = quoter.best_route(&routes, amount_in).await?;
+///
+/// let mut router = UniversalRouter::new_with_chain(client, Chain::Mainnet).unwrap();
+/// let amount_out_min = Slippage::percent(0.5)?.minimum_amount_out(amount_out);
+/// let call = router
+///     .swap_mixed(best, Amount::ExactIn(amount_in), amount_out_min, MSG_SENDER, weth)?
+///     .call(Some(300));
+/// # Ok(()) }
+/// ```
+pub struct RouteQuoter<M> {
+    /// The Uniswap V2 factory of the pairs.
+    factory: v2::Factory<M>,
+
+    /// The Uniswap V3 quoter.
+    quoter: v3::Quoter<M>,
+
+    /// The wrapped native token, which replaces [`NATIVE_ADDRESS`] in the routes.
+    weth: Address,
+}
+
+impl<M> Clone for RouteQuoter<M> {
+    fn clone(&self) -> Self {
+        Self { factory: self.factory.clone(), quoter: self.quoter.clone(), weth: self.weth }
+    }
+}
+
+impl<M> fmt::Debug for RouteQuoter<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteQuoter")
+            .field("factory", &self.factory)
+            .field("quoter", &self.quoter)
+            .field("weth", &self.weth)
+            .finish()
+    }
+}
+
+impl<M: Middleware> RouteQuoter<M> {
+    /// Creates a new instance using the provided V2 factory, V3 quoter and wrapped native token.
+    pub fn new(factory: v2::Factory<M>, quoter: v3::Quoter<M>, weth: Address) -> Self {
+        Self { factory, quoter, weth }
+    }
+
+    /// Returns the Uniswap V2 factory.
+    pub fn factory(&self) -> &v2::Factory<M> {
+        &self.factory
+    }
+
+    /// Returns the Uniswap V3 quoter.
+    pub fn quoter(&self) -> &v3::Quoter<M> {
+        &self.quoter
+    }
+
+    /// Quotes swapping `amount_in` through each leg of `route`, returning the input amount
+    /// followed by the output amount of each leg.
+    pub async fn quote_route(&self, route: &MixedRoute, amount_in: U256) -> Result<Vec<U256>> {
+        let mut amounts = Vec::with_capacity(route.legs().len() + 1);
+        amounts.push(amount_in);
+        for leg in route.legs() {
+            let amount_in = amounts[amounts.len() - 1];
+            let amount_out = match leg {
+                Route::V2 { path } => {
+                    let mut path = path.clone();
+                    map_native(&mut path, self.weth);
+                    let amounts = v2::Library::get_amounts_out(&self.factory, amount_in, &path);
+                    amounts.await?.pop().unwrap_or_default()
+                }
+                Route::V3 { path } => {
+                    let mut path = path.clone();
+                    map_native(path.tokens_mut(), self.weth);
+                    self.quoter.quote_exact_input(&path, amount_in).await?.amount
+                }
+            };
+            amounts.push(amount_out);
+        }
+        Ok(amounts)
+    }
+
+    /// Returns the route with the greatest output for `amount_in`, like the best of a pure V2, a
+    /// pure V3 and a mixed route, and its output amount.
+    ///
+    /// Routes which fail to quote, like through a pool which does not exist, are skipped. Returns
+    /// the error of the last route if none can be quoted, or [`Error::InvalidPath`] if there are
+    /// no routes.
+    ///
+    /// [`Error::InvalidPath`]: crate::errors::Error::InvalidPath
+    pub async fn best_route<'a>(
+        &self,
+        routes: &'a [MixedRoute],
+        amount_in: U256,
+    ) -> Result<(&'a MixedRoute, U256)> {
+        let mut best: Option<(&MixedRoute, U256)> = None;
+        let mut last_error = None;
+        for route in routes {
+            match self.quote_route(route, amount_in).await {
+                Ok(amounts) => {
+                    let amount_out = amounts[amounts.len() - 1];
+                    if best.map_or(true, |(_, best)| amount_out > best) {
+                        best = Some((route, amount_out));
+                    }
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        match (best, last_error) {
+            (Some(best), _) => Ok(best),
+            (None, Some(e)) => Err(e),
+            (None, None) => Err(crate::errors::Error::InvalidPath),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{v3::FeeAmount, ProtocolType, NATIVE_ADDRESS};
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+    use std::sync::Arc;
+
+    const WETH: Address = Address::repeat_byte(0x0a);
+    const USDC: Address = Address::repeat_byte(0x0b);
+    const SHIB: Address = Address::repeat_byte(0x0c);
+
+    fn push(mock: &MockProvider, tokens: &[Token]) {
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(tokens))).unwrap();
+    }
+
+    fn push_reserves(mock: &MockProvider, reserve_a: u64, reserve_b: u64) {
+        push(
+            mock,
+            &[Token::Uint(reserve_a.into()), Token::Uint(reserve_b.into()), Token::Uint(0.into())],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quote_route() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let factory =
+            v2::Factory::new(client.clone(), Address::repeat_byte(1), ProtocolType::UniswapV2);
+        let quoter = v3::Quoter::new(client, Address::repeat_byte(2));
+        let quoter = RouteQuoter::new(factory, quoter, WETH);
+
+        // ETH -> USDC on V3, then USDC -> SHIB on V2
+        let route = MixedRoute::new(vec![
+            v3::Path::single(NATIVE_ADDRESS, USDC, FeeAmount::Low).into(),
+            vec![USDC, SHIB].into(),
+        ])
+        .unwrap();
+        // responses are popped in reverse
+        push_reserves(&mock, 1_000, 4_000);
+        push(&mock, &[Token::Uint(100.into())]);
+        let amounts = quoter.quote_route(&route, 1.into()).await.unwrap();
+        let out = v2::Library::get_amount_out(100.into(), 1_000.into(), 4_000.into()).unwrap();
+        assert_eq!(amounts, [1.into(), 100.into(), out]);
+
+        // the failing route is skipped
+        let routes = [route.clone(), MixedRoute::from(Route::from(vec![WETH, SHIB]))];
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        push_reserves(&mock, 1_000, 4_000);
+        push(&mock, &[Token::Uint(100.into())]);
+        let (best, amount) = quoter.best_route(&routes, 1.into()).await.unwrap();
+        assert_eq!((best, amount), (&route, out));
+    }
+}
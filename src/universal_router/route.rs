@@ -0,0 +1,123 @@
+use crate::{
+    errors::{Error, Result},
+    v3::Path,
+    NATIVE_ADDRESS,
+};
+use ethers_core::types::Address;
+
+/// The path of a swap through the Universal Router, and the protocol of its pools.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Route {
+    /// A path through Uniswap V2 pairs.
+    V2 {
+        /// The tokens of the path.
+        path: Vec<Address>,
+    },
+    /// A path through Uniswap V3 pools.
+    V3 {
+        /// The tokens and fees of the path.
+        path: Path,
+    },
+}
+
+impl Route {
+    /// Returns the tokens of the path.
+    pub fn tokens(&self) -> &[Address] {
+        match self {
+            Self::V2 { path } => path,
+            Self::V3 { path } => path.tokens(),
+        }
+    }
+
+    /// Returns the input token.
+    pub fn token_in(&self) -> Address {
+        self.tokens()[0]
+    }
+
+    /// Returns the output token.
+    pub fn token_out(&self) -> Address {
+        self.tokens()[self.tokens().len() - 1]
+    }
+}
+
+impl From<Vec<Address>> for Route {
+    fn from(path: Vec<Address>) -> Self {
+        Self::V2 { path }
+    }
+}
+
+impl From<Path> for Route {
+    fn from(path: Path) -> Self {
+        Self::V3 { path }
+    }
+}
+
+/// A route through any number of Uniswap V2 and V3 legs, like WETH to USDC through a V3 pool
+/// then USDC to SHIB through a V2 pair.
+///
+/// Each leg starts with the output token of the previous one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MixedRoute {
+    /// The legs of the route.
+    legs: Vec<Route>,
+}
+
+impl MixedRoute {
+    /// Creates a new route from its legs.
+    ///
+    /// Returns [`Error::InvalidPath`] if there are no legs, if a leg has fewer than 2 tokens, or
+    /// if a leg does not start with the output token of the previous one, and
+    /// [`Error::NativeMidPath`] if [`NATIVE_ADDRESS`] is in the middle of the route.
+    pub fn new(legs: Vec<Route>) -> Result<Self> {
+        if legs.is_empty() || legs.iter().any(|leg| leg.tokens().len() < 2) {
+            return Err(Error::InvalidPath);
+        }
+        if legs.windows(2).any(|w| w[0].token_out() != w[1].token_in()) {
+            return Err(Error::InvalidPath);
+        }
+        let this = Self { legs };
+        let tokens = this.tokens();
+        if tokens[1..tokens.len() - 1].contains(&NATIVE_ADDRESS) {
+            return Err(Error::NativeMidPath);
+        }
+        Ok(this)
+    }
+
+    /// Returns the legs of the route.
+    pub fn legs(&self) -> &[Route] {
+        &self.legs
+    }
+
+    /// Returns the tokens of the route, including the tokens between legs once.
+    pub fn tokens(&self) -> Vec<Address> {
+        let mut tokens = self.legs[0].tokens().to_vec();
+        for leg in &self.legs[1..] {
+            tokens.extend_from_slice(&leg.tokens()[1..]);
+        }
+        tokens
+    }
+
+    /// Returns the input token.
+    pub fn token_in(&self) -> Address {
+        self.legs[0].token_in()
+    }
+
+    /// Returns the output token.
+    pub fn token_out(&self) -> Address {
+        self.legs[self.legs.len() - 1].token_out()
+    }
+
+    /// Returns whether the route goes through both Uniswap V2 and V3.
+    pub fn is_mixed(&self) -> bool {
+        let is_v2 = |leg: &Route| matches!(leg, Route::V2 { .. });
+        self.legs.iter().any(is_v2) && !self.legs.iter().all(is_v2)
+    }
+}
+
+impl From<Route> for MixedRoute {
+    fn from(route: Route) -> Self {
+        Self { legs: vec![route] }
+    }
+}
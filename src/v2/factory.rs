@@ -1,9 +1,38 @@
-use super::{Library, Pair};
-use crate::{contracts::bindings::i_uniswap_v2_factory::IUniswapV2Factory, ProtocolType};
-use ethers_core::types::{Address, Chain, H256};
+use super::{Library, Pair, PairInfo, PairRegistry};
+use crate::{
+    common::{call_multi, scan_logs},
+    contracts::bindings::{
+        i_solidly_factory::ISolidlyFactory,
+        i_uniswap_v2_factory::{IUniswapV2Factory, PairCreatedFilter},
+        i_uniswap_v2_pair::IUniswapV2Pair,
+        ierc20::IERC20,
+    },
+    errors::{Error, Result},
+    pair_code_hashes::PAIR_CODE_HASHES,
+    DecodedLog, ProtocolType, RequestPolicy,
+};
+use ethers_contract::{builders::ContractCall, ContractError};
+use ethers_core::{
+    abi::Tokenizable,
+    types::{Address, Chain, H256, U256},
+};
 use ethers_providers::Middleware;
 use std::sync::Arc;
 
+type Reserves = (U256, U256, u32);
+
+/// The protocol fee configuration of a factory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ProtocolFeeInfo {
+    /// The recipient of the protocol fee, or the zero address if it is off.
+    pub fee_to: Address,
+    /// The address allowed to set `fee_to`.
+    pub fee_to_setter: Address,
+    /// Whether the protocol fee is on.
+    pub fee_enabled: bool,
+}
+
 contract_struct! {
     /// A Uniswap V2 factory.
     pub struct Factory<M> {
@@ -15,6 +44,18 @@ contract_struct! {
 
         /// The chain.
         pub chain: Option<Chain>,
+
+        /// The raw chain ID, for chains which are not a [`Chain`].
+        chain_id: Option<u64>,
+
+        /// The pair code hash override.
+        pair_code_hash: Option<H256>,
+
+        /// The swap fee override, in basis points.
+        pub(super) swap_fee_bps: Option<u32>,
+
+        /// The policy of the factory's batched requests and log scans.
+        request_policy: RequestPolicy,
     }
 }
 
@@ -28,8 +69,45 @@ impl<M> Factory<M> {
     ///
     /// Note: `chain` is used only when the pair code hash differs in the same protocol, for example
     /// `Pancakeswap` has two different code hashes for BSC mainnet and testnet.
+    ///
+    /// Returns the hash set with [`set_pair_code_hash`][Self::set_pair_code_hash], if any.
     pub fn pair_code_hash(&self, chain: Option<Chain>) -> H256 {
-        self.protocol.pair_code_hash(self.chain.or(chain))
+        match self.pair_code_hash {
+            Some(pair_code_hash) => pair_code_hash,
+            None => self.protocol.pair_code_hash(self.chain.or(chain)),
+        }
+    }
+
+    /// Sets the pair code hash, overriding the one of the factory's protocol. Used for forks with
+    /// a different pair code hash.
+    pub fn set_pair_code_hash(&mut self, pair_code_hash: H256) {
+        self.pair_code_hash = Some(pair_code_hash)
+    }
+
+    /// Sets the pair code hash, overriding the one of the factory's protocol.
+    ///
+    /// See [`set_pair_code_hash`][Self::set_pair_code_hash] for more details.
+    #[must_use]
+    pub fn with_pair_code_hash(mut self, pair_code_hash: H256) -> Self {
+        self.set_pair_code_hash(pair_code_hash);
+        self
+    }
+
+    /// Returns the fee taken on each swap by the factory's pairs, in basis points.
+    ///
+    /// Returns the fee set with [`set_swap_fee_bps`][Self::set_swap_fee_bps], if any, otherwise
+    /// the [protocol's][ProtocolType::swap_fee_bps].
+    pub fn swap_fee_bps(&self) -> u32 {
+        self.swap_fee_bps.unwrap_or_else(|| self.protocol.swap_fee_bps())
+    }
+
+    /// Sets the swap fee, in basis points, overriding the one of the factory's protocol. Used for
+    /// forks with a different or variable fee.
+    ///
+    /// Returns [`Error::InvalidFee`] if the fee is not lower than `10_000` (100%).
+    pub fn set_swap_fee_bps(&mut self, fee_bps: u32) -> Result<()> {
+        self.swap_fee_bps = Some(validate_fee(fee_bps)?);
+        Ok(())
     }
 
     /// Returns the factory's chain.
@@ -39,7 +117,33 @@ impl<M> Factory<M> {
 
     /// Sets the factory's chain.
     pub fn set_chain(&mut self, chain: Chain) {
-        self.chain = Some(chain)
+        self.chain = Some(chain);
+        self.chain_id = Some(chain as u64);
+    }
+
+    /// Returns the factory's chain ID, which is set even if the chain is not a [`Chain`].
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id.or(self.chain.map(|chain| chain as u64))
+    }
+
+    /// Sets the factory's chain ID, and its [chain][Self::chain] if the ID is a known [`Chain`].
+    ///
+    /// Used for custom networks whose chain ID is not a [`Chain`], together with a
+    /// [pair code hash override][Self::set_pair_code_hash].
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        self.chain = Chain::try_from(chain_id).ok();
+        self.chain_id = Some(chain_id);
+    }
+
+    /// Returns the policy of the factory's batched requests and log scans.
+    pub fn request_policy(&self) -> RequestPolicy {
+        self.request_policy
+    }
+
+    /// Sets the policy of the factory's batched requests and log scans, like
+    /// [`get_reserves_multi`][Self::get_reserves_multi] and [`scan_pairs`][Self::scan_pairs].
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) {
+        self.request_policy = policy;
     }
 }
 
@@ -48,24 +152,818 @@ impl<M: Middleware> Factory<M> {
     pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
         // assert!(protocol.is_v2(), "protocol must be v2");
         let contract = IUniswapV2Factory::new(address, client);
-        Self { contract, protocol, chain: None }
+        Self {
+            contract,
+            protocol,
+            chain: None,
+            chain_id: None,
+            pair_code_hash: None,
+            swap_fee_bps: None,
+            request_policy: RequestPolicy::new(),
+        }
     }
 
     /// Creates a new instance by searching for the required addresses in the [addressbook].
     ///
+    /// The pair code hash of a [registered][register] entry overrides the protocol's.
+    ///
     /// [addressbook]: crate::contracts::addresses
+    /// [register]: crate::contracts::addresses::register
     #[cfg(feature = "addresses")]
     pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
         // assert!(protocol.is_v2(), "protocol must be v2");
+        use crate::contracts::addresses::with_registry;
+
         protocol.try_addresses(chain).0.map(|address| {
             let contract = IUniswapV2Factory::new(address, client);
-            Self { contract, protocol, chain: Some(chain) }
+            Self {
+                contract,
+                protocol,
+                chain: Some(chain),
+                chain_id: Some(chain as u64),
+                pair_code_hash: with_registry(|book| book.init_code_hash(protocol, chain)),
+                swap_fee_bps: None,
+                request_policy: RequestPolicy::new(),
+            }
         })
     }
 
     /// Returns the pair for two token addresses.
     pub fn pair_for(&self, token_a: Address, token_b: Address) -> Pair<M> {
-        let address = Library::pair_for(self, token_a, token_b);
-        Pair::new(self.client(), address, self.protocol)
+        self.pair_for_hop(token_a, token_b, self.protocol.stable())
+    }
+
+    /// Returns the `stable` or volatile pair for two token addresses of a Solidly fork.
+    pub fn pair_for_solidly(&self, token_a: Address, token_b: Address, stable: bool) -> Pair<M> {
+        self.pair_for_hop(token_a, token_b, Some(stable))
+    }
+
+    fn pair_for_hop(&self, token_a: Address, token_b: Address, stable: Option<bool>) -> Pair<M> {
+        let address = Library::pair_for_hop(self, token_a, token_b, stable);
+        let protocol = match stable {
+            Some(stable) => ProtocolType::Velodrome { stable },
+            None => self.protocol,
+        };
+        let mut pair = Pair::new(self.client(), address, protocol);
+        pair.swap_fee_bps = self.swap_fee_bps;
+        pair
+    }
+
+    /// Fetches the factory's protocol fee configuration.
+    pub async fn protocol_fee_info(&self) -> Result<ProtocolFeeInfo> {
+        let calls = vec![self.contract.fee_to(), self.contract.fee_to_setter()];
+        let addresses = self.call_multi(calls).await?;
+        let (fee_to, fee_to_setter) = (addresses[0], addresses[1]);
+        Ok(ProtocolFeeInfo { fee_to, fee_to_setter, fee_enabled: !fee_to.is_zero() })
+    }
+
+    /// Returns the contract call for the factory's `setFeeTo` method, which can only be called by
+    /// its `feeToSetter`. Setting the zero address turns the protocol fee off.
+    pub fn set_fee_to(&self, fee_to: Address) -> ContractCall<M, ()> {
+        self.contract.set_fee_to(fee_to)
+    }
+
+    /// Returns the contract call for the factory's `setFeeToSetter` method, which can only be
+    /// called by its current `feeToSetter`.
+    pub fn set_fee_to_setter(&self, fee_to_setter: Address) -> ContractCall<M, ()> {
+        self.contract.set_fee_to_setter(fee_to_setter)
+    }
+
+    /// Returns the address of the pair for two token addresses from the factory's `getPair`
+    /// mapping, checking that it matches the address computed with
+    /// [`Library::pair_for`].
+    ///
+    /// The pair of a Solidly fork is the [stable][ProtocolType::stable] or volatile one of the
+    /// factory's protocol, read with the Solidly factory's `getPair(tokenA, tokenB, stable)`.
+    ///
+    /// Returns [`Error::PairNotDeployed`] if the pair has not been created, and
+    /// [`Error::PairAddressMismatch`] if the addresses differ, which usually means that the
+    /// protocol's pair code hash is wrong for this factory.
+    pub async fn get_pair_checked(&self, token_a: Address, token_b: Address) -> Result<Address> {
+        self.get_pair_checked_hop(token_a, token_b, self.protocol.stable()).await
+    }
+
+    /// Returns the address of the `stable` or volatile pair for two token addresses of a Solidly
+    /// fork.
+    ///
+    /// See [`get_pair_checked`][Self::get_pair_checked] for more details.
+    pub async fn get_pair_checked_solidly(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        stable: bool,
+    ) -> Result<Address> {
+        self.get_pair_checked_hop(token_a, token_b, Some(stable)).await
+    }
+
+    async fn get_pair_checked_hop(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        stable: Option<bool>,
+    ) -> Result<Address> {
+        let actual = self.get_pair_call(token_a, token_b, stable).call().await?;
+        if actual.is_zero() {
+            return Err(Error::PairNotDeployed { token_a, token_b });
+        }
+        let expected = Library::pair_for_hop(self, token_a, token_b, stable);
+        if actual != expected {
+            return Err(Error::PairAddressMismatch { expected, actual });
+        }
+        Ok(actual)
+    }
+
+    /// Returns whether the pair for two token addresses has been deployed, by checking the code at
+    /// its computed address. Falls back to the factory's `getPair` mapping if the code could not
+    /// be fetched.
+    pub async fn pair_exists(&self, token_a: Address, token_b: Address) -> Result<bool> {
+        self.pair_exists_hop(token_a, token_b, self.protocol.stable()).await
+    }
+
+    /// Returns whether the `stable` or volatile pair for two token addresses of a Solidly fork has
+    /// been deployed.
+    ///
+    /// See [`pair_exists`][Self::pair_exists] for more details.
+    pub async fn pair_exists_solidly(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        stable: bool,
+    ) -> Result<bool> {
+        self.pair_exists_hop(token_a, token_b, Some(stable)).await
+    }
+
+    pub(crate) async fn pair_exists_hop(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        stable: Option<bool>,
+    ) -> Result<bool> {
+        match self.pair_for_hop(token_a, token_b, stable).exists().await {
+            Ok(exists) => Ok(exists),
+            Err(_) => Ok(!self.get_pair_call(token_a, token_b, stable).call().await?.is_zero()),
+        }
+    }
+
+    /// Returns the `getPair` call of the factory, which is Solidly's
+    /// `getPair(tokenA, tokenB, stable)` if `stable` is set.
+    fn get_pair_call(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        stable: Option<bool>,
+    ) -> ContractCall<M, Address> {
+        match stable {
+            Some(stable) => ISolidlyFactory::new(self.address(), self.client())
+                .get_pair(token_a, token_b, stable),
+            None => self.contract.get_pair(token_a, token_b),
+        }
+    }
+
+    /// Detects the pair code hash of the factory from its first created pair.
+    ///
+    /// The known [pair code hashes] are tried first. Otherwise, the pair's creation code is
+    /// searched for in the factory's bytecode, where it is embedded by the Solidity compiler.
+    ///
+    /// Returns [`Error::NoPairs`] if the factory has not created any pairs, and
+    /// [`Error::PairCodeHashNotFound`] if no hash matches the first pair's address.
+    ///
+    /// [pair code hashes]: crate::pair_code_hashes
+    pub async fn detect_pair_code_hash(&self) -> Result<H256> {
+        if self.contract.all_pairs_length().call().await?.is_zero() {
+            return Err(Error::NoPairs);
+        }
+        let address = self.contract.all_pairs(U256::zero()).call().await?;
+        let pair = IUniswapV2Pair::new(address, self.client());
+        let token_0 = pair.token_0().call().await?;
+        let token_1 = pair.token_1().call().await?;
+
+        let factory = self.address();
+        let salt = ethers_core::utils::keccak256([token_0.0, token_1.0].concat());
+        let matches = |hash: &H256| {
+            ethers_core::utils::get_create2_address_from_hash(factory, salt, hash.0) == address
+        };
+
+        let known = std::iter::once(self.pair_code_hash(None)).chain(PAIR_CODE_HASHES);
+        if let Some(hash) = known.into_iter().find(matches) {
+            return Ok(hash);
+        }
+
+        let code = self
+            .client()
+            .get_code(factory, None)
+            .await
+            .map_err(ContractError::<M>::MiddlewareError)?;
+        let hash = creation_code_hashes(&code).find(matches);
+        hash.ok_or(Error::PairCodeHashNotFound)
+    }
+
+    /// Returns whether the factory's [pair code hash][Self::pair_code_hash] matches the address
+    /// of its first created pair, or None if it has not created any pairs.
+    pub(crate) async fn check_pair_code_hash(&self) -> Result<Option<bool>> {
+        if self.contract.all_pairs_length().call().await?.is_zero() {
+            return Ok(None);
+        }
+        let address = self.contract.all_pairs(U256::zero()).call().await?;
+        let pair = IUniswapV2Pair::new(address, self.client());
+        let token_0 = pair.token_0().call().await?;
+        let token_1 = pair.token_1().call().await?;
+
+        // the first pair of a Solidly fork can be either stable or volatile
+        let salts = match self.protocol.stable() {
+            Some(_) => [false, true]
+                .map(|stable| [&token_0.0[..], &token_1.0[..], &[stable as u8]].concat())
+                .to_vec(),
+            None => vec![[token_0.0, token_1.0].concat()],
+        };
+        let hash = self.pair_code_hash(None);
+        Ok(Some(salts.into_iter().any(|salt| {
+            let salt = ethers_core::utils::keccak256(salt);
+            ethers_core::utils::get_create2_address_from_hash(self.address(), salt, hash.0)
+                == address
+        })))
+    }
+
+    /// Fetches the addresses of multiple pairs using the factory's `getPair` method, which returns
+    /// the zero address for pairs that have not been created.
+    ///
+    /// See [`get_reserves_multi`][Self::get_reserves_multi] for more details.
+    pub async fn get_pairs_multi(&self, pairs: &[(Address, Address)]) -> Result<Vec<Address>> {
+        let stable = self.protocol.stable();
+        let calls = pairs.iter().map(|&(a, b)| self.get_pair_call(a, b, stable)).collect();
+        self.call_multi(calls).await
+    }
+
+    /// Fetches the addresses of multiple `stable` or volatile pairs of a Solidly fork, given as
+    /// `(token_a, token_b, stable)`.
+    ///
+    /// See [`get_pairs_multi`][Self::get_pairs_multi] for more details.
+    pub async fn get_pairs_multi_solidly(
+        &self,
+        pairs: &[(Address, Address, bool)],
+    ) -> Result<Vec<Address>> {
+        let calls = pairs.iter().map(|&(a, b, s)| self.get_pair_call(a, b, Some(s))).collect();
+        self.call_multi(calls).await
+    }
+
+    /// Fetches the decimals of multiple tokens.
+    ///
+    /// See [`get_reserves_multi`][Self::get_reserves_multi] for more details.
+    pub async fn get_decimals_multi(&self, tokens: &[Address]) -> Result<Vec<u8>> {
+        let token = IERC20::new(Address::zero(), self.client());
+        let calls = tokens
+            .iter()
+            .map(|&address| {
+                let mut call = token.decimals();
+                call.tx.set_to(address);
+                call
+            })
+            .collect();
+        self.call_multi(calls).await
+    }
+
+    /// Fetches the reserves of multiple pairs, sorted in the order of each pair's tokens, and the
+    /// timestamp of the block in which they were last updated.
+    ///
+    /// All the `getReserves` calls are aggregated into a single
+    /// [Multicall][crate::utils::Multicall] request, so that the reserves are all read at
+    /// the same block. Falls back to sequential calls, pinned to the latest block, if
+    /// Multicall3 is not deployed on the factory's chain.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(factory = ?self.address(), ?pairs))
+    )]
+    pub async fn get_reserves_multi(&self, pairs: &[(Address, Address)]) -> Result<Vec<Reserves>> {
+        let stable = self.protocol.stable();
+        let pairs: Vec<_> = pairs.iter().map(|&(a, b)| (a, b, stable)).collect();
+        self.get_reserves_multi_hops(&pairs).await
+    }
+
+    /// Fetches the reserves of multiple `stable` or volatile pairs of a Solidly fork, given as
+    /// `(token_a, token_b, stable)`.
+    ///
+    /// See [`get_reserves_multi`][Self::get_reserves_multi] for more details.
+    pub async fn get_reserves_multi_solidly(
+        &self,
+        pairs: &[(Address, Address, bool)],
+    ) -> Result<Vec<Reserves>> {
+        let pairs: Vec<_> = pairs.iter().map(|&(a, b, s)| (a, b, Some(s))).collect();
+        self.get_reserves_multi_hops(&pairs).await
+    }
+
+    async fn get_reserves_multi_hops(
+        &self,
+        pairs: &[(Address, Address, Option<bool>)],
+    ) -> Result<Vec<Reserves>> {
+        let pair = IUniswapV2Pair::new(Address::zero(), self.client());
+        let calls = pairs
+            .iter()
+            .map(|&(a, b, stable)| {
+                let mut call = pair.get_reserves();
+                call.tx.set_to(Library::pair_for_hop(self, a, b, stable));
+                call
+            })
+            .collect();
+        let reserves = self.call_multi(calls).await?;
+        let reserves: Vec<_> =
+            reserves.into_iter().zip(pairs).map(|(r, &(a, b, _))| sort_reserves(a, b, r)).collect();
+        debug!(?reserves, "fetched the reserves");
+        Ok(reserves)
+    }
+
+    /// Scans the factory's `PairCreated` events from `from_block` to `to_block`, inclusive, in
+    /// ranges of at most `chunk_size` blocks, returning the created pairs.
+    ///
+    /// See [`resume_scan`][Self::resume_scan] for more details.
+    pub async fn scan_pairs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        chunk_size: u64,
+    ) -> Result<PairRegistry> {
+        let mut registry = PairRegistry::new(from_block);
+        self.resume_scan(&mut registry, to_block, chunk_size).await?;
+        Ok(registry)
+    }
+
+    /// Resumes scanning the factory's `PairCreated` events into `registry`, from its
+    /// [`next_block`][PairRegistry::next_block] to `to_block`, inclusive, in ranges of at most
+    /// `chunk_size` blocks.
+    ///
+    /// Ranges for which the provider returns too many results are bisected until they succeed,
+    /// and throttled queries are retried according to the factory's
+    /// [request policy][Self::request_policy]. The registry is updated after every range, so that
+    /// scanning can be resumed after an error.
+    pub async fn resume_scan(
+        &self,
+        registry: &mut PairRegistry,
+        to_block: u64,
+        chunk_size: u64,
+    ) -> Result<()> {
+        let filter = self.contract.pair_created_filter().filter;
+        let from_block = registry.next_block;
+        let (client, policy) = (self.client(), &self.request_policy);
+        scan_logs(
+            client.as_ref(),
+            &filter,
+            from_block,
+            to_block,
+            chunk_size,
+            policy,
+            |logs, end| {
+                for log in logs {
+                    let block_number = log.block_number.unwrap_or_default().as_u64();
+                    let event = DecodedLog::<PairCreatedFilter>::decode(&log)?.event;
+                    registry.insert(PairInfo {
+                        token0: event.token_0,
+                        token1: event.token_1,
+                        address: event.pair,
+                        block_number,
+                    });
+                }
+                registry.next_block = end + 1;
+                Ok(())
+            },
+        )
+        .await
+    }
+
+    /// Aggregates `calls` into a single [Multicall][crate::utils::Multicall] request, retried
+    /// according to the factory's [request policy][Self::request_policy]. Falls back to sequential
+    /// calls, pinned to the latest block, if Multicall3 is not deployed on the factory's chain.
+    async fn call_multi<D: Tokenizable>(&self, calls: Vec<ContractCall<M, D>>) -> Result<Vec<D>> {
+        let policy = &self.request_policy;
+        policy.retry(|| call_multi(self.client(), self.chain, calls.clone())).await
+    }
+}
+
+/// Returns `fee_bps`, or [`Error::InvalidFee`] if it is not lower than `10_000` (100%).
+pub(crate) fn validate_fee(fee_bps: u32) -> Result<u32> {
+    if fee_bps < 10_000 {
+        Ok(fee_bps)
+    } else {
+        Err(Error::InvalidFee)
+    }
+}
+
+/// Returns the hashes of all the possible contract creation codes embedded in `code`.
+///
+/// A creation code compiled by Solidity starts with the free memory pointer initialization and
+/// ends with the CBOR-encoded metadata of its runtime code, followed by the metadata's length.
+fn creation_code_hashes(code: &[u8]) -> impl Iterator<Item = H256> + '_ {
+    const PREFIXES: [[u8; 5]; 2] = [[0x60, 0x80, 0x60, 0x40, 0x52], [0x60, 0x60, 0x60, 0x40, 0x52]];
+
+    let starts = (0..code.len()).filter(|&i| PREFIXES.iter().any(|p| code[i..].starts_with(p)));
+    let ends: Vec<_> = (2..=code.len())
+        .filter(|&end| {
+            let len = u16::from_be_bytes([code[end - 2], code[end - 1]]) as usize;
+            // metadata maps have 1 to 5 entries
+            (10..=0x80).contains(&len)
+                && end - 2 >= len
+                && (0xa1..=0xa5).contains(&code[end - 2 - len])
+        })
+        .collect();
+    starts.flat_map(move |start| {
+        ends.clone()
+            .into_iter()
+            .filter(move |&end| end > start)
+            .map(move |end| H256(ethers_core::utils::keccak256(&code[start..end])))
+    })
+}
+
+/// Sorts the reserves of the pair composed of `a` and `b` in the same order.
+pub(crate) fn sort_reserves(
+    a: Address,
+    b: Address,
+    (reserve_0, reserve_1, timestamp): (u128, u128, u32),
+) -> Reserves {
+    let (address_0, _) = Library::sort_tokens(a, b);
+    if a == address_0 {
+        (reserve_0.into(), reserve_1.into(), timestamp)
+    } else {
+        (reserve_1.into(), reserve_0.into(), timestamp)
+    }
+}
+
+#[cfg(all(test, feature = "addresses"))]
+mod tests {
+    use super::*;
+    use crate::{pair_code_hashes::*, CustomProtocol};
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    fn encode_reserves(reserve_0: u128, reserve_1: u128, timestamp: u32) -> Vec<u8> {
+        abi::encode(&[
+            Token::Uint(reserve_0.into()),
+            Token::Uint(reserve_1.into()),
+            Token::Uint(timestamp.into()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_get_reserves_multi_single_call() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let factory =
+            Factory::new_with_chain(provider.into(), Chain::Mainnet, ProtocolType::UniswapV2)
+                .unwrap();
+
+        // 4 hops
+        let path: Vec<_> = (1..=5u8).map(Address::repeat_byte).collect();
+        let pairs: Vec<_> = path.windows(2).map(|w| (w[0], w[1])).collect();
+        let reserves: Vec<_> = (1..=4u128).map(|i| (i, i * 10, i as u32)).collect();
+
+        // only one response: any other request would fail
+        let results = reserves
+            .iter()
+            .map(|&(a, b, ts)| {
+                Token::Tuple(vec![Token::Bool(true), Token::Bytes(encode_reserves(a, b, ts))])
+            })
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+
+        let res = factory.get_reserves_multi(&pairs).await.unwrap();
+        assert_eq!(res.len(), 4);
+        for ((a, b, ts), (r_a, r_b, r_ts)) in reserves.into_iter().zip(res) {
+            // tokens are already sorted
+            assert_eq!((U256::from(a), U256::from(b), ts), (r_a, r_b, r_ts));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_reserves_multi_sorted() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let factory = Factory::new(provider.into(), Address::zero(), ProtocolType::UniswapV2);
+        let (a, b) = (Address::repeat_byte(2), Address::repeat_byte(1));
+        mock.push::<Bytes, _>(Bytes::from(encode_reserves(1, 2, 3))).unwrap();
+
+        let res = factory.get_reserves_multi(&[(a, b)]).await.unwrap();
+        assert_eq!(res, vec![(2.into(), 1.into(), 3)]);
+    }
+
+    #[test]
+    fn test_pair_code_hash_override() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let mut factory = Factory::new(provider.into(), Address::zero(), ProtocolType::UniswapV2);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let default = factory.pair_for(a, b).address();
+        assert_eq!(factory.pair_code_hash(None), UNISWAP_V2_PAIR_CODE_HASH);
+
+        factory.set_pair_code_hash(SUSHISWAP_PAIR_CODE_HASH);
+        assert_eq!(factory.pair_code_hash(None), SUSHISWAP_PAIR_CODE_HASH);
+        assert_ne!(factory.pair_for(a, b).address(), default);
+
+        let factory = factory.with_pair_code_hash(UNISWAP_V2_PAIR_CODE_HASH);
+        assert_eq!(factory.pair_for(a, b).address(), default);
+    }
+
+    #[test]
+    fn test_swap_fee_override() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let mut factory = Factory::new(provider.into(), Address::zero(), ProtocolType::Pancakeswap);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(factory.swap_fee_bps(), 25);
+        assert_eq!(factory.pair_for(a, b).swap_fee_bps(), 25);
+
+        factory.set_swap_fee_bps(17).unwrap();
+        assert_eq!(factory.swap_fee_bps(), 17);
+        assert_eq!(factory.pair_for(a, b).swap_fee_bps(), 17);
+
+        assert!(matches!(factory.set_swap_fee_bps(10_000).unwrap_err(), Error::InvalidFee));
+        assert_eq!(factory.swap_fee_bps(), 17);
+    }
+
+    #[test]
+    fn test_custom_protocol() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let custom =
+            CustomProtocol::new(Address::zero(), Address::zero(), true, SUSHISWAP_PAIR_CODE_HASH)
+                .with_swap_fee_bps(17);
+        let factory = Factory::new(provider.into(), Address::zero(), custom.into());
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(factory.pair_code_hash(None), SUSHISWAP_PAIR_CODE_HASH);
+        assert_eq!(factory.pair_for(a, b).swap_fee_bps(), 17);
+    }
+
+    mod detect {
+        use super::*;
+
+        const TOKEN_0: Address = Address::repeat_byte(1);
+        const TOKEN_1: Address = Address::repeat_byte(2);
+
+        fn push_address(mock: &MockProvider, address: Address) {
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(address)]))).unwrap();
+        }
+
+        /// Pushes the responses for the calls made before fetching the factory's code, in reverse.
+        fn push_pair(mock: &MockProvider, pair: Address) {
+            push_address(mock, TOKEN_1);
+            push_address(mock, TOKEN_0);
+            push_address(mock, pair);
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(1.into())]))).unwrap();
+        }
+
+        fn create2(factory: Address, hash: H256) -> Address {
+            let salt = ethers_core::utils::keccak256([TOKEN_0.0, TOKEN_1.0].concat());
+            ethers_core::utils::get_create2_address_from_hash(factory, salt, hash.0)
+        }
+
+        #[tokio::test]
+        async fn test_detect_known() {
+            let (provider, mock) = Provider::<MockProvider>::mocked();
+            let address = Address::repeat_byte(0x11);
+            let factory = Factory::new(provider.into(), address, ProtocolType::UniswapV2);
+            push_pair(&mock, create2(address, SUSHISWAP_PAIR_CODE_HASH));
+            assert_eq!(factory.detect_pair_code_hash().await.unwrap(), SUSHISWAP_PAIR_CODE_HASH);
+        }
+
+        #[tokio::test]
+        async fn test_detect_from_code() {
+            let (provider, mock) = Provider::<MockProvider>::mocked();
+            let address = Address::repeat_byte(0x11);
+            let factory = Factory::new(provider.into(), address, ProtocolType::UniswapV2);
+
+            // init code, runtime code, `a1 <11 bytes>` metadata and its length
+            let mut creation_code = vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x34, 0x80, 0x15];
+            creation_code.extend([0x60, 0x80, 0x60, 0x40, 0x52, 0x00, 0xfe, 0xa1]);
+            creation_code.extend([0x42; 11]);
+            creation_code.extend([0x00, 0x0c]);
+            let hash = H256(ethers_core::utils::keccak256(&creation_code));
+
+            let mut code = vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x00, 0x56];
+            code.extend(&creation_code);
+            code.push(0xa1);
+            code.extend([0x24; 15]);
+            code.extend([0x00, 0x10]);
+
+            mock.push::<Bytes, _>(Bytes::from(code)).unwrap();
+            push_pair(&mock, create2(address, hash));
+            assert_eq!(factory.detect_pair_code_hash().await.unwrap(), hash);
+
+            mock.push::<Bytes, _>(Bytes::from(vec![0x60, 0x80, 0x60, 0x40, 0x52])).unwrap();
+            push_pair(&mock, Address::repeat_byte(0x22));
+            let err = factory.detect_pair_code_hash().await.unwrap_err();
+            assert!(matches!(err, Error::PairCodeHashNotFound));
+        }
+
+        #[tokio::test]
+        async fn test_detect_no_pairs() {
+            let (provider, mock) = Provider::<MockProvider>::mocked();
+            let factory = Factory::new(provider.into(), Address::zero(), ProtocolType::UniswapV2);
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(0.into())]))).unwrap();
+            let err = factory.detect_pair_code_hash().await.unwrap_err();
+            assert!(matches!(err, Error::NoPairs));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_protocol_fee_info() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let factory =
+            Factory::new_with_chain(provider.into(), Chain::Mainnet, ProtocolType::UniswapV2)
+                .unwrap();
+        let push_info = |fee_to: Address, fee_to_setter: Address| {
+            let results = [fee_to, fee_to_setter]
+                .map(|address| {
+                    let data = abi::encode(&[Token::Address(address)]);
+                    Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)])
+                })
+                .to_vec();
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        };
+
+        let setter = Address::repeat_byte(2);
+        push_info(Address::zero(), setter);
+        let info = factory.protocol_fee_info().await.unwrap();
+        assert_eq!(
+            info,
+            ProtocolFeeInfo { fee_to: Address::zero(), fee_to_setter: setter, fee_enabled: false }
+        );
+
+        push_info(Address::repeat_byte(1), setter);
+        let info = factory.protocol_fee_info().await.unwrap();
+        assert!(info.fee_enabled);
+        assert_eq!(info.fee_to, Address::repeat_byte(1));
+    }
+
+    #[tokio::test]
+    async fn test_get_pair_checked() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let factory = Factory::new(provider.into(), Address::zero(), ProtocolType::UniswapV2);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let expected = factory.pair_for(a, b).address();
+        let push_address =
+            |address| mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(address)])));
+
+        push_address(expected).unwrap();
+        assert_eq!(factory.get_pair_checked(a, b).await.unwrap(), expected);
+
+        push_address(Address::zero()).unwrap();
+        let err = factory.get_pair_checked(a, b).await.unwrap_err();
+        assert!(
+            matches!(err, Error::PairNotDeployed { token_a, token_b } if (token_a, token_b) == (a, b))
+        );
+
+        let actual = Address::repeat_byte(3);
+        push_address(actual).unwrap();
+        let err = factory.get_pair_checked(a, b).await.unwrap_err();
+        assert!(
+            matches!(err, Error::PairAddressMismatch { expected: e, actual: r } if e == expected && r == actual)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_pair_checked_solidly() {
+        let (provider, mock) = crate::testing::MockDex::mocked();
+        let protocol = ProtocolType::Velodrome { stable: false };
+        let factory = Factory::new(provider.into(), Address::repeat_byte(0x11), protocol);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let stable = factory.pair_for_solidly(a, b, true).address();
+        assert_ne!(stable, factory.pair_for(a, b).address());
+
+        // only the Solidly `getPair(tokenA, tokenB, stable)` is programmed
+        mock.mock_get_pair_solidly(factory.address(), a, b, true, stable);
+        mock.mock_get_pair_solidly(factory.address(), a, b, false, Address::zero());
+        assert_eq!(factory.get_pair_checked_solidly(b, a, true).await.unwrap(), stable);
+        let err = factory.get_pair_checked(a, b).await.unwrap_err();
+        assert!(matches!(err, Error::PairNotDeployed { .. }));
+
+        // the pairs' code is not programmed, so this falls back to `getPair`
+        assert!(factory.pair_exists_solidly(a, b, true).await.unwrap());
+        assert!(!factory.pair_exists(a, b).await.unwrap());
+        let pairs = [(a, b, true), (a, b, false)];
+        assert_eq!(
+            factory.get_pairs_multi_solidly(&pairs).await.unwrap(),
+            vec![stable, Address::zero()]
+        );
+        assert_eq!(factory.get_pairs_multi(&[(a, b)]).await.unwrap(), vec![Address::zero()]);
+    }
+
+    mod scan {
+        use super::*;
+        use async_trait::async_trait;
+        use ethers_contract::EthEvent;
+        use ethers_core::types::{BlockNumber, Filter, FilterBlockOption, Log, H256};
+        use ethers_providers::{FromErr, ProviderError};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// A middleware which serves `PairCreated` logs from memory, and errors like a provider
+        /// when a query spans more than `max_range` blocks.
+        #[derive(Debug)]
+        struct LimitedLogs {
+            inner: Provider<MockProvider>,
+            logs: Vec<Log>,
+            max_range: u64,
+            calls: AtomicUsize,
+        }
+
+        #[derive(Debug, thiserror::Error)]
+        enum LimitedLogsError {
+            #[error("query returned more than 10000 results")]
+            TooManyResults,
+            #[error(transparent)]
+            Provider(#[from] ProviderError),
+        }
+
+        impl FromErr<ProviderError> for LimitedLogsError {
+            fn from(src: ProviderError) -> Self {
+                Self::Provider(src)
+            }
+        }
+
+        #[async_trait]
+        impl Middleware for LimitedLogs {
+            type Error = LimitedLogsError;
+            type Provider = MockProvider;
+            type Inner = Provider<MockProvider>;
+
+            fn inner(&self) -> &Self::Inner {
+                &self.inner
+            }
+
+            async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Self::Error> {
+                self.calls.fetch_add(1, Ordering::Relaxed);
+                let (from, to) = match filter.block_option {
+                    FilterBlockOption::Range {
+                        from_block: Some(BlockNumber::Number(from)),
+                        to_block: Some(BlockNumber::Number(to)),
+                    } => (from.as_u64(), to.as_u64()),
+                    _ => unreachable!(),
+                };
+                if to - from + 1 > self.max_range {
+                    return Err(LimitedLogsError::TooManyResults);
+                }
+                let in_range = |log: &&Log| {
+                    (from..=to).contains(&log.block_number.unwrap_or_default().as_u64())
+                };
+                Ok(self.logs.iter().filter(in_range).cloned().collect())
+            }
+        }
+
+        fn pair_created(token0: u8, token1: u8, block: u64) -> Log {
+            let topic = |byte| H256::from(Address::repeat_byte(byte));
+            Log {
+                topics: vec![PairCreatedFilter::signature(), topic(token0), topic(token1)],
+                data: abi::encode(&[
+                    Token::Address(Address::repeat_byte(token0 + token1)),
+                    Token::Uint(block.into()),
+                ])
+                .into(),
+                block_number: Some(block.into()),
+                ..Default::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn test_scan_pairs() {
+            let logs = (1..=8).map(|i| pair_created(i, i + 10, i as u64 * 10)).collect();
+            let client = LimitedLogs {
+                inner: Provider::mocked().0,
+                logs,
+                max_range: 20,
+                calls: AtomicUsize::new(0),
+            };
+            let factory = Factory::new(Arc::new(client), Address::zero(), ProtocolType::UniswapV2);
+
+            // [0, 49]: bisected into [0, 24] -> [0, 12] + [13, 24], and [25, 49] -> ...
+            let mut registry = factory.scan_pairs(0, 49, 50).await.unwrap();
+            assert_eq!(registry.len(), 4);
+            assert_eq!(registry.next_block, 50);
+            let pair = registry.get(Address::repeat_byte(13), Address::repeat_byte(3)).unwrap();
+            assert_eq!(pair.address, Address::repeat_byte(16));
+            assert_eq!(pair.block_number, 30);
+
+            // resume
+            factory.resume_scan(&mut registry, 100, 20).await.unwrap();
+            assert_eq!(registry.len(), 8);
+            assert_eq!(registry.next_block, 101);
+            let calls = factory.client().calls.load(Ordering::Relaxed);
+            assert!(calls > 6);
+        }
+
+        #[tokio::test]
+        #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+        async fn test_scan_pairs_fork() {
+            use crate::testing::Fork;
+
+            let fork = Fork::from_env().await.unwrap();
+            let provider = fork.provider().clone();
+            let factory = Factory::new_with_chain(
+                Arc::new(provider),
+                Chain::Mainnet,
+                ProtocolType::UniswapV2,
+            )
+            .unwrap();
+
+            // USDC/WETH
+            let registry = factory.scan_pairs(10_008_300, 10_008_400, 50).await.unwrap();
+            let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+            let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+            let pair = registry.get(usdc, weth).unwrap();
+            assert_eq!(pair.address, Library::pair_for(&factory, usdc, weth));
+            assert_eq!(pair.block_number, 10_008_355);
+        }
     }
 }
@@ -1,15 +1,11 @@
-use super::factory::Factory;
+use super::factory::{validate_fee, Factory};
 use crate::{
+    constants::BPS_U256,
     contracts::bindings::i_uniswap_v2_pair::IUniswapV2Pair,
     errors::{Error, Result},
 };
-use ethers_contract::{Multicall, MulticallVersion};
-use ethers_core::{
-    abi::Tokenizable,
-    types::{Address, U256},
-};
+use ethers_core::types::{Address, U256};
 use ethers_providers::Middleware;
-use std::cmp::Ordering;
 
 /// The Uniswap V2 library, ported to Rust from Solidity.
 ///
@@ -23,23 +19,49 @@ impl Library {
     /// order.
     #[inline]
     pub fn sort_tokens(a: Address, b: Address) -> (Address, Address) {
-        match a.cmp(&b) {
-            Ordering::Less => (a, b),
-            _ => (b, a),
-        }
+        crate::utils::sort_tokens(a, b)
     }
 
     /// Calculates the CREATE2 address for a pair without making any external calls.
-    pub fn pair_for<M: Middleware>(
+    ///
+    /// The pairs of Solidly forks also include whether they are [stable][ProtocolType::stable] in
+    /// the salt, which is the factory's flag. See [`pair_for_solidly`][Self::pair_for_solidly]
+    /// for the other pairs of the tokens.
+    ///
+    /// [ProtocolType::stable]: crate::ProtocolType::stable
+    pub fn pair_for<M: Middleware>(factory: &Factory<M>, a: Address, b: Address) -> Address {
+        Self::pair_for_hop(factory, a, b, factory.protocol().stable())
+    }
+
+    /// Calculates the CREATE2 address for the `stable` or volatile pair of a Solidly fork without
+    /// making any external calls.
+    pub fn pair_for_solidly<M: Middleware>(
+        factory: &Factory<M>,
+        a: Address,
+        b: Address,
+        stable: bool,
+    ) -> Address {
+        Self::pair_for_hop(factory, a, b, Some(stable))
+    }
+
+    /// Calculates the CREATE2 address for a pair, which is a Solidly pair if `stable` is set.
+    pub(crate) fn pair_for_hop<M: Middleware>(
         factory: &Factory<M>,
         mut a: Address,
         mut b: Address,
+        stable: Option<bool>,
     ) -> Address {
         (a, b) = Self::sort_tokens(a, b);
 
         let from = factory.address();
-        // keccak256(abi.encodePacked(a, b))
-        let salt = ethers_core::utils::keccak256([a.0, b.0].concat());
+        let salt = match stable {
+            // keccak256(abi.encodePacked(a, b, stable))
+            Some(stable) => {
+                ethers_core::utils::keccak256([&a.0[..], &b.0[..], &[stable as u8]].concat())
+            }
+            // keccak256(abi.encodePacked(a, b))
+            None => ethers_core::utils::keccak256([a.0, b.0].concat()),
+        };
         let init_code_hash = factory.pair_code_hash(None).0;
 
         ethers_core::utils::get_create2_address_from_hash(from, salt, init_code_hash)
@@ -64,55 +86,28 @@ impl Library {
 
     /// Fetches and sorts the reserves for multiple pairs. Makes only 1 call to the client by using
     /// [Multicall].
+    ///
+    /// See [`Factory::get_reserves_multi`] for more details.
+    ///
+    /// [Multicall]: crate::utils::Multicall
     pub async fn get_reserves_multi<M: Middleware>(
         factory: &Factory<M>,
         path: &[Address],
     ) -> Result<Vec<(U256, U256)>> {
-        let len = match path.len() {
-            0 | 1 => return Err(Error::InvalidPath),
-            2 => {
-                // avoid multicall for only 1 call
-                let (a, b) = (path[0], path[1]);
-                let res = Self::get_reserves(factory, a, b).await?;
-                return Ok(vec![res]);
-            }
-            l => l - 1,
-        };
-
-        let client = factory.client();
-        let mut multicall =
-            Multicall::new(client.clone(), None).await?.version(MulticallVersion::Multicall);
-        // whether to sort the reserves later
-        let mut sorted = Vec::with_capacity(len);
-
-        let pair = IUniswapV2Pair::new(Address::zero(), client);
-        let call = pair.get_reserves();
-        for slice in path.windows(2) {
-            let (a, b) = (slice[0], slice[1]);
-
-            let (address_0, _) = Self::sort_tokens(a, b);
-            sorted.push(address_0 == b);
-
-            let mut call = call.clone();
-            call.tx.set_to(Self::pair_for(factory, a, b));
-            multicall.add_call(call, false);
+        if path.len() < 2 {
+            return Err(Error::InvalidPath);
         }
-
-        multicall
-            .call_raw()
-            .await?
-            .into_iter()
-            .zip(sorted)
-            .map(|(token, sort)| {
-                let (a, b): (U256, U256) = Tokenizable::from_token(token)?;
-                Ok(if sort { (b, a) } else { (a, b) })
-            })
-            .collect()
+        let pairs: Vec<_> = path.windows(2).map(|w| (w[0], w[1])).collect();
+        let reserves = factory.get_reserves_multi(&pairs).await?;
+        Ok(reserves.into_iter().map(|(a, b, _)| (a, b)).collect())
     }
 
     /// Given some amount of an asset and pair reserves, returns an equivalent amount of the other
     /// asset.
     pub fn quote(amount_a: U256, reserve_a: U256, reserve_b: U256) -> Result<U256> {
+        if amount_a.is_zero() {
+            return Err(Error::InsufficientAmount);
+        }
         if reserve_a.is_zero() || reserve_b.is_zero() {
             return Err(Error::InsufficientLiquidity);
         }
@@ -121,66 +116,391 @@ impl Library {
 
     /// Given an input amount of an asset and pair reserves, returns the maximum output amount of
     /// the other asset.
+    ///
+    /// Uses the default Uniswap V2 swap fee of 0.3%. See
+    /// [`get_amount_out_with_fee`][Self::get_amount_out_with_fee] for other fees.
+    #[inline]
     pub fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> Result<U256> {
+        Self::get_amount_out_with_fee(amount_in, reserve_in, reserve_out, DEFAULT_FEE_BPS)
+    }
+
+    /// Given an input amount of an asset, pair reserves and the swap fee in basis points, returns
+    /// the maximum output amount of the other asset.
+    ///
+    /// Returns [`Error::InvalidFee`] if the fee is not lower than 100%, and [`Error::Overflow`] if
+    /// the amounts overflow.
+    pub fn get_amount_out_with_fee(
+        amount_in: U256,
+        reserve_in: U256,
+        reserve_out: U256,
+        fee_bps: u32,
+    ) -> Result<U256> {
+        if amount_in.is_zero() {
+            return Err(Error::InsufficientInputAmount);
+        }
         if reserve_in.is_zero() || reserve_out.is_zero() {
             return Err(Error::InsufficientLiquidity);
         }
-        let amount_in_with_fee = amount_in * 997;
-        let numerator = amount_in_with_fee * reserve_out;
-        let denominator = reserve_in * 1000 + amount_in_with_fee;
+        let amount_in_with_fee =
+            amount_in.checked_mul(fee_multiplier(fee_bps)?).ok_or(Error::Overflow)?;
+        let numerator = amount_in_with_fee.checked_mul(reserve_out).ok_or(Error::Overflow)?;
+        let denominator = reserve_in
+            .checked_mul(BPS_U256)
+            .and_then(|reserve_in| reserve_in.checked_add(amount_in_with_fee))
+            .ok_or(Error::Overflow)?;
         Ok(numerator / denominator)
     }
 
     /// Given an output amount of an asset and pair reserves, returns a required input amount of the
     /// other asset.
+    ///
+    /// Uses the default Uniswap V2 swap fee of 0.3%. See
+    /// [`get_amount_in_with_fee`][Self::get_amount_in_with_fee] for other fees.
+    #[inline]
     pub fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> Result<U256> {
-        if reserve_in.is_zero() || reserve_out.is_zero() {
+        Self::get_amount_in_with_fee(amount_out, reserve_in, reserve_out, DEFAULT_FEE_BPS)
+    }
+
+    /// Given an output amount of an asset, pair reserves and the swap fee in basis points, returns
+    /// a required input amount of the other asset.
+    ///
+    /// Returns [`Error::InvalidFee`] if the fee is not lower than 100%, and [`Error::Overflow`] if
+    /// the amounts overflow.
+    pub fn get_amount_in_with_fee(
+        amount_out: U256,
+        reserve_in: U256,
+        reserve_out: U256,
+        fee_bps: u32,
+    ) -> Result<U256> {
+        if amount_out.is_zero() {
+            return Err(Error::InsufficientOutputAmount);
+        }
+        // `reserve_out - amount_out` underflows or divides by zero in Solidity
+        if reserve_in.is_zero() || reserve_out <= amount_out {
             return Err(Error::InsufficientLiquidity);
         }
-        let numerator = reserve_in * amount_out * 1000;
-        let denominator = (reserve_out - amount_out) * 997;
+        let numerator = reserve_in
+            .checked_mul(amount_out)
+            .and_then(|numerator| numerator.checked_mul(BPS_U256))
+            .ok_or(Error::Overflow)?;
+        let denominator = (reserve_out - amount_out)
+            .checked_mul(fee_multiplier(fee_bps)?)
+            .ok_or(Error::Overflow)?;
         Ok((numerator / denominator) + 1)
     }
 
+    /// Given an input amount of an asset, the reserves of a Solidly volatile pair and the swap fee
+    /// in basis points, returns the maximum output amount of the other asset.
+    ///
+    /// Unlike [`get_amount_out_with_fee`][Self::get_amount_out_with_fee], the fee is deducted from
+    /// the input amount before applying the `xy` invariant, like Solidly pairs' `getAmountOut`.
+    pub fn get_amount_out_volatile(
+        amount_in: U256,
+        reserve_in: U256,
+        reserve_out: U256,
+        fee_bps: u32,
+    ) -> Result<U256> {
+        if amount_in.is_zero() {
+            return Err(Error::InsufficientInputAmount);
+        }
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let amount_in = deduct_fee(amount_in, fee_bps)?;
+        let numerator = amount_in.checked_mul(reserve_out).ok_or(Error::Overflow)?;
+        Ok(numerator / (reserve_in + amount_in))
+    }
+
+    /// Given an input amount of an asset, the reserves of a Solidly stable pair, the decimals of
+    /// its tokens and the swap fee in basis points, returns the maximum output amount of the other
+    /// asset, like Solidly pairs' `getAmountOut`.
+    ///
+    /// The output is found by solving the `x³y + y³x` invariant, on reserves normalized to 18
+    /// decimals, with Newton's method.
+    pub fn get_amount_out_stable(
+        amount_in: U256,
+        reserve_in: U256,
+        reserve_out: U256,
+        decimals_in: u8,
+        decimals_out: u8,
+        fee_bps: u32,
+    ) -> Result<U256> {
+        if amount_in.is_zero() {
+            return Err(Error::InsufficientInputAmount);
+        }
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let (unit_in, unit_out) =
+            (U256::exp10(decimals_in.into()), U256::exp10(decimals_out.into()));
+        let amount_in = deduct_fee(amount_in, fee_bps)?;
+
+        let xy = stable_k(reserve_in, reserve_out, unit_in, unit_out)?;
+        let reserve_in = mul_div(reserve_in, WAD, unit_in)?;
+        let reserve_out = mul_div(reserve_out, WAD, unit_out)?;
+        let amount_in = mul_div(amount_in, WAD, unit_in)?;
+        let x0 = amount_in.checked_add(reserve_in).ok_or(Error::Overflow)?;
+        let y = reserve_out.checked_sub(get_y(x0, xy, reserve_out)?).ok_or(Error::Overflow)?;
+        mul_div(y, unit_out, WAD)
+    }
+
+    /// Performs chained get_amount_out calculations on the already fetched, sorted `reserves` of
+    /// each pair in a path of Solidly pairs, where `stable` is whether each pair is stable.
+    ///
+    /// `decimals` must contain the decimals of each token in the path if any pair is stable,
+    /// otherwise it may be None.
+    pub fn get_amounts_out_solidly(
+        amount_in: U256,
+        reserves: &[(U256, U256)],
+        stable: &[bool],
+        decimals: Option<&[u8]>,
+        fee_bps: u32,
+    ) -> Result<Vec<U256>> {
+        if reserves.is_empty() {
+            return Err(Error::InvalidPath);
+        }
+        if stable.len() != reserves.len() {
+            return Err(Error::InvalidReserves);
+        }
+        let decimals = match decimals {
+            Some(decimals) if decimals.len() != reserves.len() + 1 => {
+                return Err(Error::InvalidReserves)
+            }
+            None if stable.contains(&true) => return Err(Error::InvalidReserves),
+            decimals => decimals,
+        };
+        let mut amounts = Vec::with_capacity(reserves.len() + 1);
+        amounts.push(amount_in);
+        for (i, &(reserve_in, reserve_out)) in reserves.iter().enumerate() {
+            let amount_out = match decimals.filter(|_| stable[i]) {
+                Some(decimals) => Self::get_amount_out_stable(
+                    amounts[i],
+                    reserve_in,
+                    reserve_out,
+                    decimals[i],
+                    decimals[i + 1],
+                    fee_bps,
+                )?,
+                None => {
+                    Self::get_amount_out_volatile(amounts[i], reserve_in, reserve_out, fee_bps)?
+                }
+            };
+            amounts.push(amount_out);
+        }
+        Ok(amounts)
+    }
+
+    /// Performs chained get_amount_out calculations on the already fetched, sorted `reserves` of
+    /// each pair in a path.
+    pub fn get_amounts_out_with_reserves(
+        amount_in: U256,
+        reserves: &[(U256, U256)],
+        fee_bps: u32,
+    ) -> Result<Vec<U256>> {
+        if reserves.is_empty() {
+            return Err(Error::InvalidPath);
+        }
+        let mut amounts = Vec::with_capacity(reserves.len() + 1);
+        amounts.push(amount_in);
+        for (i, &(reserve_in, reserve_out)) in reserves.iter().enumerate() {
+            amounts.push(Self::get_amount_out_with_fee(
+                amounts[i],
+                reserve_in,
+                reserve_out,
+                fee_bps,
+            )?);
+        }
+        Ok(amounts)
+    }
+
+    /// Performs chained get_amount_in calculations on the already fetched, sorted `reserves` of
+    /// each pair in a path.
+    pub fn get_amounts_in_with_reserves(
+        amount_out: U256,
+        reserves: &[(U256, U256)],
+        fee_bps: u32,
+    ) -> Result<Vec<U256>> {
+        if reserves.is_empty() {
+            return Err(Error::InvalidPath);
+        }
+        let len = reserves.len() + 1;
+        let mut amounts = vec![U256::zero(); len];
+        amounts[len - 1] = amount_out;
+        for (i, &(reserve_in, reserve_out)) in reserves.iter().enumerate().rev() {
+            amounts[i] =
+                Self::get_amount_in_with_fee(amounts[i + 1], reserve_in, reserve_out, fee_bps)?;
+        }
+        Ok(amounts)
+    }
+
     /// Performs chained get_amount_out calculations on any number of pairs.
+    ///
+    /// For Solidly stable pairs, also fetches the decimals of the tokens in the path.
     pub async fn get_amounts_out<M: Middleware>(
         factory: &Factory<M>,
         amount_in: U256,
         path: &[Address],
     ) -> Result<Vec<U256>> {
-        let len = path.len();
-        if len < 2 {
+        if path.len() < 2 {
             return Err(Error::InvalidPath);
         }
 
         let reserves = Self::get_reserves_multi(factory, path).await?;
-        let mut amounts = Vec::with_capacity(len);
-        amounts.push(amount_in);
-        for (i, (reserve_in, reserve_out)) in reserves.into_iter().enumerate() {
-            amounts.push(Self::get_amount_out(amounts[i], reserve_in, reserve_out)?);
+        let fee_bps = factory.swap_fee_bps();
+        match factory.protocol().stable() {
+            Some(stable) => {
+                let stable = vec![stable; reserves.len()];
+                Self::get_amounts_out_solidly_with_decimals(
+                    factory, amount_in, path, &reserves, &stable,
+                )
+                .await
+            }
+            None => Self::get_amounts_out_with_reserves(amount_in, &reserves, fee_bps),
         }
-        Ok(amounts)
+    }
+
+    /// Performs chained get_amount_out calculations on a path of Solidly pairs, where `stable` is
+    /// whether each pair is stable, allowing paths which mix stable and volatile pairs.
+    ///
+    /// Also fetches the decimals of the tokens in the path if any pair is stable.
+    pub async fn get_amounts_out_stable<M: Middleware>(
+        factory: &Factory<M>,
+        amount_in: U256,
+        path: &[Address],
+        stable: &[bool],
+    ) -> Result<Vec<U256>> {
+        if path.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        let reserves = Self::get_reserves_multi_solidly(factory, path, stable).await?;
+        Self::get_amounts_out_solidly_with_decimals(factory, amount_in, path, &reserves, stable)
+            .await
+    }
+
+    /// Fetches and sorts the reserves of the `stable` or volatile Solidly pairs of each hop of
+    /// `path`.
+    pub async fn get_reserves_multi_solidly<M: Middleware>(
+        factory: &Factory<M>,
+        path: &[Address],
+        stable: &[bool],
+    ) -> Result<Vec<(U256, U256)>> {
+        if path.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        if stable.len() + 1 != path.len() {
+            return Err(Error::InvalidReserves);
+        }
+        let pairs: Vec<_> = path.windows(2).zip(stable).map(|(w, &s)| (w[0], w[1], s)).collect();
+        let reserves = factory.get_reserves_multi_solidly(&pairs).await?;
+        Ok(reserves.into_iter().map(|(a, b, _)| (a, b)).collect())
+    }
+
+    async fn get_amounts_out_solidly_with_decimals<M: Middleware>(
+        factory: &Factory<M>,
+        amount_in: U256,
+        path: &[Address],
+        reserves: &[(U256, U256)],
+        stable: &[bool],
+    ) -> Result<Vec<U256>> {
+        let decimals = match stable.contains(&true) {
+            true => Some(factory.get_decimals_multi(path).await?),
+            false => None,
+        };
+        let fee_bps = factory.swap_fee_bps();
+        Self::get_amounts_out_solidly(amount_in, reserves, stable, decimals.as_deref(), fee_bps)
     }
 
     /// Performs chained get_amount_in calculations on any number of pairs.
+    ///
+    /// Returns [`Error::ExactOutUnsupported`] for Solidly pairs.
     pub async fn get_amounts_in<M: Middleware>(
         factory: &Factory<M>,
         amount_out: U256,
         path: &[Address],
     ) -> Result<Vec<U256>> {
-        let len = path.len();
-        if len < 2 {
+        if path.len() < 2 {
             return Err(Error::InvalidPath);
         }
+        if factory.protocol().is_solidly() {
+            return Err(Error::ExactOutUnsupported);
+        }
 
         let reserves = Self::get_reserves_multi(factory, path).await?;
-        let mut amounts = vec![U256::zero(); len];
-        amounts[len - 1] = amount_out;
-        for (i, (reserve_in, reserve_out)) in reserves.into_iter().enumerate().rev() {
-            amounts[i] = Self::get_amount_in(amounts[i + 1], reserve_in, reserve_out)?;
+        let fee_bps = factory.swap_fee_bps();
+        Self::get_amounts_in_with_reserves(amount_out, &reserves, fee_bps)
+    }
+}
+
+/// The default Uniswap V2 swap fee, in basis points.
+const DEFAULT_FEE_BPS: u32 = 30;
+
+/// Returns `10_000 - fee_bps`, the multiplier applied to input amounts, or
+/// [`Error::InvalidFee`] if the fee is not lower than 100%.
+#[inline]
+fn fee_multiplier(fee_bps: u32) -> Result<U256> {
+    Ok(BPS_U256 - validate_fee(fee_bps)?)
+}
+
+/// Returns `amount` minus the fee of `fee_bps`, rounded down, deducted from it.
+#[inline]
+fn deduct_fee(amount: U256, fee_bps: u32) -> Result<U256> {
+    let fee = mul_div(amount, validate_fee(fee_bps)?.into(), BPS_U256)?;
+    Ok(amount - fee)
+}
+
+/// `1e18`, the fixed-point unit of the stable swap math.
+const WAD: U256 = U256([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Returns `a * b / denominator`, or [`Error::Overflow`] if `a * b` overflows.
+#[inline]
+fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256> {
+    Ok(a.checked_mul(b).ok_or(Error::Overflow)? / denominator)
+}
+
+/// Returns the `x³y + y³x` invariant of a stable pair's reserves, normalized to 18 decimals.
+fn stable_k(x: U256, y: U256, unit_x: U256, unit_y: U256) -> Result<U256> {
+    let x = mul_div(x, WAD, unit_x)?;
+    let y = mul_div(y, WAD, unit_y)?;
+    let a = mul_div(x, y, WAD)?;
+    let b = mul_div(x, x, WAD)?.checked_add(mul_div(y, y, WAD)?).ok_or(Error::Overflow)?;
+    mul_div(a, b, WAD)
+}
+
+/// `x0³y + y³x0`, the Solidly pair's `_f`.
+fn f(x0: U256, y: U256) -> Result<U256> {
+    let y3 = mul_div(mul_div(y, y, WAD)?, y, WAD)?;
+    let x3 = mul_div(mul_div(x0, x0, WAD)?, x0, WAD)?;
+    mul_div(x0, y3, WAD)?.checked_add(mul_div(x3, y, WAD)?).ok_or(Error::Overflow)
+}
+
+/// `3x0y² + x0³`, the derivative of [f] with respect to `y`, the Solidly pair's `_d`.
+fn d(x0: U256, y: U256) -> Result<U256> {
+    let x0_3 = x0.checked_mul(3.into()).ok_or(Error::Overflow)?;
+    let x3 = mul_div(mul_div(x0, x0, WAD)?, x0, WAD)?;
+    mul_div(x0_3, mul_div(y, y, WAD)?, WAD)?.checked_add(x3).ok_or(Error::Overflow)
+}
+
+/// Solves `f(x0, y) = xy` for `y` with at most 255 iterations of Newton's method starting from
+/// `y`, the Solidly pair's `_get_y`.
+fn get_y(x0: U256, xy: U256, mut y: U256) -> Result<U256> {
+    for _ in 0..255 {
+        let y_prev = y;
+        let k = f(x0, y)?;
+        let d = d(x0, y)?;
+        if d.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        if k < xy {
+            y = y.checked_add(mul_div(xy - k, WAD, d)?).ok_or(Error::Overflow)?;
+        } else {
+            y = y.checked_sub(mul_div(k - xy, WAD, d)?).ok_or(Error::Overflow)?;
+        }
+        let diff = if y > y_prev { y - y_prev } else { y_prev - y };
+        if diff <= U256::one() {
+            return Ok(y);
         }
-        Ok(amounts)
     }
+    Ok(y)
 }
 
 #[cfg(all(test, feature = "addresses"))]
@@ -190,6 +510,7 @@ mod tests {
     use ethers_contract::Lazy;
     use ethers_core::types::Chain;
     use ethers_providers::{Http, Provider, MAINNET};
+    use std::sync::Arc;
 
     static FACTORY: Lazy<Factory<Provider<Http>>> = Lazy::new(|| {
         Factory::new_with_chain(MAINNET.provider().into(), Chain::Mainnet, ProtocolType::UniswapV2)
@@ -204,6 +525,8 @@ mod tests {
 
     #[test]
     fn can_sort_tokens() {
+        use std::cmp::Ordering;
+
         let (a, b) = (Address::random(), Address::random());
         let (res_a, res_b) = Library::sort_tokens(a, b);
         match a.cmp(&b) {
@@ -261,6 +584,199 @@ mod tests {
         assert_eq!(amount_b, (amount_a * reserve_b) / reserve_a);
     }
 
+    #[test]
+    fn can_get_amount_out_offline() {
+        // https://github.com/Uniswap/v2-core/blob/master/test/UniswapV2Pair.spec.ts
+        let cases = [
+            (1, 5, 10, "1662497915624478906"),
+            (1, 10, 5, "453305446940074565"),
+            (2, 5, 10, "2851015155847869602"),
+            (2, 10, 5, "831248957812239453"),
+            (1, 10, 10, "906610893880149131"),
+            (1, 100, 100, "987158034397061298"),
+            (1, 1000, 1000, "996006981039903216"),
+        ];
+        let base = U256::exp10(18);
+        for (amount_in, reserve_in, reserve_out, expected) in cases {
+            let amount_in = U256::from(amount_in) * base;
+            let (reserve_in, reserve_out) =
+                (U256::from(reserve_in) * base, U256::from(reserve_out) * base);
+            let amount_out = Library::get_amount_out(amount_in, reserve_in, reserve_out).unwrap();
+            assert_eq!(amount_out, U256::from_dec_str(expected).unwrap());
+        }
+
+        // https://github.com/Uniswap/v2-periphery/blob/master/test/UniswapV2Router01.spec.ts
+        let (two, hundred) = (U256::from(2), U256::from(100));
+        assert_eq!(Library::get_amount_out(two, hundred, hundred).unwrap(), 1.into());
+        assert_eq!(Library::get_amount_in(1.into(), hundred, hundred).unwrap(), 2.into());
+
+        let res = Library::get_amount_out(U256::zero(), hundred, hundred);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientInputAmount));
+        let res = Library::get_amount_out(two, U256::zero(), hundred);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientLiquidity));
+        let res = Library::get_amount_in(U256::zero(), hundred, hundred);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientOutputAmount));
+        let res = Library::get_amount_in(hundred, hundred, hundred);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientLiquidity));
+    }
+
+    #[test]
+    fn can_get_amounts_with_fee() {
+        let amount = U256::exp10(18);
+        let (reserve_in, reserve_out) = (U256::exp10(21), U256::exp10(24));
+
+        // 30 bps == 997 / 1000
+        let amount_out = Library::get_amount_out_with_fee(amount, reserve_in, reserve_out, 30);
+        let expected = (amount * 997 * reserve_out) / (reserve_in * 1000 + amount * 997);
+        assert_eq!(amount_out.unwrap(), expected);
+
+        // Pancakeswap: 9975 / 10000
+        let fee_bps = ProtocolType::Pancakeswap.swap_fee_bps();
+        let amount_out = Library::get_amount_out_with_fee(amount, reserve_in, reserve_out, fee_bps);
+        assert_eq!(amount_out.unwrap(), U256::from_dec_str("996505985279683515693").unwrap());
+        let amount_in = Library::get_amount_in_with_fee(amount, reserve_in, reserve_out, fee_bps);
+        assert_eq!(amount_in.unwrap(), U256::from_dec_str("1002507268171429").unwrap());
+
+        // chained
+        let reserves = [(reserve_in, reserve_out), (reserve_out, reserve_in)];
+        let amounts = Library::get_amounts_out_with_reserves(amount, &reserves, 30).unwrap();
+        assert_eq!(amounts.len(), 3);
+        assert_eq!(amounts[0], amount);
+        let amounts_in = Library::get_amounts_in_with_reserves(amounts[2], &reserves, 30).unwrap();
+        assert_eq!(amounts_in.len(), 3);
+        assert!(amounts_in[0] <= amount);
+        assert!(Library::get_amounts_out_with_reserves(amount, &[], 30).is_err());
+
+        // invalid fees and user supplied reserves do not panic
+        for fee_bps in [10_000, u32::MAX] {
+            let res = Library::get_amount_out_with_fee(amount, reserve_in, reserve_out, fee_bps);
+            assert!(matches!(res.unwrap_err(), Error::InvalidFee));
+            let res = Library::get_amount_in_with_fee(amount, reserve_in, reserve_out, fee_bps);
+            assert!(matches!(res.unwrap_err(), Error::InvalidFee));
+            let res = Library::get_amount_out_volatile(amount, reserve_in, reserve_out, fee_bps);
+            assert!(matches!(res.unwrap_err(), Error::InvalidFee));
+        }
+        let res = Library::get_amount_out_with_fee(U256::MAX, reserve_in, reserve_out, 30);
+        assert!(matches!(res.unwrap_err(), Error::Overflow));
+        let res = Library::get_amount_out_with_fee(amount, U256::MAX, reserve_out, 30);
+        assert!(matches!(res.unwrap_err(), Error::Overflow));
+        let res = Library::get_amount_in_with_fee(amount, U256::MAX, U256::MAX, 30);
+        assert!(matches!(res.unwrap_err(), Error::Overflow));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a BSC node at UNISWAP_RS_BSC_FORK_URL"]
+    async fn pancake_quotes_match_router() {
+        let url = std::env::var("UNISWAP_RS_BSC_FORK_URL").unwrap();
+        let client = Arc::new(Provider::<Http>::try_from(url).unwrap());
+        let chain = Chain::BinanceSmartChain;
+        let protocol = ProtocolType::Pancakeswap;
+        let factory = Factory::new_with_chain(client.clone(), chain, protocol).unwrap();
+        let router = crate::contracts::bindings::i_uniswap_v2_router_02::IUniswapV2Router02::new(
+            protocol.try_addresses(chain).1.unwrap(),
+            client,
+        );
+
+        // WBNB -> BUSD -> USDT
+        let path = [
+            "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c".parse().unwrap(),
+            "0xe9e7CEA3DedcA5984780Bafc599bD69ADd087D56".parse().unwrap(),
+            "0x55d398326f99059fF775485246999027B3197955".parse().unwrap(),
+        ];
+        let amount = U256::exp10(18);
+        let one = U256::one();
+        let within_one_wei = |a: &[U256], b: &[U256]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(&a, &b)| a.max(b) - a.min(b) <= one)
+        };
+
+        let expected = router.get_amounts_out(amount, path.to_vec()).call().await.unwrap();
+        let amounts = Library::get_amounts_out(&factory, amount, &path).await.unwrap();
+        assert!(within_one_wei(&amounts, &expected), "{amounts:?} != {expected:?}");
+
+        let expected = router.get_amounts_in(amount, path.to_vec()).call().await.unwrap();
+        let amounts = Library::get_amounts_in(&factory, amount, &path).await.unwrap();
+        assert!(within_one_wei(&amounts, &expected), "{amounts:?} != {expected:?}");
+    }
+
+    #[test]
+    fn can_get_solidly_pair_address() {
+        let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545").unwrap());
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let address = Address::repeat_byte(0x11);
+        let factory =
+            |stable| Factory::new(client.clone(), address, ProtocolType::Velodrome { stable });
+        let pair_for = |stable| Library::pair_for(&factory(stable), b, a);
+
+        for stable in [false, true] {
+            let salt =
+                ethers_core::utils::keccak256([&a.0[..], &b.0[..], &[stable as u8]].concat());
+            let expected = ethers_core::utils::get_create2_address_from_hash(
+                address,
+                salt,
+                crate::pair_code_hashes::VELODROME_PAIR_CODE_HASH.0,
+            );
+            assert_eq!(pair_for(stable), expected);
+            // the other pair of the tokens does not depend on the factory's flag
+            assert_eq!(Library::pair_for_solidly(&factory(!stable), a, b, stable), expected);
+        }
+        assert_ne!(pair_for(false), pair_for(true));
+    }
+
+    #[test]
+    fn can_get_solidly_amounts() {
+        let e6 = |x: u64| U256::from(x) * U256::exp10(6);
+        let e18 = |x: u64| U256::from(x) * U256::exp10(18);
+
+        // values computed with the Solidity implementation
+        let amount_out =
+            Library::get_amount_out_stable(e6(1_000), e6(10_000_000), e18(10_000_000), 6, 18, 2);
+        assert_eq!(amount_out.unwrap(), U256::from_dec_str("999799999999500399880").unwrap());
+        let amount_out =
+            Library::get_amount_out_stable(e18(100_000), e18(5_000_000), e6(12_000_000), 18, 6, 2);
+        assert_eq!(amount_out.unwrap(), U256::from(114_283_925_883u64));
+        let amount_out = Library::get_amount_out_volatile(e18(1), e18(1_000), e18(1_000_000), 2);
+        assert_eq!(amount_out.unwrap(), U256::from_dec_str("998801398361917754628").unwrap());
+
+        let res = Library::get_amount_out_stable(U256::zero(), e6(1), e18(1), 6, 18, 2);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientInputAmount));
+        let res = Library::get_amount_out_stable(e6(1), U256::zero(), e18(1), 6, 18, 2);
+        assert!(matches!(res.unwrap_err(), Error::InsufficientLiquidity));
+        let res = Library::get_amount_out_stable(e6(1), U256::MAX, e18(1), 6, 18, 2);
+        assert!(matches!(res.unwrap_err(), Error::Overflow));
+
+        // chained
+        let reserves = [(e6(10_000_000), e18(10_000_000)), (e18(10_000_000), e6(10_000_000))];
+        let decimals = Some(&[6, 18, 6][..]);
+        let amounts =
+            Library::get_amounts_out_solidly(e6(1_000), &reserves, &[true; 2], decimals, 2)
+                .unwrap();
+        assert_eq!(amounts.len(), 3);
+        assert!(amounts[2] < amounts[0] && amounts[2] > e6(999));
+        let res = Library::get_amounts_out_solidly(e6(1_000), &reserves, &[true; 2], None, 2);
+        assert!(matches!(res.unwrap_err(), Error::InvalidReserves));
+        let res = Library::get_amounts_out_solidly(e6(1_000), &reserves, &[true], decimals, 2);
+        assert!(matches!(res.unwrap_err(), Error::InvalidReserves));
+        let res =
+            Library::get_amounts_out_solidly(e6(1_000), &reserves, &[true; 2], Some(&[6, 18]), 2);
+        assert!(matches!(res.unwrap_err(), Error::InvalidReserves));
+
+        // mixed: a stable hop followed by a volatile one
+        let reserves = [(e6(10_000_000), e18(10_000_000)), (e18(1_000), e18(1_000_000))];
+        let amounts =
+            Library::get_amounts_out_solidly(e6(1_000), &reserves, &[true, false], decimals, 2)
+                .unwrap();
+        let stable =
+            Library::get_amount_out_stable(e6(1_000), e6(10_000_000), e18(10_000_000), 6, 18, 2)
+                .unwrap();
+        let volatile =
+            Library::get_amount_out_volatile(stable, e18(1_000), e18(1_000_000), 2).unwrap();
+        assert_eq!(amounts, [e6(1_000), stable, volatile]);
+        // the decimals are optional without stable hops
+        let amounts =
+            Library::get_amounts_out_solidly(e6(1_000), &reserves, &[false; 2], None, 2).unwrap();
+        assert_eq!(amounts.len(), 3);
+    }
+
     #[tokio::test]
     #[ignore = "async test"]
     async fn can_quote_async() {
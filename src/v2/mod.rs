@@ -2,12 +2,21 @@
 
 mod factory;
 mod library;
+mod oracle;
 mod pair;
+mod position;
 mod protocol;
+mod registry;
 mod router;
+mod trade;
 
-pub use factory::Factory;
+pub(crate) use factory::{sort_reserves, validate_fee};
+pub use factory::{Factory, ProtocolFeeInfo};
 pub use library::Library;
-pub use pair::Pair;
+pub use oracle::{CumulativePrices, Twap};
+pub use pair::{HistoricalPrice, Pair, PairState};
+pub use position::LiquidityPosition;
 pub use protocol::Protocol;
-pub use router::Router;
+pub use registry::{PairInfo, PairRegistry};
+pub use router::{Router, DEFAULT_MAX_HOPS};
+pub use trade::Trade;
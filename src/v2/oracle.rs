@@ -0,0 +1,216 @@
+use crate::errors::{Error, Result};
+use ethers_core::types::U256;
+use std::time::Duration;
+
+/// The number of fractional bits of a UQ112x112 fixed-point number.
+const RESOLUTION: usize = 112;
+
+/// A pair's cumulative prices at a block, as returned by the Solidity [oracle library]'s
+/// `currentCumulativePrices`.
+///
+/// [oracle library]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/libraries/UniswapV2OracleLibrary.sol
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CumulativePrices {
+    /// The time-weighted sum of the UQ112x112 price of `token0`, wrapping on overflow.
+    pub price0_cumulative: U256,
+    /// The time-weighted sum of the UQ112x112 price of `token1`, wrapping on overflow.
+    pub price1_cumulative: U256,
+    /// The block timestamp, modulo `2**32`.
+    pub timestamp: u32,
+}
+
+impl CumulativePrices {
+    /// Returns the cumulative prices at a block with `block_timestamp`, given the pair's last
+    /// cumulative prices and its reserves, counterfactually accumulating the prices since the
+    /// reserves were last updated to save a `sync` call.
+    ///
+    /// Returns [`Error::ZeroReserves`] if the prices must be accumulated while a reserve is zero,
+    /// in which case the Solidity library reverts.
+    pub fn new(
+        price0_cumulative_last: U256,
+        price1_cumulative_last: U256,
+        (reserve0, reserve1, timestamp_last): (u128, u128, u32),
+        block_timestamp: u64,
+    ) -> Result<Self> {
+        let timestamp = block_timestamp as u32;
+        let (mut price0_cumulative, mut price1_cumulative) =
+            (price0_cumulative_last, price1_cumulative_last);
+
+        // if time has elapsed since the last update on the pair, mock the accumulated price values
+        if timestamp_last != timestamp {
+            // subtraction overflow is desired
+            let time_elapsed = U256::from(timestamp.wrapping_sub(timestamp_last));
+            // addition overflow is desired
+            price0_cumulative =
+                price0_cumulative.overflowing_add(fraction(reserve1, reserve0)? * time_elapsed).0;
+            price1_cumulative =
+                price1_cumulative.overflowing_add(fraction(reserve0, reserve1)? * time_elapsed).0;
+        }
+
+        Ok(Self { price0_cumulative, price1_cumulative, timestamp })
+    }
+}
+
+/// The time-weighted average prices of a pair's tokens over a window, returned by
+/// [`Pair::observe`][super::Pair::observe].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Twap {
+    /// The average UQ112x112 price of `token0` in terms of `token1`.
+    pub price0: U256,
+    /// The average UQ112x112 price of `token1` in terms of `token0`.
+    pub price1: U256,
+    /// The actual duration of the window.
+    pub window_actual: Duration,
+}
+
+impl Twap {
+    /// Returns the average prices between the `start` and `end` cumulative prices, like the
+    /// Solidity [example oracle].
+    ///
+    /// The timestamps and cumulative prices wrap around, so the window must be shorter than
+    /// `2**32` seconds. Returns [`Error::InvalidTwapWindow`] if no time has elapsed.
+    ///
+    /// [example oracle]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/examples/ExampleOracleSimple.sol
+    pub fn new(start: &CumulativePrices, end: &CumulativePrices) -> Result<Self> {
+        // overflow is desired
+        let time_elapsed = end.timestamp.wrapping_sub(start.timestamp);
+        if time_elapsed == 0 {
+            return Err(Error::InvalidTwapWindow);
+        }
+        let average = |start: U256, end: U256| {
+            // overflow is desired
+            let average = end.overflowing_sub(start).0 / U256::from(time_elapsed);
+            // truncate to uint224
+            average & ((U256::one() << 224) - 1)
+        };
+        Ok(Self {
+            price0: average(start.price0_cumulative, end.price0_cumulative),
+            price1: average(start.price1_cumulative, end.price1_cumulative),
+            window_actual: Duration::from_secs(time_elapsed.into()),
+        })
+    }
+
+    /// Returns the amount of `token1` equivalent to `amount_in` of `token0` at the average price,
+    /// rounded down, or None if it overflows.
+    pub fn consult0(&self, amount_in: U256) -> Option<U256> {
+        consult(self.price0, amount_in)
+    }
+
+    /// Returns the amount of `token0` equivalent to `amount_in` of `token1` at the average price,
+    /// rounded down, or None if it overflows.
+    pub fn consult1(&self, amount_in: U256) -> Option<U256> {
+        consult(self.price1, amount_in)
+    }
+
+    /// Returns the average price of `token0` as a float. Not adjusted for the tokens' decimals.
+    pub fn price0_f64(&self) -> f64 {
+        to_f64(self.price0)
+    }
+
+    /// Returns the average price of `token1` as a float. Not adjusted for the tokens' decimals.
+    pub fn price1_f64(&self) -> f64 {
+        to_f64(self.price1)
+    }
+}
+
+/// Returns a UQ112x112 which represents the ratio of the numerator to the denominator, like
+/// `FixedPoint.fraction`.
+fn fraction(numerator: u128, denominator: u128) -> Result<U256> {
+    if denominator == 0 {
+        return Err(Error::ZeroReserves);
+    }
+    Ok((U256::from(numerator) << RESOLUTION) / denominator)
+}
+
+/// Multiplies a UQ112x112 by `y` and decodes the result, like `FixedPoint.mul(..).decode144()`,
+/// returning None on a `MULTIPLICATION_OVERFLOW` revert.
+fn consult(price: U256, y: U256) -> Option<U256> {
+    let z = price.checked_mul(y)?;
+    // uint144
+    Some((z >> RESOLUTION) & ((U256::one() << 144) - 1))
+}
+
+/// Converts a UQ112x112 into a float.
+fn to_f64(x: U256) -> f64 {
+    let int = (x >> RESOLUTION).low_u128() as f64;
+    let frac = (x & ((U256::one() << RESOLUTION) - 1)).low_u128() as f64;
+    int + frac / 2f64.powi(RESOLUTION as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const Q112: U256 = U256([0, 1 << 48, 0, 0]);
+
+    #[test]
+    fn test_cumulative_prices() {
+        let reserves = (1_000, 4_000, 100);
+
+        // no time elapsed since the last update
+        let prices = CumulativePrices::new(1.into(), 2.into(), reserves, 100).unwrap();
+        assert_eq!(
+            prices,
+            CumulativePrices {
+                price0_cumulative: 1.into(),
+                price1_cumulative: 2.into(),
+                timestamp: 100
+            }
+        );
+
+        let prices = CumulativePrices::new(U256::zero(), U256::zero(), reserves, 110).unwrap();
+        assert_eq!(prices.price0_cumulative, Q112 * 4 * 10);
+        assert_eq!(prices.price1_cumulative, Q112 / 4 * 10);
+
+        let res = CumulativePrices::new(U256::zero(), U256::zero(), (0, 1, 100), 110);
+        assert!(matches!(res.unwrap_err(), Error::ZeroReserves));
+    }
+
+    #[test]
+    fn test_twap() {
+        let start =
+            CumulativePrices { price0_cumulative: Q112, price1_cumulative: Q112, timestamp: 0 };
+        let end = CumulativePrices {
+            price0_cumulative: Q112 * 21,
+            price1_cumulative: Q112 * 6,
+            timestamp: 10,
+        };
+        let twap = Twap::new(&start, &end).unwrap();
+        assert_eq!(twap.price0, Q112 * 2);
+        assert_eq!(twap.price1, Q112 / 2);
+        assert_eq!(twap.window_actual, Duration::from_secs(10));
+        assert_eq!(twap.price0_f64(), 2.0);
+        assert_eq!(twap.price1_f64(), 0.5);
+        assert_eq!(twap.consult0(1_001.into()), Some(2_002.into()));
+        assert_eq!(twap.consult1(1_001.into()), Some(500.into()));
+        assert_eq!(twap.consult0(U256::MAX), None);
+
+        let res = Twap::new(&start, &start);
+        assert!(matches!(res.unwrap_err(), Error::InvalidTwapWindow));
+    }
+
+    #[test]
+    fn test_twap_wraparound() {
+        // the timestamp wraps around 2**32 and the cumulative prices around 2**256
+        let reserves = (1_000, 3_000, u32::MAX - 4);
+        let start =
+            CumulativePrices::new(U256::MAX - Q112, U256::MAX, reserves, u32::MAX as u64 - 4)
+                .unwrap();
+        let end = CumulativePrices::new(
+            start.price0_cumulative,
+            start.price1_cumulative,
+            reserves,
+            1 << 32 | 5,
+        )
+        .unwrap();
+        assert_eq!(end.timestamp, 5);
+        assert!(end.price0_cumulative < start.price0_cumulative);
+
+        let twap = Twap::new(&start, &end).unwrap();
+        assert_eq!(twap.window_actual, Duration::from_secs(10));
+        assert_eq!(twap.price0, Q112 * 3);
+        assert_eq!(twap.price1, Q112 / 3);
+    }
+}
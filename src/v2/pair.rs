@@ -1,53 +1,124 @@
-use super::{Factory, Library};
-use crate::{contracts::bindings::i_uniswap_v2_pair::IUniswapV2Pair, errors::Result, ProtocolType};
+use super::{
+    position::{amounts_for_liquidity, protocol_fee_liquidity},
+    CumulativePrices, Factory, Library, LiquidityPosition, Twap,
+};
+use crate::{
+    common::{block_at_timestamp, get_block, get_logs, subscribe_logs, watch_logs},
+    constants::BPS_U256,
+    contracts::bindings::{
+        i_uniswap_v2_callee::UniswapV2CallCall,
+        i_uniswap_v2_pair::{IUniswapV2Pair, IUniswapV2PairEvents, SwapFilter, SyncFilter},
+    },
+    errors::{Error, Result},
+    utils, DecodedLog, Permit, ProtocolType, RequestPolicy,
+};
 use ethers_contract::{
     builders::ContractCall, ContractError, Multicall, MulticallError, MulticallVersion,
 };
 use ethers_core::{
-    abi::{Detokenize, Token},
-    types::{Address, Chain, H256},
+    abi::{Detokenize, InvalidOutputType, Token, Tokenizable},
+    types::{Address, BlockId, BlockNumber, Bytes, Chain, Log, H256, U256, U512, U64},
+};
+use ethers_providers::{Middleware, PubsubClient};
+use futures_util::{lock::Mutex, stream, Stream, StreamExt, TryStreamExt};
+use once_cell::sync::OnceCell;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, PoisonError, RwLock,
+    },
+    time::Duration,
 };
-use ethers_providers::Middleware;
-use std::{fmt, sync::Arc};
 
 type Tokens = (Address, Address);
 type Reserves = (u128, u128, u32);
 
+/// A snapshot of a pair's state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PairState {
+    /// The reserves of the pair and the timestamp of the block in which they were last updated.
+    pub reserves: Reserves,
+    /// The total supply of liquidity tokens.
+    pub total_supply: U256,
+    /// The number of the block at which the snapshot was taken.
+    pub block_number: u64,
+    /// The timestamp of the block at which the snapshot was taken.
+    pub timestamp: u64,
+}
+
+/// The reserves and mid price of a pair at a past block, returned by
+/// [`Pair::price_history`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct HistoricalPrice {
+    /// The number of the block.
+    pub block_number: u64,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The reserve of `token0`.
+    pub reserve0: u128,
+    /// The reserve of `token1`.
+    pub reserve1: u128,
+    /// The mid price of the base token in terms of the other token, or None if a reserve is zero.
+    pub price: Option<f64>,
+}
+
+/// The cached state of a pair, shared between its clones.
+#[derive(Debug, Default)]
+struct Cache {
+    /// The ordered tokens of the pair, which never change once fetched.
+    tokens: OnceCell<Tokens>,
+
+    /// Whether the pair is currently deployed in the client's network.
+    deployed: AtomicBool,
+
+    /// The latest snapshot of the pair's state.
+    state: RwLock<Option<PairState>>,
+
+    /// The number of completed syncs, used to deduplicate concurrent ones.
+    syncs: AtomicUsize,
+
+    /// Serializes the syncs.
+    lock: Mutex<()>,
+}
+
 contract_struct! {
     /// A UniswapV2 liquidity pair, composed of 2 different ERC20 tokens.
+    ///
+    /// The pair's tokens and state are cached and shared between its clones.
     pub struct Pair<M> {
         /// The pair contract.
         contract: IUniswapV2Pair<M>,
 
-        /// The ordered tokens of the pair.
-        tokens: Option<Tokens>,
-
-        /// Whether the pair is currently deployed in the client's network.
-        deployed: bool,
-
-        /// The token reserves of the pair.
-        reserves: Option<Reserves>,
+        /// The cached state of the pair.
+        cache: Arc<Cache>,
 
         /// The protocol of the pair.
         pub protocol: ProtocolType,
+
+        /// The swap fee override, in basis points.
+        pub(super) swap_fee_bps: Option<u32>,
     }
 }
 
 impl<M> fmt::Display for Pair<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let address = self.address();
-        if self.tokens.is_none() && self.reserves.is_none() {
+        let (tokens, reserves) = (self.tokens(), self.reserves());
+        if tokens.is_none() && reserves.is_none() {
             return writeln!(f, "Pair: {address:?}");
         }
         writeln!(f, "Pair:     {address:?}")?;
-        if let Some((a, b)) = self.tokens {
+        if let Some((a, b)) = tokens {
             writeln!(f, "Token0:   {a:?}")?;
             write!(f, "Token1:   {b:?}")?;
-            if self.reserves.is_some() {
+            if reserves.is_some() {
                 writeln!(f)?
             };
         }
-        if let Some((a, b, _)) = self.reserves {
+        if let Some((a, b, _)) = reserves {
             writeln!(f, "Reserve0: {a:?}")?;
             write!(f, "Reserve1: {b:?}")?;
         }
@@ -60,21 +131,101 @@ impl<M> Pair<M> {
     ///
     /// Note: this will always be false before syncing.
     pub fn deployed(&self) -> bool {
-        self.deployed
+        self.cache.deployed.load(Ordering::Acquire)
     }
 
     /// Returns the addresses of the tokens that make up this pair.
     ///
-    /// Note: this will always be None before syncing.
+    /// Note: this will always be None before syncing, unless the pair was created with
+    /// [`new_with_factory`][Pair::new_with_factory].
     pub fn tokens(&self) -> Option<Tokens> {
-        self.tokens
+        self.cache.tokens.get().copied()
     }
 
-    /// Returns the reserves of the pair.
+    /// Returns the cached reserves of the pair.
     ///
     /// Note: this will always be None before syncing.
     pub fn reserves(&self) -> Option<Reserves> {
-        self.reserves
+        self.state().map(|state| state.reserves)
+    }
+
+    /// Returns the cached snapshot of the pair's state.
+    ///
+    /// Note: this will always be None before syncing.
+    pub fn state(&self) -> Option<PairState> {
+        *self.cache.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the mid price of `base_token` in terms of the other token of the pair, from the
+    /// cached reserves.
+    ///
+    /// The price is the ratio of the raw reserves, and is not adjusted for the tokens' decimals.
+    /// Returns [`Error::PairNotSynced`] if the tokens or reserves have not been cached yet, and
+    /// [`Error::TokenNotInPair`] if `base_token` is not one of the pair's tokens.
+    pub fn price(&self, base_token: Address) -> Result<f64> {
+        let tokens = self.tokens().ok_or(Error::PairNotSynced)?;
+        let (reserve0, reserve1, _) = self.reserves().ok_or(Error::PairNotSynced)?;
+        mid_price(is_token0(tokens, base_token)?, reserve0, reserve1)
+    }
+
+    /// Returns the amounts of the pair's tokens received by burning `liquidity` tokens, from the
+    /// cached reserves and total supply, as computed by the pair's `burn` method.
+    ///
+    /// `liquidity` is capped at the redeemable supply, `total_supply - MINIMUM_LIQUIDITY`, since
+    /// the first [MINIMUM_LIQUIDITY] tokens are locked forever, so the amounts never exceed the
+    /// reserves.
+    ///
+    /// Note: the pair's balances may be greater than its reserves, and the protocol fee, if
+    /// enabled, is minted before burning, so the actual amounts may slightly differ.
+    ///
+    /// Returns [`Error::PairNotSynced`] if the state has not been cached yet.
+    ///
+    /// [MINIMUM_LIQUIDITY]: crate::constants::MINIMUM_LIQUIDITY
+    pub fn amounts_for_liquidity(&self, liquidity: U256) -> Result<(U256, U256)> {
+        let state = self.state().ok_or(Error::PairNotSynced)?;
+        let (reserve0, reserve1, _) = state.reserves;
+        amounts_for_liquidity(liquidity, (reserve0.into(), reserve1.into()), state.total_supply)
+    }
+
+    /// Returns the liquidity tokens which will be minted to the factory's `feeTo` on the pair's
+    /// next mint or burn, from the cached reserves and total supply, given the pair's
+    /// [`k_last`][Self::k_last].
+    ///
+    /// When the protocol fee is on, a sixth of the growth of `sqrt(k)` since `k_last` is minted,
+    /// diluting the existing liquidity providers. `k_last` is zero when the fee is off.
+    ///
+    /// Returns [`Error::PairNotSynced`] if the state has not been cached yet.
+    pub fn protocol_fee_liquidity(&self, k_last: U256) -> Result<U256> {
+        let state = self.state().ok_or(Error::PairNotSynced)?;
+        let (reserve0, reserve1, _) = state.reserves;
+        Ok(protocol_fee_liquidity((reserve0.into(), reserve1.into()), state.total_supply, k_last))
+    }
+
+    /// Returns the fee taken on each swap by the pair, in basis points.
+    ///
+    /// Returns the fee set with [`set_swap_fee_bps`][Self::set_swap_fee_bps] or inherited from the
+    /// [factory][Factory::swap_fee_bps] the pair was created with, if any, otherwise the
+    /// [protocol's][ProtocolType::swap_fee_bps].
+    pub fn swap_fee_bps(&self) -> u32 {
+        self.swap_fee_bps.unwrap_or_else(|| self.protocol.swap_fee_bps())
+    }
+
+    /// Sets the swap fee, in basis points, overriding the one of the pair's protocol.
+    ///
+    /// Returns [`Error::InvalidFee`] if the fee is not lower than `10_000` (100%).
+    pub fn set_swap_fee_bps(&mut self, fee_bps: u32) -> Result<()> {
+        self.swap_fee_bps = Some(super::factory::validate_fee(fee_bps)?);
+        Ok(())
+    }
+
+    /// Returns the fee which must be paid on top of `amount` when repaying a
+    /// [flash swap][Self::flash_swap] of `amount` with the same token, rounded up. This is about
+    /// 0.3009% with the default 0.3% swap fee. Saturates at [`U256::MAX`].
+    pub fn flash_fee(&self, amount: U256) -> U256 {
+        // `(amount + fee) * (1 - swap_fee) >= amount`
+        let multiplier = U512::from(BPS_U256 - self.swap_fee_bps());
+        let repay = (amount.full_mul(BPS_U256) + multiplier - 1) / multiplier;
+        U256::try_from(repay).map_or(U256::MAX, |repay| repay - amount)
     }
 
     /// Returns the hash of the pair's deployment code. This can be used to determinalistically
@@ -91,7 +242,7 @@ impl<M: Middleware> Pair<M> {
     /// Creates a new instance using the provided client and address.
     pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
         let contract = IUniswapV2Pair::new(address, client);
-        Self { contract, tokens: None, deployed: false, reserves: None, protocol }
+        Self { contract, cache: Default::default(), protocol, swap_fee_bps: None }
     }
 
     /// Creates a new instance using the provided client, factory and tokens' addresses.
@@ -104,26 +255,390 @@ impl<M: Middleware> Pair<M> {
         let address = Library::pair_for(factory, token0, token1);
         let contract = IUniswapV2Pair::new(address, factory.client());
 
+        let cache = Cache { tokens: OnceCell::with_value((token0, token1)), ..Default::default() };
         Ok(Self {
             contract,
-            tokens: Some((token0, token1)),
-            deployed: false,
-            reserves: None,
+            cache: Arc::new(cache),
             protocol: factory.protocol(),
+            swap_fee_bps: factory.swap_fee_bps,
         })
     }
 
+    /// Returns whether the pair has been deployed, by checking if there is any code at its address.
+    ///
+    /// Unlike [`deployed`][Self::deployed], this always queries the blockchain.
+    pub async fn exists(&self) -> Result<bool> {
+        let code = self
+            .client()
+            .get_code(self.address(), None)
+            .await
+            .map_err(ContractError::<M>::MiddlewareError)?;
+        Ok(!code.is_empty())
+    }
+
     /// Returns the contract calls for getting the addresses of the pair's tokens.
     pub fn get_tokens(&self) -> (ContractCall<M, Address>, ContractCall<M, Address>) {
         (self.contract.token_0(), self.contract.token_1())
     }
 
-    /// Syncs the tokens and reserves of the pair by querying the blockchain.
+    /// Returns the contract call for the pair's `kLast` method, which returns the product of the
+    /// reserves as of the most recent liquidity event, or zero if the protocol fee is off.
+    pub fn k_last(&self) -> ContractCall<M, U256> {
+        self.contract.k_last()
+    }
+
+    /// Returns the contract call for the pair's `skim` method, which sends to `to` the pair's token
+    /// balances in excess of its reserves.
+    pub fn skim(&self, to: Address) -> ContractCall<M, ()> {
+        self.contract.skim(to)
+    }
+
+    /// Returns the contract call for the pair's `sync` method, which updates the pair's reserves to
+    /// its token balances.
+    ///
+    /// Not to be confused with [`sync`][Self::sync], which only updates the cached state.
+    pub fn force_sync(&self) -> ContractCall<M, ()> {
+        self.contract.sync()
+    }
+
+    /// Returns the contract call for the pair's low-level `mint` method, which mints to `to` the
+    /// liquidity tokens for the token balances transferred to the pair in excess of its reserves,
+    /// returning the minted liquidity.
+    pub fn mint_to(&self, to: Address) -> ContractCall<M, U256> {
+        self.contract.mint(to)
+    }
+
+    /// Returns the contract call for the pair's low-level `burn` method, which burns the liquidity
+    /// tokens transferred to the pair and sends the corresponding tokens to `to`, returning the
+    /// amounts of `token0` and `token1` sent.
+    pub fn burn_to(&self, to: Address) -> ContractCall<M, (U256, U256)> {
+        self.contract.burn(to)
+    }
+
+    /// Returns the contract call for the pair's low-level `swap` method, which sends `amount0_out`
+    /// and `amount1_out` of the pair's tokens to `to` before checking that it has been paid.
+    ///
+    /// If `data` is not empty, this is a flash swap: the pair calls `uniswapV2Call` on `to`, with
+    /// the parameters returned by [`flash_callback`][Self::flash_callback], which must pay the pair
+    /// back. Repaying with the borrowed token requires paying an additional
+    /// [`flash_fee`][Self::flash_fee].
+    ///
+    /// Returns [`Error::InsufficientOutputAmount`] if both amounts are zero,
+    /// [`Error::PairNotSynced`] if the reserves have not been cached yet, and
+    /// [`Error::InsufficientLiquidity`] if an amount is not lower than its cached reserve.
+    pub fn flash_swap(
+        &self,
+        amount0_out: U256,
+        amount1_out: U256,
+        to: Address,
+        data: Bytes,
+    ) -> Result<ContractCall<M, ()>> {
+        if amount0_out.is_zero() && amount1_out.is_zero() {
+            return Err(Error::InsufficientOutputAmount);
+        }
+        let (reserve0, reserve1, _) = self.reserves().ok_or(Error::PairNotSynced)?;
+        if amount0_out >= reserve0.into() || amount1_out >= reserve1.into() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        Ok(self.contract.swap(amount0_out, amount1_out, to, data))
+    }
+
+    /// Returns the parameters of the `uniswapV2Call` callback which the pair makes to the
+    /// recipient of a [flash swap][Self::flash_swap] sent by the client's default sender. The
+    /// calldata can be obtained with [`AbiEncode::encode`][ethers_core::abi::AbiEncode::encode].
+    ///
+    /// Returns [`Error::MissingSender`] if the client does not have a default sender.
+    pub fn flash_callback(
+        &self,
+        amount0_out: U256,
+        amount1_out: U256,
+        data: Bytes,
+    ) -> Result<UniswapV2CallCall> {
+        let sender = self.client().default_sender().ok_or(Error::MissingSender)?;
+        Ok(UniswapV2CallCall { sender, amount_0: amount0_out, amount_1: amount1_out, data })
+    }
+
+    /// Returns the EIP-2612 [Permit] for approving `spender` to transfer `value` of `owner`'s
+    /// liquidity tokens, fetching the pair's `DOMAIN_SEPARATOR` and, if not provided, `owner`'s
+    /// current nonce.
+    pub async fn get_permit(
+        &self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        nonce: Option<U256>,
+        deadline: U256,
+    ) -> Result<Permit> {
+        let domain_separator = self.contract.domain_separator().call().await?.into();
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self.contract.nonces(owner).call().await?,
+        };
+        Ok(Permit { domain_separator, owner, spender, value, nonce, deadline })
+    }
+
+    /// Returns the EIP-712 digest of the [permit][Self::get_permit] for approving `spender` to
+    /// transfer `value` of `owner`'s liquidity tokens, which can then be signed with
+    /// [`sign_permit`][crate::permit::sign_permit].
+    pub async fn permit_digest(
+        &self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        nonce: Option<U256>,
+        deadline: U256,
+    ) -> Result<H256> {
+        Ok(self.get_permit(owner, spender, value, nonce, deadline).await?.digest())
+    }
+
+    /// Returns the [LiquidityPosition] of `owner` in the pair, fetching its balance of liquidity
+    /// tokens, the total supply, the reserves and, if not cached, the tokens with a single
+    /// [Multicall] request.
+    ///
+    /// See [`amounts_for_liquidity`][Self::amounts_for_liquidity] for more details.
+    pub async fn position_of(&self, owner: Address) -> Result<LiquidityPosition> {
+        let multicall = Multicall::new(self.client(), None).await?;
+        let mut multicall = multicall.version(MulticallVersion::Multicall3);
+
+        let tokens = self.tokens();
+        if tokens.is_none() {
+            let calls = self.get_tokens();
+            multicall.add_call(calls.0, false).add_call(calls.1, false);
+        }
+        multicall
+            .add_call(self.contract.balance_of(owner), false)
+            .add_call(self.contract.total_supply(), false)
+            .add_call(self.contract.get_reserves(), false);
+
+        let position = parse_position_result(multicall.call_raw().await?, tokens)?;
+        self.cache.tokens.get_or_init(|| (position.token0, position.token1));
+        Ok(position)
+    }
+
+    /// Returns the pair's reserves at `block`, and the timestamp of the block in which they were
+    /// last updated, without updating the cache.
+    ///
+    /// Returns [`Error::ArchiveRequired`] if the node has pruned the state at `block`.
+    pub async fn reserves_at(&self, block: impl Into<BlockId>) -> Result<Reserves> {
+        let block = block.into();
+        let call = self.contract.get_reserves().block(block);
+        call.call().await.map_err(|e| Error::from(e).archive_required(block))
+    }
+
+    /// Returns the reserves and mid price of `base_token` in terms of the other token of the pair
+    /// at each of `blocks`, in order, fetching the tokens if they have not been cached yet.
+    ///
+    /// The reserves and timestamp of each block are read with a [Multicall][utils::Multicall]
+    /// request pinned to that block, and the requests are sent concurrently and retried according
+    /// to the default [RequestPolicy].
+    ///
+    /// Returns [`Error::TokenNotInPair`] if `base_token` is not one of the pair's tokens,
+    /// [`Error::ArchiveRequired`] if the node has pruned the state at a block, and
+    /// [`Error::MulticallCallFailed`] if the pair had not been deployed at a block.
+    pub async fn price_history(
+        &self,
+        base_token: Address,
+        blocks: &[u64],
+    ) -> Result<Vec<HistoricalPrice>> {
+        let base_is_token0 = is_token0(self.fetch_tokens().await?, base_token)?;
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+        let multicall = utils::Multicall::new(self.client(), None).await?;
+        let policy = RequestPolicy::new();
+        stream::iter(blocks.iter().map(|&number| {
+            let mut multicall = multicall.clone().block(number);
+            multicall
+                .add_call(self.contract.get_reserves(), false)
+                .add_get_current_block_timestamp();
+            async move {
+                let block = BlockId::from(number);
+                let tokens = policy.retry(|| multicall.call()).await;
+                let tokens = tokens.map_err(|e| e.archive_required(block))?;
+                let ((reserve0, reserve1, _), timestamp) = <(Reserves, U256)>::from_tokens(tokens)?;
+                Ok(HistoricalPrice {
+                    block_number: number,
+                    timestamp: timestamp.as_u64(),
+                    reserve0,
+                    reserve1,
+                    price: mid_price(base_is_token0, reserve0, reserve1).ok(),
+                })
+            }
+        }))
+        .buffered(policy.concurrency())
+        .try_collect()
+        .await
+    }
+
+    /// Returns the time-weighted average prices of the pair's tokens over approximately the last
+    /// `window`, using the pair's cumulative prices like the Solidity [example oracle].
+    ///
+    /// The cumulative prices are read at the latest block and at the latest block which is at
+    /// least `window` old, found with a binary search by timestamp. This requires a client with
+    /// access to historical state, like an archive node.
+    ///
+    /// Returns [`Error::InvalidTwapWindow`] if both blocks have the same timestamp.
+    ///
+    /// [example oracle]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/examples/ExampleOracleSimple.sol
+    pub async fn observe(&self, window: Duration) -> Result<Twap> {
+        let client = self.client();
+        let latest = get_block(client.as_ref(), BlockNumber::Latest).await?;
+        let end = self.cumulative_prices(latest.number, latest.timestamp).await?;
+
+        let timestamp = latest.timestamp.saturating_sub(window.as_secs().into());
+        let start = block_at_timestamp(client.as_ref(), latest, timestamp).await?;
+        let start = self.cumulative_prices(start.number, start.timestamp).await?;
+        Twap::new(&start, &end)
+    }
+
+    /// Fetches the pair's cumulative prices at the block with `number` and `timestamp`.
+    async fn cumulative_prices(
+        &self,
+        number: Option<U64>,
+        timestamp: U256,
+    ) -> Result<CumulativePrices> {
+        let block = number.unwrap_or_default();
+        let price0_cumulative_last =
+            self.contract.price_0_cumulative_last().block(block).call().await?;
+        let price1_cumulative_last =
+            self.contract.price_1_cumulative_last().block(block).call().await?;
+        let reserves = self.contract.get_reserves().block(block).call().await?;
+        CumulativePrices::new(
+            price0_cumulative_last,
+            price1_cumulative_last,
+            reserves,
+            timestamp.low_u64(),
+        )
+    }
+
+    /// Returns the pair's `Swap` events between `from_block` and `to_block`, inclusive.
+    pub async fn swap_events(
+        &self,
+        from_block: impl Into<BlockNumber>,
+        to_block: impl Into<BlockNumber>,
+    ) -> Result<Vec<DecodedLog<SwapFilter>>> {
+        let filter = self.contract.swap_filter().filter.from_block(from_block).to_block(to_block);
+        get_logs(self.client().as_ref(), &filter).await
+    }
+
+    /// Returns all the pair's events between `from_block` and `to_block`, inclusive.
+    pub async fn events(
+        &self,
+        from_block: impl Into<BlockNumber>,
+        to_block: impl Into<BlockNumber>,
+    ) -> Result<Vec<DecodedLog<IUniswapV2PairEvents>>> {
+        let filter = self.contract.events().filter.from_block(from_block).to_block(to_block);
+        get_logs(self.client().as_ref(), &filter).await
+    }
+
+    /// Returns a stream of the pair's new `Swap` events, by polling a filter at the client's
+    /// polling interval. See [`subscribe_swaps`][Self::subscribe_swaps] for clients which support
+    /// subscriptions.
+    ///
+    /// Logs removed by a chain reorganization are emitted again with
+    /// [`removed`][DecodedLog::removed] set.
+    pub async fn stream_swaps(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<SwapFilter>>> + Unpin> {
+        let filter = self.contract.swap_filter().filter;
+        let logs = watch_logs(self.client(), &filter).await?;
+        Ok(logs.map(|log| DecodedLog::decode(&log?)))
+    }
+
+    /// Returns a stream of the pair's new `Swap` events, by subscribing to them. See
+    /// [`stream_swaps`][Self::stream_swaps] for more details.
+    pub async fn subscribe_swaps(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<SwapFilter>>> + Unpin>
+    where
+        M::Provider: PubsubClient,
+    {
+        let filter = self.contract.swap_filter().filter;
+        let logs = subscribe_logs(self.client(), &filter).await?;
+        Ok(logs.map(|log| DecodedLog::decode(&log?)))
+    }
+
+    /// Returns a stream of the mid price of `base_token`, as returned by [`price`][Self::price],
+    /// after each of the pair's new `Sync` events, by polling a filter at the client's polling
+    /// interval. See [`subscribe_prices`][Self::subscribe_prices] for clients which support
+    /// subscriptions.
+    ///
+    /// The price of a log removed by a chain reorganization is emitted again with
+    /// [`removed`][DecodedLog::removed] set, and is no longer valid.
+    pub async fn stream_prices(
+        &self,
+        base_token: Address,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<f64>>> + Unpin> {
+        let is_token0 = is_token0(self.fetch_tokens().await?, base_token)?;
+        let filter = self.contract.sync_filter().filter;
+        let logs = watch_logs(self.client(), &filter).await?;
+        Ok(logs.map(move |log| sync_to_price(is_token0, log?)))
+    }
+
+    /// Returns a stream of the mid price of `base_token` after each of the pair's new `Sync`
+    /// events, by subscribing to them. See [`stream_prices`][Self::stream_prices] for more
+    /// details.
+    pub async fn subscribe_prices(
+        &self,
+        base_token: Address,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<f64>>> + Unpin>
+    where
+        M::Provider: PubsubClient,
+    {
+        let is_token0 = is_token0(self.fetch_tokens().await?, base_token)?;
+        let filter = self.contract.sync_filter().filter;
+        let logs = subscribe_logs(self.client(), &filter).await?;
+        Ok(logs.map(move |log| sync_to_price(is_token0, log?)))
+    }
+
+    /// Syncs the tokens and state of the pair by querying the blockchain.
     ///
     /// Assumes that any call failure means the pair has not been deployed yet.
     pub async fn sync(&mut self, sync_tokens: bool, sync_reserves: bool) -> Result<&mut Self> {
-        // let sync_tokens = self.tokens.is_none() || !self.deployed;
-        // let sync_reserves = self.reserves.is_none() || !self.deployed;
+        {
+            let _guard = self.cache.lock.lock().await;
+            self.fetch(sync_tokens, sync_reserves).await?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the tokens of the pair, fetching them only the first time.
+    pub async fn fetch_tokens(&self) -> Result<Tokens> {
+        if let Some(tokens) = self.tokens() {
+            return Ok(tokens);
+        }
+        let _guard = self.cache.lock.lock().await;
+        // the tokens may have been fetched while waiting for the lock
+        if let Some(tokens) = self.tokens() {
+            return Ok(tokens);
+        }
+        let (token0, token1) = self.get_tokens();
+        let tokens = (token0.call().await?, token1.call().await?);
+        Ok(*self.cache.tokens.get_or_init(|| tokens))
+    }
+
+    /// Refreshes the cached reserves, total supply and block of the pair's state with a single
+    /// [Multicall] request, and fetches the tokens if they have not been cached yet.
+    ///
+    /// Concurrent syncs, including from clones of the pair, are serialized: a sync which started
+    /// while another one was in progress returns its result instead of fetching again.
+    ///
+    /// Returns [`Error::PairNotSynced`] if the pair has not been deployed.
+    pub async fn sync_state(&self) -> Result<PairState> {
+        let syncs = self.cache.syncs.load(Ordering::Acquire);
+        let _guard = self.cache.lock.lock().await;
+        if self.cache.syncs.load(Ordering::Acquire) == syncs {
+            self.fetch(self.tokens().is_none(), true).await?;
+        }
+        self.state().ok_or(Error::PairNotSynced)
+    }
+
+    /// Fetches the tokens and state of the pair and updates the cache. Must be called while holding
+    /// the cache's lock.
+    async fn fetch(&self, sync_tokens: bool, sync_state: bool) -> Result<()> {
+        if !sync_tokens && !sync_state {
+            return Ok(());
+        }
 
         let multicall = Multicall::new(self.client(), None).await?;
         let mut multicall = multicall.version(MulticallVersion::Multicall3);
@@ -134,66 +649,81 @@ impl<M: Middleware> Pair<M> {
             multicall.add_call(calls.1, true);
         }
 
-        if sync_reserves {
+        if sync_state {
             multicall.add_call(self.contract.get_reserves(), true);
+            multicall.add_call(self.contract.total_supply(), true);
+            multicall.add_get_block_number().add_get_current_block_timestamp();
         }
 
         // Assume any call failure means the contract has not been deployed yet
-        let result = multicall.call_raw().await;
-        let result = match result {
+        let result = match multicall.call_raw().await {
             Ok(result) => result,
             Err(MulticallError::ContractError(ContractError::DecodingError(_))) => {
-                self.tokens = None;
-                self.deployed = false;
-                return Ok(self);
+                self.set_deployed(false);
+                return Ok(());
             }
             Err(e) => return Err(e.into()),
         };
 
-        match (sync_tokens, sync_reserves) {
-            (true, true) => {
-                let tokens = parse_tokens_result(result[0..2].to_vec())?;
-                let reserves = parse_reserves_result(result[2..].to_vec())?;
-
-                if tokens.is_none() || reserves.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self);
+        let (tokens, state) = result.split_at(if sync_tokens { 2 } else { 0 });
+        if sync_tokens {
+            match parse_tokens_result(tokens.to_vec())? {
+                Some(tokens) => {
+                    self.cache.tokens.get_or_init(|| tokens);
                 }
-
-                self.deployed = true;
-                self.tokens = tokens;
-                self.reserves = reserves;
-            }
-            (true, false) => {
-                let tokens = parse_tokens_result(result)?;
-
-                if tokens.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self);
+                None => {
+                    self.set_deployed(false);
+                    return Ok(());
                 }
-
-                self.deployed = true;
-                self.tokens = tokens;
             }
-            (false, true) => {
-                let reserves = parse_reserves_result(result)?;
-
-                if reserves.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self);
+        }
+        if sync_state {
+            match parse_state_result(state.to_vec())? {
+                Some(state) => {
+                    *self.cache.state.write().unwrap_or_else(PoisonError::into_inner) = Some(state);
+                    self.cache.syncs.fetch_add(1, Ordering::AcqRel);
+                }
+                None => {
+                    self.set_deployed(false);
+                    return Ok(());
                 }
-
-                self.deployed = true;
-                self.reserves = reserves;
             }
-            (false, false) => {}
         }
+        self.set_deployed(true);
+        Ok(())
+    }
 
-        Ok(self)
+    fn set_deployed(&self, deployed: bool) {
+        self.cache.deployed.store(deployed, Ordering::Release);
+    }
+}
+
+/// Returns whether `token` is the first of `tokens`, or [`Error::TokenNotInPair`] if it is not
+/// one of them.
+fn is_token0((token0, token1): Tokens, token: Address) -> Result<bool> {
+    if token == token0 {
+        Ok(true)
+    } else if token == token1 {
+        Ok(false)
+    } else {
+        Err(Error::TokenNotInPair(token))
+    }
+}
+
+/// Returns the mid price of the first or second token of a pair from its reserves.
+fn mid_price(base_is_token0: bool, reserve0: u128, reserve1: u128) -> Result<f64> {
+    if reserve0 == 0 || reserve1 == 0 {
+        return Err(Error::ZeroReserves);
     }
+    let (base, quote) = if base_is_token0 { (reserve0, reserve1) } else { (reserve1, reserve0) };
+    Ok(quote as f64 / base as f64)
+}
+
+/// Decodes a `Sync` event log into the mid price of the first or second token of the pair.
+fn sync_to_price(base_is_token0: bool, log: Log) -> Result<DecodedLog<f64>> {
+    let sync = DecodedLog::<SyncFilter>::decode(&log)?;
+    let price = mid_price(base_is_token0, sync.event.reserve_0, sync.event.reserve_1)?;
+    Ok(sync.map(|_| price))
 }
 
 /// Parses (bool, String) from a vector of tokens.
@@ -267,6 +797,46 @@ fn parse_reserves_result(tokens: Vec<Token>) -> Result<Option<Reserves>> {
     }
 }
 
+/// Parses a multicall result of the calls added by Pair::fetch() for the pair's state, returning
+/// None if any call returned an error.
+/// Parses the results of [`Pair::position_of`]'s request, which starts with the tokens if they
+/// are None.
+fn parse_position_result(results: Vec<Token>, tokens: Option<Tokens>) -> Result<LiquidityPosition> {
+    let mut results = results.into_iter();
+    let mut next = || results.next().ok_or(InvalidOutputType("missing result".into()));
+    let tokens = match tokens {
+        Some(tokens) => tokens,
+        None => {
+            let token0 = <(bool, Address)>::from_token(next()?)?.1;
+            let token1 = <(bool, Address)>::from_token(next()?)?.1;
+            (token0, token1)
+        }
+    };
+    let liquidity = <(bool, U256)>::from_token(next()?)?.1;
+    let total_supply = <(bool, U256)>::from_token(next()?)?.1;
+    let (reserve0, reserve1, _) = <(bool, Reserves)>::from_token(next()?)?.1;
+    LiquidityPosition::new(tokens, liquidity, (reserve0.into(), reserve1.into()), total_supply)
+}
+
+fn parse_state_result(mut tokens: Vec<Token>) -> Result<Option<PairState>> {
+    type StateResult = ((bool, U256), (bool, U256), (bool, U256));
+    let rest = tokens.split_off(1);
+    let reserves = parse_reserves_result(tokens)?;
+    let res: Option<StateResult> = parse_result(rest)?;
+
+    match (reserves, res) {
+        (Some(reserves), Some(((true, total_supply), (_, block_number), (_, timestamp)))) => {
+            Ok(Some(PairState {
+                reserves,
+                total_supply,
+                block_number: block_number.as_u64(),
+                timestamp: timestamp.as_u64(),
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -380,4 +950,490 @@ mod tests {
         assert_ne!(reserves.1, 0);
         assert_ne!(reserves.2, 0);
     }
+
+    #[tokio::test]
+    async fn test_exists() {
+        use ethers_core::types::Bytes;
+        use ethers_providers::MockProvider;
+
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(provider.into(), Address::repeat_byte(1), ProtocolType::UniswapV2);
+
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        assert!(!pair.exists().await.unwrap());
+
+        mock.push::<Bytes, _>(Bytes::from(vec![0x60, 0x80])).unwrap();
+        assert!(pair.exists().await.unwrap());
+    }
+
+    #[test]
+    fn test_maintenance_calls() {
+        use crate::contracts::bindings::i_uniswap_v2_pair::{
+            BurnCall, MintCall, SkimCall, SyncCall,
+        };
+        use ethers_contract::EthCall;
+        use ethers_providers::MockProvider;
+
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(provider.into(), Address::repeat_byte(1), ProtocolType::UniswapV2);
+        let to = Address::repeat_byte(2);
+        let selector =
+            |calldata: Option<ethers_core::types::Bytes>| calldata.unwrap()[..4].to_vec();
+        assert_eq!(selector(pair.skim(to).calldata()), SkimCall::selector());
+        assert_eq!(selector(pair.force_sync().calldata()), SyncCall::selector());
+        assert_eq!(selector(pair.mint_to(to).calldata()), MintCall::selector());
+        assert_eq!(selector(pair.burn_to(to).calldata()), BurnCall::selector());
+    }
+
+    #[cfg(feature = "deploy")]
+    #[tokio::test]
+    #[ignore = "needs anvil"]
+    async fn test_skim_fork() {
+        use crate::{contracts::bindings::iweth::IWETH, deploy::V2Bytecode, testing::Fork};
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+        let bytecode = V2Bytecode::canonical().unwrap();
+        let deployment = fork.deploy_v2(&bytecode).await.unwrap();
+        // the WETH9 of another deployment is an ERC20 which can be minted with ether
+        let token = fork.deploy_v2(&bytecode).await.unwrap().weth;
+
+        let weth = IWETH::new(deployment.weth, client.clone());
+        let token = IWETH::new(token, client.clone());
+        let amount = U256::exp10(18);
+        let factory = &deployment.factory;
+        let create = factory.contract().create_pair(weth.address(), token.address());
+        create.send().await.unwrap().await.unwrap();
+        let pair = factory.pair_for(weth.address(), token.address());
+        let pair = Pair::new(client, pair.address(), ProtocolType::UniswapV2);
+        for token in [&weth, &token] {
+            token.deposit().value(amount).send().await.unwrap().await.unwrap();
+            token.transfer(pair.address(), amount).send().await.unwrap().await.unwrap();
+        }
+        pair.mint_to(me).send().await.unwrap().await.unwrap();
+
+        // donate, then skim
+        weth.deposit().value(amount * 2).send().await.unwrap().await.unwrap();
+        let balance = weth.balance_of(me).call().await.unwrap();
+        weth.transfer(pair.address(), amount).send().await.unwrap().await.unwrap();
+        assert_eq!(weth.balance_of(me).call().await.unwrap(), balance - amount);
+        pair.skim(me).send().await.unwrap().await.unwrap();
+        assert_eq!(weth.balance_of(me).call().await.unwrap(), balance);
+
+        // donate, then sync
+        let reserves = |(reserve0, reserve1, _): (u128, u128, u32)| reserve0 + reserve1;
+        let before = reserves(pair.contract().get_reserves().call().await.unwrap());
+        weth.transfer(pair.address(), amount).send().await.unwrap().await.unwrap();
+        pair.force_sync().send().await.unwrap().await.unwrap();
+        let after = reserves(pair.contract().get_reserves().call().await.unwrap());
+        assert_eq!(U256::from(after), U256::from(before) + amount);
+    }
+
+    #[tokio::test]
+    async fn test_permit_digest() {
+        use ethers_core::{
+            abi,
+            types::{transaction::eip712::EIP712Domain, Bytes},
+            utils::keccak256,
+        };
+        use ethers_providers::MockProvider;
+
+        // WETH/USDC
+        let address: Address = "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc".parse().unwrap();
+        let domain_separator: H256 =
+            "0xe8d93546d488d196c53f3e93ad73ba237e3fb527bddca6a240f54d03552dc70f".parse().unwrap();
+        let domain = EIP712Domain {
+            name: Some("Uniswap V2".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(1.into()),
+            verifying_contract: Some(address),
+            salt: None,
+        };
+        assert_eq!(H256(domain.separator()), domain_separator);
+
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pair = Pair::new(Arc::new(provider), address, ProtocolType::UniswapV2);
+        let (owner, spender) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (value, nonce, deadline) = (U256::exp10(18), U256::from(3), U256::from(1_700_000_000));
+
+        // LIFO
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(nonce)]))).unwrap();
+        let separator_token = Token::FixedBytes(domain_separator.0.to_vec());
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[separator_token]))).unwrap();
+        let digest = pair.permit_digest(owner, spender, value, None, deadline).await.unwrap();
+
+        let struct_hash = keccak256(abi::encode(&[
+            Token::FixedBytes(keccak256(crate::permit::PERMIT_TYPE).to_vec()),
+            Token::Address(owner),
+            Token::Address(spender),
+            Token::Uint(value),
+            Token::Uint(nonce),
+            Token::Uint(deadline),
+        ]));
+        let expected = keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat());
+        assert_eq!(digest, H256(expected));
+    }
+
+    mod cache {
+        use super::*;
+        use ethers_core::{abi, types::Bytes};
+        use ethers_providers::MockProvider;
+
+        const TOKEN0: Address = Address::repeat_byte(1);
+        const TOKEN1: Address = Address::repeat_byte(2);
+
+        fn mocked() -> (Pair<Provider<MockProvider>>, MockProvider) {
+            let (provider, mock) = Provider::mocked();
+            let pair =
+                Pair::new(Arc::new(provider), Address::repeat_byte(3), ProtocolType::UniswapV2);
+            (pair, mock)
+        }
+
+        /// Pushes the `aggregate3` response of a sync with the tokens, and the chain id queried
+        /// by [Multicall::new].
+        fn push_sync(mock: &MockProvider, reserves: (u128, u128)) {
+            let success = |data: Vec<u8>| Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)]);
+            let uints = |values: &[u64]| {
+                abi::encode(&values.iter().map(|&v| Token::Uint(v.into())).collect::<Vec<_>>())
+            };
+            let results = vec![
+                success(abi::encode(&[Token::Address(TOKEN0)])),
+                success(abi::encode(&[Token::Address(TOKEN1)])),
+                success(abi::encode(&[
+                    Token::Uint(reserves.0.into()),
+                    Token::Uint(reserves.1.into()),
+                    Token::Uint(1_000.into()),
+                ])),
+                success(uints(&[500])),
+                success(uints(&[100])),
+                success(uints(&[1_200])),
+            ];
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+            mock.push(U256::one()).unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_sync_state() {
+            let (pair, mock) = mocked();
+            assert!(pair.state().is_none());
+            assert!(matches!(pair.price(TOKEN0).unwrap_err(), Error::PairNotSynced));
+
+            push_sync(&mock, (2_000, 1_000));
+            let state = pair.sync_state().await.unwrap();
+            assert_eq!(
+                state,
+                PairState {
+                    reserves: (2_000, 1_000, 1_000),
+                    total_supply: 500.into(),
+                    block_number: 100,
+                    timestamp: 1_200
+                }
+            );
+
+            // shared between clones
+            let clone = pair.clone();
+            assert!(clone.deployed());
+            assert_eq!(clone.tokens(), Some((TOKEN0, TOKEN1)));
+            assert_eq!(clone.state(), Some(state));
+            // memoized
+            assert_eq!(clone.fetch_tokens().await.unwrap(), (TOKEN0, TOKEN1));
+
+            assert_eq!(pair.price(TOKEN0).unwrap(), 0.5);
+            assert_eq!(pair.price(TOKEN1).unwrap(), 2.0);
+            let res = pair.price(Address::zero());
+            assert!(matches!(res.unwrap_err(), Error::TokenNotInPair(_)));
+        }
+
+        #[tokio::test]
+        async fn test_sync_nothing() {
+            let (mut pair, mock) = mocked();
+            push_sync(&mock, (2_000, 1_000));
+            pair.sync(true, true).await.unwrap();
+            assert!(pair.deployed());
+
+            // no responses left: this would fail if it made any request
+            pair.sync(false, false).await.unwrap();
+            assert!(pair.deployed());
+            assert_eq!(pair.state().unwrap().reserves, (2_000, 1_000, 1_000));
+        }
+
+        #[tokio::test]
+        async fn test_sync_state_concurrent() {
+            let (pair, mock) = mocked();
+            let clone = pair.clone();
+
+            // another sync is in progress
+            let guard = pair.cache.lock.lock().await;
+            let mut waiting = Box::pin(clone.sync_state());
+            assert!(futures_util::poll!(&mut waiting).is_pending());
+
+            push_sync(&mock, (2_000, 1_000));
+            pair.fetch(true, true).await.unwrap();
+            drop(guard);
+
+            // no responses left: this would fail if it fetched again
+            let state = waiting.await.unwrap();
+            assert_eq!(state.reserves, (2_000, 1_000, 1_000));
+        }
+
+        #[tokio::test]
+        async fn test_position_of() {
+            let (pair, mock) = mocked();
+            assert!(matches!(
+                pair.amounts_for_liquidity(1.into()).unwrap_err(),
+                Error::PairNotSynced
+            ));
+
+            let success = |data: Vec<u8>| Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)]);
+            let uint = |value: u64| abi::encode(&[Token::Uint(value.into())]);
+            let results = vec![
+                success(abi::encode(&[Token::Address(TOKEN0)])),
+                success(abi::encode(&[Token::Address(TOKEN1)])),
+                success(uint(2_500)),
+                success(uint(10_000)),
+                success(abi::encode(&[
+                    Token::Uint(40_000.into()),
+                    Token::Uint(1_000.into()),
+                    Token::Uint(1.into()),
+                ])),
+            ];
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+            mock.push(U256::one()).unwrap();
+
+            let position = pair.position_of(Address::repeat_byte(4)).await.unwrap();
+            assert_eq!(
+                position,
+                LiquidityPosition {
+                    token0: TOKEN0,
+                    token1: TOKEN1,
+                    liquidity: 2_500.into(),
+                    share_bps: 2_500,
+                    amount0: 10_000.into(),
+                    amount1: 250.into(),
+                }
+            );
+            assert_eq!(pair.tokens(), Some((TOKEN0, TOKEN1)));
+        }
+
+        #[tokio::test]
+        async fn test_price_history() {
+            let (pair, mock) = mocked();
+            let success = |data: Vec<u8>| Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)]);
+            let uints = |values: &[u64]| {
+                abi::encode(&values.iter().map(|&v| Token::Uint(v.into())).collect::<Vec<_>>())
+            };
+            let push_block = |reserves: [u64; 2], timestamp: u64| {
+                let results = vec![
+                    success(uints(&[reserves[0], reserves[1], timestamp - 1])),
+                    success(uints(&[timestamp])),
+                ];
+                mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+            };
+
+            // LIFO: the second block, the first block, the chain id and the tokens
+            push_block([0, 0], 1_200);
+            push_block([1_000, 2_000], 1_000);
+            mock.push(U256::one()).unwrap();
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(TOKEN1)]))).unwrap();
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Address(TOKEN0)]))).unwrap();
+
+            let history = pair.price_history(TOKEN1, &[100, 200]).await.unwrap();
+            assert_eq!(
+                history,
+                [
+                    HistoricalPrice {
+                        block_number: 100,
+                        timestamp: 1_000,
+                        reserve0: 1_000,
+                        reserve1: 2_000,
+                        price: Some(0.5),
+                    },
+                    HistoricalPrice { block_number: 200, timestamp: 1_200, ..Default::default() },
+                ]
+            );
+            assert!(pair.price_history(TOKEN0, &[]).await.unwrap().is_empty());
+            let res = pair.price_history(Address::zero(), &[100]).await;
+            assert!(matches!(res.unwrap_err(), Error::TokenNotInPair(_)));
+
+            let reserves = uints(&[1_000, 2_000, 900]);
+            mock.push::<Bytes, _>(Bytes::from(reserves)).unwrap();
+            assert_eq!(pair.reserves_at(100).await.unwrap(), (1_000, 2_000, 900));
+            assert_eq!(pair.reserves(), None);
+        }
+
+        #[tokio::test]
+        async fn test_observe() {
+            use ethers_core::types::{Block, TxHash};
+
+            let (pair, mock) = mocked();
+            let q112 = U256::one() << 112;
+            let uint = |value: U256| Bytes::from(abi::encode(&[Token::Uint(value)]));
+            let reserves = |timestamp: u32| {
+                let reserves = [1_000.into(), 2_000.into(), timestamp.into()].map(Token::Uint);
+                Bytes::from(abi::encode(&reserves))
+            };
+            let block = |number: u64, timestamp: u64| Block::<TxHash> {
+                number: Some(number.into()),
+                timestamp: timestamp.into(),
+                ..Default::default()
+            };
+
+            // LIFO: start cumulative prices, start block, end cumulative prices, latest block
+            mock.push::<Bytes, _>(reserves(880)).unwrap();
+            mock.push::<Bytes, _>(uint(q112 * 1_000)).unwrap();
+            mock.push::<Bytes, _>(uint(q112 * 3_000)).unwrap();
+            mock.push(block(900, 900)).unwrap();
+            // updated 10 seconds before the latest block
+            mock.push::<Bytes, _>(reserves(990)).unwrap();
+            mock.push::<Bytes, _>(uint(q112 * 1_055)).unwrap();
+            mock.push::<Bytes, _>(uint(q112 * 3_220)).unwrap();
+            mock.push(block(1_000, 1_000)).unwrap();
+
+            let twap = pair.observe(Duration::from_secs(100)).await.unwrap();
+            assert_eq!(twap.window_actual, Duration::from_secs(100));
+            assert_eq!(twap.price0, q112 * 2);
+            assert_eq!(twap.price1, q112 / 2);
+        }
+
+        #[tokio::test]
+        async fn test_flash_swap() {
+            use crate::contracts::bindings::i_uniswap_v2_pair::SwapCall;
+            use ethers_core::abi::{AbiDecode, AbiEncode};
+
+            let (pair, mock) = mocked();
+            let (one, to, data) = (U256::one(), Address::repeat_byte(4), Bytes::from(vec![1]));
+            let res = pair.flash_swap(one, U256::zero(), to, data.clone());
+            assert!(matches!(res.unwrap_err(), Error::PairNotSynced));
+
+            push_sync(&mock, (1_000, 2_000));
+            pair.sync_state().await.unwrap();
+            let res = pair.flash_swap(U256::zero(), U256::zero(), to, data.clone());
+            assert!(matches!(res.unwrap_err(), Error::InsufficientOutputAmount));
+            let res = pair.flash_swap(U256::zero(), 2_000.into(), to, data.clone());
+            assert!(matches!(res.unwrap_err(), Error::InsufficientLiquidity));
+
+            let call = pair.flash_swap(999.into(), U256::zero(), to, data.clone()).unwrap();
+            let call = SwapCall::decode(call.calldata().unwrap()).unwrap();
+            assert_eq!((call.amount_0_out, call.amount_1_out), (999.into(), U256::zero()));
+            assert_eq!((call.to, call.data), (to, data.clone()));
+
+            let res = pair.flash_callback(999.into(), U256::zero(), data.clone());
+            assert!(matches!(res.unwrap_err(), Error::MissingSender));
+            let sender = Address::repeat_byte(5);
+            let client = Arc::new(Provider::new(mock).with_sender(sender));
+            let pair = Pair::new(client, pair.address(), ProtocolType::UniswapV2);
+            let callback = pair.flash_callback(999.into(), U256::zero(), data).unwrap();
+            assert_eq!(callback.sender, sender);
+            assert_eq!(&callback.clone().encode()[..4], &[0x10, 0xd1, 0xe8, 0x5c]);
+        }
+
+        #[test]
+        fn test_flash_fee() {
+            let (mut pair, _mock) = mocked();
+            // 1000 * 1.003009 rounded up
+            assert_eq!(pair.flash_fee(1_000.into()), 4.into());
+            assert_eq!(pair.flash_fee(997.into()), 3.into());
+            assert_eq!(pair.flash_fee(U256::zero()), U256::zero());
+            assert_eq!(pair.flash_fee(U256::MAX), U256::MAX);
+
+            pair.protocol = ProtocolType::Pancakeswap;
+            assert_eq!(pair.flash_fee(9_975.into()), 25.into());
+        }
+    }
+
+    mod events {
+        use super::*;
+        use ethers_contract::EthEvent;
+        use ethers_core::abi;
+        use ethers_providers::MockProvider;
+        use std::time::Duration;
+
+        const TOKEN0: Address = Address::repeat_byte(1);
+        const TOKEN1: Address = Address::repeat_byte(2);
+
+        fn mocked() -> (Pair<Provider<MockProvider>>, MockProvider) {
+            let (provider, mock) = Provider::mocked();
+            let provider = provider.interval(Duration::from_millis(1));
+            let pair =
+                Pair::new(Arc::new(provider), Address::repeat_byte(3), ProtocolType::UniswapV2);
+            pair.cache.tokens.set((TOKEN0, TOKEN1)).unwrap();
+            (pair, mock)
+        }
+
+        fn log(topic: H256, data: &[u128], block: u64, removed: bool) -> Log {
+            Log {
+                topics: vec![topic, H256::zero(), H256::zero()],
+                data: abi::encode(&data.iter().map(|&v| Token::Uint(v.into())).collect::<Vec<_>>())
+                    .into(),
+                block_number: Some(block.into()),
+                removed: Some(removed),
+                ..Default::default()
+            }
+        }
+
+        fn sync_log(reserves: [u128; 2], block: u64, removed: bool) -> Log {
+            // no indexed parameters
+            Log {
+                topics: vec![SyncFilter::signature()],
+                ..log(H256::zero(), &reserves, block, removed)
+            }
+        }
+
+        #[tokio::test]
+        async fn test_swap_events() {
+            let (pair, mock) = mocked();
+            let logs = vec![
+                log(SwapFilter::signature(), &[0, 100, 50, 0], 1, false),
+                log(SwapFilter::signature(), &[20, 0, 0, 40], 2, false),
+            ];
+            mock.push::<Vec<Log>, _>(logs).unwrap();
+
+            let events = pair.swap_events(1, BlockNumber::Latest).await.unwrap();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0].event.amount_1_in, 100.into());
+            assert_eq!(events[1].event.amount_1_out, 40.into());
+            assert_eq!(events[1].meta.block_number, 2.into());
+        }
+
+        #[tokio::test]
+        async fn test_stream_swaps() {
+            let (pair, mock) = mocked();
+            // LIFO: filter id, then the changes of the first poll
+            mock.push::<Vec<Log>, _>(vec![log(
+                SwapFilter::signature(),
+                &[0, 100, 50, 0],
+                1,
+                false,
+            )])
+            .unwrap();
+            mock.push(U256::one()).unwrap();
+
+            let mut swaps = pair.stream_swaps().await.unwrap();
+            let swap = swaps.next().await.unwrap().unwrap();
+            assert_eq!(swap.event.amount_0_out, 50.into());
+            assert!(!swap.removed);
+        }
+
+        #[tokio::test]
+        async fn test_stream_prices() {
+            let (pair, mock) = mocked();
+            let logs = vec![
+                sync_log([1_000, 2_000], 1, false),
+                sync_log([1_000, 2_000], 1, true),
+                sync_log([1_000, 4_000], 2, false),
+            ];
+            mock.push::<Vec<Log>, _>(logs).unwrap();
+            mock.push(U256::one()).unwrap();
+
+            let prices: Vec<_> = pair.stream_prices(TOKEN1).await.unwrap().take(3).collect().await;
+            let prices: Vec<_> = prices.into_iter().map(|p| p.unwrap()).collect();
+            assert_eq!(prices[0].event, 0.5);
+            assert!(prices[1].removed);
+            assert_eq!(prices[2].event, 0.25);
+            assert_eq!(prices[2].meta.block_number, 2.into());
+
+            let res = pair.stream_prices(Address::zero()).await;
+            assert!(matches!(res.err().unwrap(), Error::TokenNotInPair(_)));
+        }
+    }
 }
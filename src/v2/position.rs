@@ -0,0 +1,136 @@
+use crate::{
+    constants::{BPS_U256, MINIMUM_LIQUIDITY},
+    errors::{Error, Result},
+};
+use ethers_core::types::{Address, U256, U512};
+
+/// The amounts of a pair's tokens owned by a liquidity provider.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LiquidityPosition {
+    /// The first token of the pair, sorted.
+    pub token0: Address,
+    /// The second token of the pair, sorted.
+    pub token1: Address,
+    /// The amount of liquidity tokens owned.
+    pub liquidity: U256,
+    /// The share of the pair's total supply of liquidity tokens owned, in basis points.
+    pub share_bps: u32,
+    /// The amount of `token0` which would be received by burning `liquidity`.
+    pub amount0: U256,
+    /// The amount of `token1` which would be received by burning `liquidity`.
+    pub amount1: U256,
+}
+
+impl LiquidityPosition {
+    /// Creates a new position of `liquidity` tokens of a pair with the given sorted `tokens`,
+    /// `reserves` and `total_supply`.
+    ///
+    /// See [`Pair::amounts_for_liquidity`][super::Pair::amounts_for_liquidity] for more details.
+    pub fn new(
+        (token0, token1): (Address, Address),
+        liquidity: U256,
+        reserves: (U256, U256),
+        total_supply: U256,
+    ) -> Result<Self> {
+        let (amount0, amount1) = amounts_for_liquidity(liquidity, reserves, total_supply)?;
+        let share_bps = liquidity.min(total_supply).full_mul(BPS_U256) / U512::from(total_supply);
+        let share_bps = share_bps.low_u32();
+        Ok(Self { token0, token1, liquidity, share_bps, amount0, amount1 })
+    }
+}
+
+/// Returns the amounts of a pair's tokens received by burning `liquidity` tokens, given its
+/// `reserves` and `total_supply` of liquidity tokens. See
+/// [`Pair::amounts_for_liquidity`][super::Pair::amounts_for_liquidity] for more details.
+pub(crate) fn amounts_for_liquidity(
+    liquidity: U256,
+    (reserve0, reserve1): (U256, U256),
+    total_supply: U256,
+) -> Result<(U256, U256)> {
+    if total_supply.is_zero() {
+        return Err(Error::InsufficientLiquidity);
+    }
+    let liquidity = liquidity.min(total_supply.saturating_sub(MINIMUM_LIQUIDITY));
+    let amount = |reserve: U256| {
+        // can't overflow since `liquidity < total_supply`
+        U256::try_from(liquidity.full_mul(reserve) / U512::from(total_supply)).expect("overflow")
+    };
+    Ok((amount(reserve0), amount(reserve1)))
+}
+
+/// Returns the liquidity tokens minted to the protocol fee recipient on a pair's next mint or
+/// burn, given its current `reserves`, `total_supply` and `k_last`. See
+/// [`Pair::protocol_fee_liquidity`][super::Pair::protocol_fee_liquidity] for more details.
+pub(crate) fn protocol_fee_liquidity(
+    (reserve0, reserve1): (U256, U256),
+    total_supply: U256,
+    k_last: U256,
+) -> U256 {
+    if k_last.is_zero() {
+        return U256::zero();
+    }
+    let root_k = reserve0.full_mul(reserve1).integer_sqrt();
+    let root_k_last = U512::from(k_last).integer_sqrt();
+    if root_k <= root_k_last {
+        return U256::zero();
+    }
+    let numerator = U512::from(total_supply) * (root_k - root_k_last);
+    let denominator = root_k * 5 + root_k_last;
+    // can't overflow since `numerator / denominator < total_supply`
+    U256::try_from(numerator / denominator).expect("overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENS: (Address, Address) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+    #[test]
+    fn test_position() {
+        let reserves = (U256::from(4_000_000), U256::from(1_000));
+        let position = LiquidityPosition::new(TOKENS, 50_000.into(), reserves, 200_000.into());
+        let position = position.unwrap();
+        assert_eq!(position.share_bps, 2_500);
+        assert_eq!((position.amount0, position.amount1), (1_000_000.into(), 250.into()));
+
+        let err = LiquidityPosition::new(TOKENS, 1.into(), reserves, U256::zero()).unwrap_err();
+        assert!(matches!(err, Error::InsufficientLiquidity));
+    }
+
+    #[test]
+    fn test_full_exit() {
+        // the first `MINIMUM_LIQUIDITY` tokens are locked
+        let total_supply = U256::from(101_000);
+        let reserves = (total_supply, total_supply * 2);
+        let position = LiquidityPosition::new(TOKENS, total_supply, reserves, total_supply);
+        let position = position.unwrap();
+        assert_eq!(position.share_bps, 10_000);
+        assert_eq!(position.amount0, 100_000.into());
+        assert_eq!(position.amount1, 200_000.into());
+
+        // doesn't overflow
+        let (max, half) = (U256::MAX, U256::MAX / 2);
+        let (amount0, amount1) = amounts_for_liquidity(half, (max, max), max).unwrap();
+        assert_eq!((amount0, amount1), (half, half));
+        let (amount0, _) = amounts_for_liquidity(max, (max, max), max).unwrap();
+        assert_eq!(amount0, max - MINIMUM_LIQUIDITY);
+    }
+
+    #[test]
+    fn test_protocol_fee_liquidity() {
+        let total_supply = U256::from(1_000_000);
+        let reserves = (U256::from(1_000_000), U256::from(1_000_000));
+
+        // fee off, or no growth
+        assert!(protocol_fee_liquidity(reserves, total_supply, U256::zero()).is_zero());
+        let k = reserves.0 * reserves.1;
+        assert!(protocol_fee_liquidity(reserves, total_supply, k).is_zero());
+
+        // sqrt(k) grew by 20%: 1/6th of the growth, 200k / (5 * 1.2M + 1M) * 1M
+        let reserves = (U256::from(1_200_000), U256::from(1_200_000));
+        let liquidity = protocol_fee_liquidity(reserves, total_supply, k);
+        assert_eq!(liquidity, (total_supply * 200_000) / 7_000_000);
+    }
+}
@@ -1,10 +1,31 @@
-use super::{Factory, Pair, Router};
-use crate::{errors::Result, Amount, ProtocolType};
+use super::{Factory, LiquidityPosition, Pair, Router, Trade, DEFAULT_MAX_HOPS};
+use crate::{
+    common::{
+        execute_swap, gas,
+        gas::{AccessListEstimate, GasEstimate},
+        simulate as simulate_call,
+    },
+    contracts::bindings::i_solidly_router::ISolidlyRouter,
+    errors::Result,
+    protocol::verify::{CheckStatus, VerificationReport},
+    utils::{is_native, map_native, require_signer, resolve_weth},
+    Amount, Deadline, Erc20, ProtocolType, Recipient, RequestPolicy, Route, SimulateOptions,
+    Simulation, Slippage, SwapOptions, SwapReceipt,
+};
 use ethers_contract::builders::ContractCall;
-use ethers_core::types::{Address, Chain, H256, U256};
+use ethers_core::{
+    abi::Detokenize,
+    types::{Address, Chain, H256, U256},
+};
 use ethers_providers::Middleware;
 use std::{fmt, sync::Arc};
 
+#[cfg(feature = "addresses")]
+use crate::{
+    contracts::addresses::{with_registry, AddressBook},
+    errors::Error,
+};
+
 /// A Uniswap V2 protocol implementation.
 pub struct Protocol<M> {
     /// The liquidity pair factory.
@@ -37,14 +58,33 @@ impl<M: Middleware> Protocol<M> {
         Self { factory, router }
     }
 
-    /// Creates a new instance by searching for the required addresses in the [addressbook].
+    /// Creates a new instance by searching for the required addresses in `address_book`, or in
+    /// the process-global [addressbook] if None.
+    ///
+    /// The pair code hash of an inserted entry overrides the protocol's.
     ///
     /// [addressbook]: crate::contracts::addresses
     #[cfg(feature = "addresses")]
-    pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
-        if let (Some(factory), Some(router)) = protocol.try_addresses(chain) {
+    pub fn new_with_chain(
+        client: Arc<M>,
+        chain: Chain,
+        protocol: ProtocolType,
+        address_book: Option<&AddressBook>,
+    ) -> Option<Self> {
+        let (addresses, init_code_hash) = match address_book {
+            Some(book) => {
+                (book.try_addresses(protocol, chain), book.init_code_hash(protocol, chain))
+            }
+            None => with_registry(|book| {
+                (book.try_addresses(protocol, chain), book.init_code_hash(protocol, chain))
+            }),
+        };
+        if let (Some(factory), Some(router)) = addresses {
             let mut this = Self::new(client, factory, router, protocol);
             this.factory.set_chain(chain);
+            if let Some(init_code_hash) = init_code_hash {
+                this.factory.set_pair_code_hash(init_code_hash);
+            }
             Some(this)
         } else {
             None
@@ -68,6 +108,92 @@ impl<M: Middleware> Protocol<M> {
         self.factory.set_chain(chain);
     }
 
+    /// Sets the protocol's chain ID. See [`Factory::set_chain_id`] for more details.
+    #[inline(always)]
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        self.factory.set_chain_id(chain_id);
+    }
+
+    /// Sets the swap fee, in basis points, used for quoting swaps through the protocol's pairs. See
+    /// [`Factory::set_swap_fee_bps`] for more details.
+    pub fn set_swap_fee_bps(&mut self, fee_bps: u32) -> Result<()> {
+        self.factory.set_swap_fee_bps(fee_bps)
+    }
+
+    /// Returns the policy of the factory's batched requests and log scans.
+    #[inline(always)]
+    pub fn request_policy(&self) -> RequestPolicy {
+        self.factory.request_policy()
+    }
+
+    /// Sets the policy of the factory's batched requests and log scans. See
+    /// [`Factory::set_request_policy`] for more details.
+    #[inline(always)]
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) {
+        self.factory.set_request_policy(policy);
+    }
+
+    /// Returns the address of the chain's wrapped native token, resolved from the [addressbook]
+    /// using the factory's chain.
+    ///
+    /// Returns None if the chain is not set or the token is not found.
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn wrapped_native(&self) -> Option<Address> {
+        self.chain().and_then(crate::contracts::addresses::wrapped_native)
+    }
+
+    /// Returns an error if `token` is the chain's wrapped native token, since the resulting pair
+    /// would be composed of the same token.
+    #[cfg_attr(not(feature = "addresses"), allow(unused_variables))]
+    fn check_eth_pair(&self, token: Address) -> Result<()> {
+        #[cfg(feature = "addresses")]
+        if self.wrapped_native() == Some(token) {
+            return Err(Error::IdenticalAddresses);
+        }
+        Ok(())
+    }
+
+    /// Checks the protocol's addresses against the chain: that the factory and router have code,
+    /// that the router's factory and wrapped native token are the configured ones, and that the
+    /// pair code hash matches the factory's first pair.
+    ///
+    /// The wrapped native token is checked only if the [chain][Self::chain] is set. Errors, like
+    /// failed provider requests, are reported as failed checks.
+    pub async fn verify(&self) -> VerificationReport {
+        let client = self.client();
+        let (factory, router) = (self.factory.address(), self.router.address());
+        let mut report = VerificationReport::default();
+        report.check_code(client.as_ref(), "factory_code", factory).await;
+        report.check_code(client.as_ref(), "router_code", router).await;
+
+        let (router_factory, router_weth) = if self.factory.protocol().is_solidly() {
+            let router = ISolidlyRouter::new(router, client);
+            (router.factory(), router.weth())
+        } else {
+            (self.router.contract().factory(), self.router.contract().weth())
+        };
+        #[cfg(feature = "addresses")]
+        let weth = self.wrapped_native();
+        #[cfg(not(feature = "addresses"))]
+        let weth = None;
+        report.check_call("router_factory", router_factory, Some(factory)).await;
+        report.check_call("router_wrapped_native", router_weth, weth).await;
+
+        let hash = self.factory.pair_code_hash(None);
+        let (status, details) = match self.factory.check_pair_code_hash().await {
+            Ok(Some(true)) => (CheckStatus::Passed, format!("{hash:?}")),
+            Ok(Some(false)) => {
+                (CheckStatus::Failed, format!("{hash:?} does not match the first pair"))
+            }
+            Ok(None) => (CheckStatus::Skipped, "factory has not created any pairs".to_string()),
+            Err(e) => (CheckStatus::Failed, e.to_string()),
+        };
+        report.push("pair_code_hash", status, details);
+        report
+    }
+
     /* ----------------------------------------- Factory ---------------------------------------- */
 
     /// Returns a reference to the factory.
@@ -101,9 +227,30 @@ impl<M: Middleware> Protocol<M> {
         &self.router
     }
 
+    /// Returns the contract calls that must be executed, in order, to allow `spender` to transfer
+    /// `amount` of `token` from the client's default sender. See [`Erc20::approve_if_needed`].
+    ///
+    /// Returns no calls if `token` is the native token, and
+    /// [`Error::SignerRequired`][crate::errors::Error::SignerRequired] if the client is read-only.
+    ///
+    /// [`Erc20::approve_if_needed`]: crate::Erc20::approve_if_needed
+    pub async fn approve_if_needed(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Vec<ContractCall<M, bool>>> {
+        if is_native(&token) {
+            return Ok(vec![]);
+        }
+        let client = self.client();
+        let owner = require_signer(client.as_ref(), "Approving a spender")?;
+        Erc20::new(client, token).approve_if_needed(owner, spender, amount).await
+    }
+
     /// The router's `add_liquidity` method. See documentation of [Router] for more details.
     #[inline(always)]
-    pub fn add_liquidity(
+    pub async fn add_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
@@ -111,55 +258,720 @@ impl<M: Middleware> Protocol<M> {
         amount_b_desired: U256,
         amount_a_min: U256,
         amount_b_min: U256,
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
     ) -> Result<ContractCall<M, (U256, U256, U256)>> {
-        self.router.add_liquidity(
-            token_a,
-            token_b,
-            amount_a_desired,
-            amount_b_desired,
-            amount_a_min,
-            amount_b_min,
-            to,
-            deadline,
-        )
+        self.router
+            .add_liquidity(
+                token_a,
+                token_b,
+                amount_a_desired,
+                amount_b_desired,
+                amount_a_min,
+                amount_b_min,
+                to,
+                deadline,
+            )
+            .await
     }
 
     /// The router's `remove_liquidity` method. See documentation of [Router] for more details.
     #[inline(always)]
-    pub fn remove_liquidity(
+    pub async fn remove_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
         liquidity: U256,
         amount_a_min: U256,
         amount_b_min: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        self.router
+            .remove_liquidity(token_a, token_b, liquidity, amount_a_min, amount_b_min, to, deadline)
+            .await
+    }
+
+    /// The router's `remove_liquidity` method for burning all the liquidity of `position`, with the
+    /// minimum amounts computed by applying `slippage_tolerance` to its amounts.
+    ///
+    /// See [`Pair::position_of`] for getting a position.
+    pub async fn remove_liquidity_position(
+        &self,
+        position: &LiquidityPosition,
+        slippage_tolerance: impl Into<Slippage>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        let slippage = slippage_tolerance.into().validate()?;
+        self.remove_liquidity(
+            position.token0,
+            position.token1,
+            position.liquidity,
+            slippage.minimum_amount_out(position.amount0),
+            slippage.minimum_amount_out(position.amount1),
+            to,
+            deadline,
+        )
+        .await
+    }
+
+    /// The router's `add_liquidity_eth` method. See documentation of [Router] for more details.
+    ///
+    /// The returned call's `value` is set to `amount_eth_desired`.
+    pub fn add_liquidity_eth(
+        &self,
+        token: Address,
+        amount_token_desired: U256,
+        amount_eth_desired: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+    ) -> Result<ContractCall<M, (U256, U256, U256)>> {
+        self.check_eth_pair(token)?;
+        self.router.add_liquidity_eth(
+            token,
+            amount_token_desired,
+            amount_eth_desired,
+            amount_token_min,
+            amount_eth_min,
+            to,
+            deadline,
+        )
+    }
+
+    /// The router's `remove_liquidity_eth` method. See documentation of [Router] for more details.
+    pub fn remove_liquidity_eth(
+        &self,
+        token: Address,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
         to: Address,
         deadline: U256,
     ) -> Result<ContractCall<M, (U256, U256)>> {
-        self.router.remove_liquidity(
-            token_a,
-            token_b,
+        self.check_eth_pair(token)?;
+        self.router.remove_liquidity_eth(
+            token,
+            liquidity,
+            amount_token_min,
+            amount_eth_min,
+            to,
+            deadline,
+        )
+    }
+
+    /// The router's `remove_liquidity_eth_supporting_fee_on_transfer_tokens` method. See
+    /// documentation of [Router] for more details.
+    pub fn remove_liquidity_eth_supporting_fee_on_transfer_tokens(
+        &self,
+        token: Address,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+    ) -> Result<ContractCall<M, U256>> {
+        self.check_eth_pair(token)?;
+        self.router.remove_liquidity_eth_supporting_fee_on_transfer_tokens(
+            token,
             liquidity,
-            amount_a_min,
-            amount_b_min,
+            amount_token_min,
+            amount_eth_min,
             to,
             deadline,
         )
     }
 
     /// The router's `swap` method. See documentation of [Router] for more details.
-    #[inline(always)]
+    ///
+    /// `weth` overrides the chain's wrapped native token, which is otherwise used in place of the
+    /// native token. Returns
+    /// [`Error::WrappedNativeNotFound`][crate::errors::Error::WrappedNativeNotFound] if
+    /// the swap goes from or to the native token and neither is set.
+    ///
+    /// If `route` is [`Route::Auto`], the path is found with [`Router::find_route`], or with
+    /// [`Router::find_route_solidly`] for Solidly forks, whose found flags replace
+    /// [`options.stable`][SwapOptions::stable].
     pub async fn swap(
         &self,
         amount: Amount,
-        slippage_tolerance: f32,
-        path: &[Address],
-        to: Address,
-        deadline: U256,
-        weth: Address,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
     ) -> Result<ContractCall<M, Vec<U256>>> {
-        self.router.swap(&self.factory, amount, slippage_tolerance, path, to, deadline, weth).await
+        let (amount, path, weth, stable) = self.resolve_route(amount, route.into(), weth).await?;
+        let options = with_stable(options, stable);
+        self.router
+            .swap(&self.factory, amount, slippage_tolerance, &path, to, deadline, weth, options)
+            .await
+    }
+
+    /// The router's `swap_supporting_fee_on_transfer` method, for tokens which take a fee on
+    /// transfer. See [`swap`][Self::swap] and [`Router::swap_supporting_fee_on_transfer`].
+    pub async fn swap_supporting_fee_on_transfer(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, ()>> {
+        let (amount, path, weth, stable) = self.resolve_route(amount, route.into(), weth).await?;
+        let options = with_stable(options, stable);
+        self.router
+            .swap_supporting_fee_on_transfer(
+                &self.factory,
+                amount,
+                slippage_tolerance,
+                &path,
+                to,
+                deadline,
+                weth,
+                options,
+            )
+            .await
+    }
+
+    /// Quotes the swap which would be built by [`swap`][Self::swap], returning the resulting
+    /// [Trade] with its price impact. See [`Router::quote`].
+    pub async fn quote_swap(
+        &self,
+        amount: Amount,
+        route: impl Into<Route>,
+        weth: Option<Address>,
+    ) -> Result<Trade> {
+        let (amount, path, weth, stable) = self.resolve_route(amount, route.into(), weth).await?;
+        let amount = amount.resolve(self.client(), &path).await?;
+        self.router.quote_hops(&self.factory, amount, &path, stable.as_deref(), weth).await
+    }
+
+    /// Resolves the wrapped native token and the path of `route`, along with `amount` if the path
+    /// has to be found with it, and whether each pair of the found path is stable for Solidly
+    /// forks.
+    async fn resolve_route(
+        &self,
+        mut amount: Amount,
+        route: Route,
+        weth: Option<Address>,
+    ) -> Result<(Amount, Vec<Address>, Address, Option<Vec<bool>>)> {
+        let needs_weth = matches!(amount, Amount::ExactInNative(_))
+            || match &route {
+                Route::Path(path) => path.iter().any(is_native),
+                Route::Auto { token_in, token_out } => is_native(token_in) || is_native(token_out),
+            };
+        let weth = resolve_weth(weth, self.chain(), needs_weth)?;
+        let (path, stable) = match route {
+            Route::Path(path) => (path, None),
+            Route::Auto { token_in, token_out } => {
+                if let Amount::PercentOfBalance { .. } = amount {
+                    amount = amount.resolve(self.client(), &[token_in, token_out]).await?;
+                }
+                let mut tokens = [token_in, token_out];
+                map_native(&mut tokens, weth);
+                let (router, factory) = (&self.router, &self.factory);
+                let (mut path, stable) = if factory.protocol().is_solidly() {
+                    let (path, stable) = router
+                        .find_route_solidly(factory, amount, tokens[0], tokens[1], DEFAULT_MAX_HOPS)
+                        .await?;
+                    (path, Some(stable))
+                } else {
+                    let path = router
+                        .find_route(factory, amount, tokens[0], tokens[1], DEFAULT_MAX_HOPS)
+                        .await?;
+                    (path, None)
+                };
+                // restore the native token
+                let last = path.len() - 1;
+                path[0] = token_in;
+                path[last] = token_out;
+                (path, stable)
+            }
+        };
+        Ok((amount, path, weth, stable))
+    }
+
+    /// Returns the contract call for swapping exactly `amount_in`, parsed as a decimal string
+    /// using `token_in`'s decimals, for any amount of `token_out`.
+    ///
+    /// The decimals are fetched from the token, or default to `18` for the native token. See
+    /// [`swap`][Self::swap] for more details.
+    pub async fn swap_dec(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: &str,
+        slippage_tolerance: impl Into<Slippage>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<U256>>> {
+        let decimals = if is_native(&token_in) {
+            18
+        } else {
+            Erc20::new(self.client(), token_in).get_decimals().await?
+        };
+        let amount = Amount::exact_in_dec(amount_in, decimals)?;
+        let path = [token_in, token_out];
+        self.swap(amount, slippage_tolerance, path, to, deadline, weth, options).await
+    }
+
+    /// Simulates the swap built by [`swap`][Self::swap] with `eth_call`, returning the router's
+    /// output amounts, or the decoded revert.
+    ///
+    /// `simulate` can override the sender and the state of the call, e.g. to give the sender the
+    /// input tokens and the router's allowance. Screening a token this way with both a buy and a
+    /// sell detects tokens which cannot be sold.
+    pub async fn simulate_swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+        simulate: SimulateOptions,
+    ) -> Result<Simulation<Vec<U256>>> {
+        let call =
+            self.swap(amount, slippage_tolerance, route, to, deadline, weth, options).await?;
+        simulate_call(self.client().as_ref(), call, simulate).await
+    }
+
+    /// Estimates the gas of the swap built by [`swap`][Self::swap], padded by `margin_bps` basis
+    /// points, and the suggested fees. See [`gas::estimate`].
+    pub async fn estimate_gas_swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        route: impl Into<Route>,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+        margin_bps: u32,
+    ) -> Result<GasEstimate> {
+        let call =
+            self.swap(amount, slippage_tolerance, route, to, deadline, weth, options).await?;
+        gas::estimate(self.client().as_ref(), &call, margin_bps).await
+    }
+
+    /// Sends a swap built with [`swap`][Self::swap], waits for `confirmations` confirmations and
+    /// parses the amounts of the swap from the transaction receipt.
+    ///
+    /// If the transaction reverts, the revert is decoded by replaying it at the block in which it
+    /// was included, e.g. into [`Error::SlippageExceeded`][crate::errors::Error::SlippageExceeded].
+    /// Use the call directly to manage the transaction yourself.
+    pub async fn execute_swap<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+        confirmations: usize,
+    ) -> Result<SwapReceipt> {
+        execute_swap(self.client().as_ref(), call, confirmations).await
+    }
+
+    /// Sends a swap built with [`swap`][Self::swap] privately through `submitter`, like the
+    /// [Flashbots][crate::mev::Flashbots] relay, instead of the public mempool, where it could be
+    /// sandwiched.
+    ///
+    /// The transaction is simulated, signed by the client and submitted with an expiry of
+    /// [`options.max_blocks`][crate::mev::PrivateOptions::max_blocks] blocks. The amounts of the
+    /// swap are then parsed from its receipt, as in [`execute_swap`][Self::execute_swap].
+    #[cfg(feature = "mev")]
+    pub async fn execute_swap_private<D: Detokenize, S: crate::mev::BundleSubmitter>(
+        &self,
+        call: ContractCall<M, D>,
+        submitter: &S,
+        options: crate::mev::PrivateOptions,
+    ) -> Result<SwapReceipt> {
+        crate::mev::execute_swap_private(self.client().as_ref(), call, submitter, options).await
+    }
+
+    /// Generates the access list of `call` with `eth_createAccessList`, and sets it on the call
+    /// only if it lowers its estimated gas. See [`gas::with_access_list`].
+    ///
+    /// The generated list is returned in both cases, or None if the provider does not support the
+    /// RPC.
+    pub async fn with_access_list<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+    ) -> Result<(ContractCall<M, D>, Option<AccessListEstimate>)> {
+        gas::with_access_list(self.client().as_ref(), call).await
+    }
+}
+
+/// Sets the `stable` flags of a found Solidly route on `options`.
+fn with_stable(options: SwapOptions, stable: Option<Vec<bool>>) -> SwapOptions {
+    match stable {
+        Some(stable) => options.stable(stable),
+        None => options,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::NATIVE_ADDRESS,
+        contracts::bindings::{i_uniswap_v2_router_02::*, ierc20::ApproveCall},
+        errors::Error,
+    };
+    use ethers_core::{
+        abi::{self, AbiDecode, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    fn default_protocol() -> Protocol<Provider<MockProvider>> {
+        let (provider, _mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        Protocol::new(client, factory, router, ProtocolType::UniswapV2)
+    }
+
+    #[test]
+    fn test_add_liquidity_eth() {
+        let protocol = default_protocol();
+        let token = Address::repeat_byte(0x0a);
+        let to = Address::repeat_byte(0x0b);
+        let (amount_token, amount_eth) = (U256::from(1000), U256::from(50));
+
+        let call = protocol
+            .add_liquidity_eth(token, amount_token, amount_eth, 900.into(), 45.into(), to, 1.into())
+            .unwrap();
+        assert_eq!(call.tx.value(), Some(&amount_eth));
+
+        let decoded = AddLiquidityETHCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.token, token);
+        assert_eq!(decoded.amount_token_desired, amount_token);
+        assert_eq!(decoded.amount_token_min, 900.into());
+        assert_eq!(decoded.amount_eth_min, 45.into());
+        assert_eq!(decoded.to, to);
+    }
+
+    #[tokio::test]
+    async fn test_remove_liquidity_position() {
+        let protocol = default_protocol();
+        let to = Address::repeat_byte(0x0b);
+        let position = LiquidityPosition {
+            token0: Address::repeat_byte(0x0a),
+            token1: NATIVE_ADDRESS,
+            liquidity: 1000.into(),
+            share_bps: 100,
+            amount0: 20_000.into(),
+            amount1: 500.into(),
+        };
+
+        let call = protocol.remove_liquidity_position(&position, 1.0, to, U256::MAX).await.unwrap();
+        let decoded = RemoveLiquidityETHCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.token, position.token0);
+        assert_eq!(decoded.liquidity, position.liquidity);
+        assert_eq!(decoded.amount_token_min, 19_800.into());
+        assert_eq!(decoded.amount_eth_min, 495.into());
+
+        let res = protocol.remove_liquidity_position(&position, 101.0, to, U256::MAX).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSlippage));
+    }
+
+    #[test]
+    fn test_remove_liquidity_eth() {
+        let protocol = default_protocol();
+        let token = Address::repeat_byte(0x0a);
+        let to = Address::repeat_byte(0x0b);
+        let liquidity = U256::from(1000);
+
+        let call = protocol
+            .remove_liquidity_eth(token, liquidity, 1.into(), 2.into(), to, 3.into())
+            .unwrap();
+        let decoded = RemoveLiquidityETHCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.liquidity, liquidity);
+        assert!(call.tx.value().is_none());
+
+        let call = protocol
+            .remove_liquidity_eth_supporting_fee_on_transfer_tokens(
+                token,
+                liquidity,
+                1.into(),
+                2.into(),
+                to,
+                3.into(),
+            )
+            .unwrap();
+        let decoded =
+            RemoveLiquidityETHSupportingFeeOnTransferTokensCall::decode(call.calldata().unwrap())
+                .unwrap();
+        assert_eq!(decoded.token, token);
+        assert_eq!(decoded.liquidity, liquidity);
+
+        let res = protocol.remove_liquidity_eth(
+            crate::constants::NATIVE_ADDRESS,
+            liquidity,
+            1.into(),
+            2.into(),
+            to,
+            3.into(),
+        );
+        assert!(matches!(res.unwrap_err(), Error::IdenticalAddresses));
+    }
+
+    #[test]
+    #[cfg(feature = "addresses")]
+    fn test_wrapped_native() {
+        let mut protocol = default_protocol();
+        assert!(protocol.wrapped_native().is_none());
+
+        protocol.set_chain(Chain::Mainnet);
+        let weth = protocol.wrapped_native().unwrap();
+        let res = protocol.add_liquidity_eth(
+            weth,
+            1.into(),
+            1.into(),
+            0.into(),
+            0.into(),
+            Address::zero(),
+            0.into(),
+        );
+        assert!(matches!(res.unwrap_err(), Error::IdenticalAddresses));
+    }
+
+    mod approve {
+        use super::*;
+        use crate::{contracts::bindings::ierc20::AllowanceCall, testing::MockDex};
+        use ethers_middleware::SignerMiddleware;
+        use ethers_signers::{LocalWallet, Signer};
+
+        type M = SignerMiddleware<Provider<MockDex>, LocalWallet>;
+
+        const TOKEN: Address = Address::repeat_byte(0x0a);
+        const SPENDER: Address = Address::repeat_byte(0x22);
+
+        fn setup(allowance: u64) -> (Protocol<M>, MockDex) {
+            let (provider, dex) = MockDex::mocked();
+            let signer = crate::testing::default_wallet();
+            let owner = signer.address();
+            dex.mock_call(TOKEN, AllowanceCall { owner, spender: SPENDER }, U256::from(allowance));
+            let client = Arc::new(SignerMiddleware::new(provider, signer));
+            let protocol =
+                Protocol::new(client, Address::repeat_byte(0x11), SPENDER, ProtocolType::UniswapV2);
+            (protocol, dex)
+        }
+
+        fn approve(amount: u64) -> ApproveCall {
+            ApproveCall { spender: SPENDER, amount: amount.into() }
+        }
+
+        fn decode(call: &ContractCall<M, bool>) -> ApproveCall {
+            ApproveCall::decode(call.calldata().unwrap()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_sufficient_allowance() {
+            let (protocol, _dex) = setup(100);
+            let calls = protocol.approve_if_needed(TOKEN, SPENDER, 100.into()).await.unwrap();
+            assert!(calls.is_empty());
+
+            let calls = protocol.approve_if_needed(NATIVE_ADDRESS, SPENDER, 100.into()).await;
+            assert!(calls.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_zero_allowance() {
+            let (protocol, _dex) = setup(0);
+            let calls = protocol.approve_if_needed(TOKEN, SPENDER, 100.into()).await.unwrap();
+            assert_eq!(calls.len(), 1);
+            assert_eq!(calls[0].tx.to_addr(), Some(&TOKEN));
+            let approve = decode(&calls[0]);
+            assert_eq!((approve.spender, approve.amount), (SPENDER, 100.into()));
+        }
+
+        #[tokio::test]
+        async fn test_partial_allowance() {
+            let (protocol, dex) = setup(50);
+            dex.mock_call(TOKEN, approve(100), true);
+            let calls = protocol.approve_if_needed(TOKEN, SPENDER, 100.into()).await.unwrap();
+            assert_eq!(calls.len(), 1);
+            assert_eq!(decode(&calls[0]).amount, 100.into());
+        }
+
+        #[tokio::test]
+        async fn test_usdt_reset() {
+            let (protocol, dex) = setup(50);
+            dex.mock_revert(TOKEN, approve(100), Bytes::default());
+            let calls = protocol.approve_if_needed(TOKEN, SPENDER, 100.into()).await.unwrap();
+            assert_eq!(calls.len(), 2);
+            assert_eq!(decode(&calls[0]).amount, U256::zero());
+            assert_eq!(decode(&calls[1]).amount, 100.into());
+        }
+
+        #[tokio::test]
+        async fn test_simulation_error() {
+            // the simulated approval is not programmed, so the provider fails without reverting
+            let (protocol, _dex) = setup(50);
+            let res = protocol.approve_if_needed(TOKEN, SPENDER, 100.into()).await;
+            assert!(matches!(res.unwrap_err(), Error::ContractError(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_dec() {
+        let (provider, mock) = Provider::mocked();
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let protocol = Protocol::new(Arc::new(provider), factory, router, ProtocolType::UniswapV2);
+        let (token_in, token_out) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+
+        let decimals = abi::encode(&[Token::Uint(6.into())]);
+        mock.push::<Bytes, _>(Bytes::from(decimals)).unwrap();
+        let call = protocol
+            .swap_dec(
+                token_in,
+                token_out,
+                "1.5",
+                100.0,
+                token_out,
+                U256::MAX,
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = SwapExactTokensForTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.amount_in, 1_500_000.into());
+        assert_eq!(decoded.path, vec![token_in, token_out]);
+
+        let res = protocol
+            .swap_dec(
+                NATIVE_ADDRESS,
+                token_out,
+                "1.5e18",
+                100.0,
+                token_out,
+                U256::MAX,
+                None,
+                Default::default(),
+            )
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidUnits(_)));
+
+        // the wrapped native token is not known without a chain
+        let path = [NATIVE_ADDRESS, token_out];
+        let amount = Amount::ExactIn(1.into());
+        let res = protocol.swap(amount, 1.0, path, token_out, U256::MAX, None, Default::default());
+        assert!(matches!(res.await.unwrap_err(), Error::WrappedNativeNotFound(None)));
+    }
+
+    #[tokio::test]
+    async fn test_swap_auto_route() {
+        use crate::{testing::MockDex, v2::Library, Route};
+
+        let (provider, mock) = MockDex::mocked();
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let protocol = Protocol::new(Arc::new(provider), factory, router, ProtocolType::UniswapV2);
+        let (token_a, token_b) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        let route = Route::Auto { token_in: token_a, token_out: token_b };
+        let amount_in = U256::exp10(18);
+        let amount = Amount::ExactIn(amount_in);
+
+        mock.mock_get_pair(factory, token_a, token_b, Address::zero());
+        let res =
+            protocol.swap(amount, 1.0, route.clone(), token_b, U256::MAX, None, Default::default());
+        assert!(matches!(res.await.unwrap_err(), Error::NoRouteFound));
+
+        mock.mock_pair(protocol.factory(), token_a, token_b, U256::exp10(21), U256::exp10(24));
+        let call = protocol
+            .swap(amount, 1.0, route, token_b, U256::MAX, None, Default::default())
+            .await
+            .unwrap();
+        let decoded = SwapExactTokensForTokensCall::decode(call.calldata().unwrap()).unwrap();
+        let path = [token_a, token_b];
+        let amounts = Library::get_amounts_out(protocol.factory(), amount_in, &path).await.unwrap();
+        assert_eq!(decoded.path, path.to_vec());
+        assert_eq!(decoded.amount_out_min, Slippage::from(1.0).minimum_amount_out(amounts[1]));
+    }
+
+    #[tokio::test]
+    async fn test_swap_auto_route_solidly() {
+        use crate::{
+            contracts::bindings::i_solidly_router::SwapExactTokensForTokensCall as SolidlySwapCall,
+            testing::MockDex, v2::Library, Route,
+        };
+
+        let (provider, mock) = MockDex::mocked();
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let velodrome = ProtocolType::Velodrome { stable: false };
+        let protocol = Protocol::new(Arc::new(provider), factory, router, velodrome);
+        let (token_a, token_b) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        let route = Route::Auto { token_in: token_a, token_out: token_b };
+        let amount_in = U256::exp10(6);
+        let amount = Amount::ExactIn(amount_in);
+
+        // only the stable pair exists, unlike the factory's flag
+        mock.mock_token(token_a, "Token A", "A", 6).mock_token(token_b, "Token B", "B", 18);
+        mock.mock_get_pair_solidly(factory, token_a, token_b, false, Address::zero());
+        let (reserve_a, reserve_b) = (U256::exp10(12), U256::exp10(24));
+        mock.mock_solidly_pair(protocol.factory(), token_a, token_b, true, reserve_a, reserve_b);
+
+        let call = protocol
+            .swap(amount, 1.0, route.clone(), token_b, U256::MAX, None, Default::default())
+            .await
+            .unwrap();
+        let decoded = SolidlySwapCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.routes.len(), 1);
+        assert!(decoded.routes[0].stable);
+        let path = [token_a, token_b];
+        let amounts =
+            Library::get_amounts_out_stable(protocol.factory(), amount_in, &path, &[true])
+                .await
+                .unwrap();
+        assert_eq!(decoded.amount_out_min, Slippage::from(1.0).minimum_amount_out(amounts[1]));
+
+        let trade = protocol.quote_swap(amount, route, None).await.unwrap();
+        assert_eq!(trade.amounts, amounts);
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        use crate::{v2::Library, verify::CheckStatus};
+
+        let (provider, mock) = Provider::mocked();
+        let (factory, router) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let mut protocol =
+            Protocol::new(Arc::new(provider), factory, router, ProtocolType::UniswapV2);
+        let (token_0, token_1) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let pair = Library::pair_for(protocol.factory(), token_0, token_1);
+        let push =
+            |tokens: &[Token]| mock.push::<Bytes, _>(Bytes::from(abi::encode(tokens))).unwrap();
+        let push_checks = |router_factory: Address| {
+            // responses are popped in reverse
+            push(&[Token::Address(token_1)]);
+            push(&[Token::Address(token_0)]);
+            push(&[Token::Address(pair)]);
+            push(&[Token::Uint(1.into())]);
+            push(&[Token::Address(router_factory)]);
+            mock.push::<Bytes, _>(Bytes::from(vec![1])).unwrap();
+            mock.push::<Bytes, _>(Bytes::from(vec![1])).unwrap();
+        };
+
+        push_checks(factory);
+        let report = protocol.verify().await;
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.checks.len(), 5);
+        assert_eq!(report.get("router_wrapped_native").unwrap().status, CheckStatus::Skipped);
+        assert_eq!(report.get("pair_code_hash").unwrap().status, CheckStatus::Passed);
+
+        protocol.factory.set_pair_code_hash(H256::repeat_byte(1));
+        push_checks(Address::repeat_byte(0x33));
+        let report = protocol.verify().await;
+        assert!(!report.is_ok());
+        let failed: Vec<_> = report.failed().map(|check| check.name).collect();
+        assert_eq!(failed, ["router_factory", "pair_code_hash"]);
+        let line = format!("[FAILED] router_factory: expected {factory:?}");
+        assert!(report.to_string().contains(&line), "{report}");
     }
 }
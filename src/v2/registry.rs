@@ -0,0 +1,121 @@
+use super::Library;
+use ethers_core::types::Address;
+use std::collections::BTreeMap;
+
+/// A pair created by a factory, as emitted by its `PairCreated` event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PairInfo {
+    /// The first token of the pair, sorted.
+    pub token0: Address,
+    /// The second token of the pair, sorted.
+    pub token1: Address,
+    /// The address of the pair.
+    pub address: Address,
+    /// The number of the block in which the pair was created.
+    pub block_number: u64,
+}
+
+/// A local registry of the pairs created by a factory, built by scanning its `PairCreated` events
+/// with [`Factory::scan_pairs`][super::Factory::scan_pairs].
+///
+/// The registry can be persisted and later resumed from the first block which has not been
+/// scanned yet with [`Factory::resume_scan`][super::Factory::resume_scan].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PairRegistry {
+    /// The pairs, keyed by their sorted tokens.
+    #[cfg_attr(feature = "serde", serde(with = "pairs"))]
+    pairs: BTreeMap<(Address, Address), PairInfo>,
+
+    /// The first block which has not been scanned yet.
+    pub next_block: u64,
+}
+
+impl PairRegistry {
+    /// Creates a new empty registry which starts scanning at `from_block`.
+    pub fn new(from_block: u64) -> Self {
+        Self { pairs: BTreeMap::new(), next_block: from_block }
+    }
+
+    /// Returns the pair composed of `token_a` and `token_b`, in any order.
+    pub fn get(&self, token_a: Address, token_b: Address) -> Option<&PairInfo> {
+        self.pairs.get(&Library::sort_tokens(token_a, token_b))
+    }
+
+    /// Inserts `pair`, returning the previous pair with the same tokens, if any.
+    pub fn insert(&mut self, pair: PairInfo) -> Option<PairInfo> {
+        self.pairs.insert(Library::sort_tokens(pair.token0, pair.token1), pair)
+    }
+
+    /// Returns the number of pairs in the registry.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns whether the registry does not contain any pairs.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns an iterator over the pairs, sorted by their tokens.
+    pub fn iter(&self) -> impl Iterator<Item = &PairInfo> {
+        self.pairs.values()
+    }
+}
+
+/// (De)serializes the pairs as a sequence, since JSON keys must be strings.
+#[cfg(feature = "serde")]
+mod pairs {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        pairs: &BTreeMap<(Address, Address), PairInfo>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pairs.values())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<(Address, Address), PairInfo>, D::Error> {
+        let pairs = Vec::<PairInfo>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().map(|p| (Library::sort_tokens(p.token0, p.token1), p)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let (a, b) = (Address::repeat_byte(2), Address::repeat_byte(1));
+        let pair =
+            PairInfo { token0: b, token1: a, address: Address::repeat_byte(3), block_number: 1 };
+
+        let mut registry = PairRegistry::new(1);
+        assert!(registry.is_empty());
+        assert!(registry.insert(pair).is_none());
+        assert_eq!(registry.get(a, b), Some(&pair));
+        assert_eq!(registry.get(b, a), Some(&pair));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_registry_serde() {
+        let mut registry = PairRegistry::new(10);
+        for i in 1..=3u8 {
+            let (token0, token1) = (Address::repeat_byte(i), Address::repeat_byte(i + 10));
+            let address = Address::repeat_byte(i + 20);
+            registry.insert(PairInfo { token0, token1, address, block_number: i as u64 });
+        }
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let deserialized: PairRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, registry);
+        assert_eq!(deserialized.next_block, 10);
+    }
+}
@@ -1,26 +1,56 @@
-use super::{Factory, Library};
+use super::{factory::validate_fee, Factory, Library, Trade};
 use crate::{
-    constants::BPS_U256,
-    contracts::bindings::i_uniswap_v2_router_02::IUniswapV2Router02,
+    common::MethodCall,
+    constants::NATIVE_ADDRESS,
+    contracts::bindings::{
+        i_solidly_router::{self as solidly, ISolidlyRouter, Route as SolidlyRoute},
+        i_uniswap_v2_pair::SwapFilter,
+        i_uniswap_v2_router_02::{
+            IUniswapV2Router02, SwapETHForExactTokensCall, SwapExactETHForTokensCall,
+            SwapExactETHForTokensSupportingFeeOnTransferTokensCall, SwapExactTokensForETHCall,
+            SwapExactTokensForETHSupportingFeeOnTransferTokensCall, SwapExactTokensForTokensCall,
+            SwapExactTokensForTokensSupportingFeeOnTransferTokensCall, SwapTokensForExactETHCall,
+            SwapTokensForExactTokensCall,
+        },
+    },
     errors::{Error, Result},
-    utils::{is_native_path, map_native},
-    Amount,
+    gas,
+    permit::split_signature,
+    tokens::TokenCache,
+    utils::{is_native, is_native_mid_path, is_native_path, map_native},
+    Amount, Deadline, QuoteCache, Recipient, Slippage, SwapOptions,
+};
+use ethers_contract::{builders::ContractCall, EthLogDecode};
+use ethers_core::{
+    abi::Detokenize,
+    types::{Address, Chain, Signature, TransactionReceipt, U256},
 };
-use ethers_contract::builders::ContractCall;
-use ethers_core::types::{Address, U256};
 use ethers_providers::Middleware;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 #[cfg(feature = "addresses")]
 use crate::protocol::ProtocolType;
-#[cfg(feature = "addresses")]
-use ethers_core::types::Chain;
+
+/// The default maximum number of hops, or pairs, in a route found by [`Router::find_route`].
+pub const DEFAULT_MAX_HOPS: usize = 3;
 
 contract_struct! {
     /// A UniswapV2 router.
     pub struct Router<M> {
         /// The router contract.
         contract: IUniswapV2Router02<M>,
+
+        /// The intermediary tokens used for finding routes.
+        pub base_tokens: Vec<Address>,
+
+        /// The swap fee override, in basis points.
+        swap_fee_bps: Option<u32>,
+
+        /// The cache of token metadata used for formatting amounts, and its chain.
+        token_cache: Option<(Chain, TokenCache)>,
+
+        /// The cache of reserves used for quoting swaps, and its chain.
+        quote_cache: Option<(Chain, QuoteCache)>,
     }
 }
 
@@ -29,7 +59,13 @@ impl<M: Middleware> Router<M> {
     pub fn new(client: Arc<M>, address: Address) -> Self {
         // assert!(protocol.is_v2(), "protocol must be v2");
         let contract = IUniswapV2Router02::new(address, client);
-        Self { contract }
+        Self {
+            contract,
+            base_tokens: vec![],
+            swap_fee_bps: None,
+            token_cache: None,
+            quote_cache: None,
+        }
     }
 
     /// Creates a new instance by searching for the required addresses in the [addressbook].
@@ -38,7 +74,63 @@ impl<M: Middleware> Router<M> {
     #[cfg(feature = "addresses")]
     pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
         // assert!(protocol.is_v2(), "protocol must be v2");
-        protocol.try_addresses(chain).1.map(|address| Self::new(client, address))
+        protocol.try_addresses(chain).1.map(|address| {
+            let mut router = Self::new(client, address);
+            router.base_tokens = crate::contracts::addresses::base_tokens(chain);
+            router
+        })
+    }
+
+    /// Sets the intermediary tokens used for finding routes.
+    pub fn set_base_tokens(&mut self, base_tokens: Vec<Address>) {
+        self.base_tokens = base_tokens;
+    }
+
+    /// Returns the swap fee, in basis points, used for quoting swaps through the pairs of
+    /// `factory`: the fee set with [`set_swap_fee_bps`][Self::set_swap_fee_bps], if any, otherwise
+    /// the [factory's][Factory::swap_fee_bps].
+    pub fn swap_fee_bps(&self, factory: &Factory<M>) -> u32 {
+        self.swap_fee_bps.unwrap_or_else(|| factory.swap_fee_bps())
+    }
+
+    /// Sets the swap fee used for quoting swaps, in basis points, overriding the one of the
+    /// factory.
+    ///
+    /// Returns [`Error::InvalidFee`] if the fee is not lower than `10_000` (100%).
+    pub fn set_swap_fee_bps(&mut self, fee_bps: u32) -> Result<()> {
+        self.swap_fee_bps = Some(validate_fee(fee_bps)?);
+        Ok(())
+    }
+
+    /// Returns the cache of token metadata and its chain, if set.
+    pub fn token_cache(&self) -> Option<(Chain, &TokenCache)> {
+        self.token_cache.as_ref().map(|(chain, cache)| (*chain, cache))
+    }
+
+    /// Sets the cache of token metadata on `chain` used by
+    /// [`format_amount`][Self::format_amount].
+    pub fn set_token_cache(&mut self, chain: Chain, cache: TokenCache) {
+        self.token_cache = Some((chain, cache));
+    }
+
+    /// Formats `amount` of `token` with its decimals and symbol from the token cache, like
+    /// `"1.5 WETH"`, or None if no cache is set or the token's decimals are not cached. Tokens can
+    /// be cached with [`TokenCache::fetch`].
+    pub fn format_amount(&self, token: Address, amount: U256) -> Option<String> {
+        let (chain, cache) = self.token_cache.as_ref()?;
+        cache.format_amount(*chain, token, amount)
+    }
+
+    /// Returns the cache of reserves and its chain, if set.
+    pub fn quote_cache(&self) -> Option<(Chain, &QuoteCache)> {
+        self.quote_cache.as_ref().map(|(chain, cache)| (*chain, cache))
+    }
+
+    /// Sets the cache of the reserves of the pairs on `chain` used by [`swap`][Self::swap] and
+    /// [`quote`][Self::quote], so that quoting the same path again within a block does not make
+    /// any request.
+    pub fn set_quote_cache(&mut self, chain: Chain, cache: QuoteCache) {
+        self.quote_cache = Some((chain, cache));
     }
 
     /// Generalized add_liquidity function for the various [UniswapV2Router] methods.
@@ -51,7 +143,7 @@ impl<M: Middleware> Router<M> {
     ///
     /// [UniswapV2Router]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/UniswapV2Router01.sol
     /// [Dex]: crate::Dex
-    pub fn add_liquidity(
+    pub async fn add_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
@@ -59,9 +151,11 @@ impl<M: Middleware> Router<M> {
         amount_b_desired: U256,
         amount_a_min: U256,
         amount_b_min: U256,
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
     ) -> Result<ContractCall<M, (U256, U256, U256)>> {
+        let deadline = deadline.into().resolve(self.client().as_ref()).await?;
+        let to = to.into().resolve(self.client().as_ref()).await?;
         let router = self.contract();
         let (native_a, native_b) = is_native_path(&[token_a, token_b]);
 
@@ -117,16 +211,18 @@ impl<M: Middleware> Router<M> {
     ///
     /// [UniswapV2Router]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/UniswapV2Router01.sol
     /// [Dex]: crate::Dex
-    pub fn remove_liquidity(
+    pub async fn remove_liquidity(
         &self,
         token_a: Address,
         token_b: Address,
         liquidity: U256,
         amount_a_min: U256,
         amount_b_min: U256,
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
     ) -> Result<ContractCall<M, (U256, U256)>> {
+        let deadline = deadline.into().resolve(self.client().as_ref()).await?;
+        let to = to.into().resolve(self.client().as_ref()).await?;
         let router = self.contract();
         let (native_a, native_b) = is_native_path(&[token_a, token_b]);
 
@@ -163,99 +259,1665 @@ impl<M: Middleware> Router<M> {
         Ok(call)
     }
 
+    /// Returns the contract call for the router's `addLiquidityETH` method, with `value` set to
+    /// `amount_eth_desired`.
+    pub fn add_liquidity_eth(
+        &self,
+        token: Address,
+        amount_token_desired: U256,
+        amount_eth_desired: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+    ) -> Result<ContractCall<M, (U256, U256, U256)>> {
+        if is_native(&token) {
+            return Err(Error::IdenticalAddresses);
+        }
+        let call = self
+            .contract()
+            .add_liquidity_eth(
+                token,
+                amount_token_desired,
+                amount_token_min,
+                amount_eth_min,
+                to,
+                deadline,
+            )
+            .value(amount_eth_desired);
+        Ok(call)
+    }
+
+    /// Returns the contract call for the router's `removeLiquidityETH` method.
+    pub fn remove_liquidity_eth(
+        &self,
+        token: Address,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        if is_native(&token) {
+            return Err(Error::IdenticalAddresses);
+        }
+        let call = self.contract().remove_liquidity_eth(
+            token,
+            liquidity,
+            amount_token_min,
+            amount_eth_min,
+            to,
+            deadline,
+        );
+        Ok(call)
+    }
+
+    /// Returns the contract call for the router's
+    /// `removeLiquidityETHSupportingFeeOnTransferTokens` method, which returns only the amount of
+    /// ETH received.
+    pub fn remove_liquidity_eth_supporting_fee_on_transfer_tokens(
+        &self,
+        token: Address,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+    ) -> Result<ContractCall<M, U256>> {
+        if is_native(&token) {
+            return Err(Error::IdenticalAddresses);
+        }
+        let call = self.contract().remove_liquidity_eth_supporting_fee_on_transfer_tokens(
+            token,
+            liquidity,
+            amount_token_min,
+            amount_eth_min,
+            to,
+            deadline,
+        );
+        Ok(call)
+    }
+
+    /// Returns the contract call for the router's `removeLiquidityWithPermit` method, approving the
+    /// router to transfer the liquidity tokens with the `signature` of the pair's [Permit] instead
+    /// of a separate `approve` transaction.
+    ///
+    /// `deadline` must be the same as the signed permit's, and `approve_max` whether the permit's
+    /// value is [`U256::MAX`] rather than `liquidity`.
+    ///
+    /// [Permit]: crate::Permit
+    pub fn remove_liquidity_with_permit(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        liquidity: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Address,
+        deadline: U256,
+        approve_max: bool,
+        signature: &Signature,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        if is_native(&token_a) || is_native(&token_b) {
+            return Err(Error::IdenticalAddresses);
+        }
+        let (v, r, s) = split_signature(signature);
+        let call = self.contract().remove_liquidity_with_permit(
+            token_a,
+            token_b,
+            liquidity,
+            amount_a_min,
+            amount_b_min,
+            to,
+            deadline,
+            approve_max,
+            v,
+            r,
+            s,
+        );
+        Ok(call)
+    }
+
+    /// Returns the contract call for the router's `removeLiquidityETHWithPermit` method. See
+    /// [`remove_liquidity_with_permit`][Self::remove_liquidity_with_permit] for more details.
+    pub fn remove_liquidity_eth_with_permit(
+        &self,
+        token: Address,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_eth_min: U256,
+        to: Address,
+        deadline: U256,
+        approve_max: bool,
+        signature: &Signature,
+    ) -> Result<ContractCall<M, (U256, U256)>> {
+        if is_native(&token) {
+            return Err(Error::IdenticalAddresses);
+        }
+        let (v, r, s) = split_signature(signature);
+        let call = self.contract().remove_liquidity_eth_with_permit(
+            token,
+            liquidity,
+            amount_token_min,
+            amount_eth_min,
+            to,
+            deadline,
+            approve_max,
+            v,
+            r,
+            s,
+        );
+        Ok(call)
+    }
+
     /// Generalized swap function for the various [UniswapV2Router] `swap[Exact]XFor[Exact]Y`.
     /// Returns the contract call with the necessary parameters set (value, calldata).
     ///
     /// See documentation of [Dex] for more details on arguments.
     ///
+    /// Use [`swap_supporting_fee_on_transfer`][Self::swap_supporting_fee_on_transfer] for tokens
+    /// which take a fee on transfer.
+    ///
+    /// When [`options.max_price_impact_bps`][SwapOptions::max_price_impact_bps] is set, returns
+    /// [`Error::PriceImpactTooHigh`] if the quoted [Trade]'s price impact is greater than it.
+    ///
+    /// [`NATIVE_ADDRESS`][crate::NATIVE_ADDRESS] at the start or end of `path` selects the `ETH`
+    /// router methods, setting the call's `value`, and is replaced with `weth` for quoting.
+    /// Returns [`Error::NativeMidPath`] if it is anywhere else in the path. Use `weth` instead to
+    /// swap from or to the wrapped token.
+    ///
+    /// For exact output swaps from the native token, `amountInMax` is always quoted from the
+    /// reserves and sent as the call's `value`, with the router refunding any unspent amount.
+    /// Returns [`Error::InvalidSlippage`] if the slippage is 100%, since the value would be
+    /// unbounded.
+    ///
+    /// [`Amount::PercentOfBalance`] is resolved against the balance of the client's default sender.
+    /// See [`Amount::resolve`].
+    ///
+    /// For Solidly forks, the router is expected to take a `Route { from, to, stable }` array
+    /// instead of the path, with each route using its flag from
+    /// [`options.stable`][SwapOptions::stable], or the factory's [stable][ProtocolType::stable]
+    /// flag by default. Returns [`Error::InvalidPath`] if the number of flags does not match the
+    /// path, and [`Error::ExactOutUnsupported`] for exact output swaps.
+    ///
     /// Note: this function does not perform many sanity checks and it should be called by using the
     /// [Dex] struct.
     ///
     /// [UniswapV2Router]: https://github.com/Uniswap/v2-periphery/blob/master/contracts/UniswapV2Router01.sol
     /// [Dex]: crate::Dex
+    /// [ProtocolType::stable]: crate::ProtocolType::stable
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(router = ?self.address(), ?path))
+    )]
     pub async fn swap(
         &self,
         factory: &Factory<M>,
         amount: Amount,
-        slippage_tolerance: f32,
+        slippage_tolerance: impl Into<Slippage>,
         path: &[Address],
-        to: Address,
-        deadline: U256,
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
         weth: Address,
+        options: SwapOptions,
     ) -> Result<ContractCall<M, Vec<U256>>> {
-        let router = self.contract();
-        let (from_native, to_native) = is_native_path(path);
+        self.build_swap(
+            factory,
+            amount,
+            slippage_tolerance,
+            path,
+            to,
+            deadline,
+            weth,
+            options,
+            false,
+        )
+        .await
+    }
+
+    /// Like [`swap`][Self::swap], but uses the router's `SupportingFeeOnTransferTokens` swap
+    /// methods.
+    ///
+    /// These are required when swapping tokens that take a fee on transfer, since the standard
+    /// methods revert when the received amount is less than the quoted one. They only exist for
+    /// exact input swaps, so this returns [`Error::FeeOnTransferExactOut`] for exact output ones,
+    /// and they have no return values.
+    pub async fn swap_supporting_fee_on_transfer(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &[Address],
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Address,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, ()>> {
+        self.build_swap(
+            factory,
+            amount,
+            slippage_tolerance,
+            path,
+            to,
+            deadline,
+            weth,
+            options,
+            true,
+        )
+        .await
+    }
+
+    /// Builds the call of [`swap`][Self::swap], or of
+    /// [`swap_supporting_fee_on_transfer`][Self::swap_supporting_fee_on_transfer] if
+    /// `fee_on_transfer` is set.
+    async fn build_swap<D: Detokenize>(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &[Address],
+        to: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Address,
+        options: SwapOptions,
+        fee_on_transfer: bool,
+    ) -> Result<ContractCall<M, D>> {
+        let slippage = slippage_tolerance.into().validate()?;
+        let deadline = deadline.into().resolve(self.client().as_ref()).await?;
+        let to = to.into().resolve(self.client().as_ref()).await?;
+        let solidly = factory.protocol().is_solidly();
+        let path = swap_path(amount, path, weth, solidly, fee_on_transfer)?;
+        let stable = hop_stable(factory, &path, options.stable.as_deref())?;
+        let stable = stable.as_deref();
+        let amount = amount.resolve(self.client(), &path).await?;
+        debug!(?amount, "resolved the amount");
+        let (path, native) = map_swap_path(amount, slippage, path, weth)?;
+
+        let trade = if slippage.is_max() && options.max_price_impact_bps.is_none() {
+            None
+        } else {
+            let reserves = self.get_reserves(factory, &path, &options, stable).await?;
+            let trade = self.trade(factory, amount, &path, &reserves, stable).await?;
+            debug!(
+                amounts = ?trade.amounts,
+                price_impact_bps = trade.price_impact_bps,
+                "quoted the trade"
+            );
+            if let Some(max_price_impact_bps) = options.max_price_impact_bps {
+                trade.check_price_impact(max_price_impact_bps)?;
+            }
+            Some(trade)
+        };
+
+        let call = swap_call(
+            amount,
+            slippage,
+            trade,
+            path,
+            to,
+            deadline,
+            native,
+            stable,
+            fee_on_transfer,
+        )?;
+        let call = match stable {
+            Some(_) => call.build(&ISolidlyRouter::new(self.address(), self.client()))?,
+            None => call.build(self.contract())?,
+        };
+        debug!(
+            method = %call.function.name,
+            selector = %ethers_core::utils::hex::encode(call.function.short_signature()),
+            "built the swap call"
+        );
+        let call = match options.gas_margin_bps {
+            Some(margin_bps) => {
+                gas::estimate(self.client().as_ref(), &call, margin_bps).await?.apply(call)
+            }
+            None => call,
+        };
+        options.tx.apply(call)
+    }
+
+    /// Quotes a swap of `amount` through `path`, returning the resulting [Trade] with its execution
+    /// price, mid price and price impact.
+    ///
+    /// The native token address in `path` is replaced with `weth`.
+    pub async fn quote(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        path: &[Address],
+        weth: Address,
+    ) -> Result<Trade> {
+        self.quote_hops(factory, amount, path, None, weth).await
+    }
+
+    /// Quotes a swap of `amount` through `path` of a Solidly fork, where `stable` is whether each
+    /// pair is stable.
+    ///
+    /// See [`quote`][Self::quote] for more details.
+    pub async fn quote_solidly(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        path: &[Address],
+        stable: &[bool],
+        weth: Address,
+    ) -> Result<Trade> {
+        if !factory.protocol().is_solidly() {
+            return Err(Error::UnsupportedProtocol);
+        }
+        self.quote_hops(factory, amount, path, Some(stable), weth).await
+    }
+
+    /// Quotes a swap through `path`, with the `stable` flags of a Solidly swap if set, otherwise
+    /// the factory's.
+    pub(crate) async fn quote_hops(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        path: &[Address],
+        stable: Option<&[bool]>,
+        weth: Address,
+    ) -> Result<Trade> {
         let mut path = path.to_vec();
         map_native(&mut path, weth);
-        let call = match amount {
-            Amount::ExactIn(amount_in) => {
-                let amount_out_min = if slippage_tolerance == 100.0 {
-                    U256::zero()
-                } else {
-                    let last_amount_out = *Library::get_amounts_out(factory, amount_in, &path)
-                        .await?
-                        .last()
-                        .expect("path is empty");
-                    if slippage_tolerance == 0.0 {
-                        last_amount_out
-                    } else {
-                        let mult = 100.0 - slippage_tolerance;
-                        let mult_bps = U256::from((mult * 100.0) as u32);
-                        (last_amount_out * mult_bps) / BPS_U256
-                    }
+        let stable = hop_stable(factory, &path, stable)?;
+        let reserves = self.get_reserves_checked(factory, &path, stable.as_deref()).await?;
+        self.trade(factory, amount, &path, &reserves, stable.as_deref()).await
+    }
+
+    /// Parses the actual amounts of a swap, in the same format as the router's return value, from
+    /// the pair `Swap` events in its transaction `receipt`.
+    ///
+    /// The first amount is the input of the first swap, e.g. the amount of ETH actually spent in an
+    /// exact output swap, followed by the output of each swap. Returns [`Error::NoSwapEvents`] if
+    /// the receipt does not contain any.
+    pub fn parse_swap_receipt(receipt: &TransactionReceipt) -> Result<Vec<U256>> {
+        let swaps: Vec<_> = receipt
+            .logs
+            .iter()
+            .filter_map(|log| {
+                SwapFilter::decode_log(&(log.topics.clone(), log.data.to_vec()).into()).ok()
+            })
+            .collect();
+        let first = swaps.first().ok_or(Error::NoSwapEvents)?;
+
+        let mut amounts = Vec::with_capacity(swaps.len() + 1);
+        amounts.push(first.amount_0_in + first.amount_1_in);
+        amounts.extend(swaps.iter().map(|swap| swap.amount_0_out + swap.amount_1_out));
+        Ok(amounts)
+    }
+
+    /// Finds the path with the best output for `amount` between `token_in` and `token_out`.
+    ///
+    /// Checks the direct pair and the routes through the router's [base tokens][Self::base_tokens]
+    /// of up to `max_hops` pairs, capped at 3. Pairs which have not been created or have no
+    /// reserves are skipped. Returns [`Error::NoRouteFound`] if no route has enough liquidity.
+    ///
+    /// Returns [`Error::UnsupportedProtocol`] for Solidly forks, since a path alone does not
+    /// select their pairs. Use [`find_route_solidly`][Self::find_route_solidly] instead.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(?token_in, ?token_out))
+    )]
+    pub async fn find_route(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        token_in: Address,
+        token_out: Address,
+        max_hops: usize,
+    ) -> Result<Vec<Address>> {
+        if factory.protocol().is_solidly() {
+            return Err(Error::UnsupportedProtocol);
+        }
+        if let Amount::PercentOfBalance { .. } = amount {
+            return Err(Error::UnresolvedAmount);
+        }
+        if token_in == token_out {
+            return Err(Error::SwapToSelf);
+        }
+
+        let paths = route_paths(token_in, token_out, &self.base_tokens, max_hops);
+
+        // fetch the sorted reserves of all the unique, existing pairs
+        let mut pairs: Vec<_> = paths
+            .iter()
+            .flat_map(|path| path.windows(2).map(|w| Library::sort_tokens(w[0], w[1])))
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        let addresses = factory.get_pairs_multi(&pairs).await?;
+        pairs = pairs
+            .into_iter()
+            .zip(addresses)
+            .filter(|(_, a)| !a.is_zero())
+            .map(|(p, _)| p)
+            .collect();
+        let reserves = factory.get_reserves_multi(&pairs).await?;
+        let reserves: HashMap<_, _> = pairs
+            .into_iter()
+            .zip(reserves)
+            .filter(|(_, (r0, r1, _))| !r0.is_zero() && !r1.is_zero())
+            .map(|(pair, (r0, r1, _))| (pair, (r0, r1)))
+            .collect();
+
+        let trades = paths.into_iter().filter_map(|path| {
+            let path_reserves = path
+                .windows(2)
+                .map(|w| {
+                    let (r0, r1) = *reserves.get(&Library::sort_tokens(w[0], w[1]))?;
+                    Some(if w[0] < w[1] { (r0, r1) } else { (r1, r0) })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let trade = Trade::new(amount, &path_reserves, self.swap_fee_bps(factory)).ok()?;
+            Some((path, trade))
+        });
+        best_route(amount, trades)
+    }
+
+    /// Finds the path with the best output for `amount` between `token_in` and `token_out` through
+    /// the pairs of a Solidly fork, returning it with whether each of its pairs is stable.
+    ///
+    /// Like [`find_route`][Self::find_route], but both the stable and the volatile pair of each
+    /// hop are considered, so that the route can mix them. Returns
+    /// [`Error::UnsupportedProtocol`] for other protocols, and [`Error::ExactOutUnsupported`] for
+    /// exact output amounts.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(?token_in, ?token_out))
+    )]
+    pub async fn find_route_solidly(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        token_in: Address,
+        token_out: Address,
+        max_hops: usize,
+    ) -> Result<(Vec<Address>, Vec<bool>)> {
+        if !factory.protocol().is_solidly() {
+            return Err(Error::UnsupportedProtocol);
+        }
+        if let Amount::PercentOfBalance { .. } = amount {
+            return Err(Error::UnresolvedAmount);
+        }
+        if !amount.is_exact_in() {
+            return Err(Error::ExactOutUnsupported);
+        }
+        if token_in == token_out {
+            return Err(Error::SwapToSelf);
+        }
+
+        let paths = route_paths(token_in, token_out, &self.base_tokens, max_hops);
+
+        // fetch the sorted reserves of both pairs of all the unique token pairs, if they exist
+        let mut pairs: Vec<_> = paths
+            .iter()
+            .flat_map(|path| path.windows(2).map(|w| Library::sort_tokens(w[0], w[1])))
+            .flat_map(|(a, b)| [(a, b, false), (a, b, true)])
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        let addresses = factory.get_pairs_multi_solidly(&pairs).await?;
+        pairs = pairs
+            .into_iter()
+            .zip(addresses)
+            .filter(|(_, a)| !a.is_zero())
+            .map(|(p, _)| p)
+            .collect();
+        let reserves = factory.get_reserves_multi_solidly(&pairs).await?;
+        let reserves: HashMap<_, _> = pairs
+            .into_iter()
+            .zip(reserves)
+            .filter(|(_, (r0, r1, _))| !r0.is_zero() && !r1.is_zero())
+            .map(|(pair, (r0, r1, _))| (pair, (r0, r1)))
+            .collect();
+
+        // the decimals are only needed to quote stable pairs
+        let decimals: HashMap<_, _> = if reserves.keys().any(|&(_, _, stable)| stable) {
+            let mut tokens: Vec<_> = paths.iter().flatten().copied().collect();
+            tokens.sort_unstable();
+            tokens.dedup();
+            let decimals = factory.get_decimals_multi(&tokens).await?;
+            tokens.into_iter().zip(decimals).collect()
+        } else {
+            HashMap::new()
+        };
+
+        let fee_bps = self.swap_fee_bps(factory);
+        let trades = paths.into_iter().flat_map(|path| {
+            let hops = path.len() - 1;
+            let path_decimals =
+                path.iter().map(|token| decimals.get(token).copied()).collect::<Option<Vec<_>>>();
+            let reserves = &reserves;
+            (0..1u32 << hops).filter_map(move |mask| {
+                let stable: Vec<_> = (0..hops).map(|i| mask & (1 << i) != 0).collect();
+                let path_reserves = path
+                    .windows(2)
+                    .zip(&stable)
+                    .map(|(w, &s)| {
+                        let (a, b) = Library::sort_tokens(w[0], w[1]);
+                        let (r0, r1) = *reserves.get(&(a, b, s))?;
+                        Some(if w[0] < w[1] { (r0, r1) } else { (r1, r0) })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let trade = Trade::new_solidly(
+                    amount,
+                    &path_reserves,
+                    &stable,
+                    path_decimals.as_deref(),
+                    fee_bps,
+                )
+                .ok()?;
+                Some(((path.clone(), stable), trade))
+            })
+        });
+        best_route(amount, trades)
+    }
+
+    /// Quotes a [Trade] from the already fetched `reserves` of the pairs in `path`, using the
+    /// invariant of the factory's protocol. Fetches the tokens' decimals for Solidly stable pairs.
+    ///
+    /// `stable` is whether each pair of a Solidly swap is stable, otherwise the factory's flag.
+    pub(crate) async fn trade(
+        &self,
+        factory: &Factory<M>,
+        amount: Amount,
+        path: &[Address],
+        reserves: &[(U256, U256)],
+        stable: Option<&[bool]>,
+    ) -> Result<Trade> {
+        let fee_bps = self.swap_fee_bps(factory);
+        match hop_stable(factory, path, stable)? {
+            Some(stable) => {
+                let decimals = match stable.contains(&true) {
+                    true => Some(factory.get_decimals_multi(path).await?),
+                    false => None,
                 };
+                Trade::new_solidly(amount, reserves, &stable, decimals.as_deref(), fee_bps)
+            }
+            None => Trade::new(amount, reserves, fee_bps),
+        }
+    }
+
+    /// Returns the reserves provided in `options`, otherwise fetches them from the chain.
+    async fn get_reserves(
+        &self,
+        factory: &Factory<M>,
+        path: &[Address],
+        options: &SwapOptions,
+        stable: Option<&[bool]>,
+    ) -> Result<Vec<(U256, U256)>> {
+        match &options.reserves {
+            Some(reserves) if reserves.len() + 1 == path.len() => {
+                debug!(?reserves, "using the provided reserves");
+                Ok(reserves.clone())
+            }
+            Some(_) => Err(Error::InvalidReserves),
+            None => self.get_reserves_checked(factory, path, stable).await,
+        }
+    }
+
+    /// Fetches the reserves of the pairs in `path`, or returns them from the quote cache if they
+    /// are all cached. If that fails, returns [`Error::PairNotDeployed`] for the first pair which
+    /// has not been deployed, if any.
+    ///
+    /// `stable` is whether each pair of a Solidly swap is stable, as returned by [hop_stable].
+    async fn get_reserves_checked(
+        &self,
+        factory: &Factory<M>,
+        path: &[Address],
+        stable: Option<&[bool]>,
+    ) -> Result<Vec<(U256, U256)>> {
+        let cache = self.quote_cache.as_ref().filter(|_| path.len() >= 2);
+        if let Some((chain, cache)) = cache {
+            if let Some(reserves) = cached_reserves(cache, *chain, factory, path, stable) {
+                debug!(?reserves, "using the cached reserves");
+                return Ok(reserves);
+            }
+        }
+        let res = match stable {
+            Some(stable) => Library::get_reserves_multi_solidly(factory, path, stable).await,
+            None => Library::get_reserves_multi(factory, path).await,
+        };
+        let err = match res {
+            Ok(reserves) => {
+                if let Some((chain, cache)) = cache {
+                    cache_reserves(cache, *chain, factory, path, stable, &reserves);
+                }
+                return Ok(reserves);
+            }
+            Err(err) => err,
+        };
+        for (i, pair) in path.windows(2).enumerate() {
+            let (token_a, token_b) = (pair[0], pair[1]);
+            let stable = stable.map(|stable| stable[i]);
+            if let Ok(false) = factory.pair_exists_hop(token_a, token_b, stable).await {
+                return Err(Error::PairNotDeployed { token_a, token_b });
+            }
+        }
+        Err(err)
+    }
+}
+
+/// Returns whether each pair in `path` is stable for Solidly forks: `stable` if it is set,
+/// otherwise the factory's flag. Returns None for other protocols, and [`Error::InvalidPath`] if
+/// the number of flags does not match the path.
+fn hop_stable<M: Middleware>(
+    factory: &Factory<M>,
+    path: &[Address],
+    stable: Option<&[bool]>,
+) -> Result<Option<Vec<bool>>> {
+    let hops = path.len().saturating_sub(1);
+    match (factory.protocol().stable(), stable) {
+        (None, _) => Ok(None),
+        (Some(_), Some(stable)) if stable.len() != hops => Err(Error::InvalidPath),
+        (Some(_), Some(stable)) => Ok(Some(stable.to_vec())),
+        (Some(default), None) => Ok(Some(vec![default; hops])),
+    }
+}
+
+/// Returns the reserves of the pairs in `path` from `cache`, sorted in the order of the path, if
+/// they are all cached.
+fn cached_reserves<M: Middleware>(
+    cache: &QuoteCache,
+    chain: Chain,
+    factory: &Factory<M>,
+    path: &[Address],
+    stable: Option<&[bool]>,
+) -> Option<Vec<(U256, U256)>> {
+    path.windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            let pair = Library::pair_for_hop(factory, w[0], w[1], stable.map(|s| s[i]));
+            let (reserve0, reserve1) = cache.reserves(chain, pair)?;
+            Some(if w[0] < w[1] { (reserve0, reserve1) } else { (reserve1, reserve0) })
+        })
+        .collect()
+}
 
+/// Caches the `reserves` of the pairs in `path`, given in the order of the path, sorted in the
+/// order of each pair's tokens.
+fn cache_reserves<M: Middleware>(
+    cache: &QuoteCache,
+    chain: Chain,
+    factory: &Factory<M>,
+    path: &[Address],
+    stable: Option<&[bool]>,
+    reserves: &[(U256, U256)],
+) {
+    for (i, (w, &(reserve_a, reserve_b))) in path.windows(2).zip(reserves).enumerate() {
+        let reserves = if w[0] < w[1] { (reserve_a, reserve_b) } else { (reserve_b, reserve_a) };
+        let pair = Library::pair_for_hop(factory, w[0], w[1], stable.map(|s| s[i]));
+        cache.insert_reserves(chain, pair, reserves);
+    }
+}
+
+/// Checks the parameters of [`Router::swap`] which do not need the chain. Returns its path, with
+/// the native token first for [`Amount::ExactInNative`].
+fn swap_path(
+    amount: Amount,
+    path: &[Address],
+    weth: Address,
+    solidly: bool,
+    fee_on_transfer: bool,
+) -> Result<Vec<Address>> {
+    if fee_on_transfer && !amount.is_exact_in() {
+        return Err(Error::FeeOnTransferExactOut);
+    }
+    if solidly && !amount.is_exact_in() {
+        return Err(Error::ExactOutUnsupported);
+    }
+
+    if is_native_mid_path(path) {
+        return Err(Error::NativeMidPath);
+    }
+
+    let mut path = path.to_vec();
+    if let Amount::ExactInNative(_) = amount {
+        match path.first() {
+            Some(token) if is_native(token) || *token == weth => path[0] = NATIVE_ADDRESS,
+            _ => return Err(Error::InvalidPath),
+        }
+    }
+    Ok(path)
+}
+
+/// Replaces the native token in `path` with `weth`, returning whether it was swapped from and to.
+fn map_swap_path(
+    amount: Amount,
+    slippage: Slippage,
+    mut path: Vec<Address>,
+    weth: Address,
+) -> Result<(Vec<Address>, (bool, bool))> {
+    let (from_native, to_native) = is_native_path(&path);
+    map_native(&mut path, weth);
+
+    let exact_out_native = from_native && !amount.is_exact_in();
+    if exact_out_native && slippage.is_max() {
+        return Err(Error::InvalidSlippage);
+    }
+    Ok((path, (from_native, to_native)))
+}
+
+/// Returns the router method call of [`Router::swap`], with the limit amount from `trade`.
+fn swap_call(
+    amount: Amount,
+    slippage: Slippage,
+    trade: Option<Trade>,
+    path: Vec<Address>,
+    to: Address,
+    deadline: U256,
+    (from_native, to_native): (bool, bool),
+    stable: Option<&[bool]>,
+    fee_on_transfer: bool,
+) -> Result<MethodCall> {
+    let call = match amount {
+        Amount::ExactIn(amount_in) => {
+            let amount_out_min = match trade {
+                Some(trade) => slippage.minimum_amount_out(trade.amount_out()),
+                None => U256::zero(),
+            };
+            debug!(%amount_in, %amount_out_min, "computed the minimum output");
+
+            if let Some(stable) = stable {
+                swap_exact_in_solidly(
+                    stable,
+                    amount_in,
+                    amount_out_min,
+                    &path,
+                    to,
+                    deadline,
+                    (from_native, to_native),
+                    fee_on_transfer,
+                )
+            } else if fee_on_transfer {
+                // these methods have no outputs
                 if from_native {
-                    router
-                        .swap_exact_eth_for_tokens(amount_out_min, path, to, deadline)
-                        .value(amount_in)
+                    MethodCall::new(SwapExactETHForTokensSupportingFeeOnTransferTokensCall {
+                        amount_out_min,
+                        path,
+                        to,
+                        deadline,
+                    })
+                    .value(amount_in)
                 } else if to_native {
-                    router.swap_exact_tokens_for_eth(amount_in, amount_out_min, path, to, deadline)
-                } else {
-                    router.swap_exact_tokens_for_tokens(
+                    MethodCall::new(SwapExactTokensForETHSupportingFeeOnTransferTokensCall {
                         amount_in,
                         amount_out_min,
                         path,
                         to,
                         deadline,
-                    )
-                }
-            }
-            Amount::ExactOut(amount_out) => {
-                let amount_in_max = if slippage_tolerance == 100.0 {
-                    U256::MAX
-                } else {
-                    let first_amount_in = *Library::get_amounts_in(factory, amount_out, &path)
-                        .await?
-                        .first()
-                        .expect("path is empty");
-                    if slippage_tolerance == 0.0 {
-                        first_amount_in
-                    } else {
-                        let mult = 1.0 / (100.0 - slippage_tolerance);
-                        let mult_bps = U256::from((mult * 100.0).round() as u32);
-                        (first_amount_in * mult_bps) / BPS_U256
-                    }
-                };
-
-                if from_native {
-                    router
-                        .swap_eth_for_exact_tokens(amount_out, path, to, deadline)
-                        .value(amount_in_max)
-                } else if to_native {
-                    router.swap_tokens_for_exact_eth(amount_out, amount_in_max, path, to, deadline)
+                    })
                 } else {
-                    router.swap_tokens_for_exact_tokens(
-                        amount_out,
-                        amount_in_max,
+                    MethodCall::new(SwapExactTokensForTokensSupportingFeeOnTransferTokensCall {
+                        amount_in,
+                        amount_out_min,
                         path,
                         to,
                         deadline,
-                    )
+                    })
                 }
+            } else if from_native {
+                MethodCall::new(SwapExactETHForTokensCall { amount_out_min, path, to, deadline })
+                    .value(amount_in)
+            } else if to_native {
+                MethodCall::new(SwapExactTokensForETHCall {
+                    amount_in,
+                    amount_out_min,
+                    path,
+                    to,
+                    deadline,
+                })
+            } else {
+                MethodCall::new(SwapExactTokensForTokensCall {
+                    amount_in,
+                    amount_out_min,
+                    path,
+                    to,
+                    deadline,
+                })
+            }
+        }
+        Amount::ExactInNative(_) | Amount::PercentOfBalance { .. } => {
+            return Err(Error::UnresolvedAmount)
+        }
+        Amount::ExactOut(amount_out) => {
+            let amount_in_max = match trade {
+                Some(trade) => slippage.maximum_amount_in(trade.amount_in()),
+                None => U256::MAX,
+            };
+            debug!(%amount_out, %amount_in_max, "computed the maximum input");
+
+            if from_native {
+                MethodCall::new(SwapETHForExactTokensCall { amount_out, path, to, deadline })
+                    .value(amount_in_max)
+            } else if to_native {
+                MethodCall::new(SwapTokensForExactETHCall {
+                    amount_out,
+                    amount_in_max,
+                    path,
+                    to,
+                    deadline,
+                })
+            } else {
+                MethodCall::new(SwapTokensForExactTokensCall {
+                    amount_out,
+                    amount_in_max,
+                    path,
+                    to,
+                    deadline,
+                })
+            }
+        }
+    };
+    Ok(call)
+}
+
+/// Returns the method call for one of the Solidly router's exact input swap methods, routing
+/// through the stable or volatile pair of each hop of `path`, according to `stable`.
+fn swap_exact_in_solidly(
+    stable: &[bool],
+    amount_in: U256,
+    amount_out_min: U256,
+    path: &[Address],
+    to: Address,
+    deadline: U256,
+    (from_native, to_native): (bool, bool),
+    fee_on_transfer: bool,
+) -> MethodCall {
+    use solidly::*;
+
+    let routes: Vec<_> = path
+        .windows(2)
+        .zip(stable)
+        .map(|(w, &stable)| SolidlyRoute { from: w[0], to: w[1], stable })
+        .collect();
+
+    if fee_on_transfer {
+        if from_native {
+            MethodCall::new(SwapExactETHForTokensSupportingFeeOnTransferTokensCall {
+                amount_out_min,
+                routes,
+                to,
+                deadline,
+            })
+            .value(amount_in)
+        } else if to_native {
+            MethodCall::new(SwapExactTokensForETHSupportingFeeOnTransferTokensCall {
+                amount_in,
+                amount_out_min,
+                routes,
+                to,
+                deadline,
+            })
+        } else {
+            MethodCall::new(SwapExactTokensForTokensSupportingFeeOnTransferTokensCall {
+                amount_in,
+                amount_out_min,
+                routes,
+                to,
+                deadline,
+            })
+        }
+    } else if from_native {
+        MethodCall::new(SwapExactETHForTokensCall { amount_out_min, routes, to, deadline })
+            .value(amount_in)
+    } else if to_native {
+        MethodCall::new(SwapExactTokensForETHCall {
+            amount_in,
+            amount_out_min,
+            routes,
+            to,
+            deadline,
+        })
+    } else {
+        MethodCall::new(SwapExactTokensForTokensCall {
+            amount_in,
+            amount_out_min,
+            routes,
+            to,
+            deadline,
+        })
+    }
+}
+
+/// Returns the candidate paths of [`Router::find_route`] from `token_in` to `token_out`, through
+/// the `bases` tokens.
+fn route_paths(
+    token_in: Address,
+    token_out: Address,
+    bases: &[Address],
+    max_hops: usize,
+) -> Vec<Vec<Address>> {
+    let mut bases = bases.to_vec();
+    bases.retain(|&token| token != token_in && token != token_out);
+    bases.sort_unstable();
+    bases.dedup();
+
+    let mut paths = vec![vec![token_in, token_out]];
+    if max_hops >= 2 {
+        paths.extend(bases.iter().map(|&base| vec![token_in, base, token_out]));
+    }
+    if max_hops >= 3 {
+        for &a in &bases {
+            for &b in bases.iter().filter(|&&b| b != a) {
+                paths.push(vec![token_in, a, b, token_out]);
             }
+        }
+    }
+    paths
+}
+
+/// Returns the route of `trades` with the best trade for `amount`.
+fn best_route<R: std::fmt::Debug>(
+    amount: Amount,
+    trades: impl IntoIterator<Item = (R, Trade)>,
+) -> Result<R> {
+    let mut best: Option<(R, Trade)> = None;
+    for (route, trade) in trades {
+        let is_better = match &best {
+            None => true,
+            Some((_, best)) if amount.is_exact_in() => trade.amount_out() > best.amount_out(),
+            Some((_, best)) => trade.amount_in() < best.amount_in(),
         };
+        debug!(?route, amounts = ?trade.amounts, "quoted a route");
+        if is_better {
+            best = Some((route, trade));
+        }
+    }
 
-        Ok(call)
+    let (route, _) = best.ok_or(Error::NoRouteFound)?;
+    info!(?route, "found a route");
+    Ok(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::BPS_U256, contracts::bindings::i_uniswap_v2_router_02::*, testing::MockDex,
+        ProtocolType,
+    };
+    use ethers_contract::EthCall;
+    use ethers_core::{
+        abi::{self, AbiDecode, Token},
+        types::Bytes,
+    };
+    use ethers_providers::Provider;
+
+    type M = Provider<MockDex>;
+
+    const TOKEN_A: Address = Address::repeat_byte(0x0a);
+    const TOKEN_B: Address = Address::repeat_byte(0x0b);
+    const WETH: Address = Address::repeat_byte(0xaa);
+
+    fn setup() -> (Factory<M>, Router<M>, MockDex) {
+        let (provider, mock) = MockDex::mocked();
+        let client = Arc::new(provider);
+        let factory =
+            Factory::new(client.clone(), Address::repeat_byte(0x11), ProtocolType::UniswapV2);
+        let router = Router::new(client, Address::repeat_byte(0x22));
+        (factory, router, mock)
+    }
+
+    fn push_reserves(mock: &MockDex, reserve_0: U256, reserve_1: U256) {
+        let data =
+            abi::encode(&[Token::Uint(reserve_0), Token::Uint(reserve_1), Token::Uint(0.into())]);
+        mock.push::<Bytes, _>(Bytes::from(data)).unwrap();
+    }
+
+    async fn swap(
+        (factory, router): (&Factory<M>, &Router<M>),
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &[Address],
+        options: SwapOptions,
+    ) -> Result<Bytes> {
+        let call = router
+            .swap(factory, amount, slippage_tolerance, path, TOKEN_B, U256::MAX, WETH, options)
+            .await?;
+        Ok(call.calldata().unwrap())
+    }
+
+    async fn swap_fee_on_transfer(
+        (factory, router): (&Factory<M>, &Router<M>),
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &[Address],
+    ) -> Result<Bytes> {
+        let call: ContractCall<M, ()> = router
+            .swap_supporting_fee_on_transfer(
+                factory,
+                amount,
+                slippage_tolerance,
+                path,
+                TOKEN_B,
+                U256::MAX,
+                WETH,
+                SwapOptions::new(),
+            )
+            .await?;
+        Ok(call.calldata().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_fee_on_transfer_selectors() {
+        let (factory, router, _mock) = setup();
+        let amount = Amount::ExactIn(U256::exp10(18));
+
+        let cases = [
+            (
+                [TOKEN_A, TOKEN_B],
+                SwapExactTokensForTokensSupportingFeeOnTransferTokensCall::selector(),
+            ),
+            (
+                [NATIVE_ADDRESS, TOKEN_B],
+                SwapExactETHForTokensSupportingFeeOnTransferTokensCall::selector(),
+            ),
+            (
+                [TOKEN_A, NATIVE_ADDRESS],
+                SwapExactTokensForETHSupportingFeeOnTransferTokensCall::selector(),
+            ),
+        ];
+        for (path, selector) in cases {
+            let calldata =
+                swap_fee_on_transfer((&factory, &router), amount, 100.0, &path).await.unwrap();
+            assert_eq!(calldata[..4], selector);
+        }
+
+        let calldata =
+            swap((&factory, &router), amount, 100.0, &[TOKEN_A, TOKEN_B], SwapOptions::new())
+                .await
+                .unwrap();
+        assert_eq!(calldata[..4], SwapExactTokensForTokensCall::selector());
+    }
+
+    #[tokio::test]
+    async fn test_fee_on_transfer_slippage() {
+        let (factory, router, mock) = setup();
+        let amount_in = U256::exp10(18);
+        let (reserve_a, reserve_b) = (U256::exp10(21), U256::exp10(24));
+        mock.mock_pair(&factory, TOKEN_A, TOKEN_B, reserve_a, reserve_b);
+
+        let amount = Amount::ExactIn(amount_in);
+        let calldata = swap_fee_on_transfer((&factory, &router), amount, 1.0, &[TOKEN_A, TOKEN_B])
+            .await
+            .unwrap();
+        let call =
+            SwapExactTokensForTokensSupportingFeeOnTransferTokensCall::decode(calldata).unwrap();
+
+        let amount_out = Library::get_amount_out(amount_in, reserve_a, reserve_b).unwrap();
+        assert_eq!(call.amount_in, amount_in);
+        assert_eq!(call.amount_out_min, amount_out * 9900 / BPS_U256);
+        assert_eq!(call.path, vec![TOKEN_A, TOKEN_B]);
+    }
+
+    #[tokio::test]
+    async fn test_swap_pair_not_deployed() {
+        let (factory, router, mock) = setup();
+        // LIFO: the failed `getReserves` call, then the `getCode` check
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let res =
+            swap((&factory, &router), amount, 1.0, &[TOKEN_A, TOKEN_B], SwapOptions::new()).await;
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, Error::PairNotDeployed { token_a, token_b } if (token_a, token_b) == (TOKEN_A, TOKEN_B))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fee_on_transfer_exact_out() {
+        let (factory, router, _mock) = setup();
+        let amount = Amount::ExactOut(U256::exp10(18));
+        let res = swap_fee_on_transfer((&factory, &router), amount, 1.0, &[TOKEN_A, TOKEN_B]).await;
+        assert!(matches!(res.unwrap_err(), Error::FeeOnTransferExactOut));
+    }
+
+    #[tokio::test]
+    async fn test_swap_quote_cache() {
+        let (factory, mut router, mock) = setup();
+        let cache = QuoteCache::new(16);
+        router.set_quote_cache(Chain::Mainnet, cache.clone());
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let (reserve_a, reserve_b) = (U256::exp10(21), U256::exp10(24));
+        push_reserves(&mock, reserve_a, reserve_b);
+
+        let path = [TOKEN_A, TOKEN_B];
+        let calldata = swap((&factory, &router), amount, 1.0, &path, SwapOptions::new());
+        let calldata = calldata.await.unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        // only one response was pushed, so these would fail if any call was made
+        let cached = swap((&factory, &router), amount, 1.0, &path, SwapOptions::new());
+        assert_eq!(cached.await.unwrap(), calldata);
+        let path = [TOKEN_B, TOKEN_A];
+        let calldata = swap((&factory, &router), amount, 1.0, &path, SwapOptions::new());
+        let call = SwapExactTokensForTokensCall::decode(calldata.await.unwrap()).unwrap();
+        let amount_out = Library::get_amount_out(U256::exp10(18), reserve_b, reserve_a).unwrap();
+        assert_eq!(call.amount_out_min, amount_out * 9900 / BPS_U256);
+        assert_eq!((cache.hits(), cache.misses()), (2, 1));
+
+        cache.notify_new_block(1);
+        let res = swap((&factory, &router), amount, 1.0, &path, SwapOptions::new()).await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_swap_with_reserves() {
+        let (factory, router, _mock) = setup();
+        let amount_in = U256::exp10(18);
+        let reserves = vec![(U256::exp10(21), U256::exp10(24)), (U256::exp10(22), U256::exp10(20))];
+        let path = [TOKEN_A, TOKEN_B, WETH];
+
+        // no reserves are pushed to the mock provider, so this would fail if any call was made
+        let options = SwapOptions::new().reserves(reserves.clone());
+        let calldata = swap((&factory, &router), Amount::ExactIn(amount_in), 0.0, &path, options)
+            .await
+            .unwrap();
+        let call = SwapExactTokensForTokensCall::decode(calldata).unwrap();
+        let amounts = Library::get_amounts_out_with_reserves(amount_in, &reserves, 30).unwrap();
+        assert_eq!(call.amount_out_min, amounts[2]);
+
+        let options = SwapOptions::new().reserves(reserves[..1].to_vec());
+        let res = swap((&factory, &router), Amount::ExactIn(amount_in), 0.0, &path, options).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidReserves));
+    }
+
+    #[tokio::test]
+    async fn test_swap_fee_override() {
+        let (mut factory, mut router, _mock) = setup();
+        let amount_in = U256::exp10(18);
+        let reserves = vec![(U256::exp10(21), U256::exp10(24))];
+        let path = [TOKEN_A, TOKEN_B];
+        let amount_out_min = |factory: &Factory<M>, router: &Router<M>| {
+            let options = SwapOptions::new().reserves(reserves.clone());
+            let (factory, router) = (factory.clone(), router.clone());
+            async move {
+                let calldata =
+                    swap((&factory, &router), Amount::ExactIn(amount_in), 0.0, &path, options)
+                        .await
+                        .unwrap();
+                SwapExactTokensForTokensCall::decode(calldata).unwrap().amount_out_min
+            }
+        };
+        let expected = |fee_bps| {
+            Library::get_amounts_out_with_reserves(amount_in, &reserves, fee_bps).unwrap()[1]
+        };
+
+        assert_eq!(router.swap_fee_bps(&factory), 30);
+        assert_eq!(amount_out_min(&factory, &router).await, expected(30));
+
+        factory.set_swap_fee_bps(25).unwrap();
+        assert_eq!(router.swap_fee_bps(&factory), 25);
+        assert_eq!(amount_out_min(&factory, &router).await, expected(25));
+
+        // the router's override takes precedence over the factory's
+        router.set_swap_fee_bps(100).unwrap();
+        assert_eq!(router.swap_fee_bps(&factory), 100);
+        assert_eq!(amount_out_min(&factory, &router).await, expected(100));
+
+        assert!(matches!(router.set_swap_fee_bps(10_001).unwrap_err(), Error::InvalidFee));
+    }
+
+    #[test]
+    fn test_format_amount() {
+        let (_factory, mut router, _mock) = setup();
+        let amount = U256::exp10(17) * 15;
+        assert_eq!(router.format_amount(WETH, amount), None);
+
+        let cache = TokenCache::new();
+        router.set_token_cache(Chain::Mainnet, cache.clone());
+        assert_eq!(router.format_amount(WETH, amount), None);
+
+        let info = crate::tokens::TokenInfo {
+            address: WETH,
+            symbol: Some("WETH".into()),
+            name: Some("Wrapped Ether".into()),
+            decimals: Some(18),
+            is_standard: true,
+        };
+        cache.insert(Chain::Mainnet, info);
+        assert_eq!(router.format_amount(WETH, amount).unwrap(), "1.5 WETH");
+        assert_eq!(router.format_amount(TOKEN_A, amount), None);
+    }
+
+    #[tokio::test]
+    async fn test_swap_solidly() {
+        let (provider, mock) = MockDex::mocked();
+        let client = Arc::new(provider);
+        let router = Router::new(client.clone(), Address::repeat_byte(0x22));
+        let factory = |stable| {
+            let protocol = ProtocolType::Velodrome { stable };
+            Factory::new(client.clone(), Address::repeat_byte(0x11), protocol)
+        };
+        let amount_in = U256::exp10(6);
+        let reserves = vec![(U256::exp10(12), U256::exp10(24))];
+        let options = || SwapOptions::new().reserves(reserves.clone());
+        let path = [TOKEN_A, TOKEN_B];
+        let decode = |calldata: Bytes| {
+            use crate::contracts::bindings::i_solidly_router::SwapExactTokensForTokensCall;
+            SwapExactTokensForTokensCall::decode(calldata).unwrap()
+        };
+
+        let volatile = factory(false);
+        let calldata =
+            swap((&volatile, &router), Amount::ExactIn(amount_in), 0.0, &path, options())
+                .await
+                .unwrap();
+        let call = decode(calldata);
+        assert_eq!(call.routes, vec![SolidlyRoute { from: TOKEN_A, to: TOKEN_B, stable: false }]);
+        let amounts =
+            Library::get_amounts_out_solidly(amount_in, &reserves, &[false], None, 2).unwrap();
+        assert_eq!(call.amount_out_min, amounts[1]);
+
+        // the decimals of the tokens are fetched for stable pairs
+        let stable = factory(true);
+        mock.mock_token(TOKEN_A, "Token A", "A", 6).mock_token(TOKEN_B, "Token B", "B", 18);
+        let calldata = swap((&stable, &router), Amount::ExactIn(amount_in), 0.0, &path, options())
+            .await
+            .unwrap();
+        let call = decode(calldata);
+        assert!(call.routes[0].stable);
+        let decimals = Some(&[6, 18][..]);
+        let amounts =
+            Library::get_amounts_out_solidly(amount_in, &reserves, &[true], decimals, 2).unwrap();
+        assert_eq!(call.amount_out_min, amounts[1]);
+        // less price impact than the volatile curve
+        let volatile_amounts =
+            Library::get_amounts_out_solidly(amount_in, &reserves, &[false], None, 2);
+        assert!(call.amount_out_min > volatile_amounts.unwrap()[1]);
+
+        let amount = Amount::ExactOut(U256::exp10(18));
+        let res = swap((&volatile, &router), amount, 0.0, &path, options()).await;
+        assert!(matches!(res.unwrap_err(), Error::ExactOutUnsupported));
+    }
+
+    #[tokio::test]
+    async fn test_swap_solidly_mixed() {
+        let (provider, mock) = MockDex::mocked();
+        let client = Arc::new(provider);
+        let router = Router::new(client.clone(), Address::repeat_byte(0x22));
+        let protocol = ProtocolType::Velodrome { stable: false };
+        let factory = Factory::new(client, Address::repeat_byte(0x11), protocol);
+        mock.mock_token(TOKEN_A, "Token A", "A", 6).mock_token(TOKEN_B, "Token B", "B", 18);
+        mock.mock_token(WETH, "Wrapped Ether", "WETH", 18);
+
+        // a stable pair followed by a volatile one, whose reserves are fetched from their own
+        // addresses
+        let reserves = vec![(U256::exp10(12), U256::exp10(24)), (U256::exp10(24), U256::exp10(21))];
+        mock.mock_solidly_pair(&factory, TOKEN_A, TOKEN_B, true, reserves[0].0, reserves[0].1);
+        mock.mock_solidly_pair(&factory, TOKEN_B, WETH, false, reserves[1].0, reserves[1].1);
+        let amount_in = U256::exp10(6);
+        let path = [TOKEN_A, TOKEN_B, WETH];
+        let options = SwapOptions::new().stable(vec![true, false]);
+        let calldata =
+            swap((&factory, &router), Amount::ExactIn(amount_in), 0.0, &path, options.clone())
+                .await
+                .unwrap();
+
+        use crate::contracts::bindings::i_solidly_router::SwapExactTokensForTokensCall;
+        let call = SwapExactTokensForTokensCall::decode(calldata).unwrap();
+        assert_eq!(
+            call.routes,
+            vec![
+                SolidlyRoute { from: TOKEN_A, to: TOKEN_B, stable: true },
+                SolidlyRoute { from: TOKEN_B, to: WETH, stable: false },
+            ]
+        );
+        let decimals = Some(&[6, 18, 18][..]);
+        let amounts =
+            Library::get_amounts_out_solidly(amount_in, &reserves, &[true, false], decimals, 2)
+                .unwrap();
+        assert_eq!(call.amount_out_min, amounts[2]);
+
+        let trade = router
+            .quote_solidly(&factory, Amount::ExactIn(amount_in), &path, &[true, false], WETH)
+            .await
+            .unwrap();
+        assert_eq!(trade.amounts, amounts);
+
+        let options = options.stable(vec![true]);
+        let res = swap((&factory, &router), Amount::ExactIn(amount_in), 0.0, &path, options).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+    }
+
+    #[tokio::test]
+    async fn test_max_price_impact() {
+        let (factory, router, mock) = setup();
+        let amount = Amount::ExactIn(U256::exp10(20));
+        let reserves = vec![(U256::exp10(21), U256::exp10(24))];
+        let path = [TOKEN_A, TOKEN_B];
+
+        let options = SwapOptions::new().reserves(reserves.clone()).max_price_impact_bps(100);
+        let res = swap((&factory, &router), amount, 100.0, &path, options).await;
+        assert!(matches!(res.unwrap_err(), Error::PriceImpactTooHigh { actual: 934, max: 100 }));
+
+        let options = SwapOptions::new().reserves(reserves).max_price_impact_bps(1000);
+        let calldata = swap((&factory, &router), amount, 100.0, &path, options).await.unwrap();
+        let call = SwapExactTokensForTokensCall::decode(calldata).unwrap();
+        assert_eq!(call.amount_out_min, U256::zero());
+
+        push_reserves(&mock, U256::zero(), U256::exp10(24));
+        let res = router.quote(&factory, amount, &path, WETH).await;
+        assert!(matches!(res.unwrap_err(), Error::ZeroReserves));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_in_past() {
+        let (factory, router, mock) = setup();
+        mock.set_block(100, 1_000);
+
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let path = [TOKEN_A, TOKEN_B];
+        let res = router
+            .swap(
+                &factory,
+                amount,
+                100.0,
+                &path,
+                TOKEN_B,
+                U256::from(999),
+                WETH,
+                SwapOptions::new(),
+            )
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::DeadlineInPast));
+    }
+
+    #[tokio::test]
+    async fn test_native_path() {
+        let (factory, router, _mock) = setup();
+        let reserves = vec![(U256::exp10(21), U256::exp10(24))];
+        let options = SwapOptions::new().reserves(reserves);
+        let amount_in = U256::exp10(18);
+
+        let call = router
+            .swap(
+                &factory,
+                Amount::ExactIn(amount_in),
+                Slippage::ZERO,
+                &[NATIVE_ADDRESS, TOKEN_B],
+                TOKEN_B,
+                U256::MAX,
+                WETH,
+                options.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(call.tx.value(), Some(&amount_in));
+        let decoded = SwapExactETHForTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.path, vec![WETH, TOKEN_B]);
+
+        let amount_out = U256::exp10(20);
+        let call = router
+            .swap(
+                &factory,
+                Amount::ExactOut(amount_out),
+                Slippage::ZERO,
+                &[NATIVE_ADDRESS, TOKEN_B],
+                TOKEN_B,
+                U256::MAX,
+                WETH,
+                options.clone(),
+            )
+            .await
+            .unwrap();
+        let decoded = SwapETHForExactTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.amount_out, amount_out);
+        let amount_in =
+            Library::get_amount_in(amount_out, U256::exp10(21), U256::exp10(24)).unwrap();
+        assert_eq!(call.tx.value(), Some(&amount_in));
+
+        let calldata = swap(
+            (&factory, &router),
+            Amount::ExactIn(amount_in),
+            Slippage::ZERO,
+            &[TOKEN_A, NATIVE_ADDRESS],
+            options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(calldata[..4], SwapExactTokensForETHCall::selector());
+
+        let path = [TOKEN_A, NATIVE_ADDRESS, TOKEN_B];
+        let res =
+            swap((&factory, &router), Amount::ExactIn(amount_in), 100.0, &path, SwapOptions::new())
+                .await;
+        assert!(matches!(res.unwrap_err(), Error::NativeMidPath));
+    }
+
+    #[tokio::test]
+    async fn test_exact_in_native() {
+        let (factory, router, _mock) = setup();
+        let amount = Amount::ExactInNative(U256::exp10(18));
+        let options = SwapOptions::new().reserves(vec![(U256::exp10(21), U256::exp10(24))]);
+
+        for token_in in [WETH, NATIVE_ADDRESS] {
+            let calldata =
+                swap((&factory, &router), amount, 1.0, &[token_in, TOKEN_B], options.clone())
+                    .await
+                    .unwrap();
+            let decoded = SwapExactETHForTokensCall::decode(calldata).unwrap();
+            assert_eq!(decoded.path, vec![WETH, TOKEN_B]);
+        }
+
+        let res = swap((&factory, &router), amount, 1.0, &[TOKEN_A, TOKEN_B], options).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidPath));
+    }
+
+    #[tokio::test]
+    async fn test_find_route_direct() {
+        let (factory, router, mock) = setup();
+        let amount = Amount::ExactIn(U256::exp10(18));
+        mock.mock_pair(&factory, TOKEN_A, TOKEN_B, U256::exp10(21), U256::exp10(24));
+
+        let path =
+            router.find_route(&factory, amount, TOKEN_B, TOKEN_A, DEFAULT_MAX_HOPS).await.unwrap();
+        assert_eq!(path, vec![TOKEN_B, TOKEN_A]);
+    }
+
+    #[tokio::test]
+    async fn test_find_route_base_token() {
+        let (factory, mut router, mock) = setup();
+        router.set_base_tokens(vec![WETH, TOKEN_A]);
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let liquid = U256::exp10(24);
+
+        // the direct pair has no reserves
+        mock.mock_pair(&factory, TOKEN_A, TOKEN_B, 0.into(), 0.into());
+        mock.mock_pair(&factory, TOKEN_A, WETH, liquid, liquid);
+        mock.mock_pair(&factory, TOKEN_B, WETH, liquid, liquid);
+
+        let path =
+            router.find_route(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS).await.unwrap();
+        assert_eq!(path, vec![TOKEN_A, WETH, TOKEN_B]);
+    }
+
+    #[tokio::test]
+    async fn test_find_route_none() {
+        let (factory, router, mock) = setup();
+        let amount = Amount::ExactOut(U256::exp10(18));
+        mock.mock_get_pair(factory.address(), TOKEN_A, TOKEN_B, Address::zero());
+
+        let res = router.find_route(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS).await;
+        assert!(matches!(res.unwrap_err(), Error::NoRouteFound));
+
+        let res = router.find_route(&factory, amount, TOKEN_A, TOKEN_A, DEFAULT_MAX_HOPS).await;
+        assert!(matches!(res.unwrap_err(), Error::SwapToSelf));
+
+        let res =
+            router.find_route_solidly(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS).await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+    }
+
+    #[tokio::test]
+    async fn test_find_route_solidly() {
+        let (provider, mock) = MockDex::mocked();
+        let client = Arc::new(provider);
+        let mut router = Router::new(client.clone(), Address::repeat_byte(0x22));
+        router.set_base_tokens(vec![WETH]);
+        let protocol = ProtocolType::Velodrome { stable: false };
+        let factory = Factory::new(client, Address::repeat_byte(0x11), protocol);
+        mock.mock_token(TOKEN_A, "Token A", "A", 6).mock_token(TOKEN_B, "Token B", "B", 18);
+        mock.mock_token(WETH, "Wrapped Ether", "WETH", 18);
+        let (e6, e18) =
+            (|x: u64| U256::from(x) * U256::exp10(6), |x| U256::from(x) * U256::exp10(18));
+
+        // the direct volatile pair is shallow, while the stable pair of A and WETH trades ~1:1
+        mock.mock_solidly_pair(&factory, TOKEN_A, TOKEN_B, false, e6(1_000), e18(1_000));
+        mock.mock_get_pair_solidly(factory.address(), TOKEN_A, TOKEN_B, true, Address::zero());
+        mock.mock_solidly_pair(&factory, TOKEN_A, WETH, true, e6(10_000_000), e18(10_000_000));
+        mock.mock_solidly_pair(&factory, TOKEN_A, WETH, false, e6(1_000), e18(1_000));
+        mock.mock_solidly_pair(&factory, WETH, TOKEN_B, false, e18(10_000_000), e18(10_000_000));
+        mock.mock_get_pair_solidly(factory.address(), WETH, TOKEN_B, true, Address::zero());
+
+        let amount = Amount::ExactIn(e6(100));
+        let route = router
+            .find_route_solidly(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS)
+            .await
+            .unwrap();
+        assert_eq!(route, (vec![TOKEN_A, WETH, TOKEN_B], vec![true, false]));
+
+        let res = router.find_route(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS).await;
+        assert!(matches!(res.unwrap_err(), Error::UnsupportedProtocol));
+        let amount = Amount::ExactOut(e18(1));
+        let res =
+            router.find_route_solidly(&factory, amount, TOKEN_A, TOKEN_B, DEFAULT_MAX_HOPS).await;
+        assert!(matches!(res.unwrap_err(), Error::ExactOutUnsupported));
+    }
+
+    #[tokio::test]
+    async fn test_exact_out_native() {
+        let (factory, router, _mock) = setup();
+        let amount_out = U256::exp10(18);
+        let reserves = vec![(U256::exp10(24), U256::exp10(21))];
+        let options = SwapOptions::new().reserves(reserves);
+        let amount = Amount::ExactOut(amount_out);
+        let amount_in =
+            Library::get_amount_in(amount_out, U256::exp10(24), U256::exp10(21)).unwrap();
+
+        let call = router
+            .swap(
+                &factory,
+                amount,
+                1.0,
+                &[NATIVE_ADDRESS, TOKEN_B],
+                TOKEN_B,
+                U256::MAX,
+                WETH,
+                options.clone(),
+            )
+            .await
+            .unwrap();
+        let amount_in_max = Slippage::bps(100).unwrap().maximum_amount_in(amount_in);
+        assert_eq!(call.tx.value(), Some(&amount_in_max));
+        let decoded = SwapETHForExactTokensCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.amount_out, amount_out);
+        assert_eq!(decoded.path, vec![WETH, TOKEN_B]);
+
+        let path = [NATIVE_ADDRESS, TOKEN_B];
+        let res = swap((&factory, &router), amount, 100.0, &path, options.clone()).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSlippage));
+
+        // WETH, not ETH
+        let calldata = swap((&factory, &router), amount, 1.0, &[TOKEN_A, WETH], options.clone())
+            .await
+            .unwrap();
+        let decoded = SwapTokensForExactTokensCall::decode(calldata).unwrap();
+        assert_eq!(decoded.amount_in_max, amount_in_max);
+        assert_eq!(decoded.path, vec![TOKEN_A, WETH]);
+
+        let calldata = swap((&factory, &router), amount, 1.0, &[TOKEN_A, NATIVE_ADDRESS], options)
+            .await
+            .unwrap();
+        let decoded = SwapTokensForExactETHCall::decode(calldata).unwrap();
+        assert_eq!(decoded.path, vec![TOKEN_A, WETH]);
+    }
+
+    #[test]
+    fn test_parse_swap_receipt() {
+        use crate::contracts::bindings::i_uniswap_v2_pair::SwapFilter;
+        use ethers_contract::EthEvent;
+        use ethers_core::types::{Log, H256};
+
+        let swap_log = |amounts: [u64; 4]| Log {
+            topics: vec![SwapFilter::signature(), H256::zero(), H256::zero()],
+            data: abi::encode(&amounts.map(|a| Token::Uint(a.into()))).into(),
+            ..Default::default()
+        };
+        let transfer = Log { topics: vec![H256::repeat_byte(1)], ..Default::default() };
+
+        // ETH -> A -> B, with dust refunded
+        let receipt = TransactionReceipt {
+            logs: vec![transfer.clone(), swap_log([0, 97, 50, 0]), swap_log([50, 0, 0, 10])],
+            ..Default::default()
+        };
+        assert_eq!(
+            Router::<M>::parse_swap_receipt(&receipt).unwrap(),
+            vec![97.into(), 50.into(), 10.into()]
+        );
+
+        let receipt = TransactionReceipt { logs: vec![transfer], ..Default::default() };
+        let res = Router::<M>::parse_swap_receipt(&receipt);
+        assert!(matches!(res.unwrap_err(), Error::NoSwapEvents));
+    }
+
+    #[test]
+    fn test_remove_liquidity_with_permit() {
+        let (_factory, router, _mock) = setup();
+        let signature = Signature { r: 1.into(), s: 2.into(), v: 28 };
+        let liquidity = U256::exp10(18);
+
+        let call = router
+            .remove_liquidity_with_permit(
+                TOKEN_A,
+                TOKEN_B,
+                liquidity,
+                U256::zero(),
+                U256::zero(),
+                TOKEN_B,
+                U256::MAX,
+                false,
+                &signature,
+            )
+            .unwrap();
+        let decoded = RemoveLiquidityWithPermitCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!(decoded.liquidity, liquidity);
+        assert_eq!(decoded.v, 28);
+        assert_eq!(U256::from(decoded.r), 1.into());
+        assert_eq!(U256::from(decoded.s), 2.into());
+
+        let call = router
+            .remove_liquidity_eth_with_permit(
+                TOKEN_A,
+                liquidity,
+                U256::zero(),
+                U256::zero(),
+                TOKEN_B,
+                U256::MAX,
+                true,
+                &signature,
+            )
+            .unwrap();
+        let decoded = RemoveLiquidityETHWithPermitCall::decode(call.calldata().unwrap()).unwrap();
+        assert!(decoded.approve_max);
+        assert_eq!(decoded.token, TOKEN_A);
+
+        let res = router.remove_liquidity_eth_with_permit(
+            NATIVE_ADDRESS,
+            liquidity,
+            U256::zero(),
+            U256::zero(),
+            TOKEN_B,
+            U256::MAX,
+            true,
+            &signature,
+        );
+        assert!(matches!(res.unwrap_err(), Error::IdenticalAddresses));
     }
 }
@@ -0,0 +1,227 @@
+use super::Library;
+use crate::{
+    errors::{Error, Result},
+    utils::to_f64,
+    Amount,
+};
+use ethers_core::types::U256;
+
+/// A quoted swap through a path of UniswapV2 pairs.
+///
+/// Prices are expressed in raw token units, as the amount of the last token in the path per unit
+/// of the first one, and are not adjusted for the tokens' decimals.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Trade {
+    /// The amounts of each token in the path.
+    pub amounts: Vec<U256>,
+
+    /// The price at which the trade is executed: `amount_out / amount_in`.
+    pub execution_price: f64,
+
+    /// The price of the path before the trade, compounded over all of its pairs.
+    pub mid_price: f64,
+
+    /// The difference between the execution price and the mid price, in basis points. Includes
+    /// the pairs' swap fees.
+    pub price_impact_bps: u32,
+}
+
+impl Trade {
+    /// Creates a new trade from the already fetched, sorted `reserves` of each pair in a path.
+    ///
+    /// Returns [`Error::ZeroReserves`] if any of the pairs has no liquidity, and
+    /// [`Error::UnresolvedAmount`] if `amount` is a percentage of balance.
+    pub fn new(amount: Amount, reserves: &[(U256, U256)], fee_bps: u32) -> Result<Self> {
+        if reserves
+            .iter()
+            .any(|(reserve_in, reserve_out)| reserve_in.is_zero() || reserve_out.is_zero())
+        {
+            return Err(Error::ZeroReserves);
+        }
+
+        let amounts = match amount {
+            Amount::ExactIn(amount_in) | Amount::ExactInNative(amount_in) => {
+                Library::get_amounts_out_with_reserves(amount_in, reserves, fee_bps)?
+            }
+            Amount::ExactOut(amount_out) => {
+                Library::get_amounts_in_with_reserves(amount_out, reserves, fee_bps)?
+            }
+            Amount::PercentOfBalance { .. } => return Err(Error::UnresolvedAmount),
+        };
+
+        let mid_price = reserves
+            .iter()
+            .map(|&(reserve_in, reserve_out)| to_f64(reserve_out) / to_f64(reserve_in))
+            .product::<f64>();
+        Ok(Self::with_mid_price(amounts, mid_price))
+    }
+
+    /// Creates a new trade from the already fetched, sorted `reserves` of each pair in a path of
+    /// Solidly pairs, where `stable` is whether each pair is stable.
+    ///
+    /// `decimals` must contain the decimals of each token in the path if any pair is stable,
+    /// otherwise it may be None. The mid price of stable pairs is the marginal price of their `x³y + y³x`
+    /// invariant.
+    ///
+    /// Returns [`Error::ExactOutUnsupported`] for exact output amounts. See [`new`][Self::new] for
+    /// the other errors.
+    pub fn new_solidly(
+        amount: Amount,
+        reserves: &[(U256, U256)],
+        stable: &[bool],
+        decimals: Option<&[u8]>,
+        fee_bps: u32,
+    ) -> Result<Self> {
+        if reserves
+            .iter()
+            .any(|(reserve_in, reserve_out)| reserve_in.is_zero() || reserve_out.is_zero())
+        {
+            return Err(Error::ZeroReserves);
+        }
+
+        let amounts = match amount {
+            Amount::ExactIn(amount_in) | Amount::ExactInNative(amount_in) => {
+                Library::get_amounts_out_solidly(amount_in, reserves, stable, decimals, fee_bps)?
+            }
+            Amount::ExactOut(_) => return Err(Error::ExactOutUnsupported),
+            Amount::PercentOfBalance { .. } => return Err(Error::UnresolvedAmount),
+        };
+
+        let mid_price = reserves
+            .iter()
+            .enumerate()
+            .map(|(i, &(reserve_in, reserve_out))| {
+                let (reserve_in, reserve_out) = (to_f64(reserve_in), to_f64(reserve_out));
+                match decimals.filter(|_| stable[i]) {
+                    Some(decimals) => {
+                        let (unit_in, unit_out) =
+                            (10f64.powi(decimals[i].into()), 10f64.powi(decimals[i + 1].into()));
+                        let (x, y) = (reserve_in / unit_in, reserve_out / unit_out);
+                        // -dx/dy of x³y + y³x = k
+                        let price = (3.0 * x * x * y + y * y * y) / (x * x * x + 3.0 * x * y * y);
+                        price * unit_out / unit_in
+                    }
+                    None => reserve_out / reserve_in,
+                }
+            })
+            .product::<f64>();
+        Ok(Self::with_mid_price(amounts, mid_price))
+    }
+
+    /// Creates a new trade from its `amounts` and the mid price of its path.
+    fn with_mid_price(amounts: Vec<U256>, mid_price: f64) -> Self {
+        let execution_price = to_f64(amounts[amounts.len() - 1]) / to_f64(amounts[0]);
+        let price_impact = (1.0 - execution_price / mid_price) * 10_000.0;
+        let price_impact_bps = price_impact.round().clamp(0.0, 10_000.0) as u32;
+
+        Self { amounts, execution_price, mid_price, price_impact_bps }
+    }
+
+    /// Returns the amount of the first token in the path.
+    #[inline]
+    pub fn amount_in(&self) -> U256 {
+        self.amounts[0]
+    }
+
+    /// Returns the amount of the last token in the path.
+    #[inline]
+    pub fn amount_out(&self) -> U256 {
+        self.amounts[self.amounts.len() - 1]
+    }
+
+    /// Returns [`Error::PriceImpactTooHigh`] if the price impact is greater than `max_bps`.
+    pub fn check_price_impact(&self, max_bps: u32) -> Result<()> {
+        if self.price_impact_bps > max_bps {
+            Err(Error::PriceImpactTooHigh { actual: self.price_impact_bps, max: max_bps })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_impact() {
+        let reserves = [(U256::exp10(21), U256::exp10(24))];
+
+        // 0.1% of the reserves: 0.3% fee + ~0.1% impact
+        let trade = Trade::new(Amount::ExactIn(U256::exp10(18)), &reserves, 30).unwrap();
+        assert_eq!(trade.amounts.len(), 2);
+        assert_eq!(trade.mid_price, 1000.0);
+        assert_eq!(trade.price_impact_bps, 40);
+        assert!(trade.check_price_impact(40).is_ok());
+        assert!(matches!(
+            trade.check_price_impact(39).unwrap_err(),
+            Error::PriceImpactTooHigh { actual: 40, max: 39 }
+        ));
+
+        // 10% of the reserves
+        let trade = Trade::new(Amount::ExactIn(U256::exp10(20)), &reserves, 30).unwrap();
+        assert_eq!(trade.price_impact_bps, 934);
+
+        let trade = Trade::new(Amount::ExactOut(trade.amount_out()), &reserves, 30).unwrap();
+        assert_eq!(trade.price_impact_bps, 934);
+    }
+
+    #[test]
+    fn test_price_impact_multi_hop() {
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let hop = (U256::exp10(21), U256::exp10(21));
+        let single = Trade::new(amount, &[hop], 30).unwrap();
+        let double = Trade::new(amount, &[hop, hop], 30).unwrap();
+        assert_eq!(double.mid_price, 1.0);
+        assert!(double.price_impact_bps > single.price_impact_bps);
+        // 1 - (1 - 0.004)^2
+        assert_eq!(double.price_impact_bps, 80);
+    }
+
+    #[test]
+    fn test_solidly() {
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let reserves = [(U256::exp10(21), U256::exp10(24))];
+
+        // volatile: 0.02% fee + ~0.1% impact
+        let trade = Trade::new_solidly(amount, &reserves, &[false], None, 2).unwrap();
+        assert_eq!(trade.mid_price, 1000.0);
+        assert_eq!(trade.price_impact_bps, 12);
+
+        // stable: balanced reserves of tokens with different decimals trade at ~1:1
+        let reserves = [(U256::exp10(12), U256::exp10(24))];
+        let amount = Amount::ExactIn(U256::exp10(6));
+        let trade = Trade::new_solidly(amount, &reserves, &[true], Some(&[6, 18]), 2).unwrap();
+        assert!((trade.mid_price - 1e12).abs() < 1.0);
+        assert_eq!(trade.price_impact_bps, 2);
+
+        // mixed: a stable hop followed by a volatile one
+        let reserves = [(U256::exp10(12), U256::exp10(24)), (U256::exp10(24), U256::exp10(21))];
+        let trade =
+            Trade::new_solidly(amount, &reserves, &[true, false], Some(&[6, 18, 18]), 2).unwrap();
+        assert!((trade.mid_price - 1e9).abs() < 1.0);
+
+        let res = Trade::new_solidly(amount, &reserves, &[true, true], None, 2);
+        assert!(matches!(res.unwrap_err(), Error::InvalidReserves));
+        let res = Trade::new_solidly(Amount::ExactOut(U256::one()), &reserves, &[false], None, 2);
+        assert!(matches!(res.unwrap_err(), Error::ExactOutUnsupported));
+    }
+
+    #[test]
+    fn test_zero_reserves() {
+        let amount = Amount::ExactIn(U256::exp10(18));
+        let reserves = [(U256::exp10(21), U256::exp10(21)), (U256::zero(), U256::exp10(21))];
+        assert!(matches!(Trade::new(amount, &reserves, 30).unwrap_err(), Error::ZeroReserves));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let reserves = [(U256::exp10(21), U256::exp10(24))];
+        let trade = Trade::new(Amount::ExactIn(U256::exp10(18)), &reserves, 30).unwrap();
+        let json = serde_json::to_value(&trade).unwrap();
+        assert_eq!(json["amounts"][0], "0xde0b6b3a7640000");
+        assert_eq!(serde_json::from_value::<Trade>(json).unwrap(), trade);
+    }
+}
@@ -0,0 +1,163 @@
+use super::{
+    math::{
+        add_delta, get_sqrt_ratio_at_tick,
+        sqrt_price::{amount0_delta, amount1_delta},
+        MAX_TICK, MIN_TICK,
+    },
+    PoolState,
+};
+use crate::errors::Result;
+use ethers_core::types::U256;
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// The liquidity of a pool around its current price, returned by
+/// [`Pool::depth_chart`][super::Pool::depth_chart].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DepthChart {
+    /// The current tick of the pool.
+    pub tick: i32,
+    /// The cumulative amounts of `token0` available from the current price up to each initialized
+    /// tick in range and up to the upper bound of the range, in ascending order of tick.
+    pub asks: Vec<(i32, U256)>,
+    /// The cumulative amounts of `token1` available from the current price down to each
+    /// initialized tick in range and down to the lower bound of the range, in descending order of
+    /// tick.
+    pub bids: Vec<(i32, U256)>,
+}
+
+impl DepthChart {
+    /// Returns the depth of a pool with `state` within `ticks_each_side` ticks of its current tick,
+    /// from the net liquidity of its initialized ticks in that range.
+    ///
+    /// The amounts are rounded down, like those received by swapping up to each tick.
+    ///
+    /// Returns [`Error::Overflow`][crate::errors::Error::Overflow] if the liquidity overflows a
+    /// `u128`, which means that `liquidity_net` is not consistent with `state`.
+    pub fn new(
+        state: &PoolState,
+        liquidity_net: &BTreeMap<i32, i128>,
+        ticks_each_side: u32,
+    ) -> Result<Self> {
+        let range = tick_range(state.tick, ticks_each_side);
+        let (lower, upper) = (*range.start(), *range.end());
+
+        // crossing the initialized ticks above the current one from left to right
+        let mut asks = vec![];
+        let (mut sqrt_price_x96, mut liquidity) = (state.sqrt_price_x96, state.liquidity);
+        let mut amount0 = U256::zero();
+        if upper > state.tick {
+            for (&tick, &net) in liquidity_net.range(state.tick + 1..=upper) {
+                let next = get_sqrt_ratio_at_tick(tick)?;
+                amount0 += amount0_delta(sqrt_price_x96, next, liquidity, false)?;
+                asks.push((tick, amount0));
+                sqrt_price_x96 = next;
+                liquidity = add_delta(liquidity, net)?;
+            }
+            if asks.last().map(|&(tick, _)| tick) != Some(upper) {
+                let next = get_sqrt_ratio_at_tick(upper)?;
+                amount0 += amount0_delta(sqrt_price_x96, next, liquidity, false)?;
+                asks.push((upper, amount0));
+            }
+        }
+
+        // crossing the initialized ticks at or below the current one from right to left
+        let mut bids = vec![];
+        let (mut sqrt_price_x96, mut liquidity) = (state.sqrt_price_x96, state.liquidity);
+        let mut amount1 = U256::zero();
+        for (&tick, &net) in liquidity_net.range(lower..=state.tick).rev() {
+            let next = get_sqrt_ratio_at_tick(tick)?;
+            amount1 += amount1_delta(next, sqrt_price_x96, liquidity, false)?;
+            bids.push((tick, amount1));
+            sqrt_price_x96 = next;
+            liquidity = add_delta(liquidity, -net)?;
+        }
+        if bids.last().map(|&(tick, _)| tick) != Some(lower) {
+            let next = get_sqrt_ratio_at_tick(lower)?;
+            amount1 += amount1_delta(next, sqrt_price_x96, liquidity, false)?;
+            bids.push((lower, amount1));
+        }
+
+        Ok(Self { tick: state.tick, asks, bids })
+    }
+
+    /// Returns the amount of `token0` available above the current price, in the whole range.
+    pub fn amount0(&self) -> U256 {
+        self.asks.last().map(|&(_, amount)| amount).unwrap_or_default()
+    }
+
+    /// Returns the amount of `token1` available below the current price, in the whole range.
+    pub fn amount1(&self) -> U256 {
+        self.bids.last().map(|&(_, amount)| amount).unwrap_or_default()
+    }
+}
+
+/// Returns the range of ticks within `ticks_each_side` of `tick`, clamped to the valid ticks.
+pub(super) fn tick_range(tick: i32, ticks_each_side: u32) -> RangeInclusive<i32> {
+    let (tick, ticks_each_side) = (tick as i64, ticks_each_side as i64);
+    let lower = (tick - ticks_each_side).max(MIN_TICK as i64);
+    let upper = (tick + ticks_each_side).min(MAX_TICK as i64);
+    lower as i32..=upper as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3::math::liquidity::amounts_for_liquidity;
+
+    #[test]
+    fn test_depth_chart() {
+        // liquidity of 1000 from tick -600 to 600, and of 2000 from -60 to 60
+        let liquidity_net = BTreeMap::from([(-600, 1000), (-60, 2000), (60, -2000), (600, -1000)]);
+        let state = PoolState {
+            sqrt_price_x96: get_sqrt_ratio_at_tick(0).unwrap(),
+            tick: 0,
+            liquidity: 3000,
+            ..Default::default()
+        };
+        let depth = DepthChart::new(&state, &liquidity_net, 120).unwrap();
+        assert_eq!(depth.tick, 0);
+        assert_eq!(depth.asks.iter().map(|&(tick, _)| tick).collect::<Vec<_>>(), [60, 120]);
+        assert_eq!(depth.bids.iter().map(|&(tick, _)| tick).collect::<Vec<_>>(), [-60, -120]);
+
+        // the amounts of the positions in range, rounded down by segment
+        let sqrt_ratio = |tick| get_sqrt_ratio_at_tick(tick).unwrap();
+        let amounts = |lower, upper, liquidity| {
+            amounts_for_liquidity(
+                state.sqrt_price_x96,
+                sqrt_ratio(lower),
+                sqrt_ratio(upper),
+                liquidity,
+            )
+            .unwrap()
+        };
+        let (near0, near1) = amounts(-60, 60, 3000);
+        let (far0, _) = amounts(60, 120, 1000);
+        let (_, far1) = amounts(-120, -60, 1000);
+        assert_eq!(depth.asks[0].1, near0);
+        assert_eq!(depth.amount0(), near0 + far0);
+        assert_eq!(depth.bids[0].1, near1);
+        assert_eq!(depth.amount1(), near1 + far1);
+
+        // initialized bounds, and nothing beyond the positions
+        let depth = DepthChart::new(&state, &liquidity_net, 600).unwrap();
+        assert_eq!(depth.asks.last().unwrap().0, 600);
+        assert_eq!(depth.bids.last().unwrap().0, -600);
+        let wider = DepthChart::new(&state, &liquidity_net, 1200).unwrap();
+        assert_eq!(wider.amount0(), depth.amount0());
+        assert_eq!(wider.amount1(), depth.amount1());
+
+        // clamped to the valid ticks, with an empty range above the maximum tick
+        let state = PoolState {
+            sqrt_price_x96: sqrt_ratio(MAX_TICK),
+            tick: MAX_TICK,
+            liquidity: 0,
+            ..state
+        };
+        assert_eq!(tick_range(MAX_TICK, 10), MAX_TICK - 10..=MAX_TICK);
+        assert_eq!(tick_range(MIN_TICK, u32::MAX), MIN_TICK..=MAX_TICK);
+        let depth = DepthChart::new(&state, &BTreeMap::new(), 10).unwrap();
+        assert_eq!(depth.amount0(), U256::zero());
+        assert!(depth.asks.is_empty());
+    }
+}
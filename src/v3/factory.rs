@@ -1,7 +1,39 @@
-use crate::{contracts::bindings::i_uniswap_v3_factory::IUniswapV3Factory, ProtocolType};
-use ethers_core::types::{Address, Chain, H256};
+use super::{FeeAmount, Pool, PoolInfo, PoolRegistry, MAX_FEE};
+use crate::{
+    common::{call_multi, get_logs, scan_logs},
+    contracts::bindings::{
+        i_uniswap_v3_factory::{
+            FeeAmountEnabledFilter, IUniswapV3Factory, IUniswapV3FactoryEvents, PoolCreatedFilter,
+        },
+        i_uniswap_v3_pool::{IUniswapV3Pool, LiquidityCall, Slot0Call},
+    },
+    errors::{Error, Result},
+    DecodedLog, ProtocolType, RequestPolicy,
+};
+use ethers_contract::{builders::ContractCall, ContractError, EthCall, EthEvent};
+use ethers_core::{
+    abi::{self, Token, Tokenizable},
+    types::{Address, Chain, H256, U256},
+};
 use ethers_providers::Middleware;
-use std::sync::Arc;
+use std::{cmp::Reverse, sync::Arc};
+
+/// The active liquidity and price of an existing pool, as returned by
+/// [`Factory::best_pool`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PoolLiquidity {
+    /// The address of the pool.
+    pub address: Address,
+    /// The fee amount of the pool.
+    pub fee: FeeAmount,
+    /// The in-range liquidity of the pool.
+    pub liquidity: u128,
+    /// The current price of the pool, as a `sqrtPriceX96`.
+    pub sqrt_price_x96: U256,
+    /// The current tick of the pool.
+    pub tick: i32,
+}
 
 contract_struct! {
     /// A Uniswap V3 factory.
@@ -14,6 +46,18 @@ contract_struct! {
 
         /// The chain.
         pub chain: Option<Chain>,
+
+        /// The pool deployer override.
+        pool_deployer: Option<Address>,
+
+        /// The pool code hash override.
+        pool_code_hash: Option<H256>,
+
+        /// The enabled fee amounts, if known.
+        fee_amounts: Option<Vec<FeeAmount>>,
+
+        /// The policy of the factory's batched requests and log scans.
+        request_policy: RequestPolicy,
     }
 }
 
@@ -27,8 +71,20 @@ impl<M> Factory<M> {
     ///
     /// Note: `chain` is used only when the pair code hash differs in the same protocol, for example
     /// `Pancakeswap` has two different code hashes for BSC mainnet and testnet.
+    ///
+    /// Returns the hash set with [`set_pool_code_hash`][Self::set_pool_code_hash], if any.
     pub fn pair_code_hash(&self, chain: Option<Chain>) -> H256 {
-        self.protocol.pair_code_hash(self.chain.or(chain))
+        match self.pool_code_hash {
+            Some(pool_code_hash) => pool_code_hash,
+            None => self.protocol.pair_code_hash(self.chain.or(chain)),
+        }
+    }
+
+    /// Sets the pool code hash, overriding the [protocol's][ProtocolType::pair_code_hash]. Used
+    /// for deployments with a different pool bytecode.
+    pub fn set_pool_code_hash(&mut self, pool_code_hash: H256) -> &mut Self {
+        self.pool_code_hash = Some(pool_code_hash);
+        self
     }
 
     /// Sets the factory's chain.
@@ -36,13 +92,47 @@ impl<M> Factory<M> {
         self.chain = Some(chain);
         self
     }
+
+    /// Sets the address of the contract which deploys the factory's pools, overriding the
+    /// [protocol's][ProtocolType::pool_deployer].
+    pub fn set_pool_deployer(&mut self, pool_deployer: Address) -> &mut Self {
+        self.pool_deployer = Some(pool_deployer);
+        self
+    }
+
+    /// Sets the fee amounts enabled on the factory, used by [`best_pool`][Self::best_pool] instead
+    /// of querying the factory's `FeeAmountEnabled` events.
+    pub fn set_fee_amounts(&mut self, fee_amounts: Vec<FeeAmount>) -> &mut Self {
+        self.fee_amounts = Some(fee_amounts);
+        self
+    }
+
+    /// Returns the policy of the factory's batched requests and log scans.
+    pub fn request_policy(&self) -> RequestPolicy {
+        self.request_policy
+    }
+
+    /// Sets the policy of the factory's batched requests and log scans, like
+    /// [`best_pool`][Self::best_pool] and [`scan_pools`][Self::scan_pools].
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) -> &mut Self {
+        self.request_policy = policy;
+        self
+    }
 }
 
 impl<M: Middleware> Factory<M> {
     /// Creates a new instance using the provided address.
     pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
         let contract = IUniswapV3Factory::new(address, client);
-        Self { contract, protocol, chain: None }
+        Self {
+            contract,
+            protocol,
+            chain: None,
+            pool_deployer: None,
+            pool_code_hash: None,
+            fee_amounts: None,
+            request_policy: RequestPolicy::new(),
+        }
     }
 
     /// Creates a new instance by searching for the required addresses in the [addressbook].
@@ -52,7 +142,15 @@ impl<M: Middleware> Factory<M> {
     pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
         protocol.try_addresses(chain).0.map(|address| {
             let contract = IUniswapV3Factory::new(address, client);
-            Self { contract, protocol, chain: Some(chain) }
+            Self {
+                contract,
+                protocol,
+                chain: Some(chain),
+                pool_deployer: None,
+                pool_code_hash: None,
+                fee_amounts: None,
+                request_policy: RequestPolicy::new(),
+            }
         })
     }
 
@@ -64,9 +162,631 @@ impl<M: Middleware> Factory<M> {
         self.contract = self.contract.at(address).into();
     }
 
-    // /// Returns the pair for two token addresses.
-    // pub fn pair_for(&self, token_a: Address, token_b: Address) -> Pair<M> {
-    //     let address = Library::pair_for(self, token_a, token_b);
-    //     Pair::new(self.client(), address, self.protocol)
-    // }
+    /// Returns the contract call for the factory's `getPool` method, which returns the address of
+    /// the pool for two token addresses and a fee, or the zero address if it does not exist.
+    pub fn get_pool(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+    ) -> ContractCall<M, Address> {
+        self.contract.get_pool(token_a, token_b, fee.into())
+    }
+
+    /// Returns the contract call for the factory's `createPool` method.
+    pub fn create_pool(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+    ) -> ContractCall<M, Address> {
+        self.contract.create_pool(token_a, token_b, fee.into())
+    }
+
+    /// Returns the contract call for the factory's `feeAmountTickSpacing` method, which returns
+    /// the tick spacing of an enabled fee amount, or zero.
+    pub fn fee_amount_tick_spacing(&self, fee: FeeAmount) -> ContractCall<M, i32> {
+        self.contract.fee_amount_tick_spacing(fee.into())
+    }
+
+    /// Returns the contract call for the factory's `enableFeeAmount` method, which can only be
+    /// called by the factory's owner.
+    pub fn enable_fee_amount(&self, fee: FeeAmount, tick_spacing: i32) -> ContractCall<M, ()> {
+        self.contract.enable_fee_amount(fee.into(), tick_spacing)
+    }
+
+    /// Fetches the fee amounts enabled on the factory from its `FeeAmountEnabled` events, sorted.
+    ///
+    /// This queries the factory's logs from the genesis block.
+    pub async fn enabled_fee_amounts(&self) -> Result<Vec<FeeAmount>> {
+        let filter = self.contract.fee_amount_enabled_filter().from_block(0).filter;
+        let logs = get_logs::<_, FeeAmountEnabledFilter>(self.client().as_ref(), &filter).await?;
+        let mut fee_amounts = logs
+            .into_iter()
+            .map(|log| FeeAmount::try_from(log.event.fee))
+            .collect::<Result<Vec<_>>>()?;
+        fee_amounts.sort_unstable();
+        fee_amounts.dedup();
+        Ok(fee_amounts)
+    }
+
+    /// Finds the existing pools of two tokens across all the fee amounts enabled on the factory,
+    /// and returns them sorted by their in-range liquidity, the deepest first.
+    ///
+    /// The fee amounts are the default ones, plus those set with
+    /// [`set_fee_amounts`][Self::set_fee_amounts] or found by [`scan_pools`][Self::scan_pools] or,
+    /// if neither, those found by [`enabled_fee_amounts`][Self::enabled_fee_amounts]. Pools are
+    /// looked up with the factory's `getPool`, then their `liquidity` and `slot0` are all
+    /// fetched in a single multicall. Pools with no in-range liquidity are skipped.
+    ///
+    /// Returns [`Error::NoPoolFound`] if no pool with liquidity exists.
+    pub async fn best_pool(
+        &self,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<Vec<PoolLiquidity>> {
+        // validate the tokens
+        self.pool_address(token_a, token_b, FeeAmount::Medium)?;
+
+        let mut fee_amounts = FeeAmount::DEFAULTS.to_vec();
+        match &self.fee_amounts {
+            Some(enabled) => fee_amounts.extend_from_slice(enabled),
+            None => fee_amounts.extend(self.enabled_fee_amounts().await?),
+        }
+        fee_amounts.sort_unstable();
+        fee_amounts.dedup();
+
+        let calls = fee_amounts.iter().map(|&fee| self.get_pool(token_a, token_b, fee)).collect();
+        let addresses = self.call_multi(calls).await?;
+        let pools: Vec<_> = fee_amounts
+            .into_iter()
+            .zip(addresses)
+            .filter(|(_, address)| !address.is_zero())
+            .collect();
+
+        let contract = IUniswapV3Pool::new(Address::zero(), self.client());
+        let mut calls = Vec::with_capacity(pools.len() * 2);
+        for &(_, address) in &pools {
+            for selector in [LiquidityCall::selector(), Slot0Call::selector()] {
+                let mut call = contract
+                    .method_hash::<_, Token>(selector, ())
+                    .map_err(ContractError::<M>::from)?;
+                call.tx.set_to(address);
+                calls.push(call);
+            }
+        }
+        let mut results = self.call_multi(calls).await?.into_iter();
+
+        let mut liquidities = Vec::with_capacity(pools.len());
+        for (fee, address) in pools {
+            let (liquidity, slot_0) = match (results.next(), results.next()) {
+                (Some(liquidity), Some(slot_0)) => (liquidity, slot_0),
+                _ => unreachable!("one result per call"),
+            };
+            let liquidity = u128::from_token(liquidity)?;
+            if liquidity == 0 {
+                continue;
+            }
+            let (sqrt_price_x96, tick, ..) =
+                <(U256, i32, u16, u16, u16, u8, bool)>::from_token(slot_0)?;
+            liquidities.push(PoolLiquidity { address, fee, liquidity, sqrt_price_x96, tick });
+        }
+        if liquidities.is_empty() {
+            return Err(Error::NoPoolFound);
+        }
+        // stable sort: ties keep the lowest fee first
+        liquidities.sort_by_key(|pool| Reverse(pool.liquidity));
+        Ok(liquidities)
+    }
+
+    /// Scans the factory's `PoolCreated` and `FeeAmountEnabled` events from `from_block` to
+    /// `to_block`, inclusive, in ranges of at most `chunk_size` blocks, returning the created pools
+    /// and the enabled fee amounts.
+    ///
+    /// See [`resume_scan`][Self::resume_scan] for more details.
+    pub async fn scan_pools(
+        &mut self,
+        from_block: u64,
+        to_block: u64,
+        chunk_size: u64,
+    ) -> Result<PoolRegistry> {
+        let mut registry = PoolRegistry::new(from_block);
+        self.resume_scan(&mut registry, to_block, chunk_size).await?;
+        Ok(registry)
+    }
+
+    /// Resumes scanning the factory's `PoolCreated` and `FeeAmountEnabled` events into
+    /// `registry`, from its [`next_block`][PoolRegistry::next_block] to `to_block`, inclusive, in
+    /// ranges of at most `chunk_size` blocks.
+    ///
+    /// Ranges for which the provider returns too many results are bisected until they succeed,
+    /// and throttled queries are retried according to the factory's
+    /// [request policy][Self::request_policy]. The registry is updated after every range, so that
+    /// scanning can be resumed after an error.
+    ///
+    /// The fee amounts found are then added to the factory's ones, like with
+    /// [`set_fee_amounts`][Self::set_fee_amounts], so that [`best_pool`][Self::best_pool] also
+    /// looks up the pools with custom fee amounts. Scan from the factory's deployment block for
+    /// them to be complete.
+    pub async fn resume_scan(
+        &mut self,
+        registry: &mut PoolRegistry,
+        to_block: u64,
+        chunk_size: u64,
+    ) -> Result<()> {
+        let filter = self
+            .contract
+            .events()
+            .filter
+            .topic0(vec![PoolCreatedFilter::signature(), FeeAmountEnabledFilter::signature()]);
+        let from_block = registry.next_block();
+        let (client, policy) = (self.client(), self.request_policy);
+        scan_logs(
+            client.as_ref(),
+            &filter,
+            from_block,
+            to_block,
+            chunk_size,
+            &policy,
+            |logs, end| {
+                for log in logs {
+                    let block_number = log.block_number.unwrap_or_default().as_u64();
+                    match DecodedLog::<IUniswapV3FactoryEvents>::decode(&log)?.event {
+                        IUniswapV3FactoryEvents::PoolCreatedFilter(event) => {
+                            registry.insert(PoolInfo {
+                                token0: event.token_0,
+                                token1: event.token_1,
+                                fee: event.fee.try_into()?,
+                                tick_spacing: event.tick_spacing,
+                                address: event.pool,
+                                block_number,
+                            });
+                        }
+                        IUniswapV3FactoryEvents::FeeAmountEnabledFilter(event) => {
+                            registry.enable_fee_amount(event.fee.try_into()?, event.tick_spacing);
+                        }
+                        IUniswapV3FactoryEvents::OwnerChangedFilter(_) => {}
+                    }
+                }
+                registry.last_block = Some(end);
+                Ok(())
+            },
+        )
+        .await?;
+
+        let fee_amounts = self.fee_amounts.get_or_insert_with(Vec::new);
+        fee_amounts.extend(registry.fee_amounts());
+        fee_amounts.sort_unstable();
+        fee_amounts.dedup();
+        Ok(())
+    }
+
+    /// Returns the address of the contract which deploys the factory's pools: the override set
+    /// with [`set_pool_deployer`][Self::set_pool_deployer], the
+    /// [protocol's][ProtocolType::pool_deployer], or the factory itself.
+    pub fn pool_deployer(&self) -> Address {
+        self.pool_deployer
+            .or_else(|| self.protocol.pool_deployer())
+            .unwrap_or_else(|| self.address())
+    }
+
+    /// Calculates the CREATE2 address of the pool for two token addresses and a fee, without
+    /// making any external calls.
+    ///
+    /// Returns [`Error::IdenticalAddresses`] if the tokens are the same, [`Error::ZeroAddress`] if
+    /// either of them is the zero address, and [`Error::FeeTooLarge`] if `fee` does not fit in 24
+    /// bits.
+    pub fn pool_address(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+    ) -> Result<Address> {
+        if token_a == token_b {
+            return Err(Error::IdenticalAddresses);
+        }
+        if token_a.is_zero() || token_b.is_zero() {
+            return Err(Error::ZeroAddress);
+        }
+        let fee = fee.as_u32();
+        if fee > MAX_FEE {
+            return Err(Error::FeeTooLarge(fee));
+        }
+        let (token_0, token_1) =
+            if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+        // keccak256(abi.encode(token0, token1, fee))
+        let salt = ethers_core::utils::keccak256(abi::encode(&[
+            Token::Address(token_0),
+            Token::Address(token_1),
+            Token::Uint(fee.into()),
+        ]));
+        let init_code_hash = self.pair_code_hash(None).0;
+        let deployer = self.pool_deployer();
+        Ok(ethers_core::utils::get_create2_address_from_hash(deployer, salt, init_code_hash))
+    }
+
+    /// Returns the pool for two token addresses and a fee. See
+    /// [`pool_address`][Self::pool_address] for more details.
+    ///
+    /// The pool's [tick lens][Pool::set_tick_lens] is set from the addressbook if the factory's
+    /// chain is set and its protocol is Uniswap V3.
+    pub fn pool_for(&self, token_a: Address, token_b: Address, fee: FeeAmount) -> Result<Pool<M>> {
+        let address = self.pool_address(token_a, token_b, fee)?;
+        let tokens = crate::utils::sort_tokens(token_a, token_b);
+        #[cfg_attr(not(feature = "addresses"), allow(unused_mut))]
+        let mut pool = Pool::new_with_key(self.client(), address, self.protocol, tokens, fee);
+        #[cfg(feature = "addresses")]
+        if let (Some(chain), ProtocolType::UniswapV3) = (self.chain, self.protocol) {
+            if let Some(tick_lens) =
+                crate::contracts::addresses::try_address("UniswapV3TickLens", chain)
+            {
+                pool.set_tick_lens(tick_lens);
+            }
+        }
+        Ok(pool)
+    }
+
+    /// Aggregates `calls` into a single [Multicall][crate::utils::Multicall] request, retried
+    /// according to the factory's [request policy][Self::request_policy]. Falls back to sequential
+    /// calls, pinned to the latest block, if Multicall3 is not deployed on the factory's chain.
+    async fn call_multi<D: Tokenizable>(&self, calls: Vec<ContractCall<M, D>>) -> Result<Vec<D>> {
+        let policy = &self.request_policy;
+        policy.retry(|| call_multi(self.client(), self.chain, calls.clone())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Fork;
+    use ethers_core::types::{Bytes, Log, I256};
+    use ethers_providers::{Http, MockProvider, Provider};
+
+    #[test]
+    fn test_pool_for() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let address = "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap();
+        let factory = Factory::new(provider.into(), address, ProtocolType::UniswapV3);
+        let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+
+        // https://etherscan.io/address/0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640
+        let expected: Address = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
+        assert_eq!(factory.pool_address(usdc, weth, FeeAmount::Low).unwrap(), expected);
+        let pool = factory.pool_for(weth, usdc, FeeAmount::Low).unwrap();
+        assert_eq!(pool.address(), expected);
+        assert_eq!(pool.tokens(), Some((usdc, weth)));
+        assert_eq!(pool.fee(), Some(FeeAmount::Low));
+        // https://etherscan.io/address/0x8ad599c3A0ff1De082011EFDDc58f1908eb6e6D8
+        let expected: Address = "0x8ad599c3A0ff1De082011EFDDc58f1908eb6e6D8".parse().unwrap();
+        assert_eq!(factory.pool_address(usdc, weth, FeeAmount::Medium).unwrap(), expected);
+
+        assert!(matches!(
+            factory.pool_address(usdc, usdc, FeeAmount::Low),
+            Err(Error::IdenticalAddresses)
+        ));
+        let zero = Address::zero();
+        assert!(matches!(
+            factory.pool_address(usdc, zero, FeeAmount::Low),
+            Err(Error::ZeroAddress)
+        ));
+        assert!(matches!(
+            factory.pool_address(zero, weth, FeeAmount::Low),
+            Err(Error::ZeroAddress)
+        ));
+    }
+
+    #[test]
+    fn test_pool_deployer() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let client = Arc::new(provider);
+        let address = Address::repeat_byte(0x11);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let mut factory = Factory::new(client.clone(), address, ProtocolType::UniswapV3);
+        assert_eq!(factory.pool_deployer(), address);
+        let pool = factory.pool_address(a, b, FeeAmount::Low).unwrap();
+        factory.set_pool_deployer(Address::repeat_byte(0x22));
+        assert_eq!(factory.pool_deployer(), Address::repeat_byte(0x22));
+        assert_ne!(factory.pool_address(a, b, FeeAmount::Low).unwrap(), pool);
+
+        let factory = Factory::new(client, address, ProtocolType::PancakeswapV3);
+        let deployer = ProtocolType::PancakeswapV3.pool_deployer().unwrap();
+        assert_eq!(factory.pool_deployer(), deployer);
+        let salt = ethers_core::utils::keccak256(abi::encode(&[
+            Token::Address(a),
+            Token::Address(b),
+            Token::Uint(2500.into()),
+        ]));
+        let hash = ProtocolType::PancakeswapV3.pair_code_hash(None).0;
+        let expected = ethers_core::utils::get_create2_address_from_hash(deployer, salt, hash);
+        assert_eq!(factory.pool_address(b, a, FeeAmount::Custom(2500)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_pool_code_hash() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let address = Address::repeat_byte(0x11);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let mut factory = Factory::new(Arc::new(provider), address, ProtocolType::UniswapV3);
+        let pool = factory.pool_address(a, b, FeeAmount::Low).unwrap();
+        factory.set_pool_code_hash(H256::repeat_byte(3));
+        assert_eq!(factory.pair_code_hash(None), H256::repeat_byte(3));
+        assert_ne!(factory.pool_address(a, b, FeeAmount::Low).unwrap(), pool);
+    }
+
+    fn push_multicall(mock: &MockProvider, results: Vec<Vec<Token>>) {
+        let results = results
+            .iter()
+            .map(|tokens| Token::Tuple(vec![Token::Bool(true), Token::Bytes(abi::encode(tokens))]))
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        // chain id, queried by the multicall
+        mock.push(U256::one()).unwrap();
+    }
+
+    fn slot_0(sqrt_price_x96: u64, tick: i32) -> Vec<Token> {
+        let tick = I256::from(tick).into_raw();
+        let observations = Token::Uint(1.into());
+        vec![
+            Token::Uint(sqrt_price_x96.into()),
+            Token::Int(tick),
+            Token::Uint(0.into()),
+            observations.clone(),
+            observations,
+            Token::Uint(0.into()),
+            Token::Bool(true),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_best_pool() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let mut factory =
+            Factory::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        factory.set_fee_amounts(vec![FeeAmount::Custom(2500)]);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let pool = |fee: u8| Address::repeat_byte(fee);
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 2. liquidity and slot0 of the existing pools
+        let liquidity = |liquidity: u128| vec![Token::Uint(liquidity.into())];
+        push_multicall(
+            &mock,
+            vec![
+                liquidity(100),
+                slot_0(1 << 40, -5),
+                liquidity(0),
+                slot_0(1 << 41, 10),
+                liquidity(500),
+                slot_0(1 << 42, 20),
+            ],
+        );
+        // 1. getPool of 0.01%, 0.05%, 0.25%, 0.3% and 1%
+        let get_pool = |address| vec![Token::Address(address)];
+        push_multicall(
+            &mock,
+            vec![
+                get_pool(Address::zero()),
+                get_pool(pool(5)),
+                get_pool(pool(25)),
+                get_pool(pool(30)),
+                get_pool(Address::zero()),
+            ],
+        );
+
+        let pools = factory.best_pool(a, b).await.unwrap();
+        let expected = vec![
+            PoolLiquidity {
+                address: pool(30),
+                fee: FeeAmount::Medium,
+                liquidity: 500,
+                sqrt_price_x96: (1u64 << 42).into(),
+                tick: 20,
+            },
+            PoolLiquidity {
+                address: pool(5),
+                fee: FeeAmount::Low,
+                liquidity: 100,
+                sqrt_price_x96: (1u64 << 40).into(),
+                tick: -5,
+            },
+        ];
+        assert_eq!(pools, expected);
+
+        // no pool with liquidity
+        push_multicall(&mock, vec![liquidity(0), slot_0(1, 0), liquidity(0), slot_0(1, 0)]);
+        let mut pools = vec![get_pool(Address::zero()); 5];
+        pools[1] = get_pool(pool(5));
+        pools[3] = get_pool(pool(30));
+        push_multicall(&mock, pools);
+        assert!(matches!(factory.best_pool(a, b).await, Err(Error::NoPoolFound)));
+
+        assert!(matches!(factory.best_pool(a, a).await, Err(Error::IdenticalAddresses)));
+    }
+
+    #[tokio::test]
+    async fn test_enabled_fee_amounts() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let factory =
+            Factory::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+
+        let fee_amount_enabled = |fee: u32, tick_spacing: i32| Log {
+            topics: vec![
+                FeeAmountEnabledFilter::signature(),
+                H256::from_low_u64_be(fee as u64),
+                H256::from_low_u64_be(tick_spacing as u64),
+            ],
+            ..Default::default()
+        };
+        let logs = vec![
+            fee_amount_enabled(500, 10),
+            fee_amount_enabled(3000, 60),
+            fee_amount_enabled(10000, 200),
+            fee_amount_enabled(100, 1),
+            fee_amount_enabled(2500, 50),
+        ];
+        mock.push::<Vec<Log>, _>(logs).unwrap();
+        let fee_amounts = factory.enabled_fee_amounts().await.unwrap();
+        let mut expected = FeeAmount::DEFAULTS.to_vec();
+        expected.insert(2, FeeAmount::Custom(2500));
+        assert_eq!(fee_amounts, expected);
+    }
+
+    #[tokio::test]
+    async fn test_scan_pools() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let mut factory =
+            Factory::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+
+        let topic = |value: u64| H256::from_low_u64_be(value);
+        let fee_amount_enabled = |fee: u32, tick_spacing: i32, block: u64| Log {
+            topics: vec![
+                FeeAmountEnabledFilter::signature(),
+                topic(fee as u64),
+                topic(tick_spacing as u64),
+            ],
+            block_number: Some(block.into()),
+            ..Default::default()
+        };
+        let pool_created = |token0: u8, token1: u8, fee: u32, tick_spacing: i32, block: u64| Log {
+            topics: vec![
+                PoolCreatedFilter::signature(),
+                Address::repeat_byte(token0).into(),
+                Address::repeat_byte(token1).into(),
+                topic(fee as u64),
+            ],
+            data: abi::encode(&[
+                Token::Int(I256::from(tick_spacing).into_raw()),
+                Token::Address(Address::repeat_byte(token0 + token1)),
+            ])
+            .into(),
+            block_number: Some(block.into()),
+            ..Default::default()
+        };
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 3. [20, 25]
+        mock.push::<Vec<Log>, _>(vec![pool_created(3, 4, 2500, 50, 22)]).unwrap();
+        // 2. [10, 19]
+        mock.push::<Vec<Log>, _>(vec![
+            fee_amount_enabled(2500, 50, 12),
+            pool_created(1, 2, 500, 10, 15),
+        ])
+        .unwrap();
+        // 1. [0, 9]
+        mock.push::<Vec<Log>, _>(vec![
+            fee_amount_enabled(500, 10, 1),
+            fee_amount_enabled(3000, 60, 1),
+        ])
+        .unwrap();
+
+        let mut registry = factory.scan_pools(0, 19, 10).await.unwrap();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.last_block, Some(19));
+        let pool = registry.get(Address::repeat_byte(2), Address::repeat_byte(1), FeeAmount::Low);
+        assert_eq!(
+            pool,
+            Some(&PoolInfo {
+                token0: Address::repeat_byte(1),
+                token1: Address::repeat_byte(2),
+                fee: FeeAmount::Low,
+                tick_spacing: 10,
+                address: Address::repeat_byte(3),
+                block_number: 15,
+            })
+        );
+        assert_eq!(registry.tick_spacing(FeeAmount::Custom(2500)), Some(50));
+        assert_eq!(
+            factory.fee_amounts,
+            Some(vec![FeeAmount::Low, FeeAmount::Custom(2500), FeeAmount::Medium])
+        );
+
+        // resume
+        factory.resume_scan(&mut registry, 25, 10).await.unwrap();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.next_block(), 26);
+        let pool = registry.get(
+            Address::repeat_byte(3),
+            Address::repeat_byte(4),
+            2500.try_into().unwrap(),
+        );
+        assert_eq!(pool.unwrap().address, Address::repeat_byte(7));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_scan_pools_fork() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        let address = "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap();
+        let mut factory = Factory::new(client, address, ProtocolType::UniswapV3);
+        let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+
+        // from the factory's deployment, which enables the default fee amounts
+        let registry = factory.scan_pools(12_369_621, 12_380_000, 2_000).await.unwrap();
+        assert_eq!(registry.tick_spacing(FeeAmount::Low), Some(10));
+        assert_eq!(registry.tick_spacing(FeeAmount::Medium), Some(60));
+        assert_eq!(registry.tick_spacing(FeeAmount::High), Some(200));
+        let pool = registry.get(weth, usdc, FeeAmount::Low).unwrap();
+        assert_eq!(pool.address, factory.pool_address(usdc, weth, FeeAmount::Low).unwrap());
+        assert_eq!(pool.tick_spacing, 10);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn best_pool_is_deepest() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        let address = "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap();
+        let mut factory = Factory::new(client, address, ProtocolType::UniswapV3);
+        factory.set_chain(Chain::Mainnet);
+        let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+
+        let pools = factory.best_pool(usdc, weth).await.unwrap();
+        assert!(pools.windows(2).all(|pools| pools[0].liquidity >= pools[1].liquidity));
+        for pool in &pools {
+            assert!(pool.liquidity > 0);
+            let expected = factory.pool_address(usdc, weth, pool.fee).unwrap();
+            assert_eq!(pool.address, expected);
+            let liquidity = factory.pool_for(usdc, weth, pool.fee).unwrap().contract().liquidity();
+            assert_eq!(liquidity.call().await.unwrap(), pool.liquidity);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn pool_address_matches_get_pool() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        let address = "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap();
+        let factory = Factory::new(client, address, ProtocolType::UniswapV3);
+        let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+
+        for fee in FeeAmount::DEFAULTS {
+            let expected = factory.get_pool(weth, usdc, fee).call().await.unwrap();
+            assert_eq!(factory.pool_address(usdc, weth, fee).unwrap(), expected, "{fee:?}");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a BSC node at UNISWAP_RS_BSC_FORK_URL"]
+    async fn pancake_pool_address_matches_get_pool() {
+        let url = std::env::var("UNISWAP_RS_BSC_FORK_URL").unwrap();
+        let client = Arc::new(Provider::<Http>::try_from(url).unwrap());
+        let address = "0x0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865".parse().unwrap();
+        let factory = Factory::new(client, address, ProtocolType::PancakeswapV3);
+        let usdt: Address = "0x55d398326f99059fF775485246999027B3197955".parse().unwrap();
+        let wbnb: Address = "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c".parse().unwrap();
+
+        for fee in [100, 500, 2500, 10000] {
+            let fee = FeeAmount::try_from(fee).unwrap();
+            let expected = factory.get_pool(wbnb, usdt, fee).call().await.unwrap();
+            assert_eq!(factory.pool_address(usdt, wbnb, fee).unwrap(), expected, "{fee:?}");
+        }
+    }
 }
@@ -1,22 +1,236 @@
-/// The default factory enabled fee amounts, denominated in hundredths of bips.
-#[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-#[repr(usize)]
+use crate::errors::{Error, Result};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+/// The maximum fee amount, as fees are `uint24`.
+pub const MAX_FEE: u32 = (1 << 24) - 1;
+
+/// A pool's fee amount, denominated in hundredths of bips.
+///
+/// The default factory enabled fee amounts have their own variants, while any other fee amount,
+/// like those enabled with `enableFeeAmount` on forks, is [`Custom`][FeeAmount::Custom]. Fee
+/// amounts are compared by value, so `Custom(500)` is equal to `Low`; use
+/// [`TryFrom<u32>`][FeeAmount::try_from] to create normalized fee amounts. Fee amounts are
+/// (de)serialized as their value.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(into = "u32", try_from = "u32")
+)]
 pub enum FeeAmount {
-    Lowest = 100,
-    Low = 500,
-    Medium = 3000,
-    High = 10000,
+    /// 0.01%
+    Lowest,
+    /// 0.05%
+    Low,
+    /// 0.3%
+    Medium,
+    /// 1%
+    High,
+    /// Any other fee amount. Must fit in 24 bits.
+    Custom(u32),
+}
+
+impl From<FeeAmount> for u32 {
+    fn from(fee: FeeAmount) -> Self {
+        fee.as_u32()
+    }
+}
+
+impl TryFrom<u32> for FeeAmount {
+    type Error = Error;
+
+    /// Returns [`Error::FeeTooLarge`] if `fee` does not fit in 24 bits.
+    fn try_from(fee: u32) -> Result<Self> {
+        Ok(match fee {
+            100 => Self::Lowest,
+            500 => Self::Low,
+            3000 => Self::Medium,
+            10000 => Self::High,
+            fee if fee > MAX_FEE => return Err(Error::FeeTooLarge(fee)),
+            fee => Self::Custom(fee),
+        })
+    }
+}
+
+impl fmt::Display for FeeAmount {
+    /// Formats the fee amount as a percentage, like `0.05%`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fee = self.as_u32();
+        let (whole, fraction) = (fee / 10_000, fee % 10_000);
+        if fraction == 0 {
+            f.pad(&format!("{whole}%"))
+        } else {
+            let fraction = format!("{fraction:04}");
+            f.pad(&format!("{whole}.{}%", fraction.trim_end_matches('0')))
+        }
+    }
+}
+
+impl FromStr for FeeAmount {
+    type Err = Error;
+
+    /// Parses a fee amount from its percentage, like `"0.05%"`, its value in hundredths of bips,
+    /// like `"500"`, or the name of a default fee amount, like `"low"`, ignoring case.
+    ///
+    /// Returns [`Error::InvalidFeeAmount`] if it is not a fee amount, and [`Error::FeeTooLarge`]
+    /// if it does not fit in 24 bits.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidFeeAmount(s.to_string());
+        let fee = match s.trim().to_lowercase().as_str() {
+            "lowest" => return Ok(Self::Lowest),
+            "low" => return Ok(Self::Low),
+            "medium" => return Ok(Self::Medium),
+            "high" => return Ok(Self::High),
+            fee => match fee.strip_suffix('%') {
+                Some(percent) => parse_percent(percent.trim_end()).ok_or_else(invalid)?,
+                None if fee.bytes().all(|b| b.is_ascii_digit()) => {
+                    fee.parse().map_err(|_| invalid())?
+                }
+                None => return Err(invalid()),
+            },
+        };
+        Self::try_from(fee)
+    }
+}
+
+/// Parses a percentage with at most 4 decimals into hundredths of bips, like `"0.05"` into `500`.
+fn parse_percent(percent: &str) -> Option<u32> {
+    let (whole, fraction) = percent.split_once('.').unwrap_or((percent, ""));
+    let fraction = fraction.trim_end_matches('0');
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || fraction.len() > 4 || !is_digits(whole) || !is_digits(fraction) {
+        return None;
+    }
+    let fraction = format!("{fraction:0<4}").parse::<u32>().ok()?;
+    whole.parse::<u32>().ok()?.checked_mul(10_000)?.checked_add(fraction)
+}
+
+impl PartialEq for FeeAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_u32() == other.as_u32()
+    }
+}
+
+impl Eq for FeeAmount {}
+
+impl Hash for FeeAmount {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_u32().hash(state)
+    }
+}
+
+impl PartialOrd for FeeAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FeeAmount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_u32().cmp(&other.as_u32())
+    }
 }
 
 impl FeeAmount {
+    /// The default factory enabled fee amounts.
+    pub const DEFAULTS: [Self; 4] = [Self::Lowest, Self::Low, Self::Medium, Self::High];
+
+    /// Returns the fee amount, in hundredths of bips.
+    pub const fn as_u32(&self) -> u32 {
+        match self {
+            Self::Lowest => 100,
+            Self::Low => 500,
+            Self::Medium => 3000,
+            Self::High => 10000,
+            Self::Custom(fee) => *fee,
+        }
+    }
+
     /// The default factory tick spacings by fee amount.
-    pub const fn tick_spacing(&self) -> usize {
+    ///
+    /// Returns None for [`Custom`][FeeAmount::Custom] fee amounts, whose tick spacing is set when
+    /// they are enabled on the factory, see [`Factory::fee_amount_tick_spacing`].
+    ///
+    /// [`Factory::fee_amount_tick_spacing`]: crate::v3::Factory::fee_amount_tick_spacing
+    pub const fn tick_spacing(&self) -> Option<i32> {
         match self {
-            Self::Lowest => 1,
-            Self::Low => 10,
-            Self::Medium => 60,
-            Self::High => 200,
+            Self::Lowest => Some(1),
+            Self::Low => Some(10),
+            Self::Medium => Some(60),
+            Self::High => Some(200),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+/// How to select the pool of a single hop swap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FeeSelection {
+    /// Use the pool with this fee amount.
+    Fixed(FeeAmount),
+    /// Use the pool with the most in-range liquidity, see [`Factory::best_pool`].
+    ///
+    /// [`Factory::best_pool`]: crate::v3::Factory::best_pool
+    #[default]
+    Auto,
+}
+
+impl From<FeeAmount> for FeeSelection {
+    fn from(fee: FeeAmount) -> Self {
+        Self::Fixed(fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_amount() {
+        for fee in FeeAmount::DEFAULTS {
+            assert_eq!(FeeAmount::try_from(fee.as_u32()).unwrap(), fee);
+            assert!(fee.tick_spacing().is_some());
+        }
+        assert!(matches!(FeeAmount::try_from(3000).unwrap(), FeeAmount::Medium));
+        assert!(matches!(FeeAmount::try_from(2500).unwrap(), FeeAmount::Custom(2500)));
+        assert!(matches!(FeeAmount::try_from(MAX_FEE).unwrap(), FeeAmount::Custom(MAX_FEE)));
+        assert!(matches!(FeeAmount::try_from(MAX_FEE + 1), Err(Error::FeeTooLarge(_))));
+
+        assert_eq!(FeeAmount::Custom(500), FeeAmount::Low);
+        assert_eq!(FeeAmount::Custom(2500).tick_spacing(), None);
+        assert_eq!(u32::from(FeeAmount::Custom(2500)), 2500);
+        assert!(FeeAmount::Low < FeeAmount::Custom(2500));
+        assert!(FeeAmount::Custom(2500) < FeeAmount::Medium);
+    }
+
+    #[test]
+    fn test_fee_amount_str() {
+        let fees =
+            [(FeeAmount::Lowest, "0.01%"), (FeeAmount::Low, "0.05%"), (FeeAmount::High, "1%")];
+        for (fee, s) in fees {
+            assert_eq!(fee.to_string(), s);
+            assert_eq!(s.parse::<FeeAmount>().unwrap(), fee);
+        }
+        assert_eq!(FeeAmount::Medium.to_string(), "0.3%");
+        assert_eq!(FeeAmount::Custom(2500).to_string(), "0.25%");
+        assert_eq!(FeeAmount::Custom(1).to_string(), "0.0001%");
+        assert_eq!(FeeAmount::Custom(12_345).to_string(), "1.2345%");
+
+        for s in ["500", "0.05%", "0.0500 %", " LOW "] {
+            assert!(matches!(s.parse(), Ok(FeeAmount::Low)), "{s}");
+        }
+        assert!(matches!("2500".parse(), Ok(FeeAmount::Custom(2500))));
+        assert!(matches!("1.2345%".parse(), Ok(FeeAmount::Custom(12_345))));
+        for s in ["", "%", "0.00001%", ".5%", "-1", "+500", "0x1f4", "lower"] {
+            assert!(matches!(s.parse::<FeeAmount>(), Err(Error::InvalidFeeAmount(_))), "{s}");
         }
+        assert!(matches!("1678%".parse::<FeeAmount>(), Err(Error::FeeTooLarge(_))));
+        assert!(matches!("100000000".parse::<FeeAmount>(), Err(Error::FeeTooLarge(_))));
     }
 }
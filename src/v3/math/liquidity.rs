@@ -0,0 +1,222 @@
+//! Conversions between token amounts and liquidity, ported from the [`LiquidityAmounts`] library.
+//!
+//! All the amounts are rounded down, like in the library. The pool rounds the amounts deposited
+//! by a mint up and the amounts withdrawn by a burn down, so minting `liquidity` deposits at most
+//! 1 more of each token than [`amounts_for_liquidity`].
+//!
+//! The sqrt ratios are `sqrtPriceX96`s, like those returned by
+//! [`get_sqrt_ratio_at_tick`][super::get_sqrt_ratio_at_tick], and the bounds of the range can be
+//! given in any order.
+//!
+//! [`LiquidityAmounts`]: https://github.com/Uniswap/v3-periphery/blob/main/contracts/libraries/LiquidityAmounts.sol
+
+use super::{mul_div, Q96};
+use crate::errors::{Error, Result};
+use ethers_core::types::U256;
+
+/// Returns the liquidity received for `amount0` of `token0` in the range
+/// `sqrt_ratio_a_x96..sqrt_ratio_b_x96`, like `getLiquidityForAmount0`.
+///
+/// Returns [`Error::Overflow`] if the ratios are equal or the liquidity overflows a `u128`.
+pub fn liquidity_for_amount0(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    amount0: U256,
+) -> Result<u128> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    let intermediate = mul_div(a, b, Q96)?;
+    to_u128(mul_div(amount0, intermediate, b - a)?)
+}
+
+/// Returns the liquidity received for `amount1` of `token1` in the range
+/// `sqrt_ratio_a_x96..sqrt_ratio_b_x96`, like `getLiquidityForAmount1`.
+///
+/// Returns [`Error::Overflow`] if the ratios are equal or the liquidity overflows a `u128`.
+pub fn liquidity_for_amount1(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    amount1: U256,
+) -> Result<u128> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    to_u128(mul_div(amount1, Q96, b - a)?)
+}
+
+/// Returns the maximum liquidity received for `amount0` of `token0` and `amount1` of `token1` in
+/// the range `sqrt_ratio_a_x96..sqrt_ratio_b_x96` at the current `sqrt_ratio_x96`, like
+/// `getLiquidityForAmounts`.
+///
+/// Returns [`Error::Overflow`] if the ratios are equal or the liquidity overflows a `u128`.
+pub fn liquidity_for_amounts(
+    sqrt_ratio_x96: U256,
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    amount0: U256,
+    amount1: U256,
+) -> Result<u128> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    if sqrt_ratio_x96 <= a {
+        liquidity_for_amount0(a, b, amount0)
+    } else if sqrt_ratio_x96 < b {
+        let liquidity0 = liquidity_for_amount0(sqrt_ratio_x96, b, amount0)?;
+        let liquidity1 = liquidity_for_amount1(a, sqrt_ratio_x96, amount1)?;
+        Ok(liquidity0.min(liquidity1))
+    } else {
+        liquidity_for_amount1(a, b, amount1)
+    }
+}
+
+/// Returns the amount of `token0` for `liquidity` in the range
+/// `sqrt_ratio_a_x96..sqrt_ratio_b_x96`, like `getAmount0ForLiquidity`.
+///
+/// Returns [`Error::Overflow`] if the lower ratio is zero.
+pub fn amount0_for_liquidity(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    liquidity: u128,
+) -> Result<U256> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    if a.is_zero() {
+        return Err(Error::Overflow);
+    }
+    Ok(mul_div(U256::from(liquidity) << 96, b - a, b)? / a)
+}
+
+/// Returns the amount of `token1` for `liquidity` in the range
+/// `sqrt_ratio_a_x96..sqrt_ratio_b_x96`, like `getAmount1ForLiquidity`.
+///
+/// Returns [`Error::Overflow`] if the amount overflows a [U256], which can't happen with valid
+/// sqrt ratios.
+pub fn amount1_for_liquidity(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    liquidity: u128,
+) -> Result<U256> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    mul_div(U256::from(liquidity), b - a, Q96)
+}
+
+/// Returns the amounts of `token0` and `token1` for `liquidity` in the range
+/// `sqrt_ratio_a_x96..sqrt_ratio_b_x96` at the current `sqrt_ratio_x96`, like
+/// `getAmountsForLiquidity`.
+///
+/// Returns [`Error::Overflow`] if the lower ratio is zero or an amount overflows a [U256].
+pub fn amounts_for_liquidity(
+    sqrt_ratio_x96: U256,
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    liquidity: u128,
+) -> Result<(U256, U256)> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    if sqrt_ratio_x96 <= a {
+        Ok((amount0_for_liquidity(a, b, liquidity)?, U256::zero()))
+    } else if sqrt_ratio_x96 < b {
+        let amount0 = amount0_for_liquidity(sqrt_ratio_x96, b, liquidity)?;
+        Ok((amount0, amount1_for_liquidity(a, sqrt_ratio_x96, liquidity)?))
+    } else {
+        Ok((U256::zero(), amount1_for_liquidity(a, b, liquidity)?))
+    }
+}
+
+fn sort(a: U256, b: U256) -> (U256, U256) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+fn to_u128(x: U256) -> Result<u128> {
+    u128::try_from(x).map_err(|_| Error::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3::math::{get_sqrt_ratio_at_tick, MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+
+    /// `encodePriceSqrt(reserve1, reserve0)` of the library's tests.
+    fn encode_price_sqrt(reserve1: u64, reserve0: u64) -> U256 {
+        ((U256::from(reserve1) << 192) / reserve0).integer_sqrt()
+    }
+
+    #[test]
+    fn test_liquidity_for_amounts() {
+        let (a, b) = (encode_price_sqrt(100, 110), encode_price_sqrt(110, 100));
+        let (amount0, amount1) = (U256::from(100), U256::from(200));
+
+        // in range
+        let price = encode_price_sqrt(1, 1);
+        assert_eq!(liquidity_for_amounts(price, a, b, amount0, amount1).unwrap(), 2148);
+        assert_eq!(liquidity_for_amounts(price, b, a, amount0, amount1).unwrap(), 2148);
+        // below the range, all token0
+        let price = encode_price_sqrt(99, 110);
+        assert_eq!(liquidity_for_amounts(price, a, b, amount0, amount1).unwrap(), 1048);
+        assert_eq!(liquidity_for_amounts(a, a, b, amount0, amount1).unwrap(), 1048);
+        // above the range, all token1
+        let price = encode_price_sqrt(111, 100);
+        assert_eq!(liquidity_for_amounts(price, a, b, amount0, amount1).unwrap(), 2097);
+        assert_eq!(liquidity_for_amounts(b, a, b, amount0, amount1).unwrap(), 2097);
+
+        assert!(matches!(liquidity_for_amount0(a, a, amount0), Err(Error::Overflow)));
+        assert!(matches!(liquidity_for_amount1(a, a, amount1), Err(Error::Overflow)));
+        let min = MIN_SQRT_RATIO;
+        assert!(matches!(liquidity_for_amount1(min, min + 1, U256::MAX), Err(Error::Overflow)));
+    }
+
+    #[test]
+    fn test_amounts_for_liquidity() {
+        let (a, b) = (encode_price_sqrt(100, 110), encode_price_sqrt(110, 100));
+
+        // in range
+        let price = encode_price_sqrt(1, 1);
+        let amounts = amounts_for_liquidity(price, a, b, 2148).unwrap();
+        assert_eq!(amounts, (99.into(), 99.into()));
+        // below the range, all token0
+        let price = encode_price_sqrt(99, 110);
+        assert_eq!(amounts_for_liquidity(price, a, b, 1048).unwrap(), (99.into(), 0.into()));
+        assert_eq!(amounts_for_liquidity(a, b, a, 1048).unwrap(), (99.into(), 0.into()));
+        // above the range, all token1
+        let price = encode_price_sqrt(111, 100);
+        assert_eq!(amounts_for_liquidity(price, a, b, 2097).unwrap(), (0.into(), 199.into()));
+        assert_eq!(amounts_for_liquidity(b, a, b, 2097).unwrap(), (0.into(), 199.into()));
+
+        assert!(matches!(amount0_for_liquidity(U256::zero(), b, 1), Err(Error::Overflow)));
+        let (min, max) = (MIN_SQRT_RATIO, MAX_SQRT_RATIO);
+        // full range
+        let amount0 = amount0_for_liquidity(min, max, u128::MAX).unwrap();
+        let expected = "6276865795046577716716727052920969657919881535178523893767";
+        assert_eq!(amount0, U256::from_dec_str(expected).unwrap());
+        let amount1 = amount1_for_liquidity(min, max, u128::MAX).unwrap();
+        let expected = "6276865796315986613307619852238232712829278890652951511957";
+        assert_eq!(amount1, U256::from_dec_str(expected).unwrap());
+        assert!(matches!(
+            amount1_for_liquidity(0.into(), U256::MAX, u128::MAX),
+            Err(Error::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // the liquidity of the amounts for a liquidity is never greater than it
+        let mut seed = 0x9e37_79b9_u32;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+        for _ in 0..256 {
+            let mut ticks =
+                [next() as i32 % 887272, next() as i32 % 887272, next() as i32 % 887272];
+            ticks.sort_unstable();
+            if ticks[0] == ticks[2] {
+                continue;
+            }
+            let [lower, price, upper] = ticks.map(|tick| get_sqrt_ratio_at_tick(tick).unwrap());
+            let liquidity = (next() as u128) << 32 | next() as u128;
+            let (amount0, amount1) = amounts_for_liquidity(price, lower, upper, liquidity).unwrap();
+            let res = liquidity_for_amounts(price, lower, upper, amount0, amount1).unwrap();
+            assert!(res <= liquidity, "{ticks:?}");
+        }
+    }
+}
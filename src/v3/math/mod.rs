@@ -0,0 +1,348 @@
+//! Uniswap V3 tick and price math, ported from the [`TickMath`] library.
+//!
+//! [`TickMath`]: https://github.com/Uniswap/v3-core/blob/main/contracts/libraries/TickMath.sol
+
+use crate::errors::{Error, Result};
+pub(crate) use crate::utils::to_f64;
+use ethers_core::types::{I256, U256, U512};
+
+pub mod liquidity;
+pub mod sqrt_price;
+pub mod swap;
+
+/// The minimum tick, `log_1.0001(2**-128)`.
+pub const MIN_TICK: i32 = -887272;
+
+/// The maximum tick, `log_1.0001(2**128)`.
+pub const MAX_TICK: i32 = -MIN_TICK;
+
+/// The minimum `sqrtPriceX96`, `get_sqrt_ratio_at_tick(MIN_TICK)`.
+pub const MIN_SQRT_RATIO: U256 = U256([4295128739, 0, 0, 0]);
+
+/// The maximum `sqrtPriceX96`, `get_sqrt_ratio_at_tick(MAX_TICK)`.
+pub const MAX_SQRT_RATIO: U256 = U256([0x5d951d5263988d26, 0xefd1fc6a50648849, 0xfffd8963, 0]);
+
+/// `2**96`, the denominator of the `Q64.96` fixed-point `sqrtPriceX96`.
+pub const Q96: U256 = U256([0, 1 << 32, 0, 0]);
+
+/// `sqrt(1.0001)**-(2**i)` as `Q128.128`, for each bit `i` of the absolute tick greater than 0.
+const RATIOS: [u128; 19] = [
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x9aa508b5b7a84e1c677de54f3e99bc9,
+    0x5d6af8dedb81196699c329225ee604,
+    0x2216e584f5fa1ea926041bedfe98,
+    0x48a170391f7dc42444e8fa2,
+];
+
+/// Returns `sqrt(1.0001**tick) * 2**96`, rounded up, like `TickMath.getSqrtRatioAtTick`.
+///
+/// Returns [`Error::InvalidTick`] if `tick` is not in the range `MIN_TICK..=MAX_TICK`.
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256> {
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK as u32 {
+        return Err(Error::InvalidTick(tick));
+    }
+
+    let mut ratio = if abs_tick & 1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001_u128)
+    } else {
+        U256::one() << 128
+    };
+    for (i, &r) in RATIOS.iter().enumerate() {
+        if abs_tick & (2 << i) != 0 {
+            ratio = (ratio * U256::from(r)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Q128.128 to Q128.96, rounding up
+    let round_up = !(ratio & U256::from(u32::MAX)).is_zero();
+    Ok((ratio >> 32) + u8::from(round_up))
+}
+
+/// Returns the greatest tick whose sqrt ratio is lower than or equal to `sqrt_price_x96`, like
+/// `TickMath.getTickAtSqrtRatio`.
+///
+/// Returns [`Error::InvalidSqrtRatio`] if `sqrt_price_x96` is not in the range
+/// `MIN_SQRT_RATIO..MAX_SQRT_RATIO`.
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: U256) -> Result<i32> {
+    if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+        return Err(Error::InvalidSqrtRatio(sqrt_price_x96));
+    }
+    let ratio = sqrt_price_x96 << 32;
+
+    // the most significant bit of ratio, at least 32 and less than 160
+    let msb = ratio.bits() - 1;
+    let mut r = if msb >= 128 { ratio >> (msb - 127) } else { ratio << (127 - msb) };
+
+    // the integer part of log2(ratio) - 128, then 14 bits of its fractional part as Q64.64, by
+    // repeatedly squaring the normalized ratio
+    let mut log_2 = (msb as i128 - 128) << 64;
+    for shift in (50..64).rev() {
+        r = (r * r) >> 127;
+        let f = (r >> 128).low_u32();
+        log_2 |= (f as i128) << shift;
+        r >>= f;
+    }
+
+    // log_sqrt(1.0001)(ratio) as Q128.128, and its error bounds
+    let log_sqrt10001 = I256::from(log_2) * I256::from(255738958999603826347141_u128);
+    let tick_low =
+        (log_sqrt10001 - I256::from(3402992956809132418596140100660247210_u128)).asr(128).low_i32();
+    let tick_high = (log_sqrt10001 + I256::from(291339464771989622907027621153398088495_u128))
+        .asr(128)
+        .low_i32();
+
+    if tick_low == tick_high || get_sqrt_ratio_at_tick(tick_high)? > sqrt_price_x96 {
+        Ok(tick_low)
+    } else {
+        Ok(tick_high)
+    }
+}
+
+/// Returns the price of `token0` in terms of `token1` at `sqrt_price_x96`, adjusted for the tokens'
+/// decimals.
+pub fn sqrt_ratio_to_price(sqrt_price_x96: U256, decimals0: u8, decimals1: u8) -> f64 {
+    let sqrt_price = to_f64(sqrt_price_x96) / to_f64(Q96);
+    sqrt_price * sqrt_price * 10f64.powi(decimals0 as i32 - decimals1 as i32)
+}
+
+/// Returns the price of `token0` in terms of `token1` at `tick`, `1.0001**tick`, adjusted for the
+/// tokens' decimals.
+///
+/// Returns [`Error::InvalidTick`] if `tick` is not in the range `MIN_TICK..=MAX_TICK`.
+pub fn tick_to_price(tick: i32, decimals0: u8, decimals1: u8) -> Result<f64> {
+    let sqrt_price_x96 = get_sqrt_ratio_at_tick(tick)?;
+    Ok(sqrt_ratio_to_price(sqrt_price_x96, decimals0, decimals1))
+}
+
+/// Returns the greatest tick whose price is lower than or equal to `price`, the price of `token0`
+/// in terms of `token1` adjusted for the tokens' decimals.
+///
+/// The price is converted to a `sqrtPriceX96` with the precision of a float, so it may be one tick
+/// off when `price` is very close to the price at a tick.
+///
+/// Returns [`Error::InvalidPrice`] if the price is not finite or not in the range of the prices at
+/// `MIN_TICK..MAX_TICK`.
+pub fn price_to_tick(price: f64, decimals0: u8, decimals1: u8) -> Result<i32> {
+    get_tick_at_sqrt_ratio(price_to_sqrt_ratio(price, decimals0, decimals1)?)
+}
+
+/// Returns the `sqrtPriceX96` of `price`, the price of `token0` in terms of `token1` adjusted for
+/// the tokens' decimals, with the precision of a float.
+///
+/// Returns [`Error::InvalidPrice`] if the price is not finite or not in the range of the prices at
+/// `MIN_TICK..MAX_TICK`.
+pub fn price_to_sqrt_ratio(price: f64, decimals0: u8, decimals1: u8) -> Result<U256> {
+    let raw_price = price * 10f64.powi(decimals1 as i32 - decimals0 as i32);
+    let sqrt_price_x96 = raw_price.sqrt() * to_f64(Q96);
+    // NaN fails both comparisons
+    if !(sqrt_price_x96 >= to_f64(MIN_SQRT_RATIO) && sqrt_price_x96 < to_f64(MAX_SQRT_RATIO)) {
+        return Err(Error::InvalidPrice(price));
+    }
+    Ok(from_f64(sqrt_price_x96).clamp(MIN_SQRT_RATIO, MAX_SQRT_RATIO - 1))
+}
+
+/// Returns the tick closest to `tick` which is a multiple of `tick_spacing`, rounding half up,
+/// within `MIN_TICK..=MAX_TICK`.
+///
+/// Returns [`Error::InvalidTick`] if `tick` is not in the range `MIN_TICK..=MAX_TICK`, and
+/// [`Error::InvalidTickSpacing`] if `tick_spacing` is not positive.
+pub fn nearest_usable_tick(tick: i32, tick_spacing: i32) -> Result<i32> {
+    if tick_spacing <= 0 {
+        return Err(Error::InvalidTickSpacing(tick_spacing));
+    }
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(Error::InvalidTick(tick));
+    }
+    // round(tick / tick_spacing) = floor((2 * tick + tick_spacing) / (2 * tick_spacing))
+    let (tick, tick_spacing) = (tick as i64, tick_spacing as i64);
+    let rounded = ((2 * tick + tick_spacing).div_euclid(2 * tick_spacing) * tick_spacing) as i32;
+    let tick_spacing = tick_spacing as i32;
+    if rounded < MIN_TICK {
+        Ok(rounded + tick_spacing)
+    } else if rounded > MAX_TICK {
+        Ok(rounded - tick_spacing)
+    } else {
+        Ok(rounded)
+    }
+}
+
+/// Returns `a * b / denominator` rounded down, with full precision, like `FullMath.mulDiv`.
+///
+/// Returns [`Error::Overflow`] if `denominator` is zero or the result overflows a [U256].
+pub(crate) fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256> {
+    if denominator.is_zero() {
+        return Err(Error::Overflow);
+    }
+    U256::try_from(a.full_mul(b) / U512::from(denominator)).map_err(|_| Error::Overflow)
+}
+
+/// Returns `a * b / denominator` rounded up, with full precision, like
+/// `FullMath.mulDivRoundingUp`.
+///
+/// Returns [`Error::Overflow`] if `denominator` is zero or the result overflows a [U256].
+pub(crate) fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Result<U256> {
+    if denominator.is_zero() {
+        return Err(Error::Overflow);
+    }
+    let (quotient, remainder) = a.full_mul(b).div_mod(U512::from(denominator));
+    let quotient = U256::try_from(quotient).map_err(|_| Error::Overflow)?;
+    if remainder.is_zero() {
+        Ok(quotient)
+    } else {
+        quotient.checked_add(U256::one()).ok_or(Error::Overflow)
+    }
+}
+
+/// Returns `a / b` rounded up, like `UnsafeMath.divRoundingUp`.
+///
+/// Returns [`Error::Overflow`] if `b` is zero.
+pub(crate) fn div_rounding_up(a: U256, b: U256) -> Result<U256> {
+    if b.is_zero() {
+        return Err(Error::Overflow);
+    }
+    let (quotient, remainder) = a.div_mod(b);
+    Ok(if remainder.is_zero() { quotient } else { quotient + 1 })
+}
+
+/// Returns `liquidity` plus the signed `delta`, like `LiquidityMath.addDelta`.
+///
+/// Returns [`Error::Overflow`] if the result underflows or overflows a `u128`.
+pub(crate) fn add_delta(liquidity: u128, delta: i128) -> Result<u128> {
+    if delta < 0 {
+        liquidity.checked_sub(delta.unsigned_abs())
+    } else {
+        liquidity.checked_add(delta as u128)
+    }
+    .ok_or(Error::Overflow)
+}
+
+/// Conversion of a non-negative [f64] lower than `2**256` into a [U256], truncating its fractional
+/// part.
+fn from_f64(value: f64) -> U256 {
+    let bits = value.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mantissa = if exponent == 0 { 0 } else { (bits & ((1 << 52) - 1)) | (1 << 52) };
+    let shift = exponent - 1075;
+    if shift >= 0 {
+        U256::from(mantissa) << shift
+    } else if shift > -64 {
+        U256::from(mantissa >> -shift)
+    } else {
+        U256::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(tick, getSqrtRatioAtTick(tick))`, computed with `TickMath`.
+    const TICKS: &str = include_str!("./ticks.csv");
+
+    fn ticks() -> impl Iterator<Item = (i32, U256)> {
+        TICKS.lines().filter(|line| !line.starts_with('#')).map(|line| {
+            let (tick, ratio) = line.split_once(',').unwrap();
+            (tick.parse().unwrap(), U256::from_dec_str(ratio).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_sqrt_ratio_at_tick() {
+        assert_eq!(ticks().count(), 400);
+        for (tick, ratio) in ticks() {
+            assert_eq!(get_sqrt_ratio_at_tick(tick).unwrap(), ratio, "{tick}");
+        }
+
+        assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(0).unwrap(), Q96);
+        assert!(matches!(get_sqrt_ratio_at_tick(MIN_TICK - 1), Err(Error::InvalidTick(_))));
+        assert!(matches!(get_sqrt_ratio_at_tick(MAX_TICK + 1), Err(Error::InvalidTick(_))));
+        assert!(matches!(get_sqrt_ratio_at_tick(i32::MIN), Err(Error::InvalidTick(_))));
+    }
+
+    #[test]
+    fn test_tick_at_sqrt_ratio() {
+        for (tick, ratio) in ticks().filter(|&(tick, _)| tick < MAX_TICK) {
+            assert_eq!(get_tick_at_sqrt_ratio(ratio).unwrap(), tick, "{tick}");
+            assert_eq!(get_tick_at_sqrt_ratio(ratio + 1).unwrap(), tick, "{tick}");
+            // rounds down: just below a tick's ratio is the previous tick
+            if tick > MIN_TICK {
+                assert_eq!(get_tick_at_sqrt_ratio(ratio - 1).unwrap(), tick - 1, "{tick}");
+            }
+        }
+
+        assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO).unwrap(), MIN_TICK);
+        assert_eq!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - 1).unwrap(), MAX_TICK - 1);
+        for ratio in [U256::zero(), MIN_SQRT_RATIO - 1, MAX_SQRT_RATIO, U256::MAX] {
+            assert!(matches!(get_tick_at_sqrt_ratio(ratio), Err(Error::InvalidSqrtRatio(_))));
+        }
+    }
+
+    #[test]
+    fn test_price() {
+        assert_eq!(tick_to_price(0, 18, 18).unwrap(), 1.0);
+        let price = tick_to_price(1, 18, 18).unwrap();
+        assert!((price - 1.0001).abs() < 1e-12);
+
+        // USDC/WETH: 1 USDC = 1 / 2000 WETH
+        let tick = price_to_tick(1.0 / 2000.0, 6, 18).unwrap();
+        assert_eq!(tick, 200311);
+        let price = tick_to_price(tick, 6, 18).unwrap();
+        assert!(price <= 1.0 / 2000.0 && price > 1.0 / 2000.0 / 1.0001, "{price}");
+
+        // between two ticks
+        for tick in [MIN_TICK, -200311, -1, 0, 1, 46054, 200311, MAX_TICK - 1] {
+            let price = tick_to_price(tick, 0, 0).unwrap() * 1.00005;
+            assert_eq!(price_to_tick(price, 0, 0).unwrap(), tick);
+        }
+
+        assert_eq!(price_to_sqrt_ratio(1.0, 18, 18).unwrap(), Q96);
+        assert_eq!(price_to_sqrt_ratio(4.0, 0, 0).unwrap(), Q96 * 2);
+
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-39, 1e39] {
+            assert!(matches!(price_to_tick(price, 0, 0), Err(Error::InvalidPrice(_))), "{price}");
+            let res = price_to_sqrt_ratio(price, 0, 0);
+            assert!(matches!(res, Err(Error::InvalidPrice(_))), "{price}");
+        }
+    }
+
+    #[test]
+    fn test_nearest_usable_tick() {
+        assert_eq!(nearest_usable_tick(0, 60).unwrap(), 0);
+        assert_eq!(nearest_usable_tick(29, 60).unwrap(), 0);
+        assert_eq!(nearest_usable_tick(30, 60).unwrap(), 60);
+        assert_eq!(nearest_usable_tick(-30, 60).unwrap(), 0);
+        assert_eq!(nearest_usable_tick(-31, 60).unwrap(), -60);
+        assert_eq!(nearest_usable_tick(5, 3).unwrap(), 6);
+        assert_eq!(nearest_usable_tick(-4, 3).unwrap(), -3);
+        assert_eq!(nearest_usable_tick(-5, 3).unwrap(), -6);
+        assert_eq!(nearest_usable_tick(MIN_TICK, 1).unwrap(), MIN_TICK);
+        assert_eq!(nearest_usable_tick(MAX_TICK, 1).unwrap(), MAX_TICK);
+        assert_eq!(nearest_usable_tick(MIN_TICK, 60).unwrap(), -887220);
+        assert_eq!(nearest_usable_tick(MAX_TICK, 60).unwrap(), 887220);
+        assert_eq!(nearest_usable_tick(MAX_TICK, 200).unwrap(), 887200);
+
+        assert!(matches!(nearest_usable_tick(0, 0), Err(Error::InvalidTickSpacing(0))));
+        assert!(matches!(nearest_usable_tick(0, -60), Err(Error::InvalidTickSpacing(-60))));
+        assert!(matches!(nearest_usable_tick(MAX_TICK + 1, 1), Err(Error::InvalidTick(_))));
+    }
+}
@@ -0,0 +1,232 @@
+//! Price movements and amount deltas between prices, ported from the [`SqrtPriceMath`] library.
+//!
+//! Like in the library, the amounts are rounded in favor of the pool: up for the amounts paid to
+//! it and down for those paid by it. The prices move by at most the amounts given.
+//!
+//! [`SqrtPriceMath`]: https://github.com/Uniswap/v3-core/blob/main/contracts/libraries/SqrtPriceMath.sol
+
+use super::{div_rounding_up, mul_div, mul_div_rounding_up, Q96};
+use crate::errors::{Error, Result};
+use ethers_core::types::U256;
+
+/// `2**160 - 1`, the maximum `uint160`.
+const MAX_U160: U256 = U256([u64::MAX, u64::MAX, u32::MAX as u64, 0]);
+
+/// Returns the price after adding or removing `amount` of `token0` at `sqrt_price_x96`, rounded up,
+/// like `getNextSqrtPriceFromAmount0RoundingUp`.
+///
+/// Returns [`Error::InsufficientLiquidity`] if `liquidity` can't provide `amount` to remove, and
+/// [`Error::Overflow`] if the price overflows a `uint160`.
+pub fn next_sqrt_price_from_amount0_rounding_up(
+    sqrt_price_x96: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Result<U256> {
+    if amount.is_zero() {
+        return Ok(sqrt_price_x96);
+    }
+    let numerator1 = U256::from(liquidity) << 96;
+    let product = amount.checked_mul(sqrt_price_x96);
+    if add {
+        if let Some(denominator) = product.and_then(|product| numerator1.checked_add(product)) {
+            return mul_div_rounding_up(numerator1, sqrt_price_x96, denominator);
+        }
+        let denominator =
+            (numerator1 / sqrt_price_x96).checked_add(amount).ok_or(Error::Overflow)?;
+        div_rounding_up(numerator1, denominator)
+    } else {
+        let product = match product {
+            Some(product) if numerator1 > product => product,
+            _ => return Err(Error::InsufficientLiquidity),
+        };
+        to_u160(mul_div_rounding_up(numerator1, sqrt_price_x96, numerator1 - product)?)
+    }
+}
+
+/// Returns the price after adding or removing `amount` of `token1` at `sqrt_price_x96`, rounded
+/// down, like `getNextSqrtPriceFromAmount1RoundingDown`.
+///
+/// Returns [`Error::InsufficientLiquidity`] if `liquidity` can't provide `amount` to remove, and
+/// [`Error::Overflow`] if the price overflows a `uint160`.
+pub fn next_sqrt_price_from_amount1_rounding_down(
+    sqrt_price_x96: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Result<U256> {
+    let liquidity = U256::from(liquidity);
+    if add {
+        let quotient = if amount <= MAX_U160 {
+            (amount << 96).checked_div(liquidity).ok_or(Error::Overflow)?
+        } else {
+            mul_div(amount, Q96, liquidity)?
+        };
+        to_u160(sqrt_price_x96.checked_add(quotient).ok_or(Error::Overflow)?)
+    } else {
+        let quotient = if amount <= MAX_U160 {
+            div_rounding_up(amount << 96, liquidity)?
+        } else {
+            mul_div_rounding_up(amount, Q96, liquidity)?
+        };
+        if sqrt_price_x96 <= quotient {
+            return Err(Error::InsufficientLiquidity);
+        }
+        Ok(sqrt_price_x96 - quotient)
+    }
+}
+
+/// Returns the price after swapping `amount_in` of `token0`, if `zero_for_one`, or `token1` at
+/// `sqrt_price_x96`, like `getNextSqrtPriceFromInput`.
+///
+/// Returns [`Error::InsufficientLiquidity`] if the price or liquidity is zero.
+pub fn next_sqrt_price_from_input(
+    sqrt_price_x96: U256,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Result<U256> {
+    if sqrt_price_x96.is_zero() || liquidity == 0 {
+        return Err(Error::InsufficientLiquidity);
+    }
+    if zero_for_one {
+        next_sqrt_price_from_amount0_rounding_up(sqrt_price_x96, liquidity, amount_in, true)
+    } else {
+        next_sqrt_price_from_amount1_rounding_down(sqrt_price_x96, liquidity, amount_in, true)
+    }
+}
+
+/// Returns the price after swapping for `amount_out` of `token1`, if `zero_for_one`, or `token0`
+/// at `sqrt_price_x96`, like `getNextSqrtPriceFromOutput`.
+///
+/// Returns [`Error::InsufficientLiquidity`] if the price or liquidity is zero, or if `liquidity`
+/// can't provide `amount_out`.
+pub fn next_sqrt_price_from_output(
+    sqrt_price_x96: U256,
+    liquidity: u128,
+    amount_out: U256,
+    zero_for_one: bool,
+) -> Result<U256> {
+    if sqrt_price_x96.is_zero() || liquidity == 0 {
+        return Err(Error::InsufficientLiquidity);
+    }
+    if zero_for_one {
+        next_sqrt_price_from_amount1_rounding_down(sqrt_price_x96, liquidity, amount_out, false)
+    } else {
+        next_sqrt_price_from_amount0_rounding_up(sqrt_price_x96, liquidity, amount_out, false)
+    }
+}
+
+/// Returns the amount of `token0` between the prices `sqrt_ratio_a_x96` and `sqrt_ratio_b_x96`,
+/// in any order, for `liquidity`, like `getAmount0Delta`.
+///
+/// Returns [`Error::Overflow`] if the lower price is zero or the amount overflows a [U256].
+pub fn amount0_delta(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    if a.is_zero() {
+        return Err(Error::Overflow);
+    }
+    let numerator1 = U256::from(liquidity) << 96;
+    if round_up {
+        div_rounding_up(mul_div_rounding_up(numerator1, b - a, b)?, a)
+    } else {
+        Ok(mul_div(numerator1, b - a, b)? / a)
+    }
+}
+
+/// Returns the amount of `token1` between the prices `sqrt_ratio_a_x96` and `sqrt_ratio_b_x96`,
+/// in any order, for `liquidity`, like `getAmount1Delta`.
+///
+/// Returns [`Error::Overflow`] if the amount overflows a [U256].
+pub fn amount1_delta(
+    sqrt_ratio_a_x96: U256,
+    sqrt_ratio_b_x96: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256> {
+    let (a, b) = sort(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    if round_up {
+        mul_div_rounding_up(U256::from(liquidity), b - a, Q96)
+    } else {
+        mul_div(U256::from(liquidity), b - a, Q96)
+    }
+}
+
+fn sort(a: U256, b: U256) -> (U256, U256) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+fn to_u160(x: U256) -> Result<U256> {
+    if x > MAX_U160 {
+        Err(Error::Overflow)
+    } else {
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `encodePriceSqrt(reserve1, reserve0)` of the library's tests.
+    fn encode_price_sqrt(reserve1: u64, reserve0: u64) -> U256 {
+        ((U256::from(reserve1) << 192) / reserve0).integer_sqrt()
+    }
+
+    #[test]
+    fn test_next_sqrt_price() {
+        let price = encode_price_sqrt(1, 1);
+        let (liquidity, amount) = (10u128.pow(18), U256::exp10(17));
+
+        // from the library's tests
+        let next = next_sqrt_price_from_input(price, liquidity, amount, false).unwrap();
+        assert_eq!(next, U256::from_dec_str("87150978765690771352898345369").unwrap());
+        let next = next_sqrt_price_from_input(price, liquidity, amount, true).unwrap();
+        assert_eq!(next, U256::from_dec_str("72025602285694852357767227579").unwrap());
+        let next = next_sqrt_price_from_output(price, liquidity, amount, false).unwrap();
+        assert_eq!(next, U256::from_dec_str("88031291682515930659493278152").unwrap());
+        let next = next_sqrt_price_from_output(price, liquidity, amount, true).unwrap();
+        assert_eq!(next, U256::from_dec_str("71305346262837903834189555302").unwrap());
+
+        // amounts greater than a uint160
+        let next = next_sqrt_price_from_input(price, 1, U256::MAX >> 1, true).unwrap();
+        assert_eq!(next, U256::one());
+        let res = next_sqrt_price_from_input(MAX_U160, 1024, U256::from(1024), false);
+        assert!(matches!(res, Err(Error::Overflow)));
+        // the output can't be provided
+        let res = next_sqrt_price_from_output(price, 1, U256::from(4), false);
+        assert!(matches!(res, Err(Error::InsufficientLiquidity)));
+        let res = next_sqrt_price_from_output(price, 1, U256::from(1), true);
+        assert!(matches!(res, Err(Error::InsufficientLiquidity)));
+        let res = next_sqrt_price_from_input(price, 0, amount, true);
+        assert!(matches!(res, Err(Error::InsufficientLiquidity)));
+        assert_eq!(next_sqrt_price_from_input(price, 1, U256::zero(), true).unwrap(), price);
+    }
+
+    #[test]
+    fn test_amount_deltas() {
+        let (a, b) = (encode_price_sqrt(1, 1), encode_price_sqrt(121, 100));
+        let liquidity = 10u128.pow(18);
+
+        // from the library's tests
+        let amount0 = amount0_delta(a, b, liquidity, true).unwrap();
+        assert_eq!(amount0, U256::from(90909090909090910u64));
+        assert_eq!(amount0_delta(b, a, liquidity, false).unwrap(), amount0 - 1);
+        let amount1 = amount1_delta(a, b, liquidity, true).unwrap();
+        assert_eq!(amount1, U256::from(100000000000000000u64));
+        assert_eq!(amount1_delta(b, a, liquidity, false).unwrap(), amount1 - 1);
+
+        assert_eq!(amount0_delta(a, a, liquidity, true).unwrap(), U256::zero());
+        assert_eq!(amount1_delta(a, b, 0, true).unwrap(), U256::zero());
+        assert!(matches!(amount0_delta(U256::zero(), b, 1, true), Err(Error::Overflow)));
+    }
+}
@@ -0,0 +1,570 @@
+//! Offline swap simulation, ported from the [`SwapMath`] library and the pool's `swap` loop.
+//!
+//! The initialized ticks are read from a [`TickDataProvider`], like the [`TickData`] loaded by
+//! [`Pool::load_ticks`][crate::v3::Pool::load_ticks], so that a swap can be simulated without
+//! calling a quoter. The result is exact as long as the pool's state has not changed since it was
+//! fetched.
+//!
+//! [`SwapMath`]: https://github.com/Uniswap/v3-core/blob/main/contracts/libraries/SwapMath.sol
+
+use super::{
+    add_delta, get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, mul_div, mul_div_rounding_up,
+    sqrt_price::{
+        amount0_delta, amount1_delta, next_sqrt_price_from_input, next_sqrt_price_from_output,
+    },
+    MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO, MIN_TICK,
+};
+use crate::{
+    errors::{Error, Result},
+    v3::{FeeAmount, PoolState},
+};
+use ethers_core::types::{I256, U256};
+use std::collections::BTreeMap;
+
+/// The fee denominator, in hundredths of a basis point.
+const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// The result of a swap within a single range of liquidity, returned by [`compute_swap_step`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SwapStep {
+    /// The price after the step, which may be the target price.
+    pub sqrt_ratio_next_x96: U256,
+    /// The amount swapped in, excluding the fee.
+    pub amount_in: U256,
+    /// The amount swapped out.
+    pub amount_out: U256,
+    /// The fee paid on the amount swapped in.
+    pub fee_amount: U256,
+}
+
+/// Returns the result of swapping `amount_remaining` from `sqrt_ratio_current_x96` towards
+/// `sqrt_ratio_target_x96` with `liquidity`, like `SwapMath.computeSwapStep`.
+///
+/// A positive `amount_remaining` is an exact input, including the fee, and a negative one an exact
+/// output. `fee_pips` is the fee in hundredths of a basis point.
+///
+/// Returns [`Error::InvalidFee`] if the fee is 100% or more, [`Error::InsufficientLiquidity`] if
+/// the price can't move, and [`Error::Overflow`] if an amount overflows.
+pub fn compute_swap_step(
+    sqrt_ratio_current_x96: U256,
+    sqrt_ratio_target_x96: U256,
+    liquidity: u128,
+    amount_remaining: I256,
+    fee_pips: u32,
+) -> Result<SwapStep> {
+    if fee_pips >= FEE_DENOMINATOR {
+        return Err(Error::InvalidFee);
+    }
+    let (current, target) = (sqrt_ratio_current_x96, sqrt_ratio_target_x96);
+    let zero_for_one = current >= target;
+    let exact_in = !amount_remaining.is_negative();
+    let amount_remaining_abs = amount_remaining.into_sign_and_abs().1;
+    let (fee, fee_complement) = (U256::from(fee_pips), U256::from(FEE_DENOMINATOR - fee_pips));
+
+    let mut amount_in = U256::zero();
+    let mut amount_out = U256::zero();
+    let next = if exact_in {
+        let amount_remaining_less_fee =
+            mul_div(amount_remaining_abs, fee_complement, FEE_DENOMINATOR.into())?;
+        amount_in = if zero_for_one {
+            amount0_delta(target, current, liquidity, true)?
+        } else {
+            amount1_delta(current, target, liquidity, true)?
+        };
+        if amount_remaining_less_fee >= amount_in {
+            target
+        } else {
+            next_sqrt_price_from_input(current, liquidity, amount_remaining_less_fee, zero_for_one)?
+        }
+    } else {
+        amount_out = if zero_for_one {
+            amount1_delta(target, current, liquidity, false)?
+        } else {
+            amount0_delta(current, target, liquidity, false)?
+        };
+        if amount_remaining_abs >= amount_out {
+            target
+        } else {
+            next_sqrt_price_from_output(current, liquidity, amount_remaining_abs, zero_for_one)?
+        }
+    };
+
+    // whether the target price is reached
+    let max = next == target;
+    if zero_for_one {
+        if !(max && exact_in) {
+            amount_in = amount0_delta(next, current, liquidity, true)?;
+        }
+        if !max || exact_in {
+            amount_out = amount1_delta(next, current, liquidity, false)?;
+        }
+    } else {
+        if !(max && exact_in) {
+            amount_in = amount1_delta(current, next, liquidity, true)?;
+        }
+        if !max || exact_in {
+            amount_out = amount0_delta(current, next, liquidity, false)?;
+        }
+    }
+    // the output can't be greater than the remaining amount
+    if !exact_in && amount_out > amount_remaining_abs {
+        amount_out = amount_remaining_abs;
+    }
+
+    let fee_amount = if exact_in && next != target {
+        // the remainder of the maximum input is taken as fee
+        amount_remaining_abs - amount_in
+    } else {
+        mul_div_rounding_up(amount_in, fee, fee_complement)?
+    };
+    Ok(SwapStep { sqrt_ratio_next_x96: next, amount_in, amount_out, fee_amount })
+}
+
+/// A source of the initialized ticks of a pool, like its `tickBitmap` and `ticks` mappings.
+pub trait TickDataProvider {
+    /// Returns the tick spacing of the pool.
+    fn tick_spacing(&self) -> i32;
+
+    /// Returns the word of the tick bitmap at `word_position`, whose bits are the initialized
+    /// ticks, divided by the tick spacing, from `word_position * 256`.
+    ///
+    /// Returns [`Error::TickWordNotLoaded`] if the word is not available.
+    fn bitmap_word(&self, word_position: i16) -> Result<U256>;
+
+    /// Returns the net liquidity added when crossing the initialized `tick` from left to right.
+    ///
+    /// Returns [`Error::TickWordNotLoaded`] if the tick is not available.
+    fn liquidity_net(&self, tick: i32) -> Result<i128>;
+
+    /// Returns the next initialized tick in the same word of the tick bitmap as `tick`, to its left
+    /// (or at it) if `lte`, or to its right otherwise, like
+    /// `TickBitmap.nextInitializedTickWithinOneWord`. If there is none, returns the last tick of
+    /// the word and false.
+    fn next_initialized_tick_within_one_word(&self, tick: i32, lte: bool) -> Result<(i32, bool)> {
+        let tick_spacing = self.tick_spacing();
+        let compressed = tick.div_euclid(tick_spacing);
+        if lte {
+            let (word_position, bit_position) = position(compressed);
+            // all the bits at or to the right of the current one
+            let mask = (U256::one() << bit_position) - 1 + (U256::one() << bit_position);
+            let masked = self.bitmap_word(word_position)? & mask;
+            let initialized = !masked.is_zero();
+            let next = if initialized {
+                let most_significant_bit = 255 - masked.leading_zeros() as i32;
+                compressed - (bit_position as i32 - most_significant_bit)
+            } else {
+                compressed - bit_position as i32
+            };
+            Ok((next * tick_spacing, initialized))
+        } else {
+            // start from the word of the next tick, since the current one is already crossed
+            let (word_position, bit_position) = position(compressed + 1);
+            // all the bits at or to the left of the current one
+            let mask = !((U256::one() << bit_position) - 1);
+            let masked = self.bitmap_word(word_position)? & mask;
+            let initialized = !masked.is_zero();
+            let next = if initialized {
+                let least_significant_bit = masked.trailing_zeros() as i32;
+                compressed + 1 + (least_significant_bit - bit_position as i32)
+            } else {
+                compressed + 1 + (255 - bit_position as i32)
+            };
+            Ok((next * tick_spacing, initialized))
+        }
+    }
+}
+
+/// The initialized ticks of a pool in a range of words of its tick bitmap, returned by
+/// [`Pool::load_ticks`][crate::v3::Pool::load_ticks].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TickData {
+    /// The tick spacing of the pool.
+    pub tick_spacing: i32,
+    /// The loaded words of the tick bitmap, by position.
+    pub words: BTreeMap<i16, U256>,
+    /// The net liquidity of the initialized ticks in the loaded words, by tick.
+    pub liquidity_net: BTreeMap<i32, i128>,
+}
+
+impl TickData {
+    /// Creates a new instance without any loaded words.
+    pub fn new(tick_spacing: i32) -> Self {
+        Self { tick_spacing, ..Default::default() }
+    }
+
+    /// Returns the position of the word of the tick bitmap which contains `tick`.
+    pub fn word_position(tick: i32, tick_spacing: i32) -> i16 {
+        position(tick.div_euclid(tick_spacing)).0
+    }
+}
+
+impl TickDataProvider for TickData {
+    fn tick_spacing(&self) -> i32 {
+        self.tick_spacing
+    }
+
+    fn bitmap_word(&self, word_position: i16) -> Result<U256> {
+        self.words.get(&word_position).copied().ok_or(Error::TickWordNotLoaded(word_position))
+    }
+
+    fn liquidity_net(&self, tick: i32) -> Result<i128> {
+        match self.liquidity_net.get(&tick) {
+            Some(&liquidity_net) => Ok(liquidity_net),
+            None => Err(Error::TickWordNotLoaded(Self::word_position(tick, self.tick_spacing))),
+        }
+    }
+}
+
+/// The result of a swap simulated with [`simulate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SimulatedSwap {
+    /// The amount swapped in, including the fee.
+    pub amount_in: U256,
+    /// The amount swapped out.
+    pub amount_out: U256,
+    /// The price of the pool after the swap.
+    pub sqrt_price_x96_after: U256,
+    /// The tick of the pool after the swap.
+    pub tick_after: i32,
+    /// The in-range liquidity of the pool after the swap.
+    pub liquidity_after: u128,
+    /// The number of initialized ticks crossed by the swap.
+    ///
+    /// Note: unlike `QuoterV2`'s, this does not count an initialized tick at which the swap ends
+    /// without crossing it.
+    pub initialized_ticks_crossed: u32,
+}
+
+/// Simulates a swap of `amount_specified` in a pool with `state`, `fee` and the initialized ticks
+/// of `ticks`, like the pool's `swap` method.
+///
+/// A positive `amount_specified` is an exact input and a negative one an exact output of `token1`,
+/// if `zero_for_one`, or `token0`. The swap stops at `sqrt_price_limit_x96`, if set, even if the
+/// amount is not fully swapped, like when the pool runs out of liquidity.
+///
+/// Returns [`Error::ZeroAmount`] if `amount_specified` is zero, [`Error::InvalidSqrtRatio`] if the
+/// price limit is on the wrong side of the current price or out of range, and
+/// [`Error::TickWordNotLoaded`] if the swap reaches a word of the tick bitmap which is not
+/// provided by `ticks`.
+pub fn simulate(
+    state: &PoolState,
+    fee: FeeAmount,
+    ticks: &impl TickDataProvider,
+    amount_specified: I256,
+    zero_for_one: bool,
+    sqrt_price_limit_x96: Option<U256>,
+) -> Result<SimulatedSwap> {
+    if amount_specified.is_zero() {
+        return Err(Error::ZeroAmount);
+    }
+    let limit = match sqrt_price_limit_x96 {
+        Some(limit) => limit,
+        None if zero_for_one => MIN_SQRT_RATIO + 1,
+        None => MAX_SQRT_RATIO - 1,
+    };
+    check_sqrt_price_limit(limit, state.sqrt_price_x96, zero_for_one)?;
+
+    let exact_input = !amount_specified.is_negative();
+    let mut amount_specified_remaining = amount_specified;
+    let mut amount_calculated = I256::zero();
+    let mut sqrt_price_x96 = state.sqrt_price_x96;
+    let mut tick = state.tick;
+    let mut liquidity = state.liquidity;
+    let mut initialized_ticks_crossed = 0;
+
+    while !amount_specified_remaining.is_zero() && sqrt_price_x96 != limit {
+        let sqrt_price_start_x96 = sqrt_price_x96;
+        let (tick_next, initialized) =
+            ticks.next_initialized_tick_within_one_word(tick, zero_for_one)?;
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_x96 = get_sqrt_ratio_at_tick(tick_next)?;
+        let target =
+            if zero_for_one == (sqrt_price_next_x96 < limit) { limit } else { sqrt_price_next_x96 };
+
+        let step = compute_swap_step(
+            sqrt_price_x96,
+            target,
+            liquidity,
+            amount_specified_remaining,
+            fee.as_u32(),
+        )?;
+        sqrt_price_x96 = step.sqrt_ratio_next_x96;
+        if exact_input {
+            let amount_in = to_i256(step.amount_in.checked_add(step.fee_amount))?;
+            amount_specified_remaining =
+                checked(amount_specified_remaining.checked_sub(amount_in))?;
+            amount_calculated =
+                checked(amount_calculated.checked_sub(to_i256(Some(step.amount_out))?))?;
+        } else {
+            amount_specified_remaining =
+                checked(amount_specified_remaining.checked_add(to_i256(Some(step.amount_out))?))?;
+            let amount_in = to_i256(step.amount_in.checked_add(step.fee_amount))?;
+            amount_calculated = checked(amount_calculated.checked_add(amount_in))?;
+        }
+
+        if sqrt_price_x96 == sqrt_price_next_x96 {
+            // the next tick is reached
+            if initialized {
+                let liquidity_net = ticks.liquidity_net(tick_next)?;
+                let liquidity_net = if zero_for_one { -liquidity_net } else { liquidity_net };
+                liquidity = add_delta(liquidity, liquidity_net)?;
+                initialized_ticks_crossed += 1;
+            }
+            tick = if zero_for_one { tick_next - 1 } else { tick_next };
+        } else if sqrt_price_x96 != sqrt_price_start_x96 {
+            // the price moved within the range
+            tick = get_tick_at_sqrt_ratio(sqrt_price_x96)?;
+        }
+    }
+
+    let amount_swapped = checked(amount_specified.checked_sub(amount_specified_remaining))?;
+    let (amount_in, amount_out) = if exact_input {
+        (amount_swapped, -amount_calculated)
+    } else {
+        (amount_calculated, -amount_swapped)
+    };
+    Ok(SimulatedSwap {
+        amount_in: amount_in.into_raw(),
+        amount_out: amount_out.into_raw(),
+        sqrt_price_x96_after: sqrt_price_x96,
+        tick_after: tick,
+        liquidity_after: liquidity,
+        initialized_ticks_crossed,
+    })
+}
+
+/// Checks that `sqrt_price_limit_x96` is a valid price limit for a swap from `sqrt_price_x96`, like
+/// the pool's `swap` method: below the price if `zero_for_one`, above it otherwise, and strictly
+/// within `MIN_SQRT_RATIO..MAX_SQRT_RATIO`.
+///
+/// Returns [`Error::InvalidSqrtRatio`] if it is not.
+pub fn check_sqrt_price_limit(
+    sqrt_price_limit_x96: U256,
+    sqrt_price_x96: U256,
+    zero_for_one: bool,
+) -> Result<()> {
+    let limit = sqrt_price_limit_x96;
+    let valid = if zero_for_one {
+        limit < sqrt_price_x96 && limit > MIN_SQRT_RATIO
+    } else {
+        limit > sqrt_price_x96 && limit < MAX_SQRT_RATIO
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSqrtRatio(limit))
+    }
+}
+
+/// Returns the word and bit positions of a tick divided by the tick spacing in the tick bitmap,
+/// like `TickBitmap.position`.
+fn position(compressed: i32) -> (i16, u8) {
+    ((compressed >> 8) as i16, (compressed & 0xff) as u8)
+}
+
+fn to_i256(x: Option<U256>) -> Result<I256> {
+    x.and_then(|x| I256::try_from(x).ok()).ok_or(Error::Overflow)
+}
+
+fn checked(x: Option<I256>) -> Result<I256> {
+    x.ok_or(Error::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `encodePriceSqrt(reserve1, reserve0)` of the library's tests.
+    fn encode_price_sqrt(reserve1: u64, reserve0: u64) -> U256 {
+        ((U256::from(reserve1) << 192) / reserve0).integer_sqrt()
+    }
+
+    fn u256(s: &str) -> U256 {
+        U256::from_dec_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_compute_swap_step() {
+        let price = encode_price_sqrt(1, 1);
+        let liquidity = 2 * 10u128.pow(18);
+        let amount = I256::exp10(18);
+
+        // from the library's tests
+        // exact input capped at the target price, one for zero
+        let target = encode_price_sqrt(101, 100);
+        let step = compute_swap_step(price, target, liquidity, amount, 600).unwrap();
+        assert_eq!(step.sqrt_ratio_next_x96, target);
+        assert_eq!(step.amount_in, u256("9975124224178055"));
+        assert_eq!(step.amount_out, u256("9925619580021728"));
+        assert_eq!(step.fee_amount, u256("5988667735148"));
+        // exact output capped at the target price
+        let step = compute_swap_step(price, target, liquidity, -amount, 600).unwrap();
+        assert_eq!(step.sqrt_ratio_next_x96, target);
+        assert_eq!(step.amount_in, u256("9975124224178055"));
+        assert_eq!(step.amount_out, u256("9925619580021728"));
+        // exact input fully spent
+        let target = encode_price_sqrt(1000, 100);
+        let step = compute_swap_step(price, target, liquidity, amount, 600).unwrap();
+        assert_eq!(step.sqrt_ratio_next_x96, u256("118818475322642227089037862318"));
+        assert_eq!(step.amount_in, u256("999400000000000000"));
+        assert_eq!(step.amount_out, u256("666399946655997866"));
+        assert_eq!(step.fee_amount, u256("600000000000000"));
+        // exact output fully received
+        let target = encode_price_sqrt(10000, 100);
+        let step = compute_swap_step(price, target, liquidity, -amount, 600).unwrap();
+        assert_eq!(step.amount_in, u256("2000000000000000000"));
+        assert_eq!(step.amount_out, u256("1000000000000000000"));
+        assert_eq!(step.fee_amount, u256("1200720432259356"));
+        // the output is capped at the desired amount
+        let step = compute_swap_step(
+            u256("417332158212080721273783715441582"),
+            u256("1452870262520218020823638996"),
+            159344665391607089467575320103,
+            I256::minus_one(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(step.sqrt_ratio_next_x96, u256("417332158212080721273783715441581"));
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (1.into(), 1.into(), 1.into())
+        );
+        // the whole input is taken as fee
+        let step = compute_swap_step(
+            2413.into(),
+            u256("79887613182836312"),
+            1985041575832132834610021537970,
+            I256::from(10),
+            1872,
+        )
+        .unwrap();
+        assert_eq!(step.sqrt_ratio_next_x96, 2413.into());
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (0.into(), 0.into(), 10.into())
+        );
+
+        assert!(matches!(
+            compute_swap_step(price, target, liquidity, amount, 1_000_000),
+            Err(Error::InvalidFee)
+        ));
+    }
+
+    #[test]
+    fn test_next_initialized_tick_within_one_word() {
+        // initialized ticks from the library's tests
+        let mut ticks = TickData::new(1);
+        for tick in [-200, -55, -4, 70, 78, 84, 139, 240, 535] {
+            let (word, bit) = position(tick);
+            let bits = ticks.words.entry(word).or_default();
+            *bits |= U256::one() << bit;
+        }
+        for word in -2..=3 {
+            ticks.words.entry(word).or_default();
+        }
+        let next = |tick, lte| ticks.next_initialized_tick_within_one_word(tick, lte).unwrap();
+
+        // to the right
+        assert_eq!(next(78, false), (84, true));
+        assert_eq!(next(-55, false), (-4, true));
+        assert_eq!(next(77, false), (78, true));
+        assert_eq!(next(-56, false), (-55, true));
+        assert_eq!(next(255, false), (511, false));
+        assert_eq!(next(-257, false), (-200, true));
+        assert_eq!(next(340, false), (511, false));
+        assert_eq!(next(328, false), (511, false));
+        // to the left or at the tick
+        assert_eq!(next(78, true), (78, true));
+        assert_eq!(next(79, true), (78, true));
+        assert_eq!(next(258, true), (256, false));
+        assert_eq!(next(256, true), (256, false));
+        assert_eq!(next(72, true), (70, true));
+        assert_eq!(next(-257, true), (-512, false));
+        assert_eq!(next(1023, true), (768, false));
+        assert_eq!(next(900, true), (768, false));
+
+        assert!(matches!(
+            ticks.next_initialized_tick_within_one_word(-513, true),
+            Err(Error::TickWordNotLoaded(-3))
+        ));
+        assert!(matches!(ticks.liquidity_net(78), Err(Error::TickWordNotLoaded(0))));
+        assert_eq!(TickData::word_position(-1, 60), -1);
+        assert_eq!(TickData::word_position(15360, 60), 1);
+    }
+
+    #[test]
+    fn test_simulate() {
+        // liquidity of 10**18 from tick -600 to 600, and of 2 * 10**18 from -60 to 60
+        let mut ticks = TickData::new(60);
+        let liquidity = 10u128.pow(18) as i128;
+        for (tick, liquidity_net) in
+            [(-600, liquidity), (-60, liquidity), (60, -liquidity), (600, -liquidity)]
+        {
+            let compressed = tick / 60;
+            let (word, bit) = position(compressed);
+            let bits = ticks.words.entry(word).or_default();
+            *bits |= U256::one() << bit;
+            ticks.liquidity_net.insert(tick, liquidity_net);
+        }
+        let state = PoolState {
+            sqrt_price_x96: encode_price_sqrt(1, 1),
+            tick: 0,
+            liquidity: 2 * liquidity as u128,
+            ..Default::default()
+        };
+        let fee = FeeAmount::Medium;
+
+        // within the range: a single step
+        let amount = I256::exp10(15);
+        let swap = simulate(&state, fee, &ticks, amount, true, None).unwrap();
+        let step = compute_swap_step(
+            state.sqrt_price_x96,
+            MIN_SQRT_RATIO + 1,
+            state.liquidity,
+            amount,
+            3000,
+        )
+        .unwrap();
+        assert_eq!(swap.amount_in, amount.into_raw());
+        assert_eq!(swap.amount_out, step.amount_out);
+        assert_eq!(swap.sqrt_price_x96_after, step.sqrt_ratio_next_x96);
+        assert_eq!(swap.tick_after, get_tick_at_sqrt_ratio(step.sqrt_ratio_next_x96).unwrap());
+        assert_eq!(swap.liquidity_after, state.liquidity);
+        assert_eq!(swap.initialized_ticks_crossed, 0);
+
+        // crossing the tick at -60, where the liquidity halves
+        let amount = I256::exp10(16) * I256::from(2);
+        let swap = simulate(&state, fee, &ticks, amount, true, None).unwrap();
+        assert_eq!(swap.amount_in, amount.into_raw());
+        assert!(swap.tick_after < -60 && swap.tick_after > -600);
+        assert_eq!(swap.liquidity_after, liquidity as u128);
+        assert_eq!(swap.initialized_ticks_crossed, 1);
+        // the exact output of the same amount costs at most the same input
+        let exact_out = -I256::from_raw(swap.amount_out);
+        let reverse = simulate(&state, fee, &ticks, exact_out, true, None).unwrap();
+        assert_eq!(reverse.amount_out, swap.amount_out);
+        assert!(reverse.amount_in <= swap.amount_in && reverse.amount_in + 2 >= swap.amount_in);
+
+        // out of liquidity: stops at the price limit, in the next empty word
+        let limit = get_sqrt_ratio_at_tick(1200).unwrap();
+        let swap = simulate(&state, fee, &ticks, I256::exp10(30), false, Some(limit)).unwrap();
+        assert_eq!(swap.sqrt_price_x96_after, limit);
+        assert_eq!(swap.tick_after, 1200);
+        assert_eq!(swap.liquidity_after, 0);
+        assert_eq!(swap.initialized_ticks_crossed, 2);
+        assert!(swap.amount_in < U256::exp10(30));
+        // without a limit, the swap reaches a word which is not loaded
+        let res = simulate(&state, fee, &ticks, I256::exp10(30), false, None);
+        assert!(matches!(res, Err(Error::TickWordNotLoaded(1))));
+
+        let res = simulate(&state, fee, &ticks, I256::zero(), true, None);
+        assert!(matches!(res, Err(Error::ZeroAmount)));
+        let res = simulate(&state, fee, &ticks, amount, true, Some(state.sqrt_price_x96));
+        assert!(matches!(res, Err(Error::InvalidSqrtRatio(_))));
+        let res = simulate(&state, fee, &ticks, amount, false, Some(MAX_SQRT_RATIO));
+        assert!(matches!(res, Err(Error::InvalidSqrtRatio(_))));
+    }
+}
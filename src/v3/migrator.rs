@@ -0,0 +1,412 @@
+use super::{
+    math::{
+        get_sqrt_ratio_at_tick,
+        liquidity::{amounts_for_liquidity, liquidity_for_amounts},
+        nearest_usable_tick,
+    },
+    router::calldata,
+    Factory, FeeAmount,
+};
+use crate::{
+    constants::BPS_U256,
+    contracts::bindings::iv3_migrator::{self as bindings, IV3Migrator},
+    errors::{Error, Result},
+    permit::split_signature,
+    v2::Pair,
+    Deadline, Permit, ProtocolType, Slippage,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::types::{Address, Bytes, Signature, U256, U512};
+use ethers_providers::Middleware;
+use std::sync::Arc;
+
+contract_struct! {
+    /// A Uniswap V3 `V3Migrator`, which migrates the liquidity of Uniswap V2 pairs to V3 positions.
+    pub struct Migrator<M> {
+        /// The migrator contract.
+        contract: IV3Migrator<M>,
+
+        /// The factory of the pools.
+        factory: Factory<M>,
+    }
+}
+
+impl<M: Middleware> Migrator<M> {
+    /// Creates a new instance using the provided migrator and factory addresses.
+    pub fn new(client: Arc<M>, address: Address, factory: Address) -> Self {
+        let factory = Factory::new(client.clone(), factory, ProtocolType::UniswapV3);
+        let contract = IV3Migrator::new(address, client);
+        Self { contract, factory }
+    }
+
+    /// Creates a new instance by searching for the canonical Uniswap V3 addresses in the
+    /// [addressbook].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn new_with_chain(client: Arc<M>, chain: ethers_core::types::Chain) -> Option<Self> {
+        use crate::contracts::addresses::try_address;
+
+        let address = try_address("UniswapV3Migrator", chain)?;
+        let factory = Factory::new_with_chain(client.clone(), chain, ProtocolType::UniswapV3)?;
+        let contract = IV3Migrator::new(address, client);
+        Some(Self { contract, factory })
+    }
+
+    /// Returns a reference to the factory of the pools.
+    #[inline(always)]
+    pub fn factory(&self) -> &Factory<M> {
+        &self.factory
+    }
+
+    /// Returns the contract call for the migrator's `multicall` method, which executes each of
+    /// the migrator's calls in `data` in the same transaction and returns their results.
+    pub fn multicall(&self, data: Vec<Bytes>) -> ContractCall<M, Vec<Bytes>> {
+        self.contract.multicall(data)
+    }
+
+    /// Returns the contract call for the migrator's `selfPermit` method, approving the migrator to
+    /// transfer the tokens of the signed `permit`.
+    pub fn self_permit(
+        &self,
+        token: Address,
+        permit: &Permit,
+        signature: &Signature,
+    ) -> ContractCall<M, ()> {
+        let (v, r, s) = split_signature(signature);
+        self.contract.self_permit(token, permit.value, permit.deadline, v, r, s)
+    }
+
+    /// Migrates `percentage_bps` of the client's default sender's liquidity in `pair` to a new
+    /// position in the V3 pool of the same tokens and `fee`, in the range `tick_lower..tick_upper`.
+    /// Returns the contract call with the necessary parameters set (calldata).
+    ///
+    /// The sender's liquidity tokens and the amounts of the pair's tokens they are worth are
+    /// fetched with [`Pair::position_of`], and the pool's tick spacing and price with
+    /// [`Pool::state`][super::Pool::state]. The ticks are rounded to the nearest usable ticks, and
+    /// the amounts deposited at the current price are computed from the migrated ones, to which
+    /// `slippage_tolerance` is applied to set `amount0Min` and `amount1Min`. The position is
+    /// minted to the sender.
+    ///
+    /// Only the migrated fraction of the liquidity tokens is burned, and all the resulting tokens
+    /// are deposited, up to the amounts used by the position. The migrator refunds the rest to the
+    /// sender, which is all of one of the tokens if the range is entirely above or below the
+    /// current price.
+    ///
+    /// The migration is always wrapped in the migrator's [`multicall`][Self::multicall]. If
+    /// `permit` is set, its [`selfPermit`][Self::self_permit] call is prepended to approve the
+    /// migrator to transfer the liquidity tokens instead of a separate `approve` transaction: see
+    /// [`Pair::get_permit`].
+    ///
+    /// Returns [`Error::InvalidPercentage`] if `percentage_bps` is greater than `10_000`,
+    /// [`Error::InsufficientLiquidity`] if no liquidity tokens would be migrated,
+    /// [`Error::InvalidTick`] if the range is empty once rounded, [`Error::PoolNotDeployed`] if the
+    /// pool has not been deployed, and [`Error::MissingSender`] if the client has no default
+    /// sender.
+    pub async fn migrate(
+        &self,
+        pair: &Pair<M>,
+        percentage_bps: u32,
+        fee: FeeAmount,
+        tick_lower: i32,
+        tick_upper: i32,
+        slippage_tolerance: impl Into<Slippage>,
+        deadline: impl Into<Deadline>,
+        permit: Option<(&Permit, &Signature)>,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        if percentage_bps > 10_000 {
+            return Err(Error::InvalidPercentage);
+        }
+        let slippage = slippage_tolerance.into().validate()?;
+        let client = self.client();
+        let owner = client.default_sender().ok_or(Error::MissingSender)?;
+        let deadline = deadline.into().resolve(client.as_ref()).await?;
+
+        let position = pair.position_of(owner).await?;
+        let liquidity = position.liquidity.full_mul(percentage_bps.into()) / U512::from(BPS_U256);
+        let liquidity = U256::try_from(liquidity).expect("lower than the balance");
+        if liquidity.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        // the amounts of the migrated liquidity tokens, rounded down
+        let migrated = |amount: U256| {
+            let amount = amount.full_mul(liquidity) / U512::from(position.liquidity);
+            U256::try_from(amount).expect("lower than the amount")
+        };
+        let (amount0, amount1) = (migrated(position.amount0), migrated(position.amount1));
+
+        let pool = self.factory.pool_for(position.token0, position.token1, fee)?;
+        let state = pool.state().await?;
+        let tick_spacing = pool.tick_spacing().expect("fetched by state");
+        let tick_lower = nearest_usable_tick(tick_lower, tick_spacing)?;
+        let tick_upper = nearest_usable_tick(tick_upper, tick_spacing)?;
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidTick(tick_upper));
+        }
+
+        let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(tick_lower)?;
+        let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(tick_upper)?;
+        let position_liquidity = liquidity_for_amounts(
+            state.sqrt_price_x96,
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            amount0,
+            amount1,
+        )?;
+        let (used0, used1) = amounts_for_liquidity(
+            state.sqrt_price_x96,
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            position_liquidity,
+        )?;
+
+        let migrate = self.contract.migrate(bindings::MigrateParams {
+            pair: pair.address(),
+            liquidity_to_migrate: liquidity,
+            percentage_to_migrate: 100,
+            token_0: position.token0,
+            token_1: position.token1,
+            fee: fee.into(),
+            tick_lower,
+            tick_upper,
+            amount_0_min: slippage.minimum_amount_out(used0),
+            amount_1_min: slippage.minimum_amount_out(used1),
+            recipient: owner,
+            deadline,
+            refund_as_eth: false,
+        });
+
+        let mut data = Vec::with_capacity(2);
+        if let Some((permit, signature)) = permit {
+            data.push(calldata(self.self_permit(pair.address(), permit, signature)));
+        }
+        data.push(calldata(migrate));
+        Ok(self.multicall(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::iv3_migrator::{MigrateCall, MulticallCall, SelfPermitCall};
+    use ethers_core::{
+        abi::{self, AbiDecode, Token},
+        types::{Address, I256},
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    type M = Provider<MockProvider>;
+
+    const TOKEN0: Address = Address::repeat_byte(0x0a);
+    const TOKEN1: Address = Address::repeat_byte(0xaa);
+    const OWNER: Address = Address::repeat_byte(0xbb);
+
+    fn setup() -> (Migrator<M>, Pair<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider.with_sender(OWNER));
+        let migrator =
+            Migrator::new(client.clone(), Address::repeat_byte(0x44), Address::repeat_byte(0x11));
+        let pair = Pair::new(client, Address::repeat_byte(0x22), ProtocolType::UniswapV2);
+        (migrator, pair, mock)
+    }
+
+    fn push_multicall(mock: &MockProvider, results: Vec<Token>) {
+        let results = results
+            .into_iter()
+            .map(|token| Token::Tuple(vec![Token::Bool(true), Token::Bytes(abi::encode(&[token]))]))
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        // chain id, queried by the multicall
+        mock.push(U256::one()).unwrap();
+    }
+
+    /// Pushes the responses of `Pool::state` then `Pair::position_of`, for 1% of the pair's
+    /// liquidity tokens, with the pair's tokens unless they are cached.
+    fn push_position(mock: &MockProvider, sqrt_price_x96: U256, balance: U256, tokens: bool) {
+        push_multicall(
+            mock,
+            vec![
+                Token::Address(TOKEN0),
+                Token::Address(TOKEN1),
+                Token::Uint(3000.into()),
+                Token::Int(I256::from(60).into_raw()),
+                Token::Tuple(vec![
+                    Token::Uint(sqrt_price_x96),
+                    Token::Int(0.into()),
+                    Token::Uint(0.into()),
+                    Token::Uint(1.into()),
+                    Token::Uint(1.into()),
+                    Token::Uint(0.into()),
+                    Token::Bool(true),
+                ]),
+                Token::Uint(1000.into()),
+                Token::Uint(100.into()),
+            ],
+        );
+        let reserve = Token::Uint(U256::exp10(22));
+        let mut results = vec![
+            Token::Uint(balance),
+            Token::Uint(U256::exp10(22)),
+            Token::Tuple(vec![reserve.clone(), reserve, Token::Uint(0.into())]),
+        ];
+        if tokens {
+            results.splice(0..0, [Token::Address(TOKEN0), Token::Address(TOKEN1)]);
+        }
+        push_multicall(mock, results);
+    }
+
+    fn decode(call: ContractCall<M, Vec<Bytes>>) -> Vec<Bytes> {
+        MulticallCall::decode(call.calldata().unwrap()).unwrap().data
+    }
+
+    #[tokio::test]
+    async fn test_migrate() {
+        let (migrator, pair, mock) = setup();
+        let amount = U256::exp10(20);
+
+        // all of 1% of the reserves, at a 1:1 price and a symmetric range
+        push_position(&mock, U256::one() << 96, amount, true);
+        let call = migrator
+            .migrate(&pair, 10_000, FeeAmount::Medium, -1000, 1010, 1.0, U256::MAX, None)
+            .await
+            .unwrap();
+        let data = decode(call);
+        assert_eq!(data.len(), 1);
+        let params = MigrateCall::decode(&data[0]).unwrap().params;
+        assert_eq!(params.pair, pair.address());
+        assert_eq!(params.liquidity_to_migrate, U256::exp10(20));
+        assert_eq!(params.percentage_to_migrate, 100);
+        assert_eq!((params.token_0, params.token_1), (TOKEN0, TOKEN1));
+        assert_eq!((params.fee, params.tick_lower, params.tick_upper), (3000, -1020, 1020));
+        // the amounts are equal at the middle of the range, minus the rounding and 1% slippage
+        assert_eq!(params.amount_0_min, params.amount_1_min);
+        assert!(params.amount_0_min < amount * 99 / 100);
+        assert!(params.amount_0_min > amount * 99 / 100 - 10);
+        assert_eq!(params.recipient, OWNER);
+        assert_eq!(params.deadline, U256::MAX);
+        assert!(!params.refund_as_eth);
+
+        // a quarter, above the price: only token0 is deposited and all of token1 is refunded
+        push_position(&mock, U256::one() << 96, amount, false);
+        let call = migrator
+            .migrate(&pair, 2_500, FeeAmount::Medium, 60, 600, 0.0, U256::MAX, None)
+            .await
+            .unwrap();
+        let params = MigrateCall::decode(&decode(call)[0]).unwrap().params;
+        assert_eq!(params.liquidity_to_migrate, U256::exp10(20) / 4);
+        assert!(params.amount_0_min <= amount / 4 && params.amount_0_min + 2 >= amount / 4);
+        assert_eq!(params.amount_1_min, U256::zero());
+
+        // with a permit
+        push_position(&mock, U256::one() << 96, amount, false);
+        let permit = Permit { value: U256::MAX, deadline: 100.into(), ..Default::default() };
+        let signature = Signature { r: 1.into(), s: 2.into(), v: 28 };
+        let call = migrator
+            .migrate(
+                &pair,
+                10_000,
+                FeeAmount::Medium,
+                -60,
+                60,
+                0.0,
+                U256::MAX,
+                Some((&permit, &signature)),
+            )
+            .await
+            .unwrap();
+        let data = decode(call);
+        assert_eq!(data.len(), 2);
+        let self_permit = SelfPermitCall::decode(&data[0]).unwrap();
+        assert_eq!(self_permit.token, pair.address());
+        assert_eq!((self_permit.value, self_permit.deadline), (U256::MAX, 100.into()));
+        assert_eq!(self_permit.v, 28);
+        MigrateCall::decode(&data[1]).unwrap();
+
+        let res = migrator.migrate(&pair, 10_001, FeeAmount::Medium, -60, 60, 0.0, U256::MAX, None);
+        assert!(matches!(res.await.unwrap_err(), Error::InvalidPercentage));
+        // no liquidity tokens: the pool is not fetched
+        push_position(&mock, U256::one() << 96, U256::zero(), false);
+        let res = migrator.migrate(&pair, 10_000, FeeAmount::Medium, -60, 60, 0.0, U256::MAX, None);
+        assert!(matches!(res.await.unwrap_err(), Error::InsufficientLiquidity));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_migrate_fork() {
+        use crate::{
+            contracts::bindings::{ierc20::IERC20, iweth::IWETH},
+            permit::sign_permit,
+            testing::Fork,
+            v2::Library,
+            v3::{math::nearest_usable_tick, PositionManager},
+        };
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+        let wallet = client.signer().clone();
+
+        let factory: Address = "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap();
+        let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let weth: Address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+        let pair: Address = "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc".parse().unwrap();
+        let pair = Pair::new(client.clone(), pair, ProtocolType::UniswapV2);
+        let (usdc_token, weth_token) =
+            (IERC20::new(usdc, client.clone()), IWETH::new(weth, client.clone()));
+
+        // swap 1 WETH for USDC through the pair, then add liquidity with both
+        let amount = U256::exp10(18);
+        weth_token.deposit().value(amount * 2).send().await.unwrap().await.unwrap();
+        let (reserve0, reserve1, _) = pair.contract().get_reserves().call().await.unwrap();
+        let usdc_out = Library::get_amount_out(amount, reserve1.into(), reserve0.into()).unwrap();
+        weth_token.transfer(pair.address(), amount).send().await.unwrap().await.unwrap();
+        let swap = pair.contract().swap(usdc_out, U256::zero(), me, Bytes::default());
+        swap.send().await.unwrap().await.unwrap();
+        usdc_token.transfer(pair.address(), usdc_out).send().await.unwrap().await.unwrap();
+        weth_token.transfer(pair.address(), amount).send().await.unwrap().await.unwrap();
+        pair.mint_to(me).send().await.unwrap().await.unwrap();
+        let liquidity = pair.contract().balance_of(me).call().await.unwrap();
+        assert!(!liquidity.is_zero());
+
+        // migrate all of it around the price of the USDC/WETH 0.05% pool, with a permit
+        let migrator = Migrator::new(
+            client.clone(),
+            "0xA5644E29708357803b5A882D272c41cC0dF92B34".parse().unwrap(),
+            factory,
+        );
+        let pool = migrator.factory().pool_for(usdc, weth, FeeAmount::Low).unwrap();
+        let tick = pool.state().await.unwrap().tick;
+        let permit =
+            pair.get_permit(me, migrator.address(), liquidity, None, U256::MAX).await.unwrap();
+        let signature = sign_permit(&wallet, &permit).await.unwrap();
+        let (tick_lower, tick_upper) = (tick - 1000, tick + 1000);
+        let call = migrator
+            .migrate(
+                &pair,
+                10_000,
+                FeeAmount::Low,
+                tick_lower,
+                tick_upper,
+                1.0,
+                U256::MAX,
+                Some((&permit, &signature)),
+            )
+            .await
+            .unwrap();
+        call.send().await.unwrap().await.unwrap();
+        assert!(pair.contract().balance_of(me).call().await.unwrap().is_zero());
+
+        // the newest position in the pool
+        let manager = PositionManager::new(
+            client,
+            "0xC36442b4a4522E871399CD717aBDD847Ab11FE88".parse().unwrap(),
+            factory,
+            weth,
+        );
+        let pool = Some((usdc, weth, FeeAmount::Low));
+        let positions = manager.positions_of(me, pool, 100, 1).await.unwrap();
+        let (_, position) = positions.last().unwrap();
+        assert!(position.liquidity > 0);
+        assert_eq!(position.tick_lower, nearest_usable_tick(tick_lower, 10).unwrap());
+        assert_eq!(position.tick_upper, nearest_usable_tick(tick_upper, 10).unwrap());
+    }
+}
@@ -1,11 +1,34 @@
 //! The [Uniswap V3 protocol](https://docs.uniswap.org/contracts/v3/overview). Work in progress.
 
+mod depth;
 mod factory;
 mod library;
+pub mod math;
+#[cfg(feature = "v2")]
+mod migrator;
+mod oracle;
+mod path;
 mod pool;
+mod position_manager;
+mod protocol;
+mod quoter;
+mod registry;
 mod router;
+#[cfg(feature = "staker")]
+mod staker;
 
-pub use factory::Factory;
-pub use library::FeeAmount;
-pub use pool::Pool;
+pub use depth::DepthChart;
+pub use factory::{Factory, PoolLiquidity};
+pub use library::{FeeAmount, FeeSelection, MAX_FEE};
+#[cfg(feature = "v2")]
+pub use migrator::Migrator;
+pub use oracle::Twap;
+pub use path::Path;
+pub use pool::{Pool, PoolState, SwapEvent};
+pub use position_manager::{MintParams, Position, PositionManager};
+pub use protocol::Protocol;
+pub use quoter::{Quote, Quoter, QuoterContract, QUOTER_ADDRESS};
+pub use registry::{PoolInfo, PoolRegistry};
 pub use router::Router;
+#[cfg(feature = "staker")]
+pub use staker::{IncentiveKey, Staker};
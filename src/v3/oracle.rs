@@ -0,0 +1,67 @@
+use super::math::tick_to_price;
+use crate::errors::{Error, Result};
+use std::time::Duration;
+
+/// The time-weighted average price of a pool over a window, returned by
+/// [`Pool::twap`][super::Pool::twap].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Twap {
+    /// The arithmetic mean tick over the window.
+    pub tick: i32,
+    /// The price of `token0` in terms of `token1` at the mean tick, adjusted for the tokens'
+    /// decimals.
+    pub price: f64,
+    /// The duration of the window.
+    pub window: Duration,
+}
+
+impl Twap {
+    /// Returns the average price between the `start` and `end` tick cumulatives, `window` apart,
+    /// like the Solidity [oracle library]'s `consult`.
+    ///
+    /// Returns [`Error::InvalidTwapWindow`] if `window` is shorter than a second.
+    ///
+    /// [oracle library]: https://github.com/Uniswap/v3-periphery/blob/main/contracts/libraries/OracleLibrary.sol
+    pub fn new(
+        start: i64,
+        end: i64,
+        window: Duration,
+        decimals0: u8,
+        decimals1: u8,
+    ) -> Result<Self> {
+        let tick = arithmetic_mean_tick(start, end, window.as_secs())?;
+        let price = tick_to_price(tick, decimals0, decimals1)?;
+        Ok(Self { tick, price, window })
+    }
+}
+
+/// Returns the arithmetic mean tick between the `start` and `end` tick cumulatives, `seconds`
+/// apart, rounded to negative infinity.
+fn arithmetic_mean_tick(start: i64, end: i64, seconds: u64) -> Result<i32> {
+    if seconds == 0 {
+        return Err(Error::InvalidTwapWindow);
+    }
+    let (delta, seconds) = (end as i128 - start as i128, seconds as i128);
+    Ok(delta.div_euclid(seconds) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_mean_tick() {
+        assert_eq!(arithmetic_mean_tick(0, 600, 60).unwrap(), 10);
+        assert_eq!(arithmetic_mean_tick(600, 0, 60).unwrap(), -10);
+        // rounded to negative infinity
+        assert_eq!(arithmetic_mean_tick(0, 659, 60).unwrap(), 10);
+        assert_eq!(arithmetic_mean_tick(0, -601, 60).unwrap(), -11);
+        assert_eq!(arithmetic_mean_tick(i64::MAX, i64::MIN, 1 << 40).unwrap(), -(1 << 24));
+        assert!(matches!(arithmetic_mean_tick(0, 1, 0), Err(Error::InvalidTwapWindow)));
+
+        let twap = Twap::new(-600, 0, Duration::from_secs(60), 18, 18).unwrap();
+        assert_eq!(twap.tick, 10);
+        assert!((twap.price - 1.0001f64.powi(10)).abs() < 1e-12);
+    }
+}
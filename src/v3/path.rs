@@ -0,0 +1,207 @@
+use super::FeeAmount;
+use crate::errors::{Error, Result};
+use ethers_core::types::{Address, Bytes};
+
+const ADDR_SIZE: usize = 20;
+const FEE_SIZE: usize = 3;
+const HOP_SIZE: usize = FEE_SIZE + ADDR_SIZE;
+
+/// A Uniswap V3 multi-hop swap path: the tokens to swap through, and the fee of the pool between
+/// each consecutive pair of them.
+///
+/// It is encoded as the packed `token(20) fee(3) token(20) ...` bytes taken by the router's and
+/// the quoter's `exactInput` and `exactOutput` methods. The latter expect the path in reverse, from
+/// the output token to the input token, see [`reverse`][Path::reverse].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Path {
+    tokens: Vec<Address>,
+    fees: Vec<FeeAmount>,
+}
+
+impl Path {
+    /// Creates a new path from `tokens` and the `fees` of the pools between them.
+    ///
+    /// Returns [`Error::InvalidPath`] if there are fewer than 2 tokens,
+    /// [`Error::InvalidPathFees`] if there is not one fee per pair of tokens, and
+    /// [`Error::FeeTooLarge`] if a fee does not fit in 24 bits.
+    pub fn new(tokens: &[Address], fees: &[u32]) -> Result<Self> {
+        if tokens.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        if fees.len() != tokens.len() - 1 {
+            return Err(Error::InvalidPathFees);
+        }
+        let fees = fees.iter().map(|&fee| FeeAmount::try_from(fee)).collect::<Result<_>>()?;
+        Ok(Self { tokens: tokens.to_vec(), fees })
+    }
+
+    /// Creates a new path through the single pool of `token_in` and `token_out` with `fee`.
+    pub fn single(token_in: Address, token_out: Address, fee: FeeAmount) -> Self {
+        Self { tokens: vec![token_in, token_out], fees: vec![fee] }
+    }
+
+    /// Decodes a packed path.
+    ///
+    /// Returns [`Error::InvalidEncodedPath`] if its length is not that of at least one hop.
+    pub fn decode(path: impl AsRef<[u8]>) -> Result<Self> {
+        let path = path.as_ref();
+        if path.len() < ADDR_SIZE + HOP_SIZE || (path.len() - ADDR_SIZE) % HOP_SIZE != 0 {
+            return Err(Error::InvalidEncodedPath);
+        }
+
+        let hops = (path.len() - ADDR_SIZE) / HOP_SIZE;
+        let mut tokens = Vec::with_capacity(hops + 1);
+        let mut fees = Vec::with_capacity(hops);
+        tokens.push(Address::from_slice(&path[..ADDR_SIZE]));
+        for hop in path[ADDR_SIZE..].chunks_exact(HOP_SIZE) {
+            let (fee, token) = hop.split_at(FEE_SIZE);
+            fees.push(FeeAmount::try_from(u32::from_be_bytes([0, fee[0], fee[1], fee[2]]))?);
+            tokens.push(Address::from_slice(token));
+        }
+        Ok(Self { tokens, fees })
+    }
+
+    /// Returns the packed encoding of the path.
+    pub fn encode(&self) -> Bytes {
+        let mut path = Vec::with_capacity(ADDR_SIZE + self.fees.len() * HOP_SIZE);
+        path.extend_from_slice(self.tokens[0].as_bytes());
+        for (fee, token) in self.fees.iter().zip(&self.tokens[1..]) {
+            path.extend_from_slice(&fee.as_u32().to_be_bytes()[1..]);
+            path.extend_from_slice(token.as_bytes());
+        }
+        path.into()
+    }
+
+    /// Returns the path in reverse, from the output token to the input token.
+    pub fn reverse(&self) -> Self {
+        let mut tokens = self.tokens.clone();
+        let mut fees = self.fees.clone();
+        tokens.reverse();
+        fees.reverse();
+        Self { tokens, fees }
+    }
+
+    /// Returns the tokens of the path.
+    pub fn tokens(&self) -> &[Address] {
+        &self.tokens
+    }
+
+    /// Returns the fees of the pools in the path.
+    pub fn fees(&self) -> &[FeeAmount] {
+        &self.fees
+    }
+
+    /// Returns the input token.
+    pub fn token_in(&self) -> Address {
+        self.tokens[0]
+    }
+
+    /// Returns the output token.
+    pub fn token_out(&self) -> Address {
+        self.tokens[self.tokens.len() - 1]
+    }
+
+    /// Returns the number of pools in the path.
+    pub fn hops(&self) -> usize {
+        self.fees.len()
+    }
+
+    /// Returns an iterator over the `(token_in, token_out, fee)` of each pool in the path.
+    pub fn pools(&self) -> impl Iterator<Item = (Address, Address, FeeAmount)> + '_ {
+        self.tokens.windows(2).zip(&self.fees).map(|(tokens, &fee)| (tokens[0], tokens[1], fee))
+    }
+
+    /// Returns a mutable reference to the tokens of the path, which can not be resized.
+    pub(crate) fn tokens_mut(&mut self) -> &mut [Address] {
+        &mut self.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3::MAX_FEE;
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+
+    #[test]
+    fn test_new() {
+        let (a, b, c) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+
+        assert!(matches!(Path::new(&[], &[]), Err(Error::InvalidPath)));
+        assert!(matches!(Path::new(&[a], &[]), Err(Error::InvalidPath)));
+        assert!(matches!(Path::new(&[a, b], &[]), Err(Error::InvalidPathFees)));
+        assert!(matches!(Path::new(&[a, b, c], &[500]), Err(Error::InvalidPathFees)));
+        assert!(matches!(Path::new(&[a, b], &[500, 3000]), Err(Error::InvalidPathFees)));
+        assert!(matches!(Path::new(&[a, b], &[MAX_FEE + 1]), Err(Error::FeeTooLarge(_))));
+
+        let path = Path::new(&[a, b, c], &[500, MAX_FEE]).unwrap();
+        assert_eq!(path.hops(), 2);
+        assert_eq!((path.token_in(), path.token_out()), (a, c));
+        let pools = vec![(a, b, FeeAmount::Low), (b, c, FeeAmount::Custom(MAX_FEE))];
+        assert_eq!(path.pools().collect::<Vec<_>>(), pools);
+        assert_eq!(Path::single(a, b, FeeAmount::Medium), Path::new(&[a, b], &[3000]).unwrap());
+    }
+
+    #[test]
+    fn test_decode_mainnet() {
+        // USDC -> 0.05% -> WETH -> 0.3% -> DAI, as in `exactInput` calldata
+        let encoded: Bytes = concat!(
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "0001f4",
+            "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "000bb8",
+            "6b175474e89094c44da98b954eedeac495271d0f",
+        )
+        .parse()
+        .unwrap();
+        let path = Path::decode(&encoded).unwrap();
+        let tokens = [USDC.parse().unwrap(), WETH.parse().unwrap(), DAI.parse().unwrap()];
+        assert_eq!(path.tokens(), tokens);
+        assert_eq!(path.fees(), [FeeAmount::Low, FeeAmount::Medium]);
+        assert_eq!(path.encode(), encoded);
+
+        // exactOutput path
+        let reversed = path.reverse();
+        assert_eq!(reversed.tokens(), [tokens[2], tokens[1], tokens[0]]);
+        assert_eq!(reversed.fees(), [FeeAmount::Medium, FeeAmount::Low]);
+        assert_eq!(reversed.reverse(), path);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let encoded = Path::single(Address::zero(), Address::zero(), FeeAmount::Low).encode();
+        assert_eq!(encoded.len(), 43);
+        for len in [0, 20, 23, 42, 44, 65] {
+            let bytes = vec![0u8; len];
+            assert!(matches!(Path::decode(bytes), Err(Error::InvalidEncodedPath)), "{len}");
+        }
+        assert!(Path::decode(vec![0u8; 66]).is_ok());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // deterministic pseudo-random fees covering the whole uint24 range
+        let mut seed = 0x2545_f491_u32;
+        let mut next_fee = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed & MAX_FEE
+        };
+        for hops in 1..=8 {
+            for _ in 0..16 {
+                let tokens: Vec<_> = (0..=hops).map(|_| Address::random()).collect();
+                let fees: Vec<_> = (0..hops).map(|_| next_fee()).collect();
+                let path = Path::new(&tokens, &fees).unwrap();
+                let encoded = path.encode();
+                assert_eq!(encoded.len(), ADDR_SIZE + hops * HOP_SIZE);
+                assert_eq!(Path::decode(&encoded).unwrap(), path);
+                assert_eq!(Path::decode(path.reverse().encode()).unwrap().reverse(), path);
+            }
+        }
+    }
+}
@@ -1,49 +1,180 @@
-use crate::{contracts::bindings::i_uniswap_v3_pool::IUniswapV3Pool, ProtocolType};
-use ethers_contract::builders::ContractCall;
-use ethers_core::types::{Address, Chain, H256};
-use ethers_providers::Middleware;
-use std::{fmt, sync::Arc};
+use super::{
+    depth::{tick_range, DepthChart},
+    math::{
+        get_sqrt_ratio_at_tick, liquidity::amounts_for_liquidity, mul_div, sqrt_ratio_to_price,
+        swap::TickData, to_f64,
+    },
+    FeeAmount, Position, Twap,
+};
+use crate::{
+    common::{call_multi, revert_reason, subscribe_logs, watch_logs},
+    contracts::bindings::{
+        i_tick_lens::{ITickLens, PopulatedTick},
+        i_uniswap_v3_pool::{
+            BurnFilter, CollectFilter, FlashFilter, IUniswapV3Pool, MintFilter, SwapFilter,
+        },
+        ierc20::IERC20,
+    },
+    errors::{Error, Result},
+    utils::{CallOutput, Multicall},
+    DecodedLog, ProtocolType, QuoteCache,
+};
+use ethers_contract::builders::{ContractCall, Event};
+use ethers_core::{
+    abi::{Detokenize, InvalidOutputType, Token, Tokenizable},
+    types::{Address, BlockId, Chain, Log, H256, I256, U256},
+};
+use ethers_providers::{Middleware, PubsubClient};
+use futures_util::{Stream, StreamExt};
+use once_cell::sync::OnceCell;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, PoisonError, RwLock,
+    },
+    time::Duration,
+};
 
 type Tokens = (Address, Address);
-type Reserves = (u128, u128, u32);
+type Slot0 = (U256, i32, u16, u16, u16, u8, bool);
+type TickInfo = (u128, i128, U256, U256, i64, U256, u32, bool);
+
+/// A snapshot of a pool's state: its `slot0` and in-range liquidity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PoolState {
+    /// The current price of the pool, as a `sqrtPriceX96`.
+    pub sqrt_price_x96: U256,
+    /// The current tick of the pool.
+    pub tick: i32,
+    /// The index of the most recently written price observation.
+    pub observation_index: u16,
+    /// The number of price observations stored.
+    pub observation_cardinality: u16,
+    /// The number of price observations to be stored, once they are written.
+    pub observation_cardinality_next: u16,
+    /// The protocol fee, as the denominators of the fractions of the swap fees of `token0` (lower
+    /// 4 bits) and `token1` (upper 4 bits), or zero if off.
+    pub fee_protocol: u8,
+    /// Whether the pool is not locked by a reentrant call.
+    pub unlocked: bool,
+    /// The in-range liquidity of the pool.
+    pub liquidity: u128,
+    /// The number of the block at which the snapshot was taken.
+    pub block_number: u64,
+}
+
+/// A pool's `Swap` event, with the prices of `token0` in terms of `token1` it was executed at,
+/// adjusted for the tokens' decimals.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SwapEvent {
+    /// The address which initiated the swap.
+    pub sender: Address,
+    /// The address which received the output of the swap.
+    pub recipient: Address,
+    /// The delta of the pool's balance of `token0`: positive if it was paid in, negative if it was
+    /// paid out.
+    pub amount0: I256,
+    /// The delta of the pool's balance of `token1`: positive if it was paid in, negative if it was
+    /// paid out.
+    pub amount1: I256,
+    /// The price of the pool after the swap, as a `sqrtPriceX96`.
+    pub sqrt_price_x96: U256,
+    /// The in-range liquidity of the pool after the swap.
+    pub liquidity: u128,
+    /// The tick of the pool after the swap.
+    pub tick: i32,
+    /// The average price of the swap, `|amount1| / |amount0|`, which is not finite if no `token0`
+    /// was swapped.
+    pub execution_price: f64,
+    /// The price of the pool after the swap.
+    pub price: f64,
+}
+
+impl SwapEvent {
+    /// Creates a new instance from a decoded `Swap` event and the decimals of the pool's tokens.
+    pub fn new(event: SwapFilter, decimals0: u8, decimals1: u8) -> Self {
+        let abs = |amount: I256| to_f64(amount.into_sign_and_abs().1);
+        let scale = 10f64.powi(decimals0 as i32 - decimals1 as i32);
+        Self {
+            sender: event.sender,
+            recipient: event.recipient,
+            amount0: event.amount_0,
+            amount1: event.amount_1,
+            sqrt_price_x96: event.sqrt_price_x96,
+            liquidity: event.liquidity,
+            tick: event.tick,
+            execution_price: abs(event.amount_1) / abs(event.amount_0) * scale,
+            price: sqrt_ratio_to_price(event.sqrt_price_x96, decimals0, decimals1),
+        }
+    }
+}
+
+/// The cached state of a pool, shared between its clones.
+#[derive(Debug, Default)]
+struct Cache {
+    /// The ordered tokens of the pool, which never change once fetched.
+    tokens: OnceCell<Tokens>,
+
+    /// The fee amount of the pool, which never changes once fetched.
+    fee: OnceCell<FeeAmount>,
+
+    /// The tick spacing of the pool, which never changes once fetched.
+    tick_spacing: OnceCell<i32>,
+
+    /// Whether the pool is currently deployed in the client's network.
+    deployed: AtomicBool,
+
+    /// The latest snapshot of the pool's state.
+    state: RwLock<Option<PoolState>>,
+}
 
 contract_struct! {
     /// A Uniswap V3 liquidity pool, composed of 2 different ERC20 tokens.
+    ///
+    /// The pool's immutables, its tokens, fee and tick spacing, and state are cached and shared
+    /// between its clones.
     pub struct Pool<M> {
         /// The pool contract.
         contract: IUniswapV3Pool<M>,
 
-        /// The ordered tokens of the pool.
-        tokens: Option<Tokens>,
-
-        /// Whether the pool is currently deployed in the client's network.
-        deployed: bool,
-
-        /// The token reserves of the pool.
-        reserves: Option<Reserves>,
+        /// The cached state of the pool.
+        cache: Arc<Cache>,
 
         /// The protocol of the pool.
         pub protocol: ProtocolType,
+
+        /// The address of the `TickLens` used to read the initialized ticks.
+        tick_lens: Option<Address>,
+
+        /// The cache of pool states, and its chain.
+        quote_cache: Option<(Chain, QuoteCache)>,
     }
 }
 
 impl<M> fmt::Display for Pool<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let address = self.address();
-        if self.tokens.is_none() && self.reserves.is_none() {
+        let (tokens, state) = (self.tokens(), self.cached_state());
+        if tokens.is_none() && state.is_none() {
             return writeln!(f, "Pool: {address:?}");
         }
-        writeln!(f, "Pool:     {address:?}")?;
-        if let Some((a, b)) = self.tokens {
-            writeln!(f, "Token0:   {a:?}")?;
-            write!(f, "Token1:   {b:?}")?;
-            if self.reserves.is_some() {
+        writeln!(f, "Pool:      {address:?}")?;
+        if let Some((a, b)) = tokens {
+            writeln!(f, "Token0:    {a:?}")?;
+            write!(f, "Token1:    {b:?}")?;
+            if state.is_some() {
                 writeln!(f)?
             };
         }
-        if let Some((a, b, _)) = self.reserves {
-            writeln!(f, "Reserve0: {a:?}")?;
-            write!(f, "Reserve1: {b:?}")?;
+        if let Some(state) = state {
+            writeln!(f, "Price:     {:?}", state.sqrt_price_x96)?;
+            writeln!(f, "Tick:      {:?}", state.tick)?;
+            write!(f, "Liquidity: {:?}", state.liquidity)?;
         }
         Ok(())
     }
@@ -54,21 +185,37 @@ impl<M> Pool<M> {
     ///
     /// Note: this will always be false before syncing.
     pub fn deployed(&self) -> bool {
-        self.deployed
+        self.cache.deployed.load(Ordering::Acquire)
     }
 
     /// Returns the addresses of the tokens that make up this pool.
     ///
-    /// Note: this will always be None before syncing.
+    /// Note: this will always be None before syncing, unless the pool was created with
+    /// [`Factory::pool_for`][super::Factory::pool_for].
     pub fn tokens(&self) -> Option<Tokens> {
-        self.tokens
+        self.cache.tokens.get().copied()
     }
 
-    /// Returns the reserves of the pool.
+    /// Returns the fee amount of the pool.
+    ///
+    /// Note: this will always be None before syncing, unless the pool was created with
+    /// [`Factory::pool_for`][super::Factory::pool_for].
+    pub fn fee(&self) -> Option<FeeAmount> {
+        self.cache.fee.get().copied()
+    }
+
+    /// Returns the tick spacing of the pool.
     ///
     /// Note: this will always be None before syncing.
-    pub fn reserves(&self) -> Option<Reserves> {
-        self.reserves
+    pub fn tick_spacing(&self) -> Option<i32> {
+        self.cache.tick_spacing.get().copied()
+    }
+
+    /// Returns the cached snapshot of the pool's state.
+    ///
+    /// Note: this will always be None before syncing.
+    pub fn cached_state(&self) -> Option<PoolState> {
+        *self.cache.state.read().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Returns the hash of the pool's deployment code. This can be used to determinalistically
@@ -79,36 +226,1296 @@ impl<M> Pool<M> {
     pub fn code_hash(&self, chain: Option<Chain>) -> H256 {
         self.protocol.pair_code_hash(chain)
     }
+
+    /// Returns the address of the `TickLens` used to read the pool's initialized ticks.
+    pub fn tick_lens(&self) -> Option<Address> {
+        self.tick_lens
+    }
+
+    /// Sets the address of the `TickLens` used to read the pool's initialized ticks, like the
+    /// addressbook's `UniswapV3TickLens`, instead of reading its tick bitmap and then each of its
+    /// ticks.
+    pub fn set_tick_lens(&mut self, tick_lens: Address) -> &mut Self {
+        self.tick_lens = Some(tick_lens);
+        self
+    }
+
+    /// Returns the cache of pool states and its chain, if set.
+    pub fn quote_cache(&self) -> Option<(Chain, &QuoteCache)> {
+        self.quote_cache.as_ref().map(|(chain, cache)| (*chain, cache))
+    }
+
+    /// Sets the cache of pool states on `chain` used by [`state`][Self::state], so that fetching
+    /// the state again within a block does not make any request.
+    pub fn set_quote_cache(&mut self, chain: Chain, cache: QuoteCache) -> &mut Self {
+        self.quote_cache = Some((chain, cache));
+        self
+    }
 }
 
 impl<M: Middleware> Pool<M> {
     /// Creates a new instance using the provided client and address.
     pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
         let contract = IUniswapV3Pool::new(address, client);
-        Self { contract, tokens: None, deployed: false, reserves: None, protocol }
-    }
-
-    // /// Creates a new instance using the provided client, factory and tokens' addresses.
-    // pub fn new_with_factory(
-    //     factory: &Factory<M>,
-    //     token0: Address,
-    //     token1: Address,
-    // ) -> Result<Self> {
-    //     let (token0, token1) = Library::sort_tokens(token0, token1);
-    //     let address = Library::pool_for(factory, token0, token1);
-    //     let contract = IUniswapV3Pool::new(address, factory.client());
-
-    //     Ok(Self {
-    //         contract,
-    //         tokens: Some((token0, token1)),
-    //         deployed: false,
-    //         reserves: None,
-    //         protocol: factory.protocol(),
-    //     })
-    // }
+        Self { contract, cache: Default::default(), protocol, tick_lens: None, quote_cache: None }
+    }
+
+    /// Creates a new instance of the pool of the already sorted `tokens` and `fee`.
+    pub(super) fn new_with_key(
+        client: Arc<M>,
+        address: Address,
+        protocol: ProtocolType,
+        tokens: Tokens,
+        fee: FeeAmount,
+    ) -> Self {
+        let cache = Cache {
+            tokens: OnceCell::with_value(tokens),
+            fee: OnceCell::with_value(fee),
+            ..Default::default()
+        };
+        let contract = IUniswapV3Pool::new(address, client);
+        Self { contract, cache: Arc::new(cache), protocol, tick_lens: None, quote_cache: None }
+    }
 
     /// Returns the contract calls for getting the addresses of the pool's tokens.
     pub fn get_tokens(&self) -> (ContractCall<M, Address>, ContractCall<M, Address>) {
         (self.contract.token_0(), self.contract.token_1())
     }
+
+    /// Returns the contract call for the pool's `slot0` method, which returns the
+    /// `(sqrtPriceX96, tick, observationIndex, observationCardinality, observationCardinalityNext,
+    /// feeProtocol, unlocked)` of the pool.
+    pub fn slot_0(&self) -> ContractCall<M, Slot0> {
+        self.contract.slot_0()
+    }
+
+    /// Returns the contract call for the pool's `liquidity` method, which returns the in-range
+    /// liquidity of the pool.
+    pub fn liquidity(&self) -> ContractCall<M, u128> {
+        self.contract.liquidity()
+    }
+
+    /// Returns the contract calls for the pool's `feeGrowthGlobal0X128` and `feeGrowthGlobal1X128`
+    /// methods, which return the fees collected per unit of liquidity for the entire life of the
+    /// pool, as `Q128.128`.
+    pub fn fee_growth_global(&self) -> (ContractCall<M, U256>, ContractCall<M, U256>) {
+        (self.contract.fee_growth_global_0x128(), self.contract.fee_growth_global_1x128())
+    }
+
+    /// Returns the contract call for the pool's `ticks` method, which returns the
+    /// `(liquidityGross, liquidityNet, feeGrowthOutside0X128, feeGrowthOutside1X128,
+    /// tickCumulativeOutside, secondsPerLiquidityOutsideX128, secondsOutside, initialized)` of
+    /// `tick`.
+    pub fn ticks(&self, tick: i32) -> ContractCall<M, TickInfo> {
+        self.contract.ticks(tick)
+    }
+
+    /// Returns the contract call for the pool's `tickBitmap` method, which returns the word of the
+    /// bitmap of initialized ticks at `word_position`, the tick index divided by the tick spacing
+    /// shifted right by 8 bits.
+    pub fn tick_bitmap(&self, word_position: i16) -> ContractCall<M, U256> {
+        self.contract.tick_bitmap(word_position)
+    }
+
+    /// Returns the contract call for the pool's `observations` method, which returns the
+    /// `(blockTimestamp, tickCumulative, secondsPerLiquidityCumulativeX128, initialized)` of the
+    /// price observation at `index`.
+    pub fn observations(&self, index: u16) -> ContractCall<M, (u32, i64, U256, bool)> {
+        self.contract.observations(index.into())
+    }
+
+    /// Returns the contract call for the pool's `observe` method, which returns the
+    /// `(tickCumulatives, secondsPerLiquidityCumulativeX128s)` as of each of `seconds_agos` before
+    /// the current block's timestamp.
+    pub fn observe(&self, seconds_agos: Vec<u32>) -> ContractCall<M, (Vec<i64>, Vec<U256>)> {
+        self.contract.observe(seconds_agos)
+    }
+
+    /// Returns the tokens, fee amount and tick spacing of the pool, fetching them with a single
+    /// [Multicall] request only the first time.
+    ///
+    /// Returns [`Error::PoolNotDeployed`] if the pool has not been deployed.
+    pub async fn fetch_immutables(&self) -> Result<(Tokens, FeeAmount, i32)> {
+        if let Some(immutables) = self.immutables() {
+            return Ok(immutables);
+        }
+        self.fetch(false).await?;
+        self.immutables().ok_or(Error::PoolNotDeployed(self.address()))
+    }
+
+    /// Fetches the pool's `slot0` and liquidity with a single [Multicall] request, along with its
+    /// immutables if they have not been cached yet, and updates the cached state.
+    ///
+    /// If a [quote cache][Self::set_quote_cache] is set, returns the state from it if it is cached,
+    /// otherwise caches the fetched state.
+    ///
+    /// Returns [`Error::PoolNotDeployed`] if the pool has not been deployed.
+    pub async fn state(&self) -> Result<PoolState> {
+        if let Some((chain, cache)) = &self.quote_cache {
+            if let Some(state) = cache.pool_state(*chain, self.address()) {
+                debug!(?state, "using the cached pool state");
+                return Ok(state);
+            }
+        }
+        let state = self.fetch(true).await?.ok_or(Error::PoolNotDeployed(self.address()))?;
+        if let Some((chain, cache)) = &self.quote_cache {
+            cache.insert_pool_state(*chain, self.address(), state);
+        }
+        Ok(state)
+    }
+
+    /// Fetches the pool's `slot0` and liquidity at `block` with a single [Multicall] request,
+    /// without updating the cache.
+    ///
+    /// Returns [`Error::ArchiveRequired`] if the node has pruned the state at `block`, and
+    /// [`Error::PoolNotDeployed`] if the pool had not been deployed at `block`.
+    pub async fn state_at(&self, block: impl Into<BlockId>) -> Result<PoolState> {
+        let block = block.into();
+        let mut multicall = Multicall::new(self.client(), None).await?.block(block);
+        multicall
+            .add_call(self.slot_0(), true)
+            .add_call(self.liquidity(), true)
+            .add_get_block_number();
+        let result = multicall.call_raw().await.map_err(|e| e.archive_required(block))?;
+        parse_state_result(result).ok_or(Error::PoolNotDeployed(self.address()))
+    }
+
+    /// Returns the amounts of `token0` and `token1` for `liquidity` in the range
+    /// `tick_lower..tick_upper` at the pool's current price, fetched from its `slot0`.
+    ///
+    /// The amounts are rounded down, like those withdrawn by burning `liquidity`, while minting
+    /// `liquidity` deposits at most 1 more of each token. See [`amounts_for_liquidity`].
+    ///
+    /// Returns [`Error::InvalidTick`] if a tick is out of range.
+    pub async fn amounts_for_position(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: u128,
+    ) -> Result<(U256, U256)> {
+        let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(tick_lower)?;
+        let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(tick_upper)?;
+        let (sqrt_price_x96, ..) = self.slot_0().call().await?;
+        amounts_for_liquidity(sqrt_price_x96, sqrt_ratio_a_x96, sqrt_ratio_b_x96, liquidity)
+    }
+
+    /// Returns the tokens owed to `position`, of `token0` and `token1`, including the fees earned
+    /// since it was last updated, which are not included in its `tokensOwed`.
+    ///
+    /// The global fee growth, the fee growth outside of the position's ticks and the current tick
+    /// are fetched with a single [Multicall] request, and the fee growth inside of the range is
+    /// computed like the pool does, with wrapping arithmetic.
+    ///
+    /// Returns [`Error::PoolNotDeployed`] if the pool has not been deployed.
+    pub async fn pending_fees(&self, position: &Position) -> Result<(U256, U256)> {
+        type FeesResult = (U256, U256, TickInfo, TickInfo, Slot0);
+
+        let mut multicall = Multicall::new(self.client(), None).await?;
+        let (fee_growth_global0, fee_growth_global1) = self.fee_growth_global();
+        multicall
+            .add_call(fee_growth_global0, false)
+            .add_call(fee_growth_global1, false)
+            .add_call(self.ticks(position.tick_lower), false)
+            .add_call(self.ticks(position.tick_upper), false)
+            .add_call(self.slot_0(), false);
+        let result = match multicall.call::<Token>().await {
+            Ok(result) => result,
+            // calls to an address without code succeed with no data
+            Err(Error::MulticallCallFailed { .. }) => {
+                self.set_deployed(false);
+                return Err(Error::PoolNotDeployed(self.address()));
+            }
+            Err(e) => return Err(e),
+        };
+        let (global0, global1, lower, upper, slot_0) = FeesResult::from_tokens(result)?;
+
+        let tick = slot_0.1;
+        let inside0 = fee_growth_inside(position, tick, global0, lower.2, upper.2);
+        let inside1 = fee_growth_inside(position, tick, global1, lower.3, upper.3);
+        let owed0 = fees_owed(inside0, position.fee_growth_inside0_last_x128, position.liquidity)?;
+        let owed1 = fees_owed(inside1, position.fee_growth_inside1_last_x128, position.liquidity)?;
+        Ok((owed0 + position.tokens_owed0, owed1 + position.tokens_owed1))
+    }
+
+    /// Returns the time-weighted average price of the pool over the last `window`, from the tick
+    /// cumulatives returned by its [`observe`][Self::observe] method, like the Solidity
+    /// [oracle library]'s `consult`.
+    ///
+    /// The tick cumulatives, the decimals of the tokens and the pool's `slot0` are fetched with a
+    /// single [Multicall] request, along with the pool's immutables if they have not been cached
+    /// yet.
+    ///
+    /// Returns [`Error::OracleWindowTooLong`] with the age of the oldest observation if the pool's
+    /// observations do not cover `window`, in which case their number can be increased with
+    /// [`increase_cardinality`][Self::increase_cardinality]. Returns [`Error::InvalidTwapWindow`]
+    /// if `window` is shorter than a second or does not fit in a `uint32`.
+    ///
+    /// [oracle library]: https://github.com/Uniswap/v3-periphery/blob/main/contracts/libraries/OracleLibrary.sol
+    pub async fn twap(&self, window: Duration) -> Result<Twap> {
+        let seconds = u32::try_from(window.as_secs()).map_err(|_| Error::InvalidTwapWindow)?;
+        if seconds == 0 {
+            return Err(Error::InvalidTwapWindow);
+        }
+        let ((token0, token1), ..) = self.fetch_immutables().await?;
+
+        let mut multicall = Multicall::new(self.client(), None).await?;
+        let decimals = |token| {
+            let mut call = IERC20::new(Address::zero(), self.client()).decimals();
+            call.tx.set_to(token);
+            call
+        };
+        multicall
+            .add_call(self.observe(vec![seconds, 0]), true)
+            .add_call(decimals(token0), false)
+            .add_call(decimals(token1), false)
+            .add_call(self.slot_0(), false)
+            .add_get_current_block_timestamp();
+        let (observation_index, timestamp) =
+            match parse_twap_result(multicall.call_raw().await?, seconds)? {
+                Ok(twap) => return Ok(twap),
+                Err(oldest) => oldest,
+            };
+
+        // the oldest observation is the next one to be overwritten, unless the ring buffer has
+        // not been filled yet
+        let mut oldest = self.observations(observation_index).call().await?;
+        if !oldest.3 {
+            oldest = self.observations(0).call().await?;
+        }
+        let age = timestamp.low_u64().saturating_sub(oldest.0.into());
+        Err(Error::OracleWindowTooLong { available: Duration::from_secs(age) })
+    }
+
+    /// Returns the contract call for the pool's `increaseObservationCardinalityNext` method, which
+    /// increases the number of price observations stored by the pool to `target`, paying for their
+    /// storage.
+    pub fn increase_cardinality(&self, target: u16) -> ContractCall<M, ()> {
+        self.contract.increase_observation_cardinality_next(target)
+    }
+
+    /// Returns the initialized ticks of the pool in the `words` of its tick bitmap, for simulating
+    /// swaps with [`simulate`][super::math::swap::simulate].
+    ///
+    /// If the pool has a [tick lens][Self::set_tick_lens], the ticks are fetched from it with a
+    /// single [Multicall] request. Otherwise, or if the lens is not deployed, the words are fetched
+    /// with a single [Multicall] request, and then the net liquidity of their initialized ticks
+    /// with another one. The pool's immutables are also fetched if they have not been cached yet.
+    /// The words containing a tick can be found with [`TickData::word_position`].
+    ///
+    /// Returns [`Error::PoolNotDeployed`] if the pool has not been deployed.
+    pub async fn load_ticks(&self, words: RangeInclusive<i16>) -> Result<TickData> {
+        let (_, _, tick_spacing) = self.fetch_immutables().await?;
+        let words: Vec<_> = words.collect();
+        let mut ticks = TickData::new(tick_spacing);
+
+        if let Some(populated) = self.populated_ticks(&words).await? {
+            insert_populated(&mut ticks, words, populated);
+            return Ok(ticks);
+        }
+
+        let calls = words.iter().map(|&word| self.tick_bitmap(word)).collect();
+        let bitmaps = call_multi(self.client(), None, calls).await?;
+        let initialized = insert_bitmaps(&mut ticks, words, bitmaps);
+        let calls = initialized.iter().map(|&tick| self.ticks(tick)).collect();
+        let infos = call_multi(self.client(), None, calls).await?;
+        ticks.liquidity_net =
+            initialized.into_iter().zip(infos.into_iter().map(|info| info.1)).collect();
+        Ok(ticks)
+    }
+
+    /// Returns the initialized ticks of the pool in the word of its tick bitmap at `word_position`,
+    /// and their net liquidity, in ascending order. See [`load_ticks`][Self::load_ticks].
+    pub async fn populated_ticks_in_word(&self, word_position: i16) -> Result<Vec<(i32, i128)>> {
+        let ticks = self.load_ticks(word_position..=word_position).await?;
+        Ok(ticks.liquidity_net.into_iter().collect())
+    }
+
+    /// Returns the initialized ticks of the pool in the range of `ticks`, and their net liquidity,
+    /// loading the words of its tick bitmap which contain them. See
+    /// [`load_ticks`][Self::load_ticks].
+    pub async fn liquidity_map(&self, ticks: RangeInclusive<i32>) -> Result<BTreeMap<i32, i128>> {
+        let (_, _, tick_spacing) = self.fetch_immutables().await?;
+        let words = TickData::word_position(*ticks.start(), tick_spacing)
+            ..=TickData::word_position(*ticks.end(), tick_spacing);
+        let mut liquidity_net = self.load_ticks(words).await?.liquidity_net;
+        liquidity_net.retain(|tick, _| ticks.contains(tick));
+        Ok(liquidity_net)
+    }
+
+    /// Returns the amounts of `token0` and `token1` available within `ticks_each_side` ticks above
+    /// and below the pool's current price, fetched with [`state`][Self::state] and
+    /// [`liquidity_map`][Self::liquidity_map].
+    pub async fn depth_chart(&self, ticks_each_side: u32) -> Result<DepthChart> {
+        let state = self.state().await?;
+        let liquidity_net = self.liquidity_map(tick_range(state.tick, ticks_each_side)).await?;
+        DepthChart::new(&state, &liquidity_net, ticks_each_side)
+    }
+
+    /// Returns the pool's `Swap` event filter.
+    pub fn swap_filter(&self) -> Event<'_, M, SwapFilter> {
+        self.contract.swap_filter()
+    }
+
+    /// Returns the pool's `Mint` event filter.
+    pub fn mint_filter(&self) -> Event<'_, M, MintFilter> {
+        self.contract.mint_filter()
+    }
+
+    /// Returns the pool's `Burn` event filter.
+    pub fn burn_filter(&self) -> Event<'_, M, BurnFilter> {
+        self.contract.burn_filter()
+    }
+
+    /// Returns the pool's `Collect` event filter.
+    pub fn collect_filter(&self) -> Event<'_, M, CollectFilter> {
+        self.contract.collect_filter()
+    }
+
+    /// Returns the pool's `Flash` event filter.
+    pub fn flash_filter(&self) -> Event<'_, M, FlashFilter> {
+        self.contract.flash_filter()
+    }
+
+    /// Returns a stream of the pool's new `Swap` events, with their prices, by polling a filter at
+    /// the client's polling interval. See [`subscribe_swaps`][Self::subscribe_swaps] for clients
+    /// which support subscriptions.
+    ///
+    /// The decimals of the tokens are fetched with a single [Multicall] request first, along with
+    /// the pool's immutables if they have not been cached yet. Each item has the block number and
+    /// index of its log, and logs removed by a chain reorganization are emitted again with
+    /// [`removed`][DecodedLog::removed] set.
+    pub async fn stream_swaps(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<SwapEvent>>> + Unpin> {
+        let (decimals0, decimals1) = self.fetch_decimals().await?;
+        let logs = watch_logs(self.client(), &self.swap_filter().filter).await?;
+        Ok(logs.map(move |log| swap_event(log?, decimals0, decimals1)))
+    }
+
+    /// Returns a stream of the pool's new `Swap` events, with their prices, by subscribing to them.
+    /// See [`stream_swaps`][Self::stream_swaps] for more details.
+    pub async fn subscribe_swaps(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<SwapEvent>>> + Unpin>
+    where
+        M::Provider: PubsubClient,
+    {
+        let (decimals0, decimals1) = self.fetch_decimals().await?;
+        let logs = subscribe_logs(self.client(), &self.swap_filter().filter).await?;
+        Ok(logs.map(move |log| swap_event(log?, decimals0, decimals1)))
+    }
+
+    /// Returns a stream of the price of `token0` in terms of `token1` after each of the pool's new
+    /// `Swap` events, adjusted for the tokens' decimals, by polling a filter at the client's
+    /// polling interval. See [`subscribe_prices`][Self::subscribe_prices] for clients which
+    /// support subscriptions, and [`stream_swaps`][Self::stream_swaps] for more details.
+    pub async fn stream_prices(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<f64>>> + Unpin> {
+        let swaps = self.stream_swaps().await?;
+        Ok(swaps.map(|swap| Ok(swap?.map(|swap| swap.price))))
+    }
+
+    /// Returns a stream of the price of `token0` in terms of `token1` after each of the pool's new
+    /// `Swap` events, by subscribing to them. See [`stream_prices`][Self::stream_prices] for more
+    /// details.
+    pub async fn subscribe_prices(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DecodedLog<f64>>> + Unpin>
+    where
+        M::Provider: PubsubClient,
+    {
+        let swaps = self.subscribe_swaps().await?;
+        Ok(swaps.map(|swap| Ok(swap?.map(|swap| swap.price))))
+    }
+
+    /// Returns the decimals of the pool's tokens, fetched with a single [Multicall] request along
+    /// with the pool's immutables if they have not been cached yet.
+    async fn fetch_decimals(&self) -> Result<(u8, u8)> {
+        let ((token0, token1), ..) = self.fetch_immutables().await?;
+        let decimals = |token| {
+            let mut call = IERC20::new(Address::zero(), self.client()).decimals();
+            call.tx.set_to(token);
+            call
+        };
+        let calls = vec![decimals(token0), decimals(token1)];
+        match call_multi(self.client(), None, calls).await?[..] {
+            [decimals0, decimals1] => Ok((decimals0, decimals1)),
+            _ => Err(InvalidOutputType("missing decimals".into()).into()),
+        }
+    }
+
+    /// Returns the populated ticks of each of `words` fetched from the pool's tick lens with a
+    /// single [Multicall] request, or None if it has no tick lens or it is not deployed.
+    async fn populated_ticks(&self, words: &[i16]) -> Result<Option<Vec<Vec<PopulatedTick>>>> {
+        let lens = match self.tick_lens {
+            Some(lens) if !words.is_empty() => ITickLens::new(lens, self.client()),
+            _ => return Ok(None),
+        };
+        let mut multicall = Multicall::new(self.client(), None).await?;
+        for &word in words {
+            multicall.add_call(lens.get_populated_ticks_in_word(self.address(), word), false);
+        }
+        match multicall.call::<Vec<PopulatedTick>>().await {
+            Ok(results) => Ok(Some(results)),
+            // calls to an address without code succeed with no data
+            Err(Error::MulticallCallFailed { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn immutables(&self) -> Option<(Tokens, FeeAmount, i32)> {
+        Some((self.tokens()?, self.fee()?, self.tick_spacing()?))
+    }
+
+    /// Fetches the immutables, if not cached, and the state of the pool, and updates the cache.
+    /// Returns None if the pool has not been deployed.
+    async fn fetch(&self, fetch_state: bool) -> Result<Option<PoolState>> {
+        let mut multicall = Multicall::new(self.client(), None).await?;
+
+        let fetch_immutables = self.immutables().is_none();
+        if fetch_immutables {
+            let (token0, token1) = self.get_tokens();
+            multicall
+                .add_call(token0, true)
+                .add_call(token1, true)
+                .add_call(self.contract.fee(), true)
+                .add_call(self.contract.tick_spacing(), true);
+        }
+        if fetch_state {
+            multicall
+                .add_call(self.slot_0(), true)
+                .add_call(self.liquidity(), true)
+                .add_get_block_number();
+        }
+
+        // Assume any call failure means the contract has not been deployed yet
+        let mut result = match multicall.call_raw().await {
+            Ok(result) => result,
+            Err(Error::MulticallCallFailed { .. }) => {
+                self.set_deployed(false);
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+        let state = result.split_off(if fetch_immutables { 4 } else { 0 });
+        if fetch_immutables {
+            match parse_immutables_result(result) {
+                Some((tokens, fee, tick_spacing)) => {
+                    self.cache.tokens.get_or_init(|| tokens);
+                    self.cache.fee.get_or_init(|| fee);
+                    self.cache.tick_spacing.get_or_init(|| tick_spacing);
+                }
+                None => {
+                    self.set_deployed(false);
+                    return Ok(None);
+                }
+            }
+        }
+        let state = if fetch_state {
+            match parse_state_result(state) {
+                Some(state) => {
+                    *self.cache.state.write().unwrap_or_else(PoisonError::into_inner) = Some(state);
+                    Some(state)
+                }
+                None => {
+                    self.set_deployed(false);
+                    return Ok(None);
+                }
+            }
+        } else {
+            None
+        };
+        self.set_deployed(true);
+        Ok(state)
+    }
+
+    fn set_deployed(&self, deployed: bool) {
+        self.cache.deployed.store(deployed, Ordering::Release);
+    }
+}
+
+/// Returns the fee growth inside of the range of `position` at `tick`, like
+/// `Tick.getFeeGrowthInside`, from the global fee growth and the fee growth outside of its lower
+/// and upper ticks.
+fn fee_growth_inside(
+    position: &Position,
+    tick: i32,
+    global: U256,
+    outside_lower: U256,
+    outside_upper: U256,
+) -> U256 {
+    let below = if tick >= position.tick_lower {
+        outside_lower
+    } else {
+        global.overflowing_sub(outside_lower).0
+    };
+    let above = if tick < position.tick_upper {
+        outside_upper
+    } else {
+        global.overflowing_sub(outside_upper).0
+    };
+    global.overflowing_sub(below).0.overflowing_sub(above).0
+}
+
+/// Returns the fees earned by `liquidity` since the fee growth inside of its range was `last`,
+/// rounded down.
+fn fees_owed(inside: U256, last: U256, liquidity: u128) -> Result<U256> {
+    let q128 = U256::one() << 128;
+    // truncated to 128 bits, like the pool
+    Ok(mul_div(inside.overflowing_sub(last).0, liquidity.into(), q128)?.low_u128().into())
+}
+
+/// Decodes a `Swap` event log into a [SwapEvent] with the decimals of the pool's tokens.
+fn swap_event(log: Log, decimals0: u8, decimals1: u8) -> Result<DecodedLog<SwapEvent>> {
+    let swap = DecodedLog::<SwapFilter>::decode(&log)?;
+    Ok(swap.map(|event| SwapEvent::new(event, decimals0, decimals1)))
+}
+
+/// Parses a multicall result of the pool's `token0`, `token1`, `fee` and `tickSpacing`, returning
+/// None if any call failed or returned no data, as when calling an address without code.
+fn parse_immutables_result(results: Vec<CallOutput>) -> Option<(Tokens, FeeAmount, i32)> {
+    type ImmutablesResult = (Address, Address, u32, i32);
+    let tokens = results.into_iter().collect::<Result<_, _>>().ok()?;
+    let (token0, token1, fee, tick_spacing) = ImmutablesResult::from_tokens(tokens).ok()?;
+    Some(((token0, token1), FeeAmount::try_from(fee).ok()?, tick_spacing))
+}
+
+/// Parses a multicall result of the pool's `slot0` and `liquidity` and the block number, returning
+/// None if any call failed or returned no data, as when calling an address without code.
+/// Parses the results of [`Pool::twap`]'s request, returning the index of the next observation
+/// and the block's timestamp instead if the observations do not cover the window of `seconds`.
+fn parse_twap_result(results: Vec<CallOutput>, seconds: u32) -> Result<Result<Twap, (u16, U256)>> {
+    let mut results = results.into_iter();
+    let observed = results.next().ok_or(InvalidOutputType("missing result".into()))?;
+    let mut next = || match results.next() {
+        Some(Ok(token)) => Ok(token),
+        _ => Err(InvalidOutputType("missing result".into())),
+    };
+    let decimals0 = u8::from_token(next()?)?;
+    let decimals1 = u8::from_token(next()?)?;
+    let (_, _, observation_index, observation_cardinality, ..) = Slot0::from_token(next()?)?;
+    let timestamp = U256::from_token(next()?)?;
+
+    match observed {
+        Ok(observed) => {
+            let (tick_cumulatives, _) = <(Vec<i64>, Vec<U256>)>::from_token(observed)?;
+            let (start, end) = match tick_cumulatives[..] {
+                [start, end] => (start, end),
+                _ => return Err(InvalidOutputType("missing tick cumulative".into()).into()),
+            };
+            let window = Duration::from_secs(seconds.into());
+            Twap::new(start, end, window, decimals0, decimals1).map(Ok)
+        }
+        Err(data) if revert_reason(&data).as_deref() == Some("OLD") => {
+            Ok(Err(((observation_index + 1) % observation_cardinality.max(1), timestamp)))
+        }
+        Err(data) => {
+            let reason = revert_reason(&data).unwrap_or_else(|| data.to_string());
+            Err(Error::ContractError(format!("observe reverted: {reason}")))
+        }
+    }
+}
+
+/// Inserts the `populated` ticks of each of `words`, fetched from the tick lens, into `ticks`.
+fn insert_populated(ticks: &mut TickData, words: Vec<i16>, populated: Vec<Vec<PopulatedTick>>) {
+    for (word, populated) in words.into_iter().zip(populated) {
+        let mut bitmap = U256::zero();
+        for tick in populated {
+            bitmap |= U256::one() << ((tick.tick / ticks.tick_spacing) & 0xff) as u32;
+            ticks.liquidity_net.insert(tick.tick, tick.liquidity_net);
+        }
+        ticks.words.insert(word, bitmap);
+    }
+}
+
+/// Inserts the `bitmaps` of `words` into `ticks`, returning their initialized ticks.
+fn insert_bitmaps(ticks: &mut TickData, words: Vec<i16>, bitmaps: Vec<U256>) -> Vec<i32> {
+    let mut initialized = vec![];
+    for (word, bitmap) in words.into_iter().zip(bitmaps) {
+        for bit in (0..256).filter(|&bit| bitmap.bit(bit)) {
+            initialized.push((word as i32 * 256 + bit as i32) * ticks.tick_spacing);
+        }
+        ticks.words.insert(word, bitmap);
+    }
+    initialized
+}
+
+fn parse_state_result(results: Vec<CallOutput>) -> Option<PoolState> {
+    type StateResult = (Slot0, u128, U256);
+    let tokens = results.into_iter().collect::<Result<_, _>>().ok()?;
+    let (slot_0, liquidity, block_number) = StateResult::from_tokens(tokens).ok()?;
+    let (
+        sqrt_price_x96,
+        tick,
+        observation_index,
+        observation_cardinality,
+        observation_cardinality_next,
+        fee_protocol,
+        unlocked,
+    ) = slot_0;
+    Some(PoolState {
+        sqrt_price_x96,
+        tick,
+        observation_index,
+        observation_cardinality,
+        observation_cardinality_next,
+        fee_protocol,
+        unlocked,
+        liquidity,
+        block_number: block_number.as_u64(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::Fork, v3::math::MIN_TICK};
+    use ethers_contract::EthEvent;
+    use ethers_core::{abi, types::Bytes};
+    use ethers_providers::{MockProvider, Provider};
+
+    fn push_multicall(mock: &MockProvider, results: &[Vec<u8>]) {
+        let results: Vec<_> = results.iter().map(|data| (true, data.clone())).collect();
+        push_multicall_with_failures(mock, &results);
+    }
+
+    fn push_multicall_with_failures(mock: &MockProvider, results: &[(bool, Vec<u8>)]) {
+        let results = results
+            .iter()
+            .map(|(success, data)| {
+                Token::Tuple(vec![Token::Bool(*success), Token::Bytes(data.clone())])
+            })
+            .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        // chain id, queried by the multicall
+        mock.push(U256::one()).unwrap();
+    }
+
+    fn encode_state(sqrt_price_x96: U256, tick: i32, liquidity: u128, block: u64) -> Vec<Vec<u8>> {
+        let slot_0 = abi::encode(&[
+            Token::Uint(sqrt_price_x96),
+            Token::Int(ethers_core::types::I256::from(tick).into_raw()),
+            Token::Uint(2.into()),
+            Token::Uint(10.into()),
+            Token::Uint(10.into()),
+            Token::Uint(0.into()),
+            Token::Bool(true),
+        ]);
+        vec![
+            slot_0,
+            abi::encode(&[Token::Uint(liquidity.into())]),
+            abi::encode(&[Token::Uint(block.into())]),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_state() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert!(!pool.deployed());
+        assert_eq!(pool.cached_state(), None);
+
+        // 2. only the state, as the immutables are cached
+        let sqrt_price_x96 = U256::one() << 96;
+        push_multicall(&mock, &encode_state(sqrt_price_x96, 0, 2000, 101));
+        // 1. immutables and state
+        let mut results = vec![
+            abi::encode(&[Token::Address(a)]),
+            abi::encode(&[Token::Address(b)]),
+            abi::encode(&[Token::Uint(500.into())]),
+            abi::encode(&[Token::Int(10.into())]),
+        ];
+        results.extend(encode_state(sqrt_price_x96 * 2, -1, 1000, 100));
+        push_multicall(&mock, &results);
+
+        let state = pool.state().await.unwrap();
+        assert!(pool.deployed());
+        assert_eq!(pool.tokens(), Some((a, b)));
+        assert_eq!(pool.fee(), Some(FeeAmount::Low));
+        assert_eq!(pool.tick_spacing(), Some(10));
+        let expected = PoolState {
+            sqrt_price_x96: sqrt_price_x96 * 2,
+            tick: -1,
+            observation_index: 2,
+            observation_cardinality: 10,
+            observation_cardinality_next: 10,
+            fee_protocol: 0,
+            unlocked: true,
+            liquidity: 1000,
+            block_number: 100,
+        };
+        assert_eq!(state, expected);
+        assert_eq!(pool.cached_state(), Some(state));
+
+        // shared between clones
+        let clone = pool.clone();
+        let state = clone.state().await.unwrap();
+        assert_eq!((state.sqrt_price_x96, state.tick), (sqrt_price_x96, 0));
+        assert_eq!((state.liquidity, state.block_number), (2000, 101));
+        assert_eq!(pool.cached_state(), Some(state));
+        assert_eq!(pool.fetch_immutables().await.unwrap(), ((a, b), FeeAmount::Low, 10));
+    }
+
+    #[tokio::test]
+    async fn test_state_at() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+
+        let sqrt_price_x96 = U256::one() << 96;
+        push_multicall(&mock, &encode_state(sqrt_price_x96, 0, 2000, 100));
+        let state = pool.state_at(100).await.unwrap();
+        assert_eq!((state.sqrt_price_x96, state.tick), (sqrt_price_x96, 0));
+        assert_eq!((state.liquidity, state.block_number), (2000, 100));
+        // the cache is not updated
+        assert_eq!(pool.cached_state(), None);
+
+        // no code at the block
+        let results = [
+            (true, vec![]),
+            (true, vec![]),
+            (true, encode_state(U256::zero(), 0, 0, 1)[2].clone()),
+        ];
+        push_multicall_with_failures(&mock, &results);
+        let res = pool.state_at(1).await;
+        assert!(matches!(res.unwrap_err(), Error::PoolNotDeployed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_state_not_deployed() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let address = Address::repeat_byte(0x11);
+        let pool = Pool::new(Arc::new(provider), address, ProtocolType::UniswapV3);
+
+        // calls to an address without code succeed with no data
+        push_multicall(&mock, &vec![vec![]; 7]);
+        let res = pool.state().await;
+        assert!(matches!(res, Err(Error::PoolNotDeployed(a)) if a == address));
+        assert!(!pool.deployed());
+        assert_eq!(pool.tokens(), None);
+    }
+
+    #[tokio::test]
+    async fn test_amounts_for_position() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let push_slot_0 = |sqrt_price_x96| {
+            let slot_0 = &encode_state(sqrt_price_x96, 0, 0, 0)[0];
+            mock.push::<Bytes, _>(Bytes::from(slot_0.clone())).unwrap();
+        };
+
+        // in range: 1 token1 per token0
+        push_slot_0(U256::one() << 96);
+        let amounts = pool.amounts_for_position(-60, 60, 1_000_000).await.unwrap();
+        assert_eq!(amounts, (2995.into(), 2995.into()));
+        // below the range, all token0
+        push_slot_0(get_sqrt_ratio_at_tick(-61).unwrap());
+        let amounts = pool.amounts_for_position(-60, 60, 1_000_000).await.unwrap();
+        assert_eq!(amounts, (5999.into(), 0.into()));
+        // above the range, all token1
+        push_slot_0(get_sqrt_ratio_at_tick(60).unwrap());
+        let amounts = pool.amounts_for_position(-60, 60, 1_000_000).await.unwrap();
+        assert_eq!(amounts, (0.into(), 5999.into()));
+
+        let res = pool.amounts_for_position(-887273, 60, 1).await;
+        assert!(matches!(res, Err(Error::InvalidTick(-887273))));
+    }
+
+    #[tokio::test]
+    async fn test_pending_fees() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let x128 = |x: u64| U256::from(x) << 128;
+        let wrapped = |x: u64| U256::zero().overflowing_sub(x128(x)).0;
+        let position = Position {
+            nonce: 0,
+            operator: Address::zero(),
+            token0: Address::repeat_byte(1),
+            token1: Address::repeat_byte(2),
+            fee: FeeAmount::Medium,
+            tick_lower: -60,
+            tick_upper: 60,
+            liquidity: 3,
+            fee_growth_inside0_last_x128: x128(20),
+            fee_growth_inside1_last_x128: wrapped(10),
+            tokens_owed0: 5,
+            tokens_owed1: 7,
+        };
+        let tick_info = |outside0: U256, outside1: U256| {
+            abi::encode(&[
+                Token::Uint(1.into()),
+                Token::Int(1.into()),
+                Token::Uint(outside0),
+                Token::Uint(outside1),
+                Token::Int(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Bool(true),
+            ])
+        };
+        let results = [
+            abi::encode(&[Token::Uint(x128(100))]),
+            abi::encode(&[Token::Uint(x128(40))]),
+            tick_info(wrapped(50), x128(10)),
+            tick_info(x128(30), x128(5)),
+            encode_state(U256::one() << 96, 0, 0, 0)[0].clone(),
+        ];
+        push_multicall(&mock, &results);
+
+        // in range, inside = global - below - above, with the below growth of token0 wrapped
+        // token0: (100 + 50 - 30 - 20) * 3 + 5, token1: (40 - 10 - 5 + 10) * 3 + 7
+        let fees = pool.pending_fees(&position).await.unwrap();
+        assert_eq!(fees, (305.into(), 112.into()));
+
+        // above the range, the outside growth of the upper tick is on the other side
+        let inside = fee_growth_inside(&position, 60, x128(100), wrapped(50), x128(30));
+        assert_eq!(inside, x128(80));
+        // below the range
+        let inside = fee_growth_inside(&position, -61, x128(100), x128(50), x128(30));
+        assert_eq!(inside, x128(20));
+        // truncated to 128 bits
+        let owed = fees_owed(U256::MAX, U256::zero(), u128::MAX).unwrap();
+        assert_eq!(owed, (U256::MAX - (U256::one() << 128)).low_u128().into());
+    }
+
+    #[tokio::test]
+    async fn test_twap() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let int = |x: i64| Token::Int(ethers_core::types::I256::from(x).into_raw());
+        let uint = |x: u64| abi::encode(&[Token::Uint(x.into())]);
+        let results = |observed: (bool, Vec<u8>)| {
+            let slot_0 = encode_state(U256::one() << 96, 0, 0, 0)[0].clone();
+            [observed, (true, uint(6)), (true, uint(18)), (true, slot_0), (true, uint(1000))]
+        };
+
+        // 2. the tick cumulatives, the decimals, slot0 and the timestamp
+        let tick_cumulatives = Token::Array(vec![int(-600), int(0)]);
+        let observed = abi::encode(&[tick_cumulatives, Token::Array(vec![])]);
+        push_multicall_with_failures(&mock, &results((true, observed)));
+        // 1. the immutables
+        let immutables = [
+            abi::encode(&[Token::Address(Address::repeat_byte(1))]),
+            abi::encode(&[Token::Address(Address::repeat_byte(2))]),
+            uint(500),
+            uint(10),
+        ];
+        push_multicall(&mock, &immutables);
+
+        let twap = pool.twap(Duration::from_secs(60)).await.unwrap();
+        assert_eq!(twap.tick, 10);
+        assert_eq!(twap.window, Duration::from_secs(60));
+        let expected = 1.0001f64.powi(10) * 1e-12;
+        assert!((twap.price - expected).abs() < expected * 1e-12);
+
+        // the observations don't cover the window: the next one, at index 3, is not initialized
+        let observation = |timestamp: u64, initialized: bool| {
+            let tokens = [
+                Token::Uint(timestamp.into()),
+                int(0),
+                Token::Uint(0.into()),
+                Token::Bool(initialized),
+            ];
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&tokens))).unwrap();
+        };
+        observation(400, true);
+        observation(0, false);
+        let old =
+            [&[0x08, 0xc3, 0x79, 0xa0][..], &abi::encode(&[Token::String("OLD".into())])].concat();
+        push_multicall_with_failures(&mock, &results((false, old)));
+        let res = pool.twap(Duration::from_secs(3600)).await;
+        assert!(matches!(
+            res,
+            Err(Error::OracleWindowTooLong { available }) if available == Duration::from_secs(600)
+        ));
+
+        assert!(matches!(pool.twap(Duration::ZERO).await, Err(Error::InvalidTwapWindow)));
+    }
+
+    #[tokio::test]
+    async fn test_load_ticks() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let int = |x: i64| abi::encode(&[Token::Int(ethers_core::types::I256::from(x).into_raw())]);
+        let uint = |x: U256| abi::encode(&[Token::Uint(x)]);
+        let tick_info = |liquidity_net: i128| {
+            abi::encode(&[
+                Token::Uint(liquidity_net.unsigned_abs().into()),
+                Token::Int(ethers_core::types::I256::from(liquidity_net).into_raw()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Int(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Bool(true),
+            ])
+        };
+
+        // 3. the initialized ticks
+        push_multicall(&mock, &[tick_info(5), tick_info(7), tick_info(-12)]);
+        // 2. the words: ticks -60 and -10 in word -1, and 10 in word 0
+        let word = (U256::one() << 250) | (U256::one() << 255);
+        push_multicall(&mock, &[uint(word), uint(U256::from(2))]);
+        // 1. the immutables
+        let results = [
+            abi::encode(&[Token::Address(Address::repeat_byte(1))]),
+            abi::encode(&[Token::Address(Address::repeat_byte(2))]),
+            uint(500.into()),
+            int(10),
+        ];
+        push_multicall(&mock, &results);
+
+        let ticks = pool.load_ticks(-1..=0).await.unwrap();
+        assert_eq!(ticks.tick_spacing, 10);
+        assert_eq!(ticks.words, BTreeMap::from([(-1, word), (0, U256::from(2))]));
+        assert_eq!(ticks.liquidity_net, BTreeMap::from([(-60, 5), (-10, 7), (10, -12)]));
+
+        // with a tick lens, whose populated ticks are in descending order
+        let populated = |ticks: &[(i32, i128)]| {
+            let ticks = ticks
+                .iter()
+                .map(|&(tick, liquidity_net)| {
+                    Token::Tuple(vec![
+                        Token::Int(ethers_core::types::I256::from(tick).into_raw()),
+                        Token::Int(ethers_core::types::I256::from(liquidity_net).into_raw()),
+                        Token::Uint(liquidity_net.unsigned_abs().into()),
+                    ])
+                })
+                .collect();
+            abi::encode(&[Token::Array(ticks)])
+        };
+        let mut lens_pool = pool.clone();
+        lens_pool.set_tick_lens(Address::repeat_byte(0x22));
+        push_multicall(&mock, &[populated(&[(-10, 7), (-60, 5)]), populated(&[(10, -12)])]);
+        assert_eq!(lens_pool.load_ticks(-1..=0).await.unwrap(), ticks);
+        push_multicall(&mock, &[populated(&[(10, -12)])]);
+        assert_eq!(lens_pool.populated_ticks_in_word(0).await.unwrap(), [(10, -12)]);
+
+        // falls back to the tick bitmap if the lens is not deployed
+        push_multicall(&mock, &[tick_info(5), tick_info(7)]);
+        mock.push::<Bytes, _>(Bytes::from(uint(word))).unwrap();
+        push_multicall(&mock, &[vec![]]);
+        let liquidity_net = lens_pool.liquidity_map(-59..=-10).await.unwrap();
+        assert_eq!(liquidity_net, BTreeMap::from([(-10, 7)]));
+    }
+
+    #[tokio::test]
+    async fn test_stream_swaps() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let pool =
+            Pool::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV3);
+        let uint = |x: u64| abi::encode(&[Token::Uint(x.into())]);
+        let int = |x: i128| Token::Int(I256::from(x).into_raw());
+        // 2000 token0 with 6 decimals out for 1 token1 with 18 decimals in
+        let swap_log = |log_index: u64, removed: bool| Log {
+            topics: vec![SwapFilter::signature(), H256::zero(), H256::zero()],
+            data: abi::encode(&[
+                int(-2_000_000_000),
+                int(1_000_000_000_000_000_000),
+                Token::Uint(U256::one() << 96),
+                Token::Uint(1_000.into()),
+                int(0),
+            ])
+            .into(),
+            block_number: Some(7.into()),
+            log_index: Some(log_index.into()),
+            removed: Some(removed),
+            ..Default::default()
+        };
+
+        // (sorted in reverse, as the mock is LIFO)
+        // 4. the changes of the first poll
+        mock.push::<Vec<Log>, _>(vec![swap_log(1, false), swap_log(1, true)]).unwrap();
+        // 3. the filter id
+        mock.push(U256::one()).unwrap();
+        // 2. the decimals
+        push_multicall(&mock, &[uint(6), uint(18)]);
+        // 1. the immutables
+        let immutables = [
+            abi::encode(&[Token::Address(Address::repeat_byte(1))]),
+            abi::encode(&[Token::Address(Address::repeat_byte(2))]),
+            uint(500),
+            uint(10),
+        ];
+        push_multicall(&mock, &immutables);
+
+        let mut swaps = pool.stream_swaps().await.unwrap();
+        let swap = swaps.next().await.unwrap().unwrap();
+        assert_eq!(swap.meta.block_number, 7.into());
+        assert_eq!(swap.meta.log_index, 1.into());
+        assert!(!swap.removed);
+        assert_eq!(swap.event.amount0, I256::from(-2_000_000_000i64));
+        assert_eq!(swap.event.liquidity, 1_000);
+        assert!((swap.event.execution_price - 5e-4).abs() < 1e-18);
+        assert!((swap.event.price - 1e-12).abs() < 1e-24);
+        assert!(swaps.next().await.unwrap().unwrap().removed);
+
+        // the immutables are cached
+        mock.push::<Vec<Log>, _>(vec![swap_log(2, false)]).unwrap();
+        mock.push(U256::one()).unwrap();
+        push_multicall(&mock, &[uint(6), uint(18)]);
+        let mut prices = pool.stream_prices().await.unwrap();
+        let price = prices.next().await.unwrap().unwrap();
+        assert_eq!(price.meta.log_index, 2.into());
+        assert!((price.event - 1e-12).abs() < 1e-24);
+    }
+
+    /// The pools must not change between the calls, so the URL should be that of a fork which is
+    /// not mining.
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn simulate_matches_quoter() {
+        use crate::v3::{
+            math::swap::{simulate, TickData},
+            Quoter,
+        };
+        use ethers_core::types::I256;
+
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        let quoter = "0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse().unwrap();
+        let quoter = Quoter::new_v2(client.clone(), quoter);
+        let pools = [
+            // USDC/WETH 0.05%
+            "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640",
+            // WBTC/WETH 0.3%
+            "0xCBCdF9626bC03E24f779434178A73a0B4bad62eD",
+            // WETH/USDT 0.3%
+            "0x4e68Ccd3E89f51C3074ca5072bbAC773960dFa36",
+            // DAI/USDC 0.01%
+            "0x5777d92f208679DB4b9778590Fa3CAB3aC9e2168",
+        ];
+
+        for address in pools {
+            let pool = Pool::new(client.clone(), address.parse().unwrap(), ProtocolType::UniswapV3);
+            let ((token0, token1), fee, tick_spacing) = pool.fetch_immutables().await.unwrap();
+            let state = pool.state().await.unwrap();
+            let word = TickData::word_position(state.tick, tick_spacing);
+            let ticks = pool.load_ticks(word - 8..=word + 8).await.unwrap();
+
+            for zero_for_one in [true, false] {
+                let (token_in, token_out) =
+                    if zero_for_one { (token0, token1) } else { (token1, token0) };
+                let balance = |token| IERC20::new(token, client.clone()).balance_of(pool.address());
+                let balance_in = balance(token_in).call().await.unwrap();
+                let balance_out = balance(token_out).call().await.unwrap();
+
+                // a small swap within the current range, and large ones crossing several ticks
+                for divisor in [1_000_000u64, 20] {
+                    let amount_in = balance_in / divisor;
+                    let swap = simulate(
+                        &state,
+                        fee,
+                        &ticks,
+                        I256::from_raw(amount_in),
+                        zero_for_one,
+                        None,
+                    )
+                    .unwrap();
+                    let quote = quoter
+                        .quote_exact_input_single(token_in, token_out, fee, amount_in, U256::zero())
+                        .await
+                        .unwrap();
+                    assert_eq!(swap.amount_in, amount_in, "{address} {zero_for_one}");
+                    assert_eq!(swap.amount_out, quote.amount, "{address} {zero_for_one}");
+                    assert_eq!(swap.sqrt_price_x96_after, quote.sqrt_price_x96_after[0]);
+                    if divisor == 20 {
+                        assert!(swap.initialized_ticks_crossed > 0, "{address} {zero_for_one}");
+                    }
+
+                    let amount_out = balance_out / divisor;
+                    let swap = simulate(
+                        &state,
+                        fee,
+                        &ticks,
+                        -I256::from_raw(amount_out),
+                        zero_for_one,
+                        None,
+                    )
+                    .unwrap();
+                    let quote = quoter
+                        .quote_exact_output_single(
+                            token_in,
+                            token_out,
+                            fee,
+                            amount_out,
+                            U256::zero(),
+                        )
+                        .await
+                        .unwrap();
+                    assert_eq!(swap.amount_out, amount_out, "{address} {zero_for_one}");
+                    assert_eq!(swap.amount_in, quote.amount, "{address} {zero_for_one}");
+                    assert_eq!(swap.sqrt_price_x96_after, quote.sqrt_price_x96_after[0]);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn tick_lens_matches_bitmap() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        // USDC/WETH 0.05%
+        let address = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
+        let pool = Pool::new(client.clone(), address, ProtocolType::UniswapV3);
+        let mut lens_pool = pool.clone();
+        lens_pool.set_tick_lens("0xbfd8137f7d1516D3ea5cA83523914859ec47F573".parse().unwrap());
+
+        let state = pool.state().await.unwrap();
+        let word = TickData::word_position(state.tick, pool.tick_spacing().unwrap());
+        let ticks = pool.load_ticks(word - 4..=word + 4).await.unwrap();
+        assert!(!ticks.liquidity_net.is_empty());
+        assert_eq!(lens_pool.load_ticks(word - 4..=word + 4).await.unwrap(), ticks);
+
+        // the in-range liquidity is the sum of the net liquidity of the ticks below
+        let liquidity_net = lens_pool.liquidity_map(MIN_TICK..=state.tick).await.unwrap();
+        let liquidity = liquidity_net.values().sum::<i128>();
+        assert_eq!(liquidity, state.liquidity as i128);
+
+        let depth = lens_pool.depth_chart(1000).await.unwrap();
+        assert!(!depth.amount0().is_zero() && !depth.amount1().is_zero());
+        let narrow = lens_pool.depth_chart(10).await.unwrap();
+        assert!(narrow.amount0() <= depth.amount0() && narrow.amount1() <= depth.amount1());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn twap_fork() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        // USDC/WETH 0.05%
+        let address = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
+        let pool = Pool::new(client, address, ProtocolType::UniswapV3);
+
+        let twap = pool.twap(Duration::from_secs(600)).await.unwrap();
+        let state = pool.state().await.unwrap();
+        // USDC per WETH
+        let price = 1.0 / twap.price;
+        assert!((100.0..100_000.0).contains(&price), "{price}");
+        assert!((twap.tick - state.tick).abs() < 1000);
+
+        // more than the ~ 3 years of observations stored by the pool
+        let res = pool.twap(Duration::from_secs(100_000_000)).await;
+        assert!(matches!(res, Err(Error::OracleWindowTooLong { .. })), "{res:?}");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn amounts_for_position_match_mints() {
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        // USDC/WETH 0.05%
+        let address = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
+        let pool = Pool::new(client.clone(), address, ProtocolType::UniswapV3);
+        let manager: Address = "0xC36442b4a4522E871399CD717aBDD847Ab11FE88".parse().unwrap();
+
+        let to_block = client.get_block_number().await.unwrap();
+        let from_block = to_block - 5_000;
+        let mints = pool.contract().mint_filter().topic1(manager);
+        let mints =
+            mints.from_block(from_block).to_block(to_block).query_with_meta().await.unwrap();
+        let swaps = pool.contract().swap_filter();
+        let swaps =
+            swaps.from_block(from_block).to_block(to_block).query_with_meta().await.unwrap();
+
+        for (mint, meta) in mints {
+            // the price at the mint is the one at the end of the previous block, unless the pool
+            // was swapped before it in the same block
+            let swapped_before = swaps.iter().any(|(_, swap)| {
+                swap.block_number == meta.block_number && swap.log_index < meta.log_index
+            });
+            if swapped_before {
+                continue;
+            }
+            let block = meta.block_number - 1;
+            let (sqrt_price_x96, ..) = pool.slot_0().block(block).call().await.unwrap();
+            let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(mint.tick_lower).unwrap();
+            let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(mint.tick_upper).unwrap();
+            let (amount0, amount1) = amounts_for_liquidity(
+                sqrt_price_x96,
+                sqrt_ratio_a_x96,
+                sqrt_ratio_b_x96,
+                mint.amount,
+            )
+            .unwrap();
+            // the deposited amounts are rounded up
+            assert!(mint.amount_0 - amount0 <= U256::one(), "{:?}", meta.transaction_hash);
+            assert!(mint.amount_1 - amount1 <= U256::one(), "{:?}", meta.transaction_hash);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn pending_fees_match_collect() {
+        use crate::v3::PositionManager;
+
+        let fork = Fork::from_env().await.unwrap();
+        let client = Arc::new(fork.provider().clone());
+        let manager = PositionManager::new(
+            client.clone(),
+            "0xC36442b4a4522E871399CD717aBDD847Ab11FE88".parse().unwrap(),
+            "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap(),
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap(),
+        );
+
+        let to_block = client.get_block_number().await.unwrap();
+        let increases = manager.contract().increase_liquidity_filter();
+        let increases = increases.from_block(to_block - 1_000).to_block(to_block);
+        let increases = increases.query().await.unwrap();
+        let mut checked = 0;
+        for increase in increases.iter().rev() {
+            // burned since
+            let position = match manager.positions(increase.token_id).await {
+                Ok(position) if position.liquidity > 0 => position,
+                _ => continue,
+            };
+            let owner = manager.contract().owner_of(increase.token_id).call().await.unwrap();
+            let pool = manager.factory().pool_for(position.token0, position.token1, position.fee);
+            let fees = pool.unwrap().pending_fees(&position).await.unwrap();
+            let collect = manager.collect_all(increase.token_id, owner).from(owner);
+            assert_eq!(fees, collect.call().await.unwrap(), "{}", increase.token_id);
+
+            checked += 1;
+            if checked == 5 {
+                break;
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        use crate::v3::math::Q96;
+
+        let swap = SwapFilter {
+            sender: Address::repeat_byte(1),
+            recipient: Address::repeat_byte(2),
+            amount_0: I256::from(-1_000),
+            amount_1: I256::from(2_000),
+            sqrt_price_x96: Q96,
+            liquidity: 1_000,
+            tick: -1,
+        };
+        let json = serde_json::to_value(&swap).unwrap();
+        assert_eq!(json["sender"], "0x0101010101010101010101010101010101010101");
+        assert_eq!(json["sqrt_price_x96"], "0x1000000000000000000000000");
+        assert_eq!(serde_json::from_value::<SwapFilter>(json).unwrap(), swap);
+
+        let event = SwapEvent::new(swap, 18, 18);
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<SwapEvent>(&json).unwrap(), event);
+
+        let state = PoolState { sqrt_price_x96: Q96, liquidity: 1_000, ..Default::default() };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<PoolState>(&json).unwrap(), state);
+    }
 }
@@ -0,0 +1,854 @@
+use super::{
+    math::{
+        get_sqrt_ratio_at_tick,
+        liquidity::{amounts_for_liquidity, liquidity_for_amounts},
+        nearest_usable_tick,
+    },
+    router::calldata,
+    Factory, FeeAmount,
+};
+use crate::{
+    common::call_multi_chunked,
+    contracts::bindings::i_nonfungible_position_manager::{self as bindings, *},
+    errors::{Error, Result},
+    utils::{is_native, map_native, sort_tokens},
+    Deadline, ProtocolType, Recipient, Slippage,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::types::{Address, Bytes, U256};
+use ethers_providers::Middleware;
+use std::sync::Arc;
+
+type PositionTuple = (u128, Address, Address, Address, u32, i32, i32, u128, U256, U256, u128, u128);
+
+/// A liquidity position, as returned by the position manager's `positions` method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Position {
+    /// The nonce for permits.
+    pub nonce: u128,
+    /// The address approved to spend the position.
+    pub operator: Address,
+    /// The first token of the pool.
+    pub token0: Address,
+    /// The second token of the pool.
+    pub token1: Address,
+    /// The fee amount of the pool.
+    pub fee: FeeAmount,
+    /// The lower tick of the position's range.
+    pub tick_lower: i32,
+    /// The upper tick of the position's range.
+    pub tick_upper: i32,
+    /// The liquidity of the position.
+    pub liquidity: u128,
+    /// The fee growth of `token0` inside the range as of the last action on the position.
+    pub fee_growth_inside0_last_x128: U256,
+    /// The fee growth of `token1` inside the range as of the last action on the position.
+    pub fee_growth_inside1_last_x128: U256,
+    /// The amount of `token0` owed to the position as of the last action on it.
+    pub tokens_owed0: u128,
+    /// The amount of `token1` owed to the position as of the last action on it.
+    pub tokens_owed1: u128,
+}
+
+impl TryFrom<PositionTuple> for Position {
+    type Error = Error;
+
+    fn try_from(
+        (
+            nonce,
+            operator,
+            token0,
+            token1,
+            fee,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            fee_growth_inside0_last_x128,
+            fee_growth_inside1_last_x128,
+            tokens_owed0,
+            tokens_owed1,
+        ): PositionTuple,
+    ) -> Result<Self> {
+        Ok(Self {
+            nonce,
+            operator,
+            token0,
+            token1,
+            fee: fee.try_into()?,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            fee_growth_inside0_last_x128,
+            fee_growth_inside1_last_x128,
+            tokens_owed0,
+            tokens_owed1,
+        })
+    }
+}
+
+/// The parameters of a new position, used by [`PositionManager::mint`].
+///
+/// The tokens can be in any order, but the ticks are always those of the pool, which prices
+/// `token0` in `token1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MintParams {
+    /// The first token, or [`NATIVE_ADDRESS`][crate::NATIVE_ADDRESS] to deposit ETH.
+    pub token_a: Address,
+
+    /// The second token, or [`NATIVE_ADDRESS`][crate::NATIVE_ADDRESS] to deposit ETH.
+    pub token_b: Address,
+
+    /// The fee amount of the pool.
+    pub fee: FeeAmount,
+
+    /// The lower tick of the range, rounded to the nearest usable tick.
+    pub tick_lower: i32,
+
+    /// The upper tick of the range, rounded to the nearest usable tick.
+    pub tick_upper: i32,
+
+    /// The maximum amount of `token_a` to deposit.
+    pub amount_a_desired: U256,
+
+    /// The maximum amount of `token_b` to deposit.
+    pub amount_b_desired: U256,
+
+    /// The maximum price change tolerated until the mint, applied to the deposited amounts.
+    /// Defaults to zero.
+    pub slippage_tolerance: Slippage,
+
+    /// The recipient of the position. Defaults to the client's default sender.
+    pub recipient: Option<Address>,
+
+    /// The deadline of the mint. Defaults to none.
+    pub deadline: Deadline,
+}
+
+impl MintParams {
+    /// Creates a new instance with the default options.
+    pub fn new(
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+    ) -> Self {
+        Self {
+            token_a,
+            token_b,
+            fee,
+            tick_lower,
+            tick_upper,
+            amount_a_desired,
+            amount_b_desired,
+            slippage_tolerance: Slippage::ZERO,
+            recipient: None,
+            deadline: Deadline::default(),
+        }
+    }
+
+    /// Sets the maximum price change tolerated until the mint.
+    #[inline]
+    pub fn slippage_tolerance(mut self, slippage_tolerance: impl Into<Slippage>) -> Self {
+        self.slippage_tolerance = slippage_tolerance.into();
+        self
+    }
+
+    /// Sets the recipient of the position.
+    #[inline]
+    pub const fn recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Sets the deadline of the mint.
+    #[inline]
+    pub fn deadline(mut self, deadline: impl Into<Deadline>) -> Self {
+        self.deadline = deadline.into();
+        self
+    }
+}
+
+contract_struct! {
+    /// A Uniswap V3 `NonfungiblePositionManager`, which wraps the liquidity positions of the pools
+    /// in ERC721 tokens.
+    pub struct PositionManager<M> {
+        /// The position manager contract.
+        contract: INonfungiblePositionManager<M>,
+
+        /// The factory of the pools.
+        factory: Factory<M>,
+
+        /// The wrapped native token, used for the positions in ETH.
+        weth: Address,
+    }
+}
+
+impl<M: Middleware> PositionManager<M> {
+    /// Creates a new instance using the provided position manager, factory and WETH addresses.
+    pub fn new(client: Arc<M>, address: Address, factory: Address, weth: Address) -> Self {
+        let factory = Factory::new(client.clone(), factory, ProtocolType::UniswapV3);
+        let contract = INonfungiblePositionManager::new(address, client);
+        Self { contract, factory, weth }
+    }
+
+    /// Creates a new instance by searching for the canonical Uniswap V3 addresses in the
+    /// [addressbook].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn new_with_chain(client: Arc<M>, chain: ethers_core::types::Chain) -> Option<Self> {
+        use crate::contracts::addresses::try_address;
+
+        let address = try_address("UniswapV3NonfungiblePositionManager", chain)?;
+        let weth = try_address("WETH", chain)?;
+        let factory = Factory::new_with_chain(client.clone(), chain, ProtocolType::UniswapV3)?;
+        let contract = INonfungiblePositionManager::new(address, client);
+        Some(Self { contract, factory, weth })
+    }
+
+    /// Returns a reference to the factory of the pools.
+    #[inline(always)]
+    pub fn factory(&self) -> &Factory<M> {
+        &self.factory
+    }
+
+    /// Sets the factory of the pools, like one with a [pool code hash][Factory::set_pool_code_hash]
+    /// override.
+    #[inline(always)]
+    pub fn set_factory(&mut self, factory: Factory<M>) {
+        self.factory = factory;
+    }
+
+    /// Returns the address of the wrapped native token.
+    #[inline(always)]
+    pub fn weth(&self) -> Address {
+        self.weth
+    }
+
+    /// Returns the position of `token_id`.
+    pub async fn positions(&self, token_id: U256) -> Result<Position> {
+        self.contract.positions(token_id).call().await?.try_into()
+    }
+
+    /// Returns the positions owned by `owner`, with their token IDs. If `pool` is set, only the
+    /// positions in the pool of its tokens, in any order, and fee amount are returned.
+    ///
+    /// The token IDs, enumerated with `tokenOfOwnerByIndex`, and then their positions are fetched
+    /// in [Multicall][crate::utils::Multicall] requests of at most `chunk_size` calls each, with
+    /// at most `concurrency` requests in flight and throttled requests retried according to the
+    /// factory's [request policy][super::Factory::request_policy]. Falls back to sequential calls
+    /// if Multicall3 is not deployed on the factory's chain.
+    pub async fn positions_of(
+        &self,
+        owner: Address,
+        pool: Option<(Address, Address, FeeAmount)>,
+        chunk_size: usize,
+        concurrency: usize,
+    ) -> Result<Vec<(U256, Position)>> {
+        let (client, chain) = (self.client(), self.factory.chain);
+        let policy = self.factory.request_policy().max_concurrency(concurrency);
+        let balance = self.contract.balance_of(owner).call().await?;
+        let calls = (0..balance.low_u64())
+            .map(|index| self.contract.token_of_owner_by_index(owner, index.into()))
+            .collect();
+        let token_ids =
+            call_multi_chunked(client.clone(), chain, calls, chunk_size, policy).await?;
+
+        let calls = token_ids.iter().map(|&token_id| self.contract.positions(token_id)).collect();
+        let positions = call_multi_chunked(client, chain, calls, chunk_size, policy).await?;
+
+        let pool = pool.map(|(token_a, token_b, fee)| {
+            let (token0, token1) = sort_tokens(token_a, token_b);
+            (token0, token1, fee)
+        });
+        let mut result = Vec::with_capacity(token_ids.len());
+        for (token_id, position) in token_ids.into_iter().zip(positions) {
+            let position = Position::try_from(position)?;
+            let key = (position.token0, position.token1, position.fee);
+            if pool.map_or(true, |pool| pool == key) {
+                result.push((token_id, position));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the contract call for the manager's `increaseLiquidity` method.
+    pub fn increase_liquidity(
+        &self,
+        params: IncreaseLiquidityParams,
+    ) -> ContractCall<M, (u128, U256, U256)> {
+        self.contract.increase_liquidity(params)
+    }
+
+    /// Returns the contract call for the manager's `decreaseLiquidity` method.
+    pub fn decrease_liquidity(
+        &self,
+        params: DecreaseLiquidityParams,
+    ) -> ContractCall<M, (U256, U256)> {
+        self.contract.decrease_liquidity(params)
+    }
+
+    /// Returns the contract call for the manager's `collect` method.
+    pub fn collect(&self, params: CollectParams) -> ContractCall<M, (U256, U256)> {
+        self.contract.collect(params)
+    }
+
+    /// Returns the contract call for the manager's `collect` method, which sends all the tokens
+    /// owed to the position of `token_id`, including its uncollected fees, to `recipient`.
+    pub fn collect_all(&self, token_id: U256, recipient: Address) -> ContractCall<M, (U256, U256)> {
+        self.collect(CollectParams {
+            token_id,
+            recipient,
+            amount_0_max: u128::MAX,
+            amount_1_max: u128::MAX,
+        })
+    }
+
+    /// Returns the contract call for the manager's `burn` method, which burns a position with no
+    /// liquidity and no tokens owed.
+    pub fn burn(&self, token_id: U256) -> ContractCall<M, ()> {
+        self.contract.burn(token_id)
+    }
+
+    /// Returns the contract call for the manager's `multicall` method, which executes each of the
+    /// manager's calls in `data` in the same transaction and returns their results.
+    pub fn multicall(&self, data: Vec<Bytes>) -> ContractCall<M, Vec<Bytes>> {
+        self.contract.multicall(data)
+    }
+
+    /// Returns the contract call for the manager's `refundETH` method, which sends the manager's
+    /// ETH balance to the caller.
+    pub fn refund_eth(&self) -> ContractCall<M, ()> {
+        self.contract.refund_eth()
+    }
+
+    /// Returns the contract call for the manager's `unwrapWETH9` method, which unwraps the
+    /// manager's WETH balance, of at least `amount_minimum`, and sends it to `recipient` as ETH.
+    pub fn unwrap_weth9(&self, amount_minimum: U256, recipient: Address) -> ContractCall<M, ()> {
+        self.contract.unwrap_weth9(amount_minimum, recipient)
+    }
+
+    /// Returns the contract call for the manager's `sweepToken` method, which sends the manager's
+    /// `token` balance, of at least `amount_minimum`, to `recipient`.
+    pub fn sweep_token(
+        &self,
+        token: Address,
+        amount_minimum: U256,
+        recipient: Address,
+    ) -> ContractCall<M, ()> {
+        self.contract.sweep_token(token, amount_minimum, recipient)
+    }
+
+    /// Mints a new position in the pool of `params`. Returns the contract call with the necessary
+    /// parameters set (value, calldata).
+    ///
+    /// The pool's tick spacing and price are fetched with [`Pool::state`][super::Pool::state]. The
+    /// ticks are rounded to the nearest usable ticks, and the amounts deposited at the current
+    /// price are computed from the desired ones, to which `slippage_tolerance` is applied to set
+    /// `amount0Min` and `amount1Min`.
+    ///
+    /// The mint is always wrapped in the manager's [`multicall`][Self::multicall], whose first
+    /// result is the ABI-encoded `(tokenId, liquidity, amount0, amount1)`.
+    /// [`NATIVE_ADDRESS`][crate::NATIVE_ADDRESS] as one of the tokens deposits ETH through
+    /// [`weth`][Self::weth], setting the call's `value` to its desired amount and appending a
+    /// [`refundETH`][Self::refund_eth] call for the unspent amount.
+    ///
+    /// Returns [`Error::InvalidTick`] if the range is empty once rounded,
+    /// [`Error::PoolNotDeployed`] if the pool has not been deployed, and
+    /// [`Error::MissingSender`] if no recipient is set and the client has no default sender.
+    pub async fn mint(&self, params: MintParams) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let slippage = params.slippage_tolerance.validate()?;
+        let client = self.client();
+        let recipient = match params.recipient {
+            Some(recipient) => recipient,
+            None => client.default_sender().ok_or(Error::MissingSender)?,
+        };
+        let deadline = params.deadline.resolve(client.as_ref()).await?;
+
+        let from_native = is_native(&params.token_a) || is_native(&params.token_b);
+        let mut tokens = [params.token_a, params.token_b];
+        map_native(&mut tokens, self.weth);
+        let [token_a, token_b] = tokens;
+        let pool = self.factory.pool_for(token_a, token_b, params.fee)?;
+        let (token0, token1) = pool.tokens().expect("set by pool_for");
+        let (amount0_desired, amount1_desired) = if token0 == token_a {
+            (params.amount_a_desired, params.amount_b_desired)
+        } else {
+            (params.amount_b_desired, params.amount_a_desired)
+        };
+
+        let state = pool.state().await?;
+        let tick_spacing = pool.tick_spacing().expect("fetched by state");
+        let tick_lower = nearest_usable_tick(params.tick_lower, tick_spacing)?;
+        let tick_upper = nearest_usable_tick(params.tick_upper, tick_spacing)?;
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidTick(tick_upper));
+        }
+
+        let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(tick_lower)?;
+        let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(tick_upper)?;
+        let liquidity = liquidity_for_amounts(
+            state.sqrt_price_x96,
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            amount0_desired,
+            amount1_desired,
+        )?;
+        let (amount0, amount1) = amounts_for_liquidity(
+            state.sqrt_price_x96,
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            liquidity,
+        )?;
+
+        let mint = self.contract.mint(bindings::MintParams {
+            token_0: token0,
+            token_1: token1,
+            fee: params.fee.into(),
+            tick_lower,
+            tick_upper,
+            amount_0_desired: amount0_desired,
+            amount_1_desired: amount1_desired,
+            amount_0_min: slippage.minimum_amount_out(amount0),
+            amount_1_min: slippage.minimum_amount_out(amount1),
+            recipient,
+            deadline,
+        });
+
+        let mut data = vec![calldata(mint)];
+        if from_native {
+            data.push(calldata(self.refund_eth()));
+        }
+        let mut call = self.multicall(data);
+        if from_native {
+            let value = if token0 == self.weth { amount0_desired } else { amount1_desired };
+            call = call.value(value);
+        }
+        Ok(call)
+    }
+
+    /// Closes the position of `token_id`, removing all its liquidity, collecting all the tokens
+    /// owed to it and burning it. Returns the contract call with the necessary parameters set
+    /// (calldata).
+    ///
+    /// The amounts withdrawn at the pool's current price, fetched from its `slot0`, are computed
+    /// with [`Pool::amounts_for_position`][super::Pool::amounts_for_position], and
+    /// `slippage_tolerance` is applied to them to set `amount0Min` and `amount1Min`.
+    ///
+    /// The calls are wrapped in the manager's [`multicall`][Self::multicall]: the
+    /// [`decreaseLiquidity`][Self::decrease_liquidity], if the position has liquidity, then the
+    /// [`collect`][Self::collect_all] of all the tokens owed and the [`burn`][Self::burn]. If
+    /// `unwrap_weth` is true and one of the tokens is [`weth`][Self::weth], the manager collects
+    /// the tokens, an [`unwrapWETH9`][Self::unwrap_weth9] call sends the WETH to `recipient` as
+    /// ETH, and a [`sweepToken`][Self::sweep_token] call sends it the other token.
+    pub async fn close_position(
+        &self,
+        token_id: U256,
+        slippage_tolerance: impl Into<Slippage>,
+        recipient: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        unwrap_weth: bool,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let slippage = slippage_tolerance.into().validate()?;
+        let deadline = deadline.into().resolve(self.client().as_ref()).await?;
+        let recipient = recipient.into().resolve(self.client().as_ref()).await?;
+        let position = self.positions(token_id).await?;
+
+        let mut data = Vec::with_capacity(5);
+        let (mut amount0_min, mut amount1_min) = (U256::zero(), U256::zero());
+        if position.liquidity > 0 {
+            let pool = self.factory.pool_for(position.token0, position.token1, position.fee)?;
+            let (amount0, amount1) = pool
+                .amounts_for_position(position.tick_lower, position.tick_upper, position.liquidity)
+                .await?;
+            amount0_min = slippage.minimum_amount_out(amount0);
+            amount1_min = slippage.minimum_amount_out(amount1);
+            data.push(calldata(self.decrease_liquidity(DecreaseLiquidityParams {
+                token_id,
+                liquidity: position.liquidity,
+                amount_0_min: amount0_min,
+                amount_1_min: amount1_min,
+                deadline,
+            })));
+        }
+
+        let to_native =
+            unwrap_weth && (position.token0 == self.weth || position.token1 == self.weth);
+        if to_native {
+            // the zero address is the manager itself
+            data.push(calldata(self.collect_all(token_id, Address::zero())));
+            let (token, amount_minimum, weth_minimum) = if position.token0 == self.weth {
+                (position.token1, amount1_min, amount0_min)
+            } else {
+                (position.token0, amount0_min, amount1_min)
+            };
+            data.push(calldata(self.unwrap_weth9(weth_minimum, recipient)));
+            data.push(calldata(self.sweep_token(token, amount_minimum, recipient)));
+        } else {
+            data.push(calldata(self.collect_all(token_id, recipient)));
+        }
+        data.push(calldata(self.burn(token_id)));
+        Ok(self.multicall(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NATIVE_ADDRESS;
+    use ethers_core::{
+        abi::{self, AbiDecode, Token},
+        types::I256,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    type M = Provider<MockProvider>;
+
+    const TOKEN: Address = Address::repeat_byte(0x0a);
+    const WETH: Address = Address::repeat_byte(0xaa);
+    const RECIPIENT: Address = Address::repeat_byte(0xbb);
+
+    fn setup() -> (PositionManager<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let manager = PositionManager::new(
+            client,
+            Address::repeat_byte(0x44),
+            Address::repeat_byte(0x11),
+            WETH,
+        );
+        (manager, mock)
+    }
+
+    /// Pushes the response of the multicall of `Pool::state`, with the immutables.
+    fn push_pool(mock: &MockProvider, tick_spacing: i32, sqrt_price_x96: U256) {
+        let results = [
+            Token::Address(TOKEN),
+            Token::Address(WETH),
+            Token::Uint(3000.into()),
+            Token::Int(I256::from(tick_spacing).into_raw()),
+        ]
+        .into_iter()
+        .map(|token| abi::encode(&[token]))
+        .chain([
+            abi::encode(&[
+                Token::Uint(sqrt_price_x96),
+                Token::Int(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(1.into()),
+                Token::Uint(1.into()),
+                Token::Uint(0.into()),
+                Token::Bool(true),
+            ]),
+            abi::encode(&[Token::Uint(1000.into())]),
+            abi::encode(&[Token::Uint(100.into())]),
+        ])
+        .map(|data| Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)]))
+        .collect();
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+        // chain id, queried by the multicall
+        mock.push(U256::one()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_positions() {
+        let (manager, mock) = setup();
+        let tokens = [
+            Token::Uint(1.into()),
+            Token::Address(Address::zero()),
+            Token::Address(TOKEN),
+            Token::Address(WETH),
+            Token::Uint(500.into()),
+            Token::Int(I256::from(-60).into_raw()),
+            Token::Int(60.into()),
+            Token::Uint(1000.into()),
+            Token::Uint(U256::MAX),
+            Token::Uint(2.into()),
+            Token::Uint(3.into()),
+            Token::Uint(4.into()),
+        ];
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&tokens))).unwrap();
+
+        let position = manager.positions(1.into()).await.unwrap();
+        let expected = Position {
+            nonce: 1,
+            operator: Address::zero(),
+            token0: TOKEN,
+            token1: WETH,
+            fee: FeeAmount::Low,
+            tick_lower: -60,
+            tick_upper: 60,
+            liquidity: 1000,
+            fee_growth_inside0_last_x128: U256::MAX,
+            fee_growth_inside1_last_x128: 2.into(),
+            tokens_owed0: 3,
+            tokens_owed1: 4,
+        };
+        assert_eq!(position, expected);
+    }
+
+    #[tokio::test]
+    async fn test_positions_of() {
+        let (manager, mock) = setup();
+        let position = |token_a: Address, token_b: Address, fee: u32| {
+            Token::Tuple(vec![
+                Token::Uint(0.into()),
+                Token::Address(Address::zero()),
+                Token::Address(token_a),
+                Token::Address(token_b),
+                Token::Uint(fee.into()),
+                Token::Int(I256::from(-60).into_raw()),
+                Token::Int(60.into()),
+                Token::Uint(1000.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+            ])
+        };
+        let push_multicall = |results: Vec<Token>| {
+            let results = results
+                .into_iter()
+                .map(|token| {
+                    let data = abi::encode(&[token]);
+                    Token::Tuple(vec![Token::Bool(true), Token::Bytes(data)])
+                })
+                .collect();
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Array(results)]))).unwrap();
+            // chain id, queried by the multicall
+            mock.push(U256::one()).unwrap();
+        };
+        let other = Address::repeat_byte(0x0b);
+
+        // 3 positions in chunks of 2: a multicall, then a single call, for the IDs and positions
+        let push_positions = || {
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[position(TOKEN, WETH, 500)]))).unwrap();
+            push_multicall(vec![position(TOKEN, WETH, 3000), position(other, WETH, 3000)]);
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(9.into())]))).unwrap();
+            push_multicall(vec![Token::Uint(3.into()), Token::Uint(5.into())]);
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(3.into())]))).unwrap();
+        };
+
+        push_positions();
+        let positions = manager.positions_of(RECIPIENT, None, 2, 1).await.unwrap();
+        let token_ids: Vec<_> = positions.iter().map(|(token_id, _)| token_id.as_u64()).collect();
+        assert_eq!(token_ids, [3, 5, 9]);
+        assert_eq!(positions[1].1.token0, other);
+        assert_eq!(positions[2].1.fee, FeeAmount::Low);
+
+        // only the WETH/TOKEN 0.3% positions, with the tokens in any order
+        push_positions();
+        let pool = Some((WETH, TOKEN, FeeAmount::Medium));
+        let positions = manager.positions_of(RECIPIENT, pool, 2, 1).await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].0, 3.into());
+
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(0.into())]))).unwrap();
+        assert!(manager.positions_of(RECIPIENT, None, 2, 1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mint() {
+        let (manager, mock) = setup();
+        let amount = U256::exp10(18);
+        push_pool(&mock, 60, U256::one() << 96);
+
+        // 1:1 price, symmetric range after rounding
+        let params = MintParams::new(WETH, TOKEN, FeeAmount::Medium, -1000, 1010, amount, amount)
+            .slippage_tolerance(1.0)
+            .recipient(RECIPIENT);
+        let call = manager.mint(params).await.unwrap();
+        assert_eq!(call.tx.value(), None);
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 1);
+        let params = MintCall::decode(&data[0]).unwrap().params;
+        assert_eq!((params.token_0, params.token_1), (TOKEN, WETH));
+        assert_eq!(params.fee, 3000);
+        assert_eq!((params.tick_lower, params.tick_upper), (-1020, 1020));
+        assert_eq!((params.amount_0_desired, params.amount_1_desired), (amount, amount));
+        // the amounts are equal at the middle of the range, minus the rounding and 1% slippage
+        assert_eq!(params.amount_0_min, params.amount_1_min);
+        assert!(params.amount_0_min < amount * 99 / 100);
+        assert!(params.amount_0_min > amount * 99 / 100 - 10);
+        assert_eq!(params.recipient, RECIPIENT);
+        assert_eq!(params.deadline, U256::MAX);
+
+        // empty range once rounded
+        push_pool(&mock, 60, U256::one() << 96);
+        let params = MintParams::new(WETH, TOKEN, FeeAmount::Medium, 10, 20, amount, amount)
+            .recipient(RECIPIENT);
+        assert!(matches!(manager.mint(params).await.unwrap_err(), Error::InvalidTick(0)));
+
+        let params = MintParams::new(WETH, TOKEN, FeeAmount::Medium, -60, 60, amount, amount);
+        assert!(matches!(manager.mint(params).await.unwrap_err(), Error::MissingSender));
+    }
+
+    #[tokio::test]
+    async fn test_mint_native() {
+        let (manager, mock) = setup();
+        let (amount_token, amount_eth) = (U256::exp10(18), U256::exp10(17));
+        push_pool(&mock, 60, U256::one() << 96);
+
+        // below the range, only token0 is deposited but all the ETH is sent and refunded
+        let params = MintParams::new(
+            NATIVE_ADDRESS,
+            TOKEN,
+            FeeAmount::Medium,
+            60,
+            120,
+            amount_eth,
+            amount_token,
+        )
+        .recipient(RECIPIENT);
+        let call = manager.mint(params).await.unwrap();
+        assert_eq!(call.tx.value(), Some(&amount_eth));
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        let params = MintCall::decode(&data[0]).unwrap().params;
+        assert_eq!((params.token_0, params.token_1), (TOKEN, WETH));
+        assert_eq!((params.amount_0_desired, params.amount_1_desired), (amount_token, amount_eth));
+        assert!(params.amount_0_min <= amount_token && params.amount_0_min > amount_token - 10);
+        assert_eq!(params.amount_1_min, U256::zero());
+        RefundETHCall::decode(&data[1]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_close_position() {
+        let (manager, mock) = setup();
+        let token_id = U256::from(7);
+        let push_position = |liquidity: u128| {
+            let tokens = [
+                Token::Uint(0.into()),
+                Token::Address(Address::zero()),
+                Token::Address(TOKEN),
+                Token::Address(WETH),
+                Token::Uint(3000.into()),
+                Token::Int(I256::from(-60).into_raw()),
+                Token::Int(60.into()),
+                Token::Uint(liquidity.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(0.into()),
+            ];
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&tokens))).unwrap();
+        };
+        let push_slot_0 = |sqrt_price_x96: U256| {
+            let tokens = [
+                Token::Uint(sqrt_price_x96),
+                Token::Int(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(1.into()),
+                Token::Uint(1.into()),
+                Token::Uint(0.into()),
+                Token::Bool(true),
+            ];
+            mock.push::<Bytes, _>(Bytes::from(abi::encode(&tokens))).unwrap();
+        };
+
+        // unwrapped: collected by the manager, then unwrapped and swept
+        let liquidity = 10u128.pow(18);
+        push_slot_0(U256::one() << 96);
+        push_position(liquidity);
+        let call = manager.close_position(token_id, 1.0, RECIPIENT, U256::MAX, true).await.unwrap();
+        assert_eq!(call.tx.value(), None);
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 5);
+        let params = DecreaseLiquidityCall::decode(&data[0]).unwrap().params;
+        assert_eq!((params.token_id, params.liquidity), (token_id, liquidity));
+        assert_eq!(params.amount_0_min, params.amount_1_min);
+        assert!(params.amount_0_min > U256::zero());
+        let collect = CollectCall::decode(&data[1]).unwrap().params;
+        assert_eq!(collect.recipient, Address::zero());
+        assert_eq!((collect.amount_0_max, collect.amount_1_max), (u128::MAX, u128::MAX));
+        let unwrap = UnwrapWETH9Call::decode(&data[2]).unwrap();
+        assert_eq!((unwrap.amount_minimum, unwrap.recipient), (params.amount_1_min, RECIPIENT));
+        let sweep = SweepTokenCall::decode(&data[3]).unwrap();
+        assert_eq!((sweep.token, sweep.amount_minimum), (TOKEN, params.amount_0_min));
+        assert_eq!(sweep.recipient, RECIPIENT);
+        assert_eq!(BurnCall::decode(&data[4]).unwrap().token_id, token_id);
+
+        // no liquidity left, only the fees are collected to the recipient
+        push_position(0);
+        let call =
+            manager.close_position(token_id, 1.0, RECIPIENT, U256::MAX, false).await.unwrap();
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        assert_eq!(CollectCall::decode(&data[0]).unwrap().params.recipient, RECIPIENT);
+        BurnCall::decode(&data[1]).unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_close_position_fork() {
+        use crate::testing::Fork;
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+
+        let manager = PositionManager::new(
+            client.clone(),
+            "0xC36442b4a4522E871399CD717aBDD847Ab11FE88".parse().unwrap(),
+            "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap(),
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap(),
+        );
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let pool = manager.factory().pool_for(usdc, manager.weth(), FeeAmount::Low).unwrap();
+        let tick = pool.state().await.unwrap().tick;
+
+        // USDC/WETH 0.05%, below the price: only WETH is deposited
+        let amount = U256::exp10(18);
+        let (tick_lower, tick_upper) = (tick - 2000, tick - 1000);
+        let params = MintParams::new(
+            NATIVE_ADDRESS,
+            usdc,
+            FeeAmount::Low,
+            tick_lower,
+            tick_upper,
+            amount,
+            U256::zero(),
+        )
+        .recipient(me);
+        let call = manager.mint(params).await.unwrap();
+        let token_id = MintReturn::decode(&call.call().await.unwrap()[0]).unwrap().token_id;
+        call.send().await.unwrap().await.unwrap();
+        assert_eq!(manager.contract().owner_of(token_id).call().await.unwrap(), me);
+
+        let balance = client.get_balance(me, None).await.unwrap();
+        let call = manager.close_position(token_id, 0.5, me, U256::MAX, true).await.unwrap();
+        let receipt = call.send().await.unwrap().await.unwrap().unwrap();
+        let gas = receipt.gas_used.unwrap() * receipt.effective_gas_price.unwrap();
+        let received = client.get_balance(me, None).await.unwrap() + gas - balance;
+        // both the mint and the burn round in favor of the pool
+        assert!(received < amount && received + 10_000 > amount, "{received}");
+        assert!(manager.contract().owner_of(token_id).call().await.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let position = Position {
+            token0: TOKEN,
+            token1: WETH,
+            fee: FeeAmount::Medium,
+            tick_lower: -60,
+            tick_upper: 60,
+            liquidity: 1_000,
+            fee_growth_inside0_last_x128: U256::MAX,
+            fee_growth_inside1_last_x128: U256::zero(),
+            tokens_owed0: 1,
+            tokens_owed1: 2,
+            nonce: 0,
+            operator: Address::zero(),
+        };
+        let json = serde_json::to_value(position).unwrap();
+        assert_eq!(json["token0"], "0x0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a");
+        assert_eq!(json["fee"], 3_000);
+        assert_eq!(serde_json::from_value::<Position>(json).unwrap(), position);
+    }
+}
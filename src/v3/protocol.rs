@@ -0,0 +1,545 @@
+use super::{
+    math::swap::check_sqrt_price_limit, Factory, FeeAmount, FeeSelection, Path, Pool,
+    PoolLiquidity, Quote, Quoter, Router,
+};
+use crate::{
+    common::{execute_swap, gas, gas::AccessListEstimate},
+    errors::Result,
+    protocol::verify::VerificationReport,
+    utils::{is_native, map_native, require_signer, resolve_weth},
+    Amount, Deadline, Erc20, ProtocolType, Recipient, RequestPolicy, Slippage, SwapOptions,
+    SwapReceipt, NATIVE_ADDRESS,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::{
+    abi::Detokenize,
+    types::{Address, Bytes, Chain, U256},
+};
+use ethers_providers::Middleware;
+use std::{fmt, sync::Arc};
+
+#[cfg(feature = "addresses")]
+use crate::contracts::addresses::AddressBook;
+
+/// A Uniswap V3 protocol implementation.
+pub struct Protocol<M> {
+    /// The pool factory.
+    factory: Factory<M>,
+
+    /// The swap router.
+    router: Router<M>,
+
+    /// The quoter.
+    quoter: Quoter<M>,
+}
+
+impl<M> Clone for Protocol<M> {
+    fn clone(&self) -> Self {
+        Self {
+            factory: self.factory.clone(),
+            router: self.router.clone(),
+            quoter: self.quoter.clone(),
+        }
+    }
+}
+
+impl<M> fmt::Debug for Protocol<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Protocol")
+            .field("factory", &self.factory)
+            .field("router", &self.router)
+            .field("quoter", &self.quoter)
+            .finish()
+    }
+}
+
+impl<M: Middleware> Protocol<M> {
+    /// Creates a new instance using the provided client, factory, `SwapRouter` and `QuoterV1`
+    /// addresses. See [`set_quoter`][Self::set_quoter] to use a `QuoterV2`.
+    pub fn new(client: Arc<M>, factory: Address, router: Address, quoter: Address) -> Self {
+        let factory = Factory::new(client.clone(), factory, ProtocolType::UniswapV3);
+        let router = Router::new(client.clone(), router);
+        let quoter = Quoter::new(client, quoter);
+        Self { factory, router, quoter }
+    }
+
+    /// Creates a new instance by searching for the Uniswap V3 addresses in `address_book`, or in
+    /// the process-global [addressbook] if None.
+    ///
+    /// An inserted entry must contain the `"quoter"` extra address, and may contain
+    /// `"quoter_v2"`. Otherwise, the canonical addresses are used.
+    ///
+    /// The `QuoterV2` is used if it is deployed on `chain`, the `QuoterV1` otherwise.
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn new_with_chain(
+        client: Arc<M>,
+        chain: Chain,
+        address_book: Option<&AddressBook>,
+    ) -> Option<Self> {
+        use crate::contracts::addresses::{try_address, with_registry};
+
+        let entry = match address_book {
+            Some(book) => book.get(ProtocolType::UniswapV3, chain).cloned(),
+            None => with_registry(|book| book.get(ProtocolType::UniswapV3, chain).cloned()),
+        };
+        let (factory, router, quoter, quoter_v2) = match entry {
+            Some(entry) => {
+                (entry.factory, entry.router, entry.extra("quoter")?, entry.extra("quoter_v2"))
+            }
+            None => (
+                try_address("UniswapV3Factory", chain)?,
+                try_address("UniswapV3Router01", chain)?,
+                try_address("UniswapV3Quoter", chain)?,
+                try_address("UniswapV3QuoterV2", chain),
+            ),
+        };
+        let mut this = Self::new(client.clone(), factory, router, quoter);
+        this.factory.set_chain(chain);
+        if let Some(quoter) = quoter_v2 {
+            this.set_quoter(Quoter::new_v2(client, quoter));
+        }
+        Some(this)
+    }
+
+    /// Returns a pointer to the client.
+    #[inline(always)]
+    pub fn client(&self) -> Arc<M> {
+        self.factory.client()
+    }
+
+    /// Returns the protocol's chain.
+    #[inline(always)]
+    pub fn chain(&self) -> Option<Chain> {
+        self.factory.chain
+    }
+
+    /// Sets the protocol's chain.
+    #[inline(always)]
+    pub fn set_chain(&mut self, chain: Chain) {
+        self.factory.set_chain(chain);
+    }
+
+    /// Returns the policy of the factory's batched requests and log scans.
+    #[inline(always)]
+    pub fn request_policy(&self) -> RequestPolicy {
+        self.factory.request_policy()
+    }
+
+    /// Sets the policy of the factory's batched requests and log scans. See
+    /// [`Factory::set_request_policy`] for more details.
+    #[inline(always)]
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) {
+        self.factory.set_request_policy(policy);
+    }
+
+    /* ----------------------------------------- Factory ---------------------------------------- */
+
+    /// Returns a reference to the factory.
+    #[inline(always)]
+    pub fn factory(&self) -> &Factory<M> {
+        &self.factory
+    }
+
+    /// Sets the factory.
+    #[inline(always)]
+    pub fn set_factory(&mut self, factory: Factory<M>) {
+        self.factory = factory;
+    }
+
+    /// The factory's `pool_for` method. See documentation of [Factory] for more details.
+    #[inline(always)]
+    pub fn pool_for(&self, token_a: Address, token_b: Address, fee: FeeAmount) -> Result<Pool<M>> {
+        self.factory.pool_for(token_a, token_b, fee)
+    }
+
+    /// The factory's `best_pool` method. See documentation of [Factory] for more details.
+    #[inline(always)]
+    pub async fn best_pool(
+        &self,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<Vec<PoolLiquidity>> {
+        self.factory.best_pool(token_a, token_b).await
+    }
+
+    /* ----------------------------------------- Router ----------------------------------------- */
+
+    /// Returns a reference to the router.
+    #[inline(always)]
+    pub fn router(&self) -> &Router<M> {
+        &self.router
+    }
+
+    /// Returns the contract calls that must be executed, in order, to allow `spender` to transfer
+    /// `amount` of `token` from the client's default sender. See [`Erc20::approve_if_needed`].
+    ///
+    /// Returns no calls if `token` is the native token, and [`Error::SignerRequired`] if the client
+    /// is read-only.
+    ///
+    /// [`Erc20::approve_if_needed`]: crate::Erc20::approve_if_needed
+    /// [`Error::SignerRequired`]: crate::errors::Error::SignerRequired
+    pub async fn approve_if_needed(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Vec<ContractCall<M, bool>>> {
+        if is_native(&token) {
+            return Ok(vec![]);
+        }
+        let client = self.client();
+        let owner = require_signer(client.as_ref(), "Approving a spender")?;
+        Erc20::new(client, token).approve_if_needed(owner, spender, amount).await
+    }
+
+    /* ----------------------------------------- Quoter ----------------------------------------- */
+
+    /// Returns a reference to the quoter.
+    #[inline(always)]
+    pub fn quoter(&self) -> &Quoter<M> {
+        &self.quoter
+    }
+
+    /// Sets the quoter.
+    #[inline(always)]
+    pub fn set_quoter(&mut self, quoter: Quoter<M>) {
+        self.quoter = quoter;
+    }
+
+    /// The quoter's `quote_exact_input_single` method, with no price limit. See documentation of
+    /// [Quoter] for more details.
+    #[inline(always)]
+    pub async fn quote_exact_input_single(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        fee: FeeAmount,
+        amount_in: U256,
+    ) -> Result<Quote> {
+        self.quoter
+            .quote_exact_input_single(token_in, token_out, fee, amount_in, U256::zero())
+            .await
+    }
+
+    /// The quoter's `quote_exact_output_single` method, with no price limit. See documentation of
+    /// [Quoter] for more details.
+    #[inline(always)]
+    pub async fn quote_exact_output_single(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        fee: FeeAmount,
+        amount_out: U256,
+    ) -> Result<Quote> {
+        self.quoter
+            .quote_exact_output_single(token_in, token_out, fee, amount_out, U256::zero())
+            .await
+    }
+
+    /// The quoter's `quote_exact_input` method. See documentation of [Quoter] for more details.
+    #[inline(always)]
+    pub async fn quote_exact_input(&self, path: &Path, amount_in: U256) -> Result<Quote> {
+        self.quoter.quote_exact_input(path, amount_in).await
+    }
+
+    /// The quoter's `quote_exact_output` method. See documentation of [Quoter] for more details.
+    #[inline(always)]
+    pub async fn quote_exact_output(&self, path: &Path, amount_out: U256) -> Result<Quote> {
+        self.quoter.quote_exact_output(path, amount_out).await
+    }
+
+    /// Returns the address of the chain's wrapped native token, resolved from the [addressbook]
+    /// using the factory's chain.
+    ///
+    /// Returns None if the chain is not set or the token is not found.
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn wrapped_native(&self) -> Option<Address> {
+        self.chain().and_then(crate::contracts::addresses::wrapped_native)
+    }
+
+    /// Checks the protocol's addresses against the chain: that the factory, router and quoter
+    /// have code, and that the router's factory and wrapped native token are the configured ones.
+    ///
+    /// The wrapped native token is checked only if the [chain][Self::chain] is set. Errors, like
+    /// failed provider requests, are reported as failed checks.
+    pub async fn verify(&self) -> VerificationReport {
+        let client = self.client();
+        let factory = self.factory.address();
+        let mut report = VerificationReport::default();
+        report.check_code(client.as_ref(), "factory_code", factory).await;
+        report.check_code(client.as_ref(), "router_code", self.router.address()).await;
+        report.check_code(client.as_ref(), "quoter_code", self.quoter.address()).await;
+
+        #[cfg(feature = "addresses")]
+        let weth = self.wrapped_native();
+        #[cfg(not(feature = "addresses"))]
+        let weth = None;
+        let router = self.router.contract();
+        report.check_call("router_factory", router.factory(), Some(factory)).await;
+        report.check_call("router_wrapped_native", router.weth9(), weth).await;
+        report
+    }
+
+    /// The router's `swap` method through the single pool of `token_in` and `token_out` selected
+    /// by `fee`, quoted with the protocol's quoter. See documentation of [Router] for more details.
+    ///
+    /// With [`FeeSelection::Auto`], the pool with the most in-range liquidity is used, see
+    /// [`Factory::best_pool`]; the native token is looked up as its wrapped token.
+    ///
+    /// `weth` overrides the chain's wrapped native token. See
+    /// [`swap_path`][Self::swap_path] for more details.
+    pub async fn swap(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        token_in: Address,
+        token_out: Address,
+        fee: impl Into<FeeSelection>,
+        recipient: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let needs_weth = matches!(amount, Amount::ExactInNative(_))
+            || token_in == NATIVE_ADDRESS
+            || token_out == NATIVE_ADDRESS;
+        let weth = resolve_weth(weth, self.chain(), needs_weth)?;
+        let fee = match fee.into() {
+            FeeSelection::Fixed(fee) => fee,
+            FeeSelection::Auto => {
+                let mut tokens = [token_in, token_out];
+                map_native(&mut tokens, weth);
+                self.best_pool(tokens[0], tokens[1]).await?[0].fee
+            }
+        };
+        let path = Path::single(token_in, token_out, fee);
+        let weth = Some(weth);
+        self.swap_path(amount, slippage_tolerance, &path, recipient, deadline, weth, options).await
+    }
+
+    /// The router's `swap` method through `path`, quoted with the protocol's quoter. See
+    /// documentation of [Router] for more details.
+    ///
+    /// If [`options.price_limit`][SwapOptions::price_limit] is set for a single-hop swap, the
+    /// price of the pool is fetched to check that the limit is on the side the swap moves it
+    /// towards: below it when swapping `token0` for `token1`, above it otherwise. Returns
+    /// [`Error::InvalidSqrtRatio`][crate::errors::Error::InvalidSqrtRatio] if it is not.
+    ///
+    /// `weth` overrides the chain's wrapped native token, which is otherwise used in place of the
+    /// native token. Returns
+    /// [`Error::WrappedNativeNotFound`][crate::errors::Error::WrappedNativeNotFound] if the swap
+    /// goes from or to the native token and neither is set.
+    pub async fn swap_path(
+        &self,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &Path,
+        recipient: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Option<Address>,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let needs_weth =
+            matches!(amount, Amount::ExactInNative(_)) || path.tokens().contains(&NATIVE_ADDRESS);
+        let weth = resolve_weth(weth, self.chain(), needs_weth)?;
+        if let (Some(price_limit), 1) = (options.price_limit, path.hops()) {
+            let limit = price_limit.sqrt_price_x96()?;
+            let mut tokens = [path.token_in(), path.token_out()];
+            map_native(&mut tokens, weth);
+            let [token_in, token_out] = tokens;
+            let pool = self.pool_for(token_in, token_out, path.fees()[0])?;
+            let (sqrt_price_x96, ..) = pool.slot_0().call().await?;
+            check_sqrt_price_limit(limit, sqrt_price_x96, token_in < token_out)?;
+        }
+        self.router
+            .swap(
+                &self.quoter,
+                amount,
+                slippage_tolerance,
+                path,
+                recipient,
+                deadline,
+                weth,
+                options,
+            )
+            .await
+    }
+
+    /// Sends a swap built with [`swap`][Self::swap] or [`swap_path`][Self::swap_path], waits for
+    /// `confirmations` confirmations and parses the amounts of the swap from the transaction
+    /// receipt.
+    ///
+    /// If the transaction reverts, the revert is decoded by replaying it at the block in which it
+    /// was included, e.g. into [`Error::SlippageExceeded`][crate::errors::Error::SlippageExceeded].
+    /// Use the call directly to manage the transaction yourself.
+    pub async fn execute_swap<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+        confirmations: usize,
+    ) -> Result<SwapReceipt> {
+        execute_swap(self.client().as_ref(), call, confirmations).await
+    }
+
+    /// Sends a swap built with [`swap`][Self::swap] or [`swap_path`][Self::swap_path] privately
+    /// through `submitter`, like the [Flashbots][crate::mev::Flashbots] relay, instead of the
+    /// public mempool, where it could be sandwiched.
+    ///
+    /// The transaction is simulated, signed by the client and submitted with an expiry of
+    /// [`options.max_blocks`][crate::mev::PrivateOptions::max_blocks] blocks. The amounts of the
+    /// swap are then parsed from its receipt, as in [`execute_swap`][Self::execute_swap].
+    #[cfg(feature = "mev")]
+    pub async fn execute_swap_private<D: Detokenize, S: crate::mev::BundleSubmitter>(
+        &self,
+        call: ContractCall<M, D>,
+        submitter: &S,
+        options: crate::mev::PrivateOptions,
+    ) -> Result<SwapReceipt> {
+        crate::mev::execute_swap_private(self.client().as_ref(), call, submitter, options).await
+    }
+
+    /// Generates the access list of `call` with `eth_createAccessList`, and sets it on the call
+    /// only if it lowers its estimated gas. See [`gas::with_access_list`].
+    ///
+    /// The generated list is returned in both cases, or None if the provider does not support the
+    /// RPC.
+    pub async fn with_access_list<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+    ) -> Result<(ContractCall<M, D>, Option<AccessListEstimate>)> {
+        gas::with_access_list(self.client().as_ref(), call).await
+    }
+}
+
+#[cfg(all(test, feature = "addresses"))]
+mod tests {
+    use super::*;
+    use ethers_core::{abi, types::Bytes};
+    use ethers_providers::{MockProvider, Provider};
+
+    #[test]
+    fn test_new_with_chain() {
+        let (provider, _mock) = Provider::<MockProvider>::mocked();
+        let protocol = Protocol::new_with_chain(Arc::new(provider), Chain::Arbitrum, None).unwrap();
+        assert_eq!(protocol.chain(), Some(Chain::Arbitrum));
+        assert_eq!(
+            protocol.factory().address(),
+            "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap()
+        );
+        assert_eq!(
+            protocol.router().address(),
+            "0xE592427A0AEce92De3Edee1F18E0157C05861564".parse().unwrap()
+        );
+        assert_eq!(
+            protocol.quoter().address(),
+            "0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse().unwrap()
+        );
+        assert!(protocol.quoter().is_v2());
+
+        // USDC.e/WETH 0.05%
+        let usdc = "0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8".parse().unwrap();
+        let weth = "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".parse().unwrap();
+        assert_eq!(
+            protocol.pool_for(usdc, weth, FeeAmount::Low).unwrap().address(),
+            "0xC31E54c7a869B9FcBEcc14363CF510d1c41fa443".parse().unwrap()
+        );
+
+        assert!(
+            Protocol::new_with_chain(Arc::new(Provider::mocked().0), Chain::Fantom, None).is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quote() {
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let protocol = Protocol::new(
+            Arc::new(provider),
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        );
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let amount = U256::exp10(18);
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[abi::Token::Uint(amount)]))).unwrap();
+        let res = protocol.quote_exact_input_single(a, b, FeeAmount::Low, 1000.into()).await;
+        assert_eq!(res.unwrap().amount, amount);
+
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[abi::Token::Uint(amount)]))).unwrap();
+        let res = protocol.quote_exact_output_single(a, b, FeeAmount::Low, 1000.into()).await;
+        assert_eq!(res.unwrap().amount, amount);
+    }
+
+    #[tokio::test]
+    async fn test_swap_price_limit() {
+        use crate::{
+            contracts::bindings::i_swap_router::{ExactInputSingleCall, MulticallCall},
+            errors::Error,
+            v3::math::{price_to_sqrt_ratio, MAX_SQRT_RATIO, Q96},
+            PriceLimit,
+        };
+        use ethers_core::abi::{AbiDecode, Token};
+
+        let (provider, mock) = Provider::<MockProvider>::mocked();
+        let protocol = Protocol::new(
+            Arc::new(provider),
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        );
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let push_slot_0 = || {
+            let slot_0 = abi::encode(&[
+                Token::Uint(Q96),
+                Token::Int(0.into()),
+                Token::Uint(0.into()),
+                Token::Uint(1.into()),
+                Token::Uint(1.into()),
+                Token::Uint(0.into()),
+                Token::Bool(true),
+            ]);
+            mock.push::<Bytes, _>(Bytes::from(slot_0)).unwrap();
+        };
+        let swap = |token_in, token_out, price_limit: PriceLimit| {
+            let options = SwapOptions::new().price_limit(price_limit);
+            let amount = Amount::ExactIn(U256::exp10(18));
+            protocol.swap(
+                amount,
+                1.0,
+                token_in,
+                token_out,
+                FeeAmount::Low,
+                a,
+                U256::MAX,
+                None,
+                options,
+            )
+        };
+        let price = |price| PriceLimit::Price { price, decimals0: 18, decimals1: 18 };
+
+        // token0 for token1 moves the price down, to the limit
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(1000.into())]))).unwrap();
+        push_slot_0();
+        let call = swap(a, b, price(0.5)).await.unwrap();
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        let params = ExactInputSingleCall::decode(&data[0]).unwrap().params;
+        assert_eq!(params.sqrt_price_limit_x96, price_to_sqrt_ratio(0.5, 18, 18).unwrap());
+
+        // on the wrong side of the price
+        push_slot_0();
+        let res = swap(a, b, price(2.0)).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSqrtRatio(_)));
+        push_slot_0();
+        let res = swap(b, a, PriceLimit::SqrtPriceX96(Q96 - 1)).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSqrtRatio(_)));
+        // out of range
+        push_slot_0();
+        let res = swap(b, a, PriceLimit::SqrtPriceX96(MAX_SQRT_RATIO)).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSqrtRatio(_)));
+        let res = swap(a, b, price(f64::NAN)).await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidPrice(_)));
+    }
+}
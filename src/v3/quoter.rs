@@ -0,0 +1,451 @@
+use super::{FeeAmount, Path};
+use crate::{
+    contracts::bindings::{
+        i_quoter::IQuoter,
+        i_quoter_v2::{IQuoterV2, QuoteExactInputSingleParams, QuoteExactOutputSingleParams},
+    },
+    errors::Result,
+    QuoteCache,
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::{
+    abi::Detokenize,
+    types::{Address, Chain, H160, U256},
+};
+use ethers_providers::Middleware;
+use std::sync::Arc;
+
+/// The address of Uniswap V3's `QuoterV1`, the same on all chains.
+pub const QUOTER_ADDRESS: Address = H160([
+    0xb2, 0x73, 0x08, 0xf9, 0xf9, 0x0d, 0x60, 0x74, 0x63, 0xbb, 0x33, 0xea, 0x1b, 0xeb, 0xb4, 0x1c,
+    0x27, 0xce, 0x5a, 0xb6,
+]);
+
+/// The result of a quote.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Quote {
+    /// The amount out of an exact input quote, or the amount in of an exact output quote.
+    pub amount: U256,
+
+    /// The `sqrtPriceX96` of each pool after the swap, in the order of the swap.
+    ///
+    /// Empty for `QuoterV1`.
+    pub sqrt_price_x96_after: Vec<U256>,
+
+    /// The number of initialized ticks crossed in each pool, in the order of the swap.
+    ///
+    /// Empty for `QuoterV1`.
+    pub initialized_ticks_crossed: Vec<u32>,
+
+    /// The gas estimate of the swap, as measured by the quoter.
+    ///
+    /// None for `QuoterV1`.
+    pub gas_estimate: Option<U256>,
+}
+
+impl Quote {
+    fn v1(amount: U256) -> Self {
+        Self { amount, ..Default::default() }
+    }
+
+    fn v2_single(
+        (amount, sqrt_price_x96_after, ticks_crossed, gas): (U256, U256, u32, U256),
+    ) -> Self {
+        Self {
+            amount,
+            sqrt_price_x96_after: vec![sqrt_price_x96_after],
+            initialized_ticks_crossed: vec![ticks_crossed],
+            gas_estimate: Some(gas),
+        }
+    }
+
+    fn v2(
+        (amount, sqrt_price_x96_after, ticks_crossed, gas): (U256, Vec<U256>, Vec<u32>, U256),
+    ) -> Self {
+        Self {
+            amount,
+            sqrt_price_x96_after,
+            initialized_ticks_crossed: ticks_crossed,
+            gas_estimate: Some(gas),
+        }
+    }
+}
+
+/// A Uniswap V3 quoter contract, of either version.
+pub enum QuoterContract<M> {
+    /// The original `Quoter`, which returns only the amount.
+    V1(IQuoter<M>),
+    /// `QuoterV2`, which also returns the prices after the swap, the ticks crossed and a gas
+    /// estimate.
+    V2(IQuoterV2<M>),
+}
+
+impl<M> Clone for QuoterContract<M> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::V1(contract) => Self::V1(contract.clone()),
+            Self::V2(contract) => Self::V2(contract.clone()),
+        }
+    }
+}
+
+impl<M> QuoterContract<M> {
+    /// Returns the pointer to the contract's client.
+    pub fn client(&self) -> Arc<M> {
+        match self {
+            Self::V1(contract) => contract.client(),
+            Self::V2(contract) => contract.client(),
+        }
+    }
+
+    /// Returns the contract's address.
+    pub fn address(&self) -> Address {
+        match self {
+            Self::V1(contract) => contract.address(),
+            Self::V2(contract) => contract.address(),
+        }
+    }
+}
+
+contract_struct! {
+    /// A Uniswap V3 quoter, which simulates swaps to return their input or output amounts.
+    ///
+    /// The quoter's methods are not `view`, as they perform the swap and revert, so they are only
+    /// meant to be called with `eth_call`. `QuoterV1` catches the revert and returns the amount
+    /// parsed from its data, so a failing swap reverts with the reason of the pool or with
+    /// "Unexpected error".
+    ///
+    pub struct Quoter<M> {
+        /// The quoter contract.
+        contract: QuoterContract<M>,
+
+        /// The cache of quotes, and its chain.
+        quote_cache: Option<(Chain, QuoteCache)>,
+    }
+}
+
+impl<M: Middleware> Quoter<M> {
+    /// Creates a new instance of a `QuoterV1` using the provided address.
+    pub fn new(client: Arc<M>, address: Address) -> Self {
+        let contract = QuoterContract::V1(IQuoter::new(address, client));
+        Self { contract, quote_cache: None }
+    }
+
+    /// Creates a new instance of a `QuoterV2` using the provided address.
+    pub fn new_v2(client: Arc<M>, address: Address) -> Self {
+        let contract = QuoterContract::V2(IQuoterV2::new(address, client));
+        Self { contract, quote_cache: None }
+    }
+
+    /// Returns whether the quoter is a `QuoterV2`.
+    pub fn is_v2(&self) -> bool {
+        matches!(self.contract, QuoterContract::V2(_))
+    }
+
+    /// Returns the cache of quotes and its chain, if set.
+    pub fn quote_cache(&self) -> Option<(Chain, &QuoteCache)> {
+        self.quote_cache.as_ref().map(|(chain, cache)| (*chain, cache))
+    }
+
+    /// Sets the cache of the quotes on `chain`, keyed by their calldata, so that quoting the same
+    /// swap again within a block, like with [`V3Router::swap`][super::Router::swap], does not make
+    /// any request.
+    pub fn set_quote_cache(&mut self, chain: Chain, cache: QuoteCache) {
+        self.quote_cache = Some((chain, cache));
+    }
+
+    /// Quotes swapping `amount_in` of `token_in` for `token_out` through the pool with `fee`,
+    /// stopping at `sqrt_price_limit_x96` if it is not zero.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(quoter = ?self.address(), ?token_in, ?token_out, ?fee)
+        )
+    )]
+    pub async fn quote_exact_input_single(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        fee: FeeAmount,
+        amount_in: U256,
+        sqrt_price_limit_x96: U256,
+    ) -> Result<Quote> {
+        let fee = fee.as_u32();
+        let quote = match &self.contract {
+            QuoterContract::V1(contract) => {
+                let call = contract.quote_exact_input_single(
+                    token_in,
+                    token_out,
+                    fee,
+                    amount_in,
+                    sqrt_price_limit_x96,
+                );
+                self.call(call, Quote::v1).await?
+            }
+            QuoterContract::V2(contract) => {
+                let params = QuoteExactInputSingleParams {
+                    token_in,
+                    token_out,
+                    amount_in,
+                    fee,
+                    sqrt_price_limit_x96,
+                };
+                self.call(contract.quote_exact_input_single(params), Quote::v2_single).await?
+            }
+        };
+        debug!(%amount_in, quote = %quote.amount, gas_estimate = ?quote.gas_estimate, "quoted");
+        Ok(quote)
+    }
+
+    /// Quotes swapping `token_in` for `amount_out` of `token_out` through the pool with `fee`,
+    /// stopping at `sqrt_price_limit_x96` if it is not zero.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(quoter = ?self.address(), ?token_in, ?token_out, ?fee)
+        )
+    )]
+    pub async fn quote_exact_output_single(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        fee: FeeAmount,
+        amount_out: U256,
+        sqrt_price_limit_x96: U256,
+    ) -> Result<Quote> {
+        let fee = fee.as_u32();
+        let quote = match &self.contract {
+            QuoterContract::V1(contract) => {
+                let call = contract.quote_exact_output_single(
+                    token_in,
+                    token_out,
+                    fee,
+                    amount_out,
+                    sqrt_price_limit_x96,
+                );
+                self.call(call, Quote::v1).await?
+            }
+            QuoterContract::V2(contract) => {
+                let params = QuoteExactOutputSingleParams {
+                    token_in,
+                    token_out,
+                    amount: amount_out,
+                    fee,
+                    sqrt_price_limit_x96,
+                };
+                self.call(contract.quote_exact_output_single(params), Quote::v2_single).await?
+            }
+        };
+        debug!(%amount_out, quote = %quote.amount, gas_estimate = ?quote.gas_estimate, "quoted");
+        Ok(quote)
+    }
+
+    /// Quotes swapping `amount_in` of the first token in `path` for the last one.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(quoter = ?self.address(), path = ?path.tokens(), fees = ?path.fees())
+        )
+    )]
+    pub async fn quote_exact_input(&self, path: &Path, amount_in: U256) -> Result<Quote> {
+        let path = path.encode();
+        let quote = match &self.contract {
+            QuoterContract::V1(contract) => {
+                self.call(contract.quote_exact_input(path, amount_in), Quote::v1).await?
+            }
+            QuoterContract::V2(contract) => {
+                self.call(contract.quote_exact_input(path, amount_in), Quote::v2).await?
+            }
+        };
+        debug!(%amount_in, quote = %quote.amount, gas_estimate = ?quote.gas_estimate, "quoted");
+        Ok(quote)
+    }
+
+    /// Quotes swapping the first token in `path` for `amount_out` of the last one.
+    ///
+    /// `path` is in the order of the swap, and is reversed for the quoter.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(quoter = ?self.address(), path = ?path.tokens(), fees = ?path.fees())
+        )
+    )]
+    pub async fn quote_exact_output(&self, path: &Path, amount_out: U256) -> Result<Quote> {
+        let path = path.reverse().encode();
+        let quote = match &self.contract {
+            QuoterContract::V1(contract) => {
+                self.call(contract.quote_exact_output(path, amount_out), Quote::v1).await?
+            }
+            QuoterContract::V2(contract) => {
+                self.call(contract.quote_exact_output(path, amount_out), Quote::v2).await?
+            }
+        };
+        debug!(%amount_out, quote = %quote.amount, gas_estimate = ?quote.gas_estimate, "quoted");
+        Ok(quote)
+    }
+
+    /// Calls the quoter with `call`, mapping its output with `quote`, or returns the quote from
+    /// the quote cache if it is cached.
+    async fn call<D: Detokenize>(
+        &self,
+        call: ContractCall<M, D>,
+        quote: impl FnOnce(D) -> Quote,
+    ) -> Result<Quote> {
+        let cache = match (&self.quote_cache, call.calldata()) {
+            (Some((chain, cache)), Some(calldata)) => {
+                if let Some(quote) = cache.quote(*chain, self.address(), &calldata) {
+                    debug!("using the cached quote");
+                    return Ok(quote);
+                }
+                Some((*chain, cache, calldata))
+            }
+            _ => None,
+        };
+        let quote = quote(call.call().await?);
+        if let Some((chain, cache, calldata)) = cache {
+            cache.insert_quote(chain, self.address(), calldata, quote.clone());
+        }
+        Ok(quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+    };
+    use ethers_providers::{MockProvider, Provider};
+
+    const TOKEN_A: Address = Address::repeat_byte(0x0a);
+    const TOKEN_B: Address = Address::repeat_byte(0x0b);
+
+    fn push(mock: &MockProvider, tokens: &[Token]) {
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(tokens))).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quote_v1() {
+        use crate::{
+            contracts::bindings::i_quoter::QuoteExactInputSingleCall, errors::Error,
+            testing::MockDex,
+        };
+
+        let (provider, dex) = MockDex::mocked();
+        let address = Address::repeat_byte(0x33);
+        let quoter = Quoter::new(Arc::new(provider), address);
+        assert!(!quoter.is_v2());
+
+        let (fee, limit) = (FeeAmount::Low, U256::zero());
+        dex.mock_quote_exact_input_single(
+            address,
+            TOKEN_A,
+            TOKEN_B,
+            fee,
+            1.into(),
+            limit,
+            1000.into(),
+        );
+        let quote =
+            quoter.quote_exact_input_single(TOKEN_A, TOKEN_B, fee, 1.into(), limit).await.unwrap();
+        assert_eq!(quote, Quote::v1(1000.into()));
+        assert_eq!(quote.gas_estimate, None);
+
+        // a failing swap reverts with the reason of the pool
+        let call = QuoteExactInputSingleCall {
+            token_in: TOKEN_A,
+            token_out: TOKEN_B,
+            fee: fee.as_u32(),
+            amount_in: 2.into(),
+            sqrt_price_limit_x96: limit,
+        };
+        dex.mock_revert_reason(address, call, "SPL");
+        let res = quoter.quote_exact_input_single(TOKEN_A, TOKEN_B, fee, 2.into(), limit).await;
+        assert!(matches!(res.unwrap_err(), Error::Reverted(reason) if reason == "SPL"));
+
+        push(dex.mock(), &[Token::Uint(2000.into())]);
+        let quote = quoter
+            .quote_exact_output(&Path::single(TOKEN_A, TOKEN_B, FeeAmount::Low), 1.into())
+            .await
+            .unwrap();
+        assert_eq!(quote.amount, 2000.into());
+    }
+
+    #[tokio::test]
+    async fn test_quote_v2() {
+        let (provider, mock) = Provider::mocked();
+        let quoter = Quoter::new_v2(Arc::new(provider), Address::repeat_byte(0x33));
+        assert!(quoter.is_v2());
+
+        let limit = U256::exp10(20);
+        push(
+            &mock,
+            &[
+                Token::Uint(1000.into()),
+                Token::Uint(U256::exp10(30)),
+                Token::Uint(2.into()),
+                Token::Uint(80_000.into()),
+            ],
+        );
+        let quote = quoter
+            .quote_exact_input_single(TOKEN_A, TOKEN_B, FeeAmount::Medium, 1.into(), limit)
+            .await
+            .unwrap();
+        assert_eq!(quote.amount, 1000.into());
+        assert_eq!(quote.sqrt_price_x96_after, vec![U256::exp10(30)]);
+        assert_eq!(quote.initialized_ticks_crossed, vec![2]);
+        assert_eq!(quote.gas_estimate, Some(80_000.into()));
+
+        push(
+            &mock,
+            &[
+                Token::Uint(1000.into()),
+                Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+                Token::Array(vec![Token::Uint(3.into()), Token::Uint(4.into())]),
+                Token::Uint(160_000.into()),
+            ],
+        );
+        let quote = quoter
+            .quote_exact_input(&Path::single(TOKEN_A, TOKEN_B, FeeAmount::Low), 1.into())
+            .await
+            .unwrap();
+        assert_eq!(quote.amount, 1000.into());
+        assert_eq!(quote.sqrt_price_x96_after, vec![1.into(), 2.into()]);
+        assert_eq!(quote.initialized_ticks_crossed, vec![3, 4]);
+        assert_eq!(quote.gas_estimate, Some(160_000.into()));
+    }
+
+    #[tokio::test]
+    async fn test_quote_cache() {
+        let (provider, mock) = Provider::mocked();
+        let mut quoter = Quoter::new(Arc::new(provider), Address::repeat_byte(0x33));
+        let cache = QuoteCache::new(16);
+        quoter.set_quote_cache(Chain::Mainnet, cache.clone());
+        let path = Path::single(TOKEN_A, TOKEN_B, FeeAmount::Low);
+
+        push(&mock, &[Token::Uint(1000.into())]);
+        let quote = quoter.quote_exact_input(&path, 1.into()).await.unwrap();
+        assert_eq!(quote.amount, 1000.into());
+
+        // only one response was pushed, so this would fail if any call was made
+        assert_eq!(quoter.quote_exact_input(&path, 1.into()).await.unwrap(), quote);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // a different amount is a different call
+        assert!(quoter.quote_exact_input(&path, 2.into()).await.is_err());
+
+        cache.notify_new_block(1);
+        assert!(quoter.quote_exact_input(&path, 1.into()).await.is_err());
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+    }
+}
@@ -0,0 +1,228 @@
+use super::FeeAmount;
+use crate::utils::sort_tokens;
+use ethers_core::types::Address;
+use std::collections::BTreeMap;
+
+/// The sorted tokens and the fee amount of a pool.
+type PoolKey = (Address, Address, FeeAmount);
+
+/// A pool created by a factory, as emitted by its `PoolCreated` event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PoolInfo {
+    /// The first token of the pool, sorted.
+    pub token0: Address,
+    /// The second token of the pool, sorted.
+    pub token1: Address,
+    /// The fee amount of the pool.
+    pub fee: FeeAmount,
+    /// The tick spacing of the pool.
+    pub tick_spacing: i32,
+    /// The address of the pool.
+    pub address: Address,
+    /// The number of the block in which the pool was created.
+    pub block_number: u64,
+}
+
+/// A local registry of the pools created by a factory and of the fee amounts enabled on it, built
+/// by scanning its `PoolCreated` and `FeeAmountEnabled` events with
+/// [`Factory::scan_pools`][super::Factory::scan_pools].
+///
+/// The registry can be persisted and later resumed from its [`last_block`][Self::last_block] with
+/// [`Factory::resume_scan`][super::Factory::resume_scan].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PoolRegistry {
+    /// The pools, keyed by their sorted tokens and fee amount.
+    #[cfg_attr(feature = "serde", serde(with = "pools"))]
+    pools: BTreeMap<PoolKey, PoolInfo>,
+
+    /// The enabled fee amounts and their tick spacings.
+    #[cfg_attr(feature = "serde", serde(with = "fee_amounts"))]
+    fee_amounts: BTreeMap<FeeAmount, i32>,
+
+    /// The first block to scan.
+    pub from_block: u64,
+
+    /// The last block which has been scanned, if any.
+    pub last_block: Option<u64>,
+}
+
+impl PoolRegistry {
+    /// Creates a new empty registry which starts scanning at `from_block`.
+    pub fn new(from_block: u64) -> Self {
+        Self { from_block, ..Default::default() }
+    }
+
+    /// Returns the first block which has not been scanned yet.
+    pub fn next_block(&self) -> u64 {
+        self.last_block.map_or(self.from_block, |block| block + 1)
+    }
+
+    /// Returns the pool of `token_a` and `token_b`, in any order, with `fee`.
+    pub fn get(&self, token_a: Address, token_b: Address, fee: FeeAmount) -> Option<&PoolInfo> {
+        let (token0, token1) = sort_tokens(token_a, token_b);
+        self.pools.get(&(token0, token1, fee))
+    }
+
+    /// Returns an iterator over the pools of `token_a` and `token_b`, in any order, sorted by fee
+    /// amount.
+    pub fn pools_for(&self, token_a: Address, token_b: Address) -> impl Iterator<Item = &PoolInfo> {
+        let (token0, token1) = sort_tokens(token_a, token_b);
+        let range =
+            (token0, token1, FeeAmount::Custom(0))..=(token0, token1, FeeAmount::Custom(u32::MAX));
+        self.pools.range(range).map(|(_, pool)| pool)
+    }
+
+    /// Inserts `pool`, returning the previous pool with the same tokens and fee, if any.
+    pub fn insert(&mut self, pool: PoolInfo) -> Option<PoolInfo> {
+        let (token0, token1) = sort_tokens(pool.token0, pool.token1);
+        self.pools.insert((token0, token1, pool.fee), pool)
+    }
+
+    /// Returns the tick spacing of `fee`, if it is enabled.
+    pub fn tick_spacing(&self, fee: FeeAmount) -> Option<i32> {
+        self.fee_amounts.get(&fee).copied()
+    }
+
+    /// Returns the enabled fee amounts, sorted.
+    pub fn fee_amounts(&self) -> Vec<FeeAmount> {
+        self.fee_amounts.keys().copied().collect()
+    }
+
+    /// Records that `fee` is enabled with `tick_spacing`.
+    pub fn enable_fee_amount(&mut self, fee: FeeAmount, tick_spacing: i32) {
+        self.fee_amounts.insert(fee, tick_spacing);
+    }
+
+    /// Returns the number of pools in the registry.
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Returns whether the registry does not contain any pools.
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// Returns an iterator over the pools, sorted by their tokens and fee amount.
+    pub fn iter(&self) -> impl Iterator<Item = &PoolInfo> {
+        self.pools.values()
+    }
+}
+
+/// (De)serializes the pools as a sequence, since JSON keys must be strings.
+#[cfg(feature = "serde")]
+mod pools {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        pools: &BTreeMap<PoolKey, PoolInfo>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pools.values())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<PoolKey, PoolInfo>, D::Error> {
+        let pools = Vec::<PoolInfo>::deserialize(deserializer)?;
+        Ok(pools
+            .into_iter()
+            .map(|p| {
+                let (token0, token1) = sort_tokens(p.token0, p.token1);
+                ((token0, token1, p.fee), p)
+            })
+            .collect())
+    }
+}
+
+/// (De)serializes the fee amounts as a sequence of `(fee, tick_spacing)` pairs.
+#[cfg(feature = "serde")]
+mod fee_amounts {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        fee_amounts: &BTreeMap<FeeAmount, i32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(fee_amounts)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<FeeAmount, i32>, D::Error> {
+        Ok(Vec::<(FeeAmount, i32)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let (a, b) = (Address::repeat_byte(2), Address::repeat_byte(1));
+        let pool = |fee, address| PoolInfo {
+            token0: b,
+            token1: a,
+            fee,
+            tick_spacing: 10,
+            address: Address::repeat_byte(address),
+            block_number: 1,
+        };
+
+        let mut registry = PoolRegistry::new(1);
+        assert!(registry.is_empty());
+        assert_eq!(registry.next_block(), 1);
+        assert!(registry.insert(pool(FeeAmount::Low, 3)).is_none());
+        assert!(registry.insert(pool(FeeAmount::Custom(2500), 4)).is_none());
+        assert_eq!(registry.get(a, b, FeeAmount::Low), Some(&pool(FeeAmount::Low, 3)));
+        assert_eq!(registry.get(b, a, FeeAmount::Low), Some(&pool(FeeAmount::Low, 3)));
+        assert_eq!(registry.get(a, b, FeeAmount::Medium), None);
+        assert_eq!(registry.len(), 2);
+
+        let fees: Vec<_> = registry.pools_for(a, b).map(|pool| pool.fee).collect();
+        assert_eq!(fees, [FeeAmount::Low, FeeAmount::Custom(2500)]);
+        assert_eq!(registry.pools_for(a, Address::repeat_byte(3)).count(), 0);
+
+        registry.enable_fee_amount(FeeAmount::Custom(2500), 50);
+        registry.enable_fee_amount(FeeAmount::Low, 10);
+        assert_eq!(registry.fee_amounts(), [FeeAmount::Low, FeeAmount::Custom(2500)]);
+        assert_eq!(registry.tick_spacing(FeeAmount::Custom(2500)), Some(50));
+        assert_eq!(registry.tick_spacing(FeeAmount::High), None);
+
+        registry.last_block = Some(10);
+        assert_eq!(registry.next_block(), 11);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_registry_serde() {
+        let mut registry = PoolRegistry::new(10);
+        for i in 1..=3u8 {
+            let (token0, token1) = (Address::repeat_byte(i), Address::repeat_byte(i + 10));
+            let address = Address::repeat_byte(i + 20);
+            let fee = FeeAmount::try_from(i as u32 * 100).unwrap();
+            let tick_spacing = i as i32;
+            registry.insert(PoolInfo {
+                token0,
+                token1,
+                fee,
+                tick_spacing,
+                address,
+                block_number: i as u64,
+            });
+            registry.enable_fee_amount(fee, tick_spacing);
+        }
+        registry.last_block = Some(20);
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let deserialized: PoolRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, registry);
+        assert_eq!(deserialized.next_block(), 21);
+        assert_eq!(deserialized.tick_spacing(FeeAmount::Custom(300)), Some(3));
+    }
+}
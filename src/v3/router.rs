@@ -1,13 +1,28 @@
-#![allow(missing_docs)]
-
-use crate::contracts::bindings::i_swap_router::*;
+use super::{
+    math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+    Path, Quoter,
+};
+use crate::{
+    constants::NATIVE_ADDRESS,
+    contracts::bindings::i_swap_router::*,
+    errors::{Error, Result},
+    gas,
+    utils::{is_native, is_native_mid_path, is_native_path, map_native},
+    Amount, Deadline, PriceLimit, Recipient, Slippage, SwapOptions,
+};
 use ethers_contract::builders::ContractCall;
-use ethers_core::types::{Address, U256};
+use ethers_core::{
+    abi::AbiEncode,
+    types::{Address, Bytes, U256},
+};
 use ethers_providers::Middleware;
 use std::sync::Arc;
 
 contract_struct! {
     /// A Uniswap V3 swap router.
+    ///
+    /// Note: the parameter structs are those of the original `SwapRouter`, which take a
+    /// `deadline`, rather than `SwapRouter02`'s.
     pub struct Router<M> {
         /// The router contract.
         contract: ISwapRouter<M>,
@@ -21,27 +36,666 @@ impl<M: Middleware> Router<M> {
         Self { contract }
     }
 
+    /// Returns the contract call for the router's `exactInput` method.
     pub fn exact_input(&self, params: ExactInputParams) -> ContractCall<M, U256> {
-        let swap_router = self.contract();
-
-        swap_router.exact_input(params)
+        self.contract.exact_input(params)
     }
 
+    /// Returns the contract call for the router's `exactInputSingle` method.
     pub fn exact_input_single(&self, params: ExactInputSingleParams) -> ContractCall<M, U256> {
-        let swap_router = self.contract();
-
-        swap_router.exact_input_single(params)
+        self.contract.exact_input_single(params)
     }
 
+    /// Returns the contract call for the router's `exactOutput` method.
     pub fn exact_output(&self, params: ExactOutputParams) -> ContractCall<M, U256> {
-        let swap_router = self.contract();
-
-        swap_router.exact_output(params)
+        self.contract.exact_output(params)
     }
 
+    /// Returns the contract call for the router's `exactOutputSingle` method.
     pub fn exact_output_single(&self, params: ExactOutputSingleParams) -> ContractCall<M, U256> {
-        let swap_router = self.contract();
+        self.contract.exact_output_single(params)
+    }
+
+    /// Returns the contract call for the router's `multicall` method, which executes each of the
+    /// router's calls in `data` in the same transaction and returns their results.
+    pub fn multicall(&self, data: Vec<Bytes>) -> ContractCall<M, Vec<Bytes>> {
+        self.contract.multicall(data)
+    }
+
+    /// Returns the contract call for the router's `refundETH` method, which sends the router's
+    /// ETH balance to the caller.
+    pub fn refund_eth(&self) -> ContractCall<M, ()> {
+        self.contract.refund_eth()
+    }
+
+    /// Returns the contract call for the router's `unwrapWETH9` method, which unwraps the router's
+    /// WETH balance, of at least `amount_minimum`, and sends it to `recipient` as ETH.
+    pub fn unwrap_weth9(&self, amount_minimum: U256, recipient: Address) -> ContractCall<M, ()> {
+        self.contract.unwrap_weth9(amount_minimum, recipient)
+    }
+
+    /// Returns the contract call for the router's `sweepToken` method, which sends the router's
+    /// `token` balance, of at least `amount_minimum`, to `recipient`.
+    pub fn sweep_token(
+        &self,
+        token: Address,
+        amount_minimum: U256,
+        recipient: Address,
+    ) -> ContractCall<M, ()> {
+        self.contract.sweep_token(token, amount_minimum, recipient)
+    }
+
+    /// Generalized swap function for the router's `exact[Input|Output][Single]` methods, swapping
+    /// through the pools of `path`. Returns the contract call with the necessary parameters set
+    /// (value, calldata).
+    ///
+    /// The swap is quoted with `quoter`, and `slippage_tolerance` is applied to the quote to set
+    /// `amountOutMinimum` or `amountInMaximum`. A slippage of 100% skips quoting.
+    ///
+    /// The swap is always wrapped in the router's [`multicall`][Self::multicall], whose first
+    /// result is the ABI-encoded amount out or in. [`NATIVE_ADDRESS`][crate::NATIVE_ADDRESS] as
+    /// the first token of `path` swaps from ETH through `weth`, setting the call's `value` to the
+    /// amount in, or to `amountInMaximum` for exact output swaps, and appending a
+    /// [`refundETH`][Self::refund_eth] call for the unspent amount. As the last token,
+    /// the router receives WETH and an [`unwrapWETH9`][Self::unwrap_weth9] call sends it to
+    /// `recipient` as ETH. Returns [`Error::NativeMidPath`] if it is anywhere else in the path.
+    ///
+    /// Single-hop paths use the `*Single` methods, with
+    /// [`options.price_limit`][SwapOptions::price_limit] as the swap's price limit, zero
+    /// otherwise. When the limit is reached, the swap is partially filled and the quoted amounts
+    /// are those of the partial fill. Multi-hop paths are encoded with [`Path::encode`], in reverse
+    /// for exact output swaps. The other options are ignored.
+    ///
+    /// Returns [`Error::InvalidSqrtRatio`] if the price limit is not strictly within
+    /// `MIN_SQRT_RATIO..MAX_SQRT_RATIO`. Its side of the pool's price is not checked, since the
+    /// pool is not known, but a limit on the wrong side makes the quote revert. See
+    /// [`Protocol::swap_path`][super::Protocol::swap_path], which checks it.
+    ///
+    /// [`Amount::PercentOfBalance`] is resolved against the balance of the client's default sender.
+    /// See [`Amount::resolve`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            skip_all,
+            fields(router = ?self.address(), path = ?path.tokens(), fees = ?path.fees())
+        )
+    )]
+    pub async fn swap(
+        &self,
+        quoter: &Quoter<M>,
+        amount: Amount,
+        slippage_tolerance: impl Into<Slippage>,
+        path: &Path,
+        recipient: impl Into<Recipient>,
+        deadline: impl Into<Deadline>,
+        weth: Address,
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let slippage = slippage_tolerance.into().validate()?;
+        let deadline = deadline.into().resolve(self.client().as_ref()).await?;
+        let recipient = recipient.into().resolve(self.client().as_ref()).await?;
+
+        let mut path = swap_path(amount, path, weth)?;
+        let amount = amount.resolve(self.client(), path.tokens()).await?;
+        debug!(?amount, "resolved the amount");
+        let (native, sqrt_price_limit_x96) =
+            map_swap_path(amount, slippage, &mut path, weth, options.price_limit)?;
+
+        let quote = if slippage.is_max() {
+            None
+        } else {
+            let single = path.hops() == 1;
+            let (token_in, token_out, fee) = (path.token_in(), path.token_out(), path.fees()[0]);
+            let quote = match amount {
+                Amount::ExactIn(amount_in) if single => {
+                    quoter
+                        .quote_exact_input_single(
+                            token_in,
+                            token_out,
+                            fee,
+                            amount_in,
+                            sqrt_price_limit_x96,
+                        )
+                        .await?
+                }
+                Amount::ExactIn(amount_in) => quoter.quote_exact_input(&path, amount_in).await?,
+                Amount::ExactOut(amount_out) if single => {
+                    quoter
+                        .quote_exact_output_single(
+                            token_in,
+                            token_out,
+                            fee,
+                            amount_out,
+                            sqrt_price_limit_x96,
+                        )
+                        .await?
+                }
+                Amount::ExactOut(amount_out) => {
+                    quoter.quote_exact_output(&path, amount_out).await?
+                }
+                Amount::ExactInNative(_) | Amount::PercentOfBalance { .. } => {
+                    return Err(Error::UnresolvedAmount)
+                }
+            };
+            Some(quote.amount)
+        };
+
+        let (data, value) = swap_data(
+            amount,
+            quote,
+            slippage,
+            &path,
+            self.address(),
+            recipient,
+            deadline,
+            sqrt_price_limit_x96,
+            native,
+        )?;
+        debug!(
+            selector = %ethers_core::utils::hex::encode(&data[0][..4]),
+            calls = data.len(),
+            "built the swap call"
+        );
+        let mut call = self.multicall(data);
+        if native.0 {
+            call = call.value(value);
+        }
+        if let Some(margin_bps) = options.gas_margin_bps {
+            call = gas::estimate(self.client().as_ref(), &call, margin_bps).await?.apply(call);
+        }
+        options.tx.apply(call)
+    }
+}
+
+/// Checks the path of [`Router::swap`] before resolving `amount`, with the native token first for
+/// [`Amount::ExactInNative`].
+fn swap_path(amount: Amount, path: &Path, weth: Address) -> Result<Path> {
+    let mut path = path.clone();
+    if let Amount::ExactInNative(_) = amount {
+        let token_in = path.token_in();
+        if !is_native(&token_in) && token_in != weth {
+            return Err(Error::InvalidPath);
+        }
+        path.tokens_mut()[0] = NATIVE_ADDRESS;
+    }
+    if is_native_mid_path(path.tokens()) {
+        return Err(Error::NativeMidPath);
+    }
+    Ok(path)
+}
+
+/// Replaces the native token in `path` with `weth` and checks the swap's parameters, returning
+/// whether it is swapped from and to, and the swap's price limit.
+fn map_swap_path(
+    amount: Amount,
+    slippage: Slippage,
+    path: &mut Path,
+    weth: Address,
+    price_limit: Option<PriceLimit>,
+) -> Result<((bool, bool), U256)> {
+    let (from_native, to_native) = is_native_path(path.tokens());
+    map_native(path.tokens_mut(), weth);
+    if path.pools().any(|(token_in, token_out, _)| token_in == token_out) {
+        return Err(Error::SwapToSelf);
+    }
+    if from_native && !amount.is_exact_in() && slippage.is_max() {
+        return Err(Error::InvalidSlippage);
+    }
+
+    let sqrt_price_limit_x96 = match price_limit {
+        Some(price_limit) if path.hops() == 1 => {
+            let limit = price_limit.sqrt_price_x96()?;
+            if limit <= MIN_SQRT_RATIO || limit >= MAX_SQRT_RATIO {
+                return Err(Error::InvalidSqrtRatio(limit));
+            }
+            limit
+        }
+        _ => U256::zero(),
+    };
+    Ok(((from_native, to_native), sqrt_price_limit_x96))
+}
+
+/// Returns the calldata of the calls of [`Router::swap`] for the router's `multicall`, with the
+/// limit amount from `quote`, and the call's value.
+fn swap_data(
+    amount: Amount,
+    quote: Option<U256>,
+    slippage: Slippage,
+    path: &Path,
+    router: Address,
+    recipient: Address,
+    deadline: U256,
+    sqrt_price_limit_x96: U256,
+    (from_native, to_native): (bool, bool),
+) -> Result<(Vec<Bytes>, U256)> {
+    let single = path.hops() == 1;
+    let (token_in, token_out, fee) = (path.token_in(), path.token_out(), path.fees()[0]);
+    // the router keeps the WETH until it is unwrapped
+    let swap_recipient = if to_native { router } else { recipient };
+
+    let (swap, value, amount_out_minimum) = match amount {
+        Amount::ExactIn(amount_in) => {
+            let amount_out_minimum = match quote {
+                Some(quote) => slippage.minimum_amount_out(quote),
+                None => U256::zero(),
+            };
+            debug!(%amount_in, %amount_out_minimum, "computed the minimum output");
+            let swap = if single {
+                ExactInputSingleCall {
+                    params: ExactInputSingleParams {
+                        token_in,
+                        token_out,
+                        fee: fee.into(),
+                        recipient: swap_recipient,
+                        deadline,
+                        amount_in,
+                        amount_out_minimum,
+                        sqrt_price_limit_x96,
+                    },
+                }
+                .encode()
+            } else {
+                ExactInputCall {
+                    params: ExactInputParams {
+                        path: path.encode(),
+                        recipient: swap_recipient,
+                        deadline,
+                        amount_in,
+                        amount_out_minimum,
+                    },
+                }
+                .encode()
+            };
+            (swap, amount_in, amount_out_minimum)
+        }
+        Amount::ExactOut(amount_out) => {
+            let amount_in_maximum = match quote {
+                Some(quote) => slippage.maximum_amount_in(quote),
+                None => U256::MAX,
+            };
+            debug!(%amount_out, %amount_in_maximum, "computed the maximum input");
+            let swap = if single {
+                ExactOutputSingleCall {
+                    params: ExactOutputSingleParams {
+                        token_in,
+                        token_out,
+                        fee: fee.into(),
+                        recipient: swap_recipient,
+                        deadline,
+                        amount_out,
+                        amount_in_maximum,
+                        sqrt_price_limit_x96,
+                    },
+                }
+                .encode()
+            } else {
+                ExactOutputCall {
+                    params: ExactOutputParams {
+                        path: path.reverse().encode(),
+                        recipient: swap_recipient,
+                        deadline,
+                        amount_out,
+                        amount_in_maximum,
+                    },
+                }
+                .encode()
+            };
+            (swap, amount_in_maximum, amount_out)
+        }
+        Amount::ExactInNative(_) | Amount::PercentOfBalance { .. } => {
+            return Err(Error::UnresolvedAmount)
+        }
+    };
+
+    let mut data = vec![swap.into()];
+    if from_native {
+        data.push(RefundETHCall.encode().into());
+    }
+    if to_native {
+        data.push(
+            UnwrapWETH9Call { amount_minimum: amount_out_minimum, recipient }.encode().into(),
+        );
+    }
+    Ok((data, value))
+}
+
+/// Returns the calldata of a router call, to be passed to [`Router::multicall`].
+pub(super) fn calldata<M, D>(call: ContractCall<M, D>) -> Bytes {
+    call.tx.data().cloned().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::abi::{self, AbiDecode, Token};
+    use ethers_providers::{MockProvider, Provider};
+
+    type M = Provider<MockProvider>;
+
+    const TOKEN_A: Address = Address::repeat_byte(0x0a);
+    const TOKEN_B: Address = Address::repeat_byte(0x0b);
+    const WETH: Address = Address::repeat_byte(0xaa);
+    const RECIPIENT: Address = Address::repeat_byte(0xbb);
+
+    fn setup() -> (Router<M>, Quoter<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let router = Router::new(client.clone(), Address::repeat_byte(0x22));
+        let quoter = Quoter::new(client, Address::repeat_byte(0x33));
+        (router, quoter, mock)
+    }
+
+    fn push_quote(mock: &MockProvider, amount: U256) {
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(amount)]))).unwrap();
+    }
+
+    async fn swap(
+        (router, quoter): (&Router<M>, &Quoter<M>),
+        amount: Amount,
+        tokens: &[Address],
+        options: SwapOptions,
+    ) -> Result<ContractCall<M, Vec<Bytes>>> {
+        let path = Path::new(tokens, &vec![500; tokens.len() - 1]).unwrap();
+        router.swap(quoter, amount, 1.0, &path, RECIPIENT, U256::MAX, WETH, options).await
+    }
+
+    #[test]
+    fn test_exact_input_single_layout() {
+        let (router, _, _) = setup();
+        // 1000 USDC -> WETH 0.05%, from a mainnet transaction to the SwapRouter
+        let params = ExactInputSingleParams {
+            token_in: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap(),
+            token_out: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap(),
+            fee: 500,
+            recipient: "0x0000000000000000000000000000000000000001".parse().unwrap(),
+            deadline: 1_672_531_200.into(),
+            amount_in: 1_000_000_000.into(),
+            amount_out_minimum: 800_000_000_000_000_000u64.into(),
+            sqrt_price_limit_x96: U256::zero(),
+        };
+        let expected: Bytes = concat!(
+            "0x414bf389",
+            "000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "00000000000000000000000000000000000000000000000000000000000001f4",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000063b0cd00",
+            "000000000000000000000000000000000000000000000000000000003b9aca00",
+            "0000000000000000000000000000000000000000000000000b1a2bc2ec500000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(calldata(router.exact_input_single(params)), expected);
+    }
+
+    #[tokio::test]
+    async fn test_swap_exact_in() {
+        let (router, quoter, mock) = setup();
+        let amount_in = U256::exp10(18);
+        push_quote(&mock, 1_000.into());
+
+        let call = swap((&router, &quoter), Amount::ExactIn(amount_in), &[TOKEN_A, WETH], {
+            SwapOptions::new().sqrt_price_limit_x96(MIN_SQRT_RATIO + 1)
+        })
+        .await
+        .unwrap();
+        assert_eq!(call.tx.value(), None);
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 1);
+        let params = ExactInputSingleCall::decode(&data[0]).unwrap().params;
+        assert_eq!(params.token_in, TOKEN_A);
+        assert_eq!(params.token_out, WETH);
+        assert_eq!(params.fee, 500);
+        assert_eq!(params.recipient, RECIPIENT);
+        assert_eq!(params.amount_in, amount_in);
+        // 1% slippage
+        assert_eq!(params.amount_out_minimum, 990.into());
+        assert_eq!(params.sqrt_price_limit_x96, MIN_SQRT_RATIO + 1);
+    }
+
+    #[tokio::test]
+    async fn test_swap_native() {
+        let (router, quoter, mock) = setup();
+        let amount = U256::exp10(18);
+
+        // ETH in: value set and refundETH appended
+        push_quote(&mock, 1_000.into());
+        let call = swap((&router, &quoter), Amount::ExactIn(amount), &[NATIVE_ADDRESS, TOKEN_A], {
+            SwapOptions::new()
+        })
+        .await
+        .unwrap();
+        assert_eq!(call.tx.value(), Some(&amount));
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        let params = ExactInputSingleCall::decode(&data[0]).unwrap().params;
+        assert_eq!(params.token_in, WETH);
+        assert_eq!(params.sqrt_price_limit_x96, U256::zero());
+        RefundETHCall::decode(&data[1]).unwrap();
+
+        // ETH out: the router receives the WETH and unwraps it to the recipient
+        push_quote(&mock, 1_000.into());
+        let call =
+            swap((&router, &quoter), Amount::ExactOut(amount), &[TOKEN_A, NATIVE_ADDRESS], {
+                SwapOptions::new()
+            })
+            .await
+            .unwrap();
+        assert_eq!(call.tx.value(), None);
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        let params = ExactOutputSingleCall::decode(&data[0]).unwrap().params;
+        assert_eq!(params.recipient, router.address());
+        assert_eq!(params.amount_out, amount);
+        assert_eq!(params.amount_in_maximum, 1_011.into());
+        let unwrap = UnwrapWETH9Call::decode(&data[1]).unwrap();
+        assert_eq!((unwrap.amount_minimum, unwrap.recipient), (amount, RECIPIENT));
+    }
+
+    #[tokio::test]
+    async fn test_swap_exact_out_native_in() {
+        let (router, quoter, mock) = setup();
+        let amount = U256::exp10(18);
+
+        // the value is the maximum amount in, and the unspent ETH is refunded
+        push_quote(&mock, 1_000.into());
+        let call =
+            swap((&router, &quoter), Amount::ExactOut(amount), &[NATIVE_ADDRESS, TOKEN_A], {
+                SwapOptions::new()
+            })
+            .await
+            .unwrap();
+        assert_eq!(call.tx.value(), Some(&1_011.into()));
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        let params = ExactOutputSingleCall::decode(&data[0]).unwrap().params;
+        assert_eq!((params.token_in, params.token_out), (WETH, TOKEN_A));
+        assert_eq!(params.recipient, RECIPIENT);
+        assert_eq!((params.amount_out, params.amount_in_maximum), (amount, 1_011.into()));
+        RefundETHCall::decode(&data[1]).unwrap();
+
+        // multi-hop paths are encoded output token first
+        push_quote(&mock, 1_000.into());
+        let path = [NATIVE_ADDRESS, TOKEN_B, TOKEN_A];
+        let call = swap((&router, &quoter), Amount::ExactOut(amount), &path, SwapOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(call.tx.value(), Some(&1_011.into()));
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        assert_eq!(data.len(), 2);
+        let params = ExactOutputCall::decode(&data[0]).unwrap().params;
+        assert_eq!(Path::decode(&params.path).unwrap().tokens(), [TOKEN_A, TOKEN_B, WETH]);
+        assert_eq!((params.amount_out, params.amount_in_maximum), (amount, 1_011.into()));
+        RefundETHCall::decode(&data[1]).unwrap();
+
+        // the value can't be unbounded
+        let path = Path::new(&[NATIVE_ADDRESS, TOKEN_A], &[500]).unwrap();
+        let res = router
+            .swap(&quoter, Amount::ExactOut(amount), 100.0, &path, RECIPIENT, U256::MAX, WETH, {
+                SwapOptions::new()
+            })
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::InvalidSlippage));
+    }
+
+    #[tokio::test]
+    async fn test_swap_to_self() {
+        let (router, quoter, _mock) = setup();
+        let res = swap((&router, &quoter), Amount::ExactIn(1.into()), &[NATIVE_ADDRESS, WETH], {
+            SwapOptions::new()
+        })
+        .await;
+        assert!(matches!(res.unwrap_err(), Error::SwapToSelf));
+    }
+
+    #[tokio::test]
+    async fn test_swap_multi_hop() {
+        let (router, quoter, mock) = setup();
+        let amount = U256::exp10(18);
+        let path = [TOKEN_A, TOKEN_B, WETH];
+
+        push_quote(&mock, 1_000.into());
+        let call = swap((&router, &quoter), Amount::ExactIn(amount), &path, SwapOptions::new())
+            .await
+            .unwrap();
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        let params = ExactInputCall::decode(&data[0]).unwrap().params;
+        assert_eq!(Path::decode(&params.path).unwrap().tokens(), path);
+        assert_eq!(params.amount_out_minimum, 990.into());
+
+        // exact output paths are encoded in reverse
+        push_quote(&mock, 1_000.into());
+        let call = swap((&router, &quoter), Amount::ExactOut(amount), &path, SwapOptions::new())
+            .await
+            .unwrap();
+        let data = MulticallCall::decode(call.calldata().unwrap()).unwrap().data;
+        let params = ExactOutputCall::decode(&data[0]).unwrap().params;
+        assert_eq!(Path::decode(&params.path).unwrap().tokens(), [WETH, TOKEN_B, TOKEN_A]);
+        assert_eq!(params.amount_in_maximum, 1_011.into());
+
+        let path = [TOKEN_A, NATIVE_ADDRESS, TOKEN_B];
+        let res =
+            swap((&router, &quoter), Amount::ExactIn(amount), &path, SwapOptions::new()).await;
+        assert!(matches!(res.unwrap_err(), Error::NativeMidPath));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_swap_exact_out_native_in_fork() {
+        use crate::{contracts::bindings::ierc20::IERC20, testing::Fork, v3::FeeAmount};
+        use ethers_providers::Middleware;
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+
+        let router = Router::new(
+            client.clone(),
+            "0xE592427A0AEce92De3Edee1F18E0157C05861564".parse().unwrap(),
+        );
+        let quoter = Quoter::new_v2(
+            client.clone(),
+            "0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse().unwrap(),
+        );
+        let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let usdc_contract = IERC20::new(usdc, client.clone());
+
+        // ETH for exactly 1000 USDC through USDC/WETH 0.05%
+        let amount_out = U256::from(1_000_000_000u64);
+        let quote =
+            quoter.quote_exact_output_single(weth, usdc, FeeAmount::Low, amount_out, U256::zero());
+        let amount_in = quote.await.unwrap().amount;
+        let path = Path::new(&[NATIVE_ADDRESS, usdc], &[500]).unwrap();
+        let call = router
+            .swap(&quoter, Amount::ExactOut(amount_out), 1.0, &path, me, U256::MAX, weth, {
+                SwapOptions::new()
+            })
+            .await
+            .unwrap();
+        let value = *call.tx.value().unwrap();
+        assert_eq!(value, Slippage::bps(100).unwrap().maximum_amount_in(amount_in));
+
+        let balance = client.get_balance(me, None).await.unwrap();
+        let usdc_balance = usdc_contract.balance_of(me).call().await.unwrap();
+        let receipt = call.send().await.unwrap().await.unwrap().unwrap();
+        let gas = receipt.gas_used.unwrap() * receipt.effective_gas_price.unwrap();
+        let spent = balance - client.get_balance(me, None).await.unwrap() - gas;
+        // the difference between the value and the amount in is refunded
+        assert_eq!(spent, amount_in);
+        assert!(value > spent);
+        let received = usdc_contract.balance_of(me).call().await.unwrap() - usdc_balance;
+        assert_eq!(received, amount_out);
+        assert!(client.get_balance(router.address(), None).await.unwrap().is_zero());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_swap_price_limit_partial_fill_fork() {
+        use crate::{
+            contracts::bindings::ierc20::IERC20,
+            testing::Fork,
+            v3::{
+                math::swap::{simulate, TickData},
+                FeeAmount, Pool,
+            },
+            ProtocolType,
+        };
+        use ethers_core::{abi::AbiDecode, types::I256};
+        use ethers_providers::Middleware;
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+        fork.fund(me, U256::exp10(22)).await.unwrap();
+
+        let router = Router::new(
+            client.clone(),
+            "0xE592427A0AEce92De3Edee1F18E0157C05861564".parse().unwrap(),
+        );
+        let quoter = Quoter::new_v2(
+            client.clone(),
+            "0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse().unwrap(),
+        );
+        let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let usdc_contract = IERC20::new(usdc, client.clone());
+        // USDC/WETH 0.05%, whose price of USDC in WETH goes up when swapping WETH for USDC
+        let address = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
+        let pool = Pool::new(client.clone(), address, ProtocolType::UniswapV3);
+        let state = pool.state().await.unwrap();
+        let word = TickData::word_position(state.tick, pool.tick_spacing().unwrap());
+        let ticks = pool.load_ticks(word - 1..=word + 1).await.unwrap();
+
+        // 1000 ETH, far more than the liquidity up to 0.02% above the price
+        let amount_in = U256::exp10(21);
+        let limit = state.sqrt_price_x96 + state.sqrt_price_x96 / 10_000;
+        let expected =
+            simulate(&state, FeeAmount::Low, &ticks, I256::from_raw(amount_in), false, Some(limit))
+                .unwrap();
+        assert!(expected.amount_in < amount_in);
+        assert_eq!(expected.sqrt_price_x96_after, limit);
+
+        let path = Path::new(&[NATIVE_ADDRESS, usdc], &[500]).unwrap();
+        let options = SwapOptions::new().sqrt_price_limit_x96(limit);
+        let call = router
+            .swap(&quoter, Amount::ExactIn(amount_in), 1.0, &path, me, U256::MAX, weth, options)
+            .await
+            .unwrap();
+        assert_eq!(call.tx.value(), Some(&amount_in));
+        // the returned amount out is that of the partial fill
+        let amount_out = U256::decode(&call.call().await.unwrap()[0]).unwrap();
+        assert_eq!(amount_out, expected.amount_out);
 
-        swap_router.exact_output_single(params)
+        let balance = client.get_balance(me, None).await.unwrap();
+        let usdc_balance = usdc_contract.balance_of(me).call().await.unwrap();
+        let receipt = call.send().await.unwrap().await.unwrap().unwrap();
+        let gas = receipt.gas_used.unwrap() * receipt.effective_gas_price.unwrap();
+        // the unspent ETH is refunded
+        let spent = balance - client.get_balance(me, None).await.unwrap() - gas;
+        assert_eq!(spent, expected.amount_in);
+        let received = usdc_contract.balance_of(me).call().await.unwrap() - usdc_balance;
+        assert_eq!(received, expected.amount_out);
+        assert_eq!(pool.slot_0().call().await.unwrap().0, limit);
     }
 }
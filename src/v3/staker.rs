@@ -0,0 +1,320 @@
+use crate::{
+    contracts::bindings::{
+        i_nonfungible_position_manager::INonfungiblePositionManager,
+        i_uniswap_v3_staker::IUniswapV3Staker,
+    },
+    errors::{Error, Result},
+};
+use ethers_contract::builders::ContractCall;
+use ethers_core::{
+    abi::AbiEncode,
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use ethers_providers::Middleware;
+use std::sync::Arc;
+
+pub use crate::contracts::bindings::i_uniswap_v3_staker::IncentiveKey;
+
+impl IncentiveKey {
+    /// Returns the id of the incentive, which is the hash of the ABI-encoded key, like the staker's
+    /// `IncentiveId.compute`.
+    pub fn id(&self) -> H256 {
+        keccak256(self.clone().encode()).into()
+    }
+}
+
+contract_struct! {
+    /// A Uniswap V3 `UniswapV3Staker`, which distributes the rewards of incentives to the
+    /// positions staked in their pools.
+    pub struct Staker<M> {
+        /// The staker contract.
+        contract: IUniswapV3Staker<M>,
+
+        /// The position manager whose positions are staked.
+        position_manager: INonfungiblePositionManager<M>,
+    }
+}
+
+impl<M: Middleware> Staker<M> {
+    /// Creates a new instance using the provided staker and position manager addresses.
+    pub fn new(client: Arc<M>, address: Address, position_manager: Address) -> Self {
+        let position_manager = INonfungiblePositionManager::new(position_manager, client.clone());
+        let contract = IUniswapV3Staker::new(address, client);
+        Self { contract, position_manager }
+    }
+
+    /// Creates a new instance by searching for the canonical Uniswap V3 addresses in the
+    /// [addressbook].
+    ///
+    /// [addressbook]: crate::contracts::addresses
+    #[cfg(feature = "addresses")]
+    pub fn new_with_chain(client: Arc<M>, chain: ethers_core::types::Chain) -> Option<Self> {
+        use crate::contracts::addresses::try_address;
+
+        let address = try_address("UniswapV3Staker", chain)?;
+        let position_manager = try_address("UniswapV3NonfungiblePositionManager", chain)?;
+        Some(Self::new(client, address, position_manager))
+    }
+
+    /// Returns a reference to the position manager contract.
+    #[inline(always)]
+    pub fn position_manager(&self) -> &INonfungiblePositionManager<M> {
+        &self.position_manager
+    }
+
+    /// Returns the contract call for the staker's `createIncentive` method, which creates the
+    /// incentive of `key` with `reward` reward tokens, transferred from the sender.
+    pub fn create_incentive(&self, key: &IncentiveKey, reward: U256) -> ContractCall<M, ()> {
+        self.contract.create_incentive(key.clone(), reward)
+    }
+
+    /// Deposits the position `token_id` of the client's default sender into the staker and stakes
+    /// it in the incentive of `key`. Returns the position manager's contract call with the
+    /// necessary parameters set (calldata).
+    ///
+    /// The position is transferred with `safeTransferFrom`, whose data is the ABI-encoded key,
+    /// which the staker decodes in `onERC721Received` to stake it.
+    ///
+    /// Returns [`Error::MissingSender`] if the client has no default sender.
+    pub fn stake_position(
+        &self,
+        token_id: U256,
+        key: &IncentiveKey,
+    ) -> Result<ContractCall<M, ()>> {
+        let from = self.client().default_sender().ok_or(Error::MissingSender)?;
+        let data = Bytes::from(key.clone().encode());
+        Ok(self.position_manager.safe_transfer_from_with_from_and_to_and_data(
+            from,
+            self.address(),
+            token_id,
+            data,
+        ))
+    }
+
+    /// Returns the contract call for the staker's `stakeToken` method, which stakes the deposited
+    /// position `token_id` in the incentive of `key`.
+    pub fn stake_token(&self, token_id: U256, key: &IncentiveKey) -> ContractCall<M, ()> {
+        self.contract.stake_token(key.clone(), token_id)
+    }
+
+    /// Returns the contract call for the staker's `unstakeToken` method, which unstakes the
+    /// position `token_id` from the incentive of `key` and accrues its rewards to its owner.
+    pub fn unstake_token(&self, token_id: U256, key: &IncentiveKey) -> ContractCall<M, ()> {
+        self.contract.unstake_token(key.clone(), token_id)
+    }
+
+    /// Returns the contract call for the staker's `claimReward` method, which transfers up to
+    /// `amount_requested` of the sender's accrued `reward_token` rewards, or all of them if zero,
+    /// to `to`.
+    pub fn claim_reward(
+        &self,
+        reward_token: Address,
+        to: Address,
+        amount_requested: U256,
+    ) -> ContractCall<M, U256> {
+        self.contract.claim_reward(reward_token, to, amount_requested)
+    }
+
+    /// Returns the contract call for the staker's `withdrawToken` method, which transfers the
+    /// unstaked position `token_id` back to `to`.
+    pub fn withdraw_token(&self, token_id: U256, to: Address) -> ContractCall<M, ()> {
+        self.contract.withdraw_token(token_id, to, Bytes::default())
+    }
+
+    /// Returns the contract call for the staker's `multicall` method, which executes each of the
+    /// staker's calls in `data` in the same transaction and returns their results.
+    pub fn multicall(&self, data: Vec<Bytes>) -> ContractCall<M, Vec<Bytes>> {
+        self.contract.multicall(data)
+    }
+
+    /// Returns the rewards that the position `token_id` has earned in the incentive of `key` since
+    /// it was staked, and the seconds it has spent in range, as a Q128.128, with the staker's
+    /// `getRewardInfo`.
+    ///
+    /// The call reverts if the position is not staked in the incentive.
+    pub async fn pending_rewards(
+        &self,
+        token_id: U256,
+        key: &IncentiveKey,
+    ) -> Result<(U256, U256)> {
+        Ok(self.contract.get_reward_info(key.clone(), token_id).call().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::{
+        i_nonfungible_position_manager::SafeTransferFromWithFromAndToAndDataCall,
+        i_uniswap_v3_staker::GetRewardInfoCall,
+    };
+    use ethers_core::abi::{self, AbiDecode, Token};
+    use ethers_providers::{MockProvider, Provider};
+
+    type M = Provider<MockProvider>;
+
+    const OWNER: Address = Address::repeat_byte(0xbb);
+
+    fn setup() -> (Staker<M>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider.with_sender(OWNER));
+        let staker = Staker::new(client, Address::repeat_byte(0x55), Address::repeat_byte(0x66));
+        (staker, mock)
+    }
+
+    fn key() -> IncentiveKey {
+        IncentiveKey {
+            reward_token: Address::repeat_byte(1),
+            pool: Address::repeat_byte(2),
+            start_time: 1_000.into(),
+            end_time: 2_000.into(),
+            refundee: Address::repeat_byte(3),
+        }
+    }
+
+    #[test]
+    fn test_incentive_id() {
+        let key = key();
+        // `keccak256(abi.encode(key))`: the key only has static fields, so it is encoded in place
+        let encoded = abi::encode(&[
+            Token::Address(key.reward_token),
+            Token::Address(key.pool),
+            Token::Uint(key.start_time),
+            Token::Uint(key.end_time),
+            Token::Address(key.refundee),
+        ]);
+        assert_eq!(encoded.len(), 5 * 32);
+        assert_eq!(key.id(), H256::from(keccak256(encoded)));
+        assert_ne!(IncentiveKey { end_time: 2_001.into(), ..key.clone() }.id(), key.id());
+    }
+
+    #[test]
+    fn test_stake_position() {
+        let (staker, _) = setup();
+        let call = staker.stake_position(7.into(), &key()).unwrap();
+        assert_eq!(call.tx.to(), Some(&Address::repeat_byte(0x66).into()));
+        let decoded =
+            SafeTransferFromWithFromAndToAndDataCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!((decoded.from, decoded.to), (OWNER, staker.address()));
+        assert_eq!(decoded.token_id, 7.into());
+        assert_eq!(IncentiveKey::decode(decoded.data).unwrap(), key());
+
+        let (provider, _) = Provider::mocked();
+        let staker = Staker::new(Arc::new(provider), staker.address(), Address::zero());
+        assert!(matches!(staker.stake_position(7.into(), &key()), Err(Error::MissingSender)));
+    }
+
+    #[tokio::test]
+    async fn test_pending_rewards() {
+        let (staker, mock) = setup();
+        let seconds = U256::from(60) << 128;
+        let response = abi::encode(&[Token::Uint(100.into()), Token::Uint(seconds)]);
+        mock.push::<Bytes, _>(Bytes::from(response)).unwrap();
+        assert_eq!(staker.pending_rewards(7.into(), &key()).await.unwrap(), (100.into(), seconds));
+
+        let call = staker.contract().get_reward_info(key(), 7.into());
+        let decoded = GetRewardInfoCall::decode(call.calldata().unwrap()).unwrap();
+        assert_eq!((decoded.key, decoded.token_id), (key(), 7.into()));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a mainnet fork at UNISWAP_RS_FORK_URL"]
+    async fn test_staker_fork() {
+        use crate::{
+            contracts::bindings::{i_nonfungible_position_manager::MintReturn, iweth::IWETH},
+            testing::Fork,
+            v3::{router::calldata, FeeAmount, MintParams, PositionManager},
+            NATIVE_ADDRESS,
+        };
+
+        let fork = Fork::from_env().await.unwrap();
+        let (client, me) = (fork.client(), fork.address());
+
+        let staker = Staker::new(
+            client.clone(),
+            "0xe34139463bA50bD61336E0c446Bd8C0867c6fE65".parse().unwrap(),
+            "0xC36442b4a4522E871399CD717aBDD847Ab11FE88".parse().unwrap(),
+        );
+
+        // the ids of the incentives created on mainnet are the keys of the staker's incentives
+        let created = staker.contract().incentive_created_filter().from_block(0u64);
+        let created = created.query().await.unwrap();
+        assert!(!created.is_empty());
+        let mut found = false;
+        for event in created {
+            let key = IncentiveKey {
+                reward_token: event.reward_token,
+                pool: event.pool,
+                start_time: event.start_time,
+                end_time: event.end_time,
+                refundee: event.refundee,
+            };
+            let (unclaimed, seconds, stakes) =
+                staker.contract().incentives(key.id().into()).call().await.unwrap();
+            found |= !(unclaimed.is_zero() && seconds.is_zero() && stakes == 0);
+        }
+        assert!(found);
+
+        // mint a WETH position in the USDC/WETH 0.05% pool, below the price
+        let manager = PositionManager::new(
+            client.clone(),
+            staker.position_manager().address(),
+            "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap(),
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap(),
+        );
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let pool = manager.factory().pool_for(usdc, manager.weth(), FeeAmount::Low).unwrap();
+        let tick = pool.state().await.unwrap().tick;
+        let amount = U256::exp10(18);
+        let params = MintParams::new(
+            NATIVE_ADDRESS,
+            usdc,
+            FeeAmount::Low,
+            tick - 2000,
+            tick - 1000,
+            amount,
+            U256::zero(),
+        )
+        .recipient(me);
+        let call = manager.mint(params).await.unwrap();
+        let token_id = MintReturn::decode(&call.call().await.unwrap()[0]).unwrap().token_id;
+        call.send().await.unwrap().await.unwrap();
+
+        // create a WETH incentive starting soon, then wait for it to start
+        let weth = IWETH::new(manager.weth(), client.clone());
+        weth.deposit().value(amount).send().await.unwrap().await.unwrap();
+        weth.approve(staker.address(), amount).send().await.unwrap().await.unwrap();
+        let block = client.get_block(ethers_core::types::BlockNumber::Latest).await.unwrap();
+        let start_time = block.unwrap().timestamp + 100;
+        let key = IncentiveKey {
+            reward_token: manager.weth(),
+            pool: pool.address(),
+            start_time,
+            end_time: start_time + 3600,
+            refundee: me,
+        };
+        staker.create_incentive(&key, amount).send().await.unwrap().await.unwrap();
+        client.provider().request::<_, U256>("evm_increaseTime", [200]).await.unwrap();
+        client.provider().request::<_, String>("evm_mine", ()).await.unwrap();
+
+        let call = staker.stake_position(token_id, &key).unwrap();
+        call.send().await.unwrap().await.unwrap();
+        let (owner, stakes, ..) = staker.contract().deposits(token_id).call().await.unwrap();
+        assert_eq!((owner, stakes), (me, 1));
+        let (_, liquidity) =
+            staker.contract().stakes(token_id, key.id().into()).call().await.unwrap();
+        assert!(liquidity > 0);
+
+        // the position is out of range, so it has not earned anything
+        let (reward, _) = staker.pending_rewards(token_id, &key).await.unwrap();
+        assert!(reward.is_zero());
+
+        let data = vec![
+            calldata(staker.unstake_token(token_id, &key)),
+            calldata(staker.withdraw_token(token_id, me)),
+        ];
+        staker.multicall(data).send().await.unwrap().await.unwrap();
+        assert_eq!(manager.contract().owner_of(token_id).call().await.unwrap(), me);
+    }
+}
@@ -0,0 +1,46 @@
+//! Tests of the quoting path on `wasm32-unknown-unknown`, run with Node and
+//! `wasm-bindgen-test-runner` (from `wasm-bindgen-cli`, matching the locked `wasm-bindgen`):
+//!
+//! ```sh
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test --target wasm32-unknown-unknown --no-default-features --features v2,wasm \
+//!     --test wasm
+//! ```
+
+#![cfg(all(target_arch = "wasm32", feature = "v2", feature = "wasm"))]
+
+use ethers_core::{
+    abi::{self, Token},
+    types::{Address, Bytes, U256},
+};
+use ethers_providers::Provider;
+use std::{sync::Arc, time::Duration};
+use uniswap_rs::{
+    utils::{get_deadline, now},
+    v2::{Factory, Library},
+    ProtocolType,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_now() {
+    // after 2020-01-01
+    assert!(now() > Duration::from_secs(1_577_836_800));
+    assert!(get_deadline(60) > U256::from(now().as_secs()));
+}
+
+#[wasm_bindgen_test]
+async fn test_get_amounts_out() {
+    let (provider, mock) = Provider::mocked();
+    let factory =
+        Factory::new(Arc::new(provider), Address::repeat_byte(0x11), ProtocolType::UniswapV2);
+    let path = [Address::repeat_byte(0x0a), Address::repeat_byte(0x0b)];
+
+    let reserves = [U256::exp10(21), U256::exp10(21), U256::zero()];
+    mock.push::<Bytes, _>(Bytes::from(abi::encode(&reserves.map(Token::Uint)))).unwrap();
+
+    let amount_in = U256::exp10(18);
+    let amounts = Library::get_amounts_out(&factory, amount_in, &path).await.unwrap();
+    let expected = Library::get_amount_out(amount_in, reserves[0], reserves[1]).unwrap();
+    assert_eq!(amounts, vec![amount_in, expected]);
+}